@@ -31,9 +31,10 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use swissarmyhammer_entity::Entity;
 use swissarmyhammer_kanban::task_helpers::{
-    enrich_all_task_entities, enrich_task_entity, retain_filtered_tasks, EntitySlugRegistry,
+    enrich_all_task_entities_with_wip_limits, enrich_task_entity_with_wip_limits,
+    retain_filtered_tasks, EntitySlugRegistry,
 };
-use swissarmyhammer_kanban::virtual_tags::default_virtual_tag_registry;
+use swissarmyhammer_kanban::virtual_tags::{default_virtual_tag_registry, ColumnWipLimits};
 use tauri::menu::{ContextMenu, MenuBuilder};
 use tauri::webview::WebviewWindowBuilder;
 use tauri::{AppHandle, Emitter, Manager, State, Window};
@@ -261,8 +262,9 @@ async fn enrich_and_sort_tasks(
         .map(|c| c.id.to_string())
         .unwrap_or_else(|| "done".to_string());
 
+    let wip_limits = ColumnWipLimits::from_columns(&columns);
     let registry = default_virtual_tag_registry();
-    enrich_all_task_entities(entities, &terminal_id, registry);
+    enrich_all_task_entities_with_wip_limits(entities, &terminal_id, &wip_limits, registry);
 
     entities.sort_by(|a, b| {
         let col_a = a.get_str("position_column").unwrap_or("");
@@ -380,8 +382,15 @@ pub async fn get_entity(
             .last()
             .map(|c| c.id.to_string())
             .unwrap_or_else(|| "done".to_string());
+        let wip_limits = ColumnWipLimits::from_columns(&columns);
         let registry = default_virtual_tag_registry();
-        enrich_task_entity(&mut entity, &all_tasks, &terminal_id, registry);
+        enrich_task_entity_with_wip_limits(
+            &mut entity,
+            &all_tasks,
+            &terminal_id,
+            &wip_limits,
+            registry,
+        );
     }
 
     Ok(entity.to_json())
@@ -1776,6 +1785,7 @@ async fn perform_cross_board_drag_transfer(
         params.before_id.as_deref(),
         params.after_id.as_deref(),
         params.copy_mode,
+        false,
     )
     .await;
 
@@ -419,6 +419,12 @@ impl BoardHandle {
             }
         }
 
+        // Board hooks run for the life of the handle; a broken hooks.yaml
+        // is logged rather than blocking the board from opening.
+        if let Err(e) = ctx.start_hooks().await {
+            tracing::warn!(error = %e, "failed to start kanban hooks");
+        }
+
         let search_index = Arc::new(RwLock::new(load_search_index(&ctx).await));
 
         let mcp_server = if opts.start_mcp_server {
@@ -11,11 +11,11 @@
 //!    through `EntityContext::read` (which applies `ComputeEngine.derive_all`)
 //!    and, for task entities, running the kanban-layer cross-entity enrichment
 //!    (`enrich_task_entity`) so `virtual_tags`/`filter_tags`/`ready`/
-//!    `blocked_by`/`blocks` reflect the freshest state.
+//!    `blocked_by`/`blocks`/`children`/`progress` reflect the freshest state.
 //! 3. Fans out synthetic `EntityFieldChanged` events to dependent tasks when a
-//!    task's `position_column`, `depends_on`, or `completed` field changes —
-//!    their computed fields may have shifted even though their own files did
-//!    not.
+//!    task's `position_column`, `depends_on`, `parent`, or `completed` field
+//!    changes — their computed fields may have shifted even though their own
+//!    files did not.
 //! 4. Translates each resolved event to the matching Tauri payload shape and
 //!    tags it with a `board_path` so the frontend can route it to the correct
 //!    window.
@@ -26,8 +26,8 @@ use std::sync::Arc;
 use swissarmyhammer_entity::events::EntityEvent;
 use swissarmyhammer_entity::{Entity, EntityCache, EntityContext};
 use swissarmyhammer_entity_search::EntitySearchIndex;
-use swissarmyhammer_kanban::task_helpers::enrich_task_entity;
-use swissarmyhammer_kanban::virtual_tags::default_virtual_tag_registry;
+use swissarmyhammer_kanban::task_helpers::enrich_task_entity_with_wip_limits;
+use swissarmyhammer_kanban::virtual_tags::{default_virtual_tag_registry, ColumnWipLimits};
 use swissarmyhammer_kanban::KanbanContext;
 use swissarmyhammer_perspectives::PerspectiveEvent;
 use swissarmyhammer_views::ViewEvent;
@@ -40,8 +40,10 @@ pub use swissarmyhammer_entity::events::FieldChange;
 
 /// Task fields whose value changing can invalidate computed state on *other*
 /// tasks via the dependency graph. A column move flips downstream `BLOCKED`
-/// /`READY`; a `depends_on` edit reshuffles which tasks need re-enrichment.
-const TASK_FANOUT_TRIGGER_FIELDS: &[&str] = &["position_column", "depends_on"];
+/// /`READY`; a `depends_on` or `parent` edit reshuffles which tasks need
+/// re-enrichment (a parent's `children`, `progress` and readiness follow its
+/// subtasks).
+const TASK_FANOUT_TRIGGER_FIELDS: &[&str] = &["position_column", "depends_on", "parent"];
 
 /// Task computed fields whose value depends on cross-entity state (the full
 /// task list, the terminal column, the virtual-tag registry). These are the
@@ -53,6 +55,8 @@ const TASK_COMPUTED_FIELDS: &[&str] = &[
     "ready",
     "blocked_by",
     "blocks",
+    "children",
+    "progress",
 ];
 
 /// Events emitted to the frontend when entity state changes.
@@ -204,7 +208,7 @@ struct TaskComputedSnapshot {
 }
 
 impl TaskComputedSnapshot {
-    /// Extract the five computed task fields from an already-enriched entity.
+    /// Extract the computed task fields from an already-enriched entity.
     fn from_entity(entity: &Entity) -> Self {
         let mut fields = HashMap::with_capacity(TASK_COMPUTED_FIELDS.len());
         for name in TASK_COMPUTED_FIELDS {
@@ -298,11 +302,13 @@ async fn read_enriched(ectx: &EntityContext, entity_type: &str, id: &str) -> Opt
     Some(entity)
 }
 
-/// Read the current full task list plus the terminal column id (the column
-/// with the highest `order`, fallback `"done"`). Both are needed for every
-/// task-enrichment call, so pairing them keeps the two `list` calls in one
-/// place.
-async fn load_task_enrichment_inputs(ectx: &EntityContext) -> (Vec<Entity>, String) {
+/// Read the current full task list, the terminal column id (the column with
+/// the highest `order`, fallback `"done"`) and the per-column WIP limits. All
+/// are needed for every task-enrichment call, so bundling them keeps the two
+/// `list` calls in one place.
+async fn load_task_enrichment_inputs(
+    ectx: &EntityContext,
+) -> (Vec<Entity>, String, ColumnWipLimits) {
     let all_tasks = ectx.list("task").await.unwrap_or_default();
     let mut columns = ectx.list("column").await.unwrap_or_default();
     columns.sort_by_key(|c| c.get("order").and_then(|v| v.as_u64()).unwrap_or(0) as usize);
@@ -310,19 +316,22 @@ async fn load_task_enrichment_inputs(ectx: &EntityContext) -> (Vec<Entity>, Stri
         .last()
         .map(|c| c.id.to_string())
         .unwrap_or_else(|| "done".to_string());
-    (all_tasks, terminal_id)
+    let wip_limits = ColumnWipLimits::from_columns(&columns);
+    (all_tasks, terminal_id, wip_limits)
 }
 
-/// Run `enrich_task_entity` against a single task, sourcing the full task list
-/// and terminal column id from `ectx`. No-op if the task list or columns
-/// cannot be read — the entity is returned with whatever ComputeEngine
-/// produced, which is the same behaviour as before this card.
+/// Run `enrich_task_entity_with_wip_limits` against a single task, sourcing
+/// the full task list, terminal column id and column WIP limits from `ectx`.
+/// No-op if the task list or columns cannot be read — the entity is returned
+/// with whatever ComputeEngine produced, which is the same behaviour as
+/// before this card.
 async fn apply_task_enrichment(ectx: &EntityContext, entity: &mut Entity) {
-    let (all_tasks, terminal_id) = load_task_enrichment_inputs(ectx).await;
-    enrich_task_entity(
+    let (all_tasks, terminal_id, wip_limits) = load_task_enrichment_inputs(ectx).await;
+    enrich_task_entity_with_wip_limits(
         entity,
         &all_tasks,
         &terminal_id,
+        &wip_limits,
         default_virtual_tag_registry(),
     );
 }
@@ -348,7 +357,7 @@ async fn fan_out_task_dependents(
     trigger_id: &str,
     snapshots: &mut HashMap<String, TaskComputedSnapshot>,
 ) -> Vec<WatchEvent> {
-    let (mut all_tasks, terminal_id) = load_task_enrichment_inputs(ectx).await;
+    let (mut all_tasks, terminal_id, wip_limits) = load_task_enrichment_inputs(ectx).await;
     let registry = default_virtual_tag_registry();
     // Enrichment mutates each entity in place; clone the list up front so we
     // have both the stable `&[Entity]` input and a mutable owner.
@@ -356,7 +365,7 @@ async fn fan_out_task_dependents(
     let mut events = Vec::new();
     for entity in all_tasks.iter_mut() {
         let id = entity.id.to_string();
-        enrich_task_entity(entity, &reference, &terminal_id, registry);
+        enrich_task_entity_with_wip_limits(entity, &reference, &terminal_id, &wip_limits, registry);
         let new_snapshot = TaskComputedSnapshot::from_entity(entity);
         let prev = snapshots.get(&id).cloned().unwrap_or_default();
         let changes = prev.diff_to(&new_snapshot);
@@ -655,10 +664,10 @@ fn raw_changed_event(
 /// - Re-reads each changed entity through `EntityContext::read` and, for
 ///   tasks, runs the kanban-layer enrichment so ComputeEngine-derived
 ///   fields (`progress`, `tags`) and kanban graph fields (`virtual_tags`,
-///   `filter_tags`, `ready`, `blocked_by`, `blocks`) are merged into the
-///   emitted payload.
+///   `filter_tags`, `ready`, `blocked_by`, `blocks`, `children`) are merged
+///   into the emitted payload.
 /// - Fans out synthetic `EntityFieldChanged` events to dependent tasks when
-///   a task write touches `position_column` or `depends_on`.
+///   a task write touches `position_column`, `depends_on` or `parent`.
 /// - Updates the shared `EntitySearchIndex` in lockstep with every emission
 ///   so full-text search results don't drift behind the frontend store.
 /// - Logs and continues on `Lagged` — dropping events keeps the bridge
@@ -5,8 +5,10 @@ import { TextViewer } from "@/components/text-viewer";
 import { useMentionExtensions } from "@/hooks/use-mention-extensions";
 import {
   createMarkdownCheckboxPlugin,
+  checkboxPromoteFacet,
   checkboxToggleFacet,
 } from "@/lib/cm-markdown-checkbox";
+import { useDispatchCommand } from "@/lib/command-scope";
 import { CompactCellWrapper } from "./compact-cell-wrapper";
 import type { DisplayProps } from "./text-display";
 
@@ -44,9 +46,12 @@ interface MarkdownDisplayProps extends Omit<DisplayProps, "onCommit"> {
  *
  * Compact mode is intentionally plain text: a miniature editor per row
  * in list views would be wasteful and visually noisy.
+ *
+ * On an editable task, each checkbox can also be promoted to a subtask.
  */
 export function MarkdownDisplay({
   value,
+  entity,
   mode,
   onCommit,
 }: MarkdownDisplayProps) {
@@ -65,7 +70,13 @@ export function MarkdownDisplay({
     return <span className="text-muted-foreground italic">Empty</span>;
   }
 
-  return <MarkdownFull text={text} onCommit={onCommit} />;
+  return (
+    <MarkdownFull
+      text={text}
+      onCommit={onCommit}
+      promotable={!!onCommit && entity?.entity_type === "task"}
+    />
+  );
 }
 
 /**
@@ -76,15 +87,29 @@ export function MarkdownDisplay({
  * facet: the plugin computes the 0-based source index of the clicked
  * checkbox and invokes `onToggle`, which we use to mutate the markdown
  * source and fire `onCommit` with the updated text.
+ *
+ * When `promotable`, the plugin's promote button dispatches
+ * `task.promoteChecklistItem` for the checkbox; the task comes from the
+ * surrounding scope chain and the backend rewrites the description.
  */
 function MarkdownFull({
   text,
   onCommit,
+  promotable = false,
 }: {
   text: string;
   onCommit?: (value: string) => void;
+  promotable?: boolean;
 }) {
   const mentionExtensions = useMentionExtensions();
+  const dispatchPromote = useDispatchCommand("task.promoteChecklistItem");
+
+  const handlePromote = useCallback(
+    (sourceIndex: number) => {
+      dispatchPromote({ args: { item: sourceIndex } }).catch(console.error);
+    },
+    [dispatchPromote],
+  );
 
   const handleToggle = useCallback(
     (sourceIndex: number) => {
@@ -101,8 +126,9 @@ function MarkdownFull({
       ...mentionExtensions,
       createMarkdownCheckboxPlugin(),
       checkboxToggleFacet.of(handleToggle),
+      ...(promotable ? [checkboxPromoteFacet.of(handlePromote)] : []),
     ],
-    [mentionExtensions, handleToggle],
+    [mentionExtensions, handleToggle, promotable, handlePromote],
   );
 
   return (
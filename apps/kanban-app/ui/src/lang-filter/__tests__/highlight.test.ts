@@ -89,6 +89,33 @@ describe("filter grammar highlighting", () => {
     ).toBe(true);
   });
 
+  it("highlights field names with tok-propertyName class", () => {
+    const classes = getHighlightClasses("due < today+7d");
+    expect(classes).toContain("tok-propertyName:due");
+  });
+
+  it("highlights comparison operators with tok-operator class", () => {
+    for (const op of ["=", "!=", "<=", ">=", "~"]) {
+      const classes = getHighlightClasses(`progress ${op} 50%`);
+      expect(classes).toContain(`tok-operator:${op}`);
+    }
+  });
+
+  it("highlights predicate values by kind", () => {
+    expect(getHighlightClasses("due < today+7d")).toContain(
+      "tok-literal:today+7d",
+    );
+    expect(getHighlightClasses('title ~ "auth"')).toContain(
+      'tok-string:"auth"',
+    );
+  });
+
+  it("highlights has: as a keyword and its field as a property", () => {
+    const classes = getHighlightClasses("has:attachments");
+    expect(classes).toContain("tok-keyword:has:");
+    expect(classes).toContain("tok-propertyName:attachments");
+  });
+
   it("complex expression: operators highlighted, tags/mentions not", () => {
     const classes = getHighlightClasses("#bug && @will || !#done");
     expect(classes.some((c) => c.includes("tok-typeName"))).toBe(false);
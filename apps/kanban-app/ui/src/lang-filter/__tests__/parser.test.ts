@@ -118,6 +118,48 @@ describe("filter grammar parser", () => {
     expect(tree).toContain("not");
   });
 
+  // ── Field predicates ─────────────────────────────────────────────
+
+  it("parses field comparisons with every operator", () => {
+    for (const op of ["=", "==", "!=", "<", "<=", ">", ">=", "~"]) {
+      const input = `column ${op} doing`;
+      const tree = parseTree(input);
+      expect(tree).toContain("Field");
+      expect(tree).toContain("FieldName");
+      expect(tree).toContain("CompareOp");
+      expect(hasError(input)).toBe(false);
+    }
+  });
+
+  it("parses bare and quoted predicate values", () => {
+    expect(parseTree("due < today+7d")).toContain("BareValue");
+    expect(parseTree("progress >= 50%")).toContain("BareValue");
+    expect(parseTree('title ~ "auth flow"')).toContain("String");
+    expect(hasError('title ~ "auth flow"')).toBe(false);
+  });
+
+  it("parses has: predicates", () => {
+    const tree = parseTree("has:attachments");
+    expect(tree).toContain("Has");
+    expect(tree).toContain("FieldName");
+    expect(hasError("has:attachments")).toBe(false);
+  });
+
+  it("combines predicates with atoms and operators", () => {
+    const input = "#bug && due < today+7d || !has:assignees column = doing";
+    const tree = parseTree(input);
+    expect(tree).toContain("Tag");
+    expect(tree).toContain("Field");
+    expect(tree).toContain("Has");
+    expect(tree).toContain("Not");
+    expect(hasError(input)).toBe(false);
+  });
+
+  it("a field without a value is a parse error", () => {
+    expect(hasError("due <")).toBe(true);
+    expect(hasError("has:")).toBe(true);
+  });
+
   // ── Grouping ─────────────────────────────────────────────────────
 
   it("parses grouped expressions", () => {
@@ -1,9 +1,10 @@
 /**
  * Syntax highlighting mapping for the filter DSL grammar.
  *
- * Maps Ref nodes, keyword nodes (`not`, `and`, `or`), operator nodes
- * (`!`, `&&`, `||`), and parentheses to CodeMirror highlight tags so they
- * render in distinct colors within the editor theme.
+ * Maps Ref nodes, keyword nodes (`not`, `and`, `or`, `has:`), operator
+ * nodes (`!`, `&&`, `||`, comparisons), field names, predicate values, and
+ * parentheses to CodeMirror highlight tags so they render in distinct colors
+ * within the editor theme.
  *
  * Tag, Mention, and Project nodes are intentionally NOT mapped here — they
  * get their colors from the mention decoration system (colored pills) in
@@ -19,7 +20,11 @@ export const highlighting = styleTags({
   // highlighting. Adding them here causes defaultHighlightStyle to override
   // entity colors.
   Ref: t.link,
-  "not and or": t.keyword,
+  "not and or HasPrefix": t.keyword,
   "Bang AmpAmp PipePipe": t.operator,
+  CompareOp: t.compareOperator,
+  FieldName: t.propertyName,
+  String: t.string,
+  BareValue: t.literal,
   "( )": t.paren,
 });
@@ -16,3 +16,11 @@ export declare const OrOp: number;
 export declare const PipePipe: number;
 export declare const or: number;
 export declare const Group: number;
+export declare const Has: number;
+export declare const HasPrefix: number;
+export declare const FieldName: number;
+export declare const Field: number;
+export declare const CompareOp: number;
+export declare const Value: number;
+export declare const String: number;
+export declare const BareValue: number;
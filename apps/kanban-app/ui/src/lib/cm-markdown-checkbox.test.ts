@@ -11,6 +11,7 @@ import { EditorView } from "@codemirror/view";
 import { EditorState, type Extension } from "@codemirror/state";
 import {
   createMarkdownCheckboxPlugin,
+  checkboxPromoteFacet,
   checkboxToggleFacet,
 } from "./cm-markdown-checkbox";
 
@@ -146,4 +147,36 @@ describe("createMarkdownCheckboxPlugin", () => {
     view.destroy();
     parent.remove();
   });
+
+  it("adds promote buttons only when onPromote is provided", () => {
+    const doc = "- [ ] a\n- [x] b";
+    const plain = createEditor([createMarkdownCheckboxPlugin()], doc);
+    expect(
+      plain.parent.querySelectorAll(".cm-markdown-checkbox-promote"),
+    ).toHaveLength(0);
+    plain.view.destroy();
+    plain.parent.remove();
+
+    const onPromote = vi.fn();
+    const onToggle = vi.fn();
+    const { view, parent } = createEditor(
+      [
+        createMarkdownCheckboxPlugin(),
+        checkboxToggleFacet.of(onToggle),
+        checkboxPromoteFacet.of(onPromote),
+      ],
+      doc,
+    );
+    const buttons = parent.querySelectorAll<HTMLButtonElement>(
+      ".cm-markdown-checkbox-promote",
+    );
+    expect(buttons).toHaveLength(2);
+
+    buttons[1].click();
+    expect(onPromote).toHaveBeenCalledWith(1);
+    expect(onToggle).not.toHaveBeenCalled();
+
+    view.destroy();
+    parent.remove();
+  });
 });
@@ -8,6 +8,10 @@
  * and invokes the `onToggle(sourceIndex)` callback provided via the
  * `checkboxToggleFacet`.
  *
+ * When a `checkboxPromoteFacet` callback is supplied, each checkbox also
+ * gets a small "promote" button that hands the same index to that callback
+ * (used by the task inspector to turn a checklist item into a subtask).
+ *
  * Used by the read-only markdown viewer to preserve interactive task-list
  * checkboxes that previously lived in the ReactMarkdown pipeline.
  */
@@ -43,6 +47,20 @@ export const checkboxToggleFacet = Facet.define<
   },
 });
 
+/**
+ * Facet carrying the optional `onPromote` callback. When present, each
+ * rendered checkbox gets a promote button that invokes it with the same
+ * 0-based source index `checkboxToggleFacet` receives.
+ */
+export const checkboxPromoteFacet = Facet.define<
+  (sourceIndex: number) => void,
+  ((sourceIndex: number) => void) | null
+>({
+  combine(values) {
+    return values.length > 0 ? values[values.length - 1] : null;
+  },
+});
+
 /**
  * Count how many `- [ ]` / `- [x]` patterns occur in `source` strictly
  * before `pos`. Used to derive the 0-based source index of a checkbox
@@ -79,7 +97,8 @@ class CheckboxWidget extends WidgetType {
   }
 
   /**
-   * Build the DOM: a `<span>` wrapping an `<input type="checkbox">`.
+   * Build the DOM: a `<span>` wrapping an `<input type="checkbox">`, plus
+   * a promote button when `checkboxPromoteFacet` is provided.
    *
    * The span carries `cm-markdown-checkbox` for styling; the input is
    * disabled for read-only display but still fires `click`/`change`
@@ -104,6 +123,23 @@ class CheckboxWidget extends WidgetType {
     });
 
     span.appendChild(input);
+
+    if (view.state.facet(checkboxPromoteFacet)) {
+      const promote = document.createElement("button");
+      promote.type = "button";
+      promote.className = "cm-markdown-checkbox-promote";
+      promote.textContent = "↳";
+      promote.title = "Promote to subtask";
+      promote.addEventListener("click", (e) => {
+        e.stopPropagation();
+        const onPromote = view.state.facet(checkboxPromoteFacet);
+        if (!onPromote) return;
+        const source = view.state.doc.toString();
+        onPromote(countCheckboxesBefore(source, this.from));
+      });
+      span.appendChild(promote);
+    }
+
     return span;
   }
 
@@ -136,7 +136,12 @@ impl ServerHandler for KanbanMcpServer {
     ) -> Result<CallToolResult, McpError> {
         let kanban_dir = Self::kanban_dir()?;
         let ctx = KanbanContext::new(kanban_dir);
-        dispatch_call_tool_request(&ctx, request).await
+        if let Err(e) = ctx.start_hooks().await {
+            tracing::warn!(error = %e, "kanban hooks not started");
+        }
+        let result = dispatch_call_tool_request(&ctx, request).await;
+        ctx.drain_hooks().await;
+        result
     }
 }
 
@@ -213,12 +218,19 @@ async fn dispatch_call_tool_request(
 /// `context` is prepended to the error message so the caller can tell
 /// which operation in a batch triggered the failure (e.g.
 /// `"move task: task not found: abc123"`).
+///
+/// A write that breaks declarative field rules also carries the broken
+/// rules as `{"violations": [{field, rule, message}]}` error data, so a
+/// client can fix each named field.
 fn classify_kanban_error(context: &str, err: KanbanError) -> McpError {
     let message = format!("{context}: {err}");
+    let data = err
+        .rule_violations()
+        .map(|violations| json!({ "violations": violations }));
     match classify_kanban_error_kind(&err) {
-        ErrorClass::InvalidParams => McpError::invalid_params(message, None),
-        ErrorClass::InvalidRequest => McpError::invalid_request(message, None),
-        ErrorClass::Internal => McpError::internal_error(message, None),
+        ErrorClass::InvalidParams => McpError::invalid_params(message, data),
+        ErrorClass::InvalidRequest => McpError::invalid_request(message, data),
+        ErrorClass::Internal => McpError::internal_error(message, data),
     }
 }
 
@@ -287,8 +299,10 @@ fn classify_entity_error_kind(err: &swissarmyhammer_entity::EntityError) -> Erro
         | EntityError::ChangelogEntryNotFound { .. } => ErrorClass::InvalidRequest,
 
         EntityError::ValidationFailed { .. }
+        | EntityError::RuleViolations { .. }
         | EntityError::StaleChange { .. }
-        | EntityError::UnsupportedUndoOp { .. } => ErrorClass::InvalidParams,
+        | EntityError::UnsupportedUndoOp { .. }
+        | EntityError::HistoryCompacted { .. } => ErrorClass::InvalidParams,
 
         EntityError::InvalidFrontmatter { .. }
         | EntityError::Yaml { .. }
@@ -297,6 +311,7 @@ fn classify_entity_error_kind(err: &swissarmyhammer_entity::EntityError) -> Erro
         | EntityError::PatchApply(_)
         | EntityError::TransactionPartialFailure { .. }
         | EntityError::RestoreFromTrashFailed { .. }
+        | EntityError::AttachmentCorrupt { .. }
         | EntityError::Io(_)
         | EntityError::Store(_) => ErrorClass::Internal,
     }
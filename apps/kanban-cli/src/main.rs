@@ -15,7 +15,7 @@ use mirdan::tool_install::{run_lifecycle_command, Lifecycle};
 use serde_json::Value;
 use std::path::PathBuf;
 use swissarmyhammer_cli_completions::lifecycle;
-use tracing::error;
+use tracing::{error, warn};
 
 fn main() {
     let args: Vec<String> = std::env::args().collect();
@@ -182,6 +182,12 @@ async fn execute_kanban_operation(arguments: serde_json::Map<String, Value>) ->
         }
     };
 
+    // Hooks fire for the writes below; give their deliveries a few seconds
+    // before the process exits.
+    if let Err(e) = ctx.start_hooks().await {
+        warn!("Hooks not started: {}", e);
+    }
+    let mut exit_code = 0;
     for op in &operations {
         match swissarmyhammer_kanban::dispatch::execute_operation(&ctx, op).await {
             Ok(result) => {
@@ -191,12 +197,14 @@ async fn execute_kanban_operation(arguments: serde_json::Map<String, Value>) ->
             }
             Err(e) => {
                 error!("Error: {}", e);
-                return 1;
+                exit_code = 1;
+                break;
             }
         }
     }
+    ctx.drain_hooks().await;
 
-    0
+    exit_code
 }
 
 fn looks_like_path(s: &str) -> bool {
@@ -17,7 +17,7 @@ use clap::{Arg, ArgMatches, Command};
 use std::path::{Path, PathBuf};
 
 use swissarmyhammer_merge::jsonl::merge_jsonl;
-use swissarmyhammer_merge::md::merge_md;
+use swissarmyhammer_merge::md::merge_md_report;
 use swissarmyhammer_merge::yaml::{merge_yaml, MergeOpts};
 use swissarmyhammer_merge::MergeError;
 
@@ -215,13 +215,19 @@ pub fn run_md(base: &PathBuf, ours: &PathBuf, theirs: &PathBuf) -> i32 {
         ..Default::default()
     };
 
-    match merge_md(&base_str, &ours_str, &theirs_str, &opts) {
-        Ok(merged) => write_ours(ours, &merged),
+    match merge_md_report(&base_str, &ours_str, &theirs_str, &opts) {
+        Ok(report) if report.conflicts.is_empty() => write_ours(ours, &report.merged),
+        Ok(report) => {
+            // Write the merge, with markers around each conflicting block, to ours so
+            // the user can resolve them.
+            for conflict in &report.conflicts {
+                eprintln!("merge conflict in Markdown body at {}", conflict);
+            }
+            let _ = std::fs::write(ours, &report.merged);
+            1
+        }
         Err(MergeError::Conflict(c)) => {
-            // Write conflict markers to ours so the user can resolve them.
-            let conflict_text = c.conflicting_ids.first().map(|s| s.as_str()).unwrap_or("");
             eprintln!("merge conflict in Markdown: {}", c);
-            let _ = std::fs::write(ours, conflict_text);
             1
         }
         Err(MergeError::ParseFailure(msg)) => {
@@ -378,6 +384,24 @@ mod tests {
         assert_eq!(code, 1, "conflicting md body should return exit code 1");
     }
 
+    #[test]
+    fn run_md_checklist_ticks_merge_and_conflicts_keep_the_rest() {
+        let tmp = TempDir::new().unwrap();
+        let base = write_file(&tmp, "base.md", "- [ ] a\n- [ ] b\n\nshared line\n");
+        let ours = write_file(&tmp, "ours.md", "- [x] a\n- [ ] b\n\nours line\n");
+        let theirs = write_file(&tmp, "theirs.md", "- [ ] a\n- [x] b\n\ntheirs line\n");
+
+        let code = run_md(&base, &ours, &theirs);
+        assert_eq!(code, 1, "the rewritten line still conflicts");
+
+        let result = fs::read_to_string(&ours).unwrap();
+        assert_eq!(
+            result,
+            "- [x] a\n- [x] b\n\n<<<<<<< ours\nours line\n=======\ntheirs line\n>>>>>>> theirs\n",
+            "both ticks land and only the rewritten line is marked"
+        );
+    }
+
     #[test]
     fn run_md_missing_file_returns_2() {
         let tmp = TempDir::new().unwrap();
@@ -233,7 +233,7 @@ fn md_overlapping_body_produces_conflict_markers() {
     );
 
     let merged = read_file(dir.path(), "ours.md");
-    // The output should contain git-style conflict markers from diffy
+    // The output should contain git-style conflict markers around the block
     assert!(
         merged.contains("<<<<<<<") || merged.contains(">>>>>>>"),
         "conflict markers should be written to the ours file; got:\n{merged}"
@@ -1,6 +1,7 @@
 //! Schema-driven shell operation dispatch for the `shelltool` CLI.
 //!
-//! The op subcommand tree (`execute command`, `list processes`, `grep history`,
+//! The op subcommand tree (`execute command`, `start command`, `wait command`,
+//! `send input`, `send keys`, `read screen`, `list processes`, `grep history`,
 //! `get lines`, `kill process`) is built at runtime in `main.rs` from
 //! [`ShellExecuteTool`]'s full schema via
 //! [`swissarmyhammer_operations::cli_gen::build_commands_from_schema`]. Once clap
@@ -28,7 +28,11 @@ pub enum Shell {
 
 impl Shell {
     /// The program to spawn, and the flag that introduces the script.
-    fn program_and_flag(self) -> (&'static str, &'static str) {
+    ///
+    /// Public for callers that cannot start from [`shell_command`], such as
+    /// the `shell` tool's pseudo-terminal sessions, so they still run the
+    /// same interpreter.
+    pub fn program_and_flag(self) -> (&'static str, &'static str) {
         match self {
             Shell::Platform if cfg!(target_os = "windows") => ("cmd", "/C"),
             Shell::Platform => ("sh", "-c"),
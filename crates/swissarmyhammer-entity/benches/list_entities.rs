@@ -69,6 +69,7 @@ fn task_entity_def() -> EntityDef {
         mention_display_field: None,
         mention_slug_field: None,
         search_display_field: None,
+        rules: Vec::new(),
     }
 }
 
@@ -300,6 +300,70 @@ pub fn apply_changes(entity: &mut Entity, changes: &[(String, FieldChange)]) ->
     Ok(())
 }
 
+/// Rebuild an entity as it stood at `at` from its chronological changelog.
+///
+/// Walks back from `current` — the entity as it is now, `None` once it has
+/// been deleted or archived — undoing every entry made after `at` with
+/// [`reverse_changes`]. Undoing a create or unarchive means the entity was not
+/// on the board yet; undoing a delete or archive brings it back.
+///
+/// If the log does not rewind cleanly from the current state (the file was
+/// edited outside the store, or an undo rewrote it without a log entry), the
+/// entries up to `at` are replayed forward from nothing instead.
+///
+/// Returns `None` when the entity did not exist at `at`, or had been deleted
+/// or archived by then. Fails with [`EntityError::HistoryCompacted`] when `at`
+/// falls inside a run of entries that compaction folded into a `snapshot`
+/// entry: the log then only knows the state on either side of the run.
+///
+/// [`EntityError::HistoryCompacted`]: crate::error::EntityError::HistoryCompacted
+pub fn entity_as_of(
+    entity_type: &EntityTypeName,
+    id: &EntityId,
+    current: Option<Entity>,
+    entries: &[ChangeEntry],
+    at: DateTime<Utc>,
+) -> Result<Option<Entity>> {
+    let next = entries.iter().find(|e| e.timestamp > at);
+    if let Some(snapshot) = next.filter(|e| e.op == "snapshot") {
+        return Err(crate::error::EntityError::HistoryCompacted {
+            entity_type: entity_type.to_string(),
+            id: id.to_string(),
+            boundary: snapshot.timestamp.to_rfc3339(),
+        });
+    }
+
+    let mut exists = current.is_some();
+    let mut entity = current.unwrap_or_else(|| Entity::new(entity_type.as_str(), id.as_str()));
+    let mut rewound = true;
+    for entry in entries.iter().rev().take_while(|e| e.timestamp > at) {
+        if apply_changes(&mut entity, &reverse_changes(&entry.changes)).is_err() {
+            rewound = false;
+            break;
+        }
+        exists = match entry.op.as_str() {
+            "create" | "unarchive" => false,
+            "delete" | "archive" => true,
+            _ => exists,
+        };
+    }
+    if rewound {
+        return Ok(exists.then_some(entity));
+    }
+
+    let mut exists = false;
+    let mut entity = Entity::new(entity_type.as_str(), id.as_str());
+    for entry in entries.iter().take_while(|e| e.timestamp <= at) {
+        apply_changes(&mut entity, &entry.changes)?;
+        exists = match entry.op.as_str() {
+            "create" | "unarchive" | "snapshot" => true,
+            "delete" | "archive" => false,
+            _ => exists,
+        };
+    }
+    Ok(exists.then_some(entity))
+}
+
 /// Read all change entries from a JSONL log file (legacy entity format only).
 ///
 /// Entity-format records (`ChangeEntry`) are returned as-is; store-format
@@ -534,7 +598,7 @@ fn replay_one_store_entry(
         entity_type: entity_type.clone(),
         entity_id,
         op: store_op_to_string(&store_entry.op).to_string(),
-        actor: None,
+        actor: store_entry.actor.clone(),
         changes,
         undone_id: None,
         redone_id: None,
@@ -557,6 +621,7 @@ fn store_op_to_string(op: &ChangeOp) -> &'static str {
         ChangeOp::Delete => "delete",
         ChangeOp::Archive => "archive",
         ChangeOp::Unarchive => "unarchive",
+        ChangeOp::Snapshot => "snapshot",
     }
 }
 
@@ -1048,6 +1113,7 @@ mod tests {
             mention_display_field: None,
             mention_slug_field: None,
             search_display_field: None,
+            rules: Vec::new(),
         }
     }
 
@@ -1144,6 +1210,26 @@ mod tests {
         assert!(update2_keys.contains(&"body"), "expected body in update");
     }
 
+    #[tokio::test]
+    async fn read_changelog_carries_store_actor() {
+        let dir = tempfile::tempdir().unwrap();
+        let log_path = dir.path().join("01ABC.jsonl");
+        let v1 = task_text("First", "alpha");
+        let entry = swissarmyhammer_store::with_changelog_actor("agent", async {
+            store_entry("01ABC", ChangeOp::Create, "", &v1, chrono::Utc::now())
+        })
+        .await;
+        let mut line = serde_json::to_string(&entry).unwrap();
+        line.push('\n');
+        fs::write(&log_path, line).await.unwrap();
+
+        let entries =
+            read_changelog_for(&EntityTypeName::from("task"), &task_entity_def(), &log_path)
+                .await
+                .unwrap();
+        assert_eq!(entries[0].actor.as_deref(), Some("agent"));
+    }
+
     #[tokio::test]
     async fn read_changelog_handles_mixed_legacy_and_store_lines() {
         let dir = tempfile::tempdir().unwrap();
@@ -1807,4 +1893,93 @@ mod tests {
         let result = apply_changes(&mut entity, &reversed);
         assert!(result.is_err(), "stale reversed Changed should error");
     }
+
+    /// A chronological history for one task: created titled "v1" at minute
+    /// 1, retitled "v2" at minute 2, deleted at minute 3. Returns the
+    /// entries and the time of each step.
+    fn task_history() -> (Vec<ChangeEntry>, [DateTime<Utc>; 3]) {
+        let t0 = Utc::now() - chrono::Duration::minutes(10);
+        let times = [1, 2, 3].map(|m| t0 + chrono::Duration::minutes(m));
+        let empty = Entity::new("task", "01ABC");
+        let mut v1 = empty.clone();
+        v1.set("title", serde_json::json!("v1"));
+        v1.set("order", serde_json::json!(1));
+        let mut v2 = v1.clone();
+        v2.set("title", serde_json::json!("v2"));
+        v2.set("order", serde_json::json!(2));
+        let steps = [
+            ("create", &empty, &v1),
+            ("update", &v1, &v2),
+            ("delete", &v2, &empty),
+        ];
+        let entries = steps
+            .iter()
+            .zip(times)
+            .map(|((op, old, new), at)| {
+                let mut entry = ChangeEntry::new("task", "01ABC", *op, diff_entities(old, new));
+                entry.timestamp = at;
+                entry
+            })
+            .collect();
+        (entries, times)
+    }
+
+    #[test]
+    fn entity_as_of_rewinds_through_updates_and_delete() {
+        let (entries, [created, retitled, deleted]) = task_history();
+        let task = EntityTypeName::from("task");
+        let id = EntityId::from("01ABC");
+        let at = |t: DateTime<Utc>| entity_as_of(&task, &id, None, &entries, t).unwrap();
+
+        assert!(at(created - chrono::Duration::seconds(1)).is_none());
+        assert_eq!(at(created).unwrap().get_str("title"), Some("v1"));
+        let mid = at(retitled - chrono::Duration::seconds(1)).unwrap();
+        assert_eq!(mid.get_str("title"), Some("v1"));
+        assert_eq!(mid.get("order"), Some(&serde_json::json!(1)));
+        assert_eq!(at(retitled).unwrap().get_str("title"), Some("v2"));
+        assert!(at(deleted).is_none());
+    }
+
+    #[test]
+    fn entity_as_of_replays_forward_when_rewind_does_not_apply() {
+        let (entries, [_, retitled, _]) = task_history();
+        // The live entity disagrees with the log (e.g. an unlogged undo), so
+        // undoing the delete and the retitle cannot be applied to it.
+        let mut current = Entity::new("task", "01ABC");
+        current.set("order", serde_json::json!(7));
+        let past = entity_as_of(
+            &EntityTypeName::from("task"),
+            &EntityId::from("01ABC"),
+            Some(current),
+            &entries[..2],
+            retitled - chrono::Duration::seconds(1),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(past.get_str("title"), Some("v1"));
+        assert_eq!(past.get("order"), Some(&serde_json::json!(1)));
+    }
+
+    #[test]
+    fn entity_as_of_refuses_times_folded_into_a_snapshot() {
+        let (mut entries, [created, retitled, _]) = task_history();
+        // Compaction folded the retitle into a snapshot; the delete is gone.
+        entries.truncate(2);
+        entries[1].op = "snapshot".into();
+        let task = EntityTypeName::from("task");
+        let id = EntityId::from("01ABC");
+        let mut current = Entity::new("task", "01ABC");
+        current.set("title", serde_json::json!("v2"));
+        current.set("order", serde_json::json!(2));
+        let at = |t: DateTime<Utc>| entity_as_of(&task, &id, Some(current.clone()), &entries, t);
+
+        assert!(at(created - chrono::Duration::seconds(1))
+            .unwrap()
+            .is_none());
+        assert!(matches!(
+            at(created),
+            Err(crate::error::EntityError::HistoryCompacted { .. })
+        ));
+        assert_eq!(at(retitled).unwrap().unwrap().get_str("title"), Some("v2"));
+    }
 }
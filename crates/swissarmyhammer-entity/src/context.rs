@@ -26,14 +26,19 @@
 //! To add a new pseudo-field, see the "Computed Fields and Pseudo-Field
 //! Dependencies" section in `ARCHITECTURE.md`.
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, OnceLock};
 
+use chrono::{DateTime, Utc};
 use swissarmyhammer_fields::{
-    ComputeEngine, EntityDef, EntityTypeName, FieldType, FieldsContext, ValidationEngine,
+    rules, ComputeEngine, EntityDef, EntityTypeName, FieldDef, FieldType, FieldsContext,
+    ValidationEngine, ARCHIVED_QUERY_SUFFIX, CHANGELOG_QUERY_SUFFIX,
+};
+use swissarmyhammer_store::changelog::Changelog;
+use swissarmyhammer_store::{
+    CompactionPolicy, StoreContext, StoreHandle, StoredItemId, UndoEntryId, UndoStack,
 };
-use swissarmyhammer_store::{StoreContext, StoreHandle, StoredItemId, UndoEntryId};
 use tokio::sync::RwLock;
 
 use crate::changelog::{self, ChangeEntry};
@@ -165,6 +170,14 @@ impl EntityContext {
         let _ = self.store_context.set(ctx);
     }
 
+    /// The attached StoreContext, if any.
+    ///
+    /// Compound operations use it to open an undo group, so that every write
+    /// they make is reversed by a single undo.
+    pub fn store_context(&self) -> Option<&Arc<StoreContext>> {
+        self.store_context.get()
+    }
+
     /// Attach a validation engine. Enables field validation on write.
     pub fn with_validation(mut self, engine: Arc<ValidationEngine>) -> Self {
         self.validation = Some(engine);
@@ -961,6 +974,161 @@ impl EntityContext {
         Ok(entries)
     }
 
+    /// Read an entity as it stood at `at`, rebuilt from its changelog.
+    ///
+    /// Starts from the live file — or from nothing, when the entity has since
+    /// been deleted or archived — and rewinds every change made after `at`;
+    /// see [`changelog::entity_as_of`]. Nothing on disk is touched. Computed
+    /// fields are derived for the rebuilt entity.
+    ///
+    /// Returns `Ok(None)` when the entity was not on the board at that time.
+    pub async fn read_as_of(
+        &self,
+        entity_type: impl Into<EntityTypeName>,
+        id: impl Into<EntityId>,
+        at: DateTime<Utc>,
+    ) -> Result<Option<Entity>> {
+        let entity_type = entity_type.into();
+        let id = id.into();
+        let current = match self.read_raw_internal(&entity_type, &id).await {
+            Ok(entity) => Some(entity),
+            Err(EntityError::NotFound { .. }) => None,
+            Err(e) => return Err(e),
+        };
+        let entries = self.full_history(&entity_type, &id).await?;
+        let Some(mut entity) = changelog::entity_as_of(&entity_type, &id, current, &entries, at)?
+        else {
+            return Ok(None);
+        };
+        self.apply_compute(&entity_type, &mut entity).await?;
+        Ok(Some(entity))
+    }
+
+    /// List the entities of a type that were on the board at `at`.
+    ///
+    /// Covers live entities and every entity with a trashed or archived
+    /// changelog, each rebuilt with [`read_as_of`](Self::read_as_of).
+    /// Results are ordered by id.
+    pub async fn list_as_of(
+        &self,
+        entity_type: impl AsRef<str>,
+        at: DateTime<Utc>,
+    ) -> Result<Vec<Entity>> {
+        let entity_type = entity_type.as_ref();
+        let mut ids: BTreeSet<String> = self
+            .list_raw_internal(entity_type)
+            .await?
+            .into_iter()
+            .map(|e| e.id.to_string())
+            .collect();
+        for staging in [StagingDir::Trash, StagingDir::Archive] {
+            for path in changelogs_in(&self.staging_dir(entity_type, staging)).await? {
+                // Staged changelogs are named `{id}.{entry_id}.jsonl`.
+                if let Some(id) = path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .and_then(|n| n.split('.').next())
+                {
+                    ids.insert(id.to_string());
+                }
+            }
+        }
+
+        let mut entities = Vec::new();
+        for id in ids {
+            if let Some(entity) = self.read_as_of(entity_type, id.as_str(), at).await? {
+                entities.push(entity);
+            }
+        }
+        Ok(entities)
+    }
+
+    /// Every changelog entry an entity has, across its live changelog and any
+    /// trashed or archived ones (an id can be deleted and created again), in
+    /// chronological order.
+    async fn full_history(
+        &self,
+        entity_type: &EntityTypeName,
+        id: &EntityId,
+    ) -> Result<Vec<ChangeEntry>> {
+        let def = self.entity_def(entity_type)?;
+        let live_path = self.changelog_path(entity_type.clone(), id.clone())?;
+        let prefix = format!(
+            "{}.",
+            live_path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or(id.as_str())
+        );
+        let mut paths = vec![live_path];
+        for staging in [StagingDir::Trash, StagingDir::Archive] {
+            let staged = changelogs_in(&self.staging_dir(entity_type, staging)).await?;
+            paths.extend(staged.into_iter().filter(|p| {
+                p.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.starts_with(&prefix))
+            }));
+        }
+
+        let mut seen = HashSet::new();
+        let mut entries = Vec::new();
+        for path in paths {
+            for entry in changelog::read_changelog_for(entity_type, def, &path).await? {
+                if seen.insert(entry.id.clone()) {
+                    entries.push(entry);
+                }
+            }
+        }
+        entries.sort_by_key(|e| e.timestamp);
+        Ok(entries)
+    }
+
+    /// Ids of the changelog entries undo or redo may still need.
+    ///
+    /// That is the attached store context's stack plus the one persisted in
+    /// the root's `undo_stack.yaml`, which another process on the same board
+    /// may be using. Anything that rewrites or removes history must leave
+    /// these entries, and what they refer to, in place.
+    pub async fn pinned_undo_entries(&self) -> Result<HashSet<UndoEntryId>> {
+        let mut pinned: HashSet<UndoEntryId> = UndoStack::load(&self.root.join("undo_stack.yaml"))?
+            .entries()
+            .iter()
+            .map(|e| e.id)
+            .collect();
+        if let Some(sc) = self.store_context.get() {
+            pinned.extend(sc.pinned_entries().await);
+        }
+        Ok(pinned)
+    }
+
+    /// Fold old history in the live changelogs of an entity type into
+    /// snapshots, as selected by `policy`.
+    ///
+    /// Entries still on the undo stack, and everything after them, are kept
+    /// as they are (see [`Changelog::compact_to_snapshot`] and
+    /// [`pinned_undo_entries`](Self::pinned_undo_entries)). Trashed and
+    /// archived changelogs are not touched. Returns each compacted entity
+    /// with the number of entries folded.
+    pub async fn compact_changelogs(
+        &self,
+        entity_type: impl AsRef<str>,
+        policy: &CompactionPolicy,
+    ) -> Result<Vec<(EntityId, usize)>> {
+        let pinned = self.pinned_undo_entries().await?;
+        let mut compacted = Vec::new();
+        for path in changelogs_in(&self.entity_dir(entity_type)).await? {
+            let folded = Changelog::new(path.clone())
+                .compact_to_snapshot(policy, &pinned)
+                .await?;
+            if let Some(id) = path.file_stem().and_then(|s| s.to_str()) {
+                if folded > 0 {
+                    compacted.push((EntityId::from(id), folded));
+                }
+            }
+        }
+        Ok(compacted)
+    }
+
     // =========================================================================
     // Internal: validation and computation
     // =========================================================================
@@ -977,6 +1145,8 @@ impl EntityContext {
     /// 2. Applies field defaults for missing non-computed fields.
     /// 3. Runs field-level validation via the ValidationEngine (if present).
     /// 4. Runs entity-level cross-field validation (if present).
+    /// 5. Checks the declarative `rules` of the fields and the entity type,
+    ///    with or without a ValidationEngine.
     ///
     /// Callers can use this independently of `write()` to validate an entity
     /// before passing it to a `StoreHandle`.
@@ -1029,11 +1199,25 @@ impl EntityContext {
             .await?;
         }
 
-        // Validate fields
-        let Some(ref engine) = self.validation else {
-            return Ok(entity);
-        };
+        if let Some(ref engine) = self.validation {
+            self.run_validate_functions(engine, &entity_type, &field_defs, &mut entity)
+                .await?;
+        }
+        self.enforce_rules(&entity_type, &field_defs, &entity)
+            .await?;
 
+        Ok(entity)
+    }
+
+    /// Run the `validate` JS functions of the fields, then of the entity
+    /// type, storing the values they return.
+    async fn run_validate_functions(
+        &self,
+        engine: &ValidationEngine,
+        entity_type: &str,
+        field_defs: &[&FieldDef],
+        entity: &mut Entity,
+    ) -> Result<()> {
         // Collect field names to validate (avoid borrowing entity.fields while mutating)
         let names_to_validate: Vec<String> = field_defs
             .iter()
@@ -1066,7 +1250,7 @@ impl EntityContext {
         }
 
         // Entity-level cross-field validation (runs after all field validations)
-        let entity_def = self.entity_def(&entity_type)?;
+        let entity_def = self.entity_def(entity_type)?;
         engine
             .validate_entity(entity_def, &mut entity.fields)
             .await
@@ -1075,7 +1259,45 @@ impl EntityContext {
                 message: e.to_string(),
             })?;
 
-        Ok(entity)
+        Ok(())
+    }
+
+    /// Reject the entity if it breaks any declarative rule of its fields or
+    /// of its entity type, naming every broken rule.
+    ///
+    /// The stored entity is read only when a field declares `transitions`. A
+    /// stored entity that cannot be read fails the write: checking the
+    /// transition against nothing would let it through unchecked.
+    async fn enforce_rules(
+        &self,
+        entity_type: &str,
+        field_defs: &[&FieldDef],
+        entity: &Entity,
+    ) -> Result<()> {
+        let entity_def = self.entity_def(entity_type)?;
+        let previous = if rules::needs_previous(field_defs) {
+            let path = io::entity_file_path(&self.entity_dir(entity_type), &entity.id, entity_def);
+            match io::read_entity(&path, entity_type, &entity.id, entity_def).await {
+                Ok(previous) => Some(previous),
+                Err(EntityError::NotFound { .. }) => None,
+                Err(e) => return Err(e),
+            }
+        } else {
+            None
+        };
+        let violations = rules::check_rules(
+            entity_def,
+            field_defs,
+            &entity.fields,
+            previous.as_ref().map(|p| &p.fields),
+        );
+        if violations.is_empty() {
+            return Ok(());
+        }
+        Err(EntityError::RuleViolations {
+            entity_type: entity_type.to_string(),
+            violations,
+        })
     }
 
     /// Process a single attachment field during validation.
@@ -1237,33 +1459,31 @@ impl EntityContext {
     ///
     /// The query returns raw entities (without applying compute) to avoid
     /// infinite recursion. When an `EntityCache` is attached, queries serve
-    /// from the in-memory map; otherwise they fall through to disk.
+    /// from the in-memory map; otherwise they fall through to disk. A type
+    /// ending in [`CHANGELOG_QUERY_SUFFIX`] also injects each entity's
+    /// `_changelog`, through the cache's memoized compute inputs when one is
+    /// attached; one ending in [`ARCHIVED_QUERY_SUFFIX`] reads the archive.
+    ///
+    /// The function is built per read and answers each distinct query once,
+    /// so aggregates that ask for the same history (the board's flow fields
+    /// both read every task's changelog) share a single pass over it.
     fn build_entity_query_fn(&self) -> std::sync::Arc<swissarmyhammer_fields::EntityQueryFn> {
         let root = self.root.clone();
         let fields_ctx = Arc::clone(&self.fields);
         let cache_weak = self.cache.get().cloned();
-        std::sync::Arc::new(Box::new(move |et: &str| {
+        let memo: Arc<tokio::sync::Mutex<HashMap<String, Arc<tokio::sync::OnceCell<QueryRows>>>>> =
+            Arc::default();
+        std::sync::Arc::new(Box::new(move |query: &str| {
             let root = root.clone();
             let fields_ctx = Arc::clone(&fields_ctx);
             let cache_weak = cache_weak.clone();
-            let et = et.to_string();
+            let memo = Arc::clone(&memo);
+            let query = query.to_string();
             Box::pin(async move {
-                if let Some(cache) = cache_weak.as_ref().and_then(|w| w.upgrade()) {
-                    return cache
-                        .get_all(&et)
-                        .await
-                        .into_iter()
-                        .map(|e| e.fields)
-                        .collect();
-                }
-                let Some(def) = fields_ctx.get_entity(&et) else {
-                    return vec![];
-                };
-                let dir = root.join(format!("{}s", et));
-                let entities = io::read_entity_dir(&dir, &et, def)
+                let cell = Arc::clone(memo.lock().await.entry(query.clone()).or_default());
+                cell.get_or_init(|| run_entity_query(root, fields_ctx, cache_weak, query))
                     .await
-                    .unwrap_or_default();
-                entities.into_iter().map(|e| e.fields).collect()
+                    .clone()
             })
         }))
     }
@@ -1849,6 +2069,131 @@ fn any_field_depends_on(defs: &[swissarmyhammer_fields::FieldDef], dep_name: &st
     })
 }
 
+/// Field maps returned by an aggregate entity query.
+type QueryRows = Vec<HashMap<String, serde_json::Value>>;
+
+/// Answer one aggregate entity query; see
+/// [`EntityContext::build_entity_query_fn`].
+async fn run_entity_query(
+    root: PathBuf,
+    fields_ctx: Arc<FieldsContext>,
+    cache_weak: Option<std::sync::Weak<crate::cache::EntityCache>>,
+    query: String,
+) -> QueryRows {
+    let (et, want_changelog) = match query.strip_suffix(CHANGELOG_QUERY_SUFFIX) {
+        Some(base) => (base, true),
+        None => (query.as_str(), false),
+    };
+    if let Some(et) = et.strip_suffix(ARCHIVED_QUERY_SUFFIX) {
+        return query_archived(&root, &fields_ctx, et, want_changelog).await;
+    }
+    let et = et.to_string();
+
+    if let Some(cache) = cache_weak.as_ref().and_then(|w| w.upgrade()) {
+        let mut entities = cache.get_all(&et).await;
+        if want_changelog {
+            for entity in &mut entities {
+                let (changelog, _) = cache
+                    .get_or_load_compute_inputs(&et, entity.id.as_str(), true, false)
+                    .await;
+                entity.fields.insert("_changelog".to_string(), changelog);
+            }
+        }
+        return entities.into_iter().map(|e| e.fields).collect();
+    }
+    let Some(def) = fields_ctx.get_entity(&et) else {
+        return vec![];
+    };
+    let dir = root.join(format!("{}s", et));
+    let mut entities = io::read_entity_dir(&dir, &et, def)
+        .await
+        .unwrap_or_default();
+    if want_changelog {
+        let type_name = EntityTypeName::from(et.as_str());
+        for entity in &mut entities {
+            let path = io::entity_file_path(&dir, &entity.id, def).with_extension("jsonl");
+            let entries = changelog::read_changelog_for(&type_name, def, &path)
+                .await
+                .unwrap_or_default();
+            entity
+                .fields
+                .insert("_changelog".to_string(), changelog_json(&entries));
+        }
+    }
+    entities.into_iter().map(|e| e.fields).collect()
+}
+
+/// The archived entities of `et` for an aggregate query, read from disk,
+/// each with its `_changelog` when `want_changelog` is set.
+///
+/// An archived changelog is named `{id}.jsonl`, or `{id}.{entry_id}.jsonl`
+/// when the store moved it; every one found for an id is read.
+async fn query_archived(
+    root: &Path,
+    fields_ctx: &FieldsContext,
+    et: &str,
+    want_changelog: bool,
+) -> QueryRows {
+    let Some(def) = fields_ctx.get_entity(et) else {
+        return vec![];
+    };
+    let dir = root.join(format!("{}s", et)).join(".archive");
+    let mut entities = io::read_entity_dir(&dir, et, def).await.unwrap_or_default();
+    if want_changelog {
+        let type_name = EntityTypeName::from(et);
+        let staged = changelogs_in(&dir).await.unwrap_or_default();
+        for entity in &mut entities {
+            let prefix = format!("{}.", entity.id);
+            let mut entries = Vec::new();
+            for path in staged.iter().filter(|p| {
+                p.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.starts_with(&prefix))
+            }) {
+                entries.extend(
+                    changelog::read_changelog_for(&type_name, def, path)
+                        .await
+                        .unwrap_or_default(),
+                );
+            }
+            entries.sort_by_key(|e| e.timestamp);
+            entity
+                .fields
+                .insert("_changelog".to_string(), changelog_json(&entries));
+        }
+    }
+    entities.into_iter().map(|e| e.fields).collect()
+}
+
+/// Changelog entries as the JSON array an aggregate sees in `_changelog`.
+fn changelog_json(entries: &[ChangeEntry]) -> serde_json::Value {
+    serde_json::Value::Array(
+        entries
+            .iter()
+            .filter_map(|e| serde_json::to_value(e).ok())
+            .collect(),
+    )
+}
+
+/// The `.jsonl` changelogs directly inside `dir` (an entity directory or its
+/// trash or archive), sorted; none when the directory does not exist.
+async fn changelogs_in(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut read_dir = match tokio::fs::read_dir(dir).await {
+        Ok(read_dir) => read_dir,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(EntityError::Io(e)),
+    };
+    let mut paths = Vec::new();
+    while let Some(entry) = read_dir.next_entry().await? {
+        let path = entry.path();
+        if path.extension().is_some_and(|ext| ext == "jsonl") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
 /// Convert a `FieldsError` from the compute engine into the crate-local
 /// `EntityError::ComputeError`, preserving the offending field name and
 /// underlying message when available. Consumes `err` by value so the owned
@@ -15,13 +15,16 @@
 //!   on their own.
 //! - [`archive`] — archive, unarchive, and reading an archived entity.
 //! - [`error_paths`] — what each operation returns for an unknown entity type,
-//!   a missing entity, and an empty store.
+//!   a missing entity, an empty store, and a write that breaks declarative
+//!   rules.
 //! - [`computed`] — computed fields, the changelog and file-created values the
 //!   compute engine gets, and `list_where`.
 //! - [`cache`] — the entity cache, and the events a delete or archive emits
 //!   through undo and redo.
+//! - [`history`] — `read_as_of` and `list_as_of` rebuilding past state, and
+//!   changelog compaction.
 //!
-//! This module carries what those eight share: the imports, the
+//! This module carries what those nine share: the imports, the
 //! store-backed context fixture, and the attachment fields context.
 
 mod archive;
@@ -32,6 +35,7 @@ mod changelog;
 mod computed;
 mod enrichment;
 mod error_paths;
+mod history;
 use super::*;
 use crate::changelog::FieldChange;
 use crate::test_utils::test_fields_context;
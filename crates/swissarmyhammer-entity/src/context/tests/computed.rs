@@ -339,6 +339,140 @@ async fn changelog_stripped_after_derivation() {
     assert!(loaded.fields.contains_key("change_count"));
 }
 
+#[tokio::test]
+async fn aggregate_query_with_changelog_suffix_injects_history() {
+    let defs = vec![
+        (
+            "title",
+            "id: 00000000000000000000000TTL\nname: title\ntype:\n  kind: text\n  single_line: true\n",
+        ),
+        (
+            "task_history",
+            "id: 00000000000000000000000THS\nname: task_history\ntype:\n  kind: computed\n  derive: count-task-history\n  depends_on:\n    - task\n",
+        ),
+    ];
+    let entities = vec![
+        ("task", "name: task\nfields:\n  - title\n"),
+        (
+            "board",
+            "name: board\nfields:\n  - title\n  - task_history\n",
+        ),
+    ];
+    let dir = TempDir::new().unwrap();
+    let fields = Arc::new(FieldsContext::from_yaml_sources(dir.path(), &defs, &entities).unwrap());
+
+    // Sum the changelog lengths of every task; the plain query has none.
+    let mut engine = swissarmyhammer_fields::ComputeEngine::new();
+    engine.register_aggregate(
+        "count-task-history",
+        Box::new(|_fields, query| {
+            Box::pin(async move {
+                let plain = query("task").await;
+                assert!(plain.iter().all(|t| !t.contains_key("_changelog")));
+                let tasks = query(&format!(
+                    "task{}",
+                    swissarmyhammer_fields::CHANGELOG_QUERY_SUFFIX
+                ))
+                .await;
+                let total: usize = tasks
+                    .iter()
+                    .filter_map(|t| t.get("_changelog").and_then(|v| v.as_array()))
+                    .map(|a| a.len())
+                    .sum();
+                json!(total)
+            })
+        }),
+    );
+    let ctx = EntityContext::new(dir.path(), fields).with_compute(Arc::new(engine));
+
+    let mut task = Entity::new("task", "01ABC");
+    task.set("title", json!("Hello"));
+    ctx.write(&task).await.unwrap();
+    let log_path = ctx.changelog_path("task", "01ABC").unwrap();
+    for title in ["Hello", "Again"] {
+        let entry = ChangeEntry::new(
+            "task",
+            "01ABC",
+            "update",
+            vec![(
+                "title".into(),
+                FieldChange::Set {
+                    value: json!(title),
+                },
+            )],
+        );
+        write_legacy_changelog_line(&log_path, &entry).await;
+    }
+    let mut board = Entity::new("board", "board");
+    board.set("title", json!("Board"));
+    ctx.write(&board).await.unwrap();
+
+    let loaded = ctx.read("board", "board").await.unwrap();
+    assert_eq!(loaded.fields.get("task_history"), Some(&json!(2)));
+}
+
+#[tokio::test]
+async fn aggregate_query_with_archived_suffix_reads_the_archive() {
+    let defs = vec![
+        (
+            "title",
+            "id: 00000000000000000000000TTL\nname: title\ntype:\n  kind: text\n  single_line: true\n",
+        ),
+        (
+            "archived_history",
+            "id: 00000000000000000000000AHS\nname: archived_history\ntype:\n  kind: computed\n  derive: count-archived-history\n  depends_on:\n    - task\n",
+        ),
+    ];
+    let entities = vec![
+        ("task", "name: task\nfields:\n  - title\n"),
+        (
+            "board",
+            "name: board\nfields:\n  - title\n  - archived_history\n",
+        ),
+    ];
+    let dir = TempDir::new().unwrap();
+    let fields = Arc::new(FieldsContext::from_yaml_sources(dir.path(), &defs, &entities).unwrap());
+
+    // Archived tasks and the length of their changelogs; live tasks are not
+    // part of the archived query.
+    let mut engine = swissarmyhammer_fields::ComputeEngine::new();
+    engine.register_aggregate(
+        "count-archived-history",
+        Box::new(|_fields, query| {
+            Box::pin(async move {
+                let archived = format!(
+                    "task{}{}",
+                    swissarmyhammer_fields::ARCHIVED_QUERY_SUFFIX,
+                    swissarmyhammer_fields::CHANGELOG_QUERY_SUFFIX
+                );
+                let tasks = query(&archived).await;
+                let entries: usize = tasks
+                    .iter()
+                    .filter_map(|t| t.get("_changelog").and_then(|v| v.as_array()))
+                    .map(|a| a.len())
+                    .sum();
+                json!({ "tasks": tasks.len(), "entries": entries })
+            })
+        }),
+    );
+    let ctx = EntityContext::new(dir.path(), fields).with_compute(Arc::new(engine));
+
+    for (id, title) in [("01ARC", "Archived"), ("01LIV", "Live")] {
+        let mut task = Entity::new("task", id);
+        task.set("title", json!(title));
+        ctx.write(&task).await.unwrap();
+    }
+    ctx.archive("task", "01ARC").await.unwrap();
+    let mut board = Entity::new("board", "board");
+    board.set("title", json!("Board"));
+    ctx.write(&board).await.unwrap();
+
+    let loaded = ctx.read("board", "board").await.unwrap();
+    let history = loaded.fields.get("archived_history").unwrap();
+    assert_eq!(history["tasks"], 1, "{history}");
+    assert!(history["entries"].as_u64().unwrap() >= 1, "{history}");
+}
+
 /// Build a FieldsContext whose "task" entity includes a computed field
 /// that depends on `_file_created`.
 fn fields_context_with_file_created_computed() -> Arc<FieldsContext> {
@@ -1,7 +1,7 @@
 //! What each operation returns when the input is wrong.
 //!
-//! An unknown entity type, a missing entity, an empty store, and a
-//! changelog read with no writes behind it.
+//! An unknown entity type, a missing entity, an empty store, a changelog
+//! read with no writes behind it, and a write that breaks declarative rules.
 
 use super::*;
 
@@ -408,3 +408,86 @@ async fn read_changelog_with_trash_fallback_unknown_entity_type_errors() {
     let result = ctx.read_changelog_with_trash_fallback("unicorn", "x").await;
     assert!(result.is_err());
 }
+
+#[tokio::test]
+async fn write_rejects_rule_violations_naming_each_field() {
+    let defs = vec![
+        (
+            "title",
+            "id: 00000000000000000000000TTL\nname: title\ntype:\n  kind: text\n  single_line: true\nrules:\n  required: true\n",
+        ),
+        (
+            "status",
+            "id: 00000000000000000000000STS\nname: status\ntype:\n  kind: text\nrules:\n  transitions:\n    open: [active]\n",
+        ),
+        (
+            "scheduled",
+            "id: 00000000000000000000000SCH\nname: scheduled\ntype:\n  kind: date\n",
+        ),
+        (
+            "due",
+            "id: 00000000000000000000000DUE\nname: due\ntype:\n  kind: date\n",
+        ),
+    ];
+    let entities = vec![(
+        "item",
+        "name: item\nfields: [title, status, scheduled, due]\nrules:\n  - field: due\n    not_before: scheduled\n",
+    )];
+    let dir = TempDir::new().unwrap();
+    let fields = Arc::new(FieldsContext::from_yaml_sources(dir.path(), &defs, &entities).unwrap());
+    let ctx = EntityContext::new(dir.path(), fields);
+
+    let mut item = Entity::new("item", "01ITEM");
+    item.set("status", json!("open"));
+    item.set("scheduled", json!("2026-05-02"));
+    item.set("due", json!("2026-05-01"));
+    let err = ctx.write(&item).await.unwrap_err();
+    let EntityError::RuleViolations {
+        entity_type,
+        violations,
+    } = &err
+    else {
+        panic!("expected rule violations, got {err}");
+    };
+    assert_eq!(entity_type, "item");
+    let broken: Vec<(&str, &str)> = violations
+        .iter()
+        .map(|v| (v.field.as_str(), v.rule.as_str()))
+        .collect();
+    assert_eq!(broken, vec![("title", "required"), ("due", "not_before")]);
+    assert!(err
+        .to_string()
+        .starts_with("invalid item: title: is required; due:"));
+
+    item.set("title", json!("Fixed"));
+    item.set("due", json!("2026-05-03"));
+    ctx.write(&item).await.unwrap();
+
+    // `open` may only become `active`.
+    item.set("status", json!("closed"));
+    let err = ctx.write(&item).await.unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("status: cannot change from open to closed"));
+    // Clearing it is a change too.
+    item.set("status", json!(""));
+    let err = ctx.write(&item).await.unwrap_err();
+    assert!(err.to_string().contains("status: cannot clear open"));
+    item.set("status", json!("active"));
+    ctx.write(&item).await.unwrap();
+
+    // A stored item that cannot be read is not taken for a new one.
+    std::fs::write(
+        dir.path().join("items").join("01BROKEN.yaml"),
+        "status: [open\n",
+    )
+    .unwrap();
+    let mut broken = Entity::new("item", "01BROKEN");
+    broken.set("title", json!("Broken"));
+    broken.set("status", json!("closed"));
+    let err = ctx.write(&broken).await.unwrap_err();
+    assert!(
+        !matches!(err, EntityError::RuleViolations { .. }),
+        "expected the read error, got {err}"
+    );
+}
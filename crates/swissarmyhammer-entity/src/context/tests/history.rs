@@ -0,0 +1,144 @@
+//! Reading entities as they stood at a past time.
+
+use super::*;
+use chrono::{DateTime, Utc};
+
+/// The current time, a moment after the last write and before the next.
+async fn checkpoint() -> DateTime<Utc> {
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    let now = Utc::now();
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    now
+}
+
+#[tokio::test]
+async fn read_as_of_rewinds_updates_and_deletes() {
+    let dir = TempDir::new().unwrap();
+    let ctx = ctx_with_tag_store(&dir).await;
+
+    let before_create = checkpoint().await;
+    let mut tag = Entity::new("tag", "bug");
+    tag.set("tag_name", json!("Bug"));
+    tag.set("color", json!("#ff0000"));
+    ctx.write(&tag).await.unwrap();
+    let after_create = checkpoint().await;
+    tag.set("tag_name", json!("Defect"));
+    ctx.write(&tag).await.unwrap();
+    let after_update = checkpoint().await;
+    ctx.delete("tag", "bug").await.unwrap();
+
+    assert!(ctx
+        .read_as_of("tag", "bug", before_create)
+        .await
+        .unwrap()
+        .is_none());
+    let v1 = ctx
+        .read_as_of("tag", "bug", after_create)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(v1.get_str("tag_name"), Some("Bug"));
+    assert_eq!(v1.get_str("color"), Some("#ff0000"));
+    let v2 = ctx
+        .read_as_of("tag", "bug", after_update)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(v2.get_str("tag_name"), Some("Defect"));
+    assert!(ctx
+        .read_as_of("tag", "bug", Utc::now())
+        .await
+        .unwrap()
+        .is_none());
+
+    // Rebuilding the past leaves the files alone: the tag stays deleted.
+    assert!(ctx.list("tag").await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn list_as_of_includes_since_archived_entities() {
+    let dir = TempDir::new().unwrap();
+    let ctx = ctx_with_tag_store(&dir).await;
+
+    for id in ["bug", "feature"] {
+        let mut tag = Entity::new("tag", id);
+        tag.set("tag_name", json!(id));
+        ctx.write(&tag).await.unwrap();
+    }
+    let both = checkpoint().await;
+    ctx.archive("tag", "bug").await.unwrap();
+
+    let past = ctx.list_as_of("tag", both).await.unwrap();
+    let ids: Vec<&str> = past.iter().map(|t| t.id.as_str()).collect();
+    assert_eq!(ids, vec!["bug", "feature"]);
+
+    let now = ctx.list_as_of("tag", Utc::now()).await.unwrap();
+    let ids: Vec<&str> = now.iter().map(|t| t.id.as_str()).collect();
+    assert_eq!(ids, vec!["feature"]);
+}
+
+/// Write the `bug` tag and rename it through `names`, taking a checkpoint
+/// after each write.
+async fn renamed_tag(ctx: &EntityContext, names: &[&str]) -> Vec<DateTime<Utc>> {
+    let mut tag = Entity::new("tag", "bug");
+    let mut times = Vec::new();
+    for name in names {
+        tag.set("tag_name", json!(name));
+        ctx.write(&tag).await.unwrap();
+        times.push(checkpoint().await);
+    }
+    times
+}
+
+#[tokio::test]
+async fn compact_changelogs_folds_history_into_a_snapshot() {
+    let dir = TempDir::new().unwrap();
+    let ctx = ctx_with_tag_store(&dir).await;
+    let before_create = checkpoint().await;
+    let times = renamed_tag(&ctx, &["v1", "v2", "v3", "v4", "v5"]).await;
+
+    let policy = CompactionPolicy {
+        keep_latest: Some(1),
+        before: None,
+    };
+    let compacted = ctx.compact_changelogs("tag", &policy).await.unwrap();
+    assert_eq!(compacted, vec![(EntityId::from("bug"), 3)]);
+
+    let ops: Vec<String> = ctx
+        .read_changelog("tag", "bug")
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|e| e.op)
+        .collect();
+    assert_eq!(ops, vec!["create", "snapshot", "update"]);
+    let tag = ctx.read("tag", "bug").await.unwrap();
+    assert_eq!(tag.get_str("tag_name"), Some("v5"));
+
+    // Exact on either side of the folded run, refused inside it.
+    assert!(ctx
+        .read_as_of("tag", "bug", before_create)
+        .await
+        .unwrap()
+        .is_none());
+    let at_snapshot = ctx.read_as_of("tag", "bug", times[3]).await.unwrap();
+    assert_eq!(at_snapshot.unwrap().get_str("tag_name"), Some("v4"));
+    let inside = ctx.read_as_of("tag", "bug", times[1]).await;
+    assert!(matches!(inside, Err(EntityError::HistoryCompacted { .. })));
+}
+
+#[tokio::test]
+async fn compact_changelogs_keeps_entries_on_the_undo_stack() {
+    let dir = TempDir::new().unwrap();
+    let ctx = ctx_with_tag_store(&dir).await;
+    ctx.set_store_context(Arc::new(StoreContext::new(dir.path().to_path_buf())));
+    renamed_tag(&ctx, &["v1", "v2", "v3", "v4"]).await;
+
+    let policy = CompactionPolicy {
+        keep_latest: None,
+        before: Some(Utc::now()),
+    };
+    let compacted = ctx.compact_changelogs("tag", &policy).await.unwrap();
+    assert!(compacted.is_empty(), "every entry can still be undone");
+    assert_eq!(ctx.read_changelog("tag", "bug").await.unwrap().len(), 4);
+}
@@ -6,6 +6,14 @@ use thiserror::Error;
 /// Result type for entity operations.
 pub type Result<T> = std::result::Result<T, EntityError>;
 
+fn join_violations(violations: &[swissarmyhammer_fields::RuleViolation]) -> String {
+    violations
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
 /// Errors that can occur in entity operations.
 #[derive(Debug, Error)]
 pub enum EntityError {
@@ -32,6 +40,14 @@ pub enum EntityError {
     #[error("validation failed for field '{field}': {message}")]
     ValidationFailed { field: String, message: String },
 
+    /// The entity breaks declarative validation rules; every broken rule is
+    /// listed, each naming its field.
+    #[error("invalid {entity_type}: {}", join_violations(violations))]
+    RuleViolations {
+        entity_type: String,
+        violations: Vec<swissarmyhammer_fields::RuleViolation>,
+    },
+
     /// Computed field derivation failed.
     #[error("compute error for field '{field}': {message}")]
     ComputeError { field: String, message: String },
@@ -81,6 +97,16 @@ pub enum EntityError {
         rollback_succeeded: bool,
     },
 
+    /// The state asked for lies inside changelog history that compaction
+    /// folded into a snapshot.
+    #[error("history of {entity_type}/{id} before {boundary} has been compacted")]
+    HistoryCompacted {
+        entity_type: String,
+        id: String,
+        /// Timestamp of the snapshot entry the history was folded into.
+        boundary: String,
+    },
+
     /// Cannot restore from trash because the data file is missing.
     #[error("cannot restore from trash: data file not found at {path}")]
     RestoreFromTrashFailed { path: PathBuf },
@@ -108,6 +134,14 @@ pub enum EntityError {
         max_bytes: u64,
     },
 
+    /// Attachment file content no longer matches the hash it was stored under.
+    #[error("attachment {filename} is corrupt: expected sha256 {expected}, found {actual}")]
+    AttachmentCorrupt {
+        filename: String,
+        expected: String,
+        actual: String,
+    },
+
     /// YAML serialization/deserialization error (without file path context).
     #[error("YAML error: {0}")]
     YamlSerde(#[from] serde_yaml_ng::Error),
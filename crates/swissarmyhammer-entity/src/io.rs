@@ -573,9 +573,77 @@ pub fn sanitize_filename(name: &str) -> String {
     }
 }
 
-/// Copy a source file into `.attachments/` with atomic write (temp + rename).
+/// Get the content-addressed blob directory shared by every entity type.
 ///
-/// Returns the stored filename (`{ulid}-{sanitized_name}`).
+/// Sits beside the entity type directories: `{root}/.blobs/`. A blob is
+/// stored once per distinct content, at `{root}/.blobs/{hash[..2]}/{hash}`.
+pub fn blobs_dir(entity_type_dir: &Path) -> PathBuf {
+    entity_type_dir
+        .parent()
+        .unwrap_or(entity_type_dir)
+        .join(".blobs")
+}
+
+/// Get the path of the blob holding content with the given SHA-256 hex hash.
+pub fn blob_path(entity_type_dir: &Path, hash: &str) -> PathBuf {
+    let fanout = hash.get(..2).unwrap_or(hash);
+    blobs_dir(entity_type_dir).join(fanout).join(hash)
+}
+
+/// Lowercase hex SHA-256 of `bytes` — the key of the blob store.
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// The content hash embedded in a stored attachment filename.
+///
+/// Stored names are `{ulid}.{sha256}-{name}`; files stored before the blob
+/// store existed are `{ulid}-{name}` and have no hash.
+pub fn attachment_hash(filename: &str) -> Option<&str> {
+    let id = &filename[..filename.find('-')?];
+    let (_, hash) = id.split_once('.')?;
+    (hash.len() == 64 && hash.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')))
+        .then_some(hash)
+}
+
+/// Write `bytes` into the blob store unless a blob with the same content is
+/// already there. Returns the blob's hash.
+///
+/// A blob that is reused has its modification time refreshed, so attachment
+/// gc sees it as freshly stored until the new attachment refers to it.
+async fn store_blob(bytes: &[u8], entity_type_dir: &Path) -> Result<String> {
+    let hash = sha256_hex(bytes);
+    let dest = blob_path(entity_type_dir, &hash);
+    if fs::try_exists(&dest).await? {
+        let blob = fs::OpenOptions::new().write(true).open(&dest).await?;
+        blob.into_std()
+            .await
+            .set_modified(std::time::SystemTime::now())?;
+        return Ok(hash);
+    }
+    let dir = require_parent(&dest)?;
+    fs::create_dir_all(dir).await?;
+    let temp_path = temp_file_path(dir);
+    fs::write(&temp_path, bytes).await?;
+    rename_or_cleanup(&temp_path, &dest).await?;
+    Ok(hash)
+}
+
+/// Copy a source file into the blob store and give it a per-attachment name
+/// in `.attachments/`.
+///
+/// The content is stored once under `.blobs/` keyed by its SHA-256, so the
+/// same file attached to many tasks takes the space of one. The file in
+/// `.attachments/` is a hard link to that blob, which keeps trash and restore
+/// working per attachment while the bytes are shared. Both directories live
+/// under the board directory, so a failed link means the filesystem has no
+/// hard links at all; that is an error rather than a second full copy.
+///
+/// Returns the stored filename (`{ulid}.{sha256}-{sanitized_name}`).
 /// Validates that the source exists and does not exceed `max_bytes`.
 pub async fn copy_attachment(
     source: &Path,
@@ -604,14 +672,16 @@ pub async fn copy_attachment(
         });
     }
 
+    let bytes = fs::read(source).await?;
+    let hash = store_blob(&bytes, entity_type_dir).await?;
+
     // Generate stored filename
     let original_name = source
         .file_name()
         .and_then(|n| n.to_str())
         .unwrap_or("unnamed");
     let safe_name = sanitize_filename(original_name);
-    let ulid = Ulid::new();
-    let stored_name = format!("{}-{}", ulid, safe_name);
+    let stored_name = format!("{}.{}-{}", Ulid::new(), hash, safe_name);
 
     let dest_dir = attachments_dir(entity_type_dir);
     fs::create_dir_all(&dest_dir).await?;
@@ -619,13 +689,37 @@ pub async fn copy_attachment(
     let dest = dest_dir.join(&stored_name);
     let temp_path = temp_file_path(&dest_dir);
 
-    // Copy to temp file, then atomic rename
-    fs::copy(source, &temp_path).await?;
+    // Link to temp file, then atomic rename
+    let blob = blob_path(entity_type_dir, &hash);
+    fs::hard_link(&blob, &temp_path).await?;
     rename_or_cleanup(&temp_path, &dest).await?;
 
     Ok(stored_name)
 }
 
+/// Check a stored attachment against the hash in its name.
+///
+/// Fails with [`EntityError::AttachmentCorrupt`] when the file's content no
+/// longer hashes to the name, or when its blob is missing from the store.
+/// Files stored before the blob store (no hash in the name) have nothing to
+/// check against and pass.
+pub async fn verify_attachment(filename: &str, entity_type_dir: &Path) -> Result<()> {
+    let Some(expected) = attachment_hash(filename) else {
+        return Ok(());
+    };
+    let bytes = fs::read(attachments_dir(entity_type_dir).join(filename)).await?;
+    let actual = sha256_hex(&bytes);
+    let blob_present = fs::try_exists(blob_path(entity_type_dir, expected)).await?;
+    if actual != expected || !blob_present {
+        return Err(EntityError::AttachmentCorrupt {
+            filename: filename.to_string(),
+            expected: expected.to_string(),
+            actual,
+        });
+    }
+    Ok(())
+}
+
 /// Move an attachment file to `.attachments/.trash/`.
 ///
 /// Silently succeeds if the source file doesn't exist (already cleaned up).
@@ -639,12 +733,13 @@ pub async fn trash_attachment(filename: &str, entity_type_dir: &Path) -> Result<
 
 /// Derive attachment metadata from a stored filename.
 ///
-/// Given a stored filename like `01ABC123-screenshot.png`, extracts:
-/// - `id`: the ULID prefix (before the first `-`)
+/// Given a stored filename like `01ABC123.9f86d0…-screenshot.png`, extracts:
+/// - `id`: the prefix before the first `-` (the ULID and content hash)
 /// - `name`: the original filename (after the first `-`)
 /// - `size`: file size from fs::metadata
 /// - `mime_type`: detected from extension
 /// - `path`: absolute filesystem path
+/// - `sha256`: the content hash, for files stored in the blob store
 ///
 /// Returns a JSON object with the metadata, or `None` if the file doesn't exist.
 pub async fn attachment_metadata(
@@ -665,13 +760,17 @@ pub async fn attachment_metadata(
         .canonicalize()
         .unwrap_or_else(|_| file_path.clone());
 
-    Some(serde_json::json!({
+    let mut meta = serde_json::json!({
         "id": id,
         "name": name,
         "size": metadata.len(),
         "mime_type": mime_type.unwrap_or_else(|| "application/octet-stream".to_string()),
         "path": abs_path.to_string_lossy(),
-    }))
+    });
+    if let Some(hash) = attachment_hash(filename) {
+        meta["sha256"] = serde_json::json!(hash);
+    }
+    Some(meta)
 }
 
 /// Detect MIME type from file extension using the `mime_guess` crate.
@@ -697,6 +796,7 @@ mod tests {
             mention_display_field: None,
             mention_slug_field: None,
             search_display_field: None,
+            rules: Vec::new(),
         }
     }
 
@@ -712,6 +812,7 @@ mod tests {
             mention_display_field: None,
             mention_slug_field: None,
             search_display_field: None,
+            rules: Vec::new(),
         }
     }
 
@@ -1890,23 +1991,87 @@ mod tests {
     #[tokio::test]
     async fn copy_attachment_success() {
         let dir = tempfile::tempdir().unwrap();
+        let entity_type_dir = dir.path().join("items");
         let source = dir.path().join("test.txt");
         fs::write(&source, b"hello world").await.unwrap();
 
-        let stored = copy_attachment(&source, dir.path(), "avatar", 1_000_000)
+        let stored = copy_attachment(&source, &entity_type_dir, "avatar", 1_000_000)
             .await
             .unwrap();
 
         // Stored name should contain original filename
         assert!(stored.contains("test.txt"));
         // File should exist in .attachments/
-        let att_dir = attachments_dir(dir.path());
+        let att_dir = attachments_dir(&entity_type_dir);
         assert!(att_dir.join(&stored).exists());
         // Content should match
         let content = fs::read(att_dir.join(&stored)).await.unwrap();
         assert_eq!(content, b"hello world");
     }
 
+    #[tokio::test]
+    async fn copy_attachment_stores_identical_content_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let entity_type_dir = dir.path().join("items");
+        let first = dir.path().join("a.txt");
+        let second = dir.path().join("b.txt");
+        fs::write(&first, b"same bytes").await.unwrap();
+        fs::write(&second, b"same bytes").await.unwrap();
+
+        let a = copy_attachment(&first, &entity_type_dir, "files", 1_000)
+            .await
+            .unwrap();
+        let b = copy_attachment(&second, &entity_type_dir, "files", 1_000)
+            .await
+            .unwrap();
+
+        let hash = sha256_hex(b"same bytes");
+        assert_ne!(a, b, "each attachment keeps its own stored name");
+        assert_eq!(attachment_hash(&a), Some(hash.as_str()));
+        assert_eq!(attachment_hash(&b), Some(hash.as_str()));
+        assert_eq!(
+            fs::read(blob_path(&entity_type_dir, &hash)).await.unwrap(),
+            b"same bytes"
+        );
+        let fanout = blobs_dir(&entity_type_dir).join(&hash[..2]);
+        let mut blobs = fs::read_dir(&fanout).await.unwrap();
+        let mut count = 0;
+        while blobs.next_entry().await.unwrap().is_some() {
+            count += 1;
+        }
+        assert_eq!(count, 1, "identical content is stored as one blob");
+    }
+
+    #[tokio::test]
+    async fn verify_attachment_detects_changed_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let entity_type_dir = dir.path().join("items");
+        let source = dir.path().join("notes.txt");
+        fs::write(&source, b"original").await.unwrap();
+        let stored = copy_attachment(&source, &entity_type_dir, "files", 1_000)
+            .await
+            .unwrap();
+
+        verify_attachment(&stored, &entity_type_dir).await.unwrap();
+
+        // Replace rather than write through, so only this view changes.
+        let view = attachments_dir(&entity_type_dir).join(&stored);
+        fs::remove_file(&view).await.unwrap();
+        fs::write(&view, b"tampered").await.unwrap();
+        let err = verify_attachment(&stored, &entity_type_dir)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, EntityError::AttachmentCorrupt { .. }));
+
+        // Names from before the blob store carry no hash and always pass.
+        fs::write(attachments_dir(&entity_type_dir).join("01OLD-x.txt"), b"x")
+            .await
+            .unwrap();
+        verify_attachment("01OLD-x.txt", &entity_type_dir)
+            .await
+            .unwrap();
+    }
+
     #[tokio::test]
     async fn trash_attachment_moves_to_trash_dir() {
         let dir = tempfile::tempdir().unwrap();
@@ -244,6 +244,7 @@ mod tests {
             mention_display_field: None,
             mention_slug_field: None,
             search_display_field: None,
+            rules: Vec::new(),
         }
     }
 
@@ -260,6 +261,7 @@ mod tests {
             mention_display_field: None,
             mention_slug_field: None,
             search_display_field: None,
+            rules: Vec::new(),
         }
     }
 
@@ -280,6 +282,7 @@ mod tests {
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         }
     }
 
@@ -291,6 +294,7 @@ mod tests {
             description: None,
             type_: FieldType::Computed {
                 derive: "test-derive".into(),
+                script: None,
                 depends_on: vec![],
                 entity: None,
                 commit_display_names: false,
@@ -305,6 +309,7 @@ mod tests {
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         }
     }
 
@@ -4,14 +4,35 @@
 //! The consumer (e.g. kanban) registers its derivations at startup.
 //! The engine looks up the `derive` name from a `Computed` field type
 //! and invokes the matching function.
+//!
+//! Fields with `derive: js` need no registration: their `script` is a JS
+//! expression or function body run in the sandbox of `swissarmyhammer-js`,
+//! so a board can define fields like `age_days` in YAML alone:
+//!
+//! ```yaml
+//! name: age_days
+//! type:
+//!   kind: computed
+//!   derive: js
+//!   script: Math.floor((Date.now() - Date.parse(fields.created)) / 86400000)
+//! ```
 
 use std::collections::HashMap;
 use std::future::Future;
 use std::pin::Pin;
+use std::time::Duration;
+
+use swissarmyhammer_js::JsState;
 
 use crate::error::{FieldsError, Result};
 use crate::types::{FieldDef, FieldType};
 
+/// `derive` name of computed fields whose value comes from their `script`.
+pub const JS_DERIVE: &str = "js";
+
+/// Longest a JS derivation may run for one entity.
+pub const JS_DERIVE_TIMEOUT: Duration = Duration::from_millis(100);
+
 /// A native derivation function.
 ///
 /// Receives the entity's field values as a HashMap and returns the derived value.
@@ -34,6 +55,22 @@ pub type EntityQueryFn = Box<
         + Sync,
 >;
 
+/// Suffix on an [`EntityQueryFn`] entity type asking for each entity's
+/// changelog as well.
+///
+/// `query("task+_changelog")` returns the task field maps with the
+/// `_changelog` pseudo-field filled in, for aggregates that summarise history
+/// rather than current state.
+pub const CHANGELOG_QUERY_SUFFIX: &str = "+_changelog";
+
+/// Suffix on an [`EntityQueryFn`] entity type asking for the archived
+/// entities of that type instead of the live ones.
+///
+/// It goes before [`CHANGELOG_QUERY_SUFFIX`] when both are wanted:
+/// `query("task+_archived+_changelog")` returns every archived task with its
+/// history.
+pub const ARCHIVED_QUERY_SUFFIX: &str = "+_archived";
+
 /// A derivation function that can query other entities.
 ///
 /// Receives the entity's own fields plus a shared query function for reading
@@ -89,7 +126,8 @@ impl ComputeEngine {
     /// Derive the value of a single computed field.
     ///
     /// Returns `Ok(Null)` for non-computed fields.
-    /// Returns `Err(ComputeError)` if the derive name is not registered.
+    /// Returns `Err(ComputeError)` if the derive name is not registered, or
+    /// is `js` without a `script`.
     ///
     /// For aggregate derivations, `entity_query` must be `Some`. If it is
     /// `None` and the derive name is registered as an aggregate, an error
@@ -101,6 +139,13 @@ impl ComputeEngine {
         entity_query: Option<&std::sync::Arc<EntityQueryFn>>,
     ) -> Result<serde_json::Value> {
         match &field.type_ {
+            FieldType::Computed { derive, script, .. } if derive == JS_DERIVE => {
+                let script = script.as_deref().ok_or_else(|| FieldsError::ComputeError {
+                    field: field.name.to_string(),
+                    message: "js derivation needs a script".to_string(),
+                })?;
+                Ok(derive_js(field.name.as_str(), script, entity_fields).await)
+            }
             FieldType::Computed { derive, .. } => {
                 // Try simple derivation first
                 if let Some(f) = self.derivations.get(derive.as_str()) {
@@ -161,6 +206,59 @@ impl ComputeEngine {
     }
 }
 
+/// Run a `derive: js` script for one entity.
+///
+/// The script sees the entity's fields as `fields`, and as `ctx.fields`
+/// next to the field's `ctx.name`, like a `validate` body does. A script
+/// that is an expression yields its value; otherwise it is a function body
+/// and yields what it returns.
+///
+/// A script that throws or runs past [`JS_DERIVE_TIMEOUT`] derives `null`
+/// and logs a warning, so one bad task cannot make the board unreadable.
+async fn derive_js(
+    field_name: &str,
+    script: &str,
+    entity_fields: &HashMap<String, serde_json::Value>,
+) -> serde_json::Value {
+    let ctx_obj = serde_json::json!({
+        "fields": entity_fields,
+        "name": field_name,
+    });
+
+    // Data and script both reach JS as string literals (see
+    // `ValidationEngine::run_js_validation`); the script is compiled with
+    // `new Function`, first as an expression, then as a function body.
+    let ctx_json_str = serde_json::to_string(&ctx_obj).unwrap_or_default();
+    let ctx_json_string_literal = serde_json::to_string(&ctx_json_str).unwrap_or_default();
+    let script_literal = serde_json::to_string(script.trim()).unwrap_or_default();
+    let js_code = format!(
+        r#"(function() {{
+    var ctx = JSON.parse({ctx_json_string});
+    var src = {script};
+    var f;
+    try {{
+        f = new Function("fields", "ctx", "return (" + src + "\n);");
+    }} catch (e) {{
+        f = new Function("fields", "ctx", src);
+    }}
+    return f(ctx.fields, ctx);
+}})()"#,
+        ctx_json_string = ctx_json_string_literal,
+        script = script_literal,
+    );
+
+    match JsState::global()
+        .eval_sandboxed(&js_code, JS_DERIVE_TIMEOUT)
+        .await
+    {
+        Ok(value) => value,
+        Err(e) => {
+            tracing::warn!(field = field_name, error = %e, "js derivation failed");
+            serde_json::Value::Null
+        }
+    }
+}
+
 impl Default for ComputeEngine {
     fn default() -> Self {
         Self::new()
@@ -179,6 +277,7 @@ mod tests {
             description: None,
             type_: FieldType::Computed {
                 derive: derive.to_string(),
+                script: None,
                 depends_on: vec![],
                 entity: None,
                 commit_display_names: false,
@@ -193,9 +292,18 @@ mod tests {
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         }
     }
 
+    fn make_js_field(name: &str, script: &str) -> FieldDef {
+        let mut field = make_computed_field(name, JS_DERIVE);
+        if let FieldType::Computed { script: s, .. } = &mut field.type_ {
+            *s = Some(script.to_string());
+        }
+        field
+    }
+
     fn make_text_field(name: &str) -> FieldDef {
         FieldDef {
             id: FieldDefId::new(),
@@ -212,6 +320,7 @@ mod tests {
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         }
     }
 
@@ -423,6 +532,55 @@ mod tests {
         assert!(err.contains("aggregate"));
     }
 
+    #[tokio::test]
+    async fn js_derive_runs_expression_and_function_body() {
+        let engine = ComputeEngine::new();
+        let mut fields = HashMap::new();
+        fields.insert("title".to_string(), serde_json::json!("Fix login"));
+        fields.insert("estimate".to_string(), serde_json::json!(3));
+
+        let expr = make_js_field(
+            "risk",
+            "fields.estimate * 2 + (ctx.name === 'risk' ? 1 : 0)",
+        );
+        let result = engine.derive(&expr, &fields, None).await.unwrap();
+        assert_eq!(result, serde_json::json!(7));
+
+        let body = make_js_field(
+            "kind",
+            "const t = fields.title.toLowerCase();\nif (t.startsWith('fix')) return 'bug';\nreturn 'feature';",
+        );
+        let result = engine.derive(&body, &fields, None).await.unwrap();
+        assert_eq!(result, serde_json::json!("bug"));
+    }
+
+    #[tokio::test]
+    async fn js_derive_failures_yield_null() {
+        let engine = ComputeEngine::new();
+        let fields = HashMap::new();
+
+        let throws = make_js_field("bad", "fields.missing.length");
+        let result = engine.derive(&throws, &fields, None).await.unwrap();
+        assert_eq!(result, serde_json::Value::Null);
+
+        let spins = make_js_field("slow", "while (true) {}");
+        let result = engine.derive(&spins, &fields, None).await.unwrap();
+        assert_eq!(result, serde_json::Value::Null);
+    }
+
+    #[tokio::test]
+    async fn js_derive_without_script_errors() {
+        let engine = ComputeEngine::new();
+        let field = make_computed_field("age_days", JS_DERIVE);
+
+        let err = engine
+            .derive(&field, &HashMap::new(), None)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("needs a script"));
+    }
+
     #[test]
     fn compute_engine_default_creates_empty() {
         let engine = ComputeEngine::default();
@@ -470,6 +470,7 @@ mod tests {
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         }
     }
 
@@ -841,6 +842,7 @@ type:
             mention_display_field: None,
             mention_slug_field: None,
             search_display_field: None,
+            rules: Vec::new(),
         };
         ctx.write_entity(&entity).await.unwrap();
 
@@ -872,6 +874,7 @@ type:
             mention_display_field: None,
             mention_slug_field: None,
             search_display_field: None,
+            rules: Vec::new(),
         };
         ctx.write_entity(&entity).await.unwrap();
 
@@ -901,6 +904,7 @@ type:
                 mention_display_field: None,
                 mention_slug_field: None,
                 search_display_field: None,
+                rules: Vec::new(),
             })
             .await
             .unwrap();
@@ -937,6 +941,7 @@ type:
                 placeholder: None,
                 validate: None,
                 groupable: None,
+                rules: None,
             })
             .collect();
 
@@ -1165,6 +1170,7 @@ fields:
             mention_display_field: None,
             mention_slug_field: None,
             search_display_field: None,
+            rules: Vec::new(),
         };
         // write_entity should create_dir_all for the parent (entities/)
         ctx.write_entity(&entity).await.unwrap();
@@ -1312,6 +1318,7 @@ fields:
             mention_display_field: None,
             mention_slug_field: None,
             search_display_field: None,
+            rules: Vec::new(),
         };
         ctx.write_entity(&entity).await.unwrap();
         assert_eq!(ctx.get_entity("task").unwrap().fields.len(), 1);
@@ -1328,6 +1335,7 @@ fields:
             mention_display_field: None,
             mention_slug_field: None,
             search_display_field: None,
+            rules: Vec::new(),
         };
         ctx.write_entity(&updated).await.unwrap();
 
@@ -1354,6 +1362,7 @@ fields:
                 mention_display_field: None,
                 mention_slug_field: None,
                 search_display_field: None,
+                rules: Vec::new(),
             })
             .await
             .unwrap();
@@ -1516,6 +1525,7 @@ fields:
                 mention_display_field: None,
                 mention_slug_field: None,
                 search_display_field: None,
+                rules: Vec::new(),
             };
             ctx.write_entity(&entity).await.unwrap();
         }
@@ -16,13 +16,18 @@ pub mod context;
 pub mod derive;
 pub mod error;
 pub mod id_types;
+pub mod rules;
 pub mod types;
 pub mod validation;
 
-pub use compute::{AggregateFn, ComputeEngine, DeriveFn, EntityQueryFn};
+pub use compute::{
+    AggregateFn, ComputeEngine, DeriveFn, EntityQueryFn, ARCHIVED_QUERY_SUFFIX,
+    CHANGELOG_QUERY_SUFFIX, JS_DERIVE, JS_DERIVE_TIMEOUT,
+};
 pub use context::{load_yaml_dir, FieldsContext, FieldsContextBuilder};
 pub use derive::{DeriveError, DeriveHandler, DeriveRegistry};
 pub use error::{FieldsError, Result};
 pub use id_types::{EntityTypeName, FieldDefId, FieldName};
+pub use rules::{check_rules, EntityRule, FieldRules, RulePattern, RuleViolation};
 pub use types::{EntityDef, FieldDef, FieldType, SelectOption, SortKind};
 pub use validation::{EntityLookup, ValidationEngine};
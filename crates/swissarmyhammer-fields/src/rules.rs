@@ -0,0 +1,543 @@
+//! Declarative validation rules.
+//!
+//! A field definition can carry a `rules` block and an entity definition a
+//! list of cross-field `rules`. Unlike `validate` JS bodies they never
+//! transform values; they only accept or reject them, and every broken rule
+//! is reported at once so the caller can fix all of its input in one go.
+//!
+//! ```yaml
+//! # definitions/estimate.yaml
+//! name: estimate
+//! type:
+//!   kind: number
+//! rules:
+//!   required: true
+//!   min: 1
+//!   max: 13
+//!
+//! # entities/task.yaml
+//! rules:
+//!   - field: due
+//!     not_before: scheduled
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+use crate::id_types::FieldName;
+use crate::types::{is_false, EntityDef, FieldDef};
+
+/// Constraints on one field's value, wherever the field is used.
+///
+/// Every rule but `required` passes empty values (null, `""` and `[]`), so
+/// optional fields only need to be valid when set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct FieldRules {
+    /// The value may not be empty.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub required: bool,
+    /// Regex a text value (or each text in a list) must match.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<RulePattern>,
+    /// Smallest allowed number.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    /// Largest allowed number.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    /// Fewest characters in a text, or items in a list.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+    /// Most characters in a text, or items in a list.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+    /// Allowed changes of value: each stored value maps to the values it may
+    /// become, `""` standing for an empty one. A value that is not listed may
+    /// change to anything, and a new entity may start with any value. Clearing
+    /// a listed value is a change like any other.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub transitions: BTreeMap<String, Vec<String>>,
+    /// Replaces the generated message of any violation of these rules.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// The regex of a `pattern` rule, compiled when the definition is read.
+///
+/// A definition whose pattern does not compile fails to parse, so it is
+/// reported when definitions load rather than on every write, and the
+/// compiled regex is reused for every check.
+#[derive(Debug, Clone)]
+pub struct RulePattern(Regex);
+
+impl RulePattern {
+    /// Compile a pattern.
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        Regex::new(pattern).map(Self)
+    }
+
+    /// The pattern as written.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// Whether `text` matches the pattern.
+    pub fn is_match(&self, text: &str) -> bool {
+        self.0.is_match(text)
+    }
+}
+
+impl PartialEq for RulePattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl std::fmt::Display for RulePattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for RulePattern {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for RulePattern {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        Self::new(&pattern)
+            .map_err(|e| serde::de::Error::custom(format!("invalid pattern {pattern}: {e}")))
+    }
+}
+
+/// A constraint an entity type puts on one of its fields, possibly
+/// relative to another field.
+///
+/// Comparisons are skipped while either field is empty. Numbers compare
+/// numerically and everything else as text, which orders ISO 8601 dates.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EntityRule {
+    /// The field the rule is about, and the one violations name.
+    pub field: FieldName,
+    /// The field may not be empty on this entity type.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub required: bool,
+    /// The field must be greater than (later than) this field.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<FieldName>,
+    /// The field may not be less than (earlier than) this field.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_before: Option<FieldName>,
+    /// Replaces the generated message of a violation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// One broken rule.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RuleViolation {
+    /// The field whose value is rejected.
+    pub field: String,
+    /// The rule it breaks: `required`, `pattern`, `min`, `max`,
+    /// `min_length`, `max_length`, `transition`, `after` or `not_before`.
+    pub rule: String,
+    /// What is wrong, in words.
+    pub message: String,
+}
+
+impl std::fmt::Display for RuleViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// Check an entity's fields against the rules of its field definitions and
+/// of its entity definition.
+///
+/// `previous` holds the stored fields when the entity is being updated; it
+/// is needed for `transitions` only. Returns every violation, field rules
+/// first in `field_defs` order, then entity rules in declaration order.
+pub fn check_rules(
+    entity_def: &EntityDef,
+    field_defs: &[&FieldDef],
+    fields: &HashMap<String, Value>,
+    previous: Option<&HashMap<String, Value>>,
+) -> Vec<RuleViolation> {
+    let mut violations = Vec::new();
+    for fd in field_defs {
+        let Some(rules) = &fd.rules else {
+            continue;
+        };
+        let name = fd.name.as_str();
+        let before = violations.len();
+        check_field(
+            name,
+            rules,
+            fields.get(name),
+            previous.and_then(|p| p.get(name)),
+            &mut violations,
+        );
+        if let Some(message) = &rules.message {
+            for v in &mut violations[before..] {
+                v.message = message.clone();
+            }
+        }
+    }
+    for rule in &entity_def.rules {
+        let before = violations.len();
+        check_entity_rule(rule, fields, &mut violations);
+        if let Some(message) = &rule.message {
+            for v in &mut violations[before..] {
+                v.message = message.clone();
+            }
+        }
+    }
+    violations
+}
+
+/// Whether any of the fields declares `transitions`, the one rule that
+/// needs the stored entity.
+pub fn needs_previous(field_defs: &[&FieldDef]) -> bool {
+    field_defs
+        .iter()
+        .any(|fd| fd.rules.as_ref().is_some_and(|r| !r.transitions.is_empty()))
+}
+
+fn violation(field: &str, rule: &str, message: String) -> RuleViolation {
+    RuleViolation {
+        field: field.to_string(),
+        rule: rule.to_string(),
+        message,
+    }
+}
+
+fn is_empty(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Null) => true,
+        Some(Value::String(s)) => s.trim().is_empty(),
+        Some(Value::Array(a)) => a.is_empty(),
+        Some(_) => false,
+    }
+}
+
+/// Compact rendering of a value for messages: text without quotes.
+fn show(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn check_field(
+    name: &str,
+    rules: &FieldRules,
+    value: Option<&Value>,
+    previous: Option<&Value>,
+    out: &mut Vec<RuleViolation>,
+) {
+    check_transition(name, rules, value, previous, out);
+    if is_empty(value) {
+        if rules.required {
+            out.push(violation(name, "required", "is required".to_string()));
+        }
+        return;
+    }
+    let value = value.expect("empty values returned above");
+
+    if let Some(pattern) = &rules.pattern {
+        let texts: Vec<&str> = match value {
+            Value::String(s) => vec![s.as_str()],
+            Value::Array(items) => items.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if let Some(text) = texts.into_iter().find(|t| !pattern.is_match(t)) {
+            out.push(violation(
+                name,
+                "pattern",
+                format!("'{text}' does not match {pattern}"),
+            ));
+        }
+    }
+
+    if let Some(n) = value.as_f64() {
+        if let Some(min) = rules.min.filter(|min| n < *min) {
+            out.push(violation(name, "min", format!("{n} is less than {min}")));
+        }
+        if let Some(max) = rules.max.filter(|max| n > *max) {
+            out.push(violation(name, "max", format!("{n} is more than {max}")));
+        }
+    }
+
+    let length = match value {
+        Value::String(s) => Some(s.chars().count()),
+        Value::Array(items) => Some(items.len()),
+        _ => None,
+    };
+    if let Some(length) = length {
+        if let Some(min) = rules.min_length.filter(|min| length < *min) {
+            out.push(violation(
+                name,
+                "min_length",
+                format!("has length {length}, at least {min} is required"),
+            ));
+        }
+        if let Some(max) = rules.max_length.filter(|max| length > *max) {
+            out.push(violation(
+                name,
+                "max_length",
+                format!("has length {length}, at most {max} is allowed"),
+            ));
+        }
+    }
+}
+
+/// Check the change from the stored `previous` value to `value` against the
+/// field's `transitions`, an empty value counting as `""`.
+fn check_transition(
+    name: &str,
+    rules: &FieldRules,
+    value: Option<&Value>,
+    previous: Option<&Value>,
+    out: &mut Vec<RuleViolation>,
+) {
+    let key = |value: Option<&Value>| match value {
+        Some(value) if !is_empty(Some(value)) => show(value),
+        _ => String::new(),
+    };
+    let Some(previous) = previous else {
+        return;
+    };
+    let (from, to) = (key(Some(previous)), key(value));
+    if from == to {
+        return;
+    }
+    if let Some(allowed) = rules.transitions.get(&from) {
+        if !allowed.contains(&to) {
+            let change = if to.is_empty() {
+                format!("cannot clear {from}")
+            } else {
+                format!("cannot change from {from} to {to}")
+            };
+            out.push(violation(
+                name,
+                "transition",
+                format!("{change}; allowed: {}", allowed.join(", ")),
+            ));
+        }
+    }
+}
+
+fn check_entity_rule(
+    rule: &EntityRule,
+    fields: &HashMap<String, Value>,
+    out: &mut Vec<RuleViolation>,
+) {
+    let name = rule.field.as_str();
+    let value = fields.get(name);
+    if is_empty(value) {
+        if rule.required {
+            out.push(violation(name, "required", "is required".to_string()));
+        }
+        return;
+    }
+    let value = value.expect("empty values returned above");
+
+    let comparisons = [
+        (
+            "after",
+            rule.after.as_ref(),
+            &[Ordering::Greater][..],
+            "must be after",
+        ),
+        (
+            "not_before",
+            rule.not_before.as_ref(),
+            &[Ordering::Greater, Ordering::Equal][..],
+            "must not be before",
+        ),
+    ];
+    for (kind, other, allowed, words) in comparisons {
+        let Some(other) = other else {
+            continue;
+        };
+        let other_value = fields.get(other.as_str());
+        if is_empty(other_value) {
+            continue;
+        }
+        let other_value = other_value.expect("empty values skipped above");
+        if !allowed.contains(&compare(value, other_value)) {
+            out.push(violation(
+                name,
+                kind,
+                format!("{} {words} {other} ({})", show(value), show(other_value)),
+            ));
+        }
+    }
+}
+
+/// Numbers compare numerically, anything else by its text.
+fn compare(a: &Value, b: &Value) -> Ordering {
+    match (a.as_f64(), b.as_f64()) {
+        (Some(x), Some(y)) => x.partial_cmp(&y).unwrap_or(Ordering::Equal),
+        _ => show(a).cmp(&show(b)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn field(yaml: &str) -> FieldDef {
+        serde_yaml_ng::from_str(yaml).unwrap()
+    }
+
+    fn entity(yaml: &str) -> EntityDef {
+        serde_yaml_ng::from_str(yaml).unwrap()
+    }
+
+    fn fields(value: Value) -> HashMap<String, Value> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn field_rules_report_every_violation() {
+        let key = field(
+            "id: 01JRKEY0000000000000000000\nname: key\ntype:\n  kind: text\nrules:\n  required: true\n  pattern: '^[A-Z]+-[0-9]+$'\n  max_length: 6\n",
+        );
+        let estimate = field(
+            "id: 01JREST0000000000000000000\nname: estimate\ntype:\n  kind: number\nrules:\n  min: 1\n  max: 13\n",
+        );
+        let def = entity("name: task\nfields: [key, estimate]\n");
+        let defs = [&key, &estimate];
+
+        let found = check_rules(
+            &def,
+            &defs,
+            &fields(json!({"key": "abc-12345", "estimate": 20})),
+            None,
+        );
+        let rules: Vec<(&str, &str)> = found
+            .iter()
+            .map(|v| (v.field.as_str(), v.rule.as_str()))
+            .collect();
+        assert_eq!(
+            rules,
+            vec![
+                ("key", "pattern"),
+                ("key", "max_length"),
+                ("estimate", "max")
+            ]
+        );
+
+        let missing = check_rules(&def, &defs, &fields(json!({"key": " "})), None);
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].to_string(), "key: is required");
+
+        assert!(check_rules(
+            &def,
+            &defs,
+            &fields(json!({"key": "AB-1", "estimate": 3})),
+            None
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn invalid_patterns_fail_when_the_definition_is_read() {
+        let err = serde_yaml_ng::from_str::<FieldDef>(
+            "id: 01JRKEY0000000000000000000\nname: key\ntype:\n  kind: text\nrules:\n  pattern: '[A-Z'\n",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("invalid pattern [A-Z"), "{err}");
+
+        let key = field(
+            "id: 01JRKEY0000000000000000000\nname: key\ntype:\n  kind: text\nrules:\n  pattern: '^[A-Z]+$'\n",
+        );
+        assert_eq!(
+            key.rules
+                .as_ref()
+                .unwrap()
+                .pattern
+                .as_ref()
+                .unwrap()
+                .as_str(),
+            "^[A-Z]+$"
+        );
+        let yaml = serde_yaml_ng::to_string(&key).unwrap();
+        assert_eq!(serde_yaml_ng::from_str::<FieldDef>(&yaml).unwrap(), key);
+    }
+
+    #[test]
+    fn transitions_only_constrain_listed_values() {
+        let column = field(
+            "id: 01JRCOL0000000000000000000\nname: position_column\ntype:\n  kind: text\nrules:\n  transitions:\n    todo: [doing]\n  message: skip a step and the board lies\n",
+        );
+        let def = entity("name: task\nfields: [position_column]\n");
+        let defs = [&column];
+        let stored = fields(json!({"position_column": "todo"}));
+
+        let found = check_rules(
+            &def,
+            &defs,
+            &fields(json!({"position_column": "done"})),
+            Some(&stored),
+        );
+        assert_eq!(found[0].rule, "transition");
+        assert_eq!(found[0].message, "skip a step and the board lies");
+
+        for (from, to) in [("todo", "doing"), ("doing", "todo"), ("todo", "todo")] {
+            let stored = fields(json!({"position_column": from}));
+            let update = fields(json!({"position_column": to}));
+            assert!(check_rules(&def, &defs, &update, Some(&stored)).is_empty());
+        }
+        // Clearing a listed value is a transition too.
+        for cleared in [json!({"position_column": ""}), json!({})] {
+            let found = check_rules(&def, &defs, &fields(cleared), Some(&stored));
+            assert_eq!(found[0].rule, "transition");
+        }
+        // A new entity may start anywhere.
+        assert!(check_rules(
+            &def,
+            &defs,
+            &fields(json!({"position_column": "done"})),
+            None
+        )
+        .is_empty());
+        assert!(needs_previous(&defs));
+    }
+
+    #[test]
+    fn entity_rules_compare_fields() {
+        let def = entity(
+            "name: task\nfields: [due, scheduled]\nrules:\n  - field: due\n    not_before: scheduled\n  - field: title\n    required: true\n",
+        );
+
+        let found = check_rules(
+            &def,
+            &[],
+            &fields(json!({"title": "T", "scheduled": "2026-03-02", "due": "2026-03-01"})),
+            None,
+        );
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].field, "due");
+        assert_eq!(found[0].rule, "not_before");
+        assert!(found[0].message.contains("scheduled (2026-03-02)"));
+
+        let same_day =
+            fields(json!({"title": "T", "scheduled": "2026-03-02", "due": "2026-03-02"}));
+        assert!(check_rules(&def, &[], &same_day, None).is_empty());
+
+        let unscheduled = fields(json!({"due": "2026-03-01"}));
+        let found = check_rules(&def, &[], &unscheduled, None);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].field, "title");
+    }
+}
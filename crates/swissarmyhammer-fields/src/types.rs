@@ -7,9 +7,10 @@
 use serde::{Deserialize, Serialize};
 
 use crate::id_types::{EntityTypeName, FieldDefId, FieldName};
+use crate::rules::{EntityRule, FieldRules};
 
 /// Serde helper: skip serializing a bool field when it is `false`.
-fn is_false(b: &bool) -> bool {
+pub(crate) fn is_false(b: &bool) -> bool {
     !b
 }
 
@@ -86,8 +87,14 @@ pub enum FieldType {
     /// `depends_on` declares which entity types this aggregate depends on.
     /// When an entity of a listed type changes, the owning entity's computed
     /// field is recomputed and an `entity-field-changed` event is emitted.
+    ///
+    /// `derive: js` derives the value with the JavaScript in `script`
+    /// instead of a registered native function (see [`crate::compute`]).
     Computed {
         derive: String,
+        /// JS expression or function body for `derive: js`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        script: Option<String>,
         #[serde(default, skip_serializing_if = "Vec::is_empty")]
         depends_on: Vec<String>,
         /// Optional target entity type (e.g. "tag" for parse-body-tags).
@@ -153,6 +160,9 @@ pub struct FieldDef {
     /// Whether this field can be used as a group-by column in grid views.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub groupable: Option<bool>,
+    /// Declarative constraints checked on every write (see [`crate::rules`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules: Option<FieldRules>,
 }
 
 impl FieldDef {
@@ -267,6 +277,10 @@ pub struct EntityDef {
     pub sections: Vec<SectionDef>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub validate: Option<String>,
+    /// Cross-field constraints checked on every write, after the field
+    /// rules (see [`crate::rules`]).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<EntityRule>,
     /// Single-character prefix for mentions in markdown (e.g. "#" for tags, "@" for actors).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub mention_prefix: Option<String>,
@@ -343,6 +357,7 @@ mod tests {
     fn field_type_computed_yaml_round_trip() {
         let ft = FieldType::Computed {
             derive: "parse-body-tags".into(),
+            script: None,
             depends_on: vec![],
             entity: None,
             commit_display_names: false,
@@ -352,6 +367,17 @@ mod tests {
         assert_eq!(ft, parsed);
     }
 
+    #[test]
+    fn field_type_computed_js_script_from_yaml() {
+        let yaml = "kind: computed\nderive: js\nscript: fields.estimate * 2\n";
+        let parsed: FieldType = serde_yaml_ng::from_str(yaml).unwrap();
+        let FieldType::Computed { derive, script, .. } = parsed else {
+            panic!("expected Computed type");
+        };
+        assert_eq!(derive, "js");
+        assert_eq!(script.as_deref(), Some("fields.estimate * 2"));
+    }
+
     #[test]
     fn field_type_number_yaml_round_trip() {
         let ft = FieldType::Number {
@@ -443,6 +469,7 @@ type:
             placeholder: None,
             validate: None,
             groupable: Some(true),
+            rules: None,
         };
         let yaml = serde_yaml_ng::to_string(&field).unwrap();
         assert!(yaml.contains("groupable: true"));
@@ -467,6 +494,7 @@ type:
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         };
         let yaml = serde_yaml_ng::to_string(&field).unwrap();
         assert!(!yaml.contains("groupable"));
@@ -506,6 +534,7 @@ type:
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         };
         let yaml = serde_yaml_ng::to_string(&field).unwrap();
         let parsed: FieldDef = serde_yaml_ng::from_str(&yaml).unwrap();
@@ -529,6 +558,7 @@ type:
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         };
         let yaml = serde_yaml_ng::to_string(&field).unwrap();
         assert!(yaml.contains("type:"));
@@ -553,6 +583,7 @@ type:
             placeholder: Some("Add tags".into()),
             validate: None,
             groupable: None,
+            rules: None,
         };
         let yaml = serde_yaml_ng::to_string(&field_with_placeholder).unwrap();
         assert!(
@@ -579,6 +610,7 @@ type:
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         };
         let yaml = serde_yaml_ng::to_string(&field_without_placeholder).unwrap();
         assert!(
@@ -623,6 +655,7 @@ type:
             mention_slug_field: None,
             search_display_field: None,
             sections: vec![],
+            rules: Vec::new(),
         };
         let yaml = serde_yaml_ng::to_string(&entity).unwrap();
         let parsed: EntityDef = serde_yaml_ng::from_str(&yaml).unwrap();
@@ -642,6 +675,7 @@ type:
             mention_slug_field: None,
             search_display_field: None,
             sections: vec![],
+            rules: Vec::new(),
         };
         let yaml = serde_yaml_ng::to_string(&entity).unwrap();
         assert!(!yaml.contains("body_field"));
@@ -685,6 +719,7 @@ type:
             mention_display_field: None,
             mention_slug_field: None,
             search_display_field: None,
+            rules: Vec::new(),
         };
 
         let yaml = serde_yaml_ng::to_string(&entity).unwrap();
@@ -756,6 +791,7 @@ fields:
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         };
         assert_eq!(field.effective_editor(), "date");
         assert_eq!(field.effective_display(), "date");
@@ -778,6 +814,7 @@ fields:
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         };
         assert_eq!(field.effective_editor(), "none");
         assert_eq!(field.effective_display(), "badge");
@@ -791,6 +828,7 @@ fields:
             description: None,
             type_: FieldType::Computed {
                 derive: "parse-body-tags".into(),
+                script: None,
                 depends_on: vec![],
                 entity: None,
                 commit_display_names: false,
@@ -805,6 +843,7 @@ fields:
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         };
         assert_eq!(field.effective_editor(), "none");
         assert_eq!(field.effective_display(), "text");
@@ -830,6 +869,7 @@ fields:
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         };
         assert_eq!(single.effective_editor(), "select");
         assert_eq!(single.effective_display(), "badge");
@@ -852,6 +892,7 @@ fields:
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         };
         assert_eq!(multi.effective_editor(), "multi-select");
         assert_eq!(multi.effective_display(), "badge-list");
@@ -880,6 +921,7 @@ fields:
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         };
         assert_eq!(field.effective_editor(), "number");
         assert_eq!(field.effective_display(), "number");
@@ -1085,6 +1127,7 @@ fields:
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         };
         assert_eq!(field.effective_sort(), SortKind::Lexical);
     }
@@ -1106,6 +1149,7 @@ fields:
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         };
         assert_eq!(field.effective_sort(), SortKind::Datetime);
     }
@@ -1127,6 +1171,7 @@ fields:
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         };
         assert_eq!(field.effective_sort(), SortKind::Datetime);
     }
@@ -1151,6 +1196,7 @@ fields:
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         };
         assert_eq!(field.effective_sort(), SortKind::Numeric);
     }
@@ -1180,6 +1226,7 @@ fields:
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         };
         assert_eq!(field.effective_sort(), SortKind::OptionOrder);
 
@@ -1207,6 +1254,7 @@ fields:
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         };
         assert_eq!(multi.effective_sort(), SortKind::OptionOrder);
     }
@@ -1228,6 +1276,7 @@ fields:
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         };
         assert_eq!(field.effective_sort(), SortKind::Lexical);
     }
@@ -1250,6 +1299,7 @@ fields:
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         };
         assert_eq!(field.effective_sort(), SortKind::Lexical);
     }
@@ -1317,6 +1367,7 @@ fields:
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         }
     }
 
@@ -1347,6 +1398,7 @@ fields:
     fn effective_editor_computed_defaults_to_none() {
         let f = make_field(FieldType::Computed {
             derive: "count".into(),
+            script: None,
             depends_on: vec![],
             entity: None,
             commit_display_names: false,
@@ -1369,6 +1421,7 @@ fields:
     fn effective_display_computed_defaults_to_text() {
         let f = make_field(FieldType::Computed {
             derive: "count".into(),
+            script: None,
             depends_on: vec![],
             entity: None,
             commit_display_names: false,
@@ -1403,6 +1456,7 @@ fields:
     fn effective_sort_computed_defaults_to_lexical() {
         let f = make_field(FieldType::Computed {
             derive: "count".into(),
+            script: None,
             depends_on: vec![],
             entity: None,
             commit_display_names: false,
@@ -1501,6 +1555,7 @@ fields:
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         }
     }
 
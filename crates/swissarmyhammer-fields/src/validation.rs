@@ -280,6 +280,7 @@ mod tests {
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         }
     }
 
@@ -521,6 +522,7 @@ mod tests {
             mention_slug_field: None,
             search_display_field: None,
             sections: vec![],
+            rules: Vec::new(),
         };
         let yaml = serde_yaml_ng::to_string(&entity).unwrap();
         let parsed: EntityDef = serde_yaml_ng::from_str(&yaml).unwrap();
@@ -541,6 +543,7 @@ mod tests {
             mention_slug_field: None,
             search_display_field: None,
             sections: vec![],
+            rules: Vec::new(),
         };
         let mut fields = HashMap::new();
         fields.insert("title".to_string(), serde_json::json!("Hello"));
@@ -571,6 +574,7 @@ mod tests {
             mention_slug_field: None,
             search_display_field: None,
             sections: vec![],
+            rules: Vec::new(),
         };
         let mut fields = HashMap::new();
         fields.insert("title".to_string(), serde_json::json!("My Task"));
@@ -594,6 +598,7 @@ mod tests {
             mention_slug_field: None,
             search_display_field: None,
             sections: vec![],
+            rules: Vec::new(),
         };
         let mut fields = HashMap::new();
         fields.insert("title".to_string(), serde_json::json!("Test"));
@@ -674,6 +679,7 @@ mod tests {
             mention_slug_field: None,
             search_display_field: None,
             sections: vec![],
+            rules: Vec::new(),
         };
 
         let adversarial = r#"}})(); globalThis.__pwned3 = true; (function(){"#;
@@ -889,6 +895,7 @@ mod tests {
             mention_slug_field: None,
             search_display_field: None,
             sections: vec![],
+            rules: Vec::new(),
         };
         let mut fields = HashMap::new();
         fields.insert("title".to_string(), serde_json::json!("Test"));
@@ -921,6 +928,7 @@ mod tests {
             mention_slug_field: None,
             search_display_field: None,
             sections: vec![],
+            rules: Vec::new(),
         };
         let mut fields = HashMap::new();
         fields.insert("title".to_string(), serde_json::json!("Hello"));
@@ -951,6 +959,7 @@ mod tests {
             mention_slug_field: None,
             search_display_field: None,
             sections: vec![],
+            rules: Vec::new(),
         };
         let mut fields = HashMap::new();
         fields.insert("title".to_string(), serde_json::json!("My Task"));
@@ -1,4 +1,6 @@
-use crate::Expr;
+use crate::{CompareOp, Expr, Literal};
+use chrono::{Duration, Local, NaiveDate};
+use std::cmp::Ordering;
 
 /// Trait for providing entity data to the filter evaluator.
 ///
@@ -7,6 +9,8 @@ use crate::Expr;
 /// - `has_assignee("alice")` → entity is assigned to "alice"
 /// - `has_ref("01ABC")` → entity references card "01ABC" (via depends_on or id)
 /// - `has_project("auth")` → entity belongs to project "auth"
+/// - `field_values("due")` → the values field predicates such as
+///   `due < today` and `has:due` are evaluated against
 pub trait FilterContext {
     /// Returns true if the entity has the given tag (case-insensitive).
     fn has_tag(&self, tag: &str) -> bool;
@@ -19,6 +23,24 @@ pub trait FilterContext {
 
     /// Returns true if the entity belongs to the given project (case-insensitive).
     fn has_project(&self, project: &str) -> bool;
+
+    /// Returns the values of a named field as text, one entry per list item.
+    ///
+    /// A missing or empty field has no values. The default supports no
+    /// fields, so field predicates never match and `has:` is always false.
+    fn field_values(&self, _field: &str) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Returns true if the named field is present and non-empty.
+    fn has_field(&self, field: &str) -> bool {
+        !self.field_values(field).is_empty()
+    }
+
+    /// The date relative dates such as `today+7d` are counted from.
+    fn today(&self) -> NaiveDate {
+        Local::now().date_naive()
+    }
 }
 
 /// Evaluate a filter expression against a context.
@@ -28,12 +50,79 @@ pub(crate) fn evaluate(expr: &Expr, ctx: &dyn FilterContext) -> bool {
         Expr::Assignee(user) => ctx.has_assignee(user),
         Expr::Ref(id) => ctx.has_ref(id),
         Expr::Project(project) => ctx.has_project(project),
+        Expr::Field { field, op, value } => compare_field(ctx, field, *op, value),
+        Expr::Has(field) => ctx.has_field(field),
         Expr::And(lhs, rhs) => evaluate(lhs, ctx) && evaluate(rhs, ctx),
         Expr::Or(lhs, rhs) => evaluate(lhs, ctx) || evaluate(rhs, ctx),
         Expr::Not(inner) => !evaluate(inner, ctx),
     }
 }
 
+/// Evaluate a field predicate.
+///
+/// A list field matches when any of its values does; `!=` is the negation of
+/// `=`, so it also matches a field with no values.
+fn compare_field(ctx: &dyn FilterContext, field: &str, op: CompareOp, value: &Literal) -> bool {
+    let values = ctx.field_values(field);
+    if op == CompareOp::Ne {
+        return !values
+            .iter()
+            .any(|v| compare_value(ctx, v, CompareOp::Eq, value));
+    }
+    values.iter().any(|v| compare_value(ctx, v, op, value))
+}
+
+/// Compare one stored value against a literal. A value that cannot be read
+/// as the literal's type (e.g. text compared with a date) does not match.
+fn compare_value(ctx: &dyn FilterContext, stored: &str, op: CompareOp, value: &Literal) -> bool {
+    let ordering = match value {
+        Literal::Text(text) => {
+            let stored = stored.to_lowercase();
+            let text = text.to_lowercase();
+            if op == CompareOp::Contains {
+                return stored.contains(&text);
+            }
+            stored.cmp(&text)
+        }
+        Literal::Number(n) | Literal::Percent(n) => {
+            match parse_number(stored).and_then(|s| s.partial_cmp(n)) {
+                Some(ordering) => ordering,
+                None => return false,
+            }
+        }
+        Literal::Date(date) => match parse_date(stored) {
+            Some(stored) => stored.cmp(date),
+            None => return false,
+        },
+        Literal::RelativeDate(days) => {
+            let date = Duration::try_days(*days).and_then(|d| ctx.today().checked_add_signed(d));
+            match (parse_date(stored), date) {
+                (Some(stored), Some(date)) => stored.cmp(&date),
+                _ => return false,
+            }
+        }
+    };
+    match op {
+        CompareOp::Eq | CompareOp::Contains => ordering == Ordering::Equal,
+        CompareOp::Ne => ordering != Ordering::Equal,
+        CompareOp::Lt => ordering == Ordering::Less,
+        CompareOp::Le => ordering != Ordering::Greater,
+        CompareOp::Gt => ordering == Ordering::Greater,
+        CompareOp::Ge => ordering != Ordering::Less,
+    }
+}
+
+/// Read a stored value as a number, allowing a trailing `%`.
+fn parse_number(stored: &str) -> Option<f64> {
+    stored.trim().trim_end_matches('%').trim().parse().ok()
+}
+
+/// Read the date part of a stored `YYYY-MM-DD` or RFC 3339 timestamp.
+fn parse_date(stored: &str) -> Option<NaiveDate> {
+    let date = stored.trim().get(..10)?;
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -218,4 +307,135 @@ mod tests {
         assert!(!evaluate(&expr, &mock(&["bug", "done"], &[], &[], &[])));
         assert!(!evaluate(&expr, &mock(&["docs"], &[], &[], &[])));
     }
+
+    /// Context with named fields and a fixed "today".
+    struct FieldCtx {
+        fields: Vec<(&'static str, &'static str)>,
+    }
+
+    impl FilterContext for FieldCtx {
+        fn has_tag(&self, _tag: &str) -> bool {
+            false
+        }
+        fn has_assignee(&self, _user: &str) -> bool {
+            false
+        }
+        fn has_ref(&self, _id: &str) -> bool {
+            false
+        }
+        fn has_project(&self, _project: &str) -> bool {
+            false
+        }
+        fn field_values(&self, field: &str) -> Vec<String> {
+            self.fields
+                .iter()
+                .filter(|(name, _)| *name == field)
+                .map(|(_, value)| value.to_string())
+                .collect()
+        }
+        fn today(&self) -> NaiveDate {
+            NaiveDate::from_ymd_opt(2026, 3, 1).unwrap()
+        }
+    }
+
+    fn field(field: &str, op: CompareOp, value: Literal) -> Expr {
+        Expr::Field {
+            field: field.into(),
+            op,
+            value,
+        }
+    }
+
+    #[test]
+    fn field_text_equality_over_list_values() {
+        let ctx = FieldCtx {
+            fields: vec![("tags", "bug"), ("tags", "UI")],
+        };
+        let ui = Literal::Text("ui".into());
+        assert!(evaluate(&field("tags", CompareOp::Eq, ui.clone()), &ctx));
+        assert!(!evaluate(&field("tags", CompareOp::Ne, ui), &ctx));
+        let docs = Literal::Text("docs".into());
+        assert!(evaluate(&field("tags", CompareOp::Ne, docs.clone()), &ctx));
+        // A missing field is unequal to everything and has no ordering.
+        assert!(evaluate(
+            &field("column", CompareOp::Ne, docs.clone()),
+            &ctx
+        ));
+        assert!(!evaluate(&field("column", CompareOp::Lt, docs), &ctx));
+    }
+
+    #[test]
+    fn field_contains_is_case_insensitive() {
+        let ctx = FieldCtx {
+            fields: vec![("title", "Fix OAuth login")],
+        };
+        let expr = field("title", CompareOp::Contains, Literal::Text("auth".into()));
+        assert!(evaluate(&expr, &ctx));
+        let expr = field("title", CompareOp::Contains, Literal::Text("signup".into()));
+        assert!(!evaluate(&expr, &ctx));
+    }
+
+    #[test]
+    fn field_numbers_and_percentages() {
+        let ctx = FieldCtx {
+            fields: vec![("progress", "75"), ("points", "3")],
+        };
+        assert!(evaluate(
+            &field("progress", CompareOp::Ge, Literal::Percent(50.0)),
+            &ctx
+        ));
+        assert!(!evaluate(
+            &field("progress", CompareOp::Lt, Literal::Percent(75.0)),
+            &ctx
+        ));
+        assert!(evaluate(
+            &field("points", CompareOp::Le, Literal::Number(3.0)),
+            &ctx
+        ));
+        // Text that is not a number never matches a numeric comparison.
+        let ctx = FieldCtx {
+            fields: vec![("points", "many")],
+        };
+        assert!(!evaluate(
+            &field("points", CompareOp::Gt, Literal::Number(0.0)),
+            &ctx
+        ));
+    }
+
+    #[test]
+    fn field_dates_absolute_and_relative() {
+        let ctx = FieldCtx {
+            fields: vec![("due", "2026-03-05"), ("updated", "2026-02-20T10:00:00Z")],
+        };
+        assert!(evaluate(
+            &field("due", CompareOp::Lt, Literal::RelativeDate(7)),
+            &ctx
+        ));
+        assert!(!evaluate(
+            &field("due", CompareOp::Lt, Literal::RelativeDate(0)),
+            &ctx
+        ));
+        let date = NaiveDate::from_ymd_opt(2026, 2, 20).unwrap();
+        assert!(evaluate(
+            &field("updated", CompareOp::Eq, Literal::Date(date)),
+            &ctx
+        ));
+    }
+
+    #[test]
+    fn has_field_and_default_field_support() {
+        let ctx = FieldCtx {
+            fields: vec![("attachments", "a.png")],
+        };
+        assert!(evaluate(&Expr::Has("attachments".into()), &ctx));
+        assert!(!evaluate(&Expr::Has("due".into()), &ctx));
+
+        // Contexts that don't override `field_values` have no fields.
+        let ctx = mock(&["bug"], &[], &[], &[]);
+        assert!(!evaluate(&Expr::Has("tags".into()), &ctx));
+        assert!(!evaluate(
+            &field("tags", CompareOp::Eq, Literal::Text("bug".into())),
+            &ctx
+        ));
+    }
 }
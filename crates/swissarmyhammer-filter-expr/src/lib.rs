@@ -5,6 +5,11 @@
 //! - `@user` — match entities assigned to a user
 //! - `^ref` — match entities referencing a card ID
 //! - `$project` — match entities belonging to a project
+//! - `field op value` — compare a field: `=`, `!=`, `<`, `<=`, `>`, `>=`, or
+//!   `~` (contains). Values are quoted text, bare words, numbers, percentages
+//!   (`50%`), dates (`2026-03-01`) or dates relative to today (`today+7d`,
+//!   `today-2w`, `tomorrow`)
+//! - `has:field` — match entities whose field is present and non-empty
 //! - `&&` / `and` — boolean AND
 //! - `||` / `or` — boolean OR
 //! - `!` / `not` — boolean NOT
@@ -14,9 +19,44 @@
 mod eval;
 mod parser;
 
+pub use chrono::NaiveDate;
 pub use eval::FilterContext;
 pub use parser::ParseError;
 
+/// Comparison operator of a field predicate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    /// `=` (or `==`)
+    Eq,
+    /// `!=`
+    Ne,
+    /// `<`
+    Lt,
+    /// `<=`
+    Le,
+    /// `>`
+    Gt,
+    /// `>=`
+    Ge,
+    /// `~` — case-insensitive substring match.
+    Contains,
+}
+
+/// Right-hand side of a field predicate.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    /// Text, compared case-insensitively (a quoted string or a bare word).
+    Text(String),
+    /// A number such as `3` or `2.5`.
+    Number(f64),
+    /// A percentage such as `50%`, compared against values on a 0–100 scale.
+    Percent(f64),
+    /// A calendar date (`YYYY-MM-DD`).
+    Date(NaiveDate),
+    /// A date relative to today, as an offset in days (`today+7d` is `7`).
+    RelativeDate(i64),
+}
+
 /// A parsed filter expression AST node.
 #[derive(Debug, Clone, PartialEq)]
 pub enum Expr {
@@ -28,6 +68,14 @@ pub enum Expr {
     Ref(String),
     /// Matches entities belonging to a project (e.g. `$auth-migration`).
     Project(String),
+    /// Compares a field against a value (e.g. `due < today+7d`).
+    Field {
+        field: String,
+        op: CompareOp,
+        value: Literal,
+    },
+    /// Matches entities whose field is present and non-empty (e.g. `has:attachments`).
+    Has(String),
     /// Both sub-expressions must match.
     And(Box<Expr>, Box<Expr>),
     /// Either sub-expression must match.
@@ -1,6 +1,6 @@
 use chumsky::prelude::*;
 
-use crate::Expr;
+use crate::{CompareOp, Expr, Literal, NaiveDate};
 
 /// A parse error with span information.
 #[derive(Debug, Clone)]
@@ -32,6 +32,78 @@ fn is_body_char(c: &char) -> bool {
     !c.is_whitespace() && !"#@^$()&|!".contains(*c)
 }
 
+/// Returns true if `c` can appear in a bare (unquoted) predicate value.
+fn is_value_char(c: &char) -> bool {
+    !c.is_whitespace() && !"()&|!\"".contains(*c)
+}
+
+/// Classify the bare value of a field predicate.
+///
+/// Dates, relative dates, percentages and numbers are recognised by shape;
+/// anything else is text. A value that has the shape of a date or
+/// percentage but does not parse is an error rather than silently text.
+fn classify_value(raw: &str) -> Result<Literal, String> {
+    let lower = raw.to_ascii_lowercase();
+    for (word, base) in [("today", 0), ("tomorrow", 1), ("yesterday", -1)] {
+        let Some(rest) = lower.strip_prefix(word) else {
+            continue;
+        };
+        if rest.is_empty() {
+            return Ok(Literal::RelativeDate(base));
+        }
+        if !rest.starts_with(['+', '-']) {
+            break;
+        }
+        let days_per_unit = match rest.chars().last() {
+            Some('d') => 1,
+            Some('w') => 7,
+            _ => 0,
+        };
+        let days = rest[..rest.len() - 1]
+            .parse::<i64>()
+            .ok()
+            .filter(|_| days_per_unit > 0)
+            .and_then(|n| n.checked_mul(days_per_unit))
+            .and_then(|n| n.checked_add(base));
+        return match days {
+            Some(days) => Ok(Literal::RelativeDate(days)),
+            None => Err(format!(
+                "invalid relative date '{raw}' (expected e.g. {word}+7d or {word}-2w)"
+            )),
+        };
+    }
+
+    let bytes = raw.as_bytes();
+    let date_shaped = bytes.len() == 10
+        && bytes[4] == b'-'
+        && bytes[7] == b'-'
+        && bytes
+            .iter()
+            .enumerate()
+            .all(|(i, b)| i == 4 || i == 7 || b.is_ascii_digit());
+    if date_shaped {
+        return NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+            .map(Literal::Date)
+            .map_err(|_| format!("invalid date '{raw}'"));
+    }
+
+    if let Some(number) = raw.strip_suffix('%') {
+        return number
+            .parse::<f64>()
+            .ok()
+            .filter(|n| n.is_finite())
+            .map(Literal::Percent)
+            .ok_or_else(|| format!("invalid percentage '{raw}'"));
+    }
+
+    let numeric = raw.chars().any(|c| c.is_ascii_digit())
+        && raw.chars().all(|c| c.is_ascii_digit() || ".+-".contains(c));
+    if let (true, Ok(n)) = (numeric, raw.parse::<f64>()) {
+        return Ok(Literal::Number(n));
+    }
+    Ok(Literal::Text(raw.to_string()))
+}
+
 /// Returns true if `c` cannot follow a keyword (word boundary lookahead).
 fn is_keyword_boundary(c: &char) -> bool {
     !c.is_alphanumeric() && *c != '_'
@@ -59,6 +131,75 @@ fn binary_op<'src>(
     choice((just(symbol).to(()), keyword(lower), keyword(upper))).padded()
 }
 
+/// Build the parser for field predicates: `field op value` and `has:field`.
+///
+/// Quoted values are always text, as is the value of a `~` (contains)
+/// comparison. Bare values are classified by [`classify_value`]; a
+/// malformed date, relative date or percentage is reported with the value's
+/// span.
+fn field_predicate<'src>(
+) -> impl Parser<'src, &'src str, Expr, extra::Err<Rich<'src, char>>> + Clone {
+    let ident = any()
+        .filter(|c: &char| c.is_ascii_alphabetic() || *c == '_')
+        .then(
+            any()
+                .filter(|c: &char| c.is_ascii_alphanumeric() || *c == '_' || *c == '-')
+                .repeated(),
+        )
+        .to_slice()
+        .map(|s: &str| s.to_string());
+
+    let has = just("has:").ignore_then(ident.clone()).map(Expr::Has);
+
+    let op = choice((
+        just("<=").to(CompareOp::Le),
+        just(">=").to(CompareOp::Ge),
+        just("!=").to(CompareOp::Ne),
+        just("==").to(CompareOp::Eq),
+        just('<').to(CompareOp::Lt),
+        just('>').to(CompareOp::Gt),
+        just('=').to(CompareOp::Eq),
+        just('~').to(CompareOp::Contains),
+    ))
+    .padded();
+
+    let quoted = just('"')
+        .ignore_then(any().filter(|c: &char| *c != '"').repeated().to_slice())
+        .then_ignore(just('"'))
+        .map(|s: &str| (s.to_string(), true));
+    let bare = any()
+        .filter(is_value_char)
+        .repeated()
+        .at_least(1)
+        .to_slice()
+        .map(|s: &str| (s.to_string(), false));
+    let value = choice((quoted, bare)).map_with(|value, e| (value, e.span()));
+
+    // `validate` rather than `try_map`: the error is emitted alongside the
+    // parsed predicate, so it keeps the value's span instead of losing out
+    // to whichever alternative got furthest.
+    let compare =
+        ident
+            .then(op)
+            .then(value)
+            .validate(|((field, op), ((raw, quoted), span)), _, emitter| {
+                let value = if quoted || op == CompareOp::Contains {
+                    Literal::Text(raw)
+                } else {
+                    match classify_value(&raw) {
+                        Ok(value) => value,
+                        Err(message) => {
+                            emitter.emit(Rich::custom(span, message));
+                            Literal::Text(raw)
+                        }
+                    }
+                };
+                Expr::Field { field, op, value }
+            });
+
+    choice((has, compare))
+}
+
 /// Build the atom and NOT-expression parsers (highest precedence layer).
 fn atom_and_not<'src>(
     expr: impl Parser<'src, &'src str, Expr, extra::Err<Rich<'src, char>>> + Clone,
@@ -78,7 +219,7 @@ fn atom_and_not<'src>(
         .ignore_then(body)
         .map(|s: &str| Expr::Project(s.to_string()));
     let group = expr.delimited_by(just('(').padded(), just(')').padded());
-    let atom = choice((tag, mention, reference, project, group)).padded();
+    let atom = choice((tag, mention, reference, project, field_predicate(), group)).padded();
 
     let not_op = choice((just('!').to(()), keyword("not"), keyword("NOT"))).padded();
     not_op
@@ -94,8 +235,12 @@ fn atom_and_not<'src>(
 /// or_expr   = and_expr (("||" | "or" | "OR") and_expr)*
 /// and_expr  = not_expr (("&&" | "and" | "AND")? not_expr)*   // implicit AND
 /// not_expr  = ("!" | "not" | "NOT") not_expr | atom
-/// atom      = "#" body | "@" body | "^" body | "$" body | "(" expr ")"
+/// atom      = "#" body | "@" body | "^" body | "$" body
+///           | "has:" ident | ident op value | "(" expr ")"
 /// body      = [^ \t\n\r#@^$()&|!]+
+/// ident     = [A-Za-z_][A-Za-z0-9_-]*
+/// op        = "=" | "==" | "!=" | "<" | "<=" | ">" | ">=" | "~"
+/// value     = '"' [^"]* '"' | [^ \t\n\r()&|!"]+
 /// ```
 fn filter_parser<'src>() -> impl Parser<'src, &'src str, Expr, extra::Err<Rich<'src, char>>> {
     recursive(|expr| {
@@ -456,6 +601,129 @@ mod tests {
         );
     }
 
+    // ── Field predicates ────────────────────────────────────────────
+
+    fn field(field: &str, op: CompareOp, value: Literal) -> Expr {
+        Expr::Field {
+            field: field.into(),
+            op,
+            value,
+        }
+    }
+
+    #[test]
+    fn field_comparison_operators() {
+        let doing = || Literal::Text("doing".into());
+        for (input, op) in [
+            ("column = doing", CompareOp::Eq),
+            ("column == doing", CompareOp::Eq),
+            ("column != doing", CompareOp::Ne),
+            ("column < doing", CompareOp::Lt),
+            ("column <= doing", CompareOp::Le),
+            ("column > doing", CompareOp::Gt),
+            ("column >= doing", CompareOp::Ge),
+            ("column~doing", CompareOp::Contains),
+        ] {
+            assert_eq!(
+                parse(input).unwrap(),
+                field("column", op, doing()),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn field_value_kinds() {
+        let date = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        for (input, value) in [
+            ("due < today", Literal::RelativeDate(0)),
+            ("due < today+7d", Literal::RelativeDate(7)),
+            ("due < TODAY-2w", Literal::RelativeDate(-14)),
+            ("due < tomorrow", Literal::RelativeDate(1)),
+            ("due < yesterday+1d", Literal::RelativeDate(0)),
+            ("due < 2026-03-01", Literal::Date(date)),
+            ("due < 50%", Literal::Percent(50.0)),
+            ("due < 2.5", Literal::Number(2.5)),
+            ("due < \"2026-03-01\"", Literal::Text("2026-03-01".into())),
+            ("due < todayish", Literal::Text("todayish".into())),
+        ] {
+            assert_eq!(
+                parse(input).unwrap(),
+                field("due", CompareOp::Lt, value),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn field_contains_keeps_value_as_text() {
+        assert_eq!(
+            parse("title ~ \"log in\"").unwrap(),
+            field("title", CompareOp::Contains, Literal::Text("log in".into()))
+        );
+        assert_eq!(
+            parse("title ~ 2026-03-01").unwrap(),
+            field(
+                "title",
+                CompareOp::Contains,
+                Literal::Text("2026-03-01".into())
+            )
+        );
+    }
+
+    #[test]
+    fn has_atom() {
+        assert_eq!(
+            parse("has:attachments").unwrap(),
+            Expr::Has("attachments".into())
+        );
+    }
+
+    #[test]
+    fn field_predicates_combine_with_atoms() {
+        assert_eq!(
+            parse("#bug progress >= 50% and not has:due").unwrap(),
+            Expr::And(
+                Box::new(Expr::And(
+                    Box::new(Expr::Tag("bug".into())),
+                    Box::new(field("progress", CompareOp::Ge, Literal::Percent(50.0))),
+                )),
+                Box::new(Expr::Not(Box::new(Expr::Has("due".into())))),
+            )
+        );
+        assert_eq!(
+            parse("(column = todo || column = doing)").unwrap(),
+            Expr::Or(
+                Box::new(field("column", CompareOp::Eq, Literal::Text("todo".into()))),
+                Box::new(field(
+                    "column",
+                    CompareOp::Eq,
+                    Literal::Text("doing".into())
+                )),
+            )
+        );
+    }
+
+    #[test]
+    fn error_invalid_field_value_has_value_span() {
+        for (input, span, message) in [
+            ("due < 2026-13-01", 6..16, "invalid date"),
+            ("due < today+7x", 6..14, "invalid relative date"),
+            ("progress >= abc%", 12..16, "invalid percentage"),
+        ] {
+            let errors = parse(input).unwrap_err();
+            assert_eq!(errors.len(), 1, "{input}");
+            assert_eq!(errors[0].span, span, "{input}");
+            assert!(errors[0].message.contains(message), "{input}: {errors:?}");
+        }
+    }
+
+    #[test]
+    fn error_field_without_value() {
+        assert!(parse("due <").is_err());
+        assert!(parse("has:").is_err());
+    }
+
     // ── Error cases ─────────────────────────────────────────────────
 
     #[test]
@@ -11,6 +11,9 @@
 //! - **Process-Global State**: Single Runtime shared by all components
 //! - **In-Memory Only**: No persistence, state is lost when process terminates
 //! - **Auto-Capture**: After `set()`, new/modified JS globals are captured back into tracked context
+//! - **Sandbox**: `eval_sandboxed()` runs untrusted code in a runtime of its own,
+//!   in a fresh context per call with no env and no tracked variables, under a
+//!   deadline
 //!
 //! # Example
 //!
@@ -47,7 +50,8 @@ pub use swissarmyhammer_operations::{Execute, Operation, OperationProcessor};
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use std::path::PathBuf;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::sync::oneshot;
 
 /// Request types sent to the JS worker thread
@@ -68,6 +72,11 @@ enum JsRequest {
         path: PathBuf,
         reply: oneshot::Sender<Result<(), String>>,
     },
+    EvalSandboxed {
+        expression: String,
+        timeout: Duration,
+        reply: oneshot::Sender<Result<serde_json::Value, String>>,
+    },
 }
 
 /// Module resolver that sandboxes imports to a base directory.
@@ -149,6 +158,27 @@ impl JsWorker {
 
         let ctx = Context::full(&rt).expect("Failed to create JS context");
 
+        // Untrusted code runs in a runtime of its own, so it cannot use up the
+        // main runtime's memory, and in a fresh context per evaluation, so no
+        // evaluation sees the globals another one left behind.
+        let sandbox_rt = Runtime::new().expect("Failed to create JS sandbox runtime");
+        sandbox_rt.set_memory_limit(10 * 1024 * 1024); // 10 MB
+        sandbox_rt.set_max_stack_size(512 * 1024); // 512 KB
+
+        // QuickJS polls the interrupt handler while running code; a deadline
+        // set for the current request stops runaway scripts.
+        let deadline: Arc<Mutex<Option<Instant>>> = Arc::new(Mutex::new(None));
+        {
+            let deadline = Arc::clone(&deadline);
+            sandbox_rt.set_interrupt_handler(Some(Box::new(move || {
+                deadline
+                    .lock()
+                    .ok()
+                    .and_then(|d| *d)
+                    .is_some_and(|d| Instant::now() >= d)
+            })));
+        }
+
         /// Drain all pending microtasks/Promise jobs from the runtime.
         fn drain_pending_jobs(rt: &Runtime) {
             loop {
@@ -306,6 +336,54 @@ impl JsWorker {
                     rt.set_loader(resolver, loader);
                     let _ = reply.send(Ok(()));
                 }
+
+                JsRequest::EvalSandboxed {
+                    expression,
+                    timeout,
+                    reply,
+                } => {
+                    let started = Instant::now();
+                    if let Ok(mut d) = deadline.lock() {
+                        *d = Some(started + timeout);
+                    }
+                    let sandbox = Context::full(&sandbox_rt);
+                    let result = match &sandbox {
+                        Ok(sandbox) => sandbox.with(|ctx| {
+                            let eval_result: rquickjs::Value = ctx
+                                .eval(expression.as_bytes())
+                                .catch(&ctx)
+                                .map_err(|e| match e {
+                                    _ if started.elapsed() >= timeout => {
+                                        format!("timed out after {}ms", timeout.as_millis())
+                                    }
+                                    CaughtError::Exception(ex) => {
+                                        format!("JS error: {}", ex)
+                                    }
+                                    CaughtError::Value(v) => {
+                                        let s: std::result::Result<String, _> = v.get();
+                                        format!(
+                                            "JS threw: {}",
+                                            s.unwrap_or_else(|_| "unknown".to_string())
+                                        )
+                                    }
+                                    CaughtError::Error(e) => format!("Error: {}", e),
+                                })?;
+
+                            bridge::js_to_json(&ctx, eval_result).map_err(|e| e.to_string())
+                        }),
+                        Err(e) => Err(format!("Failed to create JS sandbox context: {}", e)),
+                    };
+                    // Jobs the code queued run under the same deadline.
+                    drain_pending_jobs(&sandbox_rt);
+                    if let Ok(mut d) = deadline.lock() {
+                        *d = None;
+                    }
+                    // Nothing of this evaluation outlives it.
+                    drop(sandbox);
+                    sandbox_rt.run_gc();
+
+                    let _ = reply.send(result);
+                }
             }
         }
 
@@ -414,6 +492,37 @@ impl JsState {
             .map_err(|_| "JS worker did not respond".to_string())?
     }
 
+    /// Evaluate untrusted JS in the sandbox and return the result.
+    ///
+    /// The sandbox is a runtime of its own, with its own memory limit, and
+    /// each call gets a fresh context: `env`, `process.env`, the tracked
+    /// variables and whatever an earlier call defined are not visible, and
+    /// nothing the code defines is captured. Evaluation, and any jobs it
+    /// queues, are interrupted once `timeout` has passed. Promises are not
+    /// awaited.
+    ///
+    /// # Arguments
+    ///
+    /// * `expression` - JavaScript expression to evaluate
+    /// * `timeout` - Longest the evaluation may run
+    ///
+    /// # Returns
+    ///
+    /// The evaluated result as a JSON value, or an error string
+    pub async fn eval_sandboxed(
+        &self,
+        expression: &str,
+        timeout: Duration,
+    ) -> Result<serde_json::Value, String> {
+        let expression = expression.to_string();
+        self.send_request(|reply| JsRequest::EvalSandboxed {
+            expression,
+            timeout,
+            reply,
+        })
+        .await
+    }
+
     /// Get all tracked variables as a HashMap
     ///
     /// Used by workflow context stacking to copy global variables
@@ -596,6 +705,78 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_sandbox_is_isolated_from_globals() {
+        let state = JsState::global();
+        let _ = state.set("sandbox_secret", "'hidden'").await;
+
+        let result = state
+            .eval_sandboxed(
+                "typeof env + ' ' + typeof sandbox_secret",
+                Duration::from_secs(1),
+            )
+            .await;
+        assert_eq!(result.unwrap(), serde_json::json!("undefined undefined"));
+
+        let _ = state
+            .eval_sandboxed("globalThis.leaked = 1", Duration::from_secs(1))
+            .await;
+        let vars = state.get_all_variables().await.unwrap();
+        assert!(!vars.contains_key("leaked"));
+    }
+
+    #[tokio::test]
+    async fn test_sandbox_evaluations_do_not_share_globals() {
+        let state = JsState::global();
+
+        let _ = state
+            .eval_sandboxed(
+                "globalThis.carried = 1; Array.prototype.tainted = 1",
+                Duration::from_secs(1),
+            )
+            .await;
+        let result = state
+            .eval_sandboxed(
+                "typeof carried + ' ' + typeof [].tainted",
+                Duration::from_secs(1),
+            )
+            .await;
+        assert_eq!(result.unwrap(), serde_json::json!("undefined undefined"));
+    }
+
+    #[tokio::test]
+    async fn test_sandbox_memory_does_not_starve_the_main_context() {
+        let state = JsState::global();
+
+        let result = state
+            .eval_sandboxed(
+                "const a = []; while (true) a.push('x'.repeat(4096));",
+                Duration::from_secs(5),
+            )
+            .await;
+        assert!(result.is_err());
+
+        let result = state.get("[1, 2, 3].map(n => n * 2)").await;
+        assert_eq!(result.unwrap(), serde_json::json!([2, 4, 6]));
+        let result = state.eval_sandboxed("6 * 7", Duration::from_secs(1)).await;
+        assert_eq!(result.unwrap(), serde_json::json!(42));
+    }
+
+    #[tokio::test]
+    async fn test_sandbox_interrupts_runaway_code() {
+        let state = JsState::global();
+
+        let err = state
+            .eval_sandboxed("while (true) {}", Duration::from_millis(50))
+            .await
+            .unwrap_err();
+        assert!(err.contains("timed out"), "Got: {}", err);
+
+        // The worker keeps serving requests, without a deadline.
+        let result = state.get("1 + 1").await;
+        assert_eq!(result.unwrap(), serde_json::json!(2));
+    }
+
     #[tokio::test]
     async fn test_promise_resolve_chain() {
         let state = JsState::global();
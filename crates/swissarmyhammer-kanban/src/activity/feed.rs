@@ -0,0 +1,390 @@
+//! Read, filter and describe changelog entries across the board.
+
+use crate::error::{KanbanError, Result};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use swissarmyhammer_entity::changelog::{read_changelog_for, ChangeEntry, FieldChange};
+use swissarmyhammer_entity::{EntityContext, EntityId};
+
+/// Longest value shown in a change summary before it is cut short.
+const MAX_SHOWN_CHARS: usize = 80;
+
+/// Which changelog entries make it into the feed.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ActivityQuery {
+    pub changed_by: Option<String>,
+    pub entity_type: Option<String>,
+    pub field: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl ActivityQuery {
+    /// Apply the query to one entry: `None` when it is filtered out,
+    /// otherwise the entry, with its changes narrowed to `field` if one is set.
+    fn apply(&self, mut entry: ChangeEntry) -> Option<ChangeEntry> {
+        if let Some(actor) = &self.changed_by {
+            if !entry
+                .actor
+                .as_deref()
+                .is_some_and(|a| a.eq_ignore_ascii_case(actor))
+            {
+                return None;
+            }
+        }
+        if self.since.is_some_and(|since| entry.timestamp < since)
+            || self.until.is_some_and(|until| entry.timestamp >= until)
+        {
+            return None;
+        }
+        if let Some(field) = &self.field {
+            entry.changes.retain(|(name, _)| name == field);
+            if entry.changes.is_empty() {
+                return None;
+            }
+        }
+        Some(entry)
+    }
+}
+
+/// One feed entry: a changelog entry plus the entity's display name.
+#[derive(Debug)]
+pub(crate) struct ActivityItem {
+    pub entry: ChangeEntry,
+    /// Latest title or name the entity's changelog records, if any.
+    pub name: Option<String>,
+}
+
+/// Read every changelog of the board's entity types and return the entries
+/// matching `query`, newest first.
+///
+/// Trashed and archived changelogs are included, so deletions stay visible.
+/// A changelog that cannot be replayed is skipped with a warning rather than
+/// failing the whole feed.
+pub(crate) async fn collect(
+    ectx: &EntityContext,
+    query: &ActivityQuery,
+) -> Result<Vec<ActivityItem>> {
+    let mut items = Vec::new();
+    let mut seen = HashSet::new();
+
+    for def in ectx.fields().all_entities() {
+        if let Some(entity_type) = &query.entity_type {
+            if !def.name.as_str().eq_ignore_ascii_case(entity_type) {
+                continue;
+            }
+        }
+        let dir = ectx.entity_dir(&def.name);
+        for location in [dir.clone(), dir.join(".trash"), dir.join(".archive")] {
+            for path in changelog_files(&location).await? {
+                let entries = match read_changelog_for(&def.name, def, &path).await {
+                    Ok(entries) => entries,
+                    Err(error) => {
+                        tracing::warn!(path = %path.display(), %error, "skipping unreadable changelog");
+                        continue;
+                    }
+                };
+                let name = latest_name(&entries);
+                // Trashed/archived files are named `{id}.{entry}.jsonl`.
+                let file_id = path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .and_then(|n| n.split('.').next())
+                    .unwrap_or_default();
+                for mut entry in entries {
+                    if !seen.insert(entry.id.clone()) {
+                        continue;
+                    }
+                    if entry.entity_id.as_str().is_empty() {
+                        entry.entity_id = EntityId::from(file_id);
+                    }
+                    if entry.entity_type.as_str().is_empty() {
+                        entry.entity_type = def.name.clone();
+                    }
+                    if let Some(entry) = query.apply(entry) {
+                        items.push(ActivityItem {
+                            entry,
+                            name: name.clone(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    items.sort_by(|a, b| b.entry.timestamp.cmp(&a.entry.timestamp));
+    Ok(items)
+}
+
+/// The `.jsonl` files directly inside `dir`, sorted; none if it doesn't exist.
+async fn changelog_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut read_dir = match tokio::fs::read_dir(dir).await {
+        Ok(read_dir) => read_dir,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut files = Vec::new();
+    while let Some(item) = read_dir.next_entry().await? {
+        let path = item.path();
+        if path.extension().is_some_and(|ext| ext == "jsonl") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// The most recent `title` or `name` value recorded in an entity's changelog.
+fn latest_name(entries: &[ChangeEntry]) -> Option<String> {
+    let mut name = None;
+    for entry in entries {
+        for (field, change) in &entry.changes {
+            if field != "title" && field != "name" {
+                continue;
+            }
+            match change {
+                FieldChange::Set { value }
+                | FieldChange::Changed {
+                    new_value: value, ..
+                } => {
+                    name = value.as_str().map(str::to_string);
+                }
+                FieldChange::TextDiff { forward_patch, .. } => {
+                    let lines = PatchLines::parse(forward_patch);
+                    if let [added] = &lines.added[..] {
+                        name = Some(added.clone());
+                    }
+                }
+                FieldChange::Removed { .. } => {}
+            }
+        }
+    }
+    name
+}
+
+/// Render a feed item as JSON with readable change summaries.
+pub(crate) fn render(item: &ActivityItem) -> Value {
+    let entry = &item.entry;
+    let changes: Vec<Value> = entry
+        .changes
+        .iter()
+        .map(|(field, change)| describe_change(field, change))
+        .collect();
+    let mut value = json!({
+        "id": entry.id.as_str(),
+        "timestamp": entry.timestamp.to_rfc3339(),
+        "op": entry.op,
+        "entity_type": entry.entity_type.as_str(),
+        "entity_id": entry.entity_id.as_str(),
+        "changes": changes,
+    });
+    if let Some(actor) = &entry.actor {
+        value["actor"] = json!(actor);
+    }
+    if let Some(name) = &item.name {
+        value["name"] = json!(name);
+    }
+    value
+}
+
+/// Describe one field change: a one-line `summary`, plus the changed lines
+/// as `diff` for multi-line text edits.
+fn describe_change(field: &str, change: &FieldChange) -> Value {
+    match change {
+        FieldChange::Set { value } => {
+            json!({ "field": field, "summary": format!("set to {}", show(value)) })
+        }
+        FieldChange::Removed { old_value } => {
+            json!({ "field": field, "summary": format!("cleared (was {})", show(old_value)) })
+        }
+        FieldChange::Changed {
+            old_value,
+            new_value,
+        } => json!({
+            "field": field,
+            "summary": format!("{} → {}", show(old_value), show(new_value)),
+        }),
+        FieldChange::TextDiff { forward_patch, .. } => {
+            let lines = PatchLines::parse(forward_patch);
+            if lines.context == 0 && lines.removed.len() <= 1 && lines.added.len() <= 1 {
+                let old = lines.removed.first().cloned().unwrap_or_default();
+                let new = lines.added.first().cloned().unwrap_or_default();
+                return json!({
+                    "field": field,
+                    "summary": format!("{} → {}", show(&json!(old)), show(&json!(new))),
+                });
+            }
+            let diff: Vec<String> = lines
+                .removed
+                .iter()
+                .map(|l| format!("- {l}"))
+                .chain(lines.added.iter().map(|l| format!("+ {l}")))
+                .collect();
+            json!({
+                "field": field,
+                "summary": format!(
+                    "edited (+{} −{} lines)",
+                    lines.added.len(),
+                    lines.removed.len()
+                ),
+                "diff": diff.join("\n"),
+            })
+        }
+    }
+}
+
+/// Show a value in a summary: text quoted, lists comma-separated, long
+/// values cut short.
+fn show(value: &Value) -> String {
+    let text = match value {
+        Value::Null => return "nothing".to_string(),
+        Value::String(s) => format!("\"{s}\""),
+        Value::Array(items) => items
+            .iter()
+            .map(|i| {
+                i.as_str()
+                    .map(str::to_string)
+                    .unwrap_or_else(|| i.to_string())
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        other => other.to_string(),
+    };
+    let text = text.replace('\n', " ");
+    if text.chars().count() > MAX_SHOWN_CHARS {
+        let cut: String = text.chars().take(MAX_SHOWN_CHARS).collect();
+        format!("{cut}…")
+    } else {
+        text
+    }
+}
+
+/// The removed, added and context lines of a unified diff.
+#[derive(Debug, Default)]
+struct PatchLines {
+    removed: Vec<String>,
+    added: Vec<String>,
+    context: usize,
+}
+
+impl PatchLines {
+    fn parse(patch: &str) -> Self {
+        let mut lines = Self::default();
+        for line in patch.lines() {
+            if line.starts_with("---") || line.starts_with("+++") || line.starts_with("@@") {
+                continue;
+            }
+            if let Some(removed) = line.strip_prefix('-') {
+                lines.removed.push(removed.to_string());
+            } else if let Some(added) = line.strip_prefix('+') {
+                lines.added.push(added.to_string());
+            } else if line.starts_with(' ') {
+                lines.context += 1;
+            }
+        }
+        lines
+    }
+}
+
+/// Parse a point in time (`since`, `until`, `as_of`): an RFC 3339 timestamp, a
+/// `YYYY-MM-DD` date (midnight UTC), or a duration ago such as `30m`, `12h`,
+/// `7d` or `2w`.
+pub(crate) fn parse_time_bound(value: &str, field: &str) -> Result<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_time(NaiveTime::MIN).and_utc());
+    }
+    let ago = value
+        .char_indices()
+        .last()
+        .and_then(|(i, unit)| {
+            let n: i64 = value[..i].parse().ok()?;
+            match unit {
+                'm' => Duration::try_minutes(n),
+                'h' => Duration::try_hours(n),
+                'd' => Duration::try_days(n),
+                'w' => Duration::try_weeks(n),
+                _ => None,
+            }
+        })
+        .and_then(|ago| Utc::now().checked_sub_signed(ago));
+    ago.ok_or_else(|| {
+        KanbanError::invalid_value(
+            field,
+            format!(
+                "'{value}' is not an RFC 3339 timestamp, a YYYY-MM-DD date or a duration such as 12h or 7d"
+            ),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swissarmyhammer_entity::changelog::diff_entities;
+    use swissarmyhammer_entity::Entity;
+
+    /// The field change `diff_entities` records for a text edit.
+    fn text_change(old: &str, new: &str) -> FieldChange {
+        let mut before = Entity::new("task", "t1");
+        before.set("body", json!(old));
+        let mut after = Entity::new("task", "t1");
+        after.set("body", json!(new));
+        diff_entities(&before, &after).remove(0).1
+    }
+
+    #[test]
+    fn describe_text_edits_single_and_multi_line() {
+        let change = text_change("Fix login", "Fix OAuth login");
+        assert_eq!(
+            describe_change("title", &change)["summary"],
+            "\"Fix login\" → \"Fix OAuth login\""
+        );
+
+        let change = text_change("a\nb\nc\nd\n", "a\nB\nc\nd\ne\n");
+        let described = describe_change("body", &change);
+        assert_eq!(described["summary"], "edited (+2 −1 lines)");
+        assert_eq!(described["diff"], "- b\n+ B\n+ e");
+    }
+
+    #[test]
+    fn describe_set_changed_and_removed() {
+        let set = FieldChange::Set {
+            value: json!(["bug", "ui"]),
+        };
+        assert_eq!(describe_change("tags", &set)["summary"], "set to bug, ui");
+        let changed = FieldChange::Changed {
+            old_value: json!(1),
+            new_value: json!(2),
+        };
+        assert_eq!(describe_change("order", &changed)["summary"], "1 → 2");
+        let removed = FieldChange::Removed {
+            old_value: json!("x".repeat(100)),
+        };
+        let described = describe_change("body", &removed);
+        let summary = described["summary"].as_str().unwrap();
+        assert!(summary.starts_with("cleared (was \"xxx"), "{summary}");
+        assert!(summary.ends_with("x…)"), "{summary}");
+    }
+
+    #[test]
+    fn parse_time_bound_accepts_timestamps_dates_and_durations() {
+        let t = parse_time_bound("2026-03-01T08:30:00+02:00", "since").unwrap();
+        assert_eq!(t.to_rfc3339(), "2026-03-01T06:30:00+00:00");
+        let d = parse_time_bound("2026-03-01", "since").unwrap();
+        assert_eq!(d.to_rfc3339(), "2026-03-01T00:00:00+00:00");
+
+        let ago = Utc::now() - parse_time_bound("12h", "since").unwrap();
+        assert!((ago.num_minutes() - 12 * 60).abs() <= 1);
+
+        assert!(matches!(
+            parse_time_bound("last night", "since"),
+            Err(KanbanError::InvalidValue { .. })
+        ));
+    }
+}
@@ -0,0 +1,269 @@
+//! ListActivity command
+
+use super::feed::{collect, parse_time_bound, render, ActivityQuery};
+use crate::context::KanbanContext;
+use crate::error::{KanbanError, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResult};
+
+/// Entries returned when no limit is given.
+const DEFAULT_LIMIT: usize = 50;
+
+/// List recent changes across every entity on the board, newest first.
+///
+/// Each entry is one changelog entry: who made it (when known), what it did
+/// to which entity, and a short summary of every field it changed.
+#[operation(
+    verb = "list",
+    noun = "activity",
+    description = "List recent changes across the board, newest first"
+)]
+#[derive(Debug, Default, Deserialize)]
+pub struct ListActivity {
+    /// Only changes made by this actor
+    pub changed_by: Option<String>,
+    /// Only changes to this entity type (task, column, tag, actor, ...)
+    pub entity_type: Option<String>,
+    /// Only changes touching this field; other field changes are left out
+    pub field: Option<String>,
+    /// Earliest change to include: RFC 3339 timestamp, YYYY-MM-DD, or a duration ago such as 12h or 7d
+    pub since: Option<String>,
+    /// Include only changes before this point, in the same forms as `since`
+    pub until: Option<String>,
+    /// Maximum number of entries to return (default 50)
+    pub limit: Option<usize>,
+}
+
+impl ListActivity {
+    /// List the most recent changes of every kind.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only changes made by this actor.
+    pub fn with_changed_by(mut self, actor: impl Into<String>) -> Self {
+        self.changed_by = Some(actor.into());
+        self
+    }
+
+    /// Only changes to this entity type.
+    pub fn with_entity_type(mut self, entity_type: impl Into<String>) -> Self {
+        self.entity_type = Some(entity_type.into());
+        self
+    }
+
+    /// Only changes touching this field.
+    pub fn with_field(mut self, field: impl Into<String>) -> Self {
+        self.field = Some(field.into());
+        self
+    }
+
+    /// Only changes at or after this point.
+    pub fn with_since(mut self, since: impl Into<String>) -> Self {
+        self.since = Some(since.into());
+        self
+    }
+
+    /// Only changes before this point.
+    pub fn with_until(mut self, until: impl Into<String>) -> Self {
+        self.until = Some(until.into());
+        self
+    }
+
+    /// Return at most `limit` entries.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+#[async_trait]
+impl Execute<KanbanContext, KanbanError> for ListActivity {
+    async fn execute(&self, ctx: &KanbanContext) -> ExecutionResult<Value, KanbanError> {
+        let result: Result<Value> = async {
+            let non_empty = |v: &Option<String>| {
+                v.as_deref()
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string)
+            };
+            let query = ActivityQuery {
+                changed_by: non_empty(&self.changed_by),
+                entity_type: non_empty(&self.entity_type),
+                field: non_empty(&self.field),
+                since: non_empty(&self.since)
+                    .map(|s| parse_time_bound(&s, "since"))
+                    .transpose()?,
+                until: non_empty(&self.until)
+                    .map(|s| parse_time_bound(&s, "until"))
+                    .transpose()?,
+            };
+
+            let ectx = ctx.entity_context().await?;
+            let items = collect(&ectx, &query).await?;
+            let total = items.len();
+            let entries: Vec<Value> = items
+                .iter()
+                .take(self.limit.unwrap_or(DEFAULT_LIMIT))
+                .map(render)
+                .collect();
+
+            Ok(json!({
+                "count": entries.len(),
+                "total": total,
+                "entries": entries,
+            }))
+        }
+        .await;
+
+        match result {
+            Ok(value) => ExecutionResult::Success { value },
+            Err(error) => ExecutionResult::Failed { error },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::InitBoard;
+    use crate::task::{AddTask, UpdateTask};
+    use crate::KanbanOperationProcessor;
+    use swissarmyhammer_operations::OperationProcessor;
+    use tempfile::TempDir;
+
+    async fn setup() -> (TempDir, KanbanContext) {
+        let temp = TempDir::new().unwrap();
+        let kanban_dir = temp.path().join(".kanban");
+        let ctx = KanbanContext::new(kanban_dir);
+
+        InitBoard::new("Test")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        (temp, ctx)
+    }
+
+    /// Add a task as `agent` and retitle it, returning the task id.
+    async fn agent_edits_task(ctx: &KanbanContext) -> String {
+        let agent = KanbanOperationProcessor::with_actor("agent");
+        let added = agent
+            .process(&AddTask::new("Fix login"), ctx)
+            .await
+            .unwrap();
+        let id = added["id"].as_str().unwrap().to_string();
+        agent
+            .process(
+                &UpdateTask::new(id.as_str()).with_title("Fix OAuth login"),
+                ctx,
+            )
+            .await
+            .unwrap();
+        id
+    }
+
+    #[tokio::test]
+    async fn test_activity_filters_by_actor_and_summarises_fields() {
+        let (_temp, ctx) = setup().await;
+        let id = agent_edits_task(&ctx).await;
+
+        let result = ListActivity::new()
+            .with_changed_by("agent")
+            .with_entity_type("task")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        let entries = result["entries"].as_array().unwrap();
+        assert_eq!(entries.len(), 2, "{result}");
+        // Newest first: the retitle, then the creation.
+        assert_eq!(entries[0]["op"], "update");
+        assert_eq!(entries[1]["op"], "create");
+        assert!(entries.iter().all(|e| e["actor"] == "agent"));
+        assert!(entries.iter().all(|e| e["entity_id"] == id.as_str()));
+        assert_eq!(entries[0]["name"], "Fix OAuth login");
+
+        let result = ListActivity::new()
+            .with_field("title")
+            .with_entity_type("task")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        let update = &result["entries"][0];
+        assert_eq!(update["changes"].as_array().unwrap().len(), 1);
+        assert_eq!(update["changes"][0]["field"], "title");
+        assert_eq!(
+            update["changes"][0]["summary"],
+            "\"Fix login\" → \"Fix OAuth login\""
+        );
+
+        let result = ListActivity::new()
+            .with_changed_by("someone-else")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(result["total"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_activity_time_window_type_filter_and_limit() {
+        let (_temp, ctx) = setup().await;
+        agent_edits_task(&ctx).await;
+
+        let all = ListActivity::new()
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert!(
+            all["total"].as_u64().unwrap() > 2,
+            "board init is listed too"
+        );
+
+        let columns = ListActivity::new()
+            .with_entity_type("column")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        let entries = columns["entries"].as_array().unwrap();
+        assert!(!entries.is_empty());
+        assert!(entries.iter().all(|e| e["entity_type"] == "column"));
+
+        let limited = ListActivity::new()
+            .with_limit(1)
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(limited["count"], 1);
+        assert_eq!(limited["total"], all["total"]);
+
+        let future = ListActivity::new()
+            .with_since("2999-01-01")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(future["total"], 0);
+        let past = ListActivity::new()
+            .with_until("1h")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(past["total"], 0);
+
+        let bad = ListActivity::new()
+            .with_since("yesterday-ish")
+            .execute(&ctx)
+            .await
+            .into_result();
+        assert!(matches!(bad, Err(KanbanError::InvalidValue { .. })));
+    }
+}
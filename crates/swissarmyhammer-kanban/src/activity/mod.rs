@@ -0,0 +1,12 @@
+//! Activity feed commands
+//!
+//! Every entity keeps its own JSONL changelog. The activity feed reads all of
+//! them — live, trashed and archived — and merges them into one time-ordered
+//! list, with each field change summarised as a readable diff. Entries carry
+//! the actor that made them when the operation was dispatched with one.
+
+mod feed;
+mod list;
+
+pub(crate) use feed::parse_time_bound;
+pub use list::ListActivity;
@@ -0,0 +1,373 @@
+//! GcAttachments command
+
+use crate::context::KanbanContext;
+use crate::error::{KanbanError, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use swissarmyhammer_entity::{io, EntityContext};
+use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResult};
+use ulid::Ulid;
+
+/// How long a newly stored attachment file or blob is kept without a
+/// reference. Attaching stores the blob and the file before the entity that
+/// refers to them is written, so a gc running in between must not take them.
+const GRACE_PERIOD: Duration = Duration::from_secs(10 * 60);
+
+/// Remove attachment files and blobs that nothing refers to any more.
+///
+/// An attachment file is kept while its stored name appears in a live,
+/// archived or trashed entity, or in a changelog entry the undo stack can
+/// still undo or redo. Everything else in `.attachments/` and its trash is
+/// removed, then every blob in `.blobs/` that no kept file was stored from.
+/// Files and blobs stored within the last ten minutes are always kept, since
+/// the entity attaching them may not be written yet. Kept blobs are re-hashed
+/// on the way and any that no longer match their name are reported as corrupt
+/// rather than removed.
+#[operation(
+    verb = "gc",
+    noun = "attachments",
+    description = "Remove attachment blobs no task, trash entry or undo entry references"
+)]
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct GcAttachments {
+    /// Report what would be removed without removing anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl GcAttachments {
+    /// Collect garbage with the default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only report what would be removed.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+}
+
+/// The files directly inside `dir`, skipping dotted names (nested trash
+/// directories and in-flight temp files). A missing directory has none.
+async fn plain_files(dir: &Path) -> Result<Vec<(String, PathBuf)>> {
+    let mut files = Vec::new();
+    let mut entries = match tokio::fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(files),
+        Err(e) => return Err(e.into()),
+    };
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !name.starts_with('.') && entry.file_type().await?.is_file() {
+            files.push((name, entry.path()));
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// The directories directly inside `dir`, skipping dotted names. Under the
+/// board root these are the entity type directories (`tasks/`, `tags/`, ...);
+/// under `.blobs/` they are the hash fan-out directories.
+async fn plain_dirs(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut dirs = Vec::new();
+    let mut entries = match tokio::fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(dirs),
+        Err(e) => return Err(e.into()),
+    };
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !name.starts_with('.') && entry.file_type().await?.is_dir() {
+            dirs.push(entry.path());
+        }
+    }
+    dirs.sort();
+    Ok(dirs)
+}
+
+/// When an attachment file or blob was stored: the time in a stored name's
+/// ULID, or else the file's modification time.
+async fn stored_at(name: &str, path: &Path) -> Result<SystemTime> {
+    let minted = name
+        .split_once('.')
+        .and_then(|(ulid, _)| Ulid::from_string(ulid).ok());
+    match minted {
+        Some(ulid) => Ok(ulid.datetime()),
+        None => Ok(tokio::fs::metadata(path).await?.modified()?),
+    }
+}
+
+/// Whether a file stored at `stored` is still inside the grace period.
+fn is_recent(stored: SystemTime, grace: Duration) -> bool {
+    stored.elapsed().map_or(true, |age| age < grace)
+}
+
+/// Everything that can refer to an attachment by its stored name: entity
+/// files in each live, archive and trash directory, plus the changelog
+/// lines of entries undo or redo may still need. A file that cannot be read
+/// fails the pass: skipping it could make its attachments look unreferenced.
+async fn reference_text(ectx: &EntityContext, entity_dirs: &[PathBuf]) -> Result<String> {
+    let undo_ids: HashSet<String> = ectx
+        .pinned_undo_entries()
+        .await?
+        .iter()
+        .map(|id| id.to_string().to_lowercase())
+        .collect();
+
+    let mut text = String::new();
+    for dir in entity_dirs {
+        for sub in [dir.clone(), dir.join(".archive"), dir.join(".trash")] {
+            for (name, path) in plain_files(&sub).await? {
+                let content = tokio::fs::read_to_string(&path).await?;
+                if name.ends_with(".jsonl") {
+                    for line in content.lines() {
+                        let id = serde_json::from_str::<Value>(line)
+                            .ok()
+                            .and_then(|entry| entry["id"].as_str().map(str::to_lowercase));
+                        if id.is_some_and(|id| undo_ids.contains(&id)) {
+                            text.push_str(line);
+                            text.push('\n');
+                        }
+                    }
+                } else {
+                    text.push_str(&content);
+                    text.push('\n');
+                }
+            }
+        }
+    }
+    Ok(text)
+}
+
+impl GcAttachments {
+    /// Collect garbage, keeping anything stored within `grace`.
+    async fn collect(&self, ctx: &KanbanContext, grace: Duration) -> Result<Value> {
+        let ectx = ctx.entity_context().await?;
+        let entity_dirs = plain_dirs(ectx.root()).await?;
+        let references = reference_text(&ectx, &entity_dirs).await?;
+
+        // Attachment files first: the hashes of the ones kept decide
+        // which blobs are still needed.
+        let mut removed_files = Vec::new();
+        let mut kept_hashes = HashSet::new();
+        let mut freed_bytes = 0u64;
+        for dir in &entity_dirs {
+            for att_dir in [io::attachments_dir(dir), io::attachments_trash_dir(dir)] {
+                for (name, path) in plain_files(&att_dir).await? {
+                    if references.contains(&name)
+                        || is_recent(stored_at(&name, &path).await?, grace)
+                    {
+                        if let Some(hash) = io::attachment_hash(&name) {
+                            kept_hashes.insert(hash.to_string());
+                        }
+                        continue;
+                    }
+                    // A file stored from a blob is a link to it; its
+                    // bytes are only freed when the blob goes.
+                    if io::attachment_hash(&name).is_none() {
+                        freed_bytes += tokio::fs::metadata(&path).await?.len();
+                    }
+                    if !self.dry_run {
+                        tokio::fs::remove_file(&path).await?;
+                    }
+                    removed_files.push(name);
+                }
+            }
+        }
+
+        let mut removed_blobs = Vec::new();
+        let mut corrupt = Vec::new();
+        let blobs_dir = io::blobs_dir(&ectx.entity_dir("task"));
+        for fanout in plain_dirs(&blobs_dir).await? {
+            for (hash, path) in plain_files(&fanout).await? {
+                if kept_hashes.contains(&hash) {
+                    let bytes = tokio::fs::read(&path).await?;
+                    if io::sha256_hex(&bytes) != hash {
+                        corrupt.push(hash);
+                    }
+                    continue;
+                }
+                let metadata = tokio::fs::metadata(&path).await?;
+                if is_recent(metadata.modified()?, grace) {
+                    continue;
+                }
+                freed_bytes += metadata.len();
+                if !self.dry_run {
+                    tokio::fs::remove_file(&path).await?;
+                }
+                removed_blobs.push(hash);
+            }
+        }
+
+        Ok(json!({
+            "dry_run": self.dry_run,
+            "removed_files": removed_files,
+            "removed_blobs": removed_blobs,
+            "freed_bytes": freed_bytes,
+            "corrupt_blobs": corrupt,
+        }))
+    }
+}
+
+#[async_trait]
+impl Execute<KanbanContext, KanbanError> for GcAttachments {
+    async fn execute(&self, ctx: &KanbanContext) -> ExecutionResult<Value, KanbanError> {
+        match self.collect(ctx, GRACE_PERIOD).await {
+            Ok(value) => ExecutionResult::Success { value },
+            Err(error) => ExecutionResult::Failed { error },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::InitBoard;
+    use crate::task::AddTask;
+    use std::sync::Arc;
+    use swissarmyhammer_store::{StoreContext, StoredItemId, UndoStack};
+    use tempfile::TempDir;
+
+    async fn setup() -> (TempDir, KanbanContext) {
+        let temp = TempDir::new().unwrap();
+        let kanban_dir = temp.path().join(".kanban");
+        let ctx = KanbanContext::new(kanban_dir);
+
+        InitBoard::new("Test")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        (temp, ctx)
+    }
+
+    async fn add_task_with_attachments(ctx: &KanbanContext, attachments: Value) -> String {
+        let task = AddTask::new("Task")
+            .execute(ctx)
+            .await
+            .into_result()
+            .unwrap();
+        let id = task["id"].as_str().unwrap().to_string();
+        let ectx = ctx.entity_context().await.unwrap();
+        let mut task = ectx.read("task", &id).await.unwrap();
+        task.set("attachments", attachments);
+        ectx.write(&task).await.unwrap();
+        id
+    }
+
+    /// Collect with no grace period, so everything just stored is eligible.
+    async fn gc(ctx: &KanbanContext, dry_run: bool) -> Value {
+        GcAttachments::new()
+            .with_dry_run(dry_run)
+            .collect(ctx, Duration::ZERO)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_gc_keeps_referenced_blobs_and_removes_orphans() {
+        let (temp, ctx) = setup().await;
+        let source = temp.path().join("shared.txt");
+        std::fs::write(&source, b"shared bytes").unwrap();
+        let source = source.to_string_lossy().to_string();
+        let hash = io::sha256_hex(b"shared bytes");
+
+        let first = add_task_with_attachments(&ctx, json!([source])).await;
+        let second = add_task_with_attachments(&ctx, json!([source])).await;
+        let ectx = ctx.entity_context().await.unwrap();
+        let tasks_dir = ectx.entity_dir("task");
+        let blob = io::blob_path(&tasks_dir, &hash);
+        assert!(blob.exists(), "both tasks share one blob");
+
+        // Detach from the first task; the undo stack still holds the change.
+        let mut task = ectx.read("task", &first).await.unwrap();
+        task.set("attachments", json!([]));
+        let entry_id = ectx.write(&task).await.unwrap().unwrap();
+        let stack_path = ectx.root().join("undo_stack.yaml");
+        let mut stack = UndoStack::new();
+        stack.push(entry_id, "update task", StoredItemId::from(first.as_str()));
+        stack.save(&stack_path).unwrap();
+
+        let report = gc(&ctx, false).await;
+        assert_eq!(report["removed_files"], json!([]));
+        assert_eq!(report["removed_blobs"], json!([]));
+
+        // Once the undo entry is gone the trashed file is garbage, but the
+        // blob stays while the second task still uses it.
+        UndoStack::new().save(&stack_path).unwrap();
+        let trash = io::attachments_trash_dir(&tasks_dir);
+        let report = gc(&ctx, true).await;
+        assert_eq!(report["removed_files"].as_array().unwrap().len(), 1);
+        assert_eq!(report["removed_blobs"], json!([]));
+        assert_eq!(std::fs::read_dir(&trash).unwrap().count(), 1, "dry run");
+
+        gc(&ctx, false).await;
+        assert_eq!(std::fs::read_dir(&trash).unwrap().count(), 0);
+        assert!(blob.exists());
+
+        let mut task = ectx.read("task", &second).await.unwrap();
+        task.set("attachments", json!([]));
+        ectx.write(&task).await.unwrap();
+        let report = gc(&ctx, false).await;
+        assert_eq!(report["removed_blobs"], json!([hash]));
+        assert_eq!(report["freed_bytes"], b"shared bytes".len());
+        assert_eq!(report["corrupt_blobs"], json!([]));
+        assert!(!blob.exists());
+    }
+
+    /// An attachment stored moments ago, before any entity refers to it, is
+    /// left alone together with its blob.
+    #[tokio::test]
+    async fn test_gc_keeps_freshly_stored_attachments() {
+        let (temp, ctx) = setup().await;
+        let source = temp.path().join("fresh.txt");
+        std::fs::write(&source, b"fresh bytes").unwrap();
+        let ectx = ctx.entity_context().await.unwrap();
+        let tasks_dir = ectx.entity_dir("task");
+        let stored = io::copy_attachment(&source, &tasks_dir, "attachments", u64::MAX)
+            .await
+            .unwrap();
+
+        let report = GcAttachments::new()
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(report["removed_files"], json!([]));
+        assert_eq!(report["removed_blobs"], json!([]));
+        assert!(io::attachments_dir(&tasks_dir).join(&stored).exists());
+
+        let report = gc(&ctx, false).await;
+        assert_eq!(report["removed_files"], json!([stored]));
+    }
+
+    /// An entry on this process's undo stack pins the attachment even when
+    /// the persisted stack no longer lists it, as for changelog compaction.
+    #[tokio::test]
+    async fn test_gc_keeps_attachments_pinned_by_the_in_process_stack() {
+        let (temp, ctx) = setup().await;
+        let source = temp.path().join("pinned.txt");
+        std::fs::write(&source, b"pinned bytes").unwrap();
+        let source = source.to_string_lossy().to_string();
+        let id = add_task_with_attachments(&ctx, json!([source])).await;
+
+        let ectx = ctx.entity_context().await.unwrap();
+        ectx.set_store_context(Arc::new(StoreContext::new(ectx.root().to_path_buf())));
+        let mut task = ectx.read("task", &id).await.unwrap();
+        task.set("attachments", json!([]));
+        ectx.write(&task).await.unwrap();
+        UndoStack::new()
+            .save(&ectx.root().join("undo_stack.yaml"))
+            .unwrap();
+
+        let report = gc(&ctx, false).await;
+        assert_eq!(report["removed_files"], json!([]));
+        assert_eq!(report["removed_blobs"], json!([]));
+    }
+}
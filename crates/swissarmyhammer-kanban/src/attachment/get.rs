@@ -1,14 +1,19 @@
 //! GetAttachment command
 
-use crate::attachment::attachment_entity_to_json;
+use crate::attachment::match_attachment_index;
 use crate::context::KanbanContext;
-use crate::error::KanbanError;
+use crate::error::{KanbanError, Result};
 use crate::types::TaskId;
 use serde::Deserialize;
 use serde_json::Value;
+use swissarmyhammer_entity::io;
 use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResult};
 
 /// Get a specific attachment from a task
+///
+/// The attachment's file is checked against the content hash it was stored
+/// under before its metadata is returned, so a file changed or damaged on
+/// disk fails the read instead of being handed out as the original.
 #[operation(
     verb = "get",
     noun = "attachment",
@@ -16,9 +21,9 @@ use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResul
 )]
 #[derive(Debug, Deserialize)]
 pub struct GetAttachment {
-    /// The task ID (kept for API compatibility; used to verify ownership)
+    /// The task that owns the attachment
     pub task_id: TaskId,
-    /// The attachment ID
+    /// The attachment ID, stored filename or absolute path
     pub id: String,
 }
 
@@ -35,28 +40,37 @@ impl GetAttachment {
 #[async_trait]
 impl Execute<KanbanContext, KanbanError> for GetAttachment {
     async fn execute(&self, ctx: &KanbanContext) -> ExecutionResult<Value, KanbanError> {
-        match async {
+        let result: Result<Value> = async {
             let ectx = ctx.entity_context().await?;
 
-            // Verify the task owns this attachment
+            // The task's attachments come back enriched with metadata; find
+            // the one asked for the same way `delete attachment` does.
             let task = ectx.read("task", self.task_id.as_str()).await?;
-            if !task.get_string_list("attachments").contains(&self.id) {
+            let attachments = task
+                .get("attachments")
+                .and_then(|v| v.as_array())
+                .cloned()
+                .unwrap_or_default();
+            let Some(idx) = match_attachment_index(&attachments, &self.id) else {
                 return Err(KanbanError::NotFound {
                     resource: "attachment".to_string(),
                     id: self.id.to_string(),
                 });
-            }
+            };
+            let attachment = attachments[idx].clone();
 
-            // Read the attachment entity
-            let attachment = ectx
-                .read("attachment", &self.id)
-                .await
-                .map_err(KanbanError::from_entity_error)?;
+            let stored_name = format!(
+                "{}-{}",
+                attachment["id"].as_str().unwrap_or_default(),
+                attachment["name"].as_str().unwrap_or_default()
+            );
+            io::verify_attachment(&stored_name, &ectx.entity_dir("task")).await?;
 
-            Ok(attachment_entity_to_json(&attachment))
+            Ok(attachment)
         }
-        .await
-        {
+        .await;
+
+        match result {
             Ok(value) => ExecutionResult::Success { value },
             Err(error) => ExecutionResult::Failed { error },
         }
@@ -147,4 +161,55 @@ mod tests {
         let result = ectx.read("task", "nonexistent").await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_get_attachment_verifies_content() {
+        let (temp, ctx) = setup().await;
+
+        let task_result = AddTask::new("Task")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        let task_id = task_result["id"].as_str().unwrap();
+
+        let file_path = create_temp_file(temp.path(), "file.txt", b"hello");
+        let ectx = ctx.entity_context().await.unwrap();
+        let mut task = ectx.read("task", task_id).await.unwrap();
+        task.set("attachments", json!([file_path]));
+        ectx.write(&task).await.unwrap();
+        let task = ectx.read("task", task_id).await.unwrap();
+        let stored_path = task.get("attachments").unwrap()[0]["path"]
+            .as_str()
+            .unwrap()
+            .to_string();
+
+        let attachment = GetAttachment::new(task_id, stored_path.as_str())
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(attachment["name"], "file.txt");
+        assert_eq!(attachment["sha256"], io::sha256_hex(b"hello"));
+
+        // Swap the stored file for different bytes behind the board's back.
+        std::fs::remove_file(&stored_path).unwrap();
+        std::fs::write(&stored_path, b"jello").unwrap();
+        let result = GetAttachment::new(task_id, stored_path.as_str())
+            .execute(&ctx)
+            .await
+            .into_result();
+        assert!(matches!(
+            result,
+            Err(KanbanError::EntityError(
+                swissarmyhammer_entity::EntityError::AttachmentCorrupt { .. }
+            ))
+        ));
+
+        let missing = GetAttachment::new(task_id, "nope").execute(&ctx).await;
+        assert!(matches!(
+            missing.into_result(),
+            Err(KanbanError::NotFound { .. })
+        ));
+    }
 }
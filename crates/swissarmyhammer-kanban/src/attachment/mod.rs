@@ -2,6 +2,7 @@
 
 mod add;
 mod delete;
+mod gc;
 mod get;
 mod list;
 mod update;
@@ -10,6 +11,7 @@ pub(crate) use add::attachment_entity_to_json;
 pub use add::AddAttachment;
 pub(crate) use delete::match_attachment_index;
 pub use delete::DeleteAttachment;
+pub use gc::GcAttachments;
 pub use get::GetAttachment;
 pub use list::ListAttachments;
 pub use update::UpdateAttachment;
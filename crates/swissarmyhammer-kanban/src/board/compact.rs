@@ -0,0 +1,224 @@
+//! CompactBoard command
+
+use crate::activity::parse_time_bound;
+use crate::context::KanbanContext;
+use crate::error::{KanbanError, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResult};
+use swissarmyhammer_store::CompactionPolicy;
+
+/// Newest entries kept per entity when neither bound is given.
+const DEFAULT_KEEP_LATEST: usize = 100;
+
+/// Fold old changelog history into snapshots.
+///
+/// Every entity keeps its own changelog, which grows with each edit. This
+/// folds the older part of each live changelog into a single snapshot entry:
+/// the current state, undo and redo, and time travel to any point after the
+/// snapshot are unaffected, while `as_of` reads inside the folded run are
+/// refused. A create entry is always kept, so creation dates survive.
+#[operation(
+    verb = "compact",
+    noun = "board",
+    description = "Fold old entity changelog history into snapshots"
+)]
+#[derive(Debug, Default, Deserialize)]
+pub struct CompactBoard {
+    /// Keep this many of each entity's newest changelog entries (default 100 when older_than is not given either)
+    #[serde(default)]
+    pub keep_latest: Option<usize>,
+    /// Fold entries older than this: RFC 3339 timestamp, YYYY-MM-DD, or a duration ago such as 30d
+    #[serde(default)]
+    pub older_than: Option<String>,
+    /// Only compact this entity type (task, column, tag, ...)
+    #[serde(default)]
+    pub entity_type: Option<String>,
+}
+
+impl CompactBoard {
+    /// Compact with the default policy.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep this many of each entity's newest entries.
+    pub fn with_keep_latest(mut self, keep_latest: usize) -> Self {
+        self.keep_latest = Some(keep_latest);
+        self
+    }
+
+    /// Fold entries older than this point.
+    pub fn with_older_than(mut self, older_than: impl Into<String>) -> Self {
+        self.older_than = Some(older_than.into());
+        self
+    }
+
+    /// Only compact this entity type.
+    pub fn with_entity_type(mut self, entity_type: impl Into<String>) -> Self {
+        self.entity_type = Some(entity_type.into());
+        self
+    }
+
+    /// The compaction policy the parameters describe.
+    fn policy(&self) -> Result<CompactionPolicy> {
+        let before = self
+            .older_than
+            .as_deref()
+            .filter(|s| !s.trim().is_empty())
+            .map(|s| parse_time_bound(s, "older_than"))
+            .transpose()?;
+        let keep_latest = match (self.keep_latest, before) {
+            (None, None) => Some(DEFAULT_KEEP_LATEST),
+            (keep, _) => keep,
+        };
+        Ok(CompactionPolicy {
+            keep_latest,
+            before,
+        })
+    }
+}
+
+#[async_trait]
+impl Execute<KanbanContext, KanbanError> for CompactBoard {
+    async fn execute(&self, ctx: &KanbanContext) -> ExecutionResult<Value, KanbanError> {
+        let result: Result<Value> = async {
+            let policy = self.policy()?;
+            let ectx = ctx.entity_context().await?;
+            let entity_types: Vec<String> = match &self.entity_type {
+                Some(entity_type) => {
+                    ectx.entity_def(entity_type)?;
+                    vec![entity_type.clone()]
+                }
+                None => ectx
+                    .fields()
+                    .all_entities()
+                    .iter()
+                    .map(|def| def.name.to_string())
+                    .collect(),
+            };
+
+            let mut compacted = Vec::new();
+            let mut folded = 0;
+            for entity_type in &entity_types {
+                for (id, count) in ectx.compact_changelogs(entity_type, &policy).await? {
+                    folded += count;
+                    compacted.push(json!({
+                        "entity_type": entity_type,
+                        "id": id.as_str(),
+                        "folded": count,
+                    }));
+                }
+            }
+
+            Ok(json!({
+                "entities": compacted.len(),
+                "folded": folded,
+                "compacted": compacted,
+            }))
+        }
+        .await;
+
+        match result {
+            Ok(value) => ExecutionResult::Success { value },
+            Err(error) => ExecutionResult::Failed { error },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::InitBoard;
+    use crate::task::{AddTask, GetTask, UpdateTask};
+    use tempfile::TempDir;
+
+    async fn setup() -> (TempDir, KanbanContext) {
+        let temp = TempDir::new().unwrap();
+        let kanban_dir = temp.path().join(".kanban");
+        let ctx = KanbanContext::new(kanban_dir);
+
+        InitBoard::new("Test")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        (temp, ctx)
+    }
+
+    #[tokio::test]
+    async fn test_compact_folds_old_task_history() {
+        let (_temp, ctx) = setup().await;
+        let added = AddTask::new("Draft")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        let id = added["id"].as_str().unwrap().to_string();
+        for title in ["Draft 2", "Draft 3", "Draft 4", "Final"] {
+            UpdateTask::new(id.as_str())
+                .with_title(title)
+                .execute(&ctx)
+                .await
+                .into_result()
+                .unwrap();
+        }
+        let created = GetTask::new(id.as_str())
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap()["created"]
+            .clone();
+
+        let result = CompactBoard::new()
+            .with_keep_latest(1)
+            .with_entity_type("task")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(result["entities"], 1, "{result}");
+        assert_eq!(result["folded"], 3);
+        assert_eq!(result["compacted"][0]["id"], id.as_str());
+
+        let ectx = ctx.entity_context().await.unwrap();
+        let ops: Vec<String> = ectx
+            .read_changelog("task", id.as_str())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|e| e.op)
+            .collect();
+        assert_eq!(ops, vec!["create", "snapshot", "update"]);
+        let task = GetTask::new(id.as_str())
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(task["title"], "Final");
+        assert_eq!(task["created"], created, "the create entry is kept");
+
+        let again = CompactBoard::new()
+            .with_keep_latest(1)
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(again["folded"], 0);
+    }
+
+    #[test]
+    fn test_policy_defaults_and_bounds() {
+        let policy = CompactBoard::new().policy().unwrap();
+        assert_eq!(policy.keep_latest, Some(DEFAULT_KEEP_LATEST));
+        assert_eq!(policy.before, None);
+
+        let policy = CompactBoard::new().with_older_than("30d").policy().unwrap();
+        assert_eq!(policy.keep_latest, None);
+        assert!(policy.before.is_some());
+
+        let bad = CompactBoard::new().with_older_than("last spring").policy();
+        assert!(matches!(bad, Err(KanbanError::InvalidValue { .. })));
+    }
+}
@@ -1,15 +1,20 @@
 //! GetBoard command
 
+use crate::activity::parse_time_bound;
 use crate::column::column_entity_to_json;
 use crate::context::KanbanContext;
 use crate::error::KanbanError;
 use crate::project::project_entity_to_json;
 use crate::tag::tag_entity_to_json;
-use crate::task_helpers::enrich_all_task_entities;
-use crate::virtual_tags::default_virtual_tag_registry;
+use crate::task_helpers::{
+    enrich_all_task_entities_with_wip_limits, task_entity_to_json, task_entity_to_rich_json,
+};
+use crate::virtual_tags::{default_virtual_tag_registry, ColumnWipLimits};
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use serde_json::{json, Value};
 use std::collections::HashMap;
+use swissarmyhammer_entity::{Entity, EntityContext};
 use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResult};
 
 /// Get the board with computed task counts
@@ -23,9 +28,18 @@ pub struct GetBoard {
     /// Whether to include task counts (default: true)
     #[serde(default)]
     pub include_counts: Option<bool>,
+    /// Snapshot the board as it stood at this time (RFC 3339 timestamp, YYYY-MM-DD, or a duration ago such as 2h or 3d); the result also lists the tasks
+    #[serde(default)]
+    pub as_of: Option<String>,
 }
 
 impl GetBoard {
+    /// Snapshot the board as it stood at `as_of`, rebuilt from changelogs.
+    pub fn with_as_of(mut self, as_of: impl Into<String>) -> Self {
+        self.as_of = Some(as_of.into());
+        self
+    }
+
     /// Whether task counts should be included, defaulting to `true` when unset.
     fn include_counts(&self) -> bool {
         self.include_counts.unwrap_or(true)
@@ -37,42 +51,66 @@ impl Execute<KanbanContext, KanbanError> for GetBoard {
     async fn execute(&self, ctx: &KanbanContext) -> ExecutionResult<Value, KanbanError> {
         match async {
             let ectx = ctx.entity_context().await?;
+            let as_of = self
+                .as_of
+                .as_deref()
+                .filter(|s| !s.trim().is_empty())
+                .map(|s| parse_time_bound(s, "as_of"))
+                .transpose()?;
             let board =
-                ectx.read("board", "board")
-                    .await
-                    .map_err(|_| KanbanError::NotInitialized {
-                        path: ctx.root().to_path_buf(),
-                    })?;
+                match as_of {
+                    Some(at) => ectx
+                        .read_as_of("board", "board", at)
+                        .await?
+                        .ok_or_else(|| {
+                            KanbanError::invalid_value(
+                                "as_of",
+                                format!("the board did not exist yet at {}", at.to_rfc3339()),
+                            )
+                        })?,
+                    None => ectx.read("board", "board").await.map_err(|_| {
+                        KanbanError::NotInitialized {
+                            path: ctx.root().to_path_buf(),
+                        }
+                    })?,
+                };
             let board_name = board.get_str("name").unwrap_or("");
             let board_description = board.get_str("description");
             let board_model = board.get_str("model");
-            let mut all_columns = ectx.list("column").await?;
+            let mut all_columns = list_at(&ectx, "column", as_of).await?;
             all_columns
                 .sort_by_key(|c| c.get("order").and_then(|v| v.as_u64()).unwrap_or(0) as usize);
-            let mut all_projects = ectx.list("project").await?;
+            let mut all_projects = list_at(&ectx, "project", as_of).await?;
             all_projects
                 .sort_by_key(|s| s.get("order").and_then(|v| v.as_u64()).unwrap_or(0) as usize);
 
             // If counts are not requested, return basic board structure
             if !self.include_counts() {
-                let all_tags = ectx.list("tag").await?;
+                let all_tags = list_at(&ectx, "tag", as_of).await?;
                 let columns_json: Vec<Value> =
                     all_columns.iter().map(column_entity_to_json).collect();
                 let projects_json: Vec<Value> =
                     all_projects.iter().map(project_entity_to_json).collect();
                 let tags_json: Vec<Value> = all_tags.iter().map(tag_entity_to_json).collect();
-                return Ok(json!({
+                let mut result = json!({
                     "name": board_name,
                     "description": board_description,
                     "model": board_model,
                     "columns": columns_json,
                     "projects": projects_json,
                     "tags": tags_json,
-                }));
+                });
+                if let Some(at) = as_of {
+                    let tasks = list_at(&ectx, "task", Some(at)).await?;
+                    result["as_of"] = json!(at.to_rfc3339());
+                    result["tasks"] =
+                        json!(tasks.iter().map(task_entity_to_json).collect::<Vec<_>>());
+                }
+                return Ok(result);
             }
 
             // Read all tasks and enrich via the same pipeline as ListTasks/NextTask
-            let mut all_tasks = ectx.list("task").await?;
+            let mut all_tasks = list_at(&ectx, "task", as_of).await?;
             let terminal_id = all_columns
                 .iter()
                 .max_by_key(|c| c.get("order").and_then(|v| v.as_u64()).unwrap_or(0))
@@ -80,7 +118,13 @@ impl Execute<KanbanContext, KanbanError> for GetBoard {
                 .unwrap_or("done");
 
             let registry = default_virtual_tag_registry();
-            enrich_all_task_entities(&mut all_tasks, terminal_id, registry);
+            let wip_limits = ColumnWipLimits::from_columns(&all_columns);
+            enrich_all_task_entities_with_wip_limits(
+                &mut all_tasks,
+                terminal_id,
+                &wip_limits,
+                registry,
+            );
 
             // Count tasks by column, reading ready status from enriched entities.
             let mut column_counts: HashMap<String, usize> = HashMap::new();
@@ -110,12 +154,16 @@ impl Execute<KanbanContext, KanbanError> for GetBoard {
                         .copied()
                         .unwrap_or(0);
 
+                    let wip_limit = wip_limits.0.get(col.id.as_str()).copied();
+
                     json!({
                         "id": col.id,
                         "name": col.get_str("name").unwrap_or(""),
                         "order": col.get("order").and_then(|v| v.as_u64()).unwrap_or(0),
                         "task_count": count,
-                        "ready_count": ready
+                        "ready_count": ready,
+                        "wip_limit": wip_limit,
+                        "over_wip_limit": wip_limit.is_some_and(|limit| count > limit)
                     })
                 })
                 .collect();
@@ -124,7 +172,7 @@ impl Execute<KanbanContext, KanbanError> for GetBoard {
             let projects: Vec<Value> = all_projects.iter().map(project_entity_to_json).collect();
 
             // Read all tags
-            let all_tags = ectx.list("tag").await?;
+            let all_tags = list_at(&ectx, "tag", as_of).await?;
             let tags: Vec<Value> = all_tags.iter().map(tag_entity_to_json).collect();
 
             // Calculate summary (ready count already computed in the column pass)
@@ -137,9 +185,9 @@ impl Execute<KanbanContext, KanbanError> for GetBoard {
             } else {
                 0
             };
-            let total_actors = ectx.list("actor").await?.len();
+            let total_actors = list_at(&ectx, "actor", as_of).await?.len();
 
-            Ok(json!({
+            let mut result = json!({
                 "name": board_name,
                 "description": board_description,
                 "model": board_model,
@@ -154,7 +202,15 @@ impl Execute<KanbanContext, KanbanError> for GetBoard {
                     "done_tasks": done_tasks,
                     "percent_complete": percent_complete
                 }
-            }))
+            });
+            if let Some(at) = as_of {
+                result["as_of"] = json!(at.to_rfc3339());
+                result["tasks"] = json!(all_tasks
+                    .iter()
+                    .map(task_entity_to_rich_json)
+                    .collect::<Vec<_>>());
+            }
+            Ok(result)
         }
         .await
         {
@@ -164,6 +220,18 @@ impl Execute<KanbanContext, KanbanError> for GetBoard {
     }
 }
 
+/// List an entity type as it is now, or as it stood at `at`.
+async fn list_at(
+    ectx: &EntityContext,
+    entity_type: &str,
+    at: Option<DateTime<Utc>>,
+) -> Result<Vec<Entity>, KanbanError> {
+    Ok(match at {
+        Some(at) => ectx.list_as_of(entity_type, at).await?,
+        None => ectx.list(entity_type).await?,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -453,6 +521,7 @@ mod tests {
 
         let result = GetBoard {
             include_counts: Some(false),
+            ..Default::default()
         }
         .execute(&ctx)
         .await
@@ -487,6 +556,7 @@ mod tests {
 
         let basic = GetBoard {
             include_counts: Some(false),
+            ..Default::default()
         }
         .execute(&ctx)
         .await
@@ -538,4 +608,117 @@ mod tests {
             "archived task should not be counted in total_tasks"
         );
     }
+
+    #[tokio::test]
+    async fn test_get_board_reports_over_wip_columns() {
+        let (_temp, ctx) = setup().await;
+        crate::column::UpdateColumn::new("doing")
+            .with_wip_limit(1)
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        let mut ids = Vec::new();
+        for title in ["A", "B"] {
+            let id = AddTask::new(title)
+                .execute(&ctx)
+                .await
+                .into_result()
+                .unwrap()["id"]
+                .as_str()
+                .unwrap()
+                .to_string();
+            MoveTask::to_column(id.clone(), "doing")
+                .with_override_wip_limit()
+                .execute(&ctx)
+                .await
+                .into_result()
+                .unwrap();
+            ids.push(id);
+        }
+
+        let result = GetBoard::default()
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        let columns = result["columns"].as_array().unwrap();
+        let doing_col = columns.iter().find(|c| c["id"] == "doing").unwrap();
+        let todo_col = columns.iter().find(|c| c["id"] == "todo").unwrap();
+        assert_eq!(doing_col["wip_limit"], 1);
+        assert_eq!(doing_col["over_wip_limit"], true);
+        assert!(todo_col["wip_limit"].is_null());
+        assert_eq!(todo_col["over_wip_limit"], false);
+
+        for id in ids {
+            let task = crate::task::GetTask::new(id)
+                .execute(&ctx)
+                .await
+                .into_result()
+                .unwrap();
+            assert_eq!(
+                task["virtual_tags"],
+                serde_json::json!(["READY", "OVER_WIP"])
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_board_as_of_rebuilds_past_snapshot() {
+        let (_temp, ctx) = setup().await;
+        let first = AddTask::new("First")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        let first_id = first["id"].as_str().unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        let snapshot_at = chrono::Utc::now().to_rfc3339();
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+
+        AddTask::new("Second")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        MoveTask::to_column(first_id, "done")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        crate::task::DeleteTask::new(first_id)
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        let past = GetBoard::default()
+            .with_as_of(snapshot_at)
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(past["summary"]["total_tasks"], 1);
+        assert_eq!(past["summary"]["done_tasks"], 0);
+        let tasks = past["tasks"].as_array().unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0]["title"], "First");
+        assert!(past["as_of"].is_string());
+
+        let now = GetBoard::default()
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(now["summary"]["total_tasks"], 1);
+        assert!(now.get("tasks").is_none());
+
+        let too_early = GetBoard::default()
+            .with_as_of("1999-01-01")
+            .execute(&ctx)
+            .await
+            .into_result();
+        assert!(matches!(too_early, Err(KanbanError::InvalidValue { .. })));
+    }
 }
@@ -283,10 +283,10 @@ fn remove_config_section(config: &str, section: &str) -> String {
     }
 }
 
-/// Ephemeral gitignore entries (search cache, undo state) that must not be
-/// version controlled. Listed explicitly (not as a `search-cache.sqlite3*`
-/// glob) so the guarantee is exact and the directory's tracked task files stay
-/// un-ignored.
+/// Ephemeral gitignore entries (search cache, undo state, hook state) that
+/// must not be version controlled. Listed explicitly (not as a
+/// `search-cache.sqlite3*` glob) so the guarantee is exact and the directory's
+/// tracked task files stay un-ignored.
 ///
 /// Declared at module scope (rather than inside [`ensure_gitignore_entries`])
 /// so the test suite can assert against the single source of truth instead of
@@ -296,6 +296,7 @@ pub(crate) const REQUIRED_GITIGNORE_ENTRIES: &[&str] = &[
     "search-cache.sqlite3",
     "search-cache.sqlite3-wal",
     "search-cache.sqlite3-shm",
+    ".hooks/",
 ];
 
 /// Reconcile the board's `.kanban/.gitignore` so every entry in
@@ -1,9 +1,11 @@
 //! Board commands
 
+mod compact;
 mod get;
 pub(crate) mod init;
 mod update;
 
+pub use compact::CompactBoard;
 pub use get::GetBoard;
 pub use init::register_merge_drivers;
 pub use init::unregister_merge_drivers;
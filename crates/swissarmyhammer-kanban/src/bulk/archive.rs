@@ -0,0 +1,107 @@
+//! ArchiveTasks command
+
+use super::run_bulk;
+use crate::context::KanbanContext;
+use crate::error::{KanbanError, Result};
+use crate::task::ArchiveTask;
+use serde::Deserialize;
+use serde_json::Value;
+use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResult};
+
+/// Archive every task a filter selects.
+///
+/// Each task is archived as by `archive task`, which also drops it from the
+/// `depends_on` lists of the tasks left behind.
+#[operation(
+    verb = "archive",
+    noun = "tasks",
+    description = "Archive every task matching a filter"
+)]
+#[derive(Debug, Default, Deserialize)]
+pub struct ArchiveTasks {
+    /// Filter DSL expression selecting the tasks (e.g. `column = done && #sprint-12`)
+    pub filter: String,
+    /// Report the matching tasks without archiving them
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl ArchiveTasks {
+    /// Archive the tasks matching `filter`.
+    pub fn new(filter: impl Into<String>) -> Self {
+        Self {
+            filter: filter.into(),
+            dry_run: false,
+        }
+    }
+
+    /// Only report what would be archived.
+    pub fn with_dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+}
+
+#[async_trait]
+impl Execute<KanbanContext, KanbanError> for ArchiveTasks {
+    async fn execute(&self, ctx: &KanbanContext) -> ExecutionResult<Value, KanbanError> {
+        let result: Result<Value> = async {
+            run_bulk(ctx, &self.filter, self.dry_run, |task| {
+                Some(ArchiveTask::new(task.id.as_str()))
+            })
+            .await
+        }
+        .await;
+
+        match result {
+            Ok(value) => ExecutionResult::Success { value },
+            Err(error) => ExecutionResult::Failed { error },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::InitBoard;
+    use crate::task::{AddTask, MoveTask};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_archive_tasks_clears_the_done_column() {
+        let temp = TempDir::new().unwrap();
+        let ctx = KanbanContext::new(temp.path().join(".kanban"));
+        InitBoard::new("Test")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        for title in ["Shipped", "Also shipped", "Open"] {
+            let added = AddTask::new(title)
+                .execute(&ctx)
+                .await
+                .into_result()
+                .unwrap();
+            if title != "Open" {
+                MoveTask::to_column(added["id"].as_str().unwrap(), "done")
+                    .execute(&ctx)
+                    .await
+                    .into_result()
+                    .unwrap();
+            }
+        }
+
+        let result = ArchiveTasks::new("column = done")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(result["changed"], 2);
+
+        let ectx = ctx.entity_context().await.unwrap();
+        let live = ectx.list("task").await.unwrap();
+        assert_eq!(live.len(), 1);
+        assert_eq!(live[0].get_str("title"), Some("Open"));
+        assert_eq!(ectx.list_archived("task").await.unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,120 @@
+//! AssignTasks command
+
+use super::run_bulk;
+use crate::context::KanbanContext;
+use crate::error::{KanbanError, Result};
+use crate::task::AssignTask;
+use serde::Deserialize;
+use serde_json::Value;
+use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResult};
+
+/// Assign one actor to every task a filter selects.
+///
+/// The actor must exist. Tasks it is already assigned to are left alone.
+#[operation(
+    verb = "assign",
+    noun = "tasks",
+    description = "Assign an actor to every task matching a filter"
+)]
+#[derive(Debug, Default, Deserialize)]
+pub struct AssignTasks {
+    /// Filter DSL expression selecting the tasks (e.g. `#bug && $backend`)
+    pub filter: String,
+    /// The actor id to assign
+    pub assignee: String,
+    /// Report the matching tasks without assigning them
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl AssignTasks {
+    /// Assign `assignee` to the tasks matching `filter`.
+    pub fn new(filter: impl Into<String>, assignee: impl Into<String>) -> Self {
+        Self {
+            filter: filter.into(),
+            assignee: assignee.into(),
+            dry_run: false,
+        }
+    }
+
+    /// Only report what would be assigned.
+    pub fn with_dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+}
+
+#[async_trait]
+impl Execute<KanbanContext, KanbanError> for AssignTasks {
+    async fn execute(&self, ctx: &KanbanContext) -> ExecutionResult<Value, KanbanError> {
+        let result: Result<Value> = async {
+            let ectx = ctx.entity_context().await?;
+            if ectx.read("actor", &self.assignee).await.is_err() {
+                return Err(KanbanError::ActorNotFound {
+                    id: self.assignee.clone(),
+                });
+            }
+            run_bulk(ctx, &self.filter, self.dry_run, |task| {
+                let assigned = task.get_string_list("assignees").contains(&self.assignee);
+                (!assigned).then(|| AssignTask::new(task.id.as_str(), self.assignee.as_str()))
+            })
+            .await
+        }
+        .await;
+
+        match result {
+            Ok(value) => ExecutionResult::Success { value },
+            Err(error) => ExecutionResult::Failed { error },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actor::AddActor;
+    use crate::board::InitBoard;
+    use crate::task::AddTask;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_assign_tasks_needs_a_known_actor() {
+        let temp = TempDir::new().unwrap();
+        let ctx = KanbanContext::new(temp.path().join(".kanban"));
+        InitBoard::new("Test")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        AddActor::new("alice", "Alice")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        let added = AddTask::new("Fix crash")
+            .with_description("#bug")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        let missing = AssignTasks::new("#bug", "nobody")
+            .execute(&ctx)
+            .await
+            .into_result();
+        assert!(matches!(missing, Err(KanbanError::ActorNotFound { .. })));
+
+        let result = AssignTasks::new("#bug", "alice")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(result["changed"], 1);
+        let ectx = ctx.entity_context().await.unwrap();
+        let task = ectx
+            .read("task", added["id"].as_str().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(task.get_string_list("assignees"), vec!["alice"]);
+    }
+}
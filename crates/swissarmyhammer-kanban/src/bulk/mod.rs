@@ -0,0 +1,136 @@
+//! Bulk task operations
+//!
+//! `move tasks`, `tag tasks`, `assign tasks`, `archive tasks` and
+//! `update tasks` apply one change to every live task a filter expression
+//! selects. Each runs the matching single-task command once per task, so the
+//! rules are exactly those of `move task`, `tag task` and the rest: WIP
+//! limits, tags created on demand, dependency cleanup on archive.
+//!
+//! With `dry_run` set the operation only reports the tasks it would change.
+//! Otherwise every write shares one undo group, so a single undo reverses
+//! the whole batch, and a failure part-way through undoes the writes already
+//! made before the error is returned.
+
+mod archive;
+mod assign;
+mod mv;
+mod tag;
+mod update;
+
+pub use archive::ArchiveTasks;
+pub use assign::AssignTasks;
+pub use mv::MoveTasks;
+pub use tag::TagTasks;
+pub use update::UpdateTasks;
+
+use crate::context::KanbanContext;
+use crate::error::{KanbanError, Result};
+use crate::task::{parse_filter_expr, retain_matching_tasks};
+use crate::task_helpers::{slim_task_json, task_entity_to_json};
+use serde_json::{json, Value};
+use swissarmyhammer_entity::Entity;
+use swissarmyhammer_operations::Execute;
+
+/// The live tasks `filter` selects, in board order.
+///
+/// An empty filter is rejected rather than taken to mean every task: a bulk
+/// change to the whole board should be asked for explicitly.
+pub(crate) async fn select_tasks(ctx: &KanbanContext, filter: &str) -> Result<Vec<Entity>> {
+    let Some(expr) = parse_filter_expr(Some(filter))? else {
+        return Err(KanbanError::invalid_value(
+            "filter",
+            "a bulk operation needs a filter expression selecting its tasks",
+        ));
+    };
+    let ectx = ctx.entity_context().await?;
+    let columns = ectx.list("column").await?;
+    let mut tasks = ectx.list("task").await?;
+    retain_matching_tasks(&ectx, &mut tasks, &columns, &expr).await?;
+
+    let column_order = |task: &Entity| {
+        let column = task.get_str("position_column");
+        columns
+            .iter()
+            .find(|c| Some(c.id.as_str()) == column)
+            .and_then(|c| c.get("order").and_then(|v| v.as_u64()))
+            .unwrap_or(u64::MAX)
+    };
+    tasks.sort_by_cached_key(|t| {
+        (
+            column_order(t),
+            t.get_str("position_ordinal")
+                .unwrap_or_default()
+                .to_string(),
+        )
+    });
+    Ok(tasks)
+}
+
+/// Apply `plan` to every task `filter` selects.
+///
+/// `plan` returns the single-task command for a task, or `None` when the
+/// task already has the requested state; those tasks are reported as
+/// unchanged and nothing is written for them. The response lists the
+/// selected tasks as slim projections with the ids of those changed (or,
+/// on a dry run, those that would be).
+pub(crate) async fn run_bulk<C, F>(
+    ctx: &KanbanContext,
+    filter: &str,
+    dry_run: bool,
+    plan: F,
+) -> Result<Value>
+where
+    C: Execute<KanbanContext, KanbanError> + Sync,
+    F: Fn(&Entity) -> Option<C>,
+{
+    let tasks = select_tasks(ctx, filter).await?;
+    let planned: Vec<(&Entity, C)> = tasks
+        .iter()
+        .filter_map(|task| plan(task).map(|cmd| (task, cmd)))
+        .collect();
+    let changed: Vec<&str> = planned.iter().map(|(t, _)| t.id.as_str()).collect();
+    let response = json!({
+        "dry_run": dry_run,
+        "matched": tasks.len(),
+        "changed": changed.len(),
+        "unchanged": tasks.len() - changed.len(),
+        "ids": changed,
+        "tasks": tasks
+            .iter()
+            .map(|t| slim_task_json(&task_entity_to_json(t)))
+            .collect::<Vec<_>>(),
+    });
+    if dry_run || planned.is_empty() {
+        return Ok(response);
+    }
+
+    let ectx = ctx.entity_context().await?;
+    let store = ectx.store_context().cloned();
+    let depth = match &store {
+        Some(sc) => sc.undo_depth().await,
+        None => 0,
+    };
+    let outcome: Result<()> = async {
+        let _undo_group = match &store {
+            Some(sc) => Some(sc.begin_undo_group().await),
+            None => None,
+        };
+        for (_, cmd) in &planned {
+            cmd.execute(ctx).await.into_result()?;
+        }
+        Ok(())
+    }
+    .await;
+
+    if let Err(error) = outcome {
+        if let Some(sc) = &store {
+            if sc.undo_depth().await > depth {
+                if let Err(undo_error) = sc.undo().await {
+                    tracing::warn!(%undo_error, "failed to roll back partial bulk operation");
+                }
+            }
+        }
+        return Err(error);
+    }
+    Ok(response)
+}
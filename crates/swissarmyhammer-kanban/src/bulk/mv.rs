@@ -0,0 +1,230 @@
+//! MoveTasks command
+
+use super::{run_bulk, select_tasks};
+use crate::column::read_column_wip_limit;
+use crate::context::KanbanContext;
+use crate::error::{KanbanError, Result};
+use crate::task::MoveTask;
+use serde::Deserialize;
+use serde_json::Value;
+use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResult};
+
+/// Move every task a filter selects to the end of one column.
+///
+/// Tasks already in the column stay where they are. The column's WIP limit
+/// is checked for the whole batch before anything moves, so a batch that
+/// would overfill the column fails without moving any task.
+#[operation(
+    verb = "move",
+    noun = "tasks",
+    description = "Move every task matching a filter to a column"
+)]
+#[derive(Debug, Default, Deserialize)]
+pub struct MoveTasks {
+    /// Filter DSL expression selecting the tasks (e.g. `#sprint-12 && column = review`)
+    pub filter: String,
+    /// Target column
+    pub column: String,
+    /// Report the matching tasks without moving them
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Move even if the target column goes over its WIP limit
+    #[serde(default)]
+    pub override_wip_limit: bool,
+}
+
+impl MoveTasks {
+    /// Move the tasks matching `filter` to `column`.
+    pub fn new(filter: impl Into<String>, column: impl Into<String>) -> Self {
+        Self {
+            filter: filter.into(),
+            column: column.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Only report what would move.
+    pub fn with_dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+
+    /// Allow the target column to go over its WIP limit.
+    pub fn with_override_wip_limit(mut self) -> Self {
+        self.override_wip_limit = true;
+        self
+    }
+
+    /// Reject the batch up front when it would push the column over its limit.
+    async fn check_wip_limit(&self, ctx: &KanbanContext) -> Result<()> {
+        let ectx = ctx.entity_context().await?;
+        let Some(limit) = read_column_wip_limit(&ectx, &self.column).await? else {
+            return Ok(());
+        };
+        let in_column = |t: &swissarmyhammer_entity::Entity| {
+            t.get_str("position_column") == Some(self.column.as_str())
+        };
+        let present = ectx
+            .list("task")
+            .await?
+            .iter()
+            .filter(|t| in_column(t))
+            .count();
+        let incoming = select_tasks(ctx, &self.filter)
+            .await?
+            .iter()
+            .filter(|t| !in_column(t))
+            .count();
+        if incoming > 0 && present + incoming > limit {
+            return Err(KanbanError::WipLimitExceeded {
+                column: self.column.clone(),
+                limit,
+                count: present,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Execute<KanbanContext, KanbanError> for MoveTasks {
+    async fn execute(&self, ctx: &KanbanContext) -> ExecutionResult<Value, KanbanError> {
+        let result: Result<Value> = async {
+            if !self.dry_run && !self.override_wip_limit {
+                self.check_wip_limit(ctx).await?;
+            }
+            run_bulk(ctx, &self.filter, self.dry_run, |task| {
+                if task.get_str("position_column") == Some(self.column.as_str()) {
+                    return None;
+                }
+                let cmd = MoveTask::to_column(task.id.as_str(), self.column.as_str());
+                Some(if self.override_wip_limit {
+                    cmd.with_override_wip_limit()
+                } else {
+                    cmd
+                })
+            })
+            .await
+        }
+        .await;
+
+        match result {
+            Ok(value) => ExecutionResult::Success { value },
+            Err(error) => ExecutionResult::Failed { error },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::InitBoard;
+    use crate::column::UpdateColumn;
+    use crate::task::{AddTask, TagTask};
+    use tempfile::TempDir;
+
+    async fn setup() -> (TempDir, KanbanContext, Vec<String>) {
+        let temp = TempDir::new().unwrap();
+        let ctx = KanbanContext::new(temp.path().join(".kanban"));
+        InitBoard::new("Test")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        let mut ids = Vec::new();
+        for (title, tag) in [("One", "sprint"), ("Two", "sprint"), ("Three", "later")] {
+            let added = AddTask::new(title)
+                .execute(&ctx)
+                .await
+                .into_result()
+                .unwrap();
+            let id = added["id"].as_str().unwrap().to_string();
+            TagTask::new(id.as_str(), tag)
+                .execute(&ctx)
+                .await
+                .into_result()
+                .unwrap();
+            ids.push(id);
+        }
+        (temp, ctx, ids)
+    }
+
+    async fn column_of(ctx: &KanbanContext, id: &str) -> String {
+        let ectx = ctx.entity_context().await.unwrap();
+        let task = ectx.read("task", id).await.unwrap();
+        task.get_str("position_column").unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_move_tasks_moves_only_the_matches() {
+        let (_temp, ctx, ids) = setup().await;
+
+        let preview = MoveTasks::new("#sprint", "done")
+            .with_dry_run()
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(preview["matched"], 2);
+        assert_eq!(preview["changed"], 2);
+        assert_eq!(
+            column_of(&ctx, &ids[0]).await,
+            "todo",
+            "dry run writes nothing"
+        );
+
+        let result = MoveTasks::new("#sprint", "done")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(result["dry_run"], false);
+        assert_eq!(column_of(&ctx, &ids[0]).await, "done");
+        assert_eq!(column_of(&ctx, &ids[1]).await, "done");
+        assert_eq!(column_of(&ctx, &ids[2]).await, "todo");
+
+        let again = MoveTasks::new("#sprint", "done")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(again["changed"], 0);
+        assert_eq!(again["unchanged"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_move_tasks_checks_the_wip_limit_for_the_whole_batch() {
+        let (_temp, ctx, ids) = setup().await;
+        UpdateColumn::new("doing")
+            .with_wip_limit(1)
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        let result = MoveTasks::new("#sprint", "doing")
+            .execute(&ctx)
+            .await
+            .into_result();
+        assert!(matches!(result, Err(KanbanError::WipLimitExceeded { .. })));
+        assert_eq!(column_of(&ctx, &ids[0]).await, "todo", "nothing moved");
+
+        MoveTasks::new("#sprint", "doing")
+            .with_override_wip_limit()
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(column_of(&ctx, &ids[1]).await, "doing");
+    }
+
+    #[tokio::test]
+    async fn test_bulk_operations_require_a_filter() {
+        let (_temp, ctx, _ids) = setup().await;
+        let result = MoveTasks::new("  ", "done")
+            .execute(&ctx)
+            .await
+            .into_result();
+        assert!(matches!(result, Err(KanbanError::InvalidValue { .. })));
+    }
+}
@@ -0,0 +1,125 @@
+//! TagTasks command
+
+use super::run_bulk;
+use crate::context::KanbanContext;
+use crate::error::{KanbanError, Result};
+use crate::task::TagTask;
+use serde::Deserialize;
+use serde_json::Value;
+use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResult};
+
+/// Add a tag to every task a filter selects.
+///
+/// The tag is a forgiving reference, as for `tag task`; a tag name that does
+/// not exist yet is created. Tasks that already carry it are left alone.
+#[operation(
+    verb = "tag",
+    noun = "tasks",
+    description = "Add a tag to every task matching a filter"
+)]
+#[derive(Debug, Default, Deserialize)]
+pub struct TagTasks {
+    /// Filter DSL expression selecting the tasks (e.g. `@alice && !#done`)
+    pub filter: String,
+    /// The tag to add (name, full ULID, `^<short>` or short id)
+    pub tag: String,
+    /// Report the matching tasks without tagging them
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl TagTasks {
+    /// Tag the tasks matching `filter` with `tag`.
+    pub fn new(filter: impl Into<String>, tag: impl Into<String>) -> Self {
+        Self {
+            filter: filter.into(),
+            tag: tag.into(),
+            dry_run: false,
+        }
+    }
+
+    /// Only report what would be tagged.
+    pub fn with_dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+}
+
+#[async_trait]
+impl Execute<KanbanContext, KanbanError> for TagTasks {
+    async fn execute(&self, ctx: &KanbanContext) -> ExecutionResult<Value, KanbanError> {
+        let result: Result<Value> = async {
+            let name = self.tag.trim_start_matches('#');
+            run_bulk(ctx, &self.filter, self.dry_run, |task| {
+                let tagged = task.get_string_list("tags").iter().any(|t| t == name);
+                (!tagged).then(|| TagTask::new(task.id.as_str(), self.tag.as_str()))
+            })
+            .await
+        }
+        .await;
+
+        match result {
+            Ok(value) => ExecutionResult::Success { value },
+            Err(error) => ExecutionResult::Failed { error },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::InitBoard;
+    use crate::task::{AddTask, MoveTask};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_tag_tasks_tags_matches_once() {
+        let temp = TempDir::new().unwrap();
+        let ctx = KanbanContext::new(temp.path().join(".kanban"));
+        InitBoard::new("Test")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        let mut ids = Vec::new();
+        for title in ["One", "Two", "Three"] {
+            let added = AddTask::new(title)
+                .execute(&ctx)
+                .await
+                .into_result()
+                .unwrap();
+            ids.push(added["id"].as_str().unwrap().to_string());
+        }
+        MoveTask::to_column(ids[2].as_str(), "done")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        let result = TagTasks::new("column = todo", "sprint-12")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(result["changed"], 2);
+
+        let ectx = ctx.entity_context().await.unwrap();
+        for (id, tagged) in ids.iter().zip([true, true, false]) {
+            let task = ectx.read("task", id).await.unwrap();
+            assert_eq!(
+                task.get_string_list("tags")
+                    .contains(&"sprint-12".to_string()),
+                tagged
+            );
+        }
+
+        let again = TagTasks::new("column = todo", "sprint-12")
+            .with_dry_run()
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(again["matched"], 2);
+        assert_eq!(again["changed"], 0);
+    }
+}
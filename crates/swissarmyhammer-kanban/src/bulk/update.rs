@@ -0,0 +1,147 @@
+//! UpdateTasks command
+
+use super::run_bulk;
+use crate::context::KanbanContext;
+use crate::entity::UpdateEntityField;
+use crate::error::{KanbanError, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResult};
+
+/// Set one field to the same value on every task a filter selects.
+///
+/// The field is set as by `update entity field`, so it must be a task field
+/// and the value passes the same validation. Tasks already holding the value
+/// are left alone.
+#[operation(
+    verb = "update",
+    noun = "tasks",
+    description = "Set a field on every task matching a filter"
+)]
+#[derive(Debug, Default, Deserialize)]
+pub struct UpdateTasks {
+    /// Filter DSL expression selecting the tasks (e.g. `#sprint-12 && !has:due`)
+    pub filter: String,
+    /// The task field to set (e.g. "project", "due")
+    pub field: String,
+    /// The new value (null to clear the field)
+    #[serde(default)]
+    pub value: Value,
+    /// Report the matching tasks without updating them
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl UpdateTasks {
+    /// Set `field` to `value` on the tasks matching `filter`.
+    pub fn new(filter: impl Into<String>, field: impl Into<String>, value: Value) -> Self {
+        Self {
+            filter: filter.into(),
+            field: field.into(),
+            value,
+            dry_run: false,
+        }
+    }
+
+    /// Only report what would be updated.
+    pub fn with_dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+}
+
+#[async_trait]
+impl Execute<KanbanContext, KanbanError> for UpdateTasks {
+    async fn execute(&self, ctx: &KanbanContext) -> ExecutionResult<Value, KanbanError> {
+        let result: Result<Value> = async {
+            run_bulk(ctx, &self.filter, self.dry_run, |task| {
+                let current = task.get(&self.field).unwrap_or(&Value::Null);
+                (*current != self.value).then(|| {
+                    UpdateEntityField::new(
+                        "task",
+                        task.id.as_str(),
+                        self.field.as_str(),
+                        self.value.clone(),
+                    )
+                })
+            })
+            .await
+        }
+        .await;
+
+        match result {
+            Ok(value) => ExecutionResult::Success { value },
+            Err(error) => ExecutionResult::Failed { error },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::InitBoard;
+    use crate::task::AddTask;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_update_tasks_sets_the_field_on_matches() {
+        let temp = TempDir::new().unwrap();
+        let ctx = KanbanContext::new(temp.path().join(".kanban"));
+        InitBoard::new("Test")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        for (title, description) in [("One", "#sprint"), ("Two", "#sprint"), ("Three", "")] {
+            AddTask::new(title)
+                .with_description(description)
+                .execute(&ctx)
+                .await
+                .into_result()
+                .unwrap();
+        }
+
+        let result = UpdateTasks::new("#sprint", "due", json!("2026-05-01"))
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(result["changed"], 2);
+
+        let ectx = ctx.entity_context().await.unwrap();
+        let mut dues: Vec<Option<String>> = ectx
+            .list("task")
+            .await
+            .unwrap()
+            .iter()
+            .map(|t| t.get_str("due").map(str::to_string))
+            .collect();
+        dues.sort();
+        assert_eq!(
+            dues,
+            vec![None, Some("2026-05-01".into()), Some("2026-05-01".into())]
+        );
+
+        let parent = ectx
+            .list("task")
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|t| t.get_str("title") == Some("One"))
+            .unwrap();
+        let looped = UpdateTasks::new("#sprint", "parent", json!(parent.id.as_str()))
+            .execute(&ctx)
+            .await
+            .into_result();
+        assert!(looped.is_err(), "the parent is one of the selected tasks");
+        let parent = ectx.read("task", parent.id.as_str()).await.unwrap();
+        assert!(parent.get_str("parent").is_none());
+
+        let unknown = UpdateTasks::new("#sprint", "no_such_field", json!(1))
+            .execute(&ctx)
+            .await
+            .into_result();
+        assert!(matches!(unknown, Err(KanbanError::InvalidValue { .. })));
+    }
+}
@@ -22,6 +22,8 @@ pub struct AddColumn {
     pub name: String,
     /// Optional position in column order
     pub order: Option<usize>,
+    /// Optional work-in-progress limit (max tasks in the column; 0 = unlimited)
+    pub wip_limit: Option<usize>,
 }
 
 impl AddColumn {
@@ -31,6 +33,7 @@ impl AddColumn {
             id: id.into(),
             name: name.into(),
             order: None,
+            wip_limit: None,
         }
     }
 
@@ -39,6 +42,12 @@ impl AddColumn {
         self.order = Some(order);
         self
     }
+
+    /// Set the work-in-progress limit
+    pub fn with_wip_limit(mut self, wip_limit: usize) -> Self {
+        self.wip_limit = Some(wip_limit);
+        self
+    }
 }
 
 #[async_trait]
@@ -68,6 +77,9 @@ impl Execute<KanbanContext, KanbanError> for AddColumn {
             let mut entity = Entity::new("column", self.id.as_str());
             entity.set("name", json!(self.name));
             entity.set("order", json!(order));
+            if let Some(wip_limit) = self.wip_limit.filter(|limit| *limit > 0) {
+                entity.set("wip_limit", json!(wip_limit));
+            }
 
             ectx.write(&entity).await?;
 
@@ -83,11 +95,14 @@ impl Execute<KanbanContext, KanbanError> for AddColumn {
 }
 
 /// Convert a column Entity to the API JSON format
+///
+/// `wip_limit` is `null` when the column is unlimited.
 pub(crate) fn column_entity_to_json(entity: &Entity) -> Value {
     json!({
         "id": entity.id,
         "name": entity.get_str("name").unwrap_or(""),
         "order": entity.get("order").and_then(|v| v.as_u64()).unwrap_or(0),
+        "wip_limit": crate::column::column_wip_limit(entity),
     })
 }
 
@@ -123,6 +138,26 @@ mod tests {
         assert_eq!(result["name"], "Blocked");
     }
 
+    #[tokio::test]
+    async fn test_add_column_with_wip_limit() {
+        let (_temp, ctx) = setup().await;
+
+        let result = AddColumn::new("review", "Review")
+            .with_wip_limit(3)
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(result["wip_limit"], 3);
+
+        let unlimited = AddColumn::new("blocked", "Blocked")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert!(unlimited["wip_limit"].is_null());
+    }
+
     #[tokio::test]
     async fn test_add_column_duplicate() {
         let (_temp, ctx) = setup().await;
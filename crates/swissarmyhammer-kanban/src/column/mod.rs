@@ -5,6 +5,7 @@ mod delete;
 mod get;
 mod list;
 mod update;
+mod wip;
 
 pub(crate) use add::column_entity_to_json;
 pub use add::AddColumn;
@@ -12,3 +13,6 @@ pub use delete::DeleteColumn;
 pub use get::GetColumn;
 pub use list::ListColumns;
 pub use update::UpdateColumn;
+pub use wip::column_wip_limit;
+pub(crate) use wip::read_column_wip_limit;
+pub(crate) use wip::{enforce_wip_limit, enforce_wip_limit_for_all};
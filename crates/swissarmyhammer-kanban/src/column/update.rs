@@ -12,7 +12,7 @@ use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResul
 #[operation(
     verb = "update",
     noun = "column",
-    description = "Update a column's name or order"
+    description = "Update a column's name, order or WIP limit"
 )]
 #[derive(Debug, Deserialize, Serialize)]
 pub struct UpdateColumn {
@@ -22,6 +22,8 @@ pub struct UpdateColumn {
     pub name: Option<String>,
     /// New position in column order
     pub order: Option<usize>,
+    /// New work-in-progress limit (0 clears the limit)
+    pub wip_limit: Option<usize>,
 }
 
 impl UpdateColumn {
@@ -30,6 +32,7 @@ impl UpdateColumn {
             id: id.into(),
             name: None,
             order: None,
+            wip_limit: None,
         }
     }
 
@@ -42,6 +45,12 @@ impl UpdateColumn {
         self.order = Some(order);
         self
     }
+
+    /// Set the work-in-progress limit; `0` clears it.
+    pub fn with_wip_limit(mut self, wip_limit: usize) -> Self {
+        self.wip_limit = Some(wip_limit);
+        self
+    }
 }
 
 #[async_trait]
@@ -60,6 +69,13 @@ impl Execute<KanbanContext, KanbanError> for UpdateColumn {
             if let Some(order) = self.order {
                 entity.set("order", json!(order));
             }
+            match self.wip_limit {
+                Some(0) => {
+                    entity.remove("wip_limit");
+                }
+                Some(limit) => entity.set("wip_limit", json!(limit)),
+                None => {}
+            }
 
             ectx.write(&entity).await?;
             Ok(column_entity_to_json(&entity))
@@ -164,6 +180,27 @@ mod tests {
         assert_eq!(result["order"], 10);
     }
 
+    #[tokio::test]
+    async fn test_update_column_wip_limit_set_and_clear() {
+        let (_temp, ctx) = setup().await;
+
+        let result = UpdateColumn::new("doing")
+            .with_wip_limit(2)
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(result["wip_limit"], 2);
+
+        let cleared = UpdateColumn::new("doing")
+            .with_wip_limit(0)
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert!(cleared["wip_limit"].is_null());
+    }
+
     #[tokio::test]
     async fn test_update_column_not_found() {
         let (_temp, ctx) = setup().await;
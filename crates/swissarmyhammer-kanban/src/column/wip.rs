@@ -0,0 +1,276 @@
+//! Work-in-progress limits for columns.
+//!
+//! A column's optional `wip_limit` field caps how many tasks may sit in it.
+//! The limit is enforced at the points where a task *enters* a column —
+//! [`AddTask`](crate::task::AddTask), [`MoveTask`](crate::task::MoveTask) and
+//! [`transfer_task`](crate::cross_board::transfer_task) — so reordering a task
+//! within its own column never trips it. An unset or zero limit means the
+//! column is unlimited.
+//!
+//! Each entry point carries an `override_wip_limit` flag. When set, the move is
+//! allowed even though it pushes the column over its limit; the column then
+//! shows up as over-limit through the `OVER_WIP` virtual tag
+//! ([`crate::virtual_tags::OverWipStrategy`]).
+
+use crate::error::{KanbanError, Result};
+use swissarmyhammer_entity::{Entity, EntityContext, EntityError};
+
+/// Read a column entity's WIP limit.
+///
+/// Returns `None` when the field is unset, not a non-negative integer, or
+/// zero — all of which mean "unlimited".
+pub fn column_wip_limit(column: &Entity) -> Option<usize> {
+    column
+        .get("wip_limit")
+        .and_then(|v| v.as_u64())
+        .filter(|limit| *limit > 0)
+        .map(|limit| limit as usize)
+}
+
+/// The WIP limit of the column `column_id`, or `None` when it is unlimited
+/// or does not exist. Any other failure to read the column is an error, so
+/// a broken column file never silently lifts its limit.
+pub(crate) async fn read_column_wip_limit(
+    ectx: &EntityContext,
+    column_id: &str,
+) -> Result<Option<usize>> {
+    match ectx.read("column", column_id).await {
+        Ok(column) => Ok(column_wip_limit(&column)),
+        Err(EntityError::NotFound { .. }) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Reject a task entering `column_id` when the column is at its WIP limit.
+///
+/// `task_id` names the task being placed, if it already exists. A task that is
+/// already in `column_id` is a reorder, not an entry, and always passes; it is
+/// also excluded from the occupancy count. A column that does not exist yet
+/// (the auto-create path of `MoveTask`) has no limit.
+///
+/// With `allow_override` set the check still runs but never fails, so callers
+/// keep a single code path; the over-limit state is then reported by the
+/// `OVER_WIP` virtual tag rather than by an error.
+pub(crate) async fn enforce_wip_limit(
+    ectx: &EntityContext,
+    column_id: &str,
+    task_id: Option<&str>,
+    allow_override: bool,
+) -> Result<()> {
+    let Some(limit) = read_column_wip_limit(ectx, column_id).await? else {
+        return Ok(());
+    };
+
+    let tasks = ectx.list("task").await?;
+    if let Some(id) = task_id {
+        let already_here = tasks
+            .iter()
+            .any(|t| t.id.as_str() == id && t.get_str("position_column") == Some(column_id));
+        if already_here {
+            return Ok(());
+        }
+    }
+
+    let count = tasks
+        .iter()
+        .filter(|t| t.get_str("position_column") == Some(column_id))
+        .filter(|t| task_id != Some(t.id.as_str()))
+        .count();
+    if count < limit {
+        return Ok(());
+    }
+
+    if allow_override {
+        tracing::info!(
+            column = column_id,
+            limit,
+            count,
+            "WIP limit overridden; column will be over its limit"
+        );
+        return Ok(());
+    }
+    Err(KanbanError::WipLimitExceeded {
+        column: column_id.to_string(),
+        limit,
+        count,
+    })
+}
+
+/// [`enforce_wip_limit`] for several existing tasks entering `column_id`
+/// together, as when a parent's subtasks follow it into the column.
+///
+/// Tasks already in the column are not entries; the rest must all fit. The
+/// check is all-or-nothing, so a move never leaves part of the set behind.
+pub(crate) async fn enforce_wip_limit_for_all(
+    ectx: &EntityContext,
+    column_id: &str,
+    task_ids: &[String],
+    allow_override: bool,
+) -> Result<()> {
+    let Some(limit) = read_column_wip_limit(ectx, column_id).await? else {
+        return Ok(());
+    };
+
+    let tasks = ectx.list("task").await?;
+    let in_column = |t: &&Entity| t.get_str("position_column") == Some(column_id);
+    let count = tasks.iter().filter(in_column).count();
+    let entering = tasks
+        .iter()
+        .filter(|t| task_ids.iter().any(|id| id == t.id.as_str()))
+        .filter(|t| !in_column(t))
+        .count();
+    if entering == 0 || count + entering <= limit {
+        return Ok(());
+    }
+
+    if allow_override {
+        tracing::info!(
+            column = column_id,
+            limit,
+            count,
+            entering,
+            "WIP limit overridden; column will be over its limit"
+        );
+        return Ok(());
+    }
+    Err(KanbanError::WipLimitExceeded {
+        column: column_id.to_string(),
+        limit,
+        count,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::InitBoard;
+    use crate::column::UpdateColumn;
+    use crate::context::KanbanContext;
+    use crate::task::{AddTask, MoveTask};
+    use crate::Execute;
+    use tempfile::TempDir;
+
+    async fn setup() -> (TempDir, KanbanContext) {
+        let temp = TempDir::new().unwrap();
+        let kanban_dir = temp.path().join(".kanban");
+        let ctx = KanbanContext::new(kanban_dir);
+        InitBoard::new("Test")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        (temp, ctx)
+    }
+
+    async fn add_task(ctx: &KanbanContext, title: &str) -> String {
+        let result = AddTask::new(title)
+            .execute(ctx)
+            .await
+            .into_result()
+            .unwrap();
+        result["id"].as_str().unwrap().to_string()
+    }
+
+    async fn set_limit(ctx: &KanbanContext, column: &str, limit: usize) {
+        UpdateColumn::new(column)
+            .with_wip_limit(limit)
+            .execute(ctx)
+            .await
+            .into_result()
+            .unwrap();
+    }
+
+    #[test]
+    fn zero_and_missing_limits_are_unlimited() {
+        let mut column = Entity::new("column", "doing");
+        assert_eq!(column_wip_limit(&column), None);
+        column.set("wip_limit", serde_json::json!(0));
+        assert_eq!(column_wip_limit(&column), None);
+        column.set("wip_limit", serde_json::json!(3));
+        assert_eq!(column_wip_limit(&column), Some(3));
+    }
+
+    #[tokio::test]
+    async fn move_into_full_column_is_rejected() {
+        let (_temp, ctx) = setup().await;
+        set_limit(&ctx, "doing", 1).await;
+
+        let a = add_task(&ctx, "A").await;
+        let b = add_task(&ctx, "B").await;
+        MoveTask::to_column(a.as_str(), "doing")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        let result = MoveTask::to_column(b.as_str(), "doing")
+            .execute(&ctx)
+            .await
+            .into_result();
+        match result {
+            Err(KanbanError::WipLimitExceeded {
+                column,
+                limit,
+                count,
+            }) => {
+                assert_eq!(column, "doing");
+                assert_eq!(limit, 1);
+                assert_eq!(count, 1);
+            }
+            other => panic!("expected WipLimitExceeded, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn override_allows_exceeding_the_limit() {
+        let (_temp, ctx) = setup().await;
+        set_limit(&ctx, "doing", 1).await;
+
+        let a = add_task(&ctx, "A").await;
+        let b = add_task(&ctx, "B").await;
+        for id in [&a, &b] {
+            MoveTask::to_column(id.as_str(), "doing")
+                .with_override_wip_limit()
+                .execute(&ctx)
+                .await
+                .into_result()
+                .unwrap();
+        }
+
+        let ectx = ctx.entity_context().await.unwrap();
+        let moved = ectx.read("task", &b).await.unwrap();
+        assert_eq!(moved.get_str("position_column"), Some("doing"));
+    }
+
+    #[tokio::test]
+    async fn reorder_within_a_full_column_is_allowed() {
+        let (_temp, ctx) = setup().await;
+        let a = add_task(&ctx, "A").await;
+        let b = add_task(&ctx, "B").await;
+        set_limit(&ctx, "todo", 2).await;
+
+        MoveTask::to_column(b.as_str(), "todo")
+            .with_before(a.as_str())
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn add_task_into_full_column_is_rejected() {
+        let (_temp, ctx) = setup().await;
+        set_limit(&ctx, "todo", 1).await;
+        add_task(&ctx, "A").await;
+
+        let result = AddTask::new("B").execute(&ctx).await.into_result();
+        assert!(matches!(result, Err(KanbanError::WipLimitExceeded { .. })));
+
+        AddTask::new("B")
+            .with_override_wip_limit()
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+    }
+}
@@ -54,6 +54,10 @@ fn register_task(map: &mut CmdMap) {
         "task.doThisNext".into(),
         Arc::new(task_commands::DoThisNextCmd),
     );
+    map.insert(
+        "task.promoteChecklistItem".into(),
+        Arc::new(task_commands::PromoteChecklistItemCmd),
+    );
 }
 
 fn register_clipboard(map: &mut CmdMap) {
@@ -377,7 +381,7 @@ mod tests {
     #[test]
     fn register_commands_returns_expected_count() {
         let cmds = register_commands();
-        // 3 task (move, untag, doThisNext) — task.add retired in favour of
+        // 4 task (move, untag, doThisNext, promoteChecklistItem) — task.add retired in favour of
         // dynamic `entity.add:task`; task.delete retired in favour of the
         // cross-cutting `entity.delete` auto-emit.
         // + 3 clipboard + 5 entity (add, update_field, delete, archive, unarchive)
@@ -410,8 +414,9 @@ mod tests {
         // + 1 board (update.board) — wraps `crate::board::UpdateBoard` so
         //   board-metadata editors can persist `name`/`description`/`model`
         //   through the unified dispatcher.
-        // = 63 (68 prior, -5 for the retired `ai.*` commands).
-        assert_eq!(cmds.len(), 63);
+        // = 64 (68 prior, -5 for the retired `ai.*` commands, +1 for
+        //   `task.promoteChecklistItem`).
+        assert_eq!(cmds.len(), 64);
     }
 
     // =========================================================================
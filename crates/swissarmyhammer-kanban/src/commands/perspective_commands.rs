@@ -9,8 +9,10 @@ use crate::perspective::{
     AddPerspective, DeletePerspective, GetPerspective, ListPerspectives, Perspective,
     RenamePerspective, SortDirection, SortEntry, UpdatePerspective,
 };
-use crate::task_helpers::{enrich_all_task_entities, filter_task_ids, EntitySlugRegistry};
-use crate::virtual_tags::default_virtual_tag_registry;
+use crate::task_helpers::{
+    enrich_all_task_entities_with_wip_limits, filter_task_ids, EntitySlugRegistry,
+};
+use crate::virtual_tags::{default_virtual_tag_registry, ColumnWipLimits};
 use async_trait::async_trait;
 use serde_json::Value;
 use swissarmyhammer_commands::{Command, CommandContext, CommandError};
@@ -53,7 +55,7 @@ fn validate_filter(filter: &str) -> Result<(), CommandError> {
         return Ok(());
     }
     swissarmyhammer_filter_expr::parse(filter).map_err(|errors| {
-        let messages: Vec<String> = errors.iter().map(|e| e.message.clone()).collect();
+        let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
         CommandError::ExecutionFailed(format!(
             "invalid filter expression: {}",
             messages.join("; ")
@@ -997,7 +999,13 @@ pub async fn evaluate_perspective_filter(
         .unwrap_or("done");
 
     let virtual_tag_registry = default_virtual_tag_registry();
-    enrich_all_task_entities(&mut tasks, terminal_column, virtual_tag_registry);
+    let wip_limits = ColumnWipLimits::from_columns(&columns);
+    enrich_all_task_entities_with_wip_limits(
+        &mut tasks,
+        terminal_column,
+        &wip_limits,
+        virtual_tag_registry,
+    );
 
     let slug_registry = EntitySlugRegistry::build(&projects, &actors, &tasks);
     filter_task_ids(&tasks, filter, &slug_registry).map_err(CommandError::ExecutionFailed)
@@ -1,4 +1,5 @@
-//! Task-related command implementations: move, untag, doThisNext.
+//! Task-related command implementations: move, untag, doThisNext,
+//! promoteChecklistItem.
 //!
 //! Task deletion is served by the cross-cutting `entity.delete` command (see
 //! `entity_commands::DeleteEntityCmd`), so there is no type-specific
@@ -252,6 +253,37 @@ impl Command for DoThisNextCmd {
     }
 }
 
+/// Turn one checklist item of a task's description into a subtask.
+///
+/// Requires `task` in the scope chain and the 0-based checkbox index as the
+/// `item` arg. The inspector's markdown display sends this from a checkbox
+/// line; the work is done by [`crate::task::PromoteTask`].
+pub struct PromoteChecklistItemCmd;
+
+#[async_trait]
+impl Command for PromoteChecklistItemCmd {
+    fn available(&self, ctx: &CommandContext) -> bool {
+        ctx.has_in_scope("task")
+    }
+
+    async fn execute(&self, ctx: &CommandContext) -> swissarmyhammer_commands::Result<Value> {
+        let kanban = ctx.require_extension::<KanbanContext>()?;
+        let task_id = ctx
+            .resolve_entity_id("task")
+            .ok_or_else(|| CommandError::MissingScope("task".into()))?;
+        let item = ctx
+            .arg("item")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| CommandError::MissingArg("item".into()))?;
+
+        run_op(
+            &crate::task::PromoteTask::new(task_id, item as usize),
+            &kanban,
+        )
+        .await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
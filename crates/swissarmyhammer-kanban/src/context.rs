@@ -8,6 +8,7 @@ use crate::defaults::{
     builtin_view_definitions, kanban_compute_engine, KanbanLookup,
 };
 use crate::error::{KanbanError, Result};
+use crate::hooks::{HookRunner, HookTrust, HooksConfig, HOOK_TRUST_FILE};
 use crate::types::{ActorId, ColumnId, TagId, TaskId};
 use fs2::FileExt;
 use std::path::{Path, PathBuf};
@@ -48,6 +49,9 @@ pub struct KanbanContext {
     /// lifetime is tied to the `KanbanContext`'s; dropping the context
     /// triggers the watcher's `Drop` which sends its shutdown signal.
     entity_watcher: OnceCell<EntityWatcher>,
+    /// Dispatcher for `.kanban/hooks.yaml`, started by `start_hooks()`. Like
+    /// the watcher, it lives as long as the context.
+    hook_runner: OnceCell<HookRunner>,
     /// View registry (populated via `open()`, None when created via `new()`)
     views: Option<RwLock<ViewsContext>>,
     /// Perspective registry — lazy-initialized on first access.
@@ -94,6 +98,7 @@ impl KanbanContext {
             entities: OnceCell::new(),
             entity_cache: OnceCell::new(),
             entity_watcher: OnceCell::new(),
+            hook_runner: OnceCell::new(),
             views: None,
             perspectives: OnceCell::new(),
             derive_registry: Arc::new(crate::derive_handlers::kanban_derive_registry()),
@@ -144,6 +149,7 @@ impl KanbanContext {
             entities: cell,
             entity_cache: OnceCell::new(),
             entity_watcher: OnceCell::new(),
+            hook_runner: OnceCell::new(),
             views: Some(RwLock::new(views)),
             perspectives: persp_cell,
             derive_registry: Arc::new(crate::derive_handlers::kanban_derive_registry()),
@@ -503,6 +509,63 @@ impl KanbanContext {
         }
     }
 
+    /// Start dispatching the board's `hooks.yaml` hooks. Idempotent — a
+    /// second call is a no-op.
+    ///
+    /// Returns `Ok(false)` when the board configures no hooks, in which case
+    /// nothing is started and no entities are loaded. Hooks fire for changes
+    /// made after this call, so one-shot callers start them before running
+    /// their operations and call [`drain_hooks`](Self::drain_hooks) after.
+    ///
+    /// Command hooks run only when the user's `~/.sah/trusted_hooks.yaml`
+    /// lists this board; see [`HookTrust`].
+    pub async fn start_hooks(&self) -> Result<bool> {
+        self.start_hooks_with_trust(&HookTrust::load_user()?).await
+    }
+
+    /// [`start_hooks`](Self::start_hooks) with an explicit trust list instead
+    /// of the user's file.
+    pub async fn start_hooks_with_trust(&self, trust: &HookTrust) -> Result<bool> {
+        if self.hook_runner.initialized() {
+            return Ok(true);
+        }
+        let mut config = HooksConfig::load(&self.root)?;
+        if !trust.allows(&self.root) {
+            config.hooks.retain(|hook| {
+                if hook.command.is_some() {
+                    tracing::warn!(
+                        hook = %hook.name,
+                        root = %self.root.display(),
+                        "command hook skipped: board is not listed in ~/.sah/{HOOK_TRUST_FILE}"
+                    );
+                }
+                hook.command.is_none()
+            });
+        }
+        if config.hooks.is_empty() {
+            return Ok(false);
+        }
+        let ectx = self.entity_context().await?;
+        let Some(cache) = self.entity_cache() else {
+            return Ok(false);
+        };
+        self.hook_runner
+            .get_or_try_init(|| HookRunner::start(self.root.clone(), config, ectx, &cache))
+            .await?;
+        Ok(true)
+    }
+
+    /// Stop the dispatcher and wait a few seconds at most for the hooks
+    /// triggered so far to be delivered (or dead-lettered). Deliveries still
+    /// running after that, such as retries against an unreachable webhook,
+    /// carry on in the background. Does nothing when hooks were never
+    /// started.
+    pub async fn drain_hooks(&self) {
+        if let Some(runner) = self.hook_runner.get() {
+            runner.drain().await;
+        }
+    }
+
     /// Register an `EntityTypeStore`-backed `StoreHandle` for every entity
     /// type in the fields context.
     ///
@@ -1001,8 +1064,8 @@ mod tests {
         let ctx = KanbanContext::open(&kanban_dir).await.unwrap();
         let fields = ctx.fields().unwrap();
 
-        // Should have all 31 built-in fields
-        assert_eq!(fields.all_fields().len(), 31);
+        // Should have all 37 built-in fields
+        assert_eq!(fields.all_fields().len(), 37);
 
         // Should have all 7 entity templates
         assert_eq!(fields.all_entities().len(), 7);
@@ -1030,10 +1093,10 @@ type:
             .await
             .unwrap();
 
-        // Open — should have 31 built-in + 1 custom = 32
+        // Open — should have 37 built-in + 1 custom = 38
         let ctx = KanbanContext::open(&kanban_dir).await.unwrap();
         let fields = ctx.fields().unwrap();
-        assert_eq!(fields.all_fields().len(), 32);
+        assert_eq!(fields.all_fields().len(), 38);
 
         // Custom field should be present
         let sprint = fields.get_field_by_name("sprint").unwrap();
@@ -1067,7 +1130,7 @@ type:
 
         // Entity fields should resolve to field definitions
         let task_fields = fields.fields_for_entity("task");
-        assert_eq!(task_fields.len(), 20); // title, tags, assignees, project, depends_on, progress, body, position_column, position_ordinal, attachments, comments, virtual_tags, filter_tags, due, scheduled, created, updated, started, completed, status_date
+        assert_eq!(task_fields.len(), 25); // title, tags, assignees, project, depends_on, parent, progress, body, position_column, position_ordinal, attachments, comments, virtual_tags, filter_tags, due, scheduled, recurrence, created, updated, started, completed, recurred_from, recurred_as, external_id, status_date
     }
 
     // =========================================================================
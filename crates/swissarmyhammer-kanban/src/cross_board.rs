@@ -18,6 +18,7 @@ use serde_json::{json, Value};
 /// - `before_id`  - Optional task ID to place before (highest priority placement)
 /// - `after_id`   - Optional task ID to place after (highest priority placement)
 /// - `copy_mode`  - When `true`, keep the source task; when `false`, delete it (move)
+/// - `override_wip_limit` - Place the task even if `target_column` is at its WIP limit
 ///
 /// # Ordinal resolution priority
 /// 1. `before_id`/`after_id` — compute ordinal from neighbors
@@ -30,6 +31,7 @@ use serde_json::{json, Value};
 ///
 /// # Notes
 /// Tags that do not exist on the target board are stripped from the transferred task.
+/// A target column at its WIP limit rejects the transfer unless `override_wip_limit` is set.
 /// The caller is responsible for flushing/emitting entity-change events for both boards.
 #[allow(clippy::too_many_arguments)]
 pub async fn transfer_task(
@@ -41,6 +43,7 @@ pub async fn transfer_task(
     before_id: Option<&str>,
     after_id: Option<&str>,
     copy_mode: bool,
+    override_wip_limit: bool,
 ) -> Result<Value, String> {
     // Read source task
     let source_ectx = source_ctx
@@ -57,6 +60,9 @@ pub async fn transfer_task(
         .entity_context()
         .await
         .map_err(|e| e.to_string())?;
+    crate::column::enforce_wip_limit(&target_ectx, target_column, None, override_wip_limit)
+        .await
+        .map_err(|e| e.to_string())?;
     let ordinal = {
         let all_tasks = target_ectx.list("task").await.map_err(|e| e.to_string())?;
         if before_id.is_some() || after_id.is_some() {
@@ -318,7 +324,7 @@ mod tests {
         write_task(&src_ctx, "TASK01", "Move me", "todo").await;
 
         transfer_task(
-            &src_ctx, &tgt_ctx, "TASK01", "todo", None, None, None, false,
+            &src_ctx, &tgt_ctx, "TASK01", "todo", None, None, None, false, false,
         )
         .await
         .expect("transfer should succeed");
@@ -344,9 +350,11 @@ mod tests {
 
         write_task(&src_ctx, "TASK02", "Copy me", "todo").await;
 
-        transfer_task(&src_ctx, &tgt_ctx, "TASK02", "todo", None, None, None, true)
-            .await
-            .expect("copy should succeed");
+        transfer_task(
+            &src_ctx, &tgt_ctx, "TASK02", "todo", None, None, None, true, false,
+        )
+        .await
+        .expect("copy should succeed");
 
         // Source should still have the task
         let src_tasks = src_ctx.list_entities_generic("task").await.unwrap();
@@ -375,9 +383,11 @@ mod tests {
             ectx.write(&task).await.unwrap();
         }
 
-        transfer_task(&src_ctx, &tgt_ctx, "TASK03", "todo", None, None, None, true)
-            .await
-            .expect("copy should succeed");
+        transfer_task(
+            &src_ctx, &tgt_ctx, "TASK03", "todo", None, None, None, true, false,
+        )
+        .await
+        .expect("copy should succeed");
 
         let tgt_tasks = tgt_ctx.list_entities_generic("task").await.unwrap();
         assert_eq!(tgt_tasks.len(), 1);
@@ -430,9 +440,11 @@ mod tests {
             "source task should have computed tags from body"
         );
 
-        transfer_task(&src_ctx, &tgt_ctx, "TASK04", "todo", None, None, None, true)
-            .await
-            .expect("copy should succeed");
+        transfer_task(
+            &src_ctx, &tgt_ctx, "TASK04", "todo", None, None, None, true, false,
+        )
+        .await
+        .expect("copy should succeed");
 
         let tgt_tasks = tgt_ctx.list_entities_generic("task").await.unwrap();
         assert_eq!(tgt_tasks.len(), 1);
@@ -478,9 +490,11 @@ mod tests {
             ectx.write(&task).await.unwrap();
         }
 
-        transfer_task(&src_ctx, &tgt_ctx, "TASK05", "todo", None, None, None, true)
-            .await
-            .expect("copy should succeed");
+        transfer_task(
+            &src_ctx, &tgt_ctx, "TASK05", "todo", None, None, None, true, false,
+        )
+        .await
+        .expect("copy should succeed");
 
         let tgt_tasks = tgt_ctx.list_entities_generic("task").await.unwrap();
         assert_eq!(tgt_tasks.len(), 1);
@@ -523,6 +537,7 @@ mod tests {
             None,
             None,
             false,
+            false,
         )
         .await
         .expect("transfer should succeed");
@@ -545,7 +560,7 @@ mod tests {
         write_task(&src_ctx, "TASK07", "Result Task", "todo").await;
 
         let result = transfer_task(
-            &src_ctx, &tgt_ctx, "TASK07", "todo", None, None, None, false,
+            &src_ctx, &tgt_ctx, "TASK07", "todo", None, None, None, false, false,
         )
         .await
         .unwrap();
@@ -571,9 +586,11 @@ mod tests {
 
         write_task(&src_ctx, "TASK08", "Copy Result Task", "todo").await;
 
-        let result = transfer_task(&src_ctx, &tgt_ctx, "TASK08", "todo", None, None, None, true)
-            .await
-            .unwrap();
+        let result = transfer_task(
+            &src_ctx, &tgt_ctx, "TASK08", "todo", None, None, None, true, false,
+        )
+        .await
+        .unwrap();
 
         assert_eq!(result["transferred"].as_bool(), Some(false));
         assert_eq!(result["copied"].as_bool(), Some(true));
@@ -593,6 +610,7 @@ mod tests {
             None,
             None,
             false,
+            false,
         )
         .await;
         assert!(result.is_err(), "should fail for nonexistent task");
@@ -622,6 +640,7 @@ mod tests {
             Some("TGT_B"),
             None,
             false,
+            false,
         )
         .await
         .expect("transfer with before_id should succeed");
@@ -669,6 +688,7 @@ mod tests {
             None,
             Some("TGT_X"),
             false,
+            false,
         )
         .await
         .expect("transfer with after_id should succeed");
@@ -691,4 +711,40 @@ mod tests {
         assert_eq!(tgt_tasks[1].get_str("title"), Some("Middle"));
         assert_eq!(tgt_tasks[2].get_str("title"), Some("Yankee"));
     }
+
+    #[tokio::test]
+    async fn cross_board_transfer_respects_target_wip_limit() {
+        let (_src_dir, src_ctx) = make_board().await;
+        let (_tgt_dir, tgt_ctx) = make_board().await;
+
+        crate::column::UpdateColumn::new("todo")
+            .with_wip_limit(1)
+            .execute(&tgt_ctx)
+            .await
+            .into_result()
+            .unwrap();
+        write_task(&tgt_ctx, "TGT_FULL", "Occupant", "todo").await;
+        write_task(&src_ctx, "SRC_WIP", "Blocked by WIP", "todo").await;
+
+        let err = transfer_task(
+            &src_ctx, &tgt_ctx, "SRC_WIP", "todo", None, None, None, false, false,
+        )
+        .await
+        .expect_err("full target column should reject the transfer");
+        assert!(err.contains("WIP limit"), "unexpected error: {err}");
+        assert_eq!(
+            src_ctx.list_entities_generic("task").await.unwrap().len(),
+            1
+        );
+
+        transfer_task(
+            &src_ctx, &tgt_ctx, "SRC_WIP", "todo", None, None, None, false, true,
+        )
+        .await
+        .expect("override should allow the transfer");
+        assert_eq!(
+            tgt_ctx.list_entities_generic("task").await.unwrap().len(),
+            2
+        );
+    }
 }
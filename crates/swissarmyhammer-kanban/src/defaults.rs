@@ -15,8 +15,12 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use include_dir::{include_dir, Dir};
 use swissarmyhammer_entity::EntityContext;
-use swissarmyhammer_fields::{ComputeEngine, EntityLookup, FieldsContext};
+use swissarmyhammer_fields::{
+    ComputeEngine, EntityLookup, EntityQueryFn, FieldsContext, ARCHIVED_QUERY_SUFFIX,
+    CHANGELOG_QUERY_SUFFIX,
+};
 
+use crate::metrics::Flow;
 use crate::tag_parser;
 use crate::task_helpers;
 
@@ -185,8 +189,73 @@ fn register_board_percent_complete(engine: &mut ComputeEngine) {
     );
 }
 
+/// Days of history the board's flow-metric fields look back over.
+const FLOW_FIELD_WINDOW_DAYS: i64 = 28;
+
+/// Rebuild the board's flow from an aggregate query, reading every task,
+/// live or archived, together with its changelog.
+///
+/// The query answers each request once per read, so the flow fields of one
+/// board read share a single pass over the changelogs.
+async fn query_flow(query: &EntityQueryFn) -> Flow {
+    let columns = query("column").await;
+    let mut tasks = query(&format!("task{CHANGELOG_QUERY_SUFFIX}")).await;
+    tasks.extend(
+        query(&format!(
+            "task{ARCHIVED_QUERY_SUFFIX}{CHANGELOG_QUERY_SUFFIX}"
+        ))
+        .await,
+    );
+    Flow::from_field_maps(&columns, &tasks)
+}
+
+/// Register the board-cycle-time derivation.
+///
+/// Summarises the cycle time (first move out of the first column to arrival
+/// in the last) of tasks completed over the last four weeks, in hours.
+fn register_board_cycle_time(engine: &mut ComputeEngine) {
+    engine.register_aggregate(
+        "board-cycle-time",
+        Box::new(|_fields, query| {
+            Box::pin(async move {
+                let flow = query_flow(&query).await;
+                let until = chrono::Utc::now();
+                let since = until - chrono::Duration::days(FLOW_FIELD_WINDOW_DAYS);
+                let mut value =
+                    serde_json::to_value(flow.cycle_time(since, until)).unwrap_or_default();
+                value["days"] = serde_json::json!(FLOW_FIELD_WINDOW_DAYS);
+                value
+            })
+        }),
+    );
+}
+
+/// Register the board-throughput derivation.
+///
+/// Counts tasks completed over the last four weeks, with the weekly average.
+fn register_board_throughput(engine: &mut ComputeEngine) {
+    engine.register_aggregate(
+        "board-throughput",
+        Box::new(|_fields, query| {
+            Box::pin(async move {
+                let flow = query_flow(&query).await;
+                let until = chrono::Utc::now();
+                let since = until - chrono::Duration::days(FLOW_FIELD_WINDOW_DAYS);
+                let completed = flow.throughput(since, until);
+                let per_week = completed as f64 * 7.0 / FLOW_FIELD_WINDOW_DAYS as f64;
+
+                serde_json::json!({
+                    "completed": completed,
+                    "days": FLOW_FIELD_WINDOW_DAYS,
+                    "per_week": (per_week * 10.0).round() / 10.0,
+                })
+            })
+        }),
+    );
+}
+
 /// Extract the `timestamp` string from a serialized `ChangeEntry` JSON value.
-fn changelog_timestamp(entry: &serde_json::Value) -> Option<&str> {
+pub(crate) fn changelog_timestamp(entry: &serde_json::Value) -> Option<&str> {
     entry.get("timestamp").and_then(|v| v.as_str())
 }
 
@@ -227,7 +296,7 @@ fn is_position_column_change(change: &serde_json::Value) -> bool {
 /// - `changed`: old/new JSON values (non-string diff path — included for robustness).
 /// - `text_diff`: a unified diff patch used for string field updates. The new value
 ///   is the line starting with `+` (excluding the diff header `+++`).
-fn extract_position_column(entry: &serde_json::Value) -> Option<String> {
+pub(crate) fn extract_position_column(entry: &serde_json::Value) -> Option<String> {
     let changes = entry.get("changes")?.as_array()?;
     changes
         .iter()
@@ -555,6 +624,8 @@ pub fn kanban_compute_engine() -> ComputeEngine {
     register_parse_body_tags(&mut engine);
     register_parse_body_progress(&mut engine);
     register_board_percent_complete(&mut engine);
+    register_board_cycle_time(&mut engine);
+    register_board_throughput(&mut engine);
 
     // compute-virtual-tags: stub — returns empty array.
     // Populated by the enrichment pipeline in a later card.
@@ -710,7 +781,7 @@ mod tests {
     #[test]
     fn builtin_field_definitions_load() {
         let defs = builtin_field_definitions();
-        assert_eq!(defs.len(), 31, "expected 31 builtin field definitions");
+        assert_eq!(defs.len(), 39, "expected 39 builtin field definitions");
     }
 
     #[test]
@@ -880,11 +951,11 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(ctx.all_fields().len(), 31);
+        assert_eq!(ctx.all_fields().len(), 37);
         assert_eq!(ctx.all_entities().len(), 7);
         assert!(ctx.get_field_by_name("title").is_some());
         assert!(ctx.get_entity("task").is_some());
-        assert_eq!(ctx.fields_for_entity("task").len(), 20);
+        assert_eq!(ctx.fields_for_entity("task").len(), 25);
     }
 
     #[test]
@@ -981,6 +1052,8 @@ mod tests {
         assert!(engine.has("parse-body-tags"));
         assert!(engine.has("parse-body-progress"));
         assert!(engine.has("derive-status-date"));
+        assert!(engine.has("board-cycle-time"));
+        assert!(engine.has("board-throughput"));
     }
 
     /// Helper: build a query function that returns known tags.
@@ -1019,6 +1092,7 @@ mod tests {
             description: None,
             type_: swissarmyhammer_fields::FieldType::Computed {
                 derive: "parse-body-tags".to_string(),
+                script: None,
                 depends_on: vec![],
                 entity: None,
                 commit_display_names: false,
@@ -1033,6 +1107,7 @@ mod tests {
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         };
 
         let mut fields = HashMap::new();
@@ -1056,6 +1131,7 @@ mod tests {
             description: None,
             type_: swissarmyhammer_fields::FieldType::Computed {
                 derive: "parse-body-tags".to_string(),
+                script: None,
                 depends_on: vec![],
                 entity: None,
                 commit_display_names: false,
@@ -1070,6 +1146,7 @@ mod tests {
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         };
 
         let mut fields = HashMap::new();
@@ -1095,6 +1172,7 @@ mod tests {
             description: None,
             type_: swissarmyhammer_fields::FieldType::Computed {
                 derive: "parse-body-progress".to_string(),
+                script: None,
                 depends_on: vec![],
                 entity: None,
                 commit_display_names: false,
@@ -1109,6 +1187,7 @@ mod tests {
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         };
 
         let mut fields = HashMap::new();
@@ -1229,6 +1308,7 @@ mod tests {
             description: None,
             type_: swissarmyhammer_fields::FieldType::Computed {
                 derive: derive.to_string(),
+                script: None,
                 depends_on: vec!["_changelog".to_string()],
                 entity: None,
                 commit_display_names: false,
@@ -1243,6 +1323,7 @@ mod tests {
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         }
     }
 
@@ -1554,6 +1635,7 @@ mod tests {
             description: None,
             type_: swissarmyhammer_fields::FieldType::Computed {
                 derive: "parse-body-progress".to_string(),
+                script: None,
                 depends_on: vec![],
                 entity: None,
                 commit_display_names: false,
@@ -1568,6 +1650,7 @@ mod tests {
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         };
 
         let fields = HashMap::new(); // No body field
@@ -1705,6 +1788,7 @@ mod tests {
             description: None,
             type_: swissarmyhammer_fields::FieldType::Computed {
                 derive: "derive-status-date".to_string(),
+                script: None,
                 depends_on: vec![
                     "completed".to_string(),
                     "started".to_string(),
@@ -1725,6 +1809,7 @@ mod tests {
             placeholder: None,
             validate: None,
             groupable: None,
+            rules: None,
         };
         let mut fields = HashMap::new();
         fields.insert(
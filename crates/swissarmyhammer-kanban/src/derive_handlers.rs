@@ -102,6 +102,7 @@ mod tests {
             mention_display_field: None,
             mention_slug_field: None,
             search_display_field: None,
+            rules: Vec::new(),
         }
     }
 
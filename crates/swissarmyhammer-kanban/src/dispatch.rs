@@ -4,27 +4,39 @@
 //! This is the single source of truth for operation dispatch, used by both the MCP tool
 //! and the standalone kanban CLI.
 
+use crate::activity::ListActivity;
 use crate::actor::{AddActor, DeleteActor, GetActor, ListActors, UpdateActor};
 use crate::attachment::{
-    AddAttachment, DeleteAttachment, GetAttachment, ListAttachments, UpdateAttachment,
+    AddAttachment, DeleteAttachment, GcAttachments, GetAttachment, ListAttachments,
+    UpdateAttachment,
 };
-use crate::board::{GetBoard, InitBoard, UpdateBoard};
+use crate::board::{CompactBoard, GetBoard, InitBoard, UpdateBoard};
+use crate::bulk::{ArchiveTasks, AssignTasks, MoveTasks, TagTasks, UpdateTasks};
 use crate::column::{AddColumn, DeleteColumn, GetColumn, ListColumns, UpdateColumn};
 use crate::comment::{AddComment, DeleteComment, GetComment, ListComments, UpdateComment};
+use crate::export::ExportBoard;
+use crate::graph::GetGraph;
+use crate::import::ImportBoard;
+use crate::metrics::{GetFlow, GetMetrics};
 use crate::perspective::{
     AddPerspective, DeletePerspective, GetPerspective, ListPerspectives, UpdatePerspective,
 };
 use crate::project::{AddProject, DeleteProject, GetProject, ListProjects, UpdateProject};
+use crate::swimlane::ListSwimlanes;
 use crate::tag::{AddTag, DeleteTag, GetTag, ListTags, UpdateTag};
 use crate::task::{
     AddTask, ArchiveTask, AssignTask, CompleteTask, DeleteTask, GetTask, ListArchived, ListTasks,
-    MoveTask, NextTask, SearchTasks, TagTask, UnarchiveTask, UnassignTask, UntagTask, UpdateTask,
+    MoveTask, NextTask, PromoteTask, SearchTasks, TagTask, UnarchiveTask, UnassignTask, UntagTask,
+    UpdateTask,
 };
+use crate::template::ListTemplates;
 use crate::types::{
     resolve_short_ref, ActorId, Noun, Operation as KanbanOperation, ResolveResult, TaskId, Verb,
 };
+use crate::workspace::GetWorkspace;
 use crate::{KanbanContext, KanbanError, KanbanOperationProcessor, OperationProcessor};
 use serde_json::Value;
+use std::collections::HashMap;
 
 /// Helper: require a string param, returning KanbanError on missing.
 fn req<'a>(op: &'a KanbanOperation, key: &str) -> Result<&'a str, KanbanError> {
@@ -320,7 +332,7 @@ fn attachment_element(value: &Value) -> Result<Value, KanbanError> {
     )))
 }
 
-/// Dispatch board operations (init, get, update).
+/// Dispatch board operations (init, get, update, import, export, compact).
 async fn execute_board_operation(
     processor: &KanbanOperationProcessor,
     ctx: &KanbanContext,
@@ -336,8 +348,11 @@ async fn execute_board_operation(
             processor.process(&cmd, ctx).await
         }
         Verb::Get => {
-            let include_counts = op.get_bool("include_counts");
-            processor.process(&GetBoard { include_counts }, ctx).await
+            let cmd = GetBoard {
+                include_counts: op.get_bool("include_counts"),
+                as_of: op.get_string("as_of").map(str::to_string),
+            };
+            processor.process(&cmd, ctx).await
         }
         Verb::Update => {
             let mut cmd = UpdateBoard::new();
@@ -352,6 +367,48 @@ async fn execute_board_operation(
             }
             processor.process(&cmd, ctx).await
         }
+        Verb::Import => {
+            let cmd = ImportBoard {
+                format: req(op, "format")?.to_string(),
+                path: op.get_string("path").map(str::to_string),
+                // `content` is a common alias of `description`, so the parser
+                // has already renamed it.
+                content: op
+                    .get_string("content")
+                    .or_else(|| op.get_string("description"))
+                    .map(str::to_string),
+                mapping: match op.get_param("mapping") {
+                    Some(val) => Some(
+                        serde_json::from_value(val.clone())
+                            .map_err(|e| KanbanError::parse(format!("invalid mapping: {}", e)))?,
+                    ),
+                    None => None,
+                },
+                dry_run: op.get_bool("dry_run").unwrap_or(false),
+            };
+            processor.process(&cmd, ctx).await
+        }
+        Verb::Export => {
+            let mut cmd = ExportBoard::new(req(op, "format")?);
+            if let Some(perspective_id) = op.get_string("perspective_id") {
+                cmd = cmd.with_perspective(perspective_id);
+            }
+            if let Some(filter) = op.get_string("filter") {
+                cmd = cmd.with_filter(filter);
+            }
+            if let Some(path) = op.get_string("path") {
+                cmd = cmd.with_path(path);
+            }
+            processor.process(&cmd, ctx).await
+        }
+        Verb::Compact => {
+            let cmd = CompactBoard {
+                keep_latest: op.get_u64("keep_latest").map(|n| n as usize),
+                older_than: op.get_string("older_than").map(str::to_string),
+                entity_type: op.get_string("entity_type").map(str::to_string),
+            };
+            processor.process(&cmd, ctx).await
+        }
         _ => Err(KanbanError::parse(format!(
             "unsupported operation: {} {}",
             op.verb, op.noun
@@ -373,6 +430,9 @@ async fn execute_column_operation(
             if let Some(order) = op.get_param("order").and_then(|v| v.as_u64()) {
                 cmd = cmd.with_order(order as usize);
             }
+            if let Some(wip_limit) = op.get_u64("wip_limit") {
+                cmd = cmd.with_wip_limit(wip_limit as usize);
+            }
             processor.process(&cmd, ctx).await
         }
         (Verb::Get, Noun::Column) => {
@@ -388,6 +448,9 @@ async fn execute_column_operation(
             if let Some(order) = op.get_param("order").and_then(|v| v.as_u64()) {
                 cmd = cmd.with_order(order as usize);
             }
+            if let Some(wip_limit) = op.get_u64("wip_limit") {
+                cmd = cmd.with_wip_limit(wip_limit as usize);
+            }
             processor.process(&cmd, ctx).await
         }
         (Verb::Delete, Noun::Column) => {
@@ -482,7 +545,8 @@ async fn resolve_assignees(
 /// Build and execute an `AddTask` command from operation parameters.
 ///
 /// Parses title (required), description, column, ordinal, assignees,
-/// depends_on, tags, project, and the user-set dates from the operation.
+/// depends_on, parent, tags, project, the user-set dates, and
+/// override_wip_limit from the operation.
 /// Assignees fall back to the operation's actor when no explicit assignee
 /// list is provided.
 async fn dispatch_add_task(
@@ -501,6 +565,9 @@ async fn dispatch_add_task(
     if let Some(ordinal) = op.get_string("ordinal") {
         cmd.ordinal = Some(ordinal.to_string());
     }
+    if op.get_bool("override_wip_limit").unwrap_or(false) {
+        cmd = cmd.with_override_wip_limit();
+    }
 
     let assignees = resolve_assignees(ctx, op).await?;
     if !assignees.is_empty() {
@@ -513,6 +580,10 @@ async fn dispatch_add_task(
         }
     }
 
+    if let Some(parent) = op.get_string("parent").filter(|p| !p.trim().is_empty()) {
+        cmd = cmd.with_parent(resolve_task_ref(ctx, parent).await?);
+    }
+
     if let Some(refs) = tag_refs(op)? {
         cmd = cmd.with_tags(refs);
     }
@@ -535,14 +606,49 @@ async fn dispatch_add_task(
     if let Some(scheduled) = date_param_to_add(op, "scheduled") {
         cmd = cmd.with_scheduled(scheduled);
     }
+    if let Some(recurrence) = op.get_string("recurrence") {
+        cmd = cmd.with_recurrence(recurrence);
+    }
+
+    if let Some(template) = op.get_string("template") {
+        cmd = cmd.with_template(template);
+    }
+    if let Some(params) = op.get_param("params").filter(|v| !v.is_null()) {
+        cmd = cmd.with_params(template_params(params)?);
+    }
+    if let Some(subtasks) = op.get_bool("subtasks") {
+        cmd = cmd.with_subtasks(subtasks);
+    }
 
     processor.process(&cmd, ctx).await
 }
 
+/// Parse the `params` object of a templated `add task`. Numbers and booleans
+/// are taken in their string form, since template parameters are strings.
+fn template_params(value: &Value) -> Result<HashMap<String, String>, KanbanError> {
+    let Some(params) = value.as_object() else {
+        return Err(KanbanError::invalid_value(
+            "params",
+            "expected an object mapping parameter names to values",
+        ));
+    };
+    params
+        .iter()
+        .map(|(name, value)| match value {
+            Value::String(s) => Ok((name.clone(), s.clone())),
+            Value::Number(_) | Value::Bool(_) => Ok((name.clone(), value.to_string())),
+            _ => Err(KanbanError::invalid_value(
+                "params",
+                format!("parameter '{name}' must be a string, number or boolean"),
+            )),
+        })
+        .collect()
+}
+
 /// Build and execute an `UpdateTask` command from operation parameters.
 ///
-/// Parses id (required), title, description, assignees, depends_on, tags,
-/// attachments, and project from the operation.
+/// Parses id (required), title, description, assignees, depends_on, parent,
+/// tags, attachments, and project from the operation.
 async fn dispatch_update_task(
     processor: &KanbanOperationProcessor,
     ctx: &KanbanContext,
@@ -564,6 +670,14 @@ async fn dispatch_update_task(
     if let Some(dep_ids) = resolve_depends_on(ctx, op).await? {
         cmd = cmd.with_depends_on(dep_ids);
     }
+    // `parent` shares the tri-state: null or "" detaches the task.
+    cmd.parent = match date_param_to_update(op, "parent") {
+        None => None,
+        Some(None) => Some(None),
+        Some(Some(raw)) => Some(Some(TaskId::from_string(
+            resolve_task_ref(ctx, &raw).await?,
+        ))),
+    };
     if let Some(refs) = tag_refs(op)? {
         cmd = cmd.with_tags(refs);
     }
@@ -581,6 +695,8 @@ async fn dispatch_update_task(
     //   - date string   → set (validated by `UpdateTask`).
     cmd.due = date_param_to_update(op, "due");
     cmd.scheduled = date_param_to_update(op, "scheduled");
+    // The repeat rule shares the tri-state: null or "" stops the recurrence.
+    cmd.recurrence = date_param_to_update(op, "recurrence");
 
     processor.process(&cmd, ctx).await
 }
@@ -627,10 +743,11 @@ fn date_param_to_add(op: &KanbanOperation, key: &str) -> Option<String> {
     Some(value.to_string())
 }
 
-/// Dispatch task CRUD operations: add, get, update, delete, complete.
+/// Dispatch task CRUD operations: add, get, update, delete, complete, promote.
 ///
 /// Delegates to [`dispatch_add_task`] and [`dispatch_update_task`] for the
-/// longer Add and Update arms; handles Get, Delete, and Complete inline.
+/// longer Add and Update arms; handles Get, Delete, Complete and Promote
+/// inline.
 async fn execute_task_crud_operation(
     processor: &KanbanOperationProcessor,
     ctx: &KanbanContext,
@@ -640,7 +757,11 @@ async fn execute_task_crud_operation(
         Verb::Add => dispatch_add_task(processor, ctx, op).await,
         Verb::Get => {
             let id = req_task_id(ctx, op, "id").await?;
-            processor.process(&GetTask::new(id), ctx).await
+            let mut cmd = GetTask::new(id);
+            if let Some(at) = op.get_string("as_of") {
+                cmd = cmd.with_as_of(at);
+            }
+            processor.process(&cmd, ctx).await
         }
         Verb::Update => dispatch_update_task(processor, ctx, op).await,
         Verb::Delete => {
@@ -651,6 +772,15 @@ async fn execute_task_crud_operation(
             let id = req_task_id(ctx, op, "id").await?;
             processor.process(&CompleteTask::new(id), ctx).await
         }
+        Verb::Promote => {
+            let id = req_task_id(ctx, op, "id").await?;
+            let item = op
+                .get_u64("item")
+                .ok_or_else(|| KanbanError::missing_field("item"))?;
+            processor
+                .process(&PromoteTask::new(id, item as usize), ctx)
+                .await
+        }
         _ => Err(KanbanError::parse(format!(
             "unsupported operation: {} {}",
             op.verb, op.noun
@@ -678,6 +808,12 @@ async fn execute_task_movement_operation(
             if let Some(after_id) = resolve_opt_placement_ref(ctx, op, "after_id").await? {
                 cmd.after_id = Some(after_id.into());
             }
+            if op.get_bool("override_wip_limit").unwrap_or(false) {
+                cmd = cmd.with_override_wip_limit();
+            }
+            cmd.group_by = op.get_string("group_by").map(|s| s.to_string());
+            cmd.from_lane = op.get_string("from_lane").map(|s| s.to_string());
+            cmd.to_lane = op.get_string("to_lane").map(|s| s.to_string());
             processor.process(&cmd, ctx).await
         }
         Verb::Archive => {
@@ -743,6 +879,12 @@ async fn execute_task_query_operation(
             if let Some(filter) = op.get_string("filter") {
                 cmd = cmd.with_filter(filter);
             }
+            if op.get_bool("workspace").unwrap_or(false) {
+                cmd = cmd.with_workspace();
+            }
+            if let Some(root) = op.get_string("workspace_root") {
+                cmd = cmd.with_workspace_root(root);
+            }
             processor.process(&cmd, ctx).await
         }
         Verb::List => {
@@ -795,6 +937,56 @@ async fn execute_task_query_operation(
             if let Some(top_k) = op.get_u64("top_k").and_then(|n| usize::try_from(n).ok()) {
                 cmd = cmd.with_top_k(top_k);
             }
+            if op.get_bool("workspace").unwrap_or(false) {
+                cmd = cmd.with_workspace();
+            }
+            if let Some(root) = op.get_string("workspace_root") {
+                cmd = cmd.with_workspace_root(root);
+            }
+            processor.process(&cmd, ctx).await
+        }
+        _ => Err(KanbanError::parse(format!(
+            "unsupported operation: {} {}",
+            op.verb, op.noun
+        ))),
+    }
+}
+
+/// Dispatch bulk task operations: move, tag, assign, archive and update every
+/// task a `filter` expression selects.
+async fn execute_bulk_task_operation(
+    processor: &KanbanOperationProcessor,
+    ctx: &KanbanContext,
+    op: &KanbanOperation,
+) -> Result<Value, KanbanError> {
+    let filter = req(op, "filter")?;
+    let dry_run = op.get_bool("dry_run").unwrap_or(false);
+    match op.verb {
+        Verb::Move => {
+            let mut cmd = MoveTasks::new(filter, req(op, "column")?);
+            cmd.dry_run = dry_run;
+            cmd.override_wip_limit = op.get_bool("override_wip_limit").unwrap_or(false);
+            processor.process(&cmd, ctx).await
+        }
+        Verb::Tag => {
+            let mut cmd = TagTasks::new(filter, req(op, "tag")?);
+            cmd.dry_run = dry_run;
+            processor.process(&cmd, ctx).await
+        }
+        Verb::Assign => {
+            let mut cmd = AssignTasks::new(filter, req(op, "assignee")?);
+            cmd.dry_run = dry_run;
+            processor.process(&cmd, ctx).await
+        }
+        Verb::Archive => {
+            let mut cmd = ArchiveTasks::new(filter);
+            cmd.dry_run = dry_run;
+            processor.process(&cmd, ctx).await
+        }
+        Verb::Update => {
+            let value = op.get_param("value").cloned().unwrap_or(Value::Null);
+            let mut cmd = UpdateTasks::new(filter, req(op, "field")?, value);
+            cmd.dry_run = dry_run;
             processor.process(&cmd, ctx).await
         }
         _ => Err(KanbanError::parse(format!(
@@ -807,13 +999,22 @@ async fn execute_task_query_operation(
 /// Dispatch task operations by delegating to category-specific handlers.
 ///
 /// Routes each verb to one of: CRUD, movement, assignment/tagging, or query.
+/// A mutating verb on the plural `tasks` noun is a bulk change instead.
 async fn execute_task_operation(
     processor: &KanbanOperationProcessor,
     ctx: &KanbanContext,
     op: &KanbanOperation,
 ) -> Result<Value, KanbanError> {
+    if op.noun == Noun::Tasks
+        && matches!(
+            op.verb,
+            Verb::Move | Verb::Tag | Verb::Assign | Verb::Archive | Verb::Update
+        )
+    {
+        return execute_bulk_task_operation(processor, ctx, op).await;
+    }
     match op.verb {
-        Verb::Add | Verb::Get | Verb::Update | Verb::Delete | Verb::Complete => {
+        Verb::Add | Verb::Get | Verb::Update | Verb::Delete | Verb::Complete | Verb::Promote => {
             execute_task_crud_operation(processor, ctx, op).await
         }
         Verb::Move | Verb::Archive | Verb::Unarchive => {
@@ -1080,7 +1281,7 @@ async fn dispatch_update_perspective(
     processor.process(&cmd, ctx).await
 }
 
-/// Dispatch attachment operations (add, get, update, delete, list).
+/// Dispatch attachment operations (add, get, update, delete, list, gc).
 async fn execute_attachment_operation(
     processor: &KanbanOperationProcessor,
     ctx: &KanbanContext,
@@ -1105,6 +1306,10 @@ async fn execute_attachment_operation(
             let task_id = req_task_id(ctx, op, "task_id").await?;
             processor.process(&ListAttachments::new(task_id), ctx).await
         }
+        Verb::Gc => {
+            let cmd = GcAttachments::new().with_dry_run(op.get_bool("dry_run").unwrap_or(false));
+            processor.process(&cmd, ctx).await
+        }
         _ => Err(KanbanError::parse(format!(
             "unsupported operation: {} {}",
             op.verb, op.noun
@@ -1222,6 +1427,19 @@ pub async fn execute_operation(
             execute_attachment_operation(&processor, ctx, op).await
         }
         Noun::Comment | Noun::Comments => execute_comment_operation(&processor, ctx, op).await,
+        Noun::Swimlanes => {
+            let mut cmd = ListSwimlanes::new();
+            if let Some(g) = op.get_string("group_by") {
+                cmd = cmd.with_group_by(g);
+            }
+            if let Some(p) = op.get_string("perspective_id") {
+                cmd = cmd.with_perspective(p);
+            }
+            if let Some(f) = op.get_string("filter") {
+                cmd = cmd.with_filter(f);
+            }
+            processor.process(&cmd, ctx).await
+        }
         Noun::Archived => {
             let mut cmd = ListArchived::new();
             if let Some(detail) = op.get_string("detail") {
@@ -1229,6 +1447,72 @@ pub async fn execute_operation(
             }
             processor.process(&cmd, ctx).await
         }
+        Noun::Activity => {
+            let mut cmd = ListActivity::new();
+            if let Some(a) = op.get_string("changed_by") {
+                cmd = cmd.with_changed_by(a);
+            }
+            if let Some(t) = op.get_string("entity_type") {
+                cmd = cmd.with_entity_type(t);
+            }
+            if let Some(f) = op.get_string("field") {
+                cmd = cmd.with_field(f);
+            }
+            if let Some(s) = op.get_string("since") {
+                cmd = cmd.with_since(s);
+            }
+            if let Some(u) = op.get_string("until") {
+                cmd = cmd.with_until(u);
+            }
+            if let Some(l) = op.get_u64("limit") {
+                cmd = cmd.with_limit(l as usize);
+            }
+            processor.process(&cmd, ctx).await
+        }
+        Noun::Graph => {
+            let mut cmd = GetGraph::new();
+            if op.get_string("task_id").is_some() {
+                cmd = cmd.with_task(req_task_id(ctx, op, "task_id").await?);
+            }
+            processor.process(&cmd, ctx).await
+        }
+        Noun::Metrics => {
+            let mut cmd = GetMetrics::new();
+            if let Some(s) = op.get_string("since") {
+                cmd = cmd.with_since(s);
+            }
+            if let Some(u) = op.get_string("until") {
+                cmd = cmd.with_until(u);
+            }
+            if let Some(f) = op.get_string("filter") {
+                cmd = cmd.with_filter(f);
+            }
+            processor.process(&cmd, ctx).await
+        }
+        Noun::Flow => {
+            let mut cmd = GetFlow::new();
+            if let Some(s) = op.get_string("since") {
+                cmd = cmd.with_since(s);
+            }
+            if let Some(u) = op.get_string("until") {
+                cmd = cmd.with_until(u);
+            }
+            if let Some(f) = op.get_string("filter") {
+                cmd = cmd.with_filter(f);
+            }
+            processor.process(&cmd, ctx).await
+        }
+        Noun::Templates => processor.process(&ListTemplates::new(), ctx).await,
+        Noun::Workspace => {
+            let mut cmd = GetWorkspace::new();
+            if let Some(f) = op.get_string("filter") {
+                cmd = cmd.with_filter(f);
+            }
+            if let Some(root) = op.get_string("workspace_root") {
+                cmd = cmd.with_workspace_root(root);
+            }
+            processor.process(&cmd, ctx).await
+        }
     }
 }
 
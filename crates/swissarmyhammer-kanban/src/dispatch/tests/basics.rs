@@ -2,8 +2,8 @@
 //!
 //! These tests hold `init board`, the column that a new task goes into, the
 //! list and get operations, the archive operations, the `detail` parameter,
-//! the error for a field that is not there, and the actor that the processor
-//! holds.
+//! the error for a field that is not there, the actor that the processor
+//! holds, the activity feed that records it, and `as_of` reads of the past.
 
 use super::*;
 
@@ -348,3 +348,56 @@ async fn dispatch_with_actor_sets_processor() {
     let result = execute_operation(&ctx, &op).await.unwrap();
     assert_eq!(result["title"], "Actor task");
 }
+
+#[tokio::test]
+async fn dispatch_list_activity_by_actor() {
+    let (_temp, ctx) = setup().await;
+
+    let ops =
+        parse_input(json!({"op": "add task", "title": "Audited", "actor": "test-actor"})).unwrap();
+    let id = execute_operation(&ctx, &ops[0]).await.unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let ops = parse_input(json!({"op": "add task", "title": "Anonymous"})).unwrap();
+    execute_operation(&ctx, &ops[0]).await.unwrap();
+
+    let ops = parse_input(json!({
+        "op": "list activity",
+        "changed_by": "test-actor",
+        "entity_type": "task",
+        "since": "1h",
+    }))
+    .unwrap();
+    let result = execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert_eq!(result["count"], 1);
+    let entry = &result["entries"][0];
+    assert_eq!(entry["op"], "create");
+    assert_eq!(entry["actor"], "test-actor");
+    assert_eq!(entry["entity_id"], id.as_str());
+    assert_eq!(entry["name"], "Audited");
+}
+
+#[tokio::test]
+async fn dispatch_get_task_and_board_as_of() {
+    let (_temp, ctx) = setup().await;
+
+    let ops = parse_input(json!({"op": "add task", "title": "Draft"})).unwrap();
+    let id = execute_operation(&ctx, &ops[0]).await.unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    let then = chrono::Utc::now().to_rfc3339();
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    let ops = parse_input(json!({"op": "update task", "id": id, "title": "Final"})).unwrap();
+    execute_operation(&ctx, &ops[0]).await.unwrap();
+
+    let ops = parse_input(json!({"op": "get task", "id": id, "asOf": then})).unwrap();
+    let result = execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert_eq!(result["title"], "Draft");
+
+    let ops = parse_input(json!({"op": "get board", "as_of": then})).unwrap();
+    let result = execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert_eq!(result["tasks"][0]["title"], "Draft");
+}
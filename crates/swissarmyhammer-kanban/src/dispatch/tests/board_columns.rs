@@ -1,8 +1,9 @@
 //! The board and the column operations.
 //!
 //! These tests hold `update board`, the column CRUD operations, the `column`
-//! alias, the column order, the board description, and the `include_counts`
-//! parameter.
+//! alias, the column order, the board description, the `include_counts`
+//! parameter, `import board`, `export board`, `compact board`, and the
+//! `get metrics` and `get flow` flow metrics.
 
 use super::*;
 
@@ -206,3 +207,113 @@ async fn dispatch_get_board_without_counts() {
     let result = execute_operation(&ctx, &ops[0]).await.unwrap();
     assert_eq!(result["name"], "Test");
 }
+
+// ------------------------------------------------------------------
+// Dispatch: import board
+// ------------------------------------------------------------------
+
+#[tokio::test]
+async fn dispatch_import_board_csv_with_mapping_is_idempotent() {
+    let (_temp, ctx) = setup().await;
+
+    let input = json!({
+        "op": "import board",
+        "format": "csv",
+        "content": "Key,Summary\nK-1,First\nK-2,Second\n",
+        "mapping": {"Key": "external_id", "Summary": "title"},
+    });
+    let ops = parse_input(input.clone()).unwrap();
+    let result = execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert_eq!(result["tasks"]["created"], 2);
+    assert_eq!(result["tasks"]["items"][0]["external_id"], "csv:K-1");
+
+    let ops = parse_input(input).unwrap();
+    let result = execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert_eq!(result["tasks"]["created"], 0);
+    assert_eq!(result["tasks"]["unchanged"], 2);
+
+    let ops = parse_input(json!({"op": "list tasks"})).unwrap();
+    let result = execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert_eq!(result["tasks"].as_array().unwrap().len(), 2);
+}
+
+// ------------------------------------------------------------------
+// Dispatch: export board
+// ------------------------------------------------------------------
+
+#[tokio::test]
+async fn dispatch_export_board_csv_with_filter() {
+    let (_temp, ctx) = setup().await;
+
+    for (title, body) in [("Fix crash", "#bug"), ("Write docs", "")] {
+        let ops =
+            parse_input(json!({"op": "add task", "title": title, "description": body})).unwrap();
+        execute_operation(&ctx, &ops[0]).await.unwrap();
+    }
+
+    let ops =
+        parse_input(json!({"op": "export board", "format": "csv", "filter": "#bug"})).unwrap();
+    let result = execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert_eq!(result["format"], "csv");
+    assert_eq!(result["count"], 1);
+    let csv = result["content"].as_str().unwrap();
+    assert!(csv.starts_with("title,position_column,tags,"));
+    assert!(csv.contains("Fix crash,To Do,bug,"));
+    assert!(!csv.contains("Write docs"));
+}
+
+// ------------------------------------------------------------------
+// Dispatch: compact board
+// ------------------------------------------------------------------
+
+#[tokio::test]
+async fn dispatch_compact_board_folds_task_history() {
+    let (_temp, ctx) = setup().await;
+
+    let ops = parse_input(json!({"op": "add task", "title": "v1"})).unwrap();
+    let added = execute_operation(&ctx, &ops[0]).await.unwrap();
+    let id = added["id"].as_str().unwrap();
+    for title in ["v2", "v3", "v4"] {
+        let ops = parse_input(json!({"op": "update task", "id": id, "title": title})).unwrap();
+        execute_operation(&ctx, &ops[0]).await.unwrap();
+    }
+
+    let ops = parse_input(json!({
+        "op": "compact board",
+        "keepLatest": 1,
+        "entityType": "task",
+    }))
+    .unwrap();
+    let result = execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert_eq!(result["entities"], 1, "{result}");
+    assert_eq!(result["folded"], 2);
+
+    let ops = parse_input(json!({"op": "get task", "id": id})).unwrap();
+    let task = execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert_eq!(task["title"], "v4");
+}
+
+// ------------------------------------------------------------------
+// Dispatch: flow metrics
+// ------------------------------------------------------------------
+
+#[tokio::test]
+async fn dispatch_get_metrics_and_flow() {
+    let (_temp, ctx) = setup().await;
+
+    let ops = parse_input(json!({"op": "add task", "title": "Ship it"})).unwrap();
+    let added = execute_operation(&ctx, &ops[0]).await.unwrap();
+    let id = added["id"].as_str().unwrap();
+    let ops = parse_input(json!({"op": "move task", "id": id, "column": "done"})).unwrap();
+    execute_operation(&ctx, &ops[0]).await.unwrap();
+
+    let ops = parse_input(json!({"op": "get metrics", "since": "7d"})).unwrap();
+    let metrics = execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert_eq!(metrics["throughput"]["total"], 1, "{metrics}");
+    assert_eq!(metrics["lead_time"]["count"], 1);
+
+    let ops = parse_input(json!({"op": "get flow", "since": "2d"})).unwrap();
+    let flow = execute_operation(&ctx, &ops[0]).await.unwrap();
+    let today = &flow["days"].as_array().unwrap().last().unwrap()["counts"];
+    assert_eq!(today["done"], 1, "{flow}");
+}
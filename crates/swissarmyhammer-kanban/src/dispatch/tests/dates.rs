@@ -1,8 +1,9 @@
-//! The `due` and `scheduled` fields.
+//! The `due` and `scheduled` fields, and the `recurrence` rule that moves them.
 //!
 //! These tests hold the dates that dispatch accepts, the invalid dates that it
-//! refuses, the null that clears a date, and the date fields that get task and
-//! list tasks give back.
+//! refuses, the null that clears a date, the date fields that get task and
+//! list tasks give back, and the next instance that completing a recurring
+//! task creates.
 
 use super::*;
 
@@ -235,3 +236,49 @@ async fn dispatch_list_tasks_emits_date_fields() {
     assert!(tasks[0]["scheduled"].is_null());
     assert!(tasks[0].get("created").is_some());
 }
+
+#[tokio::test]
+async fn dispatch_complete_recurring_task_moves_dates_forward() {
+    let (_temp, ctx) = setup().await;
+
+    let due = chrono::Utc::now().date_naive() + chrono::Duration::days(1);
+    let ops = parse_input(json!({
+        "op": "add task",
+        "title": "Standup notes",
+        "due": due.format("%Y-%m-%d").to_string(),
+        "recurrence": "FREQ=DAILY;INTERVAL=2",
+    }))
+    .unwrap();
+    let added = execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert_eq!(added["recurrence"], "FREQ=DAILY;INTERVAL=2");
+    let id = added["id"].as_str().unwrap().to_string();
+
+    let ops = parse_input(json!({"op": "complete task", "id": id})).unwrap();
+    let result = execute_operation(&ctx, &ops[0]).await.unwrap();
+    let next_id = result["recurred_as"].as_str().unwrap().to_string();
+
+    let next = get_task(&ctx, &next_id).await;
+    let next_due = due + chrono::Duration::days(2);
+    assert_eq!(next["due"], next_due.format("%Y-%m-%d").to_string());
+    assert_eq!(next["recurred_from"], id);
+    assert_eq!(get_task(&ctx, &id).await["recurred_as"], next_id);
+}
+
+#[tokio::test]
+async fn dispatch_update_task_null_recurrence_clears_it() {
+    let (_temp, ctx) = setup().await;
+    let ops = parse_input(json!({
+        "op": "add task",
+        "title": "Audit",
+        "recurrence": "FREQ=WEEKLY",
+    }))
+    .unwrap();
+    let id = execute_operation(&ctx, &ops[0]).await.unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let ops = parse_input(json!({"op": "update task", "id": id, "recurrence": null})).unwrap();
+    execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert!(get_task(&ctx, &id).await["recurrence"].is_null());
+}
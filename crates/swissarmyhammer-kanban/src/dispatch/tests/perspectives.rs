@@ -1,7 +1,8 @@
 //! The perspective operations.
 //!
 //! These tests hold the add, get, list, update and delete operations, the full
-//! lifecycle, and the errors for malformed `fields` and `sort` JSON.
+//! lifecycle, the errors for malformed `fields` and `sort` JSON, and the
+//! swimlanes that a perspective's group key produces.
 
 use super::*;
 
@@ -368,3 +369,76 @@ async fn dispatch_update_perspective_malformed_sort_returns_error() {
         "error should mention 'invalid sort', got: {err_msg}"
     );
 }
+
+// ── Swimlanes ──────────────────────────────────────────────────
+
+#[tokio::test]
+async fn dispatch_list_swimlanes_and_move_across_lanes() {
+    let (_temp, ctx) = setup().await;
+
+    let ops = parse_input(
+        json!({"op": "add perspective", "name": "By tag", "view": "board", "group": "tags"}),
+    )
+    .unwrap();
+    execute_operation(&ctx, &ops[0]).await.unwrap();
+    let ops =
+        parse_input(json!({"op": "add task", "title": "Crash", "description": "#bug"})).unwrap();
+    let id = execute_operation(&ctx, &ops[0]).await.unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let ops = parse_input(json!({"op": "list swimlanes", "perspective_id": "By tag"})).unwrap();
+    let result = execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert_eq!(result["group_by"], "tags");
+    assert_eq!(result["lanes"][0]["value"], "bug");
+    assert_eq!(result["lanes"][0]["tasks"], json!([id]));
+
+    let ops = parse_input(json!({
+        "op": "move task",
+        "id": id,
+        "column": "doing",
+        "group_by": "tags",
+        "from_lane": "bug",
+        "to_lane": "",
+    }))
+    .unwrap();
+    execute_operation(&ctx, &ops[0]).await.unwrap();
+
+    let ops = parse_input(json!({"op": "list swimlanes", "group_by": "tags"})).unwrap();
+    let result = execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert_eq!(result["count"], 1);
+    assert_eq!(result["lanes"][0]["value"], "");
+    assert_eq!(result["lanes"][0]["tasks"], json!([id]));
+}
+
+/// A lane named with stray whitespace is the same lane, so the move leaves
+/// the task's lane value alone.
+#[tokio::test]
+async fn dispatch_move_to_same_lane_ignores_whitespace() {
+    let (_temp, ctx) = setup().await;
+
+    let ops = parse_input(
+        json!({"op": "add task", "title": "Crash", "description": "#bug seen on save"}),
+    )
+    .unwrap();
+    let id = execute_operation(&ctx, &ops[0]).await.unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let ops = parse_input(json!({
+        "op": "move task",
+        "id": id,
+        "column": "doing",
+        "group_by": "tags",
+        "from_lane": "bug",
+        "to_lane": " bug ",
+    }))
+    .unwrap();
+    execute_operation(&ctx, &ops[0]).await.unwrap();
+
+    let ops = parse_input(json!({"op": "get task", "id": id})).unwrap();
+    let task = execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert_eq!(task["description"], "#bug seen on save");
+}
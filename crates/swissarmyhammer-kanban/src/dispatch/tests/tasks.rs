@@ -2,7 +2,8 @@
 //!
 //! These tests hold the task CRUD, movement, assignment and query operations.
 //! They also hold the optional parameters of add task, update task, move task
-//! and next task, and each filter that list tasks accepts.
+//! and next task, each filter that list tasks accepts, and the dependency
+//! graph built from `depends_on`.
 
 use super::*;
 
@@ -623,3 +624,211 @@ async fn dispatch_list_tasks_unknown_project_returns_empty() {
         "an unknown project must yield an empty list, not the whole board"
     );
 }
+
+// ------------------------------------------------------------------
+// Dependency graph
+// ------------------------------------------------------------------
+
+#[tokio::test]
+async fn dispatch_get_graph_and_cycle_rejection() {
+    let (_temp, ctx) = setup().await;
+
+    let ops = parse_input(json!({"op": "add task", "title": "First"})).unwrap();
+    let first = execute_operation(&ctx, &ops[0]).await.unwrap();
+    let first = first["id"].as_str().unwrap().to_string();
+    let ops = parse_input(json!({
+        "op": "add task",
+        "title": "Second",
+        "depends_on": [first.as_str()],
+    }))
+    .unwrap();
+    let second = execute_operation(&ctx, &ops[0]).await.unwrap();
+    let second = second["id"].as_str().unwrap().to_string();
+
+    // Closing the loop through a short id is still caught.
+    let short = crate::types::short_id(&second);
+    let ops = parse_input(json!({
+        "op": "update task",
+        "id": first.as_str(),
+        "depends_on": [short],
+    }))
+    .unwrap();
+    let err = execute_operation(&ctx, &ops[0]).await.unwrap_err();
+    assert!(
+        matches!(err, KanbanError::DependencyCycle { .. }),
+        "{err:?}"
+    );
+
+    let ops = parse_input(json!({"op": "get graph", "taskId": second.as_str()})).unwrap();
+    let graph = execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert_eq!(graph["edges"], json!([{"from": first, "to": second}]));
+    assert_eq!(graph["order"], json!([first, second]));
+    assert_eq!(graph["transitive_blockers"][0]["id"], first.as_str());
+}
+
+#[tokio::test]
+async fn dispatch_add_task_from_template() {
+    let (_temp, ctx) = setup().await;
+
+    let ops = parse_input(json!({
+        "op": "add task",
+        "title": "Login times out",
+        "template": "bugfix",
+        "params": {"component": "auth", "issue": 42},
+        "description": "Seen on staging only."
+    }))
+    .unwrap();
+    let r = execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert_eq!(r["template"], "bugfix");
+    let subtasks = r["subtasks"].as_array().unwrap();
+    assert_eq!(subtasks.len(), 2);
+
+    let task = get_task(&ctx, r["id"].as_str().unwrap()).await;
+    assert_eq!(task["title"], "auth: Login times out");
+    let description = task["description"].as_str().unwrap();
+    assert!(description.contains("Reported in 42."), "{description}");
+    assert!(
+        description.ends_with("Seen on staging only."),
+        "{description}"
+    );
+    assert_eq!(
+        stored_tags(&ctx, r["id"].as_str().unwrap()).await,
+        vec!["bug"]
+    );
+    let deps = task["depends_on"].as_array().unwrap();
+    assert_eq!(deps.len(), 2);
+    assert!(deps.contains(&subtasks[0]["id"]));
+
+    let reproduce = get_task(&ctx, subtasks[0]["id"].as_str().unwrap()).await;
+    assert_eq!(reproduce["title"], "Reproduce: Login times out");
+    assert_eq!(
+        reproduce["parent"], r["id"],
+        "template subtasks are subtasks"
+    );
+
+    // A required parameter left out fails before anything is written.
+    let ops =
+        parse_input(json!({"op": "add task", "title": "Broken", "template": "bugfix"})).unwrap();
+    assert!(matches!(
+        execute_operation(&ctx, &ops[0]).await,
+        Err(KanbanError::InvalidValue { .. })
+    ));
+    let ops = parse_input(json!({"op": "add task", "title": "X", "template": "no-such"})).unwrap();
+    assert!(matches!(
+        execute_operation(&ctx, &ops[0]).await,
+        Err(KanbanError::NotFound { .. })
+    ));
+
+    let ops = parse_input(json!({"op": "list templates"})).unwrap();
+    let listed = execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert!(listed["templates"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|t| t["name"] == "bugfix"));
+
+    let ops = parse_input(json!({"op": "list tasks"})).unwrap();
+    let all = execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert_eq!(all["count"], 3, "failed adds created nothing");
+}
+
+#[tokio::test]
+async fn dispatch_bulk_move_tasks_dry_run_then_apply() {
+    let (_temp, ctx) = setup().await;
+
+    for title in ["Bug one", "Bug two"] {
+        let ops = parse_input(json!({"op": "add task", "title": title, "tags": ["bug"]})).unwrap();
+        execute_operation(&ctx, &ops[0]).await.unwrap();
+    }
+    let ops = parse_input(json!({"op": "add task", "title": "Feature"})).unwrap();
+    execute_operation(&ctx, &ops[0]).await.unwrap();
+
+    let ops = parse_input(json!({
+        "op": "move tasks",
+        "filter": "#bug",
+        "column": "doing",
+        "dry_run": true
+    }))
+    .unwrap();
+    let preview = execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert_eq!(preview["dry_run"], true);
+    assert_eq!(preview["matched"], 2);
+    assert_eq!(preview["ids"].as_array().unwrap().len(), 2);
+
+    let ops = parse_input(json!({"op": "list tasks", "column": "doing"})).unwrap();
+    let listed = execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert_eq!(listed["count"], 0, "a dry run writes nothing");
+
+    let ops =
+        parse_input(json!({"op": "move tasks", "filter": "#bug", "column": "doing"})).unwrap();
+    let moved = execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert_eq!(moved["changed"], 2);
+
+    let ops = parse_input(json!({"op": "list tasks", "column": "doing"})).unwrap();
+    let listed = execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert_eq!(listed["count"], 2);
+
+    let ops = parse_input(json!({"op": "tag tasks", "filter": "", "tag": "x"})).unwrap();
+    assert!(execute_operation(&ctx, &ops[0]).await.is_err());
+}
+
+#[tokio::test]
+async fn dispatch_subtasks_block_parent_and_follow_its_moves() {
+    let (_temp, ctx) = setup().await;
+
+    let ops = parse_input(json!({"op": "add task", "title": "Epic"})).unwrap();
+    let parent = execute_operation(&ctx, &ops[0]).await.unwrap();
+    let parent_id = parent["id"].as_str().unwrap().to_string();
+
+    let ops = parse_input(json!({"op": "add task", "title": "Part", "parent": parent_id})).unwrap();
+    let child = execute_operation(&ctx, &ops[0]).await.unwrap();
+    let child_id = child["id"].as_str().unwrap().to_string();
+
+    let ops = parse_input(json!({"op": "get task", "id": parent_id})).unwrap();
+    let fetched = execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert_eq!(fetched["children"], json!([child_id]));
+    assert_eq!(fetched["ready"], false);
+
+    let ops = parse_input(json!({"op": "move task", "id": parent_id, "column": "doing"})).unwrap();
+    execute_operation(&ctx, &ops[0]).await.unwrap();
+    let ops = parse_input(json!({"op": "get task", "id": child_id})).unwrap();
+    let fetched = execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert_eq!(
+        fetched["position"]["column"], "doing",
+        "the subtask follows"
+    );
+
+    let ops =
+        parse_input(json!({"op": "update task", "id": parent_id, "parent": child_id})).unwrap();
+    assert!(
+        execute_operation(&ctx, &ops[0]).await.is_err(),
+        "a task cannot become its own subtask's child"
+    );
+}
+
+#[tokio::test]
+async fn dispatch_next_task_and_get_workspace_across_boards() {
+    let (temp, ctx) = setup().await;
+    let other = KanbanContext::new(temp.path().join("other").join(".kanban"));
+    let ops = parse_input(json!({"op": "init board", "name": "Other"})).unwrap();
+    execute_operation(&other, &ops[0]).await.unwrap();
+    let oldest = add_one_task(&other, "Oldest").await;
+    // Task ids are ULIDs: a later millisecond makes the next one newer.
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    add_one_task(&ctx, "Newer").await;
+    let root = temp.path().to_string_lossy().to_string();
+
+    let ops = parse_input(json!({"op": "next task"})).unwrap();
+    let local = execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert_eq!(local["title"], "Newer");
+
+    let ops = parse_input(json!({"op": "next task", "workspace_root": root})).unwrap();
+    let next = execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert_eq!(next["id"], format!("other/{oldest}"));
+    assert_eq!(next["board"], "other");
+
+    let ops = parse_input(json!({"op": "get workspace", "workspace_root": root})).unwrap();
+    let view = execute_operation(&ctx, &ops[0]).await.unwrap();
+    assert_eq!(view["boards"].as_array().unwrap().len(), 2);
+    assert_eq!(view["summary"]["total_tasks"], 2);
+}
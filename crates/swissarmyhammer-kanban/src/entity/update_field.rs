@@ -5,6 +5,7 @@ use crate::context::KanbanContext;
 use crate::error::KanbanError;
 use crate::tag::tag_name_exists_entity;
 use crate::tag_parser;
+use crate::task::check_task_links;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use swissarmyhammer_entity::Entity;
@@ -35,6 +36,18 @@ async fn auto_create_tags(
     Ok(())
 }
 
+/// The ids a reference-list value names: an array of ids, or one id.
+fn reference_ids(value: &Value) -> Vec<String> {
+    match value {
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect(),
+        Value::String(id) if !id.is_empty() => vec![id.clone()],
+        _ => Vec::new(),
+    }
+}
+
 /// Update a single field on any entity.
 ///
 /// Generic command that works with any entity type (task, tag, actor, etc.).
@@ -98,6 +111,22 @@ impl Execute<KanbanContext, KanbanError> for UpdateEntityField {
                 });
             }
 
+            // Dependency and parent edits face the same loop checks as
+            // `update task`, whichever path they come through.
+            if self.entity_type == "task" {
+                match self.field_name.as_str() {
+                    "depends_on" => {
+                        let deps = reference_ids(&self.value);
+                        check_task_links(&ectx, &self.id, Some(&deps), None).await?;
+                    }
+                    "parent" => {
+                        let parent = self.value.as_str().unwrap_or_default();
+                        check_task_links(&ectx, &self.id, None, Some(parent)).await?;
+                    }
+                    _ => {}
+                }
+            }
+
             // Check if this is a computed field — route through DeriveHandler
             let fields_ctx = ectx.fields();
             let field_def = fields_ctx.get_field_by_name(&self.field_name);
@@ -278,6 +307,63 @@ mod tests {
         assert!(result.is_err(), "Should fail for undefined field");
     }
 
+    #[tokio::test]
+    async fn test_update_entity_field_rejects_dependency_cycles() {
+        let (_temp, ctx) = setup().await;
+        let a = AddTask::new("A").execute(&ctx).await.into_result().unwrap()["id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        let b = AddTask::new("B").execute(&ctx).await.into_result().unwrap()["id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+
+        UpdateEntityField::new("task", &b, "depends_on", json!([a]))
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        let result = UpdateEntityField::new("task", &a, "depends_on", json!([b]))
+            .execute(&ctx)
+            .await
+            .into_result();
+        assert!(
+            matches!(result, Err(KanbanError::DependencyCycle { .. })),
+            "{result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_entity_field_rejects_parent_loops() {
+        let (_temp, ctx) = setup().await;
+        let a = AddTask::new("A").execute(&ctx).await.into_result().unwrap()["id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        let b = AddTask::new("B").execute(&ctx).await.into_result().unwrap()["id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+
+        let own = UpdateEntityField::new("task", &a, "parent", json!(a))
+            .execute(&ctx)
+            .await
+            .into_result();
+        assert!(own.is_err(), "a task cannot be its own parent");
+
+        UpdateEntityField::new("task", &b, "parent", json!(a))
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        let ancestor = UpdateEntityField::new("task", &a, "parent", json!(b))
+            .execute(&ctx)
+            .await
+            .into_result();
+        assert!(ancestor.is_err(), "a task cannot be its own ancestor");
+    }
+
     #[tokio::test]
     async fn test_update_body_auto_creates_tag_entities() {
         let (_temp, ctx) = setup().await;
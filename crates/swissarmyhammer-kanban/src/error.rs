@@ -57,6 +57,16 @@ pub enum KanbanError {
     #[error("duplicate {item_type} ID: {id}")]
     DuplicateId { item_type: String, id: String },
 
+    /// Column is already at its work-in-progress limit
+    #[error(
+        "column '{column}' is at its WIP limit ({count}/{limit}); set override_wip_limit to exceed it"
+    )]
+    WipLimitExceeded {
+        column: String,
+        limit: usize,
+        count: usize,
+    },
+
     /// Dependency cycle detected
     #[error("dependency cycle detected: {path}")]
     DependencyCycle { path: String },
@@ -178,6 +188,17 @@ impl KanbanError {
         }
     }
 
+    /// The declarative rules a write broke, when that is why it failed.
+    pub fn rule_violations(&self) -> Option<&[swissarmyhammer_fields::RuleViolation]> {
+        match self {
+            Self::EntityError(swissarmyhammer_entity::EntityError::RuleViolations {
+                violations,
+                ..
+            }) => Some(violations),
+            _ => None,
+        }
+    }
+
     /// Check if this is a retryable error
     pub fn is_retryable(&self) -> bool {
         matches!(self, Self::LockBusy)
@@ -0,0 +1,296 @@
+//! ExportBoard command
+
+use super::{
+    csv::render_csv, ics::render_ics, json::render_json, markdown::render_markdown,
+    select::ExportSet,
+};
+use crate::context::KanbanContext;
+use crate::error::{KanbanError, Result};
+use crate::graph::{render_dot, render_mermaid, DependencyGraph};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResult};
+
+/// Export the board as a Markdown report, CSV, JSON document, iCalendar feed
+/// or dependency graph.
+///
+/// With `perspective_id` the export uses that perspective's filter, field
+/// order and sort; `filter` narrows it further. The `dot` and `mermaid`
+/// formats draw the exported tasks' `depends_on` graph instead of listing
+/// them. The document is returned as `content`, or written to `path` when one
+/// is given.
+#[operation(
+    verb = "export",
+    noun = "board",
+    description = "Export the board as Markdown, CSV, JSON, an iCalendar feed or a DOT/Mermaid dependency graph"
+)]
+#[derive(Debug, Default, Deserialize)]
+pub struct ExportBoard {
+    /// Output format: "markdown", "csv", "json", "ics", "dot" or "mermaid"
+    pub format: String,
+    /// Perspective (ID or name) whose filter, fields and sort to use
+    pub perspective_id: Option<String>,
+    /// Filter DSL expression (e.g. `#bug && @alice`)
+    pub filter: Option<String>,
+    /// File to write the export to instead of returning it
+    pub path: Option<String>,
+}
+
+impl ExportBoard {
+    /// Create an export in the given format.
+    pub fn new(format: impl Into<String>) -> Self {
+        Self {
+            format: format.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Export through a perspective.
+    pub fn with_perspective(mut self, perspective_id: impl Into<String>) -> Self {
+        self.perspective_id = Some(perspective_id.into());
+        self
+    }
+
+    /// Set a filter DSL expression.
+    pub fn with_filter(mut self, filter: impl Into<String>) -> Self {
+        self.filter = Some(filter.into());
+        self
+    }
+
+    /// Write the export to a file.
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+}
+
+#[async_trait]
+impl Execute<KanbanContext, KanbanError> for ExportBoard {
+    async fn execute(&self, ctx: &KanbanContext) -> ExecutionResult<Value, KanbanError> {
+        let result: Result<Value> = async {
+            let format = match self.format.trim().to_ascii_lowercase().as_str() {
+                "markdown" | "md" => "markdown",
+                "csv" => "csv",
+                "json" => "json",
+                "ics" | "ical" | "icalendar" => "ics",
+                "dot" | "graphviz" => "dot",
+                "mermaid" | "mmd" => "mermaid",
+                other => {
+                    return Err(KanbanError::invalid_value(
+                        "format",
+                        format!(
+                            "unknown export format '{other}' (expected markdown, csv, json, ics, dot or mermaid)"
+                        ),
+                    ))
+                }
+            };
+
+            let set =
+                ExportSet::collect(ctx, self.perspective_id.as_deref(), self.filter.as_deref())
+                    .await?;
+
+            let (content, count) = match format {
+                "markdown" => (render_markdown(&set), set.tasks.len()),
+                "csv" => (render_csv(&set), set.tasks.len()),
+                "json" => (
+                    serde_json::to_string_pretty(&render_json(&set))?,
+                    set.tasks.len(),
+                ),
+                "dot" | "mermaid" => {
+                    let terminal = set.columns.last().map(|c| c.id.as_str()).unwrap_or("done");
+                    let graph = DependencyGraph::build(&set.tasks, terminal);
+                    let content = if format == "dot" {
+                        render_dot(&graph, &set.board_name)
+                    } else {
+                        render_mermaid(&graph)
+                    };
+                    (content, set.tasks.len())
+                }
+                _ => render_ics(&set),
+            };
+
+            match &self.path {
+                Some(path) => {
+                    tokio::fs::write(path, &content).await?;
+                    Ok(json!({ "format": format, "count": count, "path": path }))
+                }
+                None => Ok(json!({ "format": format, "count": count, "content": content })),
+            }
+        }
+        .await;
+
+        match result {
+            Ok(value) => ExecutionResult::Success { value },
+            Err(error) => ExecutionResult::Failed { error },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actor::AddActor;
+    use crate::board::InitBoard;
+    use crate::perspective::{AddPerspective, PerspectiveFieldEntry, SortDirection, SortEntry};
+    use crate::task::{AddTask, ListTasks, UpdateTask};
+    use tempfile::TempDir;
+
+    async fn setup() -> (TempDir, KanbanContext) {
+        let temp = TempDir::new().unwrap();
+        let kanban_dir = temp.path().join(".kanban");
+        let ctx = KanbanContext::new(kanban_dir);
+
+        InitBoard::new("Test")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        AddActor::new("ada", "Ada Lovelace")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        AddTask::new("Write spec")
+            .with_description("Draft #docs")
+            .with_assignees(vec!["ada".into()])
+            .with_due("2026-05-04")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        AddTask::new("Plan, then build")
+            .with_scheduled("2026-05-01")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        AddTask::new("Undated")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        (temp, ctx)
+    }
+
+    async fn export(ctx: &KanbanContext, cmd: ExportBoard) -> Value {
+        cmd.execute(ctx).await.into_result().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_export_markdown_groups_by_column() {
+        let (_temp, ctx) = setup().await;
+        let result = export(&ctx, ExportBoard::new("md")).await;
+
+        assert_eq!(result["format"], "markdown");
+        assert_eq!(result["count"], 3);
+        let md = result["content"].as_str().unwrap();
+        assert!(md.starts_with("# Test\n"));
+        assert!(md.contains("\n## To Do (3)\n"));
+        assert!(md.contains("| title | tags | assignees | project | due | scheduled |"));
+        assert!(md.contains("| Write spec | docs | Ada Lovelace |  | 2026-05-04 |  |"));
+        assert!(!md.contains("## Done"));
+    }
+
+    #[tokio::test]
+    async fn test_export_csv_uses_perspective_fields_and_sort() {
+        let (_temp, ctx) = setup().await;
+        AddPerspective::new("By title", "grid")
+            .with_fields(vec![
+                PerspectiveFieldEntry::new("title").with_caption("Task"),
+                PerspectiveFieldEntry::new("due"),
+            ])
+            .with_sort(vec![SortEntry::new("title", SortDirection::Desc)])
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        let result = export(&ctx, ExportBoard::new("csv").with_perspective("By title")).await;
+        assert_eq!(
+            result["content"],
+            "Task,due\r\nWrite spec,2026-05-04\r\nUndated,\r\n\"Plan, then build\",\r\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_json_has_uniform_task_shape() {
+        let (_temp, ctx) = setup().await;
+        let result = export(&ctx, ExportBoard::new("json").with_filter("#docs")).await;
+        assert_eq!(result["count"], 1);
+
+        let doc: Value = serde_json::from_str(result["content"].as_str().unwrap()).unwrap();
+        assert_eq!(doc["board"]["name"], "Test");
+        let task = &doc["tasks"][0];
+        assert_eq!(task["title"], "Write spec");
+        assert_eq!(task["assignees"], json!(["ada"]));
+        assert_eq!(task["scheduled"], Value::Null);
+        assert_eq!(
+            task.as_object().unwrap().len(),
+            1 + doc["fields"].as_array().unwrap().len()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_ics_to_file_skips_undated_tasks() {
+        let (temp, ctx) = setup().await;
+        let path = temp.path().join("board.ics");
+        let result = export(
+            &ctx,
+            ExportBoard::new("ics").with_path(path.to_string_lossy()),
+        )
+        .await;
+        assert_eq!(result["count"], 2);
+        assert!(result.get("content").is_none());
+
+        let ics = std::fs::read_to_string(&path).unwrap();
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+        assert!(ics.contains("SUMMARY:Plan\\, then build\r\n"));
+        assert!(ics.contains("DTSTART;VALUE=DATE:20260504\r\nDTEND;VALUE=DATE:20260505\r\n"));
+        assert!(ics.contains("CATEGORIES:docs\r\n"));
+        assert!(!ics.contains("Undated"));
+    }
+
+    #[tokio::test]
+    async fn test_export_dependency_graph_as_dot_and_mermaid() {
+        let (_temp, ctx) = setup().await;
+        let tasks = ListTasks::new().execute(&ctx).await.into_result().unwrap();
+        let id = |title: &str| {
+            tasks["tasks"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|t| t["title"] == title)
+                .unwrap()["id"]
+                .as_str()
+                .unwrap()
+                .to_string()
+        };
+        let (spec, plan) = (id("Write spec"), id("Plan, then build"));
+        UpdateTask::new(plan.as_str())
+            .with_depends_on(vec![spec.as_str().into()])
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        let result = export(&ctx, ExportBoard::new("graphviz")).await;
+        assert_eq!(result["format"], "dot");
+        let dot = result["content"].as_str().unwrap();
+        assert!(dot.starts_with("digraph \"Test\" {\n"));
+        assert!(dot.contains(&format!("  \"{spec}\" -> \"{plan}\";\n")));
+
+        let result = export(&ctx, ExportBoard::new("mermaid").with_filter("#docs")).await;
+        let mmd = result["content"].as_str().unwrap();
+        assert!(mmd.starts_with("flowchart LR\n  n0[\"Write spec\"]\n"));
+        assert!(!mmd.contains("-->"), "the filter drops the dependent task");
+    }
+
+    #[tokio::test]
+    async fn test_export_rejects_unknown_format() {
+        let (_temp, ctx) = setup().await;
+        let result = ExportBoard::new("pdf").execute(&ctx).await.into_result();
+        assert!(matches!(result, Err(KanbanError::InvalidValue { .. })));
+    }
+}
@@ -0,0 +1,43 @@
+//! CSV (RFC 4180): a header row of field captions, then one row per task.
+
+use super::select::ExportSet;
+
+/// Render the export set as CSV, one row per task in export order.
+pub(crate) fn render_csv(set: &ExportSet) -> String {
+    let mut out = String::new();
+    let header: Vec<String> = set.fields.iter().map(|f| field(&f.caption)).collect();
+    out.push_str(&header.join(","));
+    out.push_str("\r\n");
+    for task in &set.tasks {
+        let row: Vec<String> = set
+            .fields
+            .iter()
+            .map(|f| field(&set.text(task, f)))
+            .collect();
+        out.push_str(&row.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+/// Quote a field when it contains a delimiter, quote or line break.
+fn field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn field_quotes_only_when_needed() {
+        assert_eq!(field("plain"), "plain");
+        assert_eq!(field("a, b"), "\"a, b\"");
+        assert_eq!(field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(field("two\nlines"), "\"two\nlines\"");
+    }
+}
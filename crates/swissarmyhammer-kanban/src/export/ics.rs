@@ -0,0 +1,138 @@
+//! iCalendar (RFC 5545) feed of dated tasks.
+//!
+//! Each task with a `scheduled` or `due` date becomes an all-day `VEVENT`
+//! spanning from the earlier date through the later one; a task with only one
+//! date is a single-day event. Tasks without dates are left out. The event
+//! UID is derived from the task id, so a calendar subscribed to the feed
+//! updates its events on reload instead of duplicating them.
+
+use super::select::ExportSet;
+use chrono::{Duration, NaiveDate, Utc};
+use swissarmyhammer_entity::Entity;
+
+/// Render the dated tasks of the export set as an iCalendar feed.
+///
+/// Returns the feed and the number of events in it.
+pub(crate) fn render_ics(set: &ExportSet) -> (String, usize) {
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//swissarmyhammer//kanban//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        format!("X-WR-CALNAME:{}", escape(&set.board_name)),
+    ];
+
+    let mut count = 0;
+    for task in &set.tasks {
+        let Some((start, end)) = event_span(task) else {
+            continue;
+        };
+        count += 1;
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}@kanban", task.id.as_str()));
+        lines.push(format!("DTSTAMP:{stamp}"));
+        lines.push(format!("DTSTART;VALUE=DATE:{}", start.format("%Y%m%d")));
+        // DTEND is exclusive for all-day events.
+        lines.push(format!(
+            "DTEND;VALUE=DATE:{}",
+            (end + Duration::days(1)).format("%Y%m%d")
+        ));
+        lines.push(format!(
+            "SUMMARY:{}",
+            escape(task.get_str("title").unwrap_or_default())
+        ));
+        if let Some(body) = task.get_str("body").filter(|b| !b.trim().is_empty()) {
+            lines.push(format!("DESCRIPTION:{}", escape(body.trim())));
+        }
+        let tags = task.get_string_list("tags");
+        if !tags.is_empty() {
+            let tags: Vec<String> = tags.iter().map(|t| escape(t)).collect();
+            lines.push(format!("CATEGORIES:{}", tags.join(",")));
+        }
+        if let Some(column) = task.get_str("position_column") {
+            lines.push(format!(
+                "X-KANBAN-COLUMN:{}",
+                escape(set.column_name(column))
+            ));
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut out = String::new();
+    for line in &lines {
+        fold_into(&mut out, line);
+    }
+    (out, count)
+}
+
+/// First and last day of a task's event, from its `scheduled` and `due` dates.
+fn event_span(task: &Entity) -> Option<(NaiveDate, NaiveDate)> {
+    let date = |field: &str| {
+        task.get_str(field)
+            .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+    };
+    match (date("scheduled"), date("due")) {
+        (Some(s), Some(d)) => Some((s.min(d), s.max(d))),
+        (Some(one), None) | (None, Some(one)) => Some((one, one)),
+        (None, None) => None,
+    }
+}
+
+/// Escape a TEXT value: backslash, semicolon, comma and line breaks.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Append a content line, folded at 75 octets with CRLF + space.
+fn fold_into(out: &mut String, line: &str) {
+    let mut width = 0;
+    for ch in line.chars() {
+        let len = ch.len_utf8();
+        if width + len > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(ch);
+        width += len;
+    }
+    out.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn event_span_orders_and_defaults_dates() {
+        let mut task = Entity::new("task", "t1");
+        assert_eq!(event_span(&task), None);
+
+        task.set("due", json!("2026-03-10"));
+        let day = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        assert_eq!(event_span(&task), Some((day, day)));
+
+        task.set("scheduled", json!("2026-03-01"));
+        let start = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        assert_eq!(event_span(&task), Some((start, day)));
+    }
+
+    #[test]
+    fn escape_and_fold_follow_rfc5545() {
+        assert_eq!(escape("a,b;c\\d\ne"), "a\\,b\\;c\\\\d\\ne");
+
+        let mut out = String::new();
+        fold_into(&mut out, &format!("SUMMARY:{}", "x".repeat(100)));
+        let lines: Vec<&str> = out.split("\r\n").collect();
+        assert_eq!(lines[0].len(), 75);
+        assert!(lines[1].starts_with(' '));
+        assert_eq!(lines[1].len(), 100 + 8 - 75 + 1);
+        assert_eq!(lines[2], "");
+    }
+}
@@ -0,0 +1,40 @@
+//! Normalized JSON document.
+
+use super::select::ExportSet;
+use serde_json::{json, Map, Value};
+
+/// Render the export set as one JSON document.
+///
+/// Every task object has `id` plus exactly the exported fields, with `null`
+/// for a missing value, so consumers can rely on the shape. Values are the
+/// raw stored ones (ids for references); `columns` maps column ids to names.
+pub(crate) fn render_json(set: &ExportSet) -> Value {
+    let tasks: Vec<Value> = set
+        .tasks
+        .iter()
+        .map(|task| {
+            let mut obj = Map::new();
+            obj.insert("id".into(), json!(task.id.as_str()));
+            for field in &set.fields {
+                obj.insert(field.name.clone(), set.value(task, &field.name));
+            }
+            Value::Object(obj)
+        })
+        .collect();
+
+    json!({
+        "board": { "name": set.board_name },
+        "perspective": set.perspective.as_ref().map(|(id, name)| json!({ "id": id, "name": name })),
+        "fields": set
+            .fields
+            .iter()
+            .map(|f| json!({ "name": f.name, "caption": f.caption }))
+            .collect::<Vec<_>>(),
+        "columns": set
+            .columns
+            .iter()
+            .map(|c| json!({ "id": c.id, "name": c.name }))
+            .collect::<Vec<_>>(),
+        "tasks": tasks,
+    })
+}
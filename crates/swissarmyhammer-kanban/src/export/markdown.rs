@@ -0,0 +1,74 @@
+//! Markdown report: one section per column, each a table of its tasks.
+
+use super::select::{ExportField, ExportSet};
+
+/// Render the export set as a Markdown report.
+///
+/// Columns appear in board order and only when they hold a matching task.
+/// The column field itself is left out of the tables, since the section
+/// heading already says it.
+pub(crate) fn render_markdown(set: &ExportSet) -> String {
+    let mut out = format!("# {}\n", inline(&set.board_name));
+    if let Some((_, name)) = &set.perspective {
+        out.push_str(&format!("\nPerspective: {}\n", inline(name)));
+    }
+    out.push_str(&format!("\n{} tasks\n", set.tasks.len()));
+
+    let fields: Vec<&ExportField> = set
+        .fields
+        .iter()
+        .filter(|f| f.name != "position_column")
+        .collect();
+
+    for column in &set.columns {
+        let tasks: Vec<_> = set
+            .tasks
+            .iter()
+            .filter(|t| t.get_str("position_column") == Some(column.id.as_str()))
+            .collect();
+        if tasks.is_empty() {
+            continue;
+        }
+
+        out.push_str(&format!(
+            "\n## {} ({})\n\n",
+            inline(&column.name),
+            tasks.len()
+        ));
+        if fields.is_empty() {
+            continue;
+        }
+        let header: Vec<String> = fields.iter().map(|f| cell(&f.caption)).collect();
+        out.push_str(&format!("| {} |\n", header.join(" | ")));
+        out.push_str(&format!("|{}\n", " --- |".repeat(fields.len())));
+        for task in tasks {
+            let row: Vec<String> = fields.iter().map(|f| cell(&set.text(task, f))).collect();
+            out.push_str(&format!("| {} |\n", row.join(" | ")));
+        }
+    }
+    out
+}
+
+/// Collapse text onto one line for a heading.
+fn inline(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Escape text for a table cell: pipes are escaped and line breaks become `<br>`.
+fn cell(text: &str) -> String {
+    text.trim()
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cell_escapes_pipes_and_newlines() {
+        assert_eq!(cell(" a | b\nc "), "a \\| b<br>c");
+        assert_eq!(inline("Sprint\n  board"), "Sprint board");
+    }
+}
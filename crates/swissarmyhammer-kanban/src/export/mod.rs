@@ -0,0 +1,23 @@
+//! Board export for sharing outside the app.
+//!
+//! The board — or one perspective of it, or a filtered subset — is rendered
+//! as a single document:
+//!
+//! - `markdown` — a report with one table per column
+//! - `csv` — one row per task
+//! - `json` — a normalized document (every task has the same keys)
+//! - `ics` — an iCalendar feed of the tasks with `due` / `scheduled` dates
+//! - `dot` / `mermaid` — the `depends_on` graph between the exported tasks,
+//!   drawn by [`crate::graph`]
+//!
+//! All formats share [`select::ExportSet`], so a perspective's field order
+//! and sort entries apply the same way to each.
+
+mod board;
+mod csv;
+mod ics;
+mod json;
+mod markdown;
+mod select;
+
+pub use board::ExportBoard;
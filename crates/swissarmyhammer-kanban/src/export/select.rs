@@ -0,0 +1,344 @@
+//! Choose, order and describe the tasks an export renders.
+//!
+//! Every format works from the same [`ExportSet`]: the board's columns in
+//! order, the fields to show (a perspective's field list, or
+//! [`DEFAULT_FIELDS`]) and the matching tasks, already sorted by the
+//! perspective's sort entries with board order as the tie-break.
+
+use crate::context::KanbanContext;
+use crate::error::{KanbanError, Result};
+use crate::perspective::{PerspectiveFieldEntry, SortDirection};
+use crate::task::parse_filter_expr;
+use crate::task_helpers::{
+    enrich_all_task_entities_with_wip_limits, EntitySlugRegistry, TaskFilterAdapter,
+};
+use crate::virtual_tags::{default_virtual_tag_registry, ColumnWipLimits};
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use swissarmyhammer_entity::{Entity, EntityContext};
+use swissarmyhammer_fields::{FieldDef, FieldType};
+
+/// Fields exported when no perspective supplies its own list.
+pub(crate) const DEFAULT_FIELDS: &[&str] = &[
+    "title",
+    "position_column",
+    "tags",
+    "assignees",
+    "project",
+    "due",
+    "scheduled",
+];
+
+/// A board column, in board order.
+#[derive(Debug, Clone)]
+pub(crate) struct ExportColumn {
+    pub id: String,
+    pub name: String,
+}
+
+/// One exported field.
+#[derive(Debug, Clone)]
+pub(crate) struct ExportField {
+    /// Field name, the key in JSON output.
+    pub name: String,
+    /// Column header for Markdown and CSV: the perspective caption or the name.
+    pub caption: String,
+    /// Entity type a reference field points at, for display names.
+    reference: Option<String>,
+}
+
+/// Everything an export renders.
+#[derive(Debug)]
+pub(crate) struct ExportSet {
+    pub board_name: String,
+    /// `(id, name)` of the perspective the export was taken through.
+    pub perspective: Option<(String, String)>,
+    pub columns: Vec<ExportColumn>,
+    pub fields: Vec<ExportField>,
+    /// Matching tasks in export order.
+    pub tasks: Vec<Entity>,
+    /// Entity type → id → display name, for reference fields.
+    names: HashMap<String, HashMap<String, String>>,
+}
+
+impl ExportSet {
+    /// Collect the board's tasks, narrowed by a perspective and/or filter.
+    pub async fn collect(
+        ctx: &KanbanContext,
+        perspective_id: Option<&str>,
+        filter: Option<&str>,
+    ) -> Result<Self> {
+        let perspective = match perspective_id {
+            Some(id) => {
+                let pctx = ctx.perspective_context().await?;
+                let pctx = pctx.read().await;
+                let p = pctx
+                    .get_by_id(id)
+                    .or_else(|| pctx.get_by_name(id))
+                    .ok_or_else(|| KanbanError::not_found("perspective", id))?;
+                Some(p.clone())
+            }
+            None => None,
+        };
+
+        let filter = match (perspective.as_ref().and_then(|p| p.filter.clone()), filter) {
+            (Some(p), Some(f)) if !p.trim().is_empty() => Some(format!("({p}) && ({f})")),
+            (Some(p), None) => Some(p),
+            (_, f) => f.map(str::to_string),
+        };
+        let expr = parse_filter_expr(filter.as_deref())?;
+
+        let ectx = ctx.entity_context().await?;
+        let board_name = ectx
+            .read("board", "board")
+            .await
+            .ok()
+            .and_then(|b| b.get_str("name").map(str::to_string))
+            .unwrap_or_default();
+
+        let mut all_columns = ectx.list("column").await?;
+        all_columns.sort_by_key(|c| c.get("order").and_then(|v| v.as_u64()).unwrap_or(0));
+        let columns: Vec<ExportColumn> = all_columns
+            .iter()
+            .map(|c| ExportColumn {
+                id: c.id.as_str().to_string(),
+                name: c.get_str("name").unwrap_or(c.id.as_str()).to_string(),
+            })
+            .collect();
+
+        let mut tasks = ectx.list("task").await?;
+        let terminal_column = all_columns.last().map(|c| c.id.as_str()).unwrap_or("done");
+        let wip_limits = ColumnWipLimits::from_columns(&all_columns);
+        enrich_all_task_entities_with_wip_limits(
+            &mut tasks,
+            terminal_column,
+            &wip_limits,
+            default_virtual_tag_registry(),
+        );
+
+        let all_projects = ectx.list("project").await?;
+        let all_actors = ectx.list("actor").await?;
+        if let Some(expr) = expr {
+            let slug_registry = EntitySlugRegistry::build(&all_projects, &all_actors, &tasks);
+            tasks.retain(|t| expr.matches(&TaskFilterAdapter::with_registry(t, &slug_registry)));
+        }
+
+        let fields = resolve_fields(&ectx, perspective.as_ref().map(|p| &p.fields[..]));
+
+        let mut names: HashMap<String, HashMap<String, String>> = HashMap::new();
+        names.insert("column".into(), display_names(&all_columns, "name"));
+        names.insert("actor".into(), display_names(&all_actors, "name"));
+        names.insert("project".into(), display_names(&all_projects, "name"));
+        names.insert("task".into(), display_names(&tasks, "title"));
+
+        // Board order first, so the perspective's sort keys only reorder
+        // tasks they actually distinguish (the sort is stable).
+        let column_index: HashMap<&str, usize> = columns
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (c.id.as_str(), i))
+            .collect();
+        tasks.sort_by(|a, b| {
+            let col = |t: &Entity| {
+                t.get_str("position_column")
+                    .and_then(|c| column_index.get(c).copied())
+                    .unwrap_or(usize::MAX)
+            };
+            col(a).cmp(&col(b)).then_with(|| {
+                a.get_str("position_ordinal")
+                    .cmp(&b.get_str("position_ordinal"))
+            })
+        });
+        if let Some(p) = &perspective {
+            let keys: Vec<(String, SortDirection)> = p
+                .sort
+                .iter()
+                .filter_map(|s| {
+                    resolve_field(&ectx, &s.field)
+                        .map(|def| (def.name.to_string(), s.direction.clone()))
+                })
+                .collect();
+            tasks.sort_by(|a, b| {
+                keys.iter()
+                    .map(|(field, direction)| compare_values(a.get(field), b.get(field), direction))
+                    .find(|o| o.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            });
+        }
+
+        Ok(Self {
+            board_name,
+            perspective: perspective.map(|p| (p.id, p.name)),
+            columns,
+            fields,
+            tasks,
+            names,
+        })
+    }
+
+    /// The raw stored (or computed) value of a task field.
+    pub fn value(&self, task: &Entity, field: &str) -> Value {
+        task.get(field).cloned().unwrap_or(Value::Null)
+    }
+
+    /// A task field as display text: references show their target's name,
+    /// lists are comma-separated, and a missing value is empty.
+    pub fn text(&self, task: &Entity, field: &ExportField) -> String {
+        let names = field.reference.as_ref().and_then(|e| self.names.get(e));
+        let show = |v: &Value| -> String {
+            match v {
+                Value::String(s) => names
+                    .and_then(|n| n.get(s))
+                    .cloned()
+                    .unwrap_or_else(|| s.clone()),
+                Value::Null => String::new(),
+                other => other.to_string(),
+            }
+        };
+        match task.get(&field.name) {
+            Some(Value::Array(items)) => items.iter().map(show).collect::<Vec<_>>().join(", "),
+            Some(v) => show(v),
+            None => String::new(),
+        }
+    }
+
+    /// Display name of a column id.
+    pub fn column_name<'a>(&'a self, id: &'a str) -> &'a str {
+        self.columns
+            .iter()
+            .find(|c| c.id == id)
+            .map(|c| c.name.as_str())
+            .unwrap_or(id)
+    }
+}
+
+/// Look a field up by id (how perspectives store it) or by name.
+fn resolve_field<'a>(ectx: &'a EntityContext, field: &str) -> Option<&'a FieldDef> {
+    let fields = ectx.fields();
+    let def = fields
+        .get_field_by_id(field)
+        .or_else(|| fields.get_field_by_name(field))?;
+    fields
+        .fields_for_entity("task")
+        .iter()
+        .any(|f| f.name == def.name)
+        .then_some(def)
+}
+
+/// The perspective's fields in its order, or [`DEFAULT_FIELDS`] when it has
+/// none that resolve to task fields.
+fn resolve_fields(
+    ectx: &EntityContext,
+    entries: Option<&[PerspectiveFieldEntry]>,
+) -> Vec<ExportField> {
+    let to_export = |def: &FieldDef, caption: Option<&str>| ExportField {
+        name: def.name.to_string(),
+        caption: caption
+            .filter(|c| !c.trim().is_empty())
+            .unwrap_or(def.name.as_str())
+            .to_string(),
+        reference: match &def.type_ {
+            FieldType::Reference { entity, .. } => Some(entity.to_string()),
+            _ => None,
+        },
+    };
+
+    let from_perspective: Vec<ExportField> = entries
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|e| {
+            resolve_field(ectx, &e.field).map(|def| to_export(def, e.caption.as_deref()))
+        })
+        .collect();
+    if !from_perspective.is_empty() {
+        return from_perspective;
+    }
+    DEFAULT_FIELDS
+        .iter()
+        .filter_map(|name| resolve_field(ectx, name).map(|def| to_export(def, None)))
+        .collect()
+}
+
+/// Map entity id → the given display field.
+fn display_names(entities: &[Entity], field: &str) -> HashMap<String, String> {
+    entities
+        .iter()
+        .filter_map(|e| {
+            e.get_str(field)
+                .map(|name| (e.id.as_str().to_string(), name.to_string()))
+        })
+        .collect()
+}
+
+/// Order two field values for a sort key. Empty values always sort last;
+/// numbers compare numerically and text case-insensitively (ISO dates sort
+/// correctly as text).
+fn compare_values(a: Option<&Value>, b: Option<&Value>, direction: &SortDirection) -> Ordering {
+    let key = |v: Option<&Value>| -> Option<Value> {
+        match v? {
+            Value::Null => None,
+            Value::String(s) if s.is_empty() => None,
+            Value::Array(items) if items.is_empty() => None,
+            Value::Array(items) => Some(Value::String(
+                items
+                    .iter()
+                    .map(|i| {
+                        i.as_str()
+                            .map(str::to_string)
+                            .unwrap_or_else(|| i.to_string())
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )),
+            v => Some(v.clone()),
+        }
+    };
+    let ordering = match (key(a), key(b)) {
+        (None, None) => return Ordering::Equal,
+        (None, Some(_)) => return Ordering::Greater,
+        (Some(_), None) => return Ordering::Less,
+        (Some(Value::Number(x)), Some(Value::Number(y))) => x
+            .as_f64()
+            .unwrap_or(0.0)
+            .total_cmp(&y.as_f64().unwrap_or(0.0)),
+        (Some(Value::String(x)), Some(Value::String(y))) => x.to_lowercase().cmp(&y.to_lowercase()),
+        (Some(x), Some(y)) => x.to_string().cmp(&y.to_string()),
+    };
+    match direction {
+        SortDirection::Asc => ordering,
+        SortDirection::Desc => ordering.reverse(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn compare_values_puts_empty_last_in_both_directions() {
+        let a = json!("2026-01-02");
+        let b = json!("2026-01-01");
+        let empty = json!("");
+        assert_eq!(
+            compare_values(Some(&a), Some(&b), &SortDirection::Asc),
+            Ordering::Greater
+        );
+        assert_eq!(
+            compare_values(Some(&a), Some(&b), &SortDirection::Desc),
+            Ordering::Less
+        );
+        for dir in [SortDirection::Asc, SortDirection::Desc] {
+            assert_eq!(
+                compare_values(Some(&empty), Some(&a), &dir),
+                Ordering::Greater
+            );
+            assert_eq!(compare_values(None, Some(&a), &dir), Ordering::Greater);
+        }
+        assert_eq!(
+            compare_values(Some(&json!(10)), Some(&json!(9)), &SortDirection::Asc),
+            Ordering::Greater
+        );
+    }
+}
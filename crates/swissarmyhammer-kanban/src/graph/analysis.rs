@@ -0,0 +1,472 @@
+//! The task dependency graph and the analyses over it.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use swissarmyhammer_entity::Entity;
+
+use crate::task_helpers::task_parent;
+
+/// One task in a [`DependencyGraph`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphNode {
+    pub id: String,
+    pub title: String,
+    /// Column id the task is in.
+    pub column: String,
+    /// Whether the task is in the terminal column.
+    pub done: bool,
+}
+
+/// A board's tasks and their `depends_on` edges.
+///
+/// Built from tasks in board order, which every analysis uses to break ties.
+/// References to tasks outside the set are ignored. Finished tasks stay in
+/// the graph: a dependency chain is followed through them, but they never
+/// block anything themselves.
+#[derive(Debug, Clone)]
+pub struct DependencyGraph {
+    nodes: Vec<GraphNode>,
+    index: HashMap<String, usize>,
+    /// `deps[i]` — the tasks task `i` depends on.
+    deps: Vec<Vec<usize>>,
+}
+
+impl DependencyGraph {
+    /// Build the graph over `tasks`, given in board order.
+    pub fn build(tasks: &[Entity], terminal_column: &str) -> Self {
+        let nodes: Vec<GraphNode> = tasks
+            .iter()
+            .map(|t| GraphNode {
+                id: t.id.to_string(),
+                title: t.get_str("title").unwrap_or_default().to_string(),
+                column: t.get_str("position_column").unwrap_or_default().to_string(),
+                done: t.get_str("position_column") == Some(terminal_column),
+            })
+            .collect();
+        let index: HashMap<String, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (n.id.clone(), i))
+            .collect();
+        let deps = tasks
+            .iter()
+            .map(|t| {
+                let mut deps = Vec::new();
+                for dep in t.get_string_list("depends_on") {
+                    if let Some(&j) = index.get(dep.as_str()) {
+                        if !deps.contains(&j) {
+                            deps.push(j);
+                        }
+                    }
+                }
+                deps
+            })
+            .collect();
+        Self { nodes, index, deps }
+    }
+
+    /// Add an edge from every parent to each of its subtasks, since a parent
+    /// waits on its children (see [`crate::task_helpers::task_prerequisites`]).
+    pub fn with_subtasks(mut self, tasks: &[Entity]) -> Self {
+        for task in tasks {
+            let child = self.index.get(task.id.as_str());
+            let parent = task_parent(task).and_then(|p| self.index.get(p));
+            if let (Some(&child), Some(&parent)) = (child, parent) {
+                if !self.deps[parent].contains(&child) {
+                    self.deps[parent].push(child);
+                }
+            }
+        }
+        self
+    }
+
+    /// Every task, in board order.
+    pub fn nodes(&self) -> &[GraphNode] {
+        &self.nodes
+    }
+
+    /// The task with this id, if it is in the graph.
+    pub fn node(&self, id: &str) -> Option<&GraphNode> {
+        self.index.get(id).map(|&i| &self.nodes[i])
+    }
+
+    /// Every edge as `(dependency, dependent)`.
+    pub fn edges(&self) -> Vec<(&GraphNode, &GraphNode)> {
+        self.deps
+            .iter()
+            .enumerate()
+            .flat_map(|(i, deps)| deps.iter().map(move |&d| (&self.nodes[d], &self.nodes[i])))
+            .collect()
+    }
+
+    /// Groups of tasks that depend on each other in a loop (including a task
+    /// that depends on itself), each group in board order.
+    pub fn cycles(&self) -> Vec<Vec<&GraphNode>> {
+        let mut tarjan = Tarjan::new(&self.deps);
+        for v in 0..self.nodes.len() {
+            if tarjan.index[v].is_none() {
+                tarjan.visit(v);
+            }
+        }
+        let mut cycles: Vec<Vec<usize>> = tarjan
+            .components
+            .into_iter()
+            .filter(|c| c.len() > 1 || self.deps[c[0]].contains(&c[0]))
+            .map(|mut c| {
+                c.sort_unstable();
+                c
+            })
+            .collect();
+        cycles.sort_unstable();
+        cycles
+            .into_iter()
+            .map(|c| c.into_iter().map(|i| &self.nodes[i]).collect())
+            .collect()
+    }
+
+    /// The loop that giving `task_id` these dependencies would close, as the
+    /// ids along it from `task_id` back to itself; `None` if there is none.
+    /// The first dependency that closes a loop is reported, by its shortest
+    /// route back.
+    pub fn cycle_path(&self, task_id: &str, deps: &[String]) -> Option<Vec<String>> {
+        if deps.iter().any(|d| d == task_id) {
+            return Some(vec![task_id.to_string(), task_id.to_string()]);
+        }
+        let target = *self.index.get(task_id)?;
+        for dep in deps {
+            let Some(&start) = self.index.get(dep.as_str()) else {
+                continue;
+            };
+            let mut parent: HashMap<usize, usize> = HashMap::new();
+            let mut queue = VecDeque::from([start]);
+            let mut seen = HashSet::from([start]);
+            while let Some(v) = queue.pop_front() {
+                if v == target {
+                    let mut path = vec![target];
+                    let mut cur = target;
+                    while cur != start {
+                        cur = parent[&cur];
+                        path.push(cur);
+                    }
+                    path.push(target);
+                    path.reverse();
+                    return Some(path.into_iter().map(|i| self.nodes[i].id.clone()).collect());
+                }
+                for &w in &self.deps[v] {
+                    if seen.insert(w) {
+                        parent.insert(w, v);
+                        queue.push_back(w);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Every unfinished task `task_id` waits on, directly or through other
+    /// tasks, in board order.
+    pub fn transitive_blockers(&self, task_id: &str) -> Vec<&GraphNode> {
+        let Some(&v) = self.index.get(task_id) else {
+            return Vec::new();
+        };
+        let mut seen = HashSet::from([v]);
+        let mut stack = self.deps[v].clone();
+        let mut blockers = Vec::new();
+        while let Some(w) = stack.pop() {
+            if !seen.insert(w) {
+                continue;
+            }
+            if !self.nodes[w].done {
+                blockers.push(w);
+            }
+            stack.extend(&self.deps[w]);
+        }
+        blockers.sort_unstable();
+        blockers.into_iter().map(|i| &self.nodes[i]).collect()
+    }
+
+    /// Whether nothing unfinished holds `task_id` up, however indirectly.
+    pub fn is_unblocked(&self, task_id: &str) -> bool {
+        self.index
+            .get(task_id)
+            .is_some_and(|&v| self.open_deps(v).is_empty())
+    }
+
+    /// The unfinished tasks in dependency order: every task comes after
+    /// everything it waits on. Among the tasks free to go next, the one that
+    /// heads the longest chain of remaining work goes first, then board
+    /// order. Tasks in a cycle, or waiting on one, are left out.
+    pub fn topological_order(&self) -> Vec<&GraphNode> {
+        let (order, _) = self.schedule();
+        order.into_iter().map(|i| &self.nodes[i]).collect()
+    }
+
+    /// The longest chain of unfinished tasks, each waiting on the one
+    /// before it — the work that bounds how soon everything can be done.
+    pub fn critical_path(&self) -> Vec<&GraphNode> {
+        let (order, chain) = self.schedule();
+        let dependents = self.open_dependents();
+        let Some(mut cur) = order
+            .iter()
+            .copied()
+            .max_by_key(|&v| (chain[v], Reverse(v)))
+        else {
+            return Vec::new();
+        };
+        let mut path = vec![cur];
+        while chain[cur] > 1 {
+            let Some(next) = dependents[cur]
+                .iter()
+                .copied()
+                .filter(|&d| chain[d] == chain[cur] - 1)
+                .min()
+            else {
+                break;
+            };
+            path.push(next);
+            cur = next;
+        }
+        path.into_iter().map(|i| &self.nodes[i]).collect()
+    }
+
+    /// The unfinished tasks that directly hold `v` up: its unfinished
+    /// dependencies, plus those of any finished dependency, recursively.
+    fn open_deps(&self, v: usize) -> Vec<usize> {
+        let mut seen = HashSet::from([v]);
+        let mut stack = self.deps[v].clone();
+        let mut open = Vec::new();
+        while let Some(w) = stack.pop() {
+            if !seen.insert(w) {
+                continue;
+            }
+            if self.nodes[w].done {
+                stack.extend(&self.deps[w]);
+            } else {
+                open.push(w);
+            }
+        }
+        open
+    }
+
+    /// For each unfinished task, the unfinished tasks it directly holds up.
+    fn open_dependents(&self) -> Vec<Vec<usize>> {
+        let mut dependents = vec![Vec::new(); self.nodes.len()];
+        for v in (0..self.nodes.len()).filter(|&v| !self.nodes[v].done) {
+            for d in self.open_deps(v) {
+                dependents[d].push(v);
+            }
+        }
+        dependents
+    }
+
+    /// The scheduling order of [`topological_order`](Self::topological_order)
+    /// and, per task, the length of the longest chain of unfinished work it
+    /// heads (zero for tasks left out of the order).
+    fn schedule(&self) -> (Vec<usize>, Vec<usize>) {
+        let n = self.nodes.len();
+        let dependents = self.open_dependents();
+        let waiting_on = |v: usize| self.open_deps(v).len();
+
+        // A plain pass first, to learn which tasks can be scheduled at all
+        // and how long a chain each heads.
+        let mut pending: Vec<usize> = (0..n).map(waiting_on).collect();
+        let mut ready: VecDeque<usize> = (0..n)
+            .filter(|&v| !self.nodes[v].done && pending[v] == 0)
+            .collect();
+        let mut plain = Vec::new();
+        while let Some(v) = ready.pop_front() {
+            plain.push(v);
+            for &d in &dependents[v] {
+                pending[d] -= 1;
+                if pending[d] == 0 {
+                    ready.push_back(d);
+                }
+            }
+        }
+        let mut chain = vec![0; n];
+        for &v in plain.iter().rev() {
+            chain[v] = 1 + dependents[v].iter().map(|&d| chain[d]).max().unwrap_or(0);
+        }
+
+        let mut pending: Vec<usize> = (0..n).map(waiting_on).collect();
+        let mut heap: BinaryHeap<(usize, Reverse<usize>)> = (0..n)
+            .filter(|&v| !self.nodes[v].done && pending[v] == 0)
+            .map(|v| (chain[v], Reverse(v)))
+            .collect();
+        let mut order = Vec::with_capacity(plain.len());
+        while let Some((_, Reverse(v))) = heap.pop() {
+            order.push(v);
+            for &d in &dependents[v] {
+                pending[d] -= 1;
+                if pending[d] == 0 {
+                    heap.push((chain[d], Reverse(d)));
+                }
+            }
+        }
+        (order, chain)
+    }
+}
+
+/// Tarjan's strongly connected components over `deps`.
+struct Tarjan<'a> {
+    deps: &'a [Vec<usize>],
+    index: Vec<Option<usize>>,
+    low: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    next: usize,
+    components: Vec<Vec<usize>>,
+}
+
+impl<'a> Tarjan<'a> {
+    fn new(deps: &'a [Vec<usize>]) -> Self {
+        let n = deps.len();
+        Self {
+            deps,
+            index: vec![None; n],
+            low: vec![0; n],
+            on_stack: vec![false; n],
+            stack: Vec::new(),
+            next: 0,
+            components: Vec::new(),
+        }
+    }
+
+    fn visit(&mut self, v: usize) {
+        self.index[v] = Some(self.next);
+        self.low[v] = self.next;
+        self.next += 1;
+        self.stack.push(v);
+        self.on_stack[v] = true;
+
+        let deps = self.deps;
+        for &w in &deps[v] {
+            match self.index[w] {
+                None => {
+                    self.visit(w);
+                    self.low[v] = self.low[v].min(self.low[w]);
+                }
+                Some(iw) if self.on_stack[w] => self.low[v] = self.low[v].min(iw),
+                Some(_) => {}
+            }
+        }
+
+        if self.index[v] == Some(self.low[v]) {
+            let mut component = Vec::new();
+            while let Some(w) = self.stack.pop() {
+                self.on_stack[w] = false;
+                component.push(w);
+                if w == v {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// A task in `column` depending on `deps`.
+    fn task(id: &str, column: &str, deps: &[&str]) -> Entity {
+        let mut t = Entity::new("task", id);
+        t.set("title", json!(id.to_uppercase()));
+        t.set("position_column", json!(column));
+        t.set("depends_on", json!(deps));
+        t
+    }
+
+    fn ids(nodes: Vec<&GraphNode>) -> Vec<&str> {
+        nodes.into_iter().map(|n| n.id.as_str()).collect()
+    }
+
+    /// design → build → test → ship, with docs hanging off design and a
+    /// finished spike that build also waits on.
+    fn release() -> DependencyGraph {
+        DependencyGraph::build(
+            &[
+                task("docs", "todo", &["design"]),
+                task("design", "todo", &[]),
+                task("build", "todo", &["design", "spike"]),
+                task("spike", "done", &[]),
+                task("test", "todo", &["build"]),
+                task("ship", "todo", &["test", "docs"]),
+            ],
+            "done",
+        )
+    }
+
+    #[test]
+    fn critical_path_and_order_follow_the_longest_chain() {
+        let graph = release();
+        assert_eq!(
+            ids(graph.critical_path()),
+            ["design", "build", "test", "ship"]
+        );
+        // build heads a longer chain than docs, so it goes first even
+        // though docs is earlier on the board.
+        assert_eq!(
+            ids(graph.topological_order()),
+            ["design", "build", "docs", "test", "ship"]
+        );
+        assert!(graph.cycles().is_empty());
+    }
+
+    #[test]
+    fn transitive_blockers_reach_through_finished_tasks() {
+        let graph = DependencyGraph::build(
+            &[
+                task("a", "todo", &["b"]),
+                task("b", "done", &["c"]),
+                task("c", "doing", &[]),
+            ],
+            "done",
+        );
+        assert_eq!(ids(graph.transitive_blockers("a")), ["c"]);
+        assert!(!graph.is_unblocked("a"));
+        assert!(graph.is_unblocked("c"));
+        assert_eq!(
+            ids(release().transitive_blockers("ship")),
+            ["docs", "design", "build", "test"]
+        );
+    }
+
+    #[test]
+    fn cycles_are_found_and_cycle_creating_edits_named() {
+        let graph = DependencyGraph::build(
+            &[
+                task("a", "todo", &["b"]),
+                task("b", "todo", &["c"]),
+                task("c", "todo", &["a"]),
+                task("d", "todo", &["d"]),
+                task("e", "todo", &["a"]),
+            ],
+            "done",
+        );
+        let cycles: Vec<Vec<&str>> = graph.cycles().into_iter().map(ids).collect();
+        assert_eq!(cycles, [vec!["a", "b", "c"], vec!["d"]]);
+        assert!(
+            graph.topological_order().is_empty(),
+            "everything waits on a loop"
+        );
+
+        let graph = release();
+        assert_eq!(
+            graph.cycle_path("design", &["ship".to_string()]),
+            Some(vec![
+                "design".to_string(),
+                "ship".to_string(),
+                "docs".to_string(),
+                "design".to_string()
+            ])
+        );
+        assert_eq!(
+            graph.cycle_path("docs", &["docs".to_string()]),
+            Some(vec!["docs".to_string(), "docs".to_string()])
+        );
+        assert_eq!(graph.cycle_path("ship", &["design".to_string()]), None);
+    }
+}
@@ -0,0 +1,197 @@
+//! GetGraph command
+
+use super::{load_graph, GraphNode};
+use crate::context::KanbanContext;
+use crate::error::{KanbanError, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResult};
+
+/// Analyse the board's task dependency graph.
+///
+/// Returns every task and `depends_on` edge, any dependency cycles, the
+/// critical path (the longest chain of unfinished work) and an order the
+/// unfinished tasks can be done in. With `task_id` it also lists every
+/// unfinished task that one is transitively waiting on.
+#[operation(
+    verb = "get",
+    noun = "graph",
+    description = "Analyse task dependencies: cycles, critical path and work order"
+)]
+#[derive(Debug, Default, Deserialize)]
+pub struct GetGraph {
+    /// Also list the unfinished tasks this task transitively waits on
+    #[serde(default)]
+    pub task_id: Option<String>,
+}
+
+impl GetGraph {
+    /// Analyse the whole board.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also report this task's transitive blockers.
+    pub fn with_task(mut self, task_id: impl Into<String>) -> Self {
+        self.task_id = Some(task_id.into());
+        self
+    }
+}
+
+/// A node as returned to callers.
+fn node_json(node: &GraphNode) -> Value {
+    json!({
+        "id": node.id,
+        "title": node.title,
+        "column": node.column,
+    })
+}
+
+#[async_trait]
+impl Execute<KanbanContext, KanbanError> for GetGraph {
+    async fn execute(&self, ctx: &KanbanContext) -> ExecutionResult<Value, KanbanError> {
+        let result: Result<Value> = async {
+            let ectx = ctx.entity_context().await?;
+            let graph = load_graph(&ectx).await?;
+
+            let tasks: Vec<Value> = graph
+                .nodes()
+                .iter()
+                .map(|n| {
+                    let mut v = node_json(n);
+                    v["done"] = json!(n.done);
+                    v
+                })
+                .collect();
+            let edges: Vec<Value> = graph
+                .edges()
+                .into_iter()
+                .map(|(dependency, dependent)| json!({ "from": dependency.id, "to": dependent.id }))
+                .collect();
+            let cycles: Vec<Vec<&str>> = graph
+                .cycles()
+                .into_iter()
+                .map(|c| c.into_iter().map(|n| n.id.as_str()).collect())
+                .collect();
+            let critical_path: Vec<Value> =
+                graph.critical_path().into_iter().map(node_json).collect();
+            let order: Vec<&str> = graph
+                .topological_order()
+                .into_iter()
+                .map(|n| n.id.as_str())
+                .collect();
+
+            let mut value = json!({
+                "tasks": tasks,
+                "edges": edges,
+                "cycles": cycles,
+                "critical_path": critical_path,
+                "order": order,
+            });
+            if let Some(task_id) = self.task_id.as_deref().filter(|s| !s.trim().is_empty()) {
+                if graph.node(task_id).is_none() {
+                    return Err(KanbanError::TaskNotFound {
+                        id: task_id.to_string(),
+                    });
+                }
+                let blockers: Vec<Value> = graph
+                    .transitive_blockers(task_id)
+                    .into_iter()
+                    .map(node_json)
+                    .collect();
+                value["task_id"] = json!(task_id);
+                value["transitive_blockers"] = json!(blockers);
+            }
+            Ok(value)
+        }
+        .await;
+
+        match result {
+            Ok(value) => ExecutionResult::Success { value },
+            Err(error) => ExecutionResult::Failed { error },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::InitBoard;
+    use crate::task::{AddTask, MoveTask};
+    use crate::types::TaskId;
+    use tempfile::TempDir;
+
+    async fn setup() -> (TempDir, KanbanContext) {
+        let temp = TempDir::new().unwrap();
+        let kanban_dir = temp.path().join(".kanban");
+        let ctx = KanbanContext::new(kanban_dir);
+
+        InitBoard::new("Test")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        (temp, ctx)
+    }
+
+    async fn add(ctx: &KanbanContext, title: &str, deps: &[&str]) -> String {
+        let added = AddTask::new(title)
+            .with_depends_on(deps.iter().map(|d| TaskId::from_string(*d)).collect())
+            .execute(ctx)
+            .await
+            .into_result()
+            .unwrap();
+        added["id"].as_str().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_graph_reports_critical_path_and_blockers() {
+        let (_temp, ctx) = setup().await;
+        let spike = add(&ctx, "Spike", &[]).await;
+        let design = add(&ctx, "Design", &[spike.as_str()]).await;
+        let docs = add(&ctx, "Docs", &[design.as_str()]).await;
+        let build = add(&ctx, "Build", &[design.as_str()]).await;
+        let ship = add(&ctx, "Ship", &[build.as_str(), docs.as_str()]).await;
+        MoveTask::to_column(spike.as_str(), "done")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        let result = GetGraph::new()
+            .with_task(ship.as_str())
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(result["edges"].as_array().unwrap().len(), 5, "{result}");
+        assert_eq!(result["cycles"], json!([]));
+        let path: Vec<&str> = result["critical_path"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|n| n["title"].as_str().unwrap())
+            .collect();
+        assert_eq!(path, ["Design", "Docs", "Ship"]);
+        assert_eq!(result["order"], json!([design, docs, build, ship]));
+        let blockers: Vec<&str> = result["transitive_blockers"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|n| n["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(blockers, [design.as_str(), docs.as_str(), build.as_str()]);
+    }
+
+    #[tokio::test]
+    async fn test_graph_unknown_task_is_not_found() {
+        let (_temp, ctx) = setup().await;
+        let result = GetGraph::new()
+            .with_task("nope")
+            .execute(&ctx)
+            .await
+            .into_result();
+        assert!(matches!(result, Err(KanbanError::TaskNotFound { .. })));
+    }
+}
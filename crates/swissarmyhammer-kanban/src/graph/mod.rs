@@ -0,0 +1,57 @@
+//! Task dependency graph analysis
+//!
+//! Tasks point at the tasks they wait on through `depends_on`. Taken
+//! together those edges form a graph over the board, and this module answers
+//! questions about the whole of it rather than one hop at a time: which
+//! tasks loop back on themselves, which chain of unfinished work is longest
+//! (the critical path), everything a task is transitively waiting on, and an
+//! order in which the remaining work can be done. The graph can be exported
+//! as Graphviz DOT or a Mermaid flowchart.
+
+mod analysis;
+mod get;
+mod render;
+
+pub use analysis::{DependencyGraph, GraphNode};
+pub use get::GetGraph;
+pub(crate) use render::{render_dot, render_mermaid};
+
+use crate::error::Result;
+use crate::types::Ordinal;
+use std::collections::HashMap;
+use swissarmyhammer_entity::{Entity, EntityContext};
+
+/// Build the dependency graph over every task on the board, in board order.
+pub(crate) async fn load_graph(ectx: &EntityContext) -> Result<DependencyGraph> {
+    let mut columns = ectx.list("column").await?;
+    columns.sort_by_key(column_order);
+    let mut tasks = ectx.list("task").await?;
+    sort_by_position(&mut tasks, &columns);
+    let terminal = columns.last().map(|c| c.id.as_str()).unwrap_or("done");
+    Ok(DependencyGraph::build(&tasks, terminal))
+}
+
+/// Sort tasks into board order: by column, then by ordinal within a column.
+fn sort_by_position(tasks: &mut [Entity], columns: &[Entity]) {
+    let column_index: HashMap<&str, usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (c.id.as_str(), i))
+        .collect();
+    tasks.sort_by_cached_key(|t| {
+        let column = t
+            .get_str("position_column")
+            .and_then(|c| column_index.get(c))
+            .copied()
+            .unwrap_or(0);
+        let ordinal = Ordinal::from_string(
+            t.get_str("position_ordinal")
+                .unwrap_or(Ordinal::DEFAULT_STR),
+        );
+        (column, ordinal)
+    });
+}
+
+fn column_order(column: &Entity) -> u64 {
+    column.get("order").and_then(|v| v.as_u64()).unwrap_or(0)
+}
@@ -0,0 +1,165 @@
+//! Render a dependency graph as Graphviz DOT or a Mermaid flowchart.
+//!
+//! Edges point from a dependency to the task waiting on it, so the graph
+//! reads left to right in the order work can be done. Finished tasks are
+//! greyed out and the critical path is drawn in red.
+
+use super::{DependencyGraph, GraphNode};
+use std::collections::{HashMap, HashSet};
+
+/// Render the graph as a Graphviz `digraph` named `name`.
+pub(crate) fn render_dot(graph: &DependencyGraph, name: &str) -> String {
+    let critical: HashSet<&str> = graph
+        .critical_path()
+        .into_iter()
+        .map(|n| n.id.as_str())
+        .collect();
+
+    let mut out = format!("digraph \"{}\" {{\n", dot_escape(name));
+    out.push_str("  rankdir=LR;\n");
+    out.push_str("  node [shape=box, style=rounded];\n");
+    for node in graph.nodes() {
+        let mut attrs = vec![format!("label=\"{}\"", dot_escape(&node.title))];
+        if node.done {
+            attrs.push("style=\"rounded,filled\"".into());
+            attrs.push("fillcolor=\"#e8e8e8\"".into());
+        }
+        if critical.contains(node.id.as_str()) {
+            attrs.push("penwidth=2".into());
+            attrs.push("color=\"#d62728\"".into());
+        }
+        out.push_str(&format!(
+            "  \"{}\" [{}];\n",
+            dot_escape(&node.id),
+            attrs.join(", ")
+        ));
+    }
+    for (dependency, dependent) in graph.edges() {
+        out.push_str(&format!(
+            "  \"{}\" -> \"{}\";\n",
+            dot_escape(&dependency.id),
+            dot_escape(&dependent.id)
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Render the graph as a Mermaid `flowchart`.
+///
+/// Task ids are ULIDs, which Mermaid accepts, but node ids are numbered
+/// instead so the chart stays readable when pasted into Markdown.
+pub(crate) fn render_mermaid(graph: &DependencyGraph) -> String {
+    let critical: HashSet<&str> = graph
+        .critical_path()
+        .into_iter()
+        .map(|n| n.id.as_str())
+        .collect();
+    let node_ids: HashMap<&str, String> = graph
+        .nodes()
+        .iter()
+        .enumerate()
+        .map(|(i, n)| (n.id.as_str(), format!("n{i}")))
+        .collect();
+
+    let mut out = String::from("flowchart LR\n");
+    for (i, node) in graph.nodes().iter().enumerate() {
+        out.push_str(&format!("  n{i}[\"{}\"]\n", mermaid_escape(&node.title)));
+    }
+    for (dependency, dependent) in graph.edges() {
+        out.push_str(&format!(
+            "  {} --> {}\n",
+            node_ids[dependency.id.as_str()],
+            node_ids[dependent.id.as_str()]
+        ));
+    }
+
+    let class_list = |pick: &dyn Fn(&GraphNode) -> bool| {
+        graph
+            .nodes()
+            .iter()
+            .enumerate()
+            .filter(|(_, n)| pick(n))
+            .map(|(i, _)| format!("n{i}"))
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    let done = class_list(&|n| n.done);
+    let on_path = class_list(&|n| critical.contains(n.id.as_str()));
+    if !done.is_empty() {
+        out.push_str("  classDef done fill:#e8e8e8,color:#666\n");
+        out.push_str(&format!("  class {done} done\n"));
+    }
+    if !on_path.is_empty() {
+        out.push_str("  classDef critical stroke:#d62728,stroke-width:2px\n");
+        out.push_str(&format!("  class {on_path} critical\n"));
+    }
+    out
+}
+
+/// Escape a DOT double-quoted string.
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Escape a Mermaid quoted label: quotes become an entity, newlines a space.
+fn mermaid_escape(s: &str) -> String {
+    s.replace('"', "#quot;").replace(['\r', '\n'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use swissarmyhammer_entity::Entity;
+
+    fn graph() -> DependencyGraph {
+        let task = |id: &str, title: &str, column: &str, deps: &[&str]| {
+            let mut t = Entity::new("task", id);
+            t.set("title", json!(title));
+            t.set("position_column", json!(column));
+            t.set("depends_on", json!(deps));
+            t
+        };
+        DependencyGraph::build(
+            &[
+                task("a", "Design \"v2\"", "done", &[]),
+                task("b", "Build", "todo", &["a"]),
+                task("c", "Ship", "todo", &["b"]),
+            ],
+            "done",
+        )
+    }
+
+    #[test]
+    fn test_render_dot() {
+        let dot = render_dot(&graph(), "Sprint");
+        assert!(dot.starts_with("digraph \"Sprint\" {\n  rankdir=LR;\n"));
+        assert!(dot.contains(
+            "  \"a\" [label=\"Design \\\"v2\\\"\", style=\"rounded,filled\", fillcolor=\"#e8e8e8\"];\n"
+        ));
+        assert!(dot.contains("  \"b\" [label=\"Build\", penwidth=2, color=\"#d62728\"];\n"));
+        assert!(dot.contains("  \"a\" -> \"b\";\n  \"b\" -> \"c\";\n"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_render_mermaid() {
+        let mmd = render_mermaid(&graph());
+        assert_eq!(
+            mmd,
+            "flowchart LR\n\
+             \x20 n0[\"Design #quot;v2#quot;\"]\n\
+             \x20 n1[\"Build\"]\n\
+             \x20 n2[\"Ship\"]\n\
+             \x20 n0 --> n1\n\
+             \x20 n1 --> n2\n\
+             \x20 classDef done fill:#e8e8e8,color:#666\n\
+             \x20 class n0 done\n\
+             \x20 classDef critical stroke:#d62728,stroke-width:2px\n\
+             \x20 class n1,n2 critical\n"
+        );
+    }
+}
@@ -0,0 +1,244 @@
+//! `.kanban/hooks.yaml`: which board events notify what
+
+use crate::error::{KanbanError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use swissarmyhammer_directory::{DirectoryConfig, SwissarmyhammerConfig};
+
+/// File in the `.kanban` directory that configures hooks.
+pub const HOOKS_FILE: &str = "hooks.yaml";
+
+/// File in `~/.sah` that lists the boards whose command hooks may run.
+pub const HOOK_TRUST_FILE: &str = "trusted_hooks.yaml";
+
+/// A board event a hook can listen for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum HookEvent {
+    /// A task was created
+    #[serde(rename = "task.created")]
+    TaskCreated,
+    /// A task entered a column, by being created in it or moved into it
+    #[serde(rename = "task.entered_column")]
+    TaskEnteredColumn,
+    /// A tag was added to a task
+    #[serde(rename = "task.tagged")]
+    TaskTagged,
+    /// A task's due date passed while it was not done
+    #[serde(rename = "task.overdue")]
+    TaskOverdue,
+    /// A task was deleted or archived
+    #[serde(rename = "task.deleted")]
+    TaskDeleted,
+}
+
+impl HookEvent {
+    /// The event's name as written in `hooks.yaml`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TaskCreated => "task.created",
+            Self::TaskEnteredColumn => "task.entered_column",
+            Self::TaskTagged => "task.tagged",
+            Self::TaskOverdue => "task.overdue",
+            Self::TaskDeleted => "task.deleted",
+        }
+    }
+}
+
+/// One hook: an event filter and where to deliver it.
+///
+/// Exactly one of `command` and `url` is set. The payload is the event as
+/// JSON unless `payload` gives a Liquid template for it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HookDef {
+    /// Name used in logs and the dead-letter log
+    pub name: String,
+    /// The event that triggers the hook
+    pub on: HookEvent,
+    /// Only fire for this column id (`task.entered_column`, `task.created`)
+    #[serde(default)]
+    pub column: Option<String>,
+    /// Only fire for this tag (`task.tagged`)
+    #[serde(default)]
+    pub tag: Option<String>,
+    /// Shell command to run; the payload is written to its stdin
+    #[serde(default)]
+    pub command: Option<String>,
+    /// URL to POST the payload to
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Extra HTTP headers for `url` hooks
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Liquid template for the payload
+    #[serde(default)]
+    pub payload: Option<String>,
+    /// Retries after the first failed attempt
+    #[serde(default = "default_retries")]
+    pub retries: u32,
+    /// Delay before the first retry; each later retry waits twice as long
+    #[serde(default = "default_backoff_ms")]
+    pub backoff_ms: u64,
+    /// How long one attempt may take
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_retries() -> u32 {
+    3
+}
+
+fn default_backoff_ms() -> u64 {
+    500
+}
+
+fn default_timeout_secs() -> u64 {
+    30
+}
+
+/// The parsed `hooks.yaml`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HooksConfig {
+    /// Hooks in file order
+    #[serde(default)]
+    pub hooks: Vec<HookDef>,
+}
+
+impl HooksConfig {
+    /// Parse and check a `hooks.yaml` document.
+    pub fn parse(content: &str) -> Result<Self> {
+        let invalid = |message: String| KanbanError::parse(format!("{HOOKS_FILE}: {message}"));
+        let config = serde_yaml_ng::from_str::<Option<HooksConfig>>(content)
+            .map_err(|e| invalid(e.to_string()))?
+            .unwrap_or_default();
+        for hook in &config.hooks {
+            if hook.command.is_some() == hook.url.is_some() {
+                return Err(invalid(format!(
+                    "hook '{}' needs exactly one of `command` and `url`",
+                    hook.name
+                )));
+            }
+        }
+        Ok(config)
+    }
+
+    /// Load the board's hooks; a board without `hooks.yaml` has none.
+    pub fn load(kanban_root: &Path) -> Result<Self> {
+        match std::fs::read_to_string(kanban_root.join(HOOKS_FILE)) {
+            Ok(content) => Self::parse(&content),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// `~/.sah/trusted_hooks.yaml`: the boards allowed to run command hooks.
+///
+/// ```yaml
+/// boards:
+///   - ~/src/tools         # a repo, or its .kanban directory
+/// ```
+///
+/// A `hooks.yaml` arrives with the repository, so its `command` hooks only
+/// run for boards the user has listed here. URL hooks need no trust.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HookTrust {
+    /// Boards whose command hooks may run
+    #[serde(default)]
+    pub boards: Vec<PathBuf>,
+}
+
+impl HookTrust {
+    /// Trust exactly these boards.
+    pub fn new(boards: impl IntoIterator<Item = impl Into<PathBuf>>) -> Self {
+        Self {
+            boards: boards.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Path of the user's trust file, when there is a home directory.
+    pub fn user_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| {
+            home.join(SwissarmyhammerConfig::DIR_NAME)
+                .join(HOOK_TRUST_FILE)
+        })
+    }
+
+    /// Parse a trust file.
+    pub fn parse(content: &str) -> Result<Self> {
+        serde_yaml_ng::from_str::<Option<HookTrust>>(content)
+            .map(Option::unwrap_or_default)
+            .map_err(|e| KanbanError::parse(format!("{HOOK_TRUST_FILE}: {e}")))
+    }
+
+    /// Load the user's trust file; without one no board is trusted.
+    pub fn load_user() -> Result<Self> {
+        let Some(path) = Self::user_path() else {
+            return Ok(Self::default());
+        };
+        match std::fs::read_to_string(path) {
+            Ok(content) => Self::parse(&content),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Whether the board at `kanban_root` may run command hooks.
+    pub fn allows(&self, kanban_root: &Path) -> bool {
+        let canonical =
+            |path: &Path| std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let root = canonical(kanban_root);
+        let repo = root.parent().map(Path::to_path_buf);
+        self.boards.iter().any(|board| {
+            let board = match (board.strip_prefix("~"), dirs::home_dir()) {
+                (Ok(rest), Some(home)) => home.join(rest),
+                _ => board.clone(),
+            };
+            let board = canonical(&board);
+            board == root || Some(&board) == repo.as_ref()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_applies_defaults_and_checks_targets() {
+        let config = HooksConfig::parse(
+            "hooks:\n  - name: ci\n    on: task.entered_column\n    column: done\n    url: http://localhost:9000/\n",
+        )
+        .unwrap();
+        let hook = &config.hooks[0];
+        assert_eq!(hook.on, HookEvent::TaskEnteredColumn);
+        assert_eq!(hook.column.as_deref(), Some("done"));
+        assert_eq!(hook.retries, 3);
+        assert_eq!(hook.backoff_ms, 500);
+
+        assert!(HooksConfig::parse("").unwrap().hooks.is_empty());
+        assert!(HooksConfig::parse("hooks:\n  - name: none\n    on: task.created\n").is_err());
+        assert!(HooksConfig::parse(
+            "hooks:\n  - name: both\n    on: task.created\n    command: 'true'\n    url: http://x/\n"
+        )
+        .is_err());
+        assert!(HooksConfig::parse(
+            "hooks:\n  - name: x\n    on: task.exploded\n    command: 'true'\n"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_trust_matches_the_repo_or_its_kanban_dir() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let repo = temp.path().join("repo");
+        let root = repo.join(".kanban");
+        std::fs::create_dir_all(&root).unwrap();
+
+        assert!(HookTrust::new([&repo]).allows(&root));
+        assert!(HookTrust::new([&root]).allows(&root));
+        assert!(!HookTrust::new([temp.path()]).allows(&root));
+        assert!(!HookTrust::default().allows(&root));
+        assert!(HookTrust::parse("").unwrap().boards.is_empty());
+    }
+}
@@ -0,0 +1,205 @@
+//! Payload rendering and delivery with retry, backoff and a dead-letter log
+
+use super::config::HookDef;
+use crate::error::{KanbanError, Result};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use swissarmyhammer_config::TemplateContext;
+use swissarmyhammer_templating::Template;
+use tokio::io::AsyncWriteExt;
+
+/// Directory in the `.kanban` directory for hook state that is not board data.
+pub(crate) const HOOKS_STATE_DIR: &str = ".hooks";
+
+/// Deliveries that failed every attempt, one JSON object per line.
+pub(crate) const DEAD_LETTER_FILE: &str = "dead_letter.jsonl";
+
+/// Path of the dead-letter log for the board at `kanban_root`.
+pub fn dead_letter_path(kanban_root: &Path) -> PathBuf {
+    kanban_root.join(HOOKS_STATE_DIR).join(DEAD_LETTER_FILE)
+}
+
+/// Render the payload for `event` (the JSON object describing it).
+///
+/// Without a template the payload is the event itself. A template sees the
+/// event's keys as variables: `{{ task.title }}`, `{{ column }}` and so on.
+pub(crate) fn render_payload(hook: &HookDef, event: &Value) -> Result<String> {
+    let Some(pattern) = &hook.payload else {
+        return Ok(event.to_string());
+    };
+    let vars: HashMap<String, Value> = event
+        .as_object()
+        .map(|obj| obj.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+        .unwrap_or_default();
+    Template::new_trusted(pattern)
+        .and_then(|t| t.render_with_context(&TemplateContext::from_template_vars(vars)))
+        .map_err(|e| KanbanError::parse(format!("hook '{}' payload: {e}", hook.name)))
+}
+
+/// One delivery attempt.
+async fn attempt(
+    kanban_root: &Path,
+    hook: &HookDef,
+    event: &Value,
+    payload: &str,
+) -> std::result::Result<(), String> {
+    let timeout = Duration::from_secs(hook.timeout_secs.max(1));
+    if let Some(url) = &hook.url {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| e.to_string())?;
+        let content_type = if serde_json::from_str::<Value>(payload).is_ok() {
+            "application/json"
+        } else {
+            "text/plain; charset=utf-8"
+        };
+        let mut request = client
+            .post(url)
+            .header("content-type", content_type)
+            .header(
+                "x-kanban-event",
+                event["event"].as_str().unwrap_or_default(),
+            )
+            .body(payload.to_string());
+        for (name, value) in &hook.headers {
+            request = request.header(name, value);
+        }
+        let response = request.send().await.map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("HTTP {}", response.status()));
+        }
+        return Ok(());
+    }
+
+    let command = hook.command.as_deref().unwrap_or_default();
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let mut child = tokio::process::Command::new(shell)
+        .arg(flag)
+        .arg(command)
+        .current_dir(kanban_root.parent().unwrap_or(kanban_root))
+        .env("KANBAN_EVENT", event["event"].as_str().unwrap_or_default())
+        .env("KANBAN_HOOK", &hook.name)
+        .env(
+            "KANBAN_TASK_ID",
+            event["task"]["id"].as_str().unwrap_or_default(),
+        )
+        .env("KANBAN_ROOT", kanban_root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A command that ignores its input may exit before reading it.
+        let _ = stdin.write_all(payload.as_bytes()).await;
+    }
+    let output = tokio::time::timeout(timeout, child.wait_with_output())
+        .await
+        .map_err(|_| format!("timed out after {}s", timeout.as_secs()))?
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("{}: {}", output.status, stderr.trim()));
+    }
+    Ok(())
+}
+
+/// Deliver `payload`, retrying with exponential backoff. When every attempt
+/// fails the delivery is appended to the dead-letter log.
+pub(crate) async fn deliver(kanban_root: &Path, hook: &HookDef, event: &Value, payload: String) {
+    let attempts = hook.retries + 1;
+    let mut delay = Duration::from_millis(hook.backoff_ms);
+    let mut last_error = String::new();
+    for n in 1..=attempts {
+        match attempt(kanban_root, hook, event, &payload).await {
+            Ok(()) => return,
+            Err(e) => {
+                tracing::debug!(hook = %hook.name, attempt = n, error = %e, "hook delivery failed");
+                last_error = e;
+            }
+        }
+        if n < attempts {
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+        }
+    }
+
+    tracing::warn!(hook = %hook.name, error = %last_error, "hook delivery gave up");
+    let record = json!({
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "hook": hook.name,
+        "event": event["event"],
+        "task_id": event["task"]["id"],
+        "attempts": attempts,
+        "error": last_error,
+        "payload": payload,
+    });
+    if let Err(e) = append_line(&dead_letter_path(kanban_root), &record.to_string()).await {
+        tracing::warn!(hook = %hook.name, error = %e, "failed to write hook dead letter");
+    }
+}
+
+async fn append_line(path: &Path, line: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await?;
+    file.write_all(format!("{line}\n").as_bytes()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hooks::HooksConfig;
+
+    fn hook(yaml: &str) -> HookDef {
+        HooksConfig::parse(yaml).unwrap().hooks.remove(0)
+    }
+
+    #[test]
+    fn test_render_payload_defaults_to_event_json_and_renders_templates() {
+        let event = json!({"event": "task.tagged", "tag": "bug", "task": {"title": "Crash"}});
+        let plain = hook("hooks:\n  - name: a\n    on: task.tagged\n    command: cat\n");
+        assert_eq!(
+            serde_json::from_str::<Value>(&render_payload(&plain, &event).unwrap()).unwrap(),
+            event
+        );
+
+        let templated = hook(
+            "hooks:\n  - name: b\n    on: task.tagged\n    command: cat\n    payload: '{{ task.title }} is #{{ tag }}'\n",
+        );
+        assert_eq!(render_payload(&templated, &event).unwrap(), "Crash is #bug");
+    }
+
+    #[tokio::test]
+    async fn test_deliver_dead_letters_after_retries() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path().join(".kanban");
+        std::fs::create_dir_all(&root).unwrap();
+        let failing = hook(
+            "hooks:\n  - name: broken\n    on: task.created\n    command: 'exit 3'\n    retries: 2\n    backoff_ms: 1\n",
+        );
+        let event = json!({"event": "task.created", "task": {"id": "01T"}});
+
+        deliver(&root, &failing, &event, "{}".to_string()).await;
+
+        let log = std::fs::read_to_string(dead_letter_path(&root)).unwrap();
+        let record: Value = serde_json::from_str(log.lines().next().unwrap()).unwrap();
+        assert_eq!(record["hook"], "broken");
+        assert_eq!(record["attempts"], 3);
+        assert_eq!(record["task_id"], "01T");
+    }
+}
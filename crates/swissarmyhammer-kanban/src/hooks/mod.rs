@@ -0,0 +1,24 @@
+//! Board event hooks
+//!
+//! `.kanban/hooks.yaml` lists hooks that run a shell command or POST to a URL
+//! when a board event matches: a task is created, enters a column, gains a
+//! tag, passes its due date, or is deleted. Events come from the entity
+//! cache's change stream, so edits made by any operation — or, with the
+//! file watcher running, by hand — are seen. Each payload is the event as
+//! JSON or a Liquid template over it. A failed delivery is retried with
+//! exponential backoff and, when every attempt fails, appended to
+//! `.kanban/.hooks/dead_letter.jsonl`. Command hooks run only for boards
+//! listed in the user's `~/.sah/trusted_hooks.yaml`, since `hooks.yaml` comes
+//! with the repository.
+//!
+//! [`crate::KanbanContext::start_hooks`] starts the dispatcher;
+//! [`crate::KanbanContext::drain_hooks`] lets one-shot callers wait, for a
+//! few seconds at most, for the deliveries their operations triggered.
+
+mod config;
+mod deliver;
+mod runner;
+
+pub use config::{HookDef, HookEvent, HookTrust, HooksConfig, HOOKS_FILE, HOOK_TRUST_FILE};
+pub use deliver::dead_letter_path;
+pub use runner::HookRunner;
@@ -0,0 +1,310 @@
+//! Turning entity cache events into hook deliveries
+
+use super::config::{HookDef, HookEvent, HooksConfig};
+use super::deliver::{deliver, render_payload, HOOKS_STATE_DIR};
+use crate::error::Result;
+use crate::task_helpers::task_entity_to_json;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use swissarmyhammer_entity::{Entity, EntityCache, EntityContext, EntityEvent};
+use tokio::sync::{broadcast, oneshot, Mutex};
+use tokio::task::{JoinHandle, JoinSet};
+
+/// Overdue notices already sent, as task id → the due date they were for,
+/// so a restart does not repeat them.
+const OVERDUE_SENT_FILE: &str = "overdue_sent.json";
+
+/// How often a long-running runner looks for tasks that became overdue.
+const OVERDUE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long [`HookRunner::drain`] waits for deliveries before leaving the
+/// rest to finish in the background.
+const DRAIN_DEADLINE: Duration = Duration::from_secs(5);
+
+/// A running hook dispatcher for one board.
+///
+/// Owned by [`crate::KanbanContext`]; see
+/// [`crate::KanbanContext::start_hooks`]. Dropping it stops the dispatcher
+/// and any deliveries [`drain`](Self::drain) has not handed off.
+pub struct HookRunner {
+    stop: Mutex<Option<oneshot::Sender<()>>>,
+    task: Mutex<Option<JoinHandle<JoinSet<()>>>>,
+}
+
+impl HookRunner {
+    /// Subscribe to `cache` and start dispatching. The board's tasks are
+    /// snapshotted before this returns, so only later changes fire hooks.
+    pub(crate) async fn start(
+        root: PathBuf,
+        config: HooksConfig,
+        ectx: Arc<EntityContext>,
+        cache: &EntityCache,
+    ) -> Result<Self> {
+        let rx = cache.subscribe();
+        let mut tasks = HashMap::new();
+        for task in ectx.list("task").await? {
+            tasks.insert(task.id.to_string(), task);
+        }
+        let overdue_sent =
+            std::fs::read_to_string(root.join(HOOKS_STATE_DIR).join(OVERDUE_SENT_FILE))
+                .ok()
+                .and_then(|s| serde_json::from_str(&s).ok())
+                .unwrap_or_default();
+        let dispatcher = Dispatcher {
+            root: Arc::new(root),
+            hooks: Arc::new(config.hooks),
+            ectx,
+            tasks,
+            overdue_sent,
+            deliveries: JoinSet::new(),
+        };
+        let (stop, stopped) = oneshot::channel();
+        let task = tokio::spawn(dispatcher.run(rx, stopped));
+        Ok(Self {
+            stop: Mutex::new(Some(stop)),
+            task: Mutex::new(Some(task)),
+        })
+    }
+
+    /// Dispatch every event already received, stop, and wait up to a few
+    /// seconds for the deliveries in flight.
+    ///
+    /// Short-lived callers use this before exiting so the hooks their own
+    /// writes triggered are not cut off. Deliveries still running at the
+    /// deadline — typically retries against an unreachable URL — are
+    /// detached and finish on the runtime, so a response is never held up
+    /// by a slow hook. Later calls do nothing.
+    pub async fn drain(&self) {
+        if let Some(stop) = self.stop.lock().await.take() {
+            let _ = stop.send(());
+        }
+        let Some(task) = self.task.lock().await.take() else {
+            return;
+        };
+        let Ok(mut deliveries) = task.await else {
+            return;
+        };
+        let finished = tokio::time::timeout(DRAIN_DEADLINE, async {
+            while deliveries.join_next().await.is_some() {}
+        })
+        .await;
+        if finished.is_err() {
+            tracing::debug!(
+                pending = deliveries.len(),
+                "hook deliveries continue in the background"
+            );
+            tokio::spawn(async move { while deliveries.join_next().await.is_some() {} });
+        }
+    }
+}
+
+impl Drop for HookRunner {
+    fn drop(&mut self) {
+        if let Some(task) = self.task.get_mut().take() {
+            task.abort();
+        }
+    }
+}
+
+/// The dispatcher's state, owned by its task.
+struct Dispatcher {
+    root: Arc<PathBuf>,
+    hooks: Arc<Vec<HookDef>>,
+    ectx: Arc<EntityContext>,
+    /// Every task as of the last event seen for it. Events are applied to
+    /// this rather than re-reading the task, so a burst of moves is reported
+    /// one step at a time even when the dispatcher runs behind the writes.
+    tasks: HashMap<String, Entity>,
+    overdue_sent: BTreeMap<String, String>,
+    deliveries: JoinSet<()>,
+}
+
+impl Dispatcher {
+    async fn run(
+        mut self,
+        mut rx: broadcast::Receiver<EntityEvent>,
+        mut stopped: oneshot::Receiver<()>,
+    ) -> JoinSet<()> {
+        self.check_overdue().await;
+        let mut overdue_check = tokio::time::interval_at(
+            tokio::time::Instant::now() + OVERDUE_CHECK_INTERVAL,
+            OVERDUE_CHECK_INTERVAL,
+        );
+        loop {
+            tokio::select! {
+                event = rx.recv() => match event {
+                    Ok(event) => self.on_entity_event(event).await,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!(skipped, "hook dispatcher fell behind; events were dropped");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                _ = overdue_check.tick() => self.check_overdue().await,
+                _ = &mut stopped => {
+                    while let Ok(event) = rx.try_recv() {
+                        self.on_entity_event(event).await;
+                    }
+                    break;
+                }
+            }
+            // Reap finished deliveries so the set does not grow unbounded.
+            while self.deliveries.try_join_next().is_some() {}
+        }
+        self.deliveries
+    }
+
+    async fn on_entity_event(&mut self, event: EntityEvent) {
+        match event {
+            EntityEvent::EntityChanged {
+                entity_type,
+                id,
+                changes,
+                ..
+            } if entity_type == "task" => {
+                let previous = self.tasks.get(&id).map(task_entity_to_json);
+                let entity = self
+                    .tasks
+                    .entry(id.clone())
+                    .or_insert_with(|| Entity::new("task", id.as_str()));
+                for change in changes {
+                    if change.value.is_null() {
+                        entity.remove(&change.field);
+                    } else {
+                        entity.set(change.field, change.value);
+                    }
+                }
+                let task = task_entity_to_json(entity);
+                self.on_task_changed(previous.as_ref(), &task);
+            }
+            EntityEvent::EntityDeleted { entity_type, id } if entity_type == "task" => {
+                self.overdue_sent.remove(&id);
+                if let Some(task) = self.tasks.remove(&id) {
+                    self.fire(
+                        HookEvent::TaskDeleted,
+                        &task_entity_to_json(&task),
+                        json!({}),
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn on_task_changed(&mut self, previous: Option<&Value>, task: &Value) {
+        let column = &task["position"]["column"];
+        if previous.is_none() {
+            self.fire(HookEvent::TaskCreated, task, json!({ "column": column }));
+        }
+        let previous_column = previous.map(|p| &p["position"]["column"]);
+        if previous_column != Some(column) {
+            self.fire(
+                HookEvent::TaskEnteredColumn,
+                task,
+                json!({ "column": column, "previous_column": previous_column }),
+            );
+        }
+        let old_tags = previous
+            .and_then(|p| p["tags"].as_array())
+            .cloned()
+            .unwrap_or_default();
+        for tag in task["tags"].as_array().into_iter().flatten() {
+            if !old_tags.contains(tag) {
+                self.fire(HookEvent::TaskTagged, task, json!({ "tag": tag }));
+            }
+        }
+    }
+
+    /// Fire `task.overdue` once per due date for every unfinished task whose
+    /// due date is before today.
+    async fn check_overdue(&mut self) {
+        let Ok(columns) = self.ectx.list("column").await else {
+            return;
+        };
+        let terminal = columns
+            .iter()
+            .max_by_key(|c| c.get("order").and_then(|v| v.as_u64()).unwrap_or(0))
+            .map(|c| c.id.to_string());
+        let today = chrono::Local::now().date_naive();
+
+        let mut overdue = Vec::new();
+        for (id, task) in &self.tasks {
+            let task = task_entity_to_json(task);
+            let Some(due) = task["due"].as_str() else {
+                continue;
+            };
+            let Some(due_date) = due
+                .get(..10)
+                .and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+            else {
+                continue;
+            };
+            let done = terminal.as_deref() == task["position"]["column"].as_str();
+            let sent = self.overdue_sent.get(id).map(String::as_str) == Some(due);
+            if due_date < today && !done && !sent {
+                overdue.push((id.clone(), due.to_string(), task));
+            }
+        }
+        if overdue.is_empty() {
+            return;
+        }
+        for (id, due, task) in overdue {
+            self.fire(HookEvent::TaskOverdue, &task, json!({ "due": due }));
+            self.overdue_sent.insert(id, due);
+        }
+        let path = self.root.join(HOOKS_STATE_DIR).join(OVERDUE_SENT_FILE);
+        if let Err(e) = write_json(&path, &self.overdue_sent) {
+            tracing::warn!(error = %e, "failed to record sent overdue hooks");
+        }
+    }
+
+    /// Start a delivery for every hook that matches the event.
+    fn fire(&mut self, kind: HookEvent, task: &Value, details: Value) {
+        let mut event = json!({
+            "event": kind.as_str(),
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+            "task": task,
+        });
+        if let (Some(event), Some(details)) = (event.as_object_mut(), details.as_object()) {
+            event.extend(details.clone());
+        }
+        for hook in self.hooks.iter().filter(|hook| matches(hook, kind, &event)) {
+            let mut event = event.clone();
+            event["hook"] = json!(hook.name);
+            let payload = match render_payload(hook, &event) {
+                Ok(payload) => payload,
+                Err(e) => {
+                    tracing::warn!(hook = %hook.name, error = %e, "hook payload failed to render");
+                    continue;
+                }
+            };
+            let root = Arc::clone(&self.root);
+            let hook = hook.clone();
+            self.deliveries.spawn(async move {
+                deliver(&root, &hook, &event, payload).await;
+            });
+        }
+    }
+}
+
+/// Whether `hook` listens for this event.
+fn matches(hook: &HookDef, kind: HookEvent, event: &Value) -> bool {
+    hook.on == kind
+        && hook
+            .column
+            .as_deref()
+            .is_none_or(|column| event["column"].as_str() == Some(column))
+        && hook
+            .tag
+            .as_deref()
+            .is_none_or(|tag| event["tag"].as_str() == Some(tag))
+}
+
+fn write_json(path: &Path, value: &impl serde::Serialize) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(value)?)
+}
@@ -0,0 +1,455 @@
+//! Apply an [`ImportPlan`] to the board.
+//!
+//! Columns, actors and tags the board lacks are created first — new columns
+//! before the board's terminal column, so it stays the done column — then
+//! each planned task is matched to an existing task by its `external_id`:
+//!
+//! - no match: the task is created;
+//! - a match whose title, body, column, assignees or dates differ from the
+//!   source: the task is refreshed in place (same id, history kept);
+//! - otherwise the task is left alone.
+//!
+//! So re-importing the same export is a no-op, and re-importing a newer one
+//! only touches what changed upstream. A value the source leaves empty never
+//! clears what is on the board, and a source that only knows "open" (GitHub's
+//! [`ColumnRef::First`]) leaves an open task in whatever column it was moved
+//! to — only reopening a task in the terminal column moves it back.
+//!
+//! Import is a bulk load, so column WIP limits are not enforced.
+
+use super::plan::{ColumnRef, ImportPlan, PlannedTask};
+use crate::auto_color;
+use crate::entity::position;
+use crate::error::{KanbanError, Result};
+use crate::tag::find_tag_entity_by_name;
+use crate::tag_parser::append_tag;
+use crate::task::{auto_create_body_tags, AddTask};
+use crate::types::ActorId;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use swissarmyhammer_entity::{Entity, EntityContext};
+
+/// What happened (or, for a dry run, would happen) to one planned task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum TaskAction {
+    Created,
+    Updated,
+    Unchanged,
+}
+
+impl TaskAction {
+    fn as_str(self) -> &'static str {
+        match self {
+            TaskAction::Created => "created",
+            TaskAction::Updated => "updated",
+            TaskAction::Unchanged => "unchanged",
+        }
+    }
+}
+
+/// Apply `plan` to the board, or only report what would change when `dry_run`.
+pub(crate) async fn apply_plan(
+    ectx: &EntityContext,
+    plan: &ImportPlan,
+    dry_run: bool,
+) -> Result<Value> {
+    // Columns: planned id → board column id, in board order. New columns go
+    // before the board's terminal column, which stays the one tasks complete
+    // into; it moves up past them.
+    let mut board_columns = ectx.list("column").await?;
+    board_columns.sort_by_key(column_order);
+    let mut terminal = board_columns.last().cloned();
+    let mut column_ids: Vec<String> = board_columns
+        .iter()
+        .map(|c| c.id.as_str().to_string())
+        .collect();
+    let insert_at = column_ids.len().saturating_sub(1);
+    let mut next_order = terminal.as_ref().map(column_order).unwrap_or(0);
+
+    let mut column_map: HashMap<&str, String> = HashMap::new();
+    let mut created_columns = Vec::new();
+    for planned in &plan.columns {
+        let existing = board_columns.iter().find(|c| {
+            c.id.as_str() == planned.id
+                || c.get_str("name")
+                    .is_some_and(|n| n.eq_ignore_ascii_case(&planned.name))
+        });
+        let id = match existing {
+            Some(column) => column.id.as_str().to_string(),
+            None => {
+                if !dry_run {
+                    let mut entity = Entity::new("column", planned.id.as_str());
+                    entity.set("name", json!(planned.name));
+                    entity.set("order", json!(next_order));
+                    ectx.write(&entity).await?;
+                }
+                next_order += 1;
+                column_ids.insert(insert_at + created_columns.len(), planned.id.clone());
+                created_columns.push(planned.id.clone());
+                planned.id.clone()
+            }
+        };
+        column_map.insert(planned.id.as_str(), id);
+    }
+    if let Some(terminal) = terminal.as_mut() {
+        if !created_columns.is_empty() && !dry_run {
+            terminal.set("order", json!(next_order));
+            ectx.write(terminal).await?;
+        }
+    }
+    let first_column = column_ids.first().cloned();
+    let terminal_column = column_ids.last().cloned();
+    if first_column.is_none() && !plan.tasks.is_empty() {
+        return Err(KanbanError::invalid_value(
+            "column",
+            "the board has no columns to import tasks into",
+        ));
+    }
+
+    // Actors
+    let mut created_actors = Vec::new();
+    for planned in &plan.actors {
+        if ectx.read("actor", planned.id.as_str()).await.is_ok() {
+            continue;
+        }
+        if !dry_run {
+            let mut entity = Entity::new("actor", planned.id.as_str());
+            entity.set("name", json!(planned.name));
+            ectx.write(&entity).await?;
+        }
+        created_actors.push(planned.id.clone());
+    }
+
+    // Tags — created up front so they keep the source's colors rather than
+    // the auto-color the body-tag pass would give them.
+    let mut created_tags = Vec::new();
+    for planned in &plan.tags {
+        if find_tag_entity_by_name(ectx, &planned.name).await.is_some() {
+            continue;
+        }
+        if !dry_run {
+            let color = planned
+                .color
+                .clone()
+                .unwrap_or_else(|| auto_color::auto_color(&planned.name).to_string());
+            let mut entity = Entity::new("tag", ulid::Ulid::new().to_string().as_str());
+            entity.set("tag_name", json!(planned.name));
+            entity.set("color", json!(color));
+            ectx.write(&entity).await?;
+        }
+        created_tags.push(planned.name.clone());
+    }
+
+    // Tasks
+    let mut by_external_id: HashMap<String, Entity> = HashMap::new();
+    for task in ectx.list("task").await? {
+        if let Some(external_id) = task.get_str("external_id") {
+            by_external_id.insert(external_id.to_string(), task.clone());
+        }
+    }
+    // An archived task still claims its external id: re-importing leaves it
+    // archived rather than creating a duplicate.
+    let mut archived_by_external_id: HashMap<String, String> = HashMap::new();
+    for task in ectx.list_archived("task").await? {
+        if let Some(external_id) = task.get_str("external_id") {
+            archived_by_external_id.insert(external_id.to_string(), task.id.to_string());
+        }
+    }
+
+    let mut items = Vec::new();
+    let mut counts: HashMap<TaskAction, usize> = HashMap::new();
+    for planned in &plan.tasks {
+        let body = planned
+            .tags
+            .iter()
+            .fold(planned.body.clone(), |body, tag| append_tag(&body, tag));
+
+        if let Some(id) = archived_by_external_id.get(&planned.external_id) {
+            *counts.entry(TaskAction::Unchanged).or_default() += 1;
+            items.push(json!({
+                "external_id": planned.external_id,
+                "title": planned.title,
+                "action": TaskAction::Unchanged.as_str(),
+                "id": id,
+            }));
+            continue;
+        }
+
+        let (action, id) = match by_external_id.get(&planned.external_id).cloned() {
+            Some(existing) => {
+                let column = match &planned.column {
+                    ColumnRef::Id(id) => column_map.get(id.as_str()).cloned(),
+                    ColumnRef::Terminal => terminal_column.clone(),
+                    ColumnRef::First => {
+                        let current = existing.get_str("position_column");
+                        (current == terminal_column.as_deref())
+                            .then(|| first_column.clone())
+                            .flatten()
+                    }
+                };
+                let mut task = existing.clone();
+                let changed = refresh_task(&mut task, planned, &body, column.as_deref());
+                if changed && !dry_run {
+                    if let Some(column) =
+                        column.filter(|c| existing.get_str("position_column") != Some(c.as_str()))
+                    {
+                        let ordinal =
+                            position::resolve_ordinal(ectx, "task", &column, None).await?;
+                        task.set("position_ordinal", json!(ordinal));
+                    }
+                    ectx.write(&task).await?;
+                    auto_create_body_tags(ectx, &task).await?;
+                }
+                let action = if changed {
+                    TaskAction::Updated
+                } else {
+                    TaskAction::Unchanged
+                };
+                let id = task.id.as_str().to_string();
+                by_external_id.insert(planned.external_id.clone(), task);
+                (action, Some(id))
+            }
+            None => {
+                let column = match &planned.column {
+                    ColumnRef::Id(id) => column_map.get(id.as_str()).cloned(),
+                    ColumnRef::First => first_column.clone(),
+                    ColumnRef::Terminal => terminal_column.clone(),
+                };
+                let id = if dry_run {
+                    None
+                } else {
+                    let mut cmd = AddTask::new(planned.title.clone())
+                        .with_description(body)
+                        .with_assignees(planned.assignees.iter().map(ActorId::from).collect());
+                    cmd.column = column;
+                    cmd.due = planned.due.clone();
+                    cmd.scheduled = planned.scheduled.clone();
+                    let mut entity = cmd.build_entity(ectx).await?;
+                    entity.set("external_id", json!(planned.external_id));
+                    cmd.persist(ectx, &entity).await?;
+                    let id = entity.id.as_str().to_string();
+                    by_external_id.insert(planned.external_id.clone(), entity);
+                    Some(id)
+                };
+                (TaskAction::Created, id)
+            }
+        };
+
+        *counts.entry(action).or_default() += 1;
+        let mut item = json!({
+            "external_id": planned.external_id,
+            "title": planned.title,
+            "action": action.as_str(),
+        });
+        if let Some(id) = id {
+            item["id"] = json!(id);
+        }
+        items.push(item);
+    }
+
+    let count = |action: TaskAction| counts.get(&action).copied().unwrap_or(0);
+    Ok(json!({
+        "dry_run": dry_run,
+        "columns": { "created": created_columns },
+        "actors": { "created": created_actors },
+        "tags": { "created": created_tags },
+        "tasks": {
+            "created": count(TaskAction::Created),
+            "updated": count(TaskAction::Updated),
+            "unchanged": count(TaskAction::Unchanged),
+            "items": items,
+        },
+    }))
+}
+
+/// A column's position in board order.
+fn column_order(column: &Entity) -> u64 {
+    column.get("order").and_then(|v| v.as_u64()).unwrap_or(0)
+}
+
+/// Copy the source's values onto an existing task. Returns whether anything
+/// changed; the ordinal for a column change is the caller's job.
+fn refresh_task(
+    task: &mut Entity,
+    planned: &PlannedTask,
+    body: &str,
+    column: Option<&str>,
+) -> bool {
+    let mut changed = false;
+    let mut set = |task: &mut Entity, field: &str, value: Value| {
+        if task.get(field) != Some(&value) {
+            task.set(field, value);
+            changed = true;
+        }
+    };
+
+    set(task, "title", json!(planned.title));
+    if !body.is_empty() {
+        set(task, "body", json!(body));
+    }
+    if let Some(column) = column {
+        set(task, "position_column", json!(column));
+    }
+    if !planned.assignees.is_empty() {
+        let mut current = task.get_string_list("assignees");
+        let mut incoming = planned.assignees.clone();
+        current.sort();
+        incoming.sort();
+        if current != incoming {
+            set(task, "assignees", json!(planned.assignees));
+        }
+    }
+    if let Some(due) = &planned.due {
+        set(task, "due", json!(due));
+    }
+    if let Some(scheduled) = &planned.scheduled {
+        set(task, "scheduled", json!(scheduled));
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::InitBoard;
+    use crate::context::KanbanContext;
+    use crate::import::github::parse_github;
+    use swissarmyhammer_operations::Execute;
+    use tempfile::TempDir;
+
+    async fn setup() -> (TempDir, KanbanContext) {
+        let temp = TempDir::new().unwrap();
+        let kanban_dir = temp.path().join(".kanban");
+        let ctx = KanbanContext::new(kanban_dir);
+
+        InitBoard::new("Test")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        (temp, ctx)
+    }
+
+    const ISSUES: &str = r#"[
+      {"number": 1, "title": "Crash", "body": "Boom", "state": "OPEN",
+       "labels": [{"name": "bug", "color": "d73a4a"}],
+       "assignees": [{"login": "octocat"}]},
+      {"number": 2, "title": "Shipped", "body": "", "state": "CLOSED"}
+    ]"#;
+
+    #[tokio::test]
+    async fn reimport_is_idempotent_and_refreshes_changes() {
+        let (_temp, ctx) = setup().await;
+        let ectx = ctx.entity_context().await.unwrap();
+
+        let first = apply_plan(&ectx, &parse_github(ISSUES).unwrap(), false)
+            .await
+            .unwrap();
+        assert_eq!(first["tasks"]["created"], 2);
+        assert_eq!(first["actors"]["created"], json!(["octocat"]));
+        assert_eq!(first["tags"]["created"], json!(["bug"]));
+
+        let tag = find_tag_entity_by_name(&ectx, "bug").await.unwrap();
+        assert_eq!(tag.get_str("color"), Some("d73a4a"));
+
+        let crash_id = first["tasks"]["items"][0]["id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        let crash = ectx.read("task", &crash_id).await.unwrap();
+        assert_eq!(crash.get_str("external_id"), Some("github:#1"));
+        assert_eq!(crash.get_str("position_column"), Some("todo"));
+        assert_eq!(crash.get_str("body"), Some("Boom #bug"));
+        assert_eq!(crash.get_string_list("assignees"), vec!["octocat"]);
+        let shipped_id = first["tasks"]["items"][1]["id"].as_str().unwrap();
+        let shipped = ectx.read("task", shipped_id).await.unwrap();
+        assert_eq!(shipped.get_str("position_column"), Some("done"));
+
+        // Same export again: nothing to do.
+        let again = apply_plan(&ectx, &parse_github(ISSUES).unwrap(), false)
+            .await
+            .unwrap();
+        assert_eq!(again["tasks"]["created"], 0);
+        assert_eq!(again["tasks"]["unchanged"], 2);
+        assert_eq!(ectx.list("task").await.unwrap().len(), 2);
+
+        // An open task moved on the board stays where it was; a retitled
+        // issue is refreshed in place.
+        let mut moved = ectx.read("task", &crash_id).await.unwrap();
+        moved.set("position_column", json!("doing"));
+        ectx.write(&moved).await.unwrap();
+        let renamed = ISSUES.replace("\"Crash\"", "\"Crash on start\"");
+        let third = apply_plan(&ectx, &parse_github(&renamed).unwrap(), false)
+            .await
+            .unwrap();
+        assert_eq!(third["tasks"]["updated"], 1);
+        assert_eq!(third["tasks"]["items"][0]["id"], crash_id);
+        let crash = ectx.read("task", &crash_id).await.unwrap();
+        assert_eq!(crash.get_str("title"), Some("Crash on start"));
+        assert_eq!(crash.get_str("position_column"), Some("doing"));
+    }
+
+    #[tokio::test]
+    async fn reimport_does_not_duplicate_archived_tasks() {
+        let (_temp, ctx) = setup().await;
+        let ectx = ctx.entity_context().await.unwrap();
+
+        let first = apply_plan(&ectx, &parse_github(ISSUES).unwrap(), false)
+            .await
+            .unwrap();
+        let shipped_id = first["tasks"]["items"][1]["id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        ectx.archive("task", &shipped_id).await.unwrap();
+
+        let again = apply_plan(&ectx, &parse_github(ISSUES).unwrap(), false)
+            .await
+            .unwrap();
+        assert_eq!(again["tasks"]["created"], 0);
+        assert_eq!(again["tasks"]["unchanged"], 2);
+        assert_eq!(again["tasks"]["items"][1]["id"], shipped_id);
+        assert_eq!(ectx.list("task").await.unwrap().len(), 1);
+        assert_eq!(ectx.list_archived("task").await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn new_columns_go_before_the_terminal_column() {
+        let (_temp, ctx) = setup().await;
+        let ectx = ctx.entity_context().await.unwrap();
+        let plan = crate::import::csv::parse_csv("title,column\nA,Blocked\n", None).unwrap();
+
+        let report = apply_plan(&ectx, &plan, false).await.unwrap();
+
+        let mut columns = ectx.list("column").await.unwrap();
+        columns.sort_by_key(column_order);
+        let ids: Vec<&str> = columns.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["todo", "doing", "blocked", "done"]);
+
+        let id = report["tasks"]["items"][0]["id"].as_str().unwrap();
+        crate::task::CompleteTask::new(id)
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        let task = ectx.read("task", id).await.unwrap();
+        assert_eq!(task.get_str("position_column"), Some("done"));
+    }
+
+    #[tokio::test]
+    async fn dry_run_writes_nothing() {
+        let (_temp, ctx) = setup().await;
+        let ectx = ctx.entity_context().await.unwrap();
+        let plan = crate::import::csv::parse_csv("title,column\nA,Blocked\n", None).unwrap();
+
+        let report = apply_plan(&ectx, &plan, true).await.unwrap();
+        assert_eq!(report["dry_run"], true);
+        assert_eq!(report["columns"]["created"], json!(["blocked"]));
+        assert_eq!(report["tasks"]["created"], 1);
+        assert!(report["tasks"]["items"][0].get("id").is_none());
+
+        assert!(ectx.read("column", "blocked").await.is_err());
+        assert!(ectx.list("task").await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,180 @@
+//! ImportBoard command
+
+use super::{apply::apply_plan, csv::parse_csv, github::parse_github, trello::parse_trello};
+use crate::context::KanbanContext;
+use crate::error::{KanbanError, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResult};
+
+/// Import tasks from another tool's export.
+///
+/// Each imported task records its source key in `external_id`, so running
+/// the same import again refreshes those tasks instead of duplicating them.
+/// See [`crate::import`] for the supported formats.
+#[operation(
+    verb = "import",
+    noun = "board",
+    description = "Import tasks from a GitHub issues, Trello or CSV export"
+)]
+#[derive(Debug, Default, Deserialize)]
+pub struct ImportBoard {
+    /// Export format: "github", "trello" or "csv"
+    pub format: String,
+    /// Path of the export file (use this or `content`)
+    pub path: Option<String>,
+    /// Export text inline (use this or `path`)
+    pub content: Option<String>,
+    /// CSV only: map of CSV header to task field (title, description, column, tags, assignees, due, scheduled, external_id)
+    pub mapping: Option<HashMap<String, String>>,
+    /// Report what would change without writing anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl ImportBoard {
+    /// Import the export file at `path`.
+    pub fn from_path(format: impl Into<String>, path: impl Into<String>) -> Self {
+        Self {
+            format: format.into(),
+            path: Some(path.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Import export text passed inline.
+    pub fn from_content(format: impl Into<String>, content: impl Into<String>) -> Self {
+        Self {
+            format: format.into(),
+            content: Some(content.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Set the CSV header → task field mapping.
+    pub fn with_mapping(mut self, mapping: HashMap<String, String>) -> Self {
+        self.mapping = Some(mapping);
+        self
+    }
+
+    /// Only report what the import would change.
+    pub fn with_dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+}
+
+#[async_trait]
+impl Execute<KanbanContext, KanbanError> for ImportBoard {
+    async fn execute(&self, ctx: &KanbanContext) -> ExecutionResult<Value, KanbanError> {
+        let result: Result<Value> = async {
+            let content = match (&self.content, &self.path) {
+                (Some(content), None) => content.clone(),
+                (None, Some(path)) => tokio::fs::read_to_string(path).await?,
+                (Some(_), Some(_)) => {
+                    return Err(KanbanError::invalid_value(
+                        "content",
+                        "pass either path or content, not both",
+                    ))
+                }
+                (None, None) => return Err(KanbanError::missing_field("path")),
+            };
+
+            let format = self.format.trim().to_ascii_lowercase();
+            let plan = match format.as_str() {
+                "github" => parse_github(&content)?,
+                "trello" => parse_trello(&content)?,
+                "csv" => parse_csv(&content, self.mapping.as_ref())?,
+                other => {
+                    return Err(KanbanError::invalid_value(
+                        "format",
+                        format!("unknown import format '{other}' (expected github, trello or csv)"),
+                    ))
+                }
+            };
+
+            let ectx = ctx.entity_context().await?;
+            let mut report = apply_plan(&ectx, &plan, self.dry_run).await?;
+            report["format"] = Value::String(format);
+            Ok(report)
+        }
+        .await;
+
+        match result {
+            Ok(value) => ExecutionResult::Success { value },
+            Err(error) => ExecutionResult::Failed { error },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::InitBoard;
+    use tempfile::TempDir;
+
+    async fn setup() -> (TempDir, KanbanContext) {
+        let temp = TempDir::new().unwrap();
+        let kanban_dir = temp.path().join(".kanban");
+        let ctx = KanbanContext::new(kanban_dir);
+
+        InitBoard::new("Test")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        (temp, ctx)
+    }
+
+    #[tokio::test]
+    async fn test_import_csv_file_creates_columns_and_tasks() {
+        let (temp, ctx) = setup().await;
+        let path = temp.path().join("tasks.csv");
+        std::fs::write(
+            &path,
+            "title,column,tags\nWrite spec,Blocked,docs\nShip it,Done,\n",
+        )
+        .unwrap();
+
+        let result = ImportBoard::from_path("csv", path.to_string_lossy())
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        assert_eq!(result["format"], "csv");
+        assert_eq!(result["columns"]["created"], serde_json::json!(["blocked"]));
+        assert_eq!(result["tasks"]["created"], 2);
+
+        let ectx = ctx.entity_context().await.unwrap();
+        let blocked = ectx.read("column", "blocked").await.unwrap();
+        assert_eq!(blocked.get_str("name"), Some("Blocked"));
+        let tasks = ectx.list("task").await.unwrap();
+        let shipped = tasks
+            .iter()
+            .find(|t| t.get_str("title") == Some("Ship it"))
+            .unwrap();
+        assert_eq!(shipped.get_str("position_column"), Some("done"));
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_unknown_format_and_missing_source() {
+        let (_temp, ctx) = setup().await;
+        let result = ImportBoard::from_content("jira", "[]")
+            .execute(&ctx)
+            .await
+            .into_result();
+        assert!(matches!(result, Err(KanbanError::InvalidValue { .. })));
+
+        let result = ImportBoard {
+            format: "csv".into(),
+            ..Default::default()
+        }
+        .execute(&ctx)
+        .await
+        .into_result();
+        assert!(matches!(result, Err(KanbanError::MissingField { .. })));
+    }
+}
@@ -0,0 +1,262 @@
+//! Generic CSV (RFC 4180) import.
+//!
+//! The first record is the header. Each header maps to a task field, either
+//! through the caller's `mapping` (header → field) or, without one, by
+//! matching the header to a field name case-insensitively. Unmapped headers
+//! are ignored. Supported fields:
+//!
+//! | Field         | Meaning                                             |
+//! |---------------|-----------------------------------------------------|
+//! | `title`       | Task title (required)                               |
+//! | `description` | Task body                                           |
+//! | `column`      | Column name; created when the board lacks it        |
+//! | `tags`        | Tag names separated by `,` or `;`                   |
+//! | `assignees`   | Actor handles separated by `,` or `;`               |
+//! | `due`         | ISO 8601 date                                       |
+//! | `scheduled`   | ISO 8601 date                                       |
+//! | `external_id` | Stable row key; defaults to the title               |
+//!
+//! The external id is `csv:<external_id>`, or `csv:<title>` when the file has
+//! no external id column.
+
+use super::plan::{ColumnRef, ImportPlan, PlannedTask};
+use crate::error::{KanbanError, Result};
+use crate::task::parse_iso8601_date;
+use std::collections::HashMap;
+
+/// Task fields a CSV column can map to.
+const CSV_FIELDS: &[&str] = &[
+    "title",
+    "description",
+    "column",
+    "tags",
+    "assignees",
+    "due",
+    "scheduled",
+    "external_id",
+];
+
+/// Parse a CSV export into an import plan.
+pub(crate) fn parse_csv(
+    content: &str,
+    mapping: Option<&HashMap<String, String>>,
+) -> Result<ImportPlan> {
+    let mut records = parse_records(content)?.into_iter();
+    let header = records
+        .next()
+        .ok_or_else(|| KanbanError::parse("CSV input is empty"))?;
+
+    // field name → column index
+    let mut fields: HashMap<&'static str, usize> = HashMap::new();
+    for (index, name) in header.iter().enumerate() {
+        let name = name.trim();
+        let target = match mapping {
+            Some(mapping) => match mapping.get(name) {
+                Some(target) => target.trim(),
+                None => continue,
+            },
+            None => name,
+        };
+        match CSV_FIELDS.iter().find(|f| f.eq_ignore_ascii_case(target)) {
+            Some(field) => {
+                fields.entry(*field).or_insert(index);
+            }
+            None if mapping.is_some() => {
+                return Err(KanbanError::invalid_value(
+                    "mapping",
+                    format!(
+                        "unknown field '{target}' for column '{name}' (expected one of: {})",
+                        CSV_FIELDS.join(", ")
+                    ),
+                ))
+            }
+            None => {}
+        }
+    }
+    if !fields.contains_key("title") {
+        return Err(KanbanError::invalid_value(
+            "mapping",
+            "no CSV column maps to title",
+        ));
+    }
+
+    let mut plan = ImportPlan::default();
+    for record in records {
+        let get = |field: &str| cell(&fields, &record, field);
+        let title = get("title");
+        if title.is_empty() {
+            continue;
+        }
+
+        let column = match get("column") {
+            "" => ColumnRef::First,
+            name => ColumnRef::Id(plan.column(name)),
+        };
+        let tags = split_list(get("tags"))
+            .map(|t| plan.tag(t.trim_start_matches('#'), None))
+            .filter(|t| !t.is_empty())
+            .collect();
+        let assignees = split_list(get("assignees"))
+            .map(|a| plan.actor(a.trim_start_matches('@'), None))
+            .filter(|a| !a.is_empty())
+            .collect();
+        let due = match get("due") {
+            "" => None,
+            d => Some(parse_iso8601_date(d, "due")?),
+        };
+        let scheduled = match get("scheduled") {
+            "" => None,
+            d => Some(parse_iso8601_date(d, "scheduled")?),
+        };
+        let key = match get("external_id") {
+            "" => title,
+            key => key,
+        };
+
+        plan.tasks.push(PlannedTask {
+            external_id: format!("csv:{key}"),
+            title: title.to_string(),
+            body: get("description").to_string(),
+            column,
+            tags,
+            assignees,
+            due,
+            scheduled,
+        });
+    }
+    Ok(plan)
+}
+
+/// The trimmed value of `field` in `record`, or `""` when it is not mapped.
+fn cell<'a>(fields: &HashMap<&str, usize>, record: &'a [String], field: &str) -> &'a str {
+    fields
+        .get(field)
+        .and_then(|&i| record.get(i))
+        .map(|s| s.trim())
+        .unwrap_or_default()
+}
+
+/// Split a multi-value cell on `,` or `;`, dropping blanks.
+fn split_list(cell: &str) -> impl Iterator<Item = &str> {
+    cell.split([',', ';'])
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+/// Split CSV text into records of fields.
+///
+/// Follows RFC 4180: fields may be quoted, quoted fields may contain commas,
+/// line breaks and doubled quotes (`""`), and records end with LF or CRLF. A
+/// leading UTF-8 BOM and blank lines are skipped.
+fn parse_records(content: &str) -> Result<Vec<Vec<String>>> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut records = Vec::new();
+    let mut record: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if in_quotes {
+            match ch {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => field.push(ch),
+            }
+            continue;
+        }
+        match ch {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                if !(record.len() == 1 && record[0].is_empty()) {
+                    records.push(std::mem::take(&mut record));
+                }
+                record.clear();
+            }
+            _ => field.push(ch),
+        }
+    }
+    if in_quotes {
+        return Err(KanbanError::parse(
+            "CSV input has an unterminated quoted field",
+        ));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_records_handles_quotes_and_crlf() {
+        let records =
+            parse_records("a,b\r\n\"x, y\",\"say \"\"hi\"\"\nbye\"\r\n\r\nlast,\n").unwrap();
+        assert_eq!(
+            records,
+            vec![
+                vec!["a", "b"],
+                vec!["x, y", "say \"hi\"\nbye"],
+                vec!["last", ""],
+            ]
+        );
+        assert!(parse_records("\"open").is_err());
+    }
+
+    #[test]
+    fn headers_match_field_names_without_mapping() {
+        let csv = "Title,Column,Tags,Assignees,Due,Notes\n\
+                   Write docs,In Review,docs; #writing,@ada,2026-06-01,ignored\n\
+                   ,Todo,,,,\n";
+        let plan = parse_csv(csv, None).unwrap();
+
+        assert_eq!(plan.tasks.len(), 1);
+        let task = &plan.tasks[0];
+        assert_eq!(task.external_id, "csv:Write docs");
+        assert_eq!(task.column, ColumnRef::Id("in-review".into()));
+        assert_eq!(task.tags, vec!["docs", "writing"]);
+        assert_eq!(task.assignees, vec!["ada"]);
+        assert_eq!(task.due.as_deref(), Some("2026-06-01"));
+        assert_eq!(task.body, "");
+    }
+
+    #[test]
+    fn mapping_renames_headers() {
+        let mapping = HashMap::from([
+            ("Summary".to_string(), "title".to_string()),
+            ("Key".to_string(), "external_id".to_string()),
+            ("Details".to_string(), "description".to_string()),
+        ]);
+        let csv = "Key,Summary,Details,Status\nPROJ-1,Fix login,Steps here,Done\n";
+        let plan = parse_csv(csv, Some(&mapping)).unwrap();
+
+        let task = &plan.tasks[0];
+        assert_eq!(task.external_id, "csv:PROJ-1");
+        assert_eq!(task.title, "Fix login");
+        assert_eq!(task.body, "Steps here");
+        assert_eq!(task.column, ColumnRef::First);
+    }
+
+    #[test]
+    fn rejects_missing_title_and_unknown_mapping_target() {
+        assert!(matches!(
+            parse_csv("Name,Status\nx,y\n", None),
+            Err(KanbanError::InvalidValue { .. })
+        ));
+        let mapping = HashMap::from([("Name".to_string(), "headline".to_string())]);
+        assert!(matches!(
+            parse_csv("Name\nx\n", Some(&mapping)),
+            Err(KanbanError::InvalidValue { .. })
+        ));
+    }
+}
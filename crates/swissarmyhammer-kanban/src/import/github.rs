@@ -0,0 +1,134 @@
+//! GitHub issues export (`gh issue list --json ...`).
+//!
+//! The input is the JSON array `gh` prints, e.g. for
+//! `gh issue list --state all --json number,title,body,state,labels,assignees,url`.
+//! Only `title` and one of `url`/`number` are required. Open issues land in
+//! the board's first column and closed ones in its terminal column; labels
+//! become tags (keeping their color) and assignees become actors.
+//!
+//! The external id is `github:<url>` when the export has URLs — unique across
+//! repositories — and `github:#<number>` otherwise.
+
+use super::plan::{ColumnRef, ImportPlan, PlannedTask};
+use crate::error::{KanbanError, Result};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct Issue {
+    number: Option<u64>,
+    title: String,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    state: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    labels: Vec<Label>,
+    #[serde(default)]
+    assignees: Vec<User>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Label {
+    name: String,
+    #[serde(default)]
+    color: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct User {
+    login: String,
+    #[serde(default)]
+    name: Option<String>,
+}
+
+/// Parse a `gh issue list --json` export into an import plan.
+pub(crate) fn parse_github(content: &str) -> Result<ImportPlan> {
+    let issues: Vec<Issue> = serde_json::from_str(content)
+        .map_err(|e| KanbanError::parse(format!("invalid GitHub issues export: {e}")))?;
+
+    let mut plan = ImportPlan::default();
+    for issue in issues {
+        let external_id = match (&issue.url, issue.number) {
+            (Some(url), _) if !url.trim().is_empty() => format!("github:{}", url.trim()),
+            (_, Some(number)) => format!("github:#{number}"),
+            _ => {
+                return Err(KanbanError::parse(format!(
+                    "GitHub issue {:?} has neither url nor number",
+                    issue.title
+                )))
+            }
+        };
+        let closed = issue
+            .state
+            .as_deref()
+            .is_some_and(|s| s.eq_ignore_ascii_case("closed"));
+
+        let tags = issue
+            .labels
+            .iter()
+            .map(|l| plan.tag(&l.name, l.color.as_deref()))
+            .filter(|t| !t.is_empty())
+            .collect();
+        let assignees = issue
+            .assignees
+            .iter()
+            .map(|u| plan.actor(&u.login, u.name.as_deref()))
+            .collect();
+
+        plan.tasks.push(PlannedTask {
+            external_id,
+            title: issue.title,
+            body: issue.body.unwrap_or_default(),
+            column: if closed {
+                ColumnRef::Terminal
+            } else {
+                ColumnRef::First
+            },
+            tags,
+            assignees,
+            due: None,
+            scheduled: None,
+        });
+    }
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_gh_issue_list_json() {
+        let json = r#"[
+          {"number": 12, "title": "Crash on start", "body": "Stack trace",
+           "state": "OPEN", "url": "https://github.com/o/r/issues/12",
+           "labels": [{"name": "bug", "color": "d73a4a"}],
+           "assignees": [{"login": "octocat", "name": "The Octocat"}]},
+          {"number": 7, "title": "Old", "state": "CLOSED", "labels": [], "assignees": []}
+        ]"#;
+        let plan = parse_github(json).unwrap();
+
+        assert_eq!(plan.tasks.len(), 2);
+        let first = &plan.tasks[0];
+        assert_eq!(first.external_id, "github:https://github.com/o/r/issues/12");
+        assert_eq!(first.column, ColumnRef::First);
+        assert_eq!(first.tags, vec!["bug"]);
+        assert_eq!(first.assignees, vec!["octocat"]);
+        assert_eq!(plan.actors[0].name, "The Octocat");
+        assert_eq!(plan.tags[0].color.as_deref(), Some("d73a4a"));
+
+        assert_eq!(plan.tasks[1].external_id, "github:#7");
+        assert_eq!(plan.tasks[1].column, ColumnRef::Terminal);
+        assert!(plan.columns.is_empty());
+    }
+
+    #[test]
+    fn rejects_non_array_input() {
+        assert!(matches!(
+            parse_github(r#"{"title": "x"}"#),
+            Err(KanbanError::Parse { .. })
+        ));
+    }
+}
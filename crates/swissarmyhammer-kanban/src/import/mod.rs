@@ -0,0 +1,21 @@
+//! Board import from other tools' exports.
+//!
+//! Supported formats:
+//!
+//! - `github` — the JSON array printed by `gh issue list --json ...`
+//! - `trello` — a Trello board's JSON export
+//! - `csv` — any CSV with a header row, mapped onto task fields
+//!
+//! Each format is parsed into a format-neutral [`plan::ImportPlan`] which
+//! [`apply::apply_plan`] then reconciles with the board. Imported tasks carry
+//! the source's key in the `external_id` field, which makes re-importing
+//! idempotent.
+
+mod apply;
+mod board;
+mod csv;
+mod github;
+mod plan;
+mod trello;
+
+pub use board::ImportBoard;
@@ -0,0 +1,141 @@
+//! Format-neutral import plan.
+//!
+//! Each source parser turns its export into an [`ImportPlan`]; the plan is
+//! then applied to (or, for a dry run, compared against) the board by
+//! [`super::apply::apply_plan`]. Keeping the parsers free of board I/O means
+//! they can be tested on fixture strings alone.
+
+use crate::tag_parser::normalize_slug;
+
+/// Where an imported task lands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ColumnRef {
+    /// A column by id, created from the plan's columns if it does not exist.
+    Id(String),
+    /// The board's first column (lowest order).
+    First,
+    /// The board's terminal column (highest order).
+    Terminal,
+}
+
+/// A column to create if the board does not already have it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PlannedColumn {
+    pub id: String,
+    pub name: String,
+}
+
+/// An actor to create if the board does not already have it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PlannedActor {
+    pub id: String,
+    pub name: String,
+}
+
+/// A tag to create if the board does not already have it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PlannedTag {
+    pub name: String,
+    /// 6-char hex without `#`; `None` picks the auto color.
+    pub color: Option<String>,
+}
+
+/// A task to create, or to refresh when its external id is already on the board.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PlannedTask {
+    pub external_id: String,
+    pub title: String,
+    pub body: String,
+    pub column: ColumnRef,
+    pub tags: Vec<String>,
+    pub assignees: Vec<String>,
+    pub due: Option<String>,
+    pub scheduled: Option<String>,
+}
+
+/// Everything one import would put on the board, in board order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ImportPlan {
+    pub columns: Vec<PlannedColumn>,
+    pub actors: Vec<PlannedActor>,
+    pub tags: Vec<PlannedTag>,
+    pub tasks: Vec<PlannedTask>,
+}
+
+impl ImportPlan {
+    /// Register a column by display name and return its id. Repeats are
+    /// ignored, so first appearance decides the column order.
+    pub fn column(&mut self, name: &str) -> String {
+        let id = slug_id(name);
+        if !self.columns.iter().any(|c| c.id == id) {
+            self.columns.push(PlannedColumn {
+                id: id.clone(),
+                name: name.trim().to_string(),
+            });
+        }
+        id
+    }
+
+    /// Register an actor and return its id.
+    pub fn actor(&mut self, handle: &str, name: Option<&str>) -> String {
+        let id = slug_id(handle);
+        if !self.actors.iter().any(|a| a.id == id) {
+            let name = name
+                .map(str::trim)
+                .filter(|n| !n.is_empty())
+                .unwrap_or(handle.trim());
+            self.actors.push(PlannedActor {
+                id: id.clone(),
+                name: name.to_string(),
+            });
+        }
+        id
+    }
+
+    /// Register a tag and return its normalized name, which is empty (and
+    /// not registered) when the name has no slug characters.
+    pub fn tag(&mut self, name: &str, color: Option<&str>) -> String {
+        let slug = normalize_slug(name.trim());
+        if !slug.is_empty() && !self.tags.iter().any(|t| t.name == slug) {
+            let color = color
+                .map(|c| c.trim().trim_start_matches('#').to_ascii_lowercase())
+                .filter(|c| c.len() == 6 && c.chars().all(|ch| ch.is_ascii_hexdigit()));
+            self.tags.push(PlannedTag {
+                name: slug.clone(),
+                color,
+            });
+        }
+        slug
+    }
+}
+
+/// Lowercase slug used for column and actor ids.
+pub(crate) fn slug_id(raw: &str) -> String {
+    normalize_slug(raw.trim()).to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registrations_dedupe_and_keep_first_order() {
+        let mut plan = ImportPlan::default();
+        assert_eq!(plan.column("In Progress"), "in-progress");
+        assert_eq!(plan.column("Backlog"), "backlog");
+        assert_eq!(plan.column("in progress"), "in-progress");
+        let ids: Vec<&str> = plan.columns.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["in-progress", "backlog"]);
+
+        assert_eq!(plan.actor("Octo_Cat", Some("  ")), "octo-cat");
+        assert_eq!(plan.actors[0].name, "Octo_Cat");
+
+        assert_eq!(
+            plan.tag("good first issue", Some("#7057FF")),
+            "good-first-issue"
+        );
+        assert_eq!(plan.tags[0].color.as_deref(), Some("7057ff"));
+        plan.tag("wontfix", Some("grey"));
+        assert_eq!(plan.tags[1].color, None);
+    }
+}
@@ -0,0 +1,281 @@
+//! Trello board export (Menu → Print, export and share → Export as JSON).
+//!
+//! Open lists become columns in `pos` order and open cards become tasks in
+//! their list, also in `pos` order. Card labels become tags — a label with no
+//! name is named after its color — and card members become actors. Checklists
+//! are appended to the task body as markdown checkboxes so no content is lost.
+//!
+//! The external id is `trello:<card id>`.
+
+use super::plan::{ColumnRef, ImportPlan, PlannedTask};
+use crate::error::{KanbanError, Result};
+use crate::task::parse_iso8601_date;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Deserialize)]
+struct Board {
+    #[serde(default)]
+    lists: Vec<List>,
+    #[serde(default)]
+    cards: Vec<Card>,
+    #[serde(default)]
+    labels: Vec<Label>,
+    #[serde(default)]
+    members: Vec<Member>,
+    #[serde(default)]
+    checklists: Vec<Checklist>,
+}
+
+#[derive(Debug, Deserialize)]
+struct List {
+    id: String,
+    name: String,
+    #[serde(default)]
+    closed: bool,
+    #[serde(default)]
+    pos: f64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Card {
+    id: String,
+    name: String,
+    #[serde(default)]
+    desc: String,
+    #[serde(default)]
+    closed: bool,
+    id_list: String,
+    #[serde(default)]
+    pos: f64,
+    #[serde(default)]
+    due: Option<String>,
+    #[serde(default)]
+    labels: Vec<Label>,
+    #[serde(default)]
+    id_labels: Vec<String>,
+    #[serde(default)]
+    id_members: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Label {
+    id: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    color: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Member {
+    id: String,
+    username: String,
+    #[serde(default)]
+    full_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Checklist {
+    id_card: String,
+    name: String,
+    #[serde(default)]
+    pos: f64,
+    #[serde(default)]
+    check_items: Vec<CheckItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckItem {
+    name: String,
+    #[serde(default)]
+    state: String,
+    #[serde(default)]
+    pos: f64,
+}
+
+/// Hex for Trello's named label colors (`_dark`/`_light` variants share the base).
+fn trello_color_hex(color: &str) -> Option<&'static str> {
+    let base = color.trim_end_matches("_dark").trim_end_matches("_light");
+    Some(match base {
+        "green" => "61bd4f",
+        "yellow" => "f2d600",
+        "orange" => "ff9f1a",
+        "red" => "eb5a46",
+        "purple" => "c377e0",
+        "blue" => "0079bf",
+        "sky" => "00c2e0",
+        "lime" => "51e898",
+        "pink" => "ff78cb",
+        "black" => "344563",
+        _ => return None,
+    })
+}
+
+/// Parse a Trello board JSON export into an import plan.
+pub(crate) fn parse_trello(content: &str) -> Result<ImportPlan> {
+    let board: Board = serde_json::from_str(content)
+        .map_err(|e| KanbanError::parse(format!("invalid Trello board export: {e}")))?;
+
+    let mut plan = ImportPlan::default();
+
+    let mut lists: Vec<&List> = board.lists.iter().filter(|l| !l.closed).collect();
+    lists.sort_by(|a, b| a.pos.total_cmp(&b.pos));
+    let mut list_columns: HashMap<&str, String> = HashMap::new();
+    for list in &lists {
+        list_columns.insert(list.id.as_str(), plan.column(&list.name));
+    }
+
+    let board_labels: HashMap<&str, &Label> =
+        board.labels.iter().map(|l| (l.id.as_str(), l)).collect();
+    let members: HashMap<&str, &Member> =
+        board.members.iter().map(|m| (m.id.as_str(), m)).collect();
+
+    let mut checklists: HashMap<&str, Vec<&Checklist>> = HashMap::new();
+    for checklist in &board.checklists {
+        checklists
+            .entry(checklist.id_card.as_str())
+            .or_default()
+            .push(checklist);
+    }
+
+    let list_order: HashMap<&str, usize> = lists
+        .iter()
+        .enumerate()
+        .map(|(i, l)| (l.id.as_str(), i))
+        .collect();
+    let mut cards: Vec<&Card> = board
+        .cards
+        .iter()
+        .filter(|c| !c.closed && list_order.contains_key(c.id_list.as_str()))
+        .collect();
+    cards.sort_by(|a, b| {
+        list_order[a.id_list.as_str()]
+            .cmp(&list_order[b.id_list.as_str()])
+            .then(a.pos.total_cmp(&b.pos))
+    });
+
+    for card in cards {
+        let labels: Vec<Label> = if card.labels.is_empty() {
+            card.id_labels
+                .iter()
+                .filter_map(|id| board_labels.get(id.as_str()).map(|l| (*l).clone()))
+                .collect()
+        } else {
+            card.labels.clone()
+        };
+        let tags = labels
+            .iter()
+            .filter_map(|label| {
+                let color = label.color.as_deref().unwrap_or_default();
+                let name = if label.name.trim().is_empty() {
+                    color
+                } else {
+                    label.name.as_str()
+                };
+                let tag = plan.tag(name, trello_color_hex(color));
+                (!tag.is_empty()).then_some(tag)
+            })
+            .collect();
+
+        let assignees = card
+            .id_members
+            .iter()
+            .filter_map(|id| members.get(id.as_str()))
+            .map(|m| plan.actor(&m.username, m.full_name.as_deref()))
+            .collect();
+
+        let mut body = card.desc.trim_end().to_string();
+        if let Some(card_checklists) = checklists.get_mut(card.id.as_str()) {
+            card_checklists.sort_by(|a, b| a.pos.total_cmp(&b.pos));
+            for checklist in card_checklists.iter() {
+                if !body.is_empty() {
+                    body.push_str("\n\n");
+                }
+                body.push_str(&format!("### {}\n", checklist.name.trim()));
+                let mut items: Vec<&CheckItem> = checklist.check_items.iter().collect();
+                items.sort_by(|a, b| a.pos.total_cmp(&b.pos));
+                for item in items {
+                    let mark = if item.state == "complete" { "x" } else { " " };
+                    body.push_str(&format!("\n- [{mark}] {}", item.name.trim()));
+                }
+            }
+        }
+
+        let due = match card.due.as_deref().filter(|d| !d.trim().is_empty()) {
+            Some(due) => Some(parse_iso8601_date(due, "due")?),
+            None => None,
+        };
+
+        plan.tasks.push(PlannedTask {
+            external_id: format!("trello:{}", card.id),
+            title: card.name.clone(),
+            body,
+            column: ColumnRef::Id(list_columns[card.id_list.as_str()].clone()),
+            tags,
+            assignees,
+            due,
+            scheduled: None,
+        });
+    }
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOARD: &str = r#"{
+      "name": "Roadmap",
+      "lists": [
+        {"id": "l2", "name": "Doing", "closed": false, "pos": 2048},
+        {"id": "l1", "name": "To Do", "closed": false, "pos": 1024},
+        {"id": "l3", "name": "Old", "closed": true, "pos": 4096}
+      ],
+      "labels": [{"id": "lb1", "name": "", "color": "red"}],
+      "members": [{"id": "m1", "username": "ada", "fullName": "Ada Lovelace"}],
+      "cards": [
+        {"id": "c2", "name": "Second", "desc": "", "closed": false, "idList": "l1", "pos": 200,
+         "labels": [{"id": "lb2", "name": "Design", "color": "blue_dark"}]},
+        {"id": "c1", "name": "First", "desc": "Notes", "closed": false, "idList": "l1", "pos": 100,
+         "idLabels": ["lb1"], "idMembers": ["m1"], "due": "2026-05-01T17:00:00.000Z"},
+        {"id": "c3", "name": "Active", "closed": false, "idList": "l2", "pos": 1},
+        {"id": "c4", "name": "Archived", "closed": true, "idList": "l1", "pos": 1},
+        {"id": "c5", "name": "In closed list", "closed": false, "idList": "l3", "pos": 1}
+      ],
+      "checklists": [
+        {"id": "k1", "idCard": "c1", "name": "Steps", "pos": 1,
+         "checkItems": [
+           {"name": "b", "state": "incomplete", "pos": 2},
+           {"name": "a", "state": "complete", "pos": 1}
+         ]}
+      ]
+    }"#;
+
+    #[test]
+    fn parses_lists_cards_labels_and_checklists() {
+        let plan = parse_trello(BOARD).unwrap();
+
+        let columns: Vec<&str> = plan.columns.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(columns, vec!["to-do", "doing"]);
+
+        let titles: Vec<&str> = plan.tasks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["First", "Second", "Active"]);
+
+        let first = &plan.tasks[0];
+        assert_eq!(first.external_id, "trello:c1");
+        assert_eq!(first.column, ColumnRef::Id("to-do".into()));
+        assert_eq!(first.tags, vec!["red"]);
+        assert_eq!(first.assignees, vec!["ada"]);
+        assert_eq!(first.due.as_deref(), Some("2026-05-01"));
+        assert_eq!(first.body, "Notes\n\n### Steps\n\n- [x] a\n- [ ] b");
+
+        assert_eq!(plan.tasks[1].tags, vec!["Design"]);
+        let design = plan.tags.iter().find(|t| t.name == "Design").unwrap();
+        assert_eq!(design.color.as_deref(), Some("0079bf"));
+        assert_eq!(plan.actors[0].name, "Ada Lovelace");
+    }
+}
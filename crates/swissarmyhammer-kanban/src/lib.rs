@@ -82,19 +82,29 @@ pub mod perspective;
 pub mod commands;
 
 // Command modules
+pub mod activity;
 pub mod actor;
 pub mod attachment;
 pub mod board;
+pub mod bulk;
 pub mod column;
 pub mod comment;
 pub mod entity;
+pub mod export;
 pub mod focus;
+pub mod graph;
+pub mod hooks;
+pub mod import;
+pub mod metrics;
 pub mod project;
 pub mod schema;
 pub mod scope_commands;
+pub mod swimlane;
 pub mod tag;
 pub mod task;
+pub mod template;
 pub mod virtual_tags;
+pub mod workspace;
 
 // Re-export Execute trait and types from operations crate
 pub use swissarmyhammer_operations::{async_trait, Execute, ExecutionResult, OperationProcessor};
@@ -0,0 +1,169 @@
+//! GetFlow command
+
+use super::{load_flow, window};
+use crate::context::KanbanContext;
+use crate::error::{KanbanError, Result};
+use chrono::Duration;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResult};
+
+/// Longest window, in days, a cumulative-flow request may cover.
+const MAX_DAYS: i64 = 366;
+
+/// Get cumulative-flow data: for each day in the window, how many tasks sat
+/// in each column at the end of it.
+///
+/// Stacking the counts in column order draws the usual cumulative-flow
+/// diagram, where widening bands show work piling up in a column.
+#[operation(
+    verb = "get",
+    noun = "flow",
+    description = "Get daily per-column task counts for a cumulative-flow diagram"
+)]
+#[derive(Debug, Default, Deserialize)]
+pub struct GetFlow {
+    /// First day of the window: RFC 3339 timestamp, YYYY-MM-DD, or a duration ago such as 30d (default 30 days before until)
+    pub since: Option<String>,
+    /// End of the window, in the same forms as `since` (default now)
+    pub until: Option<String>,
+    /// Filter DSL expression selecting the tasks counted (e.g. `#bug`)
+    pub filter: Option<String>,
+}
+
+impl GetFlow {
+    /// Cumulative flow for the last thirty days across every task.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start the window at this point.
+    pub fn with_since(mut self, since: impl Into<String>) -> Self {
+        self.since = Some(since.into());
+        self
+    }
+
+    /// End the window at this point.
+    pub fn with_until(mut self, until: impl Into<String>) -> Self {
+        self.until = Some(until.into());
+        self
+    }
+
+    /// Only count tasks matching this filter expression.
+    pub fn with_filter(mut self, filter: impl Into<String>) -> Self {
+        self.filter = Some(filter.into());
+        self
+    }
+}
+
+#[async_trait]
+impl Execute<KanbanContext, KanbanError> for GetFlow {
+    async fn execute(&self, ctx: &KanbanContext) -> ExecutionResult<Value, KanbanError> {
+        let result: Result<Value> = async {
+            let (since, until) = window(
+                self.since.as_deref(),
+                self.until.as_deref(),
+                Duration::days(30),
+            )?;
+            if until - since > Duration::days(MAX_DAYS) {
+                return Err(KanbanError::invalid_value(
+                    "since",
+                    format!("cumulative flow covers at most {MAX_DAYS} days"),
+                ));
+            }
+            let ectx = ctx.entity_context().await?;
+            let (columns, flow) = load_flow(&ectx, self.filter.as_deref()).await?;
+
+            let days: Vec<Value> = flow
+                .cumulative_flow(since, until)
+                .into_iter()
+                .map(|(date, counts)| {
+                    let counts: Map<String, Value> = flow
+                        .columns
+                        .iter()
+                        .zip(counts)
+                        .map(|(column, n)| (column.clone(), json!(n)))
+                        .collect();
+                    json!({ "date": date.to_string(), "counts": counts })
+                })
+                .collect();
+
+            Ok(json!({
+                "since": since.to_rfc3339(),
+                "until": until.to_rfc3339(),
+                "columns": columns
+                    .iter()
+                    .map(|c| json!({
+                        "id": c.id.as_str(),
+                        "name": c.get_str("name").unwrap_or(c.id.as_str()),
+                    }))
+                    .collect::<Vec<_>>(),
+                "days": days,
+            }))
+        }
+        .await;
+
+        match result {
+            Ok(value) => ExecutionResult::Success { value },
+            Err(error) => ExecutionResult::Failed { error },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::InitBoard;
+    use crate::task::{AddTask, MoveTask};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_flow_counts_tasks_per_column_per_day() {
+        let temp = TempDir::new().unwrap();
+        let ctx = KanbanContext::new(temp.path().join(".kanban"));
+        InitBoard::new("Test")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        for title in ["One", "Two"] {
+            AddTask::new(title)
+                .execute(&ctx)
+                .await
+                .into_result()
+                .unwrap();
+        }
+        let added = AddTask::new("Three")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        MoveTask::to_column(added["id"].as_str().unwrap(), "done")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        let result = GetFlow::new()
+            .with_since("7d")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(result["columns"][0]["name"], "To Do");
+        let days = result["days"].as_array().unwrap();
+        assert!(days.len() >= 7, "{result}");
+        let today = &days.last().unwrap()["counts"];
+        assert_eq!(today["todo"], 2);
+        assert_eq!(today["doing"], 0);
+        assert_eq!(today["done"], 1);
+        assert_eq!(days[0]["counts"]["todo"], 0, "nothing existed a week ago");
+
+        let too_long = GetFlow::new()
+            .with_since("2020-01-01")
+            .execute(&ctx)
+            .await
+            .into_result();
+        assert!(matches!(too_long, Err(KanbanError::InvalidValue { .. })));
+    }
+}
@@ -0,0 +1,435 @@
+//! Task timelines rebuilt from changelogs, and the flow measures over them.
+//!
+//! Everything here is pure: callers read the changelogs and hand over the
+//! board's column order, so the same arithmetic backs the `get metrics` /
+//! `get flow` operations and the board's aggregate computed fields.
+
+use crate::defaults::{changelog_timestamp, extract_position_column};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Where one task has been: when it was created, and the column it entered
+/// at creation and at every move since.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TaskTimeline {
+    pub created: DateTime<Utc>,
+    /// `(when, column)` for each column change, oldest first. Consecutive
+    /// entries never name the same column.
+    pub moves: Vec<(DateTime<Utc>, String)>,
+}
+
+impl TaskTimeline {
+    /// Rebuild a timeline from a task's changelog in its JSON form (the
+    /// `_changelog` pseudo-field). `None` when the changelog records no
+    /// column at all.
+    pub fn from_changelog(changelog: &[Value]) -> Option<Self> {
+        let timestamp = |entry: &Value| {
+            changelog_timestamp(entry)
+                .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+                .map(|ts| ts.with_timezone(&Utc))
+        };
+        let created = changelog.iter().find_map(timestamp)?;
+        let mut moves: Vec<(DateTime<Utc>, String)> = Vec::new();
+        for entry in changelog {
+            let (Some(at), Some(column)) = (timestamp(entry), extract_position_column(entry))
+            else {
+                continue;
+            };
+            if moves.last().map(|(_, c)| c.as_str()) != Some(column.as_str()) {
+                moves.push((at, column));
+            }
+        }
+        if moves.is_empty() {
+            return None;
+        }
+        Some(Self { created, moves })
+    }
+
+    /// The column the task was in just before `at`; `None` before it existed.
+    pub fn column_at(&self, at: DateTime<Utc>) -> Option<&str> {
+        self.moves
+            .iter()
+            .take_while(|(when, _)| *when < at)
+            .last()
+            .map(|(_, column)| column.as_str())
+    }
+
+    /// When work began: the first move out of the first column, matching the
+    /// task's `started` field.
+    pub fn started(&self, first_column: &str) -> Option<DateTime<Utc>> {
+        self.moves
+            .iter()
+            .find(|(_, column)| column != first_column)
+            .map(|(when, _)| *when)
+    }
+
+    /// When the task reached the terminal column, if it is still there,
+    /// matching the task's `completed` field.
+    pub fn completed(&self, terminal_column: &str) -> Option<DateTime<Utc>> {
+        self.moves
+            .last()
+            .filter(|(_, column)| column == terminal_column)
+            .map(|(when, _)| *when)
+    }
+
+    /// Every finished stay as `(column, entered, left)`.
+    fn stays(&self) -> impl Iterator<Item = (&str, DateTime<Utc>, DateTime<Utc>)> {
+        self.moves
+            .windows(2)
+            .map(|pair| (pair[0].1.as_str(), pair[0].0, pair[1].0))
+    }
+}
+
+/// A summary of durations, in hours rounded to one decimal place.
+///
+/// Percentiles use the nearest-rank method, so each is a duration that was
+/// actually observed. Everything but `count` is `None` with no samples.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub(crate) struct Distribution {
+    pub count: usize,
+    pub mean: Option<f64>,
+    pub p50: Option<f64>,
+    pub p85: Option<f64>,
+    pub p95: Option<f64>,
+}
+
+impl Distribution {
+    /// Summarise a set of durations.
+    pub fn of(durations: impl IntoIterator<Item = Duration>) -> Self {
+        let mut hours: Vec<f64> = durations
+            .into_iter()
+            .map(|d| d.num_seconds() as f64 / 3600.0)
+            .collect();
+        if hours.is_empty() {
+            return Self::default();
+        }
+        hours.sort_by(f64::total_cmp);
+        let rank = |p: f64| {
+            let idx = ((p / 100.0) * hours.len() as f64).ceil() as usize;
+            round_hours(hours[idx.clamp(1, hours.len()) - 1])
+        };
+        Self {
+            count: hours.len(),
+            mean: Some(round_hours(hours.iter().sum::<f64>() / hours.len() as f64)),
+            p50: Some(rank(50.0)),
+            p85: Some(rank(85.0)),
+            p95: Some(rank(95.0)),
+        }
+    }
+}
+
+fn round_hours(hours: f64) -> f64 {
+    (hours * 10.0).round() / 10.0
+}
+
+/// The board's columns in order and the timeline of every task measured.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Flow {
+    /// Column ids, first to terminal.
+    pub columns: Vec<String>,
+    pub timelines: Vec<TaskTimeline>,
+}
+
+impl Flow {
+    /// Rebuild a flow from aggregate query results: column field maps and
+    /// task field maps carrying their `_changelog`.
+    pub fn from_field_maps(
+        columns: &[HashMap<String, Value>],
+        tasks: &[HashMap<String, Value>],
+    ) -> Self {
+        let mut columns: Vec<(u64, &str)> = columns
+            .iter()
+            .filter_map(|c| {
+                let order = c.get("order").and_then(|v| v.as_u64()).unwrap_or(0);
+                Some((order, c.get("id")?.as_str()?))
+            })
+            .collect();
+        columns.sort();
+        let timelines = tasks
+            .iter()
+            .filter_map(|t| TaskTimeline::from_changelog(t.get("_changelog")?.as_array()?))
+            .collect();
+        Self {
+            columns: columns.into_iter().map(|(_, id)| id.to_string()).collect(),
+            timelines,
+        }
+    }
+
+    fn first_column(&self) -> &str {
+        self.columns.first().map(String::as_str).unwrap_or("todo")
+    }
+
+    fn terminal_column(&self) -> &str {
+        self.columns.last().map(String::as_str).unwrap_or("done")
+    }
+
+    /// Each task completed in `[since, until)`, with when it was completed.
+    fn completions(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> impl Iterator<Item = (&TaskTimeline, DateTime<Utc>)> {
+        let terminal = self.terminal_column();
+        self.timelines.iter().filter_map(move |t| {
+            t.completed(terminal)
+                .filter(|done| *done >= since && *done < until)
+                .map(|done| (t, done))
+        })
+    }
+
+    /// How many tasks were completed in `[since, until)`.
+    pub fn throughput(&self, since: DateTime<Utc>, until: DateTime<Utc>) -> usize {
+        self.completions(since, until).count()
+    }
+
+    /// Start of work to completion, for tasks completed in `[since, until)`.
+    pub fn cycle_time(&self, since: DateTime<Utc>, until: DateTime<Utc>) -> Distribution {
+        let first = self.first_column();
+        Distribution::of(
+            self.completions(since, until)
+                .filter_map(|(t, done)| t.started(first).map(|start| done - start)),
+        )
+    }
+
+    /// Creation to completion, for tasks completed in `[since, until)`.
+    pub fn lead_time(&self, since: DateTime<Utc>, until: DateTime<Utc>) -> Distribution {
+        Distribution::of(
+            self.completions(since, until)
+                .map(|(t, done)| done - t.created),
+        )
+    }
+
+    /// Time spent in each column, in board order, over the stays that ended
+    /// in `[since, until)`.
+    pub fn dwell(&self, since: DateTime<Utc>, until: DateTime<Utc>) -> Vec<(&str, Distribution)> {
+        self.columns
+            .iter()
+            .map(|column| {
+                let stays = self.timelines.iter().flat_map(|t| t.stays()).filter_map(
+                    |(c, entered, left)| {
+                        (c == column && left >= since && left < until).then_some(left - entered)
+                    },
+                );
+                (column.as_str(), Distribution::of(stays))
+            })
+            .collect()
+    }
+
+    /// Tasks completed in each ISO week (Monday start) touching
+    /// `[since, until)`, as `(monday, count)` with empty weeks included.
+    pub fn weekly_throughput(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Vec<(NaiveDate, usize)> {
+        let monday =
+            |date: NaiveDate| date - Duration::days(date.weekday().num_days_from_monday() as i64);
+        let first = monday(since.date_naive());
+        let last = monday((until - Duration::seconds(1)).date_naive());
+        let mut weeks: Vec<(NaiveDate, usize)> = Vec::new();
+        let mut week = first;
+        while week <= last {
+            weeks.push((week, 0));
+            week += Duration::weeks(1);
+        }
+        for (_, done) in self.completions(since, until) {
+            let week = monday(done.date_naive());
+            if let Some(slot) = weeks.iter_mut().find(|(w, _)| *w == week) {
+                slot.1 += 1;
+            }
+        }
+        weeks
+    }
+
+    /// Cumulative-flow data: for each day in `[since, until)`, how many
+    /// tasks sat in each column (in board order) at the end of that day, or
+    /// at `until` for the last one.
+    pub fn cumulative_flow(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Vec<(NaiveDate, Vec<usize>)> {
+        let mut days = Vec::new();
+        let mut day = since.date_naive();
+        let last = (until - Duration::seconds(1)).date_naive();
+        while day <= last {
+            let end_of_day = (day + Duration::days(1))
+                .and_hms_opt(0, 0, 0)
+                .map(|t| t.and_utc())
+                .unwrap_or(until)
+                .min(until);
+            let mut counts = vec![0; self.columns.len()];
+            for timeline in &self.timelines {
+                if let Some(i) = timeline
+                    .column_at(end_of_day)
+                    .and_then(|c| self.columns.iter().position(|col| col == c))
+                {
+                    counts[i] += 1;
+                }
+            }
+            days.push((day, counts));
+            day += Duration::days(1);
+        }
+        days
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn timeline(moves: &[(&str, &str)]) -> TaskTimeline {
+        TaskTimeline {
+            created: at(moves[0].0),
+            moves: moves
+                .iter()
+                .map(|(when, column)| (at(when), column.to_string()))
+                .collect(),
+        }
+    }
+
+    /// Three tasks over a week: two finish, one is still in progress.
+    fn flow() -> Flow {
+        Flow {
+            columns: vec!["todo".into(), "doing".into(), "done".into()],
+            timelines: vec![
+                timeline(&[
+                    ("2026-10-05T09:00:00Z", "todo"),
+                    ("2026-10-05T10:00:00Z", "doing"),
+                    ("2026-10-05T14:00:00Z", "done"),
+                ]),
+                timeline(&[
+                    ("2026-10-05T09:00:00Z", "todo"),
+                    ("2026-10-06T09:00:00Z", "doing"),
+                    ("2026-10-12T09:00:00Z", "done"),
+                ]),
+                timeline(&[
+                    ("2026-10-07T12:00:00Z", "todo"),
+                    ("2026-10-08T12:00:00Z", "doing"),
+                ]),
+            ],
+        }
+    }
+
+    #[test]
+    fn timeline_from_changelog_keeps_column_changes() {
+        let entry = |ts: &str, changes: Value| json!({ "timestamp": ts, "changes": changes });
+        let changelog = vec![
+            entry(
+                "2026-10-05T09:00:00Z",
+                json!([
+                    ["title", { "kind": "set", "value": "Write" }],
+                    ["position_column", { "kind": "set", "value": "todo" }]
+                ]),
+            ),
+            entry(
+                "2026-10-05T09:30:00Z",
+                json!([["title", { "kind": "changed", "old_value": "Write", "new_value": "Draft" }]]),
+            ),
+            entry(
+                "2026-10-05T10:00:00Z",
+                json!([["position_column", { "kind": "changed", "old_value": "todo", "new_value": "done" }]]),
+            ),
+        ];
+        let t = TaskTimeline::from_changelog(&changelog).unwrap();
+        assert_eq!(t.created, at("2026-10-05T09:00:00Z"));
+        assert_eq!(t.moves.len(), 2);
+        assert_eq!(t.started("todo"), Some(at("2026-10-05T10:00:00Z")));
+        assert_eq!(t.completed("done"), Some(at("2026-10-05T10:00:00Z")));
+        assert_eq!(t.column_at(at("2026-10-05T09:59:00Z")), Some("todo"));
+        assert_eq!(t.column_at(at("2026-10-05T08:00:00Z")), None);
+        assert!(TaskTimeline::from_changelog(&[]).is_none());
+    }
+
+    #[test]
+    fn flow_from_field_maps_orders_columns() {
+        let map =
+            |pairs: Value| -> HashMap<String, Value> { serde_json::from_value(pairs).unwrap() };
+        let columns = [
+            map(json!({ "id": "done", "order": 2 })),
+            map(json!({ "id": "todo", "order": 0 })),
+            map(json!({ "id": "doing", "order": 1 })),
+        ];
+        let tasks = [
+            map(json!({
+                "id": "a",
+                "_changelog": [{
+                    "timestamp": "2026-10-05T09:00:00Z",
+                    "changes": [["position_column", { "kind": "set", "value": "todo" }]]
+                }]
+            })),
+            map(json!({ "id": "no-history" })),
+        ];
+        let flow = Flow::from_field_maps(&columns, &tasks);
+        assert_eq!(flow.columns, vec!["todo", "doing", "done"]);
+        assert_eq!(flow.timelines.len(), 1, "tasks without history are skipped");
+    }
+
+    #[test]
+    fn distribution_uses_nearest_rank() {
+        let d = Distribution::of((1..=20).map(Duration::hours));
+        assert_eq!(d.count, 20);
+        assert_eq!(d.mean, Some(10.5));
+        assert_eq!(d.p50, Some(10.0));
+        assert_eq!(d.p85, Some(17.0));
+        assert_eq!(d.p95, Some(19.0));
+        assert_eq!(
+            Distribution::of(std::iter::empty()),
+            Distribution::default()
+        );
+    }
+
+    #[test]
+    fn cycle_lead_and_dwell_times() {
+        let flow = flow();
+        let (since, until) = (at("2026-10-01T00:00:00Z"), at("2026-10-15T00:00:00Z"));
+
+        let cycle = flow.cycle_time(since, until);
+        assert_eq!(cycle.count, 2);
+        assert_eq!(cycle.p50, Some(4.0));
+        assert_eq!(cycle.p95, Some(144.0));
+        let lead = flow.lead_time(since, until);
+        assert_eq!(lead.p50, Some(5.0));
+        assert_eq!(lead.p95, Some(168.0));
+
+        let dwell = flow.dwell(since, until);
+        assert_eq!(dwell[0].0, "todo");
+        assert_eq!(dwell[0].1.count, 3);
+        assert_eq!(dwell[1].1.count, 2, "c is still in doing");
+        assert_eq!(dwell[2].1.count, 0);
+
+        // Only a's completion falls in the first week.
+        let early = flow.cycle_time(since, at("2026-10-10T00:00:00Z"));
+        assert_eq!(early.count, 1);
+    }
+
+    #[test]
+    fn weekly_throughput_and_cumulative_flow() {
+        let flow = flow();
+        let weeks = flow.weekly_throughput(at("2026-10-05T00:00:00Z"), at("2026-10-19T00:00:00Z"));
+        assert_eq!(
+            weeks,
+            vec![
+                (NaiveDate::from_ymd_opt(2026, 10, 5).unwrap(), 1),
+                (NaiveDate::from_ymd_opt(2026, 10, 12).unwrap(), 1),
+            ]
+        );
+
+        assert_eq!(
+            flow.throughput(at("2026-10-05T00:00:00Z"), at("2026-10-19T00:00:00Z")),
+            2
+        );
+
+        let days = flow.cumulative_flow(at("2026-10-05T00:00:00Z"), at("2026-10-08T00:00:00Z"));
+        let counts: Vec<Vec<usize>> = days.into_iter().map(|(_, c)| c).collect();
+        // Oct 5: a done, b todo. Oct 6: b doing. Oct 7: c arrives in todo.
+        assert_eq!(counts, vec![vec![1, 0, 1], vec![0, 1, 1], vec![1, 1, 1]]);
+    }
+}
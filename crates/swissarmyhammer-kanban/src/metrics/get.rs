@@ -0,0 +1,199 @@
+//! GetMetrics command
+
+use super::{load_flow, window};
+use crate::context::KanbanContext;
+use crate::error::{KanbanError, Result};
+use chrono::Duration;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResult};
+
+/// Report how work has flowed across the board over a window of time.
+///
+/// Cycle time runs from a task leaving the first column to reaching the
+/// last, lead time from its creation; both cover tasks completed inside the
+/// window. Dwell time is measured per column over the stays that ended in
+/// the window. Durations are in hours, summarised as mean and 50th, 85th
+/// and 95th percentiles.
+#[operation(
+    verb = "get",
+    noun = "metrics",
+    description = "Get cycle time, lead time, per-column dwell time and weekly throughput"
+)]
+#[derive(Debug, Default, Deserialize)]
+pub struct GetMetrics {
+    /// Start of the window: RFC 3339 timestamp, YYYY-MM-DD, or a duration ago such as 4w (default 12 weeks before until)
+    pub since: Option<String>,
+    /// End of the window, in the same forms as `since` (default now)
+    pub until: Option<String>,
+    /// Filter DSL expression selecting the tasks measured (e.g. `#bug`)
+    pub filter: Option<String>,
+}
+
+impl GetMetrics {
+    /// Metrics for the last twelve weeks across every task.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start the window at this point.
+    pub fn with_since(mut self, since: impl Into<String>) -> Self {
+        self.since = Some(since.into());
+        self
+    }
+
+    /// End the window at this point.
+    pub fn with_until(mut self, until: impl Into<String>) -> Self {
+        self.until = Some(until.into());
+        self
+    }
+
+    /// Only measure tasks matching this filter expression.
+    pub fn with_filter(mut self, filter: impl Into<String>) -> Self {
+        self.filter = Some(filter.into());
+        self
+    }
+}
+
+#[async_trait]
+impl Execute<KanbanContext, KanbanError> for GetMetrics {
+    async fn execute(&self, ctx: &KanbanContext) -> ExecutionResult<Value, KanbanError> {
+        let result: Result<Value> = async {
+            let (since, until) = window(
+                self.since.as_deref(),
+                self.until.as_deref(),
+                Duration::weeks(12),
+            )?;
+            let ectx = ctx.entity_context().await?;
+            let (columns, flow) = load_flow(&ectx, self.filter.as_deref()).await?;
+
+            let dwell: Vec<Value> = flow
+                .dwell(since, until)
+                .into_iter()
+                .zip(&columns)
+                .map(|((id, stats), column)| -> Result<Value> {
+                    let mut entry = serde_json::to_value(stats)?;
+                    entry["column"] = json!(id);
+                    entry["name"] = json!(column.get_str("name").unwrap_or(id));
+                    Ok(entry)
+                })
+                .collect::<Result<_>>()?;
+
+            let weeks = flow.weekly_throughput(since, until);
+            let total: usize = weeks.iter().map(|(_, n)| n).sum();
+            let per_week = if weeks.is_empty() {
+                0.0
+            } else {
+                (total as f64 / weeks.len() as f64 * 10.0).round() / 10.0
+            };
+
+            Ok(json!({
+                "since": since.to_rfc3339(),
+                "until": until.to_rfc3339(),
+                "unit": "hours",
+                "tasks": flow.timelines.len(),
+                "cycle_time": flow.cycle_time(since, until),
+                "lead_time": flow.lead_time(since, until),
+                "dwell": dwell,
+                "throughput": {
+                    "total": total,
+                    "per_week": per_week,
+                    "weeks": weeks
+                        .iter()
+                        .map(|(monday, completed)| json!({
+                            "week": monday.format("%G-W%V").to_string(),
+                            "start": monday.to_string(),
+                            "completed": completed,
+                        }))
+                        .collect::<Vec<_>>(),
+                },
+            }))
+        }
+        .await;
+
+        match result {
+            Ok(value) => ExecutionResult::Success { value },
+            Err(error) => ExecutionResult::Failed { error },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::InitBoard;
+    use crate::task::{AddTask, MoveTask};
+    use tempfile::TempDir;
+
+    async fn setup() -> (TempDir, KanbanContext) {
+        let temp = TempDir::new().unwrap();
+        let kanban_dir = temp.path().join(".kanban");
+        let ctx = KanbanContext::new(kanban_dir);
+
+        InitBoard::new("Test")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        (temp, ctx)
+    }
+
+    async fn add(ctx: &KanbanContext, title: &str) -> String {
+        let added = AddTask::new(title)
+            .execute(ctx)
+            .await
+            .into_result()
+            .unwrap();
+        added["id"].as_str().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_metrics_count_completed_work() {
+        let (_temp, ctx) = setup().await;
+        let shipped = add(&ctx, "Shipped").await;
+        let started = add(&ctx, "Started").await;
+        add(&ctx, "Waiting").await;
+        for (id, column) in [(&shipped, "doing"), (&shipped, "done"), (&started, "doing")] {
+            MoveTask::to_column(id.as_str(), column)
+                .execute(&ctx)
+                .await
+                .into_result()
+                .unwrap();
+        }
+
+        let result = GetMetrics::new().execute(&ctx).await.into_result().unwrap();
+        assert_eq!(result["unit"], "hours");
+        assert_eq!(result["tasks"], 3, "{result}");
+        assert_eq!(result["cycle_time"]["count"], 1);
+        assert_eq!(result["lead_time"]["count"], 1);
+        assert_eq!(result["throughput"]["total"], 1);
+        let dwell = result["dwell"].as_array().unwrap();
+        assert_eq!(dwell.len(), 4);
+        assert_eq!(dwell[0]["column"], "todo");
+        assert_eq!(dwell[0]["name"], "To Do");
+        assert_eq!(dwell[0]["count"], 2, "shipped and started left todo");
+        assert_eq!(dwell[1]["count"], 1, "only shipped left doing");
+
+        let none = GetMetrics::new()
+            .with_filter("#nothing-tagged-this")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(none["tasks"], 0);
+        assert!(none["cycle_time"]["p50"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_metrics_reject_an_empty_window() {
+        let (_temp, ctx) = setup().await;
+        let result = GetMetrics::new()
+            .with_since("2026-03-01")
+            .with_until("2026-02-01")
+            .execute(&ctx)
+            .await
+            .into_result();
+        assert!(matches!(result, Err(KanbanError::InvalidValue { .. })));
+    }
+}
@@ -0,0 +1,83 @@
+//! Flow metrics
+//!
+//! Every move between columns is recorded in the task's changelog, so how
+//! work has flowed across the board can be rebuilt after the fact: how long
+//! tasks sit in each column, how long they take from start (cycle time) or
+//! creation (lead time) to done, how many finish each week, and how many
+//! were in each column on each day (cumulative flow). Archived tasks are
+//! counted, so tidying the board does not rewrite its history.
+
+mod cumulative;
+mod flow;
+mod get;
+
+pub use cumulative::GetFlow;
+pub(crate) use flow::{Flow, TaskTimeline};
+pub use get::GetMetrics;
+
+use crate::activity::parse_time_bound;
+use crate::error::{KanbanError, Result};
+use crate::task::{parse_filter_expr, retain_matching_tasks};
+use chrono::{DateTime, Duration, Utc};
+use swissarmyhammer_entity::{Entity, EntityContext};
+
+/// Resolve a `since` / `until` pair into a window, ending now and reaching
+/// back `default_span` when either bound is left out.
+pub(crate) fn window(
+    since: Option<&str>,
+    until: Option<&str>,
+    default_span: Duration,
+) -> Result<(DateTime<Utc>, DateTime<Utc>)> {
+    let bound = |value: Option<&str>, field| {
+        value
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| parse_time_bound(s, field))
+            .transpose()
+    };
+    let until = bound(until, "until")?.unwrap_or_else(Utc::now);
+    let since = bound(since, "since")?.unwrap_or(until - default_span);
+    if since >= until {
+        return Err(KanbanError::invalid_value(
+            "since",
+            "the window must start before it ends",
+        ));
+    }
+    Ok((since, until))
+}
+
+/// Load the board's columns in order and rebuild the timeline of every task,
+/// live or archived, that matches `filter`.
+pub(crate) async fn load_flow(
+    ectx: &EntityContext,
+    filter: Option<&str>,
+) -> Result<(Vec<Entity>, Flow)> {
+    let mut columns = ectx.list("column").await?;
+    columns.sort_by_key(|c| c.get("order").and_then(|v| v.as_u64()).unwrap_or(0));
+    let mut tasks = ectx.list("task").await?;
+    tasks.extend(ectx.list_archived("task").await?);
+
+    if let Some(expr) = parse_filter_expr(filter)? {
+        retain_matching_tasks(ectx, &mut tasks, &columns, &expr).await?;
+    }
+
+    let mut timelines = Vec::with_capacity(tasks.len());
+    for task in &tasks {
+        let changelog = ectx
+            .read_changelog_with_trash_fallback("task", task.id.as_str())
+            .await?;
+        let changelog = changelog
+            .iter()
+            .map(serde_json::to_value)
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        if let Some(timeline) = TaskTimeline::from_changelog(&changelog) {
+            timelines.push(timeline);
+        }
+    }
+
+    let flow = Flow {
+        columns: columns.iter().map(|c| c.id.as_str().to_string()).collect(),
+        timelines,
+    };
+    Ok((columns, flow))
+}
@@ -8,9 +8,10 @@ use swissarmyhammer_operations::{Execute, OperationProcessor};
 
 /// Kanban-specific operation processor
 ///
-/// Handles execution and actor attribution tracing for all kanban operations.
+/// Handles execution and actor attribution for all kanban operations.
 /// Per-entity changelogs are handled by `EntityContext` / `StoreHandle`;
-/// this processor does not write a global activity log.
+/// this processor does not write a global activity log, but it attributes
+/// the changelog entries an operation writes to the processor's actor.
 pub struct KanbanOperationProcessor {
     /// Optional actor performing operations (for tracing attribution)
     pub actor: Option<String>,
@@ -65,7 +66,16 @@ impl OperationProcessor<KanbanContext, KanbanError> for KanbanOperationProcessor
         );
 
         // Execute the operation and lift its result into the domain Result.
-        operation.execute(ctx).await.into_result()
+        // With an actor, every changelog entry the operation writes is
+        // attributed to it, which is what the activity feed filters on.
+        match &self.actor {
+            Some(actor) => {
+                swissarmyhammer_store::with_changelog_actor(actor.clone(), operation.execute(ctx))
+                    .await
+                    .into_result()
+            }
+            None => operation.execute(ctx).await.into_result(),
+        }
     }
 }
 
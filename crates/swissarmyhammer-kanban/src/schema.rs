@@ -9,22 +9,33 @@ use swissarmyhammer_operations::{
     generate_mcp_schema_full, generate_mcp_schema_wire, Operation, SchemaConfig,
 };
 
+use crate::activity::ListActivity;
 use crate::actor::{AddActor, DeleteActor, GetActor, ListActors, UpdateActor};
 use crate::attachment::{
-    AddAttachment, DeleteAttachment, GetAttachment, ListAttachments, UpdateAttachment,
+    AddAttachment, DeleteAttachment, GcAttachments, GetAttachment, ListAttachments,
+    UpdateAttachment,
 };
-use crate::board::{GetBoard, InitBoard, UpdateBoard};
+use crate::board::{CompactBoard, GetBoard, InitBoard, UpdateBoard};
+use crate::bulk::{ArchiveTasks, AssignTasks, MoveTasks, TagTasks, UpdateTasks};
 use crate::column::{AddColumn, DeleteColumn, GetColumn, ListColumns, UpdateColumn};
 use crate::comment::{AddComment, DeleteComment, GetComment, ListComments, UpdateComment};
+use crate::export::ExportBoard;
+use crate::graph::GetGraph;
+use crate::import::ImportBoard;
+use crate::metrics::{GetFlow, GetMetrics};
 use crate::perspective::{
     AddPerspective, DeletePerspective, GetPerspective, ListPerspectives, UpdatePerspective,
 };
 use crate::project::{AddProject, DeleteProject, GetProject, ListProjects, UpdateProject};
+use crate::swimlane::ListSwimlanes;
 use crate::tag::{AddTag, DeleteTag, GetTag, ListTags, UpdateTag};
 use crate::task::{
     AddTask, ArchiveTask, AssignTask, CompleteTask, DeleteTask, GetTask, ListArchived, ListTasks,
-    MoveTask, NextTask, SearchTasks, TagTask, UnarchiveTask, UnassignTask, UntagTask, UpdateTask,
+    MoveTask, NextTask, PromoteTask, SearchTasks, TagTask, UnarchiveTask, UnassignTask, UntagTask,
+    UpdateTask,
 };
+use crate::template::ListTemplates;
+use crate::workspace::GetWorkspace;
 
 /// All kanban operations — the canonical list used for schema generation and CLI.
 static KANBAN_OPERATIONS: LazyLock<Vec<&'static dyn Operation>> = LazyLock::new(|| {
@@ -33,6 +44,9 @@ static KANBAN_OPERATIONS: LazyLock<Vec<&'static dyn Operation>> = LazyLock::new(
         Box::leak(Box::new(InitBoard::new(""))) as &dyn Operation,
         Box::leak(Box::new(GetBoard::default())) as &dyn Operation,
         Box::leak(Box::new(UpdateBoard::new())) as &dyn Operation,
+        Box::leak(Box::new(ImportBoard::default())) as &dyn Operation,
+        Box::leak(Box::new(ExportBoard::new(""))) as &dyn Operation,
+        Box::leak(Box::new(CompactBoard::new())) as &dyn Operation,
         // Column
         Box::leak(Box::new(AddColumn::new("", ""))) as &dyn Operation,
         Box::leak(Box::new(GetColumn::new(""))) as &dyn Operation,
@@ -52,6 +66,7 @@ static KANBAN_OPERATIONS: LazyLock<Vec<&'static dyn Operation>> = LazyLock::new(
         Box::leak(Box::new(DeleteTask::new(""))) as &dyn Operation,
         Box::leak(Box::new(MoveTask::to_column("", ""))) as &dyn Operation,
         Box::leak(Box::new(CompleteTask::new(""))) as &dyn Operation,
+        Box::leak(Box::new(PromoteTask::new("", 0))) as &dyn Operation,
         Box::leak(Box::new(AssignTask::new("", ""))) as &dyn Operation,
         Box::leak(Box::new(UnassignTask::new("", ""))) as &dyn Operation,
         Box::leak(Box::new(NextTask::new())) as &dyn Operation,
@@ -80,6 +95,7 @@ static KANBAN_OPERATIONS: LazyLock<Vec<&'static dyn Operation>> = LazyLock::new(
         Box::leak(Box::new(UpdateAttachment::new("", ""))) as &dyn Operation,
         Box::leak(Box::new(DeleteAttachment::new("", ""))) as &dyn Operation,
         Box::leak(Box::new(ListAttachments::new(""))) as &dyn Operation,
+        Box::leak(Box::new(GcAttachments::new())) as &dyn Operation,
         // Project
         Box::leak(Box::new(AddProject::new("", ""))) as &dyn Operation,
         Box::leak(Box::new(GetProject::new(""))) as &dyn Operation,
@@ -92,6 +108,25 @@ static KANBAN_OPERATIONS: LazyLock<Vec<&'static dyn Operation>> = LazyLock::new(
         Box::leak(Box::new(UpdatePerspective::new(""))) as &dyn Operation,
         Box::leak(Box::new(DeletePerspective::new(""))) as &dyn Operation,
         Box::leak(Box::new(ListPerspectives::new())) as &dyn Operation,
+        // Swimlanes
+        Box::leak(Box::new(ListSwimlanes::new())) as &dyn Operation,
+        // Activity
+        Box::leak(Box::new(ListActivity::new())) as &dyn Operation,
+        // Dependency graph
+        Box::leak(Box::new(GetGraph::new())) as &dyn Operation,
+        // Flow metrics
+        Box::leak(Box::new(GetMetrics::new())) as &dyn Operation,
+        Box::leak(Box::new(GetFlow::new())) as &dyn Operation,
+        // Task templates
+        Box::leak(Box::new(ListTemplates::new())) as &dyn Operation,
+        // Workspace
+        Box::leak(Box::new(GetWorkspace::new())) as &dyn Operation,
+        // Bulk task changes
+        Box::leak(Box::new(MoveTasks::default())) as &dyn Operation,
+        Box::leak(Box::new(TagTasks::default())) as &dyn Operation,
+        Box::leak(Box::new(AssignTasks::default())) as &dyn Operation,
+        Box::leak(Box::new(ArchiveTasks::default())) as &dyn Operation,
+        Box::leak(Box::new(UpdateTasks::default())) as &dyn Operation,
     ]
 });
 
@@ -0,0 +1,453 @@
+//! Lane computation and lane moves for swimlanes.
+
+use crate::error::{KanbanError, Result};
+use crate::tag_parser;
+use crate::types::Ordinal;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use swissarmyhammer_entity::{Entity, EntityContext};
+use swissarmyhammer_fields::{FieldType, SelectOption};
+
+/// Lane value for tasks with no value in the group field.
+pub const UNGROUPED_LANE: &str = "";
+
+/// Display label for the ungrouped lane. Matches the board UI's bucket label.
+const UNGROUPED_LABEL: &str = "(ungrouped)";
+
+/// Derive name of the computed field that reads `#tag` markers from the body.
+const BODY_TAGS_DERIVE: &str = "parse-body-tags";
+
+/// How a group field stores its lane values on a task.
+#[derive(Debug, Clone)]
+enum LaneSource {
+    /// `select` / `multi-select` — values are option values.
+    Select {
+        options: Vec<SelectOption>,
+        multiple: bool,
+    },
+    /// `reference` — values are ids of another entity type.
+    Reference { entity: String, multiple: bool },
+    /// The computed `tags` field — values are `#tag` markers in the body.
+    BodyTags,
+}
+
+/// A task field resolved for use as a swimlane key.
+///
+/// Carries everything needed to bucket, label and order lanes without further
+/// I/O: select options come from the field definition, and reference labels
+/// are loaded once from the referenced entity type.
+#[derive(Debug, Clone)]
+pub struct LaneField {
+    name: String,
+    source: LaneSource,
+    /// Reference id → (order, label), loaded from the referenced entities.
+    references: HashMap<String, (u64, String)>,
+}
+
+impl LaneField {
+    /// Resolve `name` against the board's task fields.
+    ///
+    /// Accepts `select`, `multi-select` and `reference` fields, plus the
+    /// body-derived `tags` field. Anything else — or a field explicitly marked
+    /// `groupable: false` — is an invalid `group_by`.
+    pub async fn load(ectx: &EntityContext, name: &str) -> Result<Self> {
+        let fields = ectx.fields();
+        let is_task_field = fields
+            .fields_for_entity("task")
+            .iter()
+            .any(|f| f.name.as_str() == name);
+        let def = fields
+            .get_field_by_name(name)
+            .filter(|_| is_task_field)
+            .ok_or_else(|| {
+                KanbanError::invalid_value("group_by", format!("'{name}' is not a task field"))
+            })?;
+        if def.groupable == Some(false) {
+            return Err(KanbanError::invalid_value(
+                "group_by",
+                format!("field '{name}' is not groupable"),
+            ));
+        }
+
+        let source = match &def.type_ {
+            FieldType::Select { options } => LaneSource::Select {
+                options: options.clone(),
+                multiple: false,
+            },
+            FieldType::MultiSelect { options } => LaneSource::Select {
+                options: options.clone(),
+                multiple: true,
+            },
+            FieldType::Reference { entity, multiple } => LaneSource::Reference {
+                entity: entity.to_string(),
+                multiple: *multiple,
+            },
+            FieldType::Computed { derive, .. } if derive == BODY_TAGS_DERIVE => {
+                LaneSource::BodyTags
+            }
+            _ => {
+                return Err(KanbanError::invalid_value(
+                    "group_by",
+                    format!("field '{name}' cannot be used for swimlanes"),
+                ))
+            }
+        };
+
+        let mut references = HashMap::new();
+        if let LaneSource::Reference { entity, .. } = &source {
+            let label_field = fields
+                .get_entity(entity)
+                .and_then(|e| {
+                    e.mention_display_field
+                        .as_ref()
+                        .or(e.search_display_field.as_ref())
+                })
+                .map(|f| f.to_string())
+                .unwrap_or_else(|| "name".to_string());
+            for target in ectx.list(entity).await? {
+                let label = target
+                    .get_str(&label_field)
+                    .filter(|l| !l.is_empty())
+                    .unwrap_or(target.id.as_str())
+                    .to_string();
+                let order = target
+                    .get("order")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(u64::MAX);
+                references.insert(target.id.to_string(), (order, label));
+            }
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            source,
+            references,
+        })
+    }
+
+    /// The field name lanes are keyed on.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Whether a task can sit in several lanes at once.
+    pub fn is_multiple(&self) -> bool {
+        match &self.source {
+            LaneSource::Select { multiple, .. } | LaneSource::Reference { multiple, .. } => {
+                *multiple
+            }
+            LaneSource::BodyTags => true,
+        }
+    }
+
+    /// The lane values of a task; `[UNGROUPED_LANE]` when it has none.
+    pub fn values(&self, task: &Entity) -> Vec<String> {
+        let values: Vec<String> = match task.get(&self.name) {
+            Some(Value::Array(items)) => items
+                .iter()
+                .filter_map(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect(),
+            Some(Value::String(s)) if !s.is_empty() => vec![s.clone()],
+            _ => Vec::new(),
+        };
+        if values.is_empty() {
+            vec![UNGROUPED_LANE.to_string()]
+        } else {
+            values
+        }
+    }
+
+    /// Sort key and label for a lane value.
+    ///
+    /// Select lanes follow option order, reference lanes the referenced
+    /// entity's `order` (if it has one) and then its label; values the field
+    /// does not know about sort after the known ones by label.
+    fn rank(&self, value: &str) -> (u64, String) {
+        match &self.source {
+            LaneSource::Select { options, .. } => options
+                .iter()
+                .enumerate()
+                .find(|(_, o)| o.value == value)
+                .map(|(idx, o)| {
+                    let order = ((o.order.max(0) as u64) << 32) | idx as u64;
+                    (order, o.label.clone().unwrap_or_else(|| o.value.clone()))
+                })
+                .unwrap_or_else(|| (u64::MAX, value.to_string())),
+            LaneSource::Reference { .. } => self
+                .references
+                .get(value)
+                .cloned()
+                .unwrap_or_else(|| (u64::MAX, value.to_string())),
+            LaneSource::BodyTags => (u64::MAX, value.to_string()),
+        }
+    }
+}
+
+/// Task count for one column within a lane.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LaneColumn {
+    /// Column ID.
+    pub id: String,
+    /// Number of the lane's tasks in this column.
+    pub task_count: usize,
+}
+
+/// One horizontal lane of the board.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Swimlane {
+    /// Raw field value; [`UNGROUPED_LANE`] for tasks without one.
+    pub value: String,
+    /// Human-readable lane header.
+    pub label: String,
+    /// Number of tasks in the lane.
+    pub task_count: usize,
+    /// Per-column counts, in board column order (zero counts included).
+    pub columns: Vec<LaneColumn>,
+    /// Task IDs in the lane, ordered by column and then by ordinal.
+    pub tasks: Vec<String>,
+}
+
+/// Bucket `tasks` into swimlanes keyed on `field`.
+///
+/// `columns` must be sorted in board order. A task with several values in a
+/// multi-value field appears in every matching lane, and tasks with no value
+/// land in the ungrouped lane, which always sorts last — the same bucketing the
+/// board UI applies.
+pub fn compute_swimlanes(field: &LaneField, tasks: &[Entity], columns: &[Entity]) -> Vec<Swimlane> {
+    let column_index: HashMap<&str, usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(idx, c)| (c.id.as_str(), idx))
+        .collect();
+
+    let mut ordered: Vec<&Entity> = tasks.iter().collect();
+    ordered.sort_by(|a, b| {
+        let col = |t: &Entity| {
+            t.get_str("position_column")
+                .and_then(|c| column_index.get(c).copied())
+                .unwrap_or(usize::MAX)
+        };
+        let ord = |t: &Entity| {
+            Ordinal::from_string(
+                t.get_str("position_ordinal")
+                    .unwrap_or(Ordinal::DEFAULT_STR),
+            )
+        };
+        col(a).cmp(&col(b)).then_with(|| ord(a).cmp(&ord(b)))
+    });
+
+    let mut buckets: HashMap<String, Vec<&Entity>> = HashMap::new();
+    for task in ordered {
+        for value in field.values(task) {
+            buckets.entry(value).or_default().push(task);
+        }
+    }
+
+    let mut lanes: Vec<(u64, Swimlane)> = buckets
+        .into_iter()
+        .map(|(value, members)| {
+            let (order, label) = if value == UNGROUPED_LANE {
+                (u64::MAX, UNGROUPED_LABEL.to_string())
+            } else {
+                field.rank(&value)
+            };
+            let columns = columns
+                .iter()
+                .map(|c| LaneColumn {
+                    id: c.id.to_string(),
+                    task_count: members
+                        .iter()
+                        .filter(|t| t.get_str("position_column") == Some(c.id.as_str()))
+                        .count(),
+                })
+                .collect();
+            let lane = Swimlane {
+                task_count: members.len(),
+                tasks: members.iter().map(|t| t.id.to_string()).collect(),
+                value,
+                label,
+                columns,
+            };
+            (order, lane)
+        })
+        .collect();
+
+    lanes.sort_by(|(oa, a), (ob, b)| {
+        (a.value == UNGROUPED_LANE)
+            .cmp(&(b.value == UNGROUPED_LANE))
+            .then_with(|| oa.cmp(ob))
+            .then_with(|| a.label.to_lowercase().cmp(&b.label.to_lowercase()))
+            .then_with(|| a.value.cmp(&b.value))
+    });
+    lanes.into_iter().map(|(_, lane)| lane).collect()
+}
+
+/// Move `task` from lane `from` into lane `to` by rewriting its group field.
+///
+/// This is the lane counterpart of setting `position_column`: single-value
+/// fields are overwritten (or cleared for the ungrouped lane), multi-value
+/// fields swap `from` for `to` and keep their other values, and body tags are
+/// edited as `#tag` markers in the body. `to` is validated — a select value
+/// must be one of the options and a reference must name an existing entity.
+/// The caller writes the entity.
+pub(crate) async fn apply_lane_move(
+    ectx: &EntityContext,
+    task: &mut Entity,
+    field: &LaneField,
+    from: Option<&str>,
+    to: &str,
+) -> Result<()> {
+    let to = to.trim();
+    let from = from
+        .map(str::trim)
+        .filter(|f| *f != UNGROUPED_LANE && *f != to);
+
+    match &field.source {
+        LaneSource::Select { options, .. } => {
+            if to != UNGROUPED_LANE && !options.iter().any(|o| o.value == to) {
+                return Err(KanbanError::invalid_value(
+                    field.name.as_str(),
+                    format!("'{to}' is not an option"),
+                ));
+            }
+        }
+        LaneSource::Reference { entity, .. } => {
+            if to != UNGROUPED_LANE {
+                ectx.read(entity, to)
+                    .await
+                    .map_err(KanbanError::from_entity_error)?;
+            }
+        }
+        LaneSource::BodyTags => {
+            let mut body = task.get_str("body").unwrap_or_default().to_string();
+            if let Some(from) = from {
+                body = tag_parser::remove_tag(&body, from);
+            }
+            let slug = tag_parser::normalize_slug(to);
+            if !slug.is_empty() {
+                body = tag_parser::append_tag(&body, &slug);
+            }
+            task.set("body", json!(body));
+            return Ok(());
+        }
+    }
+
+    if !field.is_multiple() {
+        if to == UNGROUPED_LANE {
+            task.remove(&field.name);
+        } else {
+            task.set(&field.name, json!(to));
+        }
+        return Ok(());
+    }
+
+    let mut values = task.get_string_list(&field.name);
+    if let Some(from) = from {
+        values.retain(|v| v != from);
+    }
+    if to != UNGROUPED_LANE && !values.iter().any(|v| v == to) {
+        values.push(to.to_string());
+    }
+    if values.is_empty() {
+        task.remove(&field.name);
+    } else {
+        task.set(&field.name, json!(values));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn select_field(multiple: bool) -> LaneField {
+        let option = |value: &str, order: i32| SelectOption {
+            value: value.into(),
+            label: Some(value.to_uppercase()),
+            color: None,
+            icon: None,
+            order,
+        };
+        LaneField {
+            name: "priority".into(),
+            source: LaneSource::Select {
+                options: vec![option("low", 2), option("high", 0), option("mid", 1)],
+                multiple,
+            },
+            references: HashMap::new(),
+        }
+    }
+
+    fn column(id: &str) -> Entity {
+        Entity::new("column", id)
+    }
+
+    fn task(id: &str, column: &str, ordinal: &str, priority: Value) -> Entity {
+        let mut t = Entity::new("task", id);
+        t.set("position_column", json!(column));
+        t.set("position_ordinal", json!(ordinal));
+        if !priority.is_null() {
+            t.set("priority", priority);
+        }
+        t
+    }
+
+    #[test]
+    fn lanes_follow_option_order_with_ungrouped_last() {
+        let field = select_field(false);
+        let tasks = [
+            task("a", "todo", "a0", json!("low")),
+            task("b", "todo", "a1", Value::Null),
+            task("c", "doing", "a0", json!("high")),
+            task("d", "todo", "a2", json!("high")),
+        ];
+        let columns = [column("todo"), column("doing")];
+        let lanes = compute_swimlanes(&field, &tasks, &columns);
+
+        let values: Vec<&str> = lanes.iter().map(|l| l.value.as_str()).collect();
+        assert_eq!(values, vec!["high", "low", ""]);
+        assert_eq!(lanes[0].label, "HIGH");
+        assert_eq!(lanes[2].label, UNGROUPED_LABEL);
+
+        // Tasks are ordered by column, then ordinal.
+        assert_eq!(lanes[0].tasks, vec!["d", "c"]);
+        assert_eq!(lanes[0].task_count, 2);
+        assert_eq!(
+            lanes[0].columns,
+            vec![
+                LaneColumn {
+                    id: "todo".into(),
+                    task_count: 1
+                },
+                LaneColumn {
+                    id: "doing".into(),
+                    task_count: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn multi_value_tasks_appear_in_every_lane() {
+        let field = select_field(true);
+        let tasks = [task("a", "todo", "a0", json!(["low", "high"]))];
+        let lanes = compute_swimlanes(&field, &tasks, &[column("todo")]);
+        assert_eq!(lanes.len(), 2);
+        assert!(lanes.iter().all(|l| l.tasks == vec!["a"]));
+    }
+
+    #[test]
+    fn unknown_values_sort_after_known_options() {
+        let field = select_field(false);
+        let tasks = [
+            task("a", "todo", "a0", json!("zzz")),
+            task("b", "todo", "a1", json!("low")),
+        ];
+        let lanes = compute_swimlanes(&field, &tasks, &[column("todo")]);
+        let values: Vec<&str> = lanes.iter().map(|l| l.value.as_str()).collect();
+        assert_eq!(values, vec!["low", "zzz"]);
+    }
+}
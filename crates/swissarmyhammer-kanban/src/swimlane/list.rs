@@ -0,0 +1,255 @@
+//! ListSwimlanes command
+
+use crate::context::KanbanContext;
+use crate::error::KanbanError;
+use crate::swimlane::{compute_swimlanes, LaneField};
+use crate::task::parse_filter_expr;
+use crate::task_helpers::{
+    enrich_all_task_entities_with_wip_limits, EntitySlugRegistry, TaskFilterAdapter,
+};
+use crate::virtual_tags::{default_virtual_tag_registry, ColumnWipLimits};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResult};
+
+/// Group the board's tasks into horizontal swimlanes.
+///
+/// Lanes are keyed on a task field — `assignees`, `project`, `tags`, or any
+/// select or reference field. `group_by` names the field directly; otherwise
+/// the group key of `perspective_id` is used, so the engine and the board UI
+/// agree on the lanes for a perspective. The perspective's filter applies
+/// too, AND-ed with any explicit `filter`.
+#[operation(
+    verb = "list",
+    noun = "swimlanes",
+    description = "Group the board's tasks into swimlanes by a field"
+)]
+#[derive(Debug, Default, Deserialize)]
+pub struct ListSwimlanes {
+    /// Field to group by (e.g. "project", "assignees", "tags"). Defaults to the perspective's group.
+    pub group_by: Option<String>,
+    /// Perspective (ID or name) whose group and filter to use
+    pub perspective_id: Option<String>,
+    /// Filter DSL expression (e.g. `#bug && @alice`)
+    pub filter: Option<String>,
+}
+
+impl ListSwimlanes {
+    /// Create a new ListSwimlanes command with no group key yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Group by the named task field.
+    pub fn with_group_by(mut self, group_by: impl Into<String>) -> Self {
+        self.group_by = Some(group_by.into());
+        self
+    }
+
+    /// Take the group key and filter from a perspective.
+    pub fn with_perspective(mut self, perspective_id: impl Into<String>) -> Self {
+        self.perspective_id = Some(perspective_id.into());
+        self
+    }
+
+    /// Set a filter DSL expression.
+    pub fn with_filter(mut self, filter: impl Into<String>) -> Self {
+        self.filter = Some(filter.into());
+        self
+    }
+}
+
+#[async_trait]
+impl Execute<KanbanContext, KanbanError> for ListSwimlanes {
+    async fn execute(&self, ctx: &KanbanContext) -> ExecutionResult<Value, KanbanError> {
+        match async {
+            let (perspective_group, perspective_filter) = match &self.perspective_id {
+                Some(id) => {
+                    let pctx = ctx.perspective_context().await?;
+                    let pctx = pctx.read().await;
+                    let p = pctx
+                        .get_by_id(id)
+                        .or_else(|| pctx.get_by_name(id))
+                        .ok_or_else(|| KanbanError::not_found("perspective", id.as_str()))?;
+                    (p.group.clone(), p.filter.clone())
+                }
+                None => (None, None),
+            };
+
+            let group_by = self
+                .group_by
+                .clone()
+                .or(perspective_group)
+                .filter(|g| !g.trim().is_empty())
+                .ok_or_else(|| KanbanError::missing_field("group_by"))?;
+
+            let filter = match (perspective_filter, self.filter.as_deref()) {
+                (Some(p), Some(f)) if !p.trim().is_empty() => Some(format!("({p}) && ({f})")),
+                (Some(p), None) => Some(p),
+                (_, f) => f.map(str::to_string),
+            };
+            let expr = parse_filter_expr(filter.as_deref())?;
+
+            let ectx = ctx.entity_context().await?;
+            let field = LaneField::load(&ectx, &group_by).await?;
+
+            let mut all_columns = ectx.list("column").await?;
+            all_columns
+                .sort_by_key(|c| c.get("order").and_then(|v| v.as_u64()).unwrap_or(0) as usize);
+            let mut all_tasks = ectx.list("task").await?;
+
+            let terminal_column = all_columns.last().map(|c| c.id.as_str()).unwrap_or("done");
+            let wip_limits = ColumnWipLimits::from_columns(&all_columns);
+            let registry = default_virtual_tag_registry();
+            enrich_all_task_entities_with_wip_limits(
+                &mut all_tasks,
+                terminal_column,
+                &wip_limits,
+                registry,
+            );
+
+            if let Some(expr) = expr {
+                let all_projects = ectx.list("project").await?;
+                let all_actors = ectx.list("actor").await?;
+                let slug_registry =
+                    EntitySlugRegistry::build(&all_projects, &all_actors, &all_tasks);
+                all_tasks
+                    .retain(|t| expr.matches(&TaskFilterAdapter::with_registry(t, &slug_registry)));
+            }
+
+            let lanes = compute_swimlanes(&field, &all_tasks, &all_columns);
+            Ok(json!({
+                "group_by": field.name(),
+                "multiple": field.is_multiple(),
+                "count": lanes.len(),
+                "lanes": lanes,
+            }))
+        }
+        .await
+        {
+            Ok(value) => ExecutionResult::Success { value },
+            Err(error) => ExecutionResult::Failed { error },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::InitBoard;
+    use crate::project::AddProject;
+    use crate::task::{AddTask, MoveTask};
+    use tempfile::TempDir;
+
+    async fn setup() -> (TempDir, KanbanContext) {
+        let temp = TempDir::new().unwrap();
+        let kanban_dir = temp.path().join(".kanban");
+        let ctx = KanbanContext::new(kanban_dir);
+
+        InitBoard::new("Test")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        (temp, ctx)
+    }
+
+    async fn add_task(ctx: &KanbanContext, cmd: AddTask) -> String {
+        cmd.execute(ctx).await.into_result().unwrap()["id"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_list_swimlanes_by_project() {
+        let (_temp, ctx) = setup().await;
+        for (id, name) in [("api", "API"), ("web", "Web")] {
+            AddProject::new(id, name)
+                .execute(&ctx)
+                .await
+                .into_result()
+                .unwrap();
+        }
+
+        let a = add_task(&ctx, AddTask::new("A").with_project("web")).await;
+        let b = add_task(&ctx, AddTask::new("B").with_project("api")).await;
+        let c = add_task(&ctx, AddTask::new("C")).await;
+        MoveTask::to_column(b.as_str(), "doing")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        let result = ListSwimlanes::new()
+            .with_group_by("project")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        assert_eq!(result["group_by"], "project");
+        assert_eq!(result["multiple"], false);
+        let lanes = result["lanes"].as_array().unwrap();
+        let values: Vec<&str> = lanes.iter().map(|l| l["value"].as_str().unwrap()).collect();
+        assert_eq!(values, vec!["api", "web", ""]);
+        assert_eq!(lanes[0]["label"], "API");
+        assert_eq!(lanes[0]["tasks"], json!([b]));
+        assert_eq!(lanes[1]["tasks"], json!([a]));
+        assert_eq!(lanes[2]["tasks"], json!([c]));
+        assert_eq!(lanes[2]["label"], "(ungrouped)");
+
+        let doing = lanes[0]["columns"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|c| c["id"] == "doing")
+            .unwrap();
+        assert_eq!(doing["task_count"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_list_swimlanes_uses_perspective_group() {
+        let (_temp, ctx) = setup().await;
+        add_task(&ctx, AddTask::new("Tagged").with_description("#bug")).await;
+        add_task(&ctx, AddTask::new("Plain")).await;
+
+        crate::perspective::AddPerspective::new("Lanes", "board")
+            .with_group("tags")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        let result = ListSwimlanes::new()
+            .with_perspective("Lanes")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(result["group_by"], "tags");
+        assert_eq!(result["multiple"], true);
+        let values: Vec<&str> = result["lanes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|l| l["value"].as_str().unwrap())
+            .collect();
+        assert_eq!(values, vec!["bug", ""]);
+    }
+
+    #[tokio::test]
+    async fn test_list_swimlanes_rejects_ungroupable_field() {
+        let (_temp, ctx) = setup().await;
+        let result = ListSwimlanes::new()
+            .with_group_by("title")
+            .execute(&ctx)
+            .await
+            .into_result();
+        assert!(matches!(result, Err(KanbanError::InvalidValue { .. })));
+
+        let result = ListSwimlanes::new().execute(&ctx).await.into_result();
+        assert!(matches!(result, Err(KanbanError::MissingField { .. })));
+    }
+}
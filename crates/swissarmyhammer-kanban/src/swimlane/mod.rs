@@ -0,0 +1,14 @@
+//! Swimlane commands
+//!
+//! Swimlanes split the board horizontally by a task field — assignee,
+//! project, tag, or any select/reference field — the way columns split it
+//! vertically by `position_column`. [`ListSwimlanes`] computes the lanes with
+//! per-lane counts and ordering; `MoveTask` moves a task across lanes by
+//! rewriting the group field (see [`crate::task::MoveTask::with_lane`]).
+
+mod lanes;
+mod list;
+
+pub(crate) use lanes::apply_lane_move;
+pub use lanes::{compute_swimlanes, LaneColumn, LaneField, Swimlane, UNGROUPED_LANE};
+pub use list::ListSwimlanes;
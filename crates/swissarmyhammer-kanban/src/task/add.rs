@@ -1,15 +1,18 @@
 //! AddTask command
 
+use crate::column::enforce_wip_limit;
 use crate::context::KanbanContext;
 use crate::entity::position;
 use crate::error::{KanbanError, Result};
+use crate::task::hierarchy::{check_cycles, check_parent};
+use crate::task::recurrence::normalize_recurrence;
 use crate::task::shared::{auto_create_body_tags, parse_iso8601_date};
 use crate::task::tags::{apply_tag_refs, TagApply};
 use crate::task_helpers::{slim_task_json, task_entity_to_json};
 use crate::types::{mint_unique_short_id, ActorId, TaskId};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use swissarmyhammer_entity::Entity;
 use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResult};
 
@@ -18,6 +21,11 @@ use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResul
 /// Tags come from two equivalent places: `#tag` patterns anywhere in the
 /// description, and the explicit `tags` list. Both end up as `#tag` markers in
 /// the stored body, so the two can never disagree.
+///
+/// With `template` set the task is pre-filled from a task template (see
+/// [`crate::template`]): its title pattern, tags, project, assignees and
+/// rendered checklist body, plus a stub subtask per template subtask that the
+/// new task depends on.
 #[operation(
     verb = "add",
     noun = "task",
@@ -25,7 +33,7 @@ use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResul
 )]
 #[derive(Debug, Deserialize, Serialize)]
 pub struct AddTask {
-    /// The task title (required)
+    /// The task title (required); a template's patterns refer to it as `{{ title }}`
     pub title: String,
     /// Detailed task description (may contain #tag patterns)
     pub description: Option<String>,
@@ -52,6 +60,9 @@ pub struct AddTask {
     /// not directly settable.
     #[serde(default)]
     pub depends_on: Vec<TaskId>,
+    /// Parent task, making this task one of its subtasks. The parent stays
+    /// blocked until all of its subtasks are done.
+    pub parent: Option<TaskId>,
     /// Tags to apply to the new task, appended as `#tag` markers to the body.
     ///
     /// Each entry is a forgiving tag reference: a tag name (created on demand),
@@ -73,6 +84,20 @@ pub struct AddTask {
     /// Optional user-set date stored alongside the task. Empty string is
     /// rejected — use `None` (omit the field) to leave it unset at creation.
     pub scheduled: Option<String>,
+    /// Repeat rule, e.g. "FREQ=WEEKLY;BYDAY=MO" or "FREQ=MONTHLY;BYMONTHDAY=1".
+    ///
+    /// Completing the task creates its next instance with the dates moved
+    /// forward. See [`crate::task::RecurrenceRule`] for the supported subset.
+    pub recurrence: Option<String>,
+    /// Create the task even if its column is at its WIP limit
+    #[serde(default)]
+    pub override_wip_limit: bool,
+    /// Name of a task template to pre-fill the task from (see `list templates`)
+    pub template: Option<String>,
+    /// With `template`: map of template parameter to value, e.g. {"component": "auth"}
+    pub params: Option<HashMap<String, String>>,
+    /// With `template`: create the template's subtasks, which the task then depends on (default true)
+    pub subtasks: Option<bool>,
 }
 
 impl AddTask {
@@ -85,10 +110,16 @@ impl AddTask {
             ordinal: None,
             assignees: Vec::new(),
             depends_on: Vec::new(),
+            parent: None,
             tags: Vec::new(),
             project: None,
             due: None,
             scheduled: None,
+            recurrence: None,
+            override_wip_limit: false,
+            template: None,
+            params: None,
+            subtasks: None,
         }
     }
 
@@ -141,6 +172,42 @@ impl AddTask {
         self
     }
 
+    /// Set the repeat rule.
+    pub fn with_recurrence(mut self, recurrence: impl Into<String>) -> Self {
+        self.recurrence = Some(recurrence.into());
+        self
+    }
+
+    /// Make the task a subtask of `parent`.
+    pub fn with_parent(mut self, parent: impl Into<TaskId>) -> Self {
+        self.parent = Some(parent.into());
+        self
+    }
+
+    /// Allow creating the task even when its column is at its WIP limit.
+    pub fn with_override_wip_limit(mut self) -> Self {
+        self.override_wip_limit = true;
+        self
+    }
+
+    /// Pre-fill the task from the named task template.
+    pub fn with_template(mut self, template: impl Into<String>) -> Self {
+        self.template = Some(template.into());
+        self
+    }
+
+    /// Set the template parameters.
+    pub fn with_params(mut self, params: HashMap<String, String>) -> Self {
+        self.params = Some(params);
+        self
+    }
+
+    /// Choose whether the template's subtasks are created.
+    pub fn with_subtasks(mut self, subtasks: bool) -> Self {
+        self.subtasks = Some(subtasks);
+        self
+    }
+
     /// Build the task entity from this command's fields.
     ///
     /// Resolves position (column + ordinal) via [`position::resolve_column`]
@@ -150,7 +217,10 @@ impl AddTask {
     ///
     /// The task's ULID is minted so its canonical short id is unique across
     /// every existing task on the board (see [`build_entity_with_mint`]).
-    async fn build_entity(&self, ectx: &swissarmyhammer_entity::EntityContext) -> Result<Entity> {
+    pub(crate) async fn build_entity(
+        &self,
+        ectx: &swissarmyhammer_entity::EntityContext,
+    ) -> Result<Entity> {
         self.build_entity_with_mint(ectx, || TaskId::new().0).await
     }
 
@@ -174,9 +244,11 @@ impl AddTask {
         let ordinal =
             position::resolve_ordinal(ectx, "task", &column, self.ordinal.as_deref()).await?;
 
-        let existing_short_ids: HashSet<String> = ectx
-            .list("task")
-            .await?
+        let existing = ectx.list("task").await?;
+        if let Some(parent) = &self.parent {
+            check_parent(&existing, None, parent.as_str())?;
+        }
+        let existing_short_ids: HashSet<String> = existing
             .iter()
             .map(|task| crate::types::short_id(task.id.as_ref()))
             .collect();
@@ -193,6 +265,14 @@ impl AddTask {
         if !self.depends_on.is_empty() {
             entity.set("depends_on", serde_json::to_value(&self.depends_on)?);
         }
+        if let Some(ref parent) = self.parent {
+            entity.set("parent", json!(parent));
+            // A new task has no dependents of its own: only its parent, which
+            // waits on it, can close a loop through its dependencies.
+            if !self.depends_on.is_empty() {
+                check_cycles(&existing, &entity)?;
+            }
+        }
         if let Some(ref project) = self.project {
             entity.set("project", json!(project));
         }
@@ -205,6 +285,9 @@ impl AddTask {
                 json!(parse_iso8601_date(scheduled, "scheduled")?),
             );
         }
+        if let Some(ref recurrence) = self.recurrence {
+            entity.set("recurrence", json!(normalize_recurrence(recurrence)?));
+        }
 
         // Explicit tags append to whatever the description already carries, so
         // `tags: [a, b]` equals one `tag task` call per entry. Resolution can
@@ -216,7 +299,7 @@ impl AddTask {
     }
 
     /// Persist the task entity and run post-write hooks (auto-tag creation).
-    async fn persist(
+    pub(crate) async fn persist(
         &self,
         ectx: &swissarmyhammer_entity::EntityContext,
         entity: &Entity,
@@ -231,8 +314,13 @@ impl AddTask {
 impl Execute<KanbanContext, KanbanError> for AddTask {
     async fn execute(&self, ctx: &KanbanContext) -> ExecutionResult<Value, KanbanError> {
         let result: Result<Value> = async {
+            if let Some(template) = &self.template {
+                return crate::template::add_from_template(self, ctx, template).await;
+            }
             let ectx = ctx.entity_context().await?;
             let entity = self.build_entity(&ectx).await?;
+            let column = entity.get_str("position_column").unwrap_or_default();
+            enforce_wip_limit(&ectx, column, None, self.override_wip_limit).await?;
             self.persist(&ectx, &entity).await?;
             // Slim projection — the caller needs the identity and placement
             // it didn't have, not its own description/attachments echoed back.
@@ -689,4 +777,45 @@ mod tests {
             );
         }
     }
+
+    /// A board-local template supplies defaults the caller can extend or
+    /// override, and `with_subtasks(false)` skips its subtask stubs.
+    #[tokio::test]
+    async fn test_add_task_from_local_template() {
+        let (_temp, ctx) = setup().await;
+        let templates = ctx.root().join("templates");
+        std::fs::create_dir_all(&templates).unwrap();
+        std::fs::write(
+            templates.join("chore.md"),
+            "---\ncolumn: doing\ntags: [chore]\nsubtasks:\n  - title: Prepare {{ title }}\n---\n- [ ] {{ title }}\n",
+        )
+        .unwrap();
+
+        let result = AddTask::new("Tidy the repo")
+            .with_template("chore")
+            .with_subtasks(false)
+            .with_tags(vec!["urgent".into()])
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert!(result.get("subtasks").is_none());
+        assert_eq!(result["position"]["column"], "doing");
+
+        let ectx = ctx.entity_context().await.unwrap();
+        let stored = ectx
+            .read("task", result["id"].as_str().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(stored.get_str("title"), Some("Tidy the repo"));
+        assert!(stored
+            .get_str("body")
+            .unwrap()
+            .starts_with("- [ ] Tidy the repo"));
+        let mut tags = stored.get_string_list("tags");
+        tags.sort();
+        assert_eq!(tags, vec!["chore", "urgent"]);
+        assert!(stored.get_string_list("depends_on").is_empty());
+        assert_eq!(ectx.list("task").await.unwrap().len(), 1);
+    }
 }
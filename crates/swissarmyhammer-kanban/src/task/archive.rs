@@ -12,7 +12,7 @@
 use crate::context::KanbanContext;
 use crate::error::{KanbanError, Result};
 use crate::task::shared::parse_detail;
-use crate::task_helpers::task_entity_to_json;
+use crate::task_helpers::{child_task_ids, task_entity_to_json};
 use crate::types::TaskId;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -22,7 +22,8 @@ use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResul
 ///
 /// When a task is archived, other tasks that have it in their `depends_on`
 /// list will have it removed — the same cleanup that `DeleteTask` performs.
-/// This ensures blocked tasks become unblocked after archiving.
+/// This ensures blocked tasks become unblocked after archiving. The task's
+/// subtasks are archived with it.
 #[operation(
     verb = "archive",
     noun = "task",
@@ -53,10 +54,17 @@ impl Execute<KanbanContext, KanbanError> for ArchiveTask {
                 .await
                 .map_err(KanbanError::from_entity_error)?;
 
+            // The dependency cleanup and the archived subtree undo as one step;
+            // the recursive archives below join this group.
+            let _undo_group = match ectx.store_context() {
+                Some(sc) => Some(sc.begin_undo_group().await),
+                None => None,
+            };
+
             // Remove this task from the depends_on list of all other tasks
             // (same cleanup as DeleteTask — archive is just delete with different storage)
             let all_tasks = ectx.list("task").await?;
-            for mut t in all_tasks {
+            for t in &all_tasks {
                 if t.id == self.id.as_str() {
                     continue;
                 }
@@ -65,13 +73,18 @@ impl Execute<KanbanContext, KanbanError> for ArchiveTask {
                 if deps.contains(&self.id.to_string()) {
                     let new_deps: Vec<String> =
                         deps.into_iter().filter(|d| d != self.id.as_str()).collect();
+                    let mut t = t.clone();
                     t.set("depends_on", serde_json::to_value(&new_deps)?);
                     ectx.write(&t).await?;
                 }
             }
 
-            // Move the task to the archive directory
+            // Move the task to the archive directory, then its subtasks,
+            // each through this same command so their own subtasks follow.
             ectx.archive("task", self.id.as_str()).await?;
+            for child in child_task_ids(self.id.as_str(), &all_tasks) {
+                ArchiveTask::new(child).execute(ctx).await.into_result()?;
+            }
 
             // Standard identity envelope plus the op-specific flag.
             let mut ack = crate::task_helpers::task_mutation_ack(&entity);
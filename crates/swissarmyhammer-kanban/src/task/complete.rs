@@ -2,13 +2,19 @@
 
 use crate::context::KanbanContext;
 use crate::error::{KanbanError, Result};
+use crate::task::hierarchy::{carry_into_column, unfinished_descendants};
+use crate::task::recurrence::spawn_next_instance;
 use crate::task_helpers::task_mutation_ack;
 use crate::types::{Ordinal, TaskId};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResult};
 
-/// Mark a task as complete by moving it to the done column
+/// Mark a task as complete by moving it to the done column.
+///
+/// Completing a task with a `recurrence` rule also creates its next instance
+/// (see [`crate::task::RecurrenceRule`]); the ack then carries the new task's
+/// id as `recurred_as`. Unfinished subtasks are completed along with it.
 #[operation(
     verb = "complete",
     noun = "task",
@@ -74,10 +80,25 @@ impl Execute<KanbanContext, KanbanError> for CompleteTask {
                 }),
             );
 
+            // The next recurring instance, the completion and the subtasks it
+            // carries along undo as one step.
+            let _undo_group = match ectx.store_context() {
+                Some(sc) => Some(sc.begin_undo_group().await),
+                None => None,
+            };
+            let today = chrono::Utc::now().date_naive();
+            let next_id = spawn_next_instance(&ectx, &mut entity, today).await?;
+
             ectx.write(&entity).await?;
+            let subtasks = unfinished_descendants(&ectx, self.id.as_str()).await?;
+            carry_into_column(&ectx, &subtasks, terminal.id.as_str()).await?;
             // Thin ack — success implies the task landed in the terminal
             // column; `get task` returns the stored position when it matters.
-            Ok(task_mutation_ack(&entity))
+            let mut ack = task_mutation_ack(&entity);
+            if let Some(next_id) = next_id {
+                ack["recurred_as"] = serde_json::json!(next_id);
+            }
+            Ok(ack)
         }
         .await;
 
@@ -198,4 +219,98 @@ mod tests {
             ord1
         );
     }
+
+    async fn add_recurring(ctx: &KanbanContext, cmd: AddTask) -> String {
+        cmd.execute(ctx).await.into_result().unwrap()["id"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_complete_recurring_task_creates_next_instance() {
+        let (_temp, ctx) = setup().await;
+        let due = chrono::Utc::now().date_naive() + chrono::Duration::days(2);
+        let scheduled = due - chrono::Duration::days(1);
+        let id = add_recurring(
+            &ctx,
+            AddTask::new("Dependency audit")
+                .with_description("Run cargo audit #chore")
+                .with_due(due.format("%Y-%m-%d").to_string())
+                .with_scheduled(scheduled.format("%Y-%m-%d").to_string())
+                .with_recurrence("freq=weekly"),
+        )
+        .await;
+
+        let result = CompleteTask::new(id.as_str())
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        crate::task_helpers::assert_task_mutation_ack_with(&result, &id, &["recurred_as"]);
+        let next_id = result["recurred_as"].as_str().unwrap();
+        assert_ne!(next_id, id);
+
+        let ectx = ctx.entity_context().await.unwrap();
+        let done = ectx.read("task", &id).await.unwrap();
+        assert_eq!(done.get_str("position_column"), Some("done"));
+        assert_eq!(done.get_str("recurred_as"), Some(next_id));
+
+        let next = ectx.read("task", next_id).await.unwrap();
+        let next_due = due + chrono::Duration::weeks(1);
+        let next_scheduled = scheduled + chrono::Duration::weeks(1);
+        assert_eq!(next.get_str("title"), Some("Dependency audit"));
+        assert_eq!(next.get_str("body"), Some("Run cargo audit #chore"));
+        assert_eq!(next.get_str("position_column"), Some("todo"));
+        assert_eq!(next.get_str("recurrence"), Some("FREQ=WEEKLY"));
+        assert_eq!(next.get_str("recurred_from"), Some(id.as_str()));
+        assert_eq!(
+            next.get_str("due"),
+            Some(next_due.format("%Y-%m-%d").to_string().as_str())
+        );
+        assert_eq!(
+            next.get_str("scheduled"),
+            Some(next_scheduled.format("%Y-%m-%d").to_string().as_str())
+        );
+
+        // The link is recorded in the new task's creation changelog entry.
+        let log = ectx.read_changelog("task", next_id).await.unwrap();
+        assert!(log.iter().any(|entry| entry
+            .changes
+            .iter()
+            .any(|(field, _)| field == "recurred_from")));
+    }
+
+    #[tokio::test]
+    async fn test_completing_twice_does_not_recur_twice() {
+        let (_temp, ctx) = setup().await;
+        let id = add_recurring(
+            &ctx,
+            AddTask::new("Release checklist").with_recurrence("FREQ=MONTHLY;BYMONTHDAY=1"),
+        )
+        .await;
+
+        let first = CompleteTask::new(id.as_str())
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert!(first["recurred_as"].is_string());
+
+        let second = CompleteTask::new(id.as_str())
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        crate::task_helpers::assert_task_mutation_ack(&second, &id);
+
+        let ectx = ctx.entity_context().await.unwrap();
+        let tasks = ectx.list("task").await.unwrap();
+        assert_eq!(tasks.len(), 2);
+        let next = tasks.iter().find(|t| t.id.as_str() != id).unwrap();
+        // No dates on the original, so the successor is scheduled for the next
+        // first of the month.
+        let scheduled = next.get_str("scheduled").unwrap();
+        assert!(scheduled.ends_with("-01"), "scheduled: {scheduled}");
+    }
 }
@@ -2,12 +2,13 @@
 
 use crate::context::KanbanContext;
 use crate::error::{KanbanError, Result};
+use crate::task_helpers::task_parent;
 use crate::types::TaskId;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResult};
 
-/// Delete a task
+/// Delete a task. Its subtasks are kept as top-level tasks.
 #[operation(
     verb = "delete",
     noun = "task",
@@ -38,18 +39,27 @@ impl Execute<KanbanContext, KanbanError> for DeleteTask {
                 .await
                 .map_err(KanbanError::from_entity_error)?;
 
-            // Remove this task from the depends_on list of all other tasks
+            // Remove this task from the depends_on list of all other tasks,
+            // and detach its subtasks, which become top-level tasks
             let all_tasks = ectx.list("task").await?;
             for mut t in all_tasks {
                 if t.id == self.id.as_str() {
                     continue;
                 }
 
+                let mut changed = false;
                 let deps = t.get_string_list("depends_on");
                 if deps.contains(&self.id.to_string()) {
                     let new_deps: Vec<String> =
                         deps.into_iter().filter(|d| d != self.id.as_str()).collect();
                     t.set("depends_on", serde_json::to_value(&new_deps)?);
+                    changed = true;
+                }
+                if task_parent(&t) == Some(self.id.as_str()) {
+                    t.remove("parent");
+                    changed = true;
+                }
+                if changed {
                     ectx.write(&t).await?;
                 }
             }
@@ -1,10 +1,11 @@
 //! GetTask command
 
+use crate::activity::parse_time_bound;
 use crate::context::KanbanContext;
 use crate::error::KanbanError;
-use crate::task_helpers::{enrich_task_entity, task_entity_to_rich_json};
+use crate::task_helpers::{enrich_task_entity_with_wip_limits, task_entity_to_rich_json};
 use crate::types::TaskId;
-use crate::virtual_tags::default_virtual_tag_registry;
+use crate::virtual_tags::{default_virtual_tag_registry, ColumnWipLimits};
 use serde::Deserialize;
 use serde_json::Value;
 use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResult};
@@ -19,12 +20,24 @@ use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResul
 pub struct GetTask {
     /// The task ID to retrieve
     pub id: TaskId,
+    /// Show the task as it stood at this time: RFC 3339 timestamp, YYYY-MM-DD, or a duration ago such as 2h or 3d
+    #[serde(default)]
+    pub as_of: Option<String>,
 }
 
 impl GetTask {
     /// Create a new GetTask command
     pub fn new(id: impl Into<TaskId>) -> Self {
-        Self { id: id.into() }
+        Self {
+            id: id.into(),
+            as_of: None,
+        }
+    }
+
+    /// Rebuild the task as it stood at `as_of` from its changelog.
+    pub fn with_as_of(mut self, as_of: impl Into<String>) -> Self {
+        self.as_of = Some(as_of.into());
+        self
     }
 }
 
@@ -33,13 +46,43 @@ impl Execute<KanbanContext, KanbanError> for GetTask {
     async fn execute(&self, ctx: &KanbanContext) -> ExecutionResult<Value, KanbanError> {
         match async {
             let ectx = ctx.entity_context().await?;
-            let entity = ectx
-                .read("task", self.id.as_str())
-                .await
-                .map_err(KanbanError::from_entity_error)?;
-
-            let all_columns = ectx.list("column").await?;
-            let all_tasks = ectx.list("task").await?;
+            let as_of = self
+                .as_of
+                .as_deref()
+                .filter(|s| !s.trim().is_empty())
+                .map(|s| parse_time_bound(s, "as_of"))
+                .transpose()?;
+
+            // A past read sees the task and the board around it as they were.
+            let (entity, all_columns, all_tasks) = match as_of {
+                Some(at) => {
+                    let entity = ectx
+                        .read_as_of("task", self.id.as_str(), at)
+                        .await?
+                        .ok_or_else(|| {
+                            KanbanError::invalid_value(
+                                "as_of",
+                                format!(
+                                    "task {} was not on the board at {}",
+                                    self.id,
+                                    at.to_rfc3339()
+                                ),
+                            )
+                        })?;
+                    (
+                        entity,
+                        ectx.list_as_of("column", at).await?,
+                        ectx.list_as_of("task", at).await?,
+                    )
+                }
+                None => {
+                    let entity = ectx
+                        .read("task", self.id.as_str())
+                        .await
+                        .map_err(KanbanError::from_entity_error)?;
+                    (entity, ectx.list("column").await?, ectx.list("task").await?)
+                }
+            };
 
             let terminal_column = all_columns
                 .iter()
@@ -49,9 +92,20 @@ impl Execute<KanbanContext, KanbanError> for GetTask {
 
             let registry = default_virtual_tag_registry();
             let mut entity = entity;
-            enrich_task_entity(&mut entity, &all_tasks, terminal_column, registry);
-
-            Ok(task_entity_to_rich_json(&entity))
+            let wip_limits = ColumnWipLimits::from_columns(&all_columns);
+            enrich_task_entity_with_wip_limits(
+                &mut entity,
+                &all_tasks,
+                terminal_column,
+                &wip_limits,
+                registry,
+            );
+
+            let mut result = task_entity_to_rich_json(&entity);
+            if let Some(at) = as_of {
+                result["as_of"] = Value::String(at.to_rfc3339());
+            }
+            Ok(result)
         }
         .await
         {
@@ -303,4 +357,54 @@ mod tests {
             "completed should be null after moving out of done"
         );
     }
+
+    #[tokio::test]
+    async fn test_get_task_as_of_shows_past_title_and_column() {
+        let (_temp, ctx) = setup().await;
+        let before_add = chrono::Utc::now().to_rfc3339();
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+
+        let add = AddTask::new("Fix login")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        let id = add["id"].as_str().unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        let picked_up = chrono::Utc::now().to_rfc3339();
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+
+        crate::task::UpdateTask::new(id)
+            .with_title("Fix OAuth login")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        MoveTask::to_column(id, "done")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        let past = GetTask::new(id)
+            .with_as_of(picked_up.as_str())
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(past["title"], "Fix login");
+        assert_eq!(past["position"]["column"], "todo");
+        assert!(past["as_of"].is_string());
+
+        let now = GetTask::new(id).execute(&ctx).await.into_result().unwrap();
+        assert_eq!(now["title"], "Fix OAuth login");
+        assert!(now.get("as_of").is_none());
+
+        let missing = GetTask::new(id)
+            .with_as_of(before_add)
+            .execute(&ctx)
+            .await
+            .into_result();
+        assert!(matches!(missing, Err(KanbanError::InvalidValue { .. })));
+    }
 }
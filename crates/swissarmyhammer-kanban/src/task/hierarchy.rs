@@ -0,0 +1,245 @@
+//! Parent/child task hierarchy
+//!
+//! A task's optional `parent` reference makes it a subtask. The parent's
+//! progress rolls up its children and it stays blocked until every child is
+//! done (see [`crate::task_helpers::task_prerequisites`]). Moving, completing
+//! or archiving a parent carries its unfinished children along.
+
+use crate::error::{KanbanError, Result};
+use crate::graph::DependencyGraph;
+use crate::task_helpers::{task_parent, task_prerequisites};
+use crate::types::Ordinal;
+use serde_json::json;
+use std::collections::HashSet;
+use swissarmyhammer_entity::{Entity, EntityContext};
+
+/// Reject `parent` as the parent of `task_id` when it names no task, the task
+/// itself, or one of the task's own descendants. `task_id` is `None` for a
+/// task that is not created yet, which can have no descendants.
+pub(crate) fn check_parent(
+    all_tasks: &[Entity],
+    task_id: Option<&str>,
+    parent: &str,
+) -> Result<()> {
+    let find = |id: &str| all_tasks.iter().find(|t| t.id.as_str() == id);
+    if find(parent).is_none() {
+        return Err(KanbanError::TaskNotFound {
+            id: parent.to_string(),
+        });
+    }
+    let Some(task_id) = task_id else {
+        return Ok(());
+    };
+
+    // Walk up from the proposed parent: reaching the task means it would
+    // become its own ancestor. `seen` stops the walk on a loop already on disk.
+    let mut seen = HashSet::new();
+    let mut current = Some(parent);
+    while let Some(id) = current {
+        if id == task_id {
+            return Err(KanbanError::invalid_value(
+                "parent",
+                format!("task {parent} is {task_id} itself or one of its subtasks"),
+            ));
+        }
+        if !seen.insert(id) {
+            break;
+        }
+        current = find(id).and_then(task_parent);
+    }
+    Ok(())
+}
+
+/// Reject `task`, as a write would leave it, when it closes a loop of tasks
+/// waiting on each other. A task waits on its `depends_on` list and a parent
+/// on its subtasks, so a child that depends on its parent is a loop too.
+pub(crate) fn check_cycles(all_tasks: &[Entity], task: &Entity) -> Result<()> {
+    let mut tasks = all_tasks.to_vec();
+    match tasks.iter_mut().find(|t| t.id == task.id) {
+        Some(existing) => *existing = task.clone(),
+        None => tasks.push(task.clone()),
+    }
+    // Only the edges matter here, so no column needs to count as done.
+    let graph = DependencyGraph::build(&tasks, "").with_subtasks(&tasks);
+    let prerequisites = task_prerequisites(task, &tasks);
+    match graph.cycle_path(task.id.as_str(), &prerequisites) {
+        Some(path) => Err(KanbanError::DependencyCycle {
+            path: path.join(" -> "),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Reject a write that would give task `task_id` a dependency cycle through
+/// `depends_on` or `parent` (see [`check_cycles`]), or a `parent`
+/// [`check_parent`] refuses. Either is `None` when the write leaves that
+/// field alone; an empty `parent` clears it.
+///
+/// Every write of either field passes through here — `update task`,
+/// `update entity field` and with it `update tasks` — so no path can store a
+/// loop the others reject.
+pub(crate) async fn check_task_links(
+    ectx: &EntityContext,
+    task_id: &str,
+    depends_on: Option<&[String]>,
+    parent: Option<&str>,
+) -> Result<()> {
+    if depends_on.is_none() && parent.is_none() {
+        return Ok(());
+    }
+    let tasks = ectx.list("task").await?;
+    if let Some(parent) = parent.filter(|p| !p.is_empty()) {
+        check_parent(&tasks, Some(task_id), parent)?;
+    }
+    let Some(mut task) = tasks.iter().find(|t| t.id.as_str() == task_id).cloned() else {
+        return Ok(());
+    };
+    if let Some(deps) = depends_on {
+        task.set("depends_on", json!(deps));
+    }
+    if let Some(parent) = parent {
+        task.set("parent", json!(parent));
+    }
+    check_cycles(&tasks, &task)
+}
+
+/// The subtasks of `task_id` that are not yet in the terminal column — its
+/// unfinished children, theirs, and so on — parents before their children.
+/// These are the tasks a move or completion of the parent carries along.
+pub(crate) async fn unfinished_descendants(
+    ectx: &EntityContext,
+    task_id: &str,
+) -> Result<Vec<String>> {
+    let columns = ectx.list("column").await?;
+    let terminal = columns
+        .iter()
+        .max_by_key(|c| c.get("order").and_then(|v| v.as_u64()).unwrap_or(0))
+        .map(|c| c.id.to_string())
+        .unwrap_or_else(|| "done".to_string());
+    let mut tasks = ectx.list("task").await?;
+    tasks.retain(|t| t.get_str("position_column") != Some(terminal.as_str()));
+    tasks.sort_by(|a, b| {
+        a.get_str("position_ordinal")
+            .unwrap_or_default()
+            .cmp(b.get_str("position_ordinal").unwrap_or_default())
+    });
+
+    // Breadth-first from the task: `found[0]` is the task itself, and each
+    // entry's children are appended as it is visited.
+    let mut found = vec![task_id.to_string()];
+    let mut visited = 0;
+    while let Some(parent) = found.get(visited).cloned() {
+        for task in &tasks {
+            let id = task.id.to_string();
+            if task_parent(task) == Some(parent.as_str()) && !found.contains(&id) {
+                found.push(id);
+            }
+        }
+        visited += 1;
+    }
+    Ok(found.split_off(1))
+}
+
+/// Move `task_ids`, in order, to the end of `column`, skipping any already
+/// there. Used for the subtasks that follow their parent; the caller has
+/// already checked the column's WIP limit for the whole set.
+pub(crate) async fn carry_into_column(
+    ectx: &EntityContext,
+    task_ids: &[String],
+    column: &str,
+) -> Result<()> {
+    let tasks = ectx.list("task").await?;
+    let mut last = tasks
+        .iter()
+        .filter(|t| t.get_str("position_column") == Some(column))
+        .map(|t| {
+            Ordinal::from_string(
+                t.get_str("position_ordinal")
+                    .unwrap_or(Ordinal::DEFAULT_STR),
+            )
+        })
+        .max();
+    for id in task_ids {
+        let Some(task) = tasks.iter().find(|t| t.id.as_str() == id) else {
+            continue;
+        };
+        if task.get_str("position_column") == Some(column) {
+            continue;
+        }
+        let ordinal = match &last {
+            Some(last) => Ordinal::after(last),
+            None => Ordinal::first(),
+        };
+        let mut task = task.clone();
+        task.set("position_column", json!(column));
+        task.set("position_ordinal", json!(ordinal.as_str()));
+        ectx.write(&task).await?;
+        last = Some(ordinal);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: &str, parent: Option<&str>) -> Entity {
+        let mut entity = Entity::new("task", id);
+        if let Some(parent) = parent {
+            entity.set("parent", json!(parent));
+        }
+        entity
+    }
+
+    #[test]
+    fn test_check_parent_rejects_missing_self_and_descendants() {
+        let tasks = vec![task("a", None), task("b", Some("a")), task("c", Some("b"))];
+
+        assert!(check_parent(&tasks, Some("c"), "a").is_ok());
+        assert!(check_parent(&tasks, None, "c").is_ok());
+        assert!(matches!(
+            check_parent(&tasks, Some("a"), "ghost"),
+            Err(KanbanError::TaskNotFound { .. })
+        ));
+        assert!(matches!(
+            check_parent(&tasks, Some("a"), "a"),
+            Err(KanbanError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            check_parent(&tasks, Some("a"), "c"),
+            Err(KanbanError::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn test_check_cycles_counts_a_parent_as_waiting_on_its_children() {
+        let mut tasks = vec![
+            task("a", None),
+            task("b", Some("a")),
+            task("c", None),
+            task("d", None),
+        ];
+        tasks[3].set("depends_on", json!(["a"]));
+
+        let mut child = task("b", Some("a"));
+        child.set("depends_on", json!(["a"]));
+        match check_cycles(&tasks, &child) {
+            Err(KanbanError::DependencyCycle { path }) => assert_eq!(path, "b -> a -> b"),
+            other => panic!("expected a dependency cycle, got {other:?}"),
+        }
+
+        // A loop through another task, closed by setting the parent.
+        let mut adopted = task("c", Some("a"));
+        adopted.set("depends_on", json!(["d"]));
+        match check_cycles(&tasks, &adopted) {
+            Err(KanbanError::DependencyCycle { path }) => {
+                assert_eq!(path, "c -> d -> a -> c")
+            }
+            other => panic!("expected a dependency cycle, got {other:?}"),
+        }
+
+        let mut sibling = task("c", Some("a"));
+        sibling.set("depends_on", json!(["b"]));
+        assert!(check_cycles(&tasks, &sibling).is_ok());
+    }
+}
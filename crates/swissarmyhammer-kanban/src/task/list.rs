@@ -4,10 +4,11 @@ use crate::context::KanbanContext;
 use crate::error::KanbanError;
 use crate::task::shared::{parse_detail, parse_filter_expr};
 use crate::task_helpers::{
-    enrich_all_task_entities, task_entity_to_rich_json, EntitySlugRegistry, TaskFilterAdapter,
+    enrich_all_task_entities_with_wip_limits, task_entity_to_rich_json, EntitySlugRegistry,
+    TaskFilterAdapter,
 };
 use crate::types::ColumnId;
-use crate::virtual_tags::default_virtual_tag_registry;
+use crate::virtual_tags::{default_virtual_tag_registry, ColumnWipLimits};
 use serde::Deserialize;
 use serde_json::Value;
 use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResult};
@@ -107,7 +108,13 @@ impl Execute<KanbanContext, KanbanError> for ListTasks {
                 .unwrap_or("done");
 
             let registry = default_virtual_tag_registry();
-            enrich_all_task_entities(&mut all_tasks, terminal_column, registry);
+            let wip_limits = ColumnWipLimits::from_columns(&all_columns);
+            enrich_all_task_entities_with_wip_limits(
+                &mut all_tasks,
+                terminal_column,
+                &wip_limits,
+                registry,
+            );
 
             // Build the id-or-slug registry so `$project`, `@user`, and
             // `^task` predicates resolve display-name slugs to entity ids.
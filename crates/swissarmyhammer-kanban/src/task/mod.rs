@@ -9,10 +9,13 @@ mod cut;
 mod delete;
 pub mod embedding_cache;
 mod get;
+mod hierarchy;
 mod list;
 mod mv;
 mod next;
 mod paste;
+mod promote;
+mod recurrence;
 mod search;
 mod shared;
 mod tag;
@@ -30,11 +33,17 @@ pub use cut::CutTask;
 pub use delete::DeleteTask;
 pub use embedding_cache::{content_hash, task_embedding_text, EmbeddingCache};
 pub use get::GetTask;
+pub(crate) use hierarchy::check_task_links;
 pub use list::ListTasks;
 pub use mv::MoveTask;
 pub use next::NextTask;
 pub use paste::PasteTask;
+pub use promote::PromoteTask;
+pub use recurrence::{Frequency, RecurrenceRule};
 pub use search::SearchTasks;
+pub(crate) use shared::{
+    auto_create_body_tags, parse_filter_expr, parse_iso8601_date, retain_matching_tasks,
+};
 pub use tag::TagTask;
 pub use unassign::UnassignTask;
 pub use untag::UntagTask;
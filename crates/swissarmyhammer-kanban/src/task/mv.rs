@@ -1,7 +1,11 @@
 //! MoveTask command
 
+use crate::column::enforce_wip_limit_for_all;
 use crate::context::KanbanContext;
 use crate::error::KanbanError;
+use crate::swimlane::{apply_lane_move, LaneField};
+use crate::task::hierarchy::{carry_into_column, unfinished_descendants};
+use crate::task::shared::auto_create_body_tags;
 use crate::task_helpers::{compute_ordinal_for_neighbors, task_mutation_ack};
 use crate::types::{ColumnId, Ordinal, TaskId};
 use serde::{Deserialize, Serialize};
@@ -9,7 +13,10 @@ use serde_json::{json, Value};
 use swissarmyhammer_entity::Entity;
 use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResult};
 
-/// Move a task to a new position
+/// Move a task to a new position.
+///
+/// When the task changes column, its unfinished subtasks move with it and
+/// are appended to the target column.
 #[operation(
     verb = "move",
     noun = "task",
@@ -30,6 +37,18 @@ pub struct MoveTask {
     /// Place after this task ID (ordinal computed from neighbors)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub after_id: Option<TaskId>,
+    /// Move even if the target column is at its WIP limit
+    #[serde(default)]
+    pub override_wip_limit: bool,
+    /// Swimlane field to move along (e.g. "project", "assignees", "tags")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_by: Option<String>,
+    /// Lane the task is leaving — only matters for multi-value fields
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_lane: Option<String>,
+    /// Lane to move into; "" is the ungrouped lane and clears the value
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_lane: Option<String>,
 }
 
 impl MoveTask {
@@ -41,6 +60,10 @@ impl MoveTask {
             ordinal: None,
             before_id: None,
             after_id: None,
+            override_wip_limit: false,
+            group_by: None,
+            from_lane: None,
+            to_lane: None,
         }
     }
 
@@ -61,6 +84,29 @@ impl MoveTask {
         self.after_id = Some(id.into());
         self
     }
+
+    /// Allow the move even when the target column is at its WIP limit.
+    pub fn with_override_wip_limit(mut self) -> Self {
+        self.override_wip_limit = true;
+        self
+    }
+
+    /// Also move the task into the `to_lane` swimlane of `group_by`.
+    ///
+    /// The group field is rewritten in the same write as the position, so a
+    /// drag that crosses both a column and a lane is a single change.
+    pub fn with_lane(mut self, group_by: impl Into<String>, to_lane: impl Into<String>) -> Self {
+        self.group_by = Some(group_by.into());
+        self.to_lane = Some(to_lane.into());
+        self
+    }
+
+    /// Name the lane being left, so a multi-value field drops that value
+    /// rather than just gaining the new one.
+    pub fn with_from_lane(mut self, from_lane: impl Into<String>) -> Self {
+        self.from_lane = Some(from_lane.into());
+        self
+    }
 }
 
 /// Auto-create a column entity if it doesn't exist. Returns a title-cased name from the slug.
@@ -84,6 +130,42 @@ impl Execute<KanbanContext, KanbanError> for MoveTask {
             let ectx = ctx.entity_context().await?;
             let mut entity = ectx.read("task", self.id.as_str()).await?;
 
+            let lane_moved = match (&self.group_by, &self.to_lane) {
+                (Some(group_by), Some(to_lane)) => {
+                    let field = LaneField::load(&ectx, group_by).await?;
+                    apply_lane_move(
+                        &ectx,
+                        &mut entity,
+                        &field,
+                        self.from_lane.as_deref(),
+                        to_lane,
+                    )
+                    .await?;
+                    true
+                }
+                (Some(_), None) => return Err(KanbanError::missing_field("to_lane")),
+                (None, Some(_)) => return Err(KanbanError::missing_field("group_by")),
+                (None, None) => false,
+            };
+
+            // Unfinished subtasks follow their parent into a new column, so
+            // the WIP check covers them too.
+            let changes_column = entity.get_str("position_column") != Some(self.column.as_str());
+            let followers = if changes_column {
+                unfinished_descendants(&ectx, self.id.as_str()).await?
+            } else {
+                Vec::new()
+            };
+            let mut entering = vec![self.id.to_string()];
+            entering.extend(followers.iter().cloned());
+            enforce_wip_limit_for_all(
+                &ectx,
+                self.column.as_str(),
+                &entering,
+                self.override_wip_limit,
+            )
+            .await?;
+
             // Auto-create column if it doesn't exist
             if ectx.read("column", self.column.as_str()).await.is_err() {
                 let columns = ectx.list("column").await?;
@@ -242,7 +324,17 @@ impl Execute<KanbanContext, KanbanError> for MoveTask {
             entity.set("position_column", json!(self.column.as_str()));
             entity.set("position_ordinal", json!(ordinal.as_str()));
 
+            // The move and the subtasks it carries along undo as one step.
+            let _undo_group = match ectx.store_context() {
+                Some(sc) => Some(sc.begin_undo_group().await),
+                None => None,
+            };
             ectx.write(&entity).await?;
+            carry_into_column(&ectx, &followers, self.column.as_str()).await?;
+            if lane_moved {
+                // A move into a tag lane may name a tag that doesn't exist yet.
+                auto_create_body_tags(&ectx, &entity).await?;
+            }
             // Thin ack — success implies the move took effect; `get task`
             // returns the stored position when it matters.
             Ok(task_mutation_ack(&entity))
@@ -483,4 +575,96 @@ mod tests {
         let col_entity = ectx.read("column", "in-review").await.unwrap();
         assert_eq!(col_entity.get_str("name").unwrap(), "In Review");
     }
+
+    #[tokio::test]
+    async fn test_move_task_across_project_lanes() {
+        let (_temp, ctx) = setup().await;
+        for (id, name) in [("api", "API"), ("web", "Web")] {
+            crate::project::AddProject::new(id, name)
+                .execute(&ctx)
+                .await
+                .into_result()
+                .unwrap();
+        }
+        let result = AddTask::new("Task")
+            .with_project("api")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        let task_id = result["id"].as_str().unwrap();
+
+        MoveTask::to_column(task_id, "doing")
+            .with_lane("project", "web")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        let ectx = ctx.entity_context().await.unwrap();
+        let entity = ectx.read("task", task_id).await.unwrap();
+        assert_eq!(entity.get_str("position_column"), Some("doing"));
+        assert_eq!(entity.get_str("project"), Some("web"));
+
+        // The ungrouped lane clears the field.
+        MoveTask::to_column(task_id, "doing")
+            .with_lane("project", "")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        let entity = ectx.read("task", task_id).await.unwrap();
+        assert_eq!(entity.get_str("project"), None);
+
+        // Unknown projects are rejected rather than silently created.
+        let result = MoveTask::to_column(task_id, "doing")
+            .with_lane("project", "nope")
+            .execute(&ctx)
+            .await
+            .into_result();
+        assert!(matches!(result, Err(KanbanError::ProjectNotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_move_task_across_tag_lanes_rewrites_body() {
+        let (_temp, ctx) = setup().await;
+        let result = AddTask::new("Task")
+            .with_description("Broken #bug #ui")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        let task_id = result["id"].as_str().unwrap();
+
+        MoveTask::to_column(task_id, "todo")
+            .with_lane("tags", "feature")
+            .with_from_lane("bug")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        let ectx = ctx.entity_context().await.unwrap();
+        let entity = ectx.read("task", task_id).await.unwrap();
+        let body = entity.get_str("body").unwrap();
+        assert!(!body.contains("#bug"), "body: {body}");
+        assert!(body.contains("#ui"), "body: {body}");
+        assert!(body.contains("#feature"), "body: {body}");
+
+        let tags = ectx.list("tag").await.unwrap();
+        assert!(tags
+            .iter()
+            .any(|t| t.get_str("tag_name") == Some("feature")));
+    }
+
+    #[tokio::test]
+    async fn test_move_task_lane_requires_group_by() {
+        let (_temp, ctx) = setup().await;
+        let task_id = add_task(&ctx, "Task").await;
+
+        let mut cmd = MoveTask::to_column(task_id.as_str(), "todo");
+        cmd.to_lane = Some("web".into());
+        let result = cmd.execute(&ctx).await.into_result();
+        assert!(matches!(result, Err(KanbanError::MissingField { .. })));
+    }
 }
@@ -2,17 +2,30 @@
 
 use crate::context::KanbanContext;
 use crate::error::KanbanError;
+use crate::graph::DependencyGraph;
 use crate::task::shared::parse_filter_expr;
 use crate::task_helpers::{
-    enrich_all_task_entities, task_entity_to_rich_json, EntitySlugRegistry, TaskFilterAdapter,
+    enrich_all_task_entities_with_wip_limits, task_entity_to_rich_json, EntitySlugRegistry,
+    TaskFilterAdapter,
 };
 use crate::types::Ordinal;
-use crate::virtual_tags::default_virtual_tag_registry;
+use crate::virtual_tags::{default_virtual_tag_registry, ColumnWipLimits};
+use crate::workspace::Workspace;
 use serde::Deserialize;
 use serde_json::Value;
+use std::path::Path;
 use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResult};
 
 /// Get the next actionable task.
+///
+/// Ready tasks are taken in the dependency graph's topological order: a task
+/// that other unfinished work is waiting on comes first, the one heading the
+/// longest such chain ahead of the rest, and board position breaks ties.
+///
+/// With `workspace` set every board of the [`Workspace`] picks its next task
+/// that way and the oldest of those wins (task ids are ULIDs, so the smallest
+/// is the oldest). Its ids are qualified with the board key and it names its
+/// `board`.
 #[operation(
     verb = "next",
     noun = "task",
@@ -22,6 +35,12 @@ use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResul
 pub struct NextTask {
     /// Filter DSL expression (e.g. `#bug`).
     pub filter: Option<String>,
+    /// Pick from every board in the workspace instead of this one
+    #[serde(default)]
+    pub workspace: bool,
+    /// Directory to find the workspace's boards in, instead of the configured
+    /// workspace; implies `workspace`
+    pub workspace_root: Option<String>,
 }
 
 impl NextTask {
@@ -35,6 +54,54 @@ impl NextTask {
         self.filter = Some(filter.into());
         self
     }
+
+    /// Pick from every board in the workspace.
+    pub fn with_workspace(mut self) -> Self {
+        self.workspace = true;
+        self
+    }
+
+    /// Pick from the boards found under `root` instead of the configured
+    /// workspace.
+    pub fn with_workspace_root(mut self, root: impl Into<String>) -> Self {
+        self.workspace_root = Some(root.into());
+        self
+    }
+
+    /// The oldest of the next tasks of the workspace's boards. A board that
+    /// cannot be read is logged and skipped.
+    async fn next_in_workspace(&self, ctx: &KanbanContext) -> Result<Value, KanbanError> {
+        let workspace =
+            Workspace::resolve(ctx.root(), self.workspace_root.as_deref().map(Path::new))?;
+        // Reject a bad filter once rather than skipping every board over it.
+        parse_filter_expr(self.filter.as_deref())?;
+        let per_board = NextTask {
+            filter: self.filter.clone(),
+            ..Self::default()
+        };
+
+        let mut oldest: Option<(String, Value)> = None;
+        for board in workspace.boards() {
+            let mut task = match per_board.execute(&board.context()).await.into_result() {
+                Ok(Value::Null) => continue,
+                Ok(task) => task,
+                Err(e) => {
+                    tracing::warn!(board = %board.key, error = %e, "skipping board for next task");
+                    continue;
+                }
+            };
+            let id = task["id"].as_str().unwrap_or_default().to_string();
+            if oldest
+                .as_ref()
+                .is_some_and(|(oldest_id, _)| *oldest_id <= id)
+            {
+                continue;
+            }
+            board.qualify_task_json(&mut task);
+            oldest = Some((id, task));
+        }
+        Ok(oldest.map(|(_, task)| task).unwrap_or(Value::Null))
+    }
 }
 
 /// Build a column-id to ordering-index map for positional sorting.
@@ -96,6 +163,9 @@ fn compare_by_position(
 impl Execute<KanbanContext, KanbanError> for NextTask {
     async fn execute(&self, ctx: &KanbanContext) -> ExecutionResult<Value, KanbanError> {
         match async {
+            if self.workspace || self.workspace_root.is_some() {
+                return self.next_in_workspace(ctx).await;
+            }
             let ectx = ctx.entity_context().await?;
             // Sort by the declared `order` field so `build_column_order` below
             // derives a stable index map. `list("column")` returns entities in
@@ -114,7 +184,13 @@ impl Execute<KanbanContext, KanbanError> for NextTask {
                 .unwrap_or("done");
 
             let registry = default_virtual_tag_registry();
-            enrich_all_task_entities(&mut all_tasks, terminal_column, registry);
+            let wip_limits = ColumnWipLimits::from_columns(&all_columns);
+            enrich_all_task_entities_with_wip_limits(
+                &mut all_tasks,
+                terminal_column,
+                &wip_limits,
+                registry,
+            );
 
             // Build the id-or-slug registry so `$project`, `@user`, and
             // `^task` predicates resolve display-name slugs to entity ids.
@@ -132,6 +208,20 @@ impl Execute<KanbanContext, KanbanError> for NextTask {
 
             candidates.sort_by(|a, b| compare_by_position(a, b, &column_order));
 
+            // Rank by the dependency graph's order. The graph is built in
+            // board order so unrelated tasks keep their position; tasks it
+            // leaves out (stuck behind a cycle) stay last.
+            let mut board_order = all_tasks.clone();
+            board_order.sort_by(|a, b| compare_by_position(a, b, &column_order));
+            let graph = DependencyGraph::build(&board_order, terminal_column);
+            let rank: std::collections::HashMap<&str, usize> = graph
+                .topological_order()
+                .into_iter()
+                .enumerate()
+                .map(|(i, n)| (n.id.as_str(), i))
+                .collect();
+            candidates.sort_by_key(|t| rank.get(t.id.as_str()).copied().unwrap_or(usize::MAX));
+
             match candidates.first() {
                 Some(task) => Ok(task_entity_to_rich_json(task)),
                 None => Ok(Value::Null),
@@ -211,6 +301,33 @@ mod tests {
         assert_eq!(result["title"], "Blocker");
     }
 
+    #[tokio::test]
+    async fn test_next_task_prefers_the_head_of_a_dependency_chain() {
+        use crate::types::TaskId;
+        let (_temp, ctx) = setup().await;
+
+        AddTask::new("Standalone")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        let head = AddTask::new("Foundation")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        AddTask::new("Walls")
+            .with_depends_on(vec![TaskId::from_string(head["id"].as_str().unwrap())])
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        // Both are ready, but Foundation holds up other work.
+        let result = NextTask::new().execute(&ctx).await.into_result().unwrap();
+        assert_eq!(result["title"], "Foundation");
+    }
+
     #[tokio::test]
     async fn test_next_task_filter_by_tag() {
         let (_temp, ctx) = setup().await;
@@ -249,6 +366,47 @@ mod tests {
         assert!(result.is_null());
     }
 
+    #[tokio::test]
+    async fn test_next_task_filter_by_field_predicates() {
+        let (_temp, ctx) = setup().await;
+        AddTask::new("Write docs")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        AddTask::new("Fix OAuth login")
+            .with_due("2026-03-05")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        let result = NextTask::new()
+            .with_filter("title ~ auth")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(result["title"], "Fix OAuth login");
+
+        let result = NextTask::new()
+            .with_filter("has:due && due <= 2026-03-05")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(result["title"], "Fix OAuth login");
+
+        // A malformed value is a parse error that points at the value.
+        let err = NextTask::new()
+            .with_filter("due < 2026-02-30")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap_err();
+        assert!(err.to_string().contains("at 6..16"), "{err}");
+    }
+
     #[tokio::test]
     async fn test_next_task_filter_by_project() {
         use crate::project::AddProject;
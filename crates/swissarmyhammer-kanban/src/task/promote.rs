@@ -0,0 +1,216 @@
+//! PromoteTask command
+
+use crate::context::KanbanContext;
+use crate::error::{KanbanError, Result};
+use crate::task::AddTask;
+use crate::task_helpers::{slim_task_json, task_entity_to_json};
+use crate::types::TaskId;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResult};
+
+/// Turn one checklist item of a task's description into a subtask.
+///
+/// The item's text becomes the subtask's title, and a checked item starts in
+/// the done column. The checkbox line is rewritten to a plain list item that
+/// mentions the subtask (`- ^<short> text`), so the description keeps the
+/// link while progress now comes from the subtask rather than the checkbox.
+#[operation(
+    verb = "promote",
+    noun = "task",
+    description = "Turn a checklist item in a task's description into a subtask"
+)]
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PromoteTask {
+    /// The task whose checklist item is promoted; it becomes the parent
+    pub id: TaskId,
+    /// 0-based index of the checklist item among the description's checkboxes
+    pub item: usize,
+}
+
+impl PromoteTask {
+    /// Create a new PromoteTask command
+    pub fn new(id: impl Into<TaskId>, item: usize) -> Self {
+        Self {
+            id: id.into(),
+            item,
+        }
+    }
+}
+
+/// A `- [ ] text` / `- [x] text` line: its indent, checked state and text.
+fn checklist_item(line: &str) -> Option<(&str, bool, &str)> {
+    let trimmed = line.trim_start();
+    let indent = &line[..line.len() - trimmed.len()];
+    let rest = trimmed.strip_prefix("- [")?;
+    let (checked, text) = match rest.get(..2)? {
+        " ]" => (false, &rest[2..]),
+        "x]" | "X]" => (true, &rest[2..]),
+        _ => return None,
+    };
+    if !text.is_empty() && !text.starts_with(' ') {
+        return None;
+    }
+    Some((indent, checked, text.trim()))
+}
+
+#[async_trait]
+impl Execute<KanbanContext, KanbanError> for PromoteTask {
+    async fn execute(&self, ctx: &KanbanContext) -> ExecutionResult<Value, KanbanError> {
+        let result: Result<Value> = async {
+            let ectx = ctx.entity_context().await?;
+            let mut parent = ectx
+                .read("task", self.id.as_str())
+                .await
+                .map_err(KanbanError::from_entity_error)?;
+            let body = parent.get_str("body").unwrap_or_default().to_string();
+
+            let mut lines: Vec<String> = body.lines().map(str::to_string).collect();
+            let (line_index, indent, checked, title) = lines
+                .iter()
+                .enumerate()
+                .filter_map(|(i, line)| checklist_item(line).map(|item| (i, item)))
+                .nth(self.item)
+                .map(|(i, (indent, checked, text))| {
+                    (i, indent.to_string(), checked, text.to_string())
+                })
+                .ok_or_else(|| {
+                    KanbanError::invalid_value(
+                        "item",
+                        format!("task {} has no checklist item {}", self.id, self.item),
+                    )
+                })?;
+            if title.is_empty() {
+                return Err(KanbanError::invalid_value(
+                    "item",
+                    "an empty checklist item cannot become a subtask",
+                ));
+            }
+
+            let mut subtask = AddTask::new(title.clone()).with_parent(self.id.clone());
+            if checked {
+                let columns = ectx.list("column").await?;
+                subtask.column = columns
+                    .iter()
+                    .max_by_key(|c| c.get("order").and_then(|v| v.as_u64()).unwrap_or(0))
+                    .map(|c| c.id.to_string());
+            }
+
+            let _undo_group = match ectx.store_context() {
+                Some(sc) => Some(sc.begin_undo_group().await),
+                None => None,
+            };
+            let entity = subtask.build_entity(&ectx).await?;
+            subtask.persist(&ectx, &entity).await?;
+
+            let short = crate::types::short_id(entity.id.as_str());
+            lines[line_index] = format!("{indent}- ^{short} {title}");
+            let mut new_body = lines.join("\n");
+            if body.ends_with('\n') {
+                new_body.push('\n');
+            }
+            parent.set("body", json!(new_body));
+            ectx.write(&parent).await?;
+
+            let mut result = slim_task_json(&task_entity_to_json(&entity));
+            result["parent"] = json!(self.id);
+            Ok(result)
+        }
+        .await;
+
+        match result {
+            Ok(value) => ExecutionResult::Success { value },
+            Err(error) => ExecutionResult::Failed { error },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::InitBoard;
+    use crate::task::{GetTask, MoveTask};
+    use tempfile::TempDir;
+
+    async fn setup() -> (TempDir, KanbanContext) {
+        let temp = TempDir::new().unwrap();
+        let kanban_dir = temp.path().join(".kanban");
+        let ctx = KanbanContext::new(kanban_dir);
+
+        InitBoard::new("Test")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+
+        (temp, ctx)
+    }
+
+    #[test]
+    fn test_checklist_item_parses_lines() {
+        assert_eq!(
+            checklist_item("- [ ] Write docs"),
+            Some(("", false, "Write docs"))
+        );
+        assert_eq!(checklist_item("  - [x] Ship"), Some(("  ", true, "Ship")));
+        assert_eq!(checklist_item("- [ ]"), Some(("", false, "")));
+        assert_eq!(checklist_item("- [y] nope"), None);
+        assert_eq!(checklist_item("- [ ]nope"), None);
+        assert_eq!(checklist_item("plain text"), None);
+    }
+
+    #[tokio::test]
+    async fn test_promote_checklist_item_links_parent_and_child() {
+        let (_temp, ctx) = setup().await;
+        let parent = AddTask::new("Release")
+            .with_description("Steps:\n- [x] Tag\n- [ ] Write notes\n")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        let parent_id = parent["id"].as_str().unwrap().to_string();
+
+        let child = PromoteTask::new(parent_id.as_str(), 1)
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(child["title"], "Write notes");
+        assert_eq!(child["parent"], parent_id.as_str());
+        let child_id = child["id"].as_str().unwrap().to_string();
+
+        let fetched = GetTask::new(parent_id.as_str())
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        let short = crate::types::short_id(&child_id);
+        assert_eq!(
+            fetched["description"],
+            format!("Steps:\n- [x] Tag\n- ^{short} Write notes\n")
+        );
+        assert_eq!(fetched["children"], json!([child_id]));
+        assert_eq!(fetched["ready"], false, "the parent waits on its subtask");
+        assert_eq!(fetched["blocked_by"], json!([child_id]));
+
+        MoveTask::to_column(child_id.as_str(), "done")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        let fetched = GetTask::new(parent_id.as_str())
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(fetched["ready"], true);
+        // One checked checkbox plus one finished subtask.
+        assert_eq!(fetched["progress"], 1.0);
+
+        let missing = PromoteTask::new(parent_id.as_str(), 5).execute(&ctx).await;
+        assert!(matches!(
+            missing.into_result(),
+            Err(KanbanError::InvalidValue { .. })
+        ));
+    }
+}
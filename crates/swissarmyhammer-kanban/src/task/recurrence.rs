@@ -0,0 +1,501 @@
+//! Recurring tasks.
+//!
+//! A task's `recurrence` field holds a rule in a small subset of the iCalendar
+//! RRULE syntax:
+//!
+//! - `FREQ=DAILY`, `FREQ=WEEKLY` or `FREQ=MONTHLY` (required)
+//! - `INTERVAL=n` — every n days/weeks/months (default 1, at most 999)
+//! - `BYDAY=MO,WE,FR` — weekdays, `WEEKLY` only
+//! - `BYMONTHDAY=15` — day of the month, `MONTHLY` only; a day past the end of
+//!   a short month falls on its last day, so `BYMONTHDAY=31` means "month end"
+//!
+//! Parts are `;`-separated, case-insensitive, and may carry an `RRULE:` prefix.
+//! Rules are stored in canonical form (see [`RecurrenceRule`]'s `Display`).
+//! A `MONTHLY` rule without `BYMONTHDAY` gains one when its first instance is
+//! spawned, so a task due on the 31st stays on month end instead of drifting
+//! to the 28th after February.
+//!
+//! When [`CompleteTask`](crate::task::CompleteTask) completes a recurring task,
+//! [`spawn_next_instance`] creates the next instance with its dates moved
+//! forward. The two tasks are linked both ways — `recurred_from` on the new
+//! task and `recurred_as` on the completed one — so each side's changelog
+//! records the link.
+
+use crate::error::{KanbanError, Result};
+use crate::task::AddTask;
+use crate::types::ActorId;
+use chrono::{Datelike, Duration, Months, NaiveDate, Weekday};
+use serde_json::json;
+use std::fmt;
+use swissarmyhammer_entity::{Entity, EntityContext};
+
+/// How often a recurring task repeats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl Frequency {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Daily => "DAILY",
+            Self::Weekly => "WEEKLY",
+            Self::Monthly => "MONTHLY",
+        }
+    }
+}
+
+/// A parsed `recurrence` rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub freq: Frequency,
+    pub interval: u32,
+    /// Weekdays for `WEEKLY` rules, Monday first; empty means "same weekday".
+    pub by_day: Vec<Weekday>,
+    /// Day of the month for `MONTHLY` rules; `None` means "same day".
+    pub by_month_day: Option<u32>,
+}
+
+/// Upper bound on catch-up steps when a recurring task is completed long
+/// after its dates (a daily rule 27 years overdue).
+const MAX_CATCH_UP_STEPS: usize = 10_000;
+
+/// Largest accepted `INTERVAL`. Keeps a single step well inside chrono's
+/// date range; the stepping itself is still checked.
+const MAX_INTERVAL: u32 = 999;
+
+fn invalid(message: impl Into<String>) -> KanbanError {
+    KanbanError::invalid_value("recurrence", message)
+}
+
+fn parse_weekday(code: &str) -> Option<Weekday> {
+    match code {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn weekday_code(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    if month == 12 {
+        return 31;
+    }
+    let first = NaiveDate::from_ymd_opt(year, month, 1).expect("valid month");
+    let next = NaiveDate::from_ymd_opt(year, month + 1, 1).expect("valid month");
+    (next - first).num_days() as u32
+}
+
+/// The `day` of the month containing `date`, clamped to the month's length.
+fn clamp_to_month(date: NaiveDate, day: u32) -> NaiveDate {
+    let day = day.min(days_in_month(date.year(), date.month()));
+    NaiveDate::from_ymd_opt(date.year(), date.month(), day).expect("clamped day is valid")
+}
+
+impl RecurrenceRule {
+    /// Parse a rule, rejecting anything outside the supported subset.
+    pub fn parse(input: &str) -> Result<Self> {
+        let trimmed = input.trim();
+        let body = trimmed
+            .get(..6)
+            .filter(|p| p.eq_ignore_ascii_case("RRULE:"))
+            .map_or(trimmed, |_| &trimmed[6..]);
+        if body.trim().is_empty() {
+            return Err(invalid("empty rule"));
+        }
+
+        let mut freq = None;
+        let mut interval = 1;
+        let mut by_day = Vec::new();
+        let mut by_month_day = None;
+
+        for part in body.split(';').map(str::trim).filter(|p| !p.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| invalid(format!("expected KEY=VALUE, got {part:?}")))?;
+            let value = value.trim().to_ascii_uppercase();
+            match key.trim().to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    freq = Some(match value.as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        other => {
+                            return Err(invalid(format!(
+                                "unsupported FREQ {other:?} (expected DAILY, WEEKLY or MONTHLY)"
+                            )))
+                        }
+                    })
+                }
+                "INTERVAL" => {
+                    interval = value
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|n| (1..=MAX_INTERVAL).contains(n))
+                        .ok_or_else(|| {
+                            invalid(format!("INTERVAL must be 1-{MAX_INTERVAL}, got {value:?}"))
+                        })?;
+                }
+                "BYDAY" => {
+                    for code in value.split(',').map(str::trim) {
+                        let day = parse_weekday(code)
+                            .ok_or_else(|| invalid(format!("unknown BYDAY weekday {code:?}")))?;
+                        if !by_day.contains(&day) {
+                            by_day.push(day);
+                        }
+                    }
+                    by_day.sort_by_key(|d| d.num_days_from_monday());
+                }
+                "BYMONTHDAY" => {
+                    by_month_day = Some(
+                        value
+                            .parse::<u32>()
+                            .ok()
+                            .filter(|d| (1..=31).contains(d))
+                            .ok_or_else(|| {
+                                invalid(format!("BYMONTHDAY must be 1-31, got {value:?}"))
+                            })?,
+                    );
+                }
+                other => return Err(invalid(format!("unsupported rule part {other:?}"))),
+            }
+        }
+
+        let freq = freq.ok_or_else(|| invalid("missing FREQ"))?;
+        if !by_day.is_empty() && freq != Frequency::Weekly {
+            return Err(invalid("BYDAY is only supported with FREQ=WEEKLY"));
+        }
+        if by_month_day.is_some() && freq != Frequency::Monthly {
+            return Err(invalid("BYMONTHDAY is only supported with FREQ=MONTHLY"));
+        }
+
+        Ok(Self {
+            freq,
+            interval,
+            by_day,
+            by_month_day,
+        })
+    }
+
+    /// The first occurrence strictly after `date`, or `None` when it falls
+    /// outside the representable date range.
+    ///
+    /// `date` need not itself be an occurrence: a `WEEKLY;BYDAY=MO` rule from
+    /// a Wednesday lands on the following Monday.
+    pub fn next_after(&self, date: NaiveDate) -> Option<NaiveDate> {
+        let days = |n: u32| Duration::days(i64::from(n));
+        let weeks = Duration::weeks(i64::from(self.interval));
+        match self.freq {
+            Frequency::Daily => date.checked_add_signed(days(self.interval)),
+            Frequency::Weekly if self.by_day.is_empty() => date.checked_add_signed(weeks),
+            Frequency::Weekly => {
+                let offset = date.weekday().num_days_from_monday();
+                let week_start = date.checked_sub_signed(days(offset))?;
+                if let Some(day) = self
+                    .by_day
+                    .iter()
+                    .find(|d| d.num_days_from_monday() > offset)
+                {
+                    return week_start.checked_add_signed(days(day.num_days_from_monday()));
+                }
+                week_start
+                    .checked_add_signed(weeks)?
+                    .checked_add_signed(days(self.by_day[0].num_days_from_monday()))
+            }
+            Frequency::Monthly => {
+                let day = self.by_month_day.unwrap_or(date.day());
+                let this_month = clamp_to_month(date, day);
+                if this_month > date {
+                    return Some(this_month);
+                }
+                let first = date
+                    .with_day(1)
+                    .expect("day 1 is valid")
+                    .checked_add_months(Months::new(self.interval))?;
+                Some(clamp_to_month(first, day))
+            }
+        }
+    }
+
+    /// The first occurrence after `date` that is also after `floor`.
+    ///
+    /// Used when a task is completed late, so the next instance is not
+    /// already overdue.
+    pub fn next_after_floor(&self, date: NaiveDate, floor: NaiveDate) -> Option<NaiveDate> {
+        let mut next = self.next_after(date)?;
+        for _ in 0..MAX_CATCH_UP_STEPS {
+            if next > floor {
+                break;
+            }
+            next = self.next_after(next)?;
+        }
+        Some(next)
+    }
+
+    /// This rule pinned to `date`'s day of the month.
+    ///
+    /// A plain `MONTHLY` rule repeats on the day it is stepped from, so a
+    /// clamped month end (the 28th after the 31st) would stick. Pinning the
+    /// day keeps every later instance on the original anchor.
+    fn anchored_at(&self, date: NaiveDate) -> Self {
+        let mut rule = self.clone();
+        if rule.freq == Frequency::Monthly && rule.by_month_day.is_none() {
+            rule.by_month_day = Some(date.day());
+        }
+        rule
+    }
+}
+
+impl fmt::Display for RecurrenceRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FREQ={}", self.freq.as_str())?;
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if !self.by_day.is_empty() {
+            let days: Vec<&str> = self.by_day.iter().map(|d| weekday_code(*d)).collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        if let Some(day) = self.by_month_day {
+            write!(f, ";BYMONTHDAY={day}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Validate a rule and return its canonical form for storage.
+pub(crate) fn normalize_recurrence(input: &str) -> Result<String> {
+    Ok(RecurrenceRule::parse(input)?.to_string())
+}
+
+fn date_field(task: &Entity, field: &str) -> Option<NaiveDate> {
+    task.get_str(field)
+        .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
+}
+
+/// Create the next instance of a recurring task that is being completed.
+///
+/// Returns the new task's id, or `None` when `task` has no (valid) rule or
+/// has already recurred — completing a task twice must not create two
+/// successors. On success `task` gains `recurred_as`; the caller writes it.
+///
+/// Dates move forward by the rule: `due` (or `scheduled` when there is no
+/// due date) advances to its next occurrence after `today`, and the other
+/// date keeps its distance from it. A task with neither date gets a
+/// `scheduled` date at the next occurrence after `today`. The new instance
+/// keeps the title, description (and so its tags), assignees, project and
+/// rule (a plain `MONTHLY` rule pinned to the day of its anchor date), and
+/// starts in the first column; it is placed even if that column is
+/// at its WIP limit, so completing work never fails on the successor.
+pub(crate) async fn spawn_next_instance(
+    ectx: &EntityContext,
+    task: &mut Entity,
+    today: NaiveDate,
+) -> Result<Option<String>> {
+    let Some(raw) = task.get_str("recurrence").filter(|r| !r.trim().is_empty()) else {
+        return Ok(None);
+    };
+    if task.get_str("recurred_as").is_some() {
+        return Ok(None);
+    }
+    let rule = match RecurrenceRule::parse(raw) {
+        Ok(rule) => rule,
+        Err(error) => {
+            tracing::warn!(
+                task = task.id.as_str(),
+                %error,
+                "skipping recurrence with invalid rule"
+            );
+            return Ok(None);
+        }
+    };
+
+    let due = date_field(task, "due");
+    let scheduled = date_field(task, "scheduled");
+    let rule = rule.anchored_at(due.or(scheduled).unwrap_or(today));
+    let dates = match (due, scheduled) {
+        (Some(due), scheduled) => rule.next_after_floor(due, today).and_then(|next| {
+            let scheduled = match scheduled {
+                Some(s) => Some(s.checked_add_signed(next - due)?),
+                None => None,
+            };
+            Some((Some(next), scheduled))
+        }),
+        (None, Some(scheduled)) => rule
+            .next_after_floor(scheduled, today)
+            .map(|next| (None, Some(next))),
+        (None, None) => rule.next_after(today).map(|next| (None, Some(next))),
+    };
+    let Some((next_due, next_scheduled)) = dates else {
+        tracing::warn!(
+            task = task.id.as_str(),
+            "skipping recurrence whose next date is out of range"
+        );
+        return Ok(None);
+    };
+
+    let mut cmd = AddTask::new(task.get_str("title").unwrap_or_default())
+        .with_description(task.get_str("body").unwrap_or_default())
+        .with_assignees(
+            task.get_string_list("assignees")
+                .into_iter()
+                .map(ActorId::from)
+                .collect(),
+        )
+        .with_override_wip_limit();
+    if let Some(project) = task.get_str("project") {
+        cmd = cmd.with_project(project);
+    }
+    if let Some(due) = next_due {
+        cmd = cmd.with_due(due.format("%Y-%m-%d").to_string());
+    }
+    if let Some(scheduled) = next_scheduled {
+        cmd = cmd.with_scheduled(scheduled.format("%Y-%m-%d").to_string());
+    }
+
+    let mut next = cmd.build_entity(ectx).await?;
+    next.set("recurrence", json!(rule.to_string()));
+    next.set("recurred_from", json!(task.id.as_str()));
+    cmd.persist(ectx, &next).await?;
+
+    task.set("recurred_as", json!(next.id.as_str()));
+    Ok(Some(next.id.as_str().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn parse_accepts_prefix_and_normalizes() {
+        let rule = RecurrenceRule::parse("rrule:freq=weekly;byday=fr,mo,mo;interval=2").unwrap();
+        assert_eq!(rule.to_string(), "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,FR");
+        assert_eq!(normalize_recurrence(" FREQ=DAILY ").unwrap(), "FREQ=DAILY");
+    }
+
+    #[test]
+    fn parse_rejects_unsupported_rules() {
+        for bad in [
+            "",
+            "INTERVAL=2",
+            "FREQ=YEARLY",
+            "FREQ=DAILY;INTERVAL=0",
+            "FREQ=DAILY;INTERVAL=1000",
+            "FREQ=DAILY;INTERVAL=4294967295",
+            "FREQ=DAILY;BYDAY=MO",
+            "FREQ=WEEKLY;BYMONTHDAY=3",
+            "FREQ=MONTHLY;BYMONTHDAY=32",
+            "FREQ=WEEKLY;BYDAY=XX",
+            "FREQ=DAILY;COUNT=3",
+        ] {
+            assert!(
+                matches!(
+                    RecurrenceRule::parse(bad),
+                    Err(KanbanError::InvalidValue { .. })
+                ),
+                "{bad:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn daily_and_plain_weekly_step_by_interval() {
+        let daily = RecurrenceRule::parse("FREQ=DAILY;INTERVAL=3").unwrap();
+        assert_eq!(
+            daily.next_after(date("2026-01-30")),
+            Some(date("2026-02-02"))
+        );
+        let weekly = RecurrenceRule::parse("FREQ=WEEKLY").unwrap();
+        assert_eq!(
+            weekly.next_after(date("2026-03-04")),
+            Some(date("2026-03-11"))
+        );
+    }
+
+    #[test]
+    fn weekly_byday_picks_next_listed_weekday() {
+        // 2026-03-04 is a Wednesday.
+        let rule = RecurrenceRule::parse("FREQ=WEEKLY;BYDAY=MO,FR").unwrap();
+        assert_eq!(
+            rule.next_after(date("2026-03-04")),
+            Some(date("2026-03-06"))
+        );
+        assert_eq!(
+            rule.next_after(date("2026-03-06")),
+            Some(date("2026-03-09"))
+        );
+
+        let fortnightly = RecurrenceRule::parse("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO").unwrap();
+        assert_eq!(
+            fortnightly.next_after(date("2026-03-02")),
+            Some(date("2026-03-16"))
+        );
+    }
+
+    #[test]
+    fn monthly_clamps_to_month_end() {
+        let rule = RecurrenceRule::parse("FREQ=MONTHLY;BYMONTHDAY=31").unwrap();
+        assert_eq!(
+            rule.next_after(date("2026-01-31")),
+            Some(date("2026-02-28"))
+        );
+        assert_eq!(
+            rule.next_after(date("2026-02-28")),
+            Some(date("2026-03-31"))
+        );
+
+        let mid = RecurrenceRule::parse("FREQ=MONTHLY;BYMONTHDAY=15").unwrap();
+        assert_eq!(mid.next_after(date("2026-04-02")), Some(date("2026-04-15")));
+        assert_eq!(mid.next_after(date("2026-04-15")), Some(date("2026-05-15")));
+    }
+
+    #[test]
+    fn next_after_floor_skips_missed_occurrences() {
+        let rule = RecurrenceRule::parse("FREQ=WEEKLY").unwrap();
+        assert_eq!(
+            rule.next_after_floor(date("2026-01-05"), date("2026-02-01")),
+            Some(date("2026-02-02"))
+        );
+    }
+
+    #[test]
+    fn next_after_is_none_past_the_date_range() {
+        let rule = RecurrenceRule::parse("FREQ=MONTHLY;INTERVAL=999").unwrap();
+        assert_eq!(rule.next_after(NaiveDate::MAX), None);
+        let weekly = RecurrenceRule::parse("FREQ=WEEKLY;BYDAY=MO").unwrap();
+        assert_eq!(weekly.next_after(NaiveDate::MAX), None);
+    }
+
+    #[test]
+    fn anchored_monthly_rule_returns_to_month_end() {
+        let rule = RecurrenceRule::parse("FREQ=MONTHLY")
+            .unwrap()
+            .anchored_at(date("2026-01-31"));
+        assert_eq!(rule.to_string(), "FREQ=MONTHLY;BYMONTHDAY=31");
+        let feb = rule.next_after(date("2026-01-31")).unwrap();
+        assert_eq!(feb, date("2026-02-28"));
+        assert_eq!(rule.next_after(feb), Some(date("2026-03-31")));
+    }
+}
@@ -15,6 +15,11 @@
 //! per-signal `signals`. The heavy payload (`description`/`attachments`/
 //! `comments`) stays one `get task` away.
 //!
+//! With `workspace` set the corpus is every in-scope task on every board of
+//! the [`Workspace`], ranked as one corpus so scores compare across boards.
+//! Hit ids are qualified with the board key (`api/01J...`) and each hit names
+//! its `board`; a board that cannot be read is reported under `errors`.
+//!
 //! Embeddings are never optional. The embedder is a process-lifetime singleton
 //! loaded at most once (see [`shared_embedder`]); if it cannot load, the op
 //! returns a [`KanbanError`] rather than silently degrading to a lexical-only
@@ -26,12 +31,14 @@ use crate::error::KanbanError;
 use crate::task::embedding_cache::{content_hash, task_embedding_text, EmbeddingCache};
 use crate::task::shared::parse_filter_expr;
 use crate::task_helpers::{
-    enrich_all_task_entities, slim_task_json, task_entity_to_rich_json, task_tags,
+    enrich_all_task_entities_with_wip_limits, slim_task_json, task_entity_to_rich_json, task_tags,
     EntitySlugRegistry, TaskFilterAdapter,
 };
-use crate::virtual_tags::default_virtual_tag_registry;
+use crate::virtual_tags::{default_virtual_tag_registry, ColumnWipLimits};
+use crate::workspace::{Workspace, WorkspaceBoard};
 use serde::Deserialize;
 use serde_json::{json, Value};
+use std::path::Path;
 use std::sync::Arc;
 use swissarmyhammer_embedding::{Embedder, TextEmbedder};
 use swissarmyhammer_entity::Entity;
@@ -67,6 +74,12 @@ pub struct SearchTasks {
     pub filter: Option<String>,
     /// Maximum number of ranked hits to return. Defaults to [`DEFAULT_TOP_K`].
     pub top_k: Option<usize>,
+    /// Search every board in the workspace instead of this one
+    #[serde(default)]
+    pub workspace: bool,
+    /// Directory to find the workspace's boards in, instead of the configured
+    /// workspace; implies `workspace`
+    pub workspace_root: Option<String>,
 }
 
 impl SearchTasks {
@@ -76,6 +89,8 @@ impl SearchTasks {
             query: query.into(),
             filter: None,
             top_k: None,
+            workspace: false,
+            workspace_root: None,
         }
     }
 
@@ -90,6 +105,19 @@ impl SearchTasks {
         self.top_k = Some(top_k);
         self
     }
+
+    /// Search every board in the workspace.
+    pub fn with_workspace(mut self) -> Self {
+        self.workspace = true;
+        self
+    }
+
+    /// Search the boards found under `root` instead of the configured
+    /// workspace.
+    pub fn with_workspace_root(mut self, root: impl Into<String>) -> Self {
+        self.workspace_root = Some(root.into());
+        self
+    }
 }
 
 /// Process-lifetime embedder handle, loaded at most once.
@@ -169,11 +197,16 @@ fn in_scope_tasks(
 /// Kept free of any embedding call so Doc construction is unit-testable without
 /// a model — the caller supplies the (cached) embedding.
 fn build_doc(entity: &Entity, embedding: Option<Vec<f32>>) -> Doc {
+    build_doc_with_id(entity.id.as_str(), entity, embedding)
+}
+
+/// [`build_doc`] under another id, such as a workspace-qualified one.
+fn build_doc_with_id(id: &str, entity: &Entity, embedding: Option<Vec<f32>>) -> Doc {
     let title = entity.get_str("title").unwrap_or("");
     let description = entity.get_str("body").unwrap_or("");
     let tags_joined = task_tags(entity).join(" ");
     Doc::new(
-        entity.id.as_str(),
+        id,
         vec![
             Field::new(TITLE_WEIGHT, title),
             Field::new(DESCRIPTION_WEIGHT, description),
@@ -191,7 +224,8 @@ fn build_doc(entity: &Entity, embedding: Option<Vec<f32>>) -> Doc {
 /// payload (`description`, `attachments`, and any future `comments`). The agent
 /// follows up with `get task` (always full) on the hit it cares about, so the
 /// per-hit payload stays lean across `top_k` results. Entities must already be
-/// enriched (see [`enrich_all_task_entities`]) so the rich fields are present.
+/// enriched (see [`crate::task_helpers::enrich_all_task_entities`]) so the rich
+/// fields are present.
 ///
 /// This is the single projection policy for the surfaced result objects; the
 /// internal corpus/Doc build (embedding text, lexical fields) is built from the
@@ -213,6 +247,9 @@ fn surfaced_task_json(entity: &Entity) -> Value {
 /// NOTE: the `Doc` lexical fields and embedding text are built from the FULL
 /// entity (unchanged); only the surfaced map-back JSON is slimmed.
 ///
+/// With a `board` the Doc ids and the surfaced JSON are qualified with its
+/// key (see [`WorkspaceBoard::qualify_task_json`]).
+///
 /// Isolated from [`SearchTasks::run`] so the embedding/cache machinery reads as a
 /// single step and `run` stays a clear sequence. Takes `cache` by value because
 /// [`EmbeddingCache`] is `Send` but not `Sync`: holding it by `&` across the
@@ -221,6 +258,7 @@ async fn build_docs_with_embeddings(
     scoped: &[Entity],
     embedder: &Embedder,
     cache: EmbeddingCache,
+    board: Option<&WorkspaceBoard>,
 ) -> Result<(Vec<Doc>, std::collections::HashMap<String, Value>), KanbanError> {
     let mut docs: Vec<Doc> = Vec::with_capacity(scoped.len());
     let mut enriched_by_id: std::collections::HashMap<String, Value> =
@@ -247,8 +285,16 @@ async fn build_docs_with_embeddings(
             }
         };
 
-        docs.push(build_doc(entity, Some(vector)));
-        enriched_by_id.insert(id.to_string(), surfaced_task_json(entity));
+        let mut surfaced = surfaced_task_json(entity);
+        let doc_id = match board {
+            Some(board) => {
+                board.qualify_task_json(&mut surfaced);
+                board.qualify(id)
+            }
+            None => id.to_string(),
+        };
+        docs.push(build_doc_with_id(&doc_id, entity, Some(vector)));
+        enriched_by_id.insert(doc_id, surfaced);
     }
     Ok((docs, enriched_by_id))
 }
@@ -295,6 +341,75 @@ impl Execute<KanbanContext, KanbanError> for SearchTasks {
 impl SearchTasks {
     /// Run the search: scope the corpus, embed-or-cache each Doc, rank, map back.
     async fn run(&self, ctx: &KanbanContext) -> Result<Value, KanbanError> {
+        if self.workspace || self.workspace_root.is_some() {
+            return self.run_in_workspace(ctx).await;
+        }
+        let scoped = self.scoped_tasks(ctx).await?;
+
+        // Empty corpus → no ranking work, no embedder load.
+        if scoped.is_empty() {
+            return Ok(json!({ "count": 0, "tasks": [] }));
+        }
+
+        // Process-lifetime embedder (loaded at most once). NO lexical-only
+        // fallback: a load failure propagates as a KanbanError.
+        let embedder = shared_embedder().await?;
+        let cache = open_embedding_cache(ctx, &embedder)?;
+
+        // Build a Doc per task, lazy-filling the embedding cache on misses.
+        let (docs, enriched_by_id) =
+            build_docs_with_embeddings(&scoped, &embedder, cache, None).await?;
+
+        let hits = search(&docs, &self.query(&embedder).await?);
+        Ok(map_hits_to_response(&hits, &enriched_by_id))
+    }
+
+    /// Run the search over every board of the workspace as one corpus.
+    async fn run_in_workspace(&self, ctx: &KanbanContext) -> Result<Value, KanbanError> {
+        let workspace =
+            Workspace::resolve(ctx.root(), self.workspace_root.as_deref().map(Path::new))?;
+        // Reject a bad filter once rather than as an error from every board.
+        parse_filter_expr(self.filter.as_deref())?;
+
+        let mut scoped_boards = Vec::new();
+        let mut errors = Vec::new();
+        for board in workspace.boards() {
+            let board_ctx = board.context();
+            match self.scoped_tasks(&board_ctx).await {
+                Ok(scoped) if scoped.is_empty() => {}
+                Ok(scoped) => scoped_boards.push((board, board_ctx, scoped)),
+                Err(e) => errors.push(json!({ "board": board.key, "error": e.to_string() })),
+            }
+        }
+
+        let mut docs = Vec::new();
+        let mut enriched_by_id = std::collections::HashMap::new();
+        let mut hits = Vec::new();
+        if !scoped_boards.is_empty() {
+            let embedder = shared_embedder().await?;
+            for (board, board_ctx, scoped) in &scoped_boards {
+                let cache = open_embedding_cache(board_ctx, &embedder)?;
+                let (board_docs, board_enriched) =
+                    build_docs_with_embeddings(scoped, &embedder, cache, Some(*board)).await?;
+                docs.extend(board_docs);
+                enriched_by_id.extend(board_enriched);
+            }
+            hits = search(&docs, &self.query(&embedder).await?);
+        }
+
+        let mut response = map_hits_to_response(&hits, &enriched_by_id);
+        response["boards"] = json!(workspace
+            .boards()
+            .iter()
+            .map(|board| board.key.as_str())
+            .collect::<Vec<_>>());
+        response["errors"] = json!(errors);
+        Ok(response)
+    }
+
+    /// The board's tasks this search covers: enriched, without the done
+    /// column, and narrowed by the filter.
+    async fn scoped_tasks(&self, ctx: &KanbanContext) -> Result<Vec<Entity>, KanbanError> {
         let ectx = ctx.entity_context().await?;
         let all_columns = ectx.list("column").await?;
         let mut all_tasks = ectx.list("task").await?;
@@ -307,33 +422,19 @@ impl SearchTasks {
             .to_string();
 
         let registry = default_virtual_tag_registry();
-        enrich_all_task_entities(&mut all_tasks, &terminal, registry);
+        let wip_limits = ColumnWipLimits::from_columns(&all_columns);
+        enrich_all_task_entities_with_wip_limits(&mut all_tasks, &terminal, &wip_limits, registry);
 
         let all_projects = ectx.list("project").await?;
         let all_actors = ectx.list("actor").await?;
         let slug_registry = EntitySlugRegistry::build(&all_projects, &all_actors, &all_tasks);
 
         let expr = parse_filter_expr(self.filter.as_deref())?;
-        let scoped = in_scope_tasks(&all_tasks, &terminal, &slug_registry, &expr);
-
-        // Empty corpus → no ranking work, no embedder load.
-        if scoped.is_empty() {
-            return Ok(json!({ "count": 0, "tasks": [] }));
-        }
-
-        // Process-lifetime embedder (loaded at most once). NO lexical-only
-        // fallback: a load failure propagates as a KanbanError.
-        let embedder = shared_embedder().await?;
-        let model_name = embedder.model_name().to_string();
-        let dim = embedder.embedding_dimension().unwrap_or(0);
-
-        let cache = EmbeddingCache::open(ctx.search_cache_path(), &model_name, dim)
-            .map_err(|e| KanbanError::parse(format!("failed to open embedding cache: {e}")))?;
-
-        // Build a Doc per task, lazy-filling the embedding cache on misses.
-        let (docs, enriched_by_id) = build_docs_with_embeddings(&scoped, &embedder, cache).await?;
+        Ok(in_scope_tasks(&all_tasks, &terminal, &slug_registry, &expr))
+    }
 
-        // Embed the query once.
+    /// The ranking query: the text, embedded once, and the hit cap.
+    async fn query(&self, embedder: &Embedder) -> Result<Query, KanbanError> {
         let query_vec = embedder
             .embed_text(&self.query)
             .await
@@ -341,16 +442,24 @@ impl SearchTasks {
             .embedding()
             .to_vec();
 
-        let query = Query::new(self.query.clone())
+        Ok(Query::new(self.query.clone())
             .with_embedding(query_vec)
             .with_weights(SignalWeights::default())
-            .with_top_k(self.top_k.unwrap_or(DEFAULT_TOP_K));
-
-        let hits = search(&docs, &query);
-        Ok(map_hits_to_response(&hits, &enriched_by_id))
+            .with_top_k(self.top_k.unwrap_or(DEFAULT_TOP_K)))
     }
 }
 
+/// Open the board's embedding cache for `embedder`'s model.
+fn open_embedding_cache(
+    ctx: &KanbanContext,
+    embedder: &Embedder,
+) -> Result<EmbeddingCache, KanbanError> {
+    let model_name = embedder.model_name().to_string();
+    let dim = embedder.embedding_dimension().unwrap_or(0);
+    EmbeddingCache::open(ctx.search_cache_path(), &model_name, dim)
+        .map_err(|e| KanbanError::parse(format!("failed to open embedding cache: {e}")))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -386,7 +495,8 @@ mod tests {
             .unwrap_or("done")
             .to_string();
         let registry = default_virtual_tag_registry();
-        enrich_all_task_entities(&mut all_tasks, &terminal, registry);
+        let wip_limits = ColumnWipLimits::from_columns(&all_columns);
+        enrich_all_task_entities_with_wip_limits(&mut all_tasks, &terminal, &wip_limits, registry);
         let all_projects = ectx.list("project").await.unwrap();
         let all_actors = ectx.list("actor").await.unwrap();
         let slug_registry = EntitySlugRegistry::build(&all_projects, &all_actors, &all_tasks);
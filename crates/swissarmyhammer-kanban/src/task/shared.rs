@@ -2,6 +2,10 @@
 
 use crate::error::KanbanError;
 use crate::tag::tag_name_exists_entity;
+use crate::task_helpers::{
+    enrich_all_task_entities_with_wip_limits, EntitySlugRegistry, TaskFilterAdapter,
+};
+use crate::virtual_tags::{default_virtual_tag_registry, ColumnWipLimits};
 use crate::{auto_color, tag_parser};
 use chrono::{DateTime, NaiveDate};
 use serde_json::json;
@@ -58,7 +62,7 @@ pub(crate) fn parse_filter_expr(
     match filter.filter(|f| !f.trim().is_empty()) {
         Some(f) => {
             let expr = swissarmyhammer_filter_expr::parse(f).map_err(|errors| {
-                let msgs: Vec<String> = errors.iter().map(|e| e.message.clone()).collect();
+                let msgs: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
                 KanbanError::parse(format!("invalid filter: {}", msgs.join("; ")))
             })?;
             Ok(Some(expr))
@@ -67,6 +71,41 @@ pub(crate) fn parse_filter_expr(
     }
 }
 
+/// Keep only the tasks in `tasks` that match `expr`.
+///
+/// The tasks are first enriched the way `list tasks` enriches them (virtual
+/// tags against the board's terminal column and WIP limits), and slugs for
+/// `$project`, `@actor` and `^task` resolve through the board's registry, so
+/// an expression selects the same tasks here as it does there.
+pub(crate) async fn retain_matching_tasks(
+    ectx: &EntityContext,
+    tasks: &mut Vec<Entity>,
+    columns: &[Entity],
+    expr: &swissarmyhammer_filter_expr::Expr,
+) -> Result<(), KanbanError> {
+    let terminal = columns
+        .iter()
+        .max_by_key(|c| c.get("order").and_then(|v| v.as_u64()).unwrap_or(0))
+        .map(|c| c.id.as_str())
+        .unwrap_or("done");
+    enrich_all_task_entities_with_wip_limits(
+        tasks,
+        terminal,
+        &ColumnWipLimits::from_columns(columns),
+        default_virtual_tag_registry(),
+    );
+    let projects = ectx.list("project").await?;
+    let actors = ectx.list("actor").await?;
+    let registry = EntitySlugRegistry::build(&projects, &actors, tasks);
+    let keep: Vec<bool> = tasks
+        .iter()
+        .map(|t| expr.matches(&TaskFilterAdapter::with_registry(t, &registry)))
+        .collect();
+    let mut keep = keep.into_iter();
+    tasks.retain(|_| keep.next().unwrap_or(false));
+    Ok(())
+}
+
 /// Per-task payload shape for list-style task operations.
 ///
 /// `Slim` (the default) is the allowlist projection produced by
@@ -2,6 +2,8 @@
 
 use crate::context::KanbanContext;
 use crate::error::{KanbanError, Result};
+use crate::task::hierarchy::check_task_links;
+use crate::task::recurrence::normalize_recurrence;
 use crate::task::shared::{auto_create_body_tags, parse_iso8601_date};
 use crate::task::tags::{apply_tag_refs, TagApply};
 use crate::task_helpers::task_mutation_ack;
@@ -50,8 +52,14 @@ pub struct UpdateTask {
     /// format (full ULID, 7-char short id, `^<short>`, unique ULID prefix,
     /// lowercase) — each resolves to the canonical full ULID. The derived
     /// `blocked_by` field (the unsatisfied subset of `depends_on`) is computed,
-    /// not directly settable.
+    /// not directly settable. A list that would close a dependency cycle is
+    /// rejected.
     pub depends_on: Option<Vec<TaskId>>,
+    /// Parent task, making this task one of its subtasks. Same tri-state
+    /// semantics as `due`: clearing it detaches the task. A parent that is the
+    /// task itself or one of its subtasks is rejected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<Option<TaskId>>,
     /// Replace the task's whole tag set.
     ///
     /// `None` leaves the tags untouched; `Some(list)` makes the tag set exactly
@@ -80,6 +88,10 @@ pub struct UpdateTask {
     /// Earliest start date (ISO 8601). Same tri-state semantics as `due`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub scheduled: Option<Option<String>>,
+    /// Repeat rule (e.g. "FREQ=WEEKLY;BYDAY=MO"). Same tri-state semantics as
+    /// `due`; clearing it stops the task from recurring.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<Option<String>>,
 }
 
 impl UpdateTask {
@@ -91,11 +103,13 @@ impl UpdateTask {
             description: None,
             assignees: None,
             depends_on: None,
+            parent: None,
             tags: None,
             attachments: None,
             project: None,
             due: None,
             scheduled: None,
+            recurrence: None,
         }
     }
 
@@ -123,6 +137,18 @@ impl UpdateTask {
         self
     }
 
+    /// Make the task a subtask of `parent`.
+    pub fn with_parent(mut self, parent: impl Into<TaskId>) -> Self {
+        self.parent = Some(Some(parent.into()));
+        self
+    }
+
+    /// Detach the task from its parent.
+    pub fn clear_parent(mut self) -> Self {
+        self.parent = Some(None);
+        self
+    }
+
     /// Replace the task's whole tag set (an empty list clears every tag).
     pub fn with_tags(mut self, tags: Vec<String>) -> Self {
         self.tags = Some(tags);
@@ -165,6 +191,18 @@ impl UpdateTask {
         self
     }
 
+    /// Set the repeat rule.
+    pub fn with_recurrence(mut self, recurrence: impl Into<String>) -> Self {
+        self.recurrence = Some(Some(recurrence.into()));
+        self
+    }
+
+    /// Clear the repeat rule so the task no longer recurs.
+    pub fn clear_recurrence(mut self) -> Self {
+        self.recurrence = Some(None);
+        self
+    }
+
     /// Apply all set fields to the entity.
     ///
     /// For the date fields, an outer `Some(None)` or an inner empty string
@@ -183,6 +221,15 @@ impl UpdateTask {
         if let Some(deps) = &self.depends_on {
             entity.set("depends_on", serde_json::to_value(deps)?);
         }
+        match &self.parent {
+            None => {}
+            Some(Some(parent)) if !parent.as_str().is_empty() => {
+                entity.set("parent", serde_json::json!(parent));
+            }
+            Some(_) => {
+                entity.remove("parent");
+            }
+        }
         if let Some(attachments) = &self.attachments {
             entity.set("attachments", attachments.clone());
         }
@@ -191,6 +238,15 @@ impl UpdateTask {
         }
         apply_optional_date(entity, "due", &self.due)?;
         apply_optional_date(entity, "scheduled", &self.scheduled)?;
+        match &self.recurrence {
+            None => {}
+            Some(Some(raw)) if !raw.trim().is_empty() => {
+                entity.set("recurrence", serde_json::json!(normalize_recurrence(raw)?));
+            }
+            Some(_) => {
+                entity.remove("recurrence");
+            }
+        }
         Ok(())
     }
 }
@@ -237,6 +293,16 @@ impl Execute<KanbanContext, KanbanError> for UpdateTask {
                 .await
                 .map_err(KanbanError::from_entity_error)?;
 
+            let deps: Option<Vec<String>> = self
+                .depends_on
+                .as_ref()
+                .map(|deps| deps.iter().map(|d| d.as_str().to_string()).collect());
+            let parent = match &self.parent {
+                Some(Some(parent)) => Some(parent.as_str()),
+                _ => None,
+            };
+            check_task_links(&ectx, self.id.as_str(), deps.as_deref(), parent).await?;
+
             self.apply_to(&mut entity)?;
             // Tags are body markers, so they apply after `description` has
             // landed and need the async resolver — hence outside `apply_to`.
@@ -472,6 +538,91 @@ mod tests {
         assert!(dep_strs.contains(&id_b), "should contain task B");
     }
 
+    #[tokio::test]
+    async fn test_update_task_rejects_dependency_cycles() {
+        let (_temp, ctx) = setup().await;
+        let mut ids = Vec::new();
+        for title in ["Task A", "Task B", "Task C"] {
+            let added = AddTask::new(title)
+                .execute(&ctx)
+                .await
+                .into_result()
+                .unwrap();
+            ids.push(added["id"].as_str().unwrap().to_string());
+        }
+        let (a, b, c) = (ids[0].as_str(), ids[1].as_str(), ids[2].as_str());
+
+        // C waits on B, B waits on A.
+        for (task, dep) in [(c, b), (b, a)] {
+            UpdateTask::new(task)
+                .with_depends_on(vec![TaskId::from_string(dep)])
+                .execute(&ctx)
+                .await
+                .into_result()
+                .unwrap();
+        }
+
+        let result = UpdateTask::new(a)
+            .with_depends_on(vec![TaskId::from_string(c)])
+            .execute(&ctx)
+            .await
+            .into_result();
+        match result {
+            Err(KanbanError::DependencyCycle { path }) => {
+                assert_eq!(path, format!("{a} -> {c} -> {b} -> {a}"));
+            }
+            other => panic!("expected a dependency cycle, got {other:?}"),
+        }
+        let self_loop = UpdateTask::new(b)
+            .with_depends_on(vec![TaskId::from_string(b)])
+            .execute(&ctx)
+            .await
+            .into_result();
+        assert!(matches!(
+            self_loop,
+            Err(KanbanError::DependencyCycle { .. })
+        ));
+        assert_eq!(fetch(&ctx, a).await["depends_on"], serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_update_task_rejects_a_child_depending_on_its_parent() {
+        let (_temp, ctx) = setup().await;
+        let parent = AddTask::new("Parent")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap()["id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        let child = AddTask::new("Child")
+            .with_parent(parent.as_str())
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap()["id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+
+        let result = UpdateTask::new(child.as_str())
+            .with_depends_on(vec![TaskId::from_string(&parent)])
+            .execute(&ctx)
+            .await
+            .into_result();
+        match result {
+            Err(KanbanError::DependencyCycle { path }) => {
+                assert_eq!(path, format!("{child} -> {parent} -> {child}"));
+            }
+            other => panic!("expected a dependency cycle, got {other:?}"),
+        }
+        assert_eq!(
+            fetch(&ctx, &child).await["depends_on"],
+            serde_json::json!([])
+        );
+    }
+
     // -----------------------------------------------------------------------
     // `tags` replacement tests
     // -----------------------------------------------------------------------
@@ -922,4 +1073,38 @@ mod tests {
             );
         }
     }
+
+    #[tokio::test]
+    async fn test_update_task_sets_and_clears_recurrence() {
+        let (_temp, ctx) = setup().await;
+        let add = AddTask::new("Weekly audit")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        let id = add["id"].as_str().unwrap();
+
+        UpdateTask::new(id)
+            .with_recurrence("RRULE:FREQ=WEEKLY;BYDAY=mo")
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(fetch(&ctx, id).await["recurrence"], "FREQ=WEEKLY;BYDAY=MO");
+
+        let result = UpdateTask::new(id)
+            .with_recurrence("every tuesday")
+            .execute(&ctx)
+            .await
+            .into_result();
+        assert!(matches!(result, Err(KanbanError::InvalidValue { .. })));
+
+        UpdateTask::new(id)
+            .clear_recurrence()
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        assert!(fetch(&ctx, id).await["recurrence"].is_null());
+    }
 }
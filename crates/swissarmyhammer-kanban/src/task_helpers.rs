@@ -12,7 +12,7 @@ use std::collections::{HashMap, HashSet};
 use swissarmyhammer_entity::{Entity, EntityFilterContext};
 
 use crate::types::{resolve_short_ref, Ordinal, ResolveResult, TaskId};
-use crate::virtual_tags::{TerminalColumnId, VirtualTagRegistry};
+use crate::virtual_tags::{ColumnWipLimits, TerminalColumnId, VirtualTagRegistry};
 
 /// Generate a default title for a new task.
 ///
@@ -135,12 +135,39 @@ pub fn parse_checklist_counts(text: &str) -> (usize, usize) {
     (total, completed)
 }
 
-/// Check if all dependencies are complete (in the given terminal column).
+/// The task's parent id, if it is a subtask.
+pub fn task_parent(entity: &Entity) -> Option<&str> {
+    entity.get_str("parent").filter(|p| !p.is_empty())
+}
+
+/// Find the task IDs whose `parent` is `task_id`.
+pub fn child_task_ids(task_id: &str, all_tasks: &[Entity]) -> Vec<String> {
+    all_tasks
+        .iter()
+        .filter(|t| task_parent(t) == Some(task_id))
+        .map(|t| t.id.to_string())
+        .collect()
+}
+
+/// The tasks that must be done before this one: its `depends_on` list
+/// followed by its children, since a parent waits on its subtasks.
+pub fn task_prerequisites(entity: &Entity, all_tasks: &[Entity]) -> Vec<String> {
+    let mut prerequisites = entity.get_string_list("depends_on");
+    for child in child_task_ids(entity.id.as_ref(), all_tasks) {
+        if !prerequisites.contains(&child) {
+            prerequisites.push(child);
+        }
+    }
+    prerequisites
+}
+
+/// Check if all prerequisites (dependencies and children) are complete (in
+/// the given terminal column).
 ///
 /// A missing dependency (ID not found in `all_tasks`) is treated as incomplete,
 /// making the task not ready. This matches the semantics in [`ReadyStrategy`].
 pub fn task_is_ready(entity: &Entity, all_tasks: &[Entity], terminal_column_id: &str) -> bool {
-    let deps = entity.get_string_list("depends_on");
+    let deps = task_prerequisites(entity, all_tasks);
     deps.iter().all(|dep_id| {
         all_tasks
             .iter()
@@ -150,7 +177,8 @@ pub fn task_is_ready(entity: &Entity, all_tasks: &[Entity], terminal_column_id:
     })
 }
 
-/// Get task IDs that this task is blocked by (incomplete dependencies).
+/// Get task IDs that this task is blocked by (incomplete dependencies and
+/// incomplete children).
 ///
 /// A missing dependency (ID not found in `all_tasks`) is included in the
 /// result, treating it as blocking. This matches the semantics in
@@ -160,7 +188,7 @@ pub fn task_blocked_by(
     all_tasks: &[Entity],
     terminal_column_id: &str,
 ) -> Vec<String> {
-    let deps = entity.get_string_list("depends_on");
+    let deps = task_prerequisites(entity, all_tasks);
     deps.into_iter()
         .filter(|dep_id| {
             all_tasks
@@ -172,15 +200,43 @@ pub fn task_blocked_by(
         .collect()
 }
 
-/// Get task IDs that depend on this task.
+/// Get task IDs that depend on this task, plus its parent, which waits on it.
 ///
-/// Thin wrapper around [`find_dependent_task_ids`] that accepts an [`Entity`]
-/// for the common case where the caller already holds one. For cases where
-/// only a bare task id is available (e.g. fan-out after a delete when the
-/// entity is no longer in `all_tasks`), call [`find_dependent_task_ids`]
-/// directly.
+/// Wraps [`find_dependent_task_ids`] for the common case where the caller
+/// already holds an [`Entity`]. For cases where only a bare task id is
+/// available (e.g. fan-out after a delete when the entity is no longer in
+/// `all_tasks`), call [`find_dependent_task_ids`] directly.
 pub fn task_blocks(entity: &Entity, all_tasks: &[Entity]) -> Vec<String> {
-    find_dependent_task_ids(entity.id.as_ref(), all_tasks)
+    let mut blocks = find_dependent_task_ids(entity.id.as_ref(), all_tasks);
+    if let Some(parent) = task_parent(entity) {
+        if !blocks.iter().any(|b| b == parent) {
+            blocks.push(parent.to_string());
+        }
+    }
+    blocks
+}
+
+/// Fold the task's children into its `progress` field: each child counts as
+/// one item, completed when it sits in the terminal column, alongside the
+/// body's checklist items.
+fn roll_up_child_progress(entity: &mut Entity, children: usize, children_done: usize) {
+    if children == 0 {
+        return;
+    }
+    let progress = entity.get("progress");
+    let count = |key: &str| {
+        progress
+            .and_then(|p| p.get(key))
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0) as usize
+    };
+    let total = count("total") + children;
+    let completed = count("completed") + children_done;
+    let percent = (completed as f64 / total as f64 * 100.0).round() as u32;
+    entity.set(
+        "progress",
+        json!({ "total": total, "completed": completed, "percent": percent }),
+    );
 }
 
 /// Find the task IDs whose `depends_on` list currently contains `task_id`.
@@ -225,23 +281,60 @@ pub fn set_task_short_id(entity: &mut Entity) {
 ///
 /// Enriches the raw entity with computed dependency-graph and progress data:
 /// - `short_id`: derived 7-char short handle (see [`set_task_short_id`])
-/// - `progress_fraction`: scalar 0.0–1.0 derived from checklist progress
-/// - `ready`: true when all dependencies are in the terminal column
-/// - `blocked_by`: list of incomplete dependency task IDs
-/// - `blocks`: list of task IDs that depend on this task
+/// - `children`: IDs of the tasks whose `parent` is this task
+/// - `progress_fraction`: scalar 0.0–1.0 derived from checklist progress,
+///   with each child counted as one more item
+/// - `ready`: true when all dependencies and children are in the terminal
+///   column
+/// - `blocked_by`: list of incomplete dependency and child task IDs
+/// - `blocks`: list of task IDs that depend on this task, plus its parent
 ///
 /// Tags and raw progress are already populated by `ComputeEngine` during read;
 /// this function adds the higher-level computed fields that require the full
 /// task list for DAG analysis.
+///
+/// Column WIP limits are not known here, so `OVER_WIP` never matches; use
+/// [`enrich_task_entity_with_wip_limits`] when the columns are at hand.
 pub fn enrich_task_entity(
     entity: &mut Entity,
     all_tasks: &[Entity],
     terminal_column_id: &str,
     registry: &VirtualTagRegistry,
+) {
+    enrich_task_entity_with_wip_limits(
+        entity,
+        all_tasks,
+        terminal_column_id,
+        &ColumnWipLimits::default(),
+        registry,
+    );
+}
+
+/// [`enrich_task_entity`] with per-column WIP limits made available to the
+/// virtual tag strategies (see [`ColumnWipLimits::from_columns`]).
+pub fn enrich_task_entity_with_wip_limits(
+    entity: &mut Entity,
+    all_tasks: &[Entity],
+    terminal_column_id: &str,
+    wip_limits: &ColumnWipLimits,
+    registry: &VirtualTagRegistry,
 ) {
     // Derived short handle — display + input only, never stored.
     set_task_short_id(entity);
 
+    // children, rolled up into progress
+    let children = child_task_ids(entity.id.as_ref(), all_tasks);
+    let children_done = children
+        .iter()
+        .filter(|id| {
+            all_tasks.iter().any(|t| {
+                t.id == id.as_str() && t.get_str("position_column") == Some(terminal_column_id)
+            })
+        })
+        .count();
+    roll_up_child_progress(entity, children.len(), children_done);
+    entity.set("children", json!(children));
+
     // progress as a scalar fraction (the progress field from ComputeEngine is {total, completed, percent})
     let progress = task_progress(entity);
     entity.set("progress_fraction", json!(progress));
@@ -261,6 +354,7 @@ pub fn enrich_task_entity(
     // virtual tags: evaluate strategies against this entity
     let mut vtag_ctx = EntityFilterContext::for_entity(entity, all_tasks);
     vtag_ctx.insert(TerminalColumnId(terminal_column_id.to_string()));
+    vtag_ctx.insert(wip_limits.clone());
     let virtual_slugs = registry.evaluate(&vtag_ctx);
     entity.set("virtual_tags", json!(virtual_slugs));
 
@@ -282,26 +376,36 @@ struct DependencyIndexes {
     blocks: HashMap<String, Vec<String>>,
     /// task_id -> list of dep_ids it depends on
     depends_on: HashMap<String, Vec<String>>,
+    /// parent task_id -> list of its child task_ids
+    children: HashMap<String, Vec<String>>,
     /// task_id -> position_column value
     positions: HashMap<String, String>,
     /// Lightweight entity stubs for virtual tag evaluation.
     ///
     /// Strategies need an immutable `&[Entity]` while we mutate each entity in
-    /// the enrichment loop, but they only read `id`, `position_column`, and
-    /// `depends_on` from the slice. Stubs contain only those fields, avoiding
+    /// the enrichment loop, but they only read `id`, `position_column`,
+    /// `depends_on` and `parent` from the slice. Stubs contain only those fields, avoiding
     /// full clones of large descriptions.
     stubs: Vec<Entity>,
 }
 
-/// Build dependency, position, and stub indexes from a task entity slice.
+/// Build dependency, hierarchy, position, and stub indexes from a task entity
+/// slice.
 ///
 /// Returns indexes that enable O(1) per-task lookups for blocks, depends_on,
-/// position columns, and virtual tag evaluation during batch enrichment.
+/// children, position columns, and virtual tag evaluation during batch enrichment.
 fn build_dependency_indexes(entities: &[Entity]) -> DependencyIndexes {
     let mut blocks: HashMap<String, Vec<String>> = HashMap::new();
     let mut depends_on: HashMap<String, Vec<String>> = HashMap::new();
+    let mut children: HashMap<String, Vec<String>> = HashMap::new();
 
     for entity in entities.iter() {
+        if let Some(parent) = task_parent(entity) {
+            children
+                .entry(parent.to_string())
+                .or_default()
+                .push(entity.id.to_string());
+        }
         let deps = entity.get_string_list("depends_on");
         for dep_id in &deps {
             blocks
@@ -332,6 +436,9 @@ fn build_dependency_indexes(entities: &[Entity]) -> DependencyIndexes {
             if let Some(deps) = e.get("depends_on") {
                 stub.set("depends_on", deps.clone());
             }
+            if let Some(parent) = e.get("parent") {
+                stub.set("parent", parent.clone());
+            }
             stub
         })
         .collect();
@@ -339,6 +446,7 @@ fn build_dependency_indexes(entities: &[Entity]) -> DependencyIndexes {
     DependencyIndexes {
         blocks,
         depends_on,
+        children,
         positions,
         stubs,
     }
@@ -346,24 +454,39 @@ fn build_dependency_indexes(entities: &[Entity]) -> DependencyIndexes {
 
 /// Enrich a single task entity using pre-built indexes.
 ///
-/// Sets short_id, progress_fraction, ready, blocked_by, blocks, virtual_tags,
-/// and filter_tags fields. Uses the indexes for O(1) dependency lookups
+/// Sets short_id, children, progress_fraction, ready, blocked_by, blocks,
+/// virtual_tags, and filter_tags fields. Uses the indexes for O(1) dependency lookups
 /// instead of scanning the full task list.
 fn enrich_task_from_indexes(
     entity: &mut Entity,
     indexes: &DependencyIndexes,
     terminal_column_id: &str,
+    wip_limits: &ColumnWipLimits,
     registry: &VirtualTagRegistry,
 ) {
     set_task_short_id(entity);
+    let id = entity.id.to_string();
+    let is_done = |task_id: &String| {
+        indexes
+            .positions
+            .get(task_id)
+            .is_some_and(|col| col == terminal_column_id)
+    };
+
+    // Children, rolled up into progress
+    let children = indexes.children.get(&id).cloned().unwrap_or_default();
+    let children_done = children.iter().filter(|c| is_done(c)).count();
+    roll_up_child_progress(entity, children.len(), children_done);
+    entity.set("children", json!(children));
     entity.set("progress_fraction", json!(task_progress(entity)));
 
-    // Ready: all deps in terminal column
-    let deps = indexes
-        .depends_on
-        .get(&entity.id.to_string())
-        .cloned()
-        .unwrap_or_default();
+    // Ready: all deps and children in terminal column
+    let mut deps = indexes.depends_on.get(&id).cloned().unwrap_or_default();
+    for child in children {
+        if !deps.contains(&child) {
+            deps.push(child);
+        }
+    }
     let blocked_by: Vec<String> = deps
         .iter()
         .filter(|dep_id| {
@@ -378,17 +501,19 @@ fn enrich_task_from_indexes(
     entity.set("ready", json!(blocked_by.is_empty()));
     entity.set("blocked_by", json!(blocked_by));
 
-    // Blocks: tasks that depend on this one
-    let blocks = indexes
-        .blocks
-        .get(&entity.id.to_string())
-        .cloned()
-        .unwrap_or_default();
+    // Blocks: tasks that depend on this one, and the parent
+    let mut blocks = indexes.blocks.get(&id).cloned().unwrap_or_default();
+    if let Some(parent) = task_parent(entity) {
+        if !blocks.iter().any(|b| b == parent) {
+            blocks.push(parent.to_string());
+        }
+    }
     entity.set("blocks", json!(blocks));
 
     // Virtual tags: evaluate strategies against lightweight stubs
     let mut vtag_ctx = EntityFilterContext::for_entity(entity, &indexes.stubs);
     vtag_ctx.insert(TerminalColumnId(terminal_column_id.to_string()));
+    vtag_ctx.insert(wip_limits.clone());
     let virtual_slugs = registry.evaluate(&vtag_ctx);
     entity.set("virtual_tags", json!(virtual_slugs));
 
@@ -410,14 +535,33 @@ fn enrich_task_from_indexes(
 /// which would be O(N^2) because each call scans all tasks for dependency
 /// lookups. This function pre-builds `blocks` and `depends_on` indexes so
 /// the per-task enrichment is O(1).
+///
+/// Like [`enrich_task_entity`], this leaves `OVER_WIP` unevaluated; use
+/// [`enrich_all_task_entities_with_wip_limits`] when the columns are at hand.
 pub fn enrich_all_task_entities(
     entities: &mut [Entity],
     terminal_column_id: &str,
     registry: &VirtualTagRegistry,
+) {
+    enrich_all_task_entities_with_wip_limits(
+        entities,
+        terminal_column_id,
+        &ColumnWipLimits::default(),
+        registry,
+    );
+}
+
+/// [`enrich_all_task_entities`] with per-column WIP limits made available to
+/// the virtual tag strategies (see [`ColumnWipLimits::from_columns`]).
+pub fn enrich_all_task_entities_with_wip_limits(
+    entities: &mut [Entity],
+    terminal_column_id: &str,
+    wip_limits: &ColumnWipLimits,
+    registry: &VirtualTagRegistry,
 ) {
     let indexes = build_dependency_indexes(entities);
     for entity in entities.iter_mut() {
-        enrich_task_from_indexes(entity, &indexes, terminal_column_id, registry);
+        enrich_task_from_indexes(entity, &indexes, terminal_column_id, wip_limits, registry);
     }
 }
 
@@ -483,6 +627,7 @@ pub fn task_entity_to_json(entity: &Entity) -> Value {
         "tags": tags,
         "assignees": entity.get_string_list("assignees"),
         "depends_on": entity.get_string_list("depends_on"),
+        "parent": task_parent(entity),
         "project": entity.get_str("project").unwrap_or(""),
         "progress": progress,
     });
@@ -503,13 +648,20 @@ pub fn task_entity_to_json(entity: &Entity) -> Value {
 
     include_date_fields(entity, &mut result);
 
+    // Recurrence rule and the links between successive instances (null when
+    // unset), see `crate::task::RecurrenceRule`.
+    for name in ["recurrence", "recurred_from", "recurred_as"] {
+        result[name] = entity.get(name).cloned().unwrap_or(Value::Null);
+    }
+
     result
 }
 
 /// Convert a pre-enriched task Entity to JSON with computed fields.
 ///
-/// Reads `ready`, `blocked_by`, `blocks`, `virtual_tags`, and `filter_tags`
-/// from fields already set by `enrich_task_entity` / `enrich_all_task_entities`.
+/// Reads `ready`, `blocked_by`, `blocks`, `children`, `virtual_tags`, and
+/// `filter_tags` from fields already set by `enrich_task_entity` /
+/// `enrich_all_task_entities`.
 /// Callers must enrich the entity before calling this function.
 pub fn task_entity_to_rich_json(entity: &Entity) -> Value {
     let mut result = task_entity_to_json(entity);
@@ -517,6 +669,7 @@ pub fn task_entity_to_rich_json(entity: &Entity) -> Value {
     result["ready"] = entity.get("ready").cloned().unwrap_or(json!(true));
     result["blocked_by"] = json!(entity.get_string_list("blocked_by"));
     result["blocks"] = json!(entity.get_string_list("blocks"));
+    result["children"] = json!(entity.get_string_list("children"));
     result["virtual_tags"] = json!(entity.get_string_list("virtual_tags"));
     result["filter_tags"] = json!(entity.get_string_list("filter_tags"));
 
@@ -527,7 +680,7 @@ pub fn task_entity_to_rich_json(entity: &Entity) -> Value {
 ///
 /// An explicit ALLOWLIST — roughly what a board card renders: identity,
 /// position, organization (project/tags/assignees), progress, the dependency
-/// fields, readiness, and the date fields. Heavy payload fields
+/// and parent/child fields, readiness, and the date fields. Heavy payload fields
 /// (`description`, `attachments`, and any future conversation log such as
 /// `comments`) are deliberately absent, so a new heavy field is excluded
 /// from listings by default rather than leaking in.
@@ -545,6 +698,8 @@ pub(crate) const SLIM_TASK_FIELDS: &[&str] = &[
     "depends_on",
     "blocked_by",
     "blocks",
+    "parent",
+    "children",
     "ready",
     "created",
     "updated",
@@ -552,6 +707,7 @@ pub(crate) const SLIM_TASK_FIELDS: &[&str] = &[
     "scheduled",
     "started",
     "completed",
+    "recurrence",
 ];
 
 /// Project an enriched task JSON value down to the slim listing shape.
@@ -602,8 +758,9 @@ pub(crate) fn assert_task_mutation_ack(result: &Value, expected_id: &str) {
 ///
 /// Test-only helper for ops whose envelope is the identity ack extended with
 /// op-specific keys: `delete task` (`deleted`), `archive task` (`archived`),
-/// `unarchive task` (`unarchived`), and the clipboard ops `cut` / `copy`
-/// (`cut`/`copied` + `clipboard_json`). The key set is asserted exactly so
+/// `unarchive task` (`unarchived`), `complete task` on a recurring task
+/// (`recurred_as`), and the clipboard ops `cut` / `copy` (`cut`/`copied` +
+/// `clipboard_json`). The key set is asserted exactly so
 /// stray echo fields cannot creep back in.
 #[cfg(test)]
 pub(crate) fn assert_task_mutation_ack_with(
@@ -772,6 +929,12 @@ fn build_slug_index(entities: &[Entity], display_field: &str) -> HashMap<String,
 /// on the slug of the referenced entity's display name. The latter requires
 /// a `registry` populated from the actor/project/task lists; passing the
 /// empty registry falls back to id-only matching (backwards compatible).
+///
+/// Field predicates (`due < today`, `has:attachments`) read the named task
+/// field, with a few conveniences: `column` is `position_column`, `tag`/`tags`
+/// include virtual tags, `assignee` is `assignees`, `description` is `body`,
+/// and `progress` is the checklist completion percentage (0–100), absent for
+/// tasks without a checklist.
 pub struct TaskFilterAdapter<'a> {
     /// The enriched task entity to evaluate against.
     pub entity: &'a Entity,
@@ -903,6 +1066,43 @@ impl<'a> swissarmyhammer_filter_expr::FilterContext for TaskFilterAdapter<'a> {
         }
         false
     }
+
+    fn field_values(&self, field: &str) -> Vec<String> {
+        let field = field.to_ascii_lowercase();
+        let name = match field.as_str() {
+            "column" => "position_column",
+            "tag" | "tags" => "filter_tags",
+            "assignee" => "assignees",
+            "description" => "body",
+            "progress" => {
+                let has_checklist = self
+                    .entity
+                    .get("progress")
+                    .and_then(|p| p.get("total"))
+                    .and_then(|t| t.as_u64())
+                    .is_some_and(|total| total > 0);
+                return if has_checklist {
+                    vec![(task_progress(self.entity) * 100.0).round().to_string()]
+                } else {
+                    Vec::new()
+                };
+            }
+            other => other,
+        };
+        self.entity.get(name).map(field_texts).unwrap_or_default()
+    }
+}
+
+/// Flatten a stored field value into the text values a filter compares:
+/// one per list item, none for null or empty text.
+fn field_texts(value: &Value) -> Vec<String> {
+    match value {
+        Value::Null => Vec::new(),
+        Value::String(s) if s.trim().is_empty() => Vec::new(),
+        Value::String(s) => vec![s.clone()],
+        Value::Array(items) => items.iter().flat_map(field_texts).collect(),
+        other => vec![other.to_string()],
+    }
 }
 
 /// Apply a filter DSL expression to a slice of enriched task entities and
@@ -936,7 +1136,7 @@ pub fn filter_task_ids(
         return Ok(entities.iter().map(|e| e.id.to_string()).collect());
     }
     let expr = swissarmyhammer_filter_expr::parse(filter_str).map_err(|errors| {
-        let msgs: Vec<String> = errors.iter().map(|e| e.message.clone()).collect();
+        let msgs: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
         format!("invalid filter expression: {}", msgs.join("; "))
     })?;
     let ids = entities
@@ -967,7 +1167,7 @@ pub fn retain_filtered_tasks(
         return Ok(());
     }
     let expr = swissarmyhammer_filter_expr::parse(filter_str).map_err(|errors| {
-        let msgs: Vec<String> = errors.iter().map(|e| e.message.clone()).collect();
+        let msgs: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
         format!("invalid filter expression: {}", msgs.join("; "))
     })?;
     entities.retain(|e| {
@@ -1915,6 +2115,34 @@ mod tests {
         assert!(!adapter.has_project("frontend"));
     }
 
+    #[test]
+    fn test_task_filter_adapter_field_predicates() {
+        let mut e = make_task_computed("t1", "Fix OAuth login", "", "doing", vec!["bug"], 4, 3);
+        e.set("due", json!("2026-03-05"));
+        e.set("filter_tags", json!(["bug", "READY"]));
+        let adapter = TaskFilterAdapter::new(&e);
+        let matches = |filter: &str| {
+            swissarmyhammer_filter_expr::parse(filter)
+                .unwrap()
+                .matches(&adapter)
+        };
+
+        assert!(matches("column = doing"));
+        assert!(matches("title ~ auth"));
+        assert!(matches("tags = ready"));
+        assert!(matches("progress >= 75%"));
+        assert!(!matches("progress > 75%"));
+        assert!(matches("due < 2026-03-06 && due >= 2026-03-05"));
+        assert!(matches("has:due && !has:attachments"));
+
+        // No checklist: progress is absent rather than 0%.
+        let plain = make_task("t2", "Plain", "", "todo");
+        let adapter = TaskFilterAdapter::new(&plain);
+        assert!(!swissarmyhammer_filter_expr::parse("progress < 50%")
+            .unwrap()
+            .matches(&adapter));
+    }
+
     // ─────────────────────────────────────────────────────────────────
     // EntitySlugRegistry + TaskFilterAdapter id-or-slug matching tests
     //
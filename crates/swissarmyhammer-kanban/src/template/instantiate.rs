@@ -0,0 +1,135 @@
+//! Creating tasks from a template
+
+use super::library::find_task_template;
+use crate::column::enforce_wip_limit;
+use crate::context::KanbanContext;
+use crate::error::{KanbanError, Result};
+use crate::task::AddTask;
+use crate::task_helpers::{slim_task_json, task_entity_to_json};
+use crate::types::TaskId;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Add the task `cmd` describes, pre-filled from the template `name`.
+///
+/// The template supplies defaults: its tags and assignees are added to the
+/// caller's, its project and column apply only where the caller set none, and
+/// its rendered body comes before any description the caller gave. With
+/// `cmd.subtasks` left on, each subtask stub is created as a subtask of the
+/// task, which then depends on all of them.
+///
+/// Every write happens inside one undo group, so a single undo removes the
+/// task and its subtasks together; a failure part-way rolls the group back.
+pub(crate) async fn add_from_template(
+    cmd: &AddTask,
+    ctx: &KanbanContext,
+    name: &str,
+) -> Result<Value> {
+    let template = find_task_template(ctx.root(), name)?;
+    let args = template.arguments(&cmd.title, &cmd.params.clone().unwrap_or_default())?;
+
+    let title = match &template.title {
+        Some(pattern) => template.render(pattern, &args)?,
+        None => cmd.title.trim().to_string(),
+    };
+    if title.is_empty() {
+        return Err(KanbanError::missing_field("title"));
+    }
+
+    let body = template.render(&template.body, &args)?;
+    let description = match cmd.description.as_deref().map(str::trim) {
+        Some(extra) if !extra.is_empty() && !body.is_empty() => format!("{body}\n\n{extra}"),
+        Some(extra) if !extra.is_empty() => extra.to_string(),
+        _ => body,
+    };
+
+    let mut task = AddTask::new(title);
+    task.description = Some(description).filter(|d| !d.is_empty());
+    task.column = cmd.column.clone().or_else(|| template.column.clone());
+    task.ordinal = cmd.ordinal.clone();
+    task.project = cmd.project.clone().or_else(|| template.project.clone());
+    task.assignees = union(&template.assignees, &cmd.assignees);
+    task.tags = union(&template.tags, &cmd.tags);
+    task.depends_on = cmd.depends_on.clone();
+    task.parent = cmd.parent.clone();
+    task.due = cmd.due.clone();
+    task.scheduled = cmd.scheduled.clone();
+    task.recurrence = cmd.recurrence.clone();
+    task.override_wip_limit = cmd.override_wip_limit;
+
+    let ectx = ctx.entity_context().await?;
+    let store = ectx.store_context().cloned();
+    let depth = match &store {
+        Some(sc) => sc.undo_depth().await,
+        None => 0,
+    };
+    let outcome: Result<Value> = async {
+        let _undo_group = match &store {
+            Some(sc) => Some(sc.begin_undo_group().await),
+            None => None,
+        };
+
+        let mut entity = task.build_entity(&ectx).await?;
+        let column = entity.get_str("position_column").unwrap_or_default();
+        enforce_wip_limit(&ectx, column, None, task.override_wip_limit).await?;
+        task.persist(&ectx, &entity).await?;
+
+        // Subtasks are written after the task so their `parent` reference
+        // exists when it is checked; the task then depends on all of them.
+        let mut subtasks = Vec::new();
+        if cmd.subtasks.unwrap_or(true) && !template.subtasks.is_empty() {
+            for stub in &template.subtasks {
+                let mut subtask = AddTask::new(template.render(&stub.title, &args)?)
+                    .with_parent(entity.id.as_str());
+                if let Some(pattern) = &stub.description {
+                    subtask.description = Some(template.render(pattern, &args)?);
+                }
+                subtask.project = task.project.clone();
+                subtask.assignees = stub.assignees.clone();
+                subtask.tags = stub.tags.clone();
+                subtask.override_wip_limit = cmd.override_wip_limit;
+
+                let sub = subtask.build_entity(&ectx).await?;
+                let column = sub.get_str("position_column").unwrap_or_default();
+                enforce_wip_limit(&ectx, column, None, subtask.override_wip_limit).await?;
+                subtask.persist(&ectx, &sub).await?;
+                task.depends_on.push(TaskId::from_string(sub.id.as_str()));
+                subtasks.push(slim_task_json(&task_entity_to_json(&sub)));
+            }
+            entity.set("depends_on", serde_json::to_value(&task.depends_on)?);
+            ectx.write(&entity).await?;
+        }
+
+        let mut result = slim_task_json(&task_entity_to_json(&entity));
+        result["template"] = json!(template.name);
+        if !subtasks.is_empty() {
+            result["subtasks"] = json!(subtasks);
+        }
+        Ok(result)
+    }
+    .await;
+
+    // A failure part-way leaves the writes made so far in the group; undo it
+    // so no half-created task or orphaned subtask stays on the board.
+    if outcome.is_err() {
+        if let Some(sc) = &store {
+            if sc.undo_depth().await > depth {
+                if let Err(undo_error) = sc.undo().await {
+                    tracing::warn!(%undo_error, "failed to roll back partial templated add");
+                }
+            }
+        }
+    }
+    outcome
+}
+
+/// `base` followed by the entries of `extra` it does not already contain.
+fn union<T: Clone + PartialEq>(base: &[T], extra: &[T]) -> Vec<T> {
+    let mut all = base.to_vec();
+    for item in extra {
+        if !all.contains(item) {
+            all.push(item.clone());
+        }
+    }
+    all
+}
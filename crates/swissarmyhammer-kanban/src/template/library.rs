@@ -0,0 +1,286 @@
+//! Template definitions and the layered template library
+
+use crate::error::{KanbanError, Result};
+use crate::types::ActorId;
+use include_dir::{include_dir, Dir};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use swissarmyhammer_directory::{
+    DirectoryConfig, FileEntry, FileSource, SwissarmyhammerConfig, VirtualFileSystem,
+};
+use swissarmyhammer_templating::{parse_frontmatter, Template};
+
+/// Builtin task templates, embedded at compile time.
+static BUILTIN_TEMPLATES: Dir = include_dir!("$CARGO_MANIFEST_DIR/builtin/templates");
+
+/// Subdirectory of `~/.sah` and of the board's `.kanban` directory that
+/// holds task templates.
+const TEMPLATES_DIR: &str = "templates";
+
+/// A value a template asks for when it is instantiated.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TemplateParameter {
+    /// Name the template refers to, as in `{{ component }}`
+    pub name: String,
+    /// What the value is for
+    pub description: Option<String>,
+    /// Value used when the caller supplies none
+    pub default: Option<String>,
+    /// Instantiation fails when this is set and no value is supplied
+    pub required: bool,
+}
+
+/// A stub subtask created under the templated task, which then depends on it.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SubtaskTemplate {
+    /// Liquid pattern for the subtask title
+    pub title: String,
+    /// Liquid pattern for the subtask description
+    pub description: Option<String>,
+    /// Tags applied to the subtask
+    pub tags: Vec<String>,
+    /// Actors assigned to the subtask
+    pub assignees: Vec<ActorId>,
+}
+
+/// A task template: defaults and Liquid patterns for a new task.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TaskTemplate {
+    /// Template name, taken from the file stem
+    #[serde(skip_deserializing)]
+    pub name: String,
+    /// One-line summary shown by `list templates`
+    pub description: Option<String>,
+    /// Liquid pattern for the task title; the caller's title is `{{ title }}`.
+    /// Without one the caller's title is used as given.
+    pub title: Option<String>,
+    /// Tags applied to the task
+    pub tags: Vec<String>,
+    /// Project the task belongs to
+    pub project: Option<String>,
+    /// Actors assigned to the task
+    pub assignees: Vec<ActorId>,
+    /// Column the task starts in
+    pub column: Option<String>,
+    /// Values the patterns refer to
+    pub parameters: Vec<TemplateParameter>,
+    /// Stub subtasks created under the task, which then depends on them
+    pub subtasks: Vec<SubtaskTemplate>,
+    /// Liquid pattern for the task body; the Markdown after the frontmatter
+    pub body: String,
+    /// Layer the template was loaded from: builtin, user or local
+    #[serde(skip_deserializing)]
+    pub source: String,
+}
+
+impl TaskTemplate {
+    /// Parse a template file. Markdown files carry their fields as YAML
+    /// frontmatter and their body after it; YAML files hold everything,
+    /// the body under a `body` key.
+    pub fn parse(name: &str, path: &Path, content: &str) -> Result<Self> {
+        let markdown = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| matches!(ext, "md" | "markdown" | "liquid"));
+        let invalid = |e: &dyn std::fmt::Display| {
+            KanbanError::parse(format!("template '{name}' ({}): {e}", path.display()))
+        };
+
+        let mut template: TaskTemplate = if markdown {
+            let parsed = parse_frontmatter(content).map_err(|e| invalid(&e))?;
+            let mut template: TaskTemplate = match parsed.metadata {
+                Some(metadata) => serde_json::from_value(metadata).map_err(|e| invalid(&e))?,
+                None => TaskTemplate::default(),
+            };
+            template.body = parsed.content;
+            template
+        } else {
+            serde_yaml_ng::from_str(content).map_err(|e| invalid(&e))?
+        };
+        template.name = name.to_string();
+        Ok(template)
+    }
+
+    /// Build the render arguments for one instantiation: the caller's
+    /// `title`, every supplied parameter, and defaults for the rest.
+    ///
+    /// A supplied name the template does not declare is rejected, as is a
+    /// required parameter left without a value.
+    pub fn arguments(
+        &self,
+        title: &str,
+        params: &HashMap<String, String>,
+    ) -> Result<HashMap<String, String>> {
+        if let Some(unknown) = params
+            .keys()
+            .find(|key| !self.parameters.iter().any(|p| &p.name == *key))
+        {
+            return Err(KanbanError::invalid_value(
+                "params",
+                format!("template '{}' has no parameter '{unknown}'", self.name),
+            ));
+        }
+
+        let mut args = HashMap::new();
+        for param in &self.parameters {
+            let value = params
+                .get(&param.name)
+                .filter(|v| !v.trim().is_empty())
+                .or(param.default.as_ref());
+            match value {
+                Some(value) => {
+                    args.insert(param.name.clone(), value.clone());
+                }
+                None if param.required => {
+                    return Err(KanbanError::invalid_value(
+                        "params",
+                        format!(
+                            "template '{}' requires parameter '{}'",
+                            self.name, param.name
+                        ),
+                    ));
+                }
+                None => {}
+            }
+        }
+        args.insert("title".to_string(), title.to_string());
+        Ok(args)
+    }
+
+    /// Render one of this template's Liquid patterns.
+    pub fn render(&self, pattern: &str, args: &HashMap<String, String>) -> Result<String> {
+        Template::new_trusted(pattern)
+            .and_then(|t| t.render(args))
+            .map(|s| s.trim().to_string())
+            .map_err(|e| KanbanError::parse(format!("template '{}': {e}", self.name)))
+    }
+}
+
+/// Load every task template, sorted by name.
+///
+/// Layers are builtin, then `~/.sah/templates`, then `templates/` inside
+/// `kanban_root`; a template in a later layer replaces the one of the same
+/// name below it.
+pub fn load_task_templates(kanban_root: &Path) -> Result<Vec<TaskTemplate>> {
+    let mut vfs = VirtualFileSystem::<SwissarmyhammerConfig>::new(TEMPLATES_DIR);
+    for file in BUILTIN_TEMPLATES.files() {
+        let (Some(name), Some(content)) = (
+            file.path().file_stem().and_then(|s| s.to_str()),
+            file.contents_utf8(),
+        ) else {
+            continue;
+        };
+        vfs.add_file(FileEntry::new(
+            name,
+            PathBuf::from("builtin")
+                .join(TEMPLATES_DIR)
+                .join(file.path()),
+            content.to_string(),
+            FileSource::Builtin,
+        ));
+    }
+    // Templates are hand-written files like the rest of `~/.sah`, so they
+    // live there rather than under the XDG data directory. The path is only
+    // read: a missing directory is skipped, never created.
+    if let Some(home) = dirs::home_dir() {
+        let user_dir = home
+            .join(SwissarmyhammerConfig::DIR_NAME)
+            .join(TEMPLATES_DIR);
+        vfs.add_search_path(user_dir, FileSource::User);
+    }
+    vfs.add_search_path(kanban_root.join(TEMPLATES_DIR), FileSource::Local);
+    vfs.load_all()
+        .map_err(|e| KanbanError::parse(format!("failed to load task templates: {e}")))?;
+
+    let mut templates = vfs
+        .list()
+        .into_iter()
+        .map(|entry| {
+            let mut template = TaskTemplate::parse(&entry.name, &entry.path, &entry.content)?;
+            template.source = entry.source.to_string();
+            Ok(template)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(templates)
+}
+
+/// Load the template called `name`.
+pub(crate) fn find_task_template(kanban_root: &Path, name: &str) -> Result<TaskTemplate> {
+    load_task_templates(kanban_root)?
+        .into_iter()
+        .find(|t| t.name == name)
+        .ok_or_else(|| KanbanError::not_found("template", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_builtin_templates_load() {
+        let temp = TempDir::new().unwrap();
+        let templates = load_task_templates(temp.path()).unwrap();
+        let bugfix = templates.iter().find(|t| t.name == "bugfix").unwrap();
+        assert_eq!(bugfix.source, "builtin");
+        assert_eq!(bugfix.tags, vec!["bug"]);
+        assert!(bugfix.body.contains("- [ ] Reproduce"));
+        assert!(!bugfix.subtasks.is_empty());
+        assert!(templates.iter().any(|t| t.name == "feature"));
+    }
+
+    #[test]
+    fn test_local_template_overrides_builtin() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join(TEMPLATES_DIR);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("bugfix.yaml"),
+            "tags: [defect]\nbody: \"Fix {{ title }}\"\n",
+        )
+        .unwrap();
+
+        let bugfix = find_task_template(temp.path(), "bugfix").unwrap();
+        assert_eq!(bugfix.source, "local");
+        assert_eq!(bugfix.tags, vec!["defect"]);
+        assert!(bugfix.subtasks.is_empty());
+        let args = bugfix.arguments("crash", &HashMap::new()).unwrap();
+        assert_eq!(bugfix.render(&bugfix.body, &args).unwrap(), "Fix crash");
+
+        assert!(matches!(
+            find_task_template(temp.path(), "nope"),
+            Err(KanbanError::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_arguments_apply_defaults_and_check_names() {
+        let template = TaskTemplate::parse(
+            "t",
+            Path::new("t.md"),
+            "---\nparameters:\n  - name: component\n    required: true\n  - name: area\n    default: core\n---\n{{ component }}/{{ area }}: {{ title }}\n",
+        )
+        .unwrap();
+
+        let params = HashMap::from([("component".to_string(), "ui".to_string())]);
+        let args = template.arguments("Crash", &params).unwrap();
+        assert_eq!(
+            template.render(&template.body, &args).unwrap(),
+            "ui/core: Crash"
+        );
+
+        let missing = template.arguments("Crash", &HashMap::new());
+        assert!(matches!(missing, Err(KanbanError::InvalidValue { .. })));
+        let unknown = HashMap::from([
+            ("component".to_string(), "ui".to_string()),
+            ("colour".to_string(), "red".to_string()),
+        ]);
+        let unknown = template.arguments("Crash", &unknown);
+        assert!(matches!(unknown, Err(KanbanError::InvalidValue { .. })));
+    }
+}
@@ -0,0 +1,76 @@
+//! ListTemplates command
+
+use super::library::load_task_templates;
+use crate::context::KanbanContext;
+use crate::error::{KanbanError, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResult};
+
+/// List the task templates `add task` can instantiate, with the parameters
+/// each one takes and the layer it was loaded from.
+#[operation(
+    verb = "list",
+    noun = "templates",
+    description = "List task templates and their parameters"
+)]
+#[derive(Debug, Default, Deserialize)]
+pub struct ListTemplates;
+
+impl ListTemplates {
+    /// List every template.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl Execute<KanbanContext, KanbanError> for ListTemplates {
+    async fn execute(&self, ctx: &KanbanContext) -> ExecutionResult<Value, KanbanError> {
+        let result: Result<Value> = async {
+            let templates: Vec<Value> = load_task_templates(ctx.root())?
+                .into_iter()
+                .map(|t| {
+                    json!({
+                        "name": t.name,
+                        "description": t.description,
+                        "source": t.source,
+                        "tags": t.tags,
+                        "parameters": t.parameters,
+                        "subtasks": t.subtasks.len(),
+                    })
+                })
+                .collect();
+            Ok(json!({ "templates": templates, "count": templates.len() }))
+        }
+        .await;
+
+        match result {
+            Ok(value) => ExecutionResult::Success { value },
+            Err(error) => ExecutionResult::Failed { error },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_list_templates_includes_builtins() {
+        let temp = TempDir::new().unwrap();
+        let ctx = KanbanContext::new(temp.path().join(".kanban"));
+
+        let result = ListTemplates::new()
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        let templates = result["templates"].as_array().unwrap();
+        let bugfix = templates.iter().find(|t| t["name"] == "bugfix").unwrap();
+        assert_eq!(bugfix["parameters"][0]["name"], "component");
+        assert_eq!(bugfix["parameters"][0]["required"], true);
+        assert_eq!(bugfix["subtasks"], 2);
+    }
+}
@@ -0,0 +1,19 @@
+//! Task templates
+//!
+//! A template pre-fills a new task: a Liquid title pattern, tags, project,
+//! assignees, a checklist body, and stub subtasks the task depends on.
+//! Templates are Markdown files whose YAML frontmatter holds everything but
+//! the body (or plain YAML files with a `body` key), stacked like the rest of
+//! the sah file library: builtin, then `~/.sah/templates`, then the board's
+//! own `.kanban/templates`, later layers replacing earlier ones by name.
+//!
+//! `add task` with `template` set instantiates one; `list templates` shows
+//! what is available.
+
+mod instantiate;
+mod library;
+mod list;
+
+pub(crate) use instantiate::add_from_template;
+pub use library::{load_task_templates, SubtaskTemplate, TaskTemplate, TemplateParameter};
+pub use list::ListTemplates;
@@ -26,6 +26,11 @@ pub enum Verb {
     Rename,
     Archive,
     Unarchive,
+    Import,
+    Export,
+    Compact,
+    Promote,
+    Gc,
 }
 
 impl Verb {
@@ -49,6 +54,11 @@ impl Verb {
             Self::Rename => "rename",
             Self::Archive => "archive",
             Self::Unarchive => "unarchive",
+            Self::Import => "import",
+            Self::Export => "export",
+            Self::Compact => "compact",
+            Self::Promote => "promote",
+            Self::Gc => "gc",
         }
     }
 
@@ -72,6 +82,11 @@ impl Verb {
             "rename" => Some(Self::Rename),
             "archive" => Some(Self::Archive),
             "unarchive" | "restore" => Some(Self::Unarchive),
+            "import" => Some(Self::Import),
+            "export" => Some(Self::Export),
+            "compact" => Some(Self::Compact),
+            "promote" => Some(Self::Promote),
+            "gc" => Some(Self::Gc),
             _ => None,
         }
     }
@@ -104,7 +119,14 @@ pub enum Noun {
     Projects,
     Perspective,
     Perspectives,
+    Swimlanes,
     Archived,
+    Activity,
+    Graph,
+    Metrics,
+    Flow,
+    Templates,
+    Workspace,
 }
 
 impl Noun {
@@ -128,7 +150,14 @@ impl Noun {
             Self::Projects => "projects",
             Self::Perspective => "perspective",
             Self::Perspectives => "perspectives",
+            Self::Swimlanes => "swimlanes",
             Self::Archived => "archived",
+            Self::Activity => "activity",
+            Self::Graph => "graph",
+            Self::Metrics => "metrics",
+            Self::Flow => "flow",
+            Self::Templates => "templates",
+            Self::Workspace => "workspace",
         }
     }
 
@@ -152,7 +181,14 @@ impl Noun {
             "projects" => Some(Self::Projects),
             "perspective" => Some(Self::Perspective),
             "perspectives" => Some(Self::Perspectives),
+            "swimlanes" | "lanes" => Some(Self::Swimlanes),
             "archived" => Some(Self::Archived),
+            "activity" => Some(Self::Activity),
+            "graph" => Some(Self::Graph),
+            "metrics" => Some(Self::Metrics),
+            "flow" => Some(Self::Flow),
+            "templates" => Some(Self::Templates),
+            "workspace" => Some(Self::Workspace),
             _ => None,
         }
     }
@@ -268,6 +304,8 @@ pub fn is_valid_operation(verb: Verb, noun: Noun) -> bool {
         (verb, noun),
         // Board operations
         (Verb::Init, Noun::Board) | (Verb::Get, Noun::Board) | (Verb::Update, Noun::Board) |
+        (Verb::Import, Noun::Board) | (Verb::Export, Noun::Board) |
+        (Verb::Compact, Noun::Board) |
         // Column operations
         (Verb::Get, Noun::Column) | (Verb::Add, Noun::Column) | (Verb::Update, Noun::Column) |
         (Verb::Delete, Noun::Column) | (Verb::List, Noun::Columns) |
@@ -279,8 +317,13 @@ pub fn is_valid_operation(verb: Verb, noun: Noun) -> bool {
         (Verb::Move, Noun::Task) | (Verb::Delete, Noun::Task) | (Verb::Next, Noun::Task) |
         (Verb::Tag, Noun::Task) | (Verb::Untag, Noun::Task) | (Verb::Complete, Noun::Task) |
         (Verb::Assign, Noun::Task) | (Verb::Unassign, Noun::Task) |
+        // Checklist item → subtask
+        (Verb::Promote, Noun::Task) |
         // Tasks listing + relevance search
         (Verb::List, Noun::Tasks) | (Verb::Search, Noun::Tasks) |
+        // Bulk task changes selected by a filter expression
+        (Verb::Move, Noun::Tasks) | (Verb::Tag, Noun::Tasks) | (Verb::Assign, Noun::Tasks) |
+        (Verb::Archive, Noun::Tasks) | (Verb::Update, Noun::Tasks) |
         // Tag operations (board-level)
         (Verb::Get, Noun::Tag) | (Verb::Add, Noun::Tag) | (Verb::Update, Noun::Tag) |
         (Verb::Delete, Noun::Tag) | (Verb::List, Noun::Tags) |
@@ -290,14 +333,26 @@ pub fn is_valid_operation(verb: Verb, noun: Noun) -> bool {
         // Attachment operations
         (Verb::Add, Noun::Attachment) | (Verb::Get, Noun::Attachment) |
         (Verb::Update, Noun::Attachment) | (Verb::Delete, Noun::Attachment) |
-        (Verb::List, Noun::Attachments) |
+        (Verb::List, Noun::Attachments) | (Verb::Gc, Noun::Attachments) |
         // Project operations
         (Verb::Get, Noun::Project) | (Verb::Add, Noun::Project) | (Verb::Update, Noun::Project) |
         (Verb::Delete, Noun::Project) | (Verb::List, Noun::Projects) |
         // Perspective operations
         (Verb::Add, Noun::Perspective) | (Verb::Get, Noun::Perspective) |
         (Verb::Update, Noun::Perspective) | (Verb::Delete, Noun::Perspective) |
-        (Verb::List, Noun::Perspectives)
+        (Verb::List, Noun::Perspectives) |
+        // Swimlanes (board lanes grouped by a task field)
+        (Verb::List, Noun::Swimlanes) |
+        // Activity feed over entity changelogs
+        (Verb::List, Noun::Activity) |
+        // Task dependency graph analysis
+        (Verb::Get, Noun::Graph) |
+        // Flow metrics rebuilt from task changelogs
+        (Verb::Get, Noun::Metrics) | (Verb::Get, Noun::Flow) |
+        // Task templates for `add task --template`
+        (Verb::List, Noun::Templates) |
+        // Merged read-only view of several boards
+        (Verb::Get, Noun::Workspace)
     )
 }
 
@@ -9,7 +9,9 @@ use std::collections::HashMap;
 use std::sync::LazyLock;
 
 use serde::{Deserialize, Serialize};
-use swissarmyhammer_entity::EntityFilterContext;
+use swissarmyhammer_entity::{Entity, EntityFilterContext};
+
+use crate::task_helpers::{task_parent, task_prerequisites};
 
 /// Newtype for terminal column ID stored in [`EntityFilterContext`] extras.
 ///
@@ -17,6 +19,27 @@ use swissarmyhammer_entity::EntityFilterContext;
 /// which column represents "done".
 pub struct TerminalColumnId(pub String);
 
+/// Per-column WIP limits stored in [`EntityFilterContext`] extras.
+///
+/// Maps column ID to its limit; unlimited columns are absent. Strategies
+/// extract this via `ctx.get::<ColumnWipLimits>()`.
+#[derive(Debug, Clone, Default)]
+pub struct ColumnWipLimits(pub HashMap<String, usize>);
+
+impl ColumnWipLimits {
+    /// Collect the limits from a list of column entities.
+    pub fn from_columns(columns: &[Entity]) -> Self {
+        Self(
+            columns
+                .iter()
+                .filter_map(|c| {
+                    crate::column::column_wip_limit(c).map(|limit| (c.id.to_string(), limit))
+                })
+                .collect(),
+        )
+    }
+}
+
 /// A command declared by a virtual tag strategy.
 ///
 /// Carries the same shape as `CommandDef` so virtual-tag commands surface in
@@ -197,8 +220,8 @@ impl Default for VirtualTagRegistry {
 /// Strategy for the READY virtual tag.
 ///
 /// A task is READY when it is not in the terminal column and all of its
-/// dependencies (if any) are in the terminal column. Tasks with no
-/// dependencies are always ready (unless already completed).
+/// dependencies and children (if any) are in the terminal column. Tasks with
+/// neither are always ready (unless already completed).
 pub struct ReadyStrategy;
 
 impl sealed::Sealed for ReadyStrategy {}
@@ -236,9 +259,10 @@ impl VirtualTagStrategy for ReadyStrategy {
             return false;
         }
 
-        // A task is READY when every dependency is in the terminal column.
-        // Missing dependencies (not found in all_tasks) count as not ready.
-        let deps = entity.get_string_list("depends_on");
+        // A task is READY when every dependency and child is in the terminal
+        // column. Missing dependencies (not found in all_tasks) count as not
+        // ready.
+        let deps = task_prerequisites(entity, ctx.entities);
         deps.iter().all(|dep_id| {
             ctx.entities
                 .iter()
@@ -251,8 +275,9 @@ impl VirtualTagStrategy for ReadyStrategy {
 
 /// Strategy that tags tasks which other tasks depend on.
 ///
-/// A task is BLOCKING when at least one other task lists it in `depends_on`
-/// AND the task itself is not yet in the terminal (done) column.
+/// A task is BLOCKING when at least one other task lists it in `depends_on`,
+/// or it is a subtask (its parent waits on it), AND the task itself is not
+/// yet in the terminal (done) column.
 pub struct BlockingStrategy;
 
 impl sealed::Sealed for BlockingStrategy {}
@@ -295,7 +320,11 @@ impl VirtualTagStrategy for BlockingStrategy {
             return false;
         }
 
-        // True if at least one other task depends on this entity.
+        // True if this is a subtask or at least one other task depends on
+        // this entity.
+        if task_parent(entity).is_some() {
+            return true;
+        }
         let my_id = entity.id.as_str();
         ctx.entities.iter().any(|t| {
             t.get_string_list("depends_on")
@@ -307,8 +336,8 @@ impl VirtualTagStrategy for BlockingStrategy {
 
 /// Strategy for the BLOCKED virtual tag.
 ///
-/// A task is BLOCKED when it has at least one dependency that is NOT
-/// in the terminal (done) column. Missing dependencies (not found in
+/// A task is BLOCKED when it has at least one dependency or child that is
+/// NOT in the terminal (done) column. Missing dependencies (not found in
 /// `all_tasks`) are also considered blocking.
 pub struct BlockedStrategy;
 
@@ -346,7 +375,7 @@ impl VirtualTagStrategy for BlockedStrategy {
             .map(|t| t.0.as_str())
             .unwrap_or("done");
 
-        let deps = entity.get_string_list("depends_on");
+        let deps = task_prerequisites(entity, ctx.entities);
         if deps.is_empty() {
             return false;
         }
@@ -360,6 +389,57 @@ impl VirtualTagStrategy for BlockedStrategy {
     }
 }
 
+/// Strategy for the OVER_WIP virtual tag.
+///
+/// A task is OVER_WIP when its column holds more tasks than the column's
+/// WIP limit allows — which only happens after a move that set
+/// `override_wip_limit`. Requires [`ColumnWipLimits`] in the context extras;
+/// without it nothing matches.
+pub struct OverWipStrategy;
+
+impl sealed::Sealed for OverWipStrategy {}
+
+impl VirtualTagStrategy for OverWipStrategy {
+    fn slug(&self) -> &str {
+        "OVER_WIP"
+    }
+
+    fn color(&self) -> &str {
+        "b60205"
+    }
+
+    fn description(&self) -> &str {
+        "Task's column is over its WIP limit"
+    }
+
+    fn commands(&self) -> Vec<VirtualTagCommand> {
+        vec![]
+    }
+
+    fn matches(&self, ctx: &EntityFilterContext) -> bool {
+        let entity = match ctx.entity {
+            Some(e) => e,
+            None => return false,
+        };
+        let Some(col) = entity.get_str("position_column") else {
+            return false;
+        };
+        let Some(limit) = ctx
+            .get::<ColumnWipLimits>()
+            .and_then(|limits| limits.0.get(col).copied())
+        else {
+            return false;
+        };
+
+        let count = ctx
+            .entities
+            .iter()
+            .filter(|t| t.get_str("position_column") == Some(col))
+            .count();
+        count > limit
+    }
+}
+
 /// Static singleton for the default virtual tag registry.
 ///
 /// The registry is immutable once built and never changes at runtime,
@@ -369,6 +449,7 @@ static DEFAULT_REGISTRY: LazyLock<VirtualTagRegistry> = LazyLock::new(|| {
     registry.register(Box::new(ReadyStrategy));
     registry.register(Box::new(BlockedStrategy));
     registry.register(Box::new(BlockingStrategy));
+    registry.register(Box::new(OverWipStrategy));
     registry
 });
 
@@ -888,4 +969,47 @@ mod tests {
         assert!(cmds[0].context_menu);
         assert!(cmds[0].keys.is_none());
     }
+
+    fn task_in(id: &str, column: &str) -> Entity {
+        let mut task = Entity::new("task", id);
+        task.set("position_column", Value::String(column.into()));
+        task
+    }
+
+    #[test]
+    fn over_wip_when_column_exceeds_limit() {
+        let strategy = OverWipStrategy;
+        let all = [task_in("t1", "doing"), task_in("t2", "doing")];
+        let mut ctx = make_ctx(&all[0], &all, "done");
+        ctx.insert(ColumnWipLimits(HashMap::from([("doing".to_string(), 1)])));
+        assert!(strategy.matches(&ctx));
+    }
+
+    #[test]
+    fn over_wip_not_at_or_under_limit() {
+        let strategy = OverWipStrategy;
+        let all = [task_in("t1", "doing"), task_in("t2", "doing")];
+        let mut ctx = make_ctx(&all[0], &all, "done");
+        ctx.insert(ColumnWipLimits(HashMap::from([("doing".to_string(), 2)])));
+        assert!(!strategy.matches(&ctx));
+    }
+
+    #[test]
+    fn over_wip_requires_limits_in_context() {
+        let strategy = OverWipStrategy;
+        let all = [task_in("t1", "doing"), task_in("t2", "doing")];
+        let ctx = make_ctx(&all[0], &all, "done");
+        assert!(!strategy.matches(&ctx));
+    }
+
+    #[test]
+    fn column_wip_limits_skip_unlimited_columns() {
+        let mut doing = Entity::new("column", "doing");
+        doing.set("wip_limit", serde_json::json!(3));
+        let mut todo = Entity::new("column", "todo");
+        todo.set("wip_limit", serde_json::json!(0));
+        let limits = ColumnWipLimits::from_columns(&[doing, todo, Entity::new("column", "done")]);
+        assert_eq!(limits.0.len(), 1);
+        assert_eq!(limits.0.get("doing"), Some(&3));
+    }
 }
@@ -0,0 +1,343 @@
+//! GetWorkspace command
+
+use super::{Workspace, WorkspaceBoard};
+use crate::context::KanbanContext;
+use crate::error::{KanbanError, Result};
+use crate::task::{parse_filter_expr, retain_matching_tasks};
+use crate::task_helpers::{
+    enrich_all_task_entities_with_wip_limits, slim_task_json, task_entity_to_rich_json,
+};
+use crate::types::Ordinal;
+use crate::virtual_tags::{default_virtual_tag_registry, ColumnWipLimits};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::Path;
+use swissarmyhammer_filter_expr::Expr;
+use swissarmyhammer_operations::{async_trait, operation, Execute, ExecutionResult};
+
+/// Merge every board of the workspace into one read-only board.
+///
+/// Columns with the same id are merged and placed by their lowest `order` on
+/// any board; columns that tie keep the order they were first met in.
+/// Tasks keep their board order within a column, board by board, with ids
+/// qualified by the board key. A board that cannot be read is reported under
+/// `errors` and left out.
+#[operation(
+    verb = "get",
+    noun = "workspace",
+    description = "Merged read-only view of every board in the workspace"
+)]
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct GetWorkspace {
+    /// Filter DSL expression applied to every board's tasks (e.g. `#bug`)
+    pub filter: Option<String>,
+    /// Directory to find the boards in, instead of the configured workspace
+    pub workspace_root: Option<String>,
+}
+
+impl GetWorkspace {
+    /// View the configured workspace.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only show tasks matching a filter DSL expression.
+    pub fn with_filter(mut self, filter: impl Into<String>) -> Self {
+        self.filter = Some(filter.into());
+        self
+    }
+
+    /// View the boards found under `root` instead of the configured
+    /// workspace.
+    pub fn with_workspace_root(mut self, root: impl Into<String>) -> Self {
+        self.workspace_root = Some(root.into());
+        self
+    }
+}
+
+/// One board's part of the merged view.
+struct BoardView {
+    name: String,
+    /// `(id, name, order)` in board order
+    columns: Vec<(String, String, u64)>,
+    terminal: String,
+    /// `(column id, task JSON)` in board order
+    tasks: Vec<(String, Value)>,
+}
+
+async fn board_view(board: &WorkspaceBoard, expr: Option<&Expr>) -> Result<BoardView> {
+    let ctx = board.context();
+    let ectx = ctx.entity_context().await?;
+    let name = ectx
+        .read("board", "board")
+        .await
+        .map_err(|_| KanbanError::NotInitialized {
+            path: board.root.clone(),
+        })?
+        .get_str("name")
+        .unwrap_or(board.key.as_str())
+        .to_string();
+
+    let mut columns = ectx.list("column").await?;
+    columns.sort_by_key(|c| {
+        (
+            c.get("order").and_then(|v| v.as_u64()).unwrap_or(0),
+            c.id.to_string(),
+        )
+    });
+    let terminal = columns
+        .last()
+        .map(|c| c.id.to_string())
+        .unwrap_or_else(|| "done".to_string());
+
+    let mut tasks = ectx.list("task").await?;
+    match expr {
+        Some(expr) => retain_matching_tasks(&ectx, &mut tasks, &columns, expr).await?,
+        None => enrich_all_task_entities_with_wip_limits(
+            &mut tasks,
+            &terminal,
+            &ColumnWipLimits::from_columns(&columns),
+            default_virtual_tag_registry(),
+        ),
+    }
+    tasks.sort_by(|a, b| {
+        let ordinal = |t: &swissarmyhammer_entity::Entity| {
+            Ordinal::from_string(
+                t.get_str("position_ordinal")
+                    .unwrap_or(Ordinal::DEFAULT_STR),
+            )
+        };
+        ordinal(a).cmp(&ordinal(b))
+    });
+
+    Ok(BoardView {
+        name,
+        columns: columns
+            .iter()
+            .map(|c| {
+                (
+                    c.id.to_string(),
+                    c.get_str("name").unwrap_or("").to_string(),
+                    c.get("order").and_then(|v| v.as_u64()).unwrap_or(0),
+                )
+            })
+            .collect(),
+        terminal,
+        tasks: tasks
+            .iter()
+            .map(|t| {
+                let mut task = slim_task_json(&task_entity_to_rich_json(t));
+                board.qualify_task_json(&mut task);
+                let column = t.get_str("position_column").unwrap_or("").to_string();
+                (column, task)
+            })
+            .collect(),
+    })
+}
+
+/// A column of the merged view.
+struct MergedColumn {
+    id: String,
+    name: String,
+    /// Lowest `order` the column has on any board
+    order: u64,
+    tasks: Vec<Value>,
+}
+
+#[async_trait]
+impl Execute<KanbanContext, KanbanError> for GetWorkspace {
+    async fn execute(&self, ctx: &KanbanContext) -> ExecutionResult<Value, KanbanError> {
+        let result: Result<Value> = async {
+            let workspace =
+                Workspace::resolve(ctx.root(), self.workspace_root.as_deref().map(Path::new))?;
+            let expr = parse_filter_expr(self.filter.as_deref())?;
+
+            let mut boards = Vec::new();
+            let mut errors = Vec::new();
+            let mut columns: Vec<MergedColumn> = Vec::new();
+            let (mut total, mut ready, mut done) = (0usize, 0usize, 0usize);
+            for board in workspace.boards() {
+                let view = match board_view(board, expr.as_ref()).await {
+                    Ok(view) => view,
+                    Err(e) => {
+                        errors.push(json!({
+                            "board": board.key,
+                            "path": board.root,
+                            "error": e.to_string(),
+                        }));
+                        continue;
+                    }
+                };
+
+                for (id, name, order) in &view.columns {
+                    match columns.iter_mut().find(|c| c.id == *id) {
+                        Some(column) => column.order = column.order.min(*order),
+                        None => columns.push(MergedColumn {
+                            id: id.clone(),
+                            name: name.clone(),
+                            order: *order,
+                            tasks: Vec::new(),
+                        }),
+                    }
+                }
+
+                let board_done = view
+                    .tasks
+                    .iter()
+                    .filter(|(column, _)| *column == view.terminal)
+                    .count();
+                let board_ready = view
+                    .tasks
+                    .iter()
+                    .filter(|(_, task)| task["ready"].as_bool().unwrap_or(false))
+                    .count();
+                boards.push(json!({
+                    "key": board.key,
+                    "name": view.name,
+                    "path": board.root,
+                    "task_count": view.tasks.len(),
+                    "ready_count": board_ready,
+                    "done_count": board_done,
+                }));
+                total += view.tasks.len();
+                ready += board_ready;
+                done += board_done;
+
+                for (column, task) in view.tasks {
+                    if let Some(merged) = columns.iter_mut().find(|c| c.id == column) {
+                        merged.tasks.push(task);
+                    }
+                }
+            }
+
+            // Stable, so columns that tie keep the order they were met in.
+            columns.sort_by_key(|c| c.order);
+            let columns: Vec<Value> = columns
+                .into_iter()
+                .map(|c| {
+                    json!({
+                        "id": c.id,
+                        "name": c.name,
+                        "task_count": c.tasks.len(),
+                        "tasks": c.tasks,
+                    })
+                })
+                .collect();
+            let percent_complete = if total > 0 {
+                (done as f64 / total as f64 * 100.0).round() as u32
+            } else {
+                0
+            };
+
+            Ok(json!({
+                "boards": boards,
+                "columns": columns,
+                "summary": {
+                    "total_tasks": total,
+                    "ready_tasks": ready,
+                    "done_tasks": done,
+                    "percent_complete": percent_complete,
+                },
+                "errors": errors,
+            }))
+        }
+        .await;
+
+        match result {
+            Ok(value) => ExecutionResult::Success { value },
+            Err(error) => ExecutionResult::Failed { error },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::InitBoard;
+    use crate::column::AddColumn;
+    use crate::task::{AddTask, MoveTask};
+    use tempfile::TempDir;
+
+    async fn board(dir: &Path, name: &str) -> KanbanContext {
+        let ctx = KanbanContext::new(dir.join(name).join(".kanban"));
+        InitBoard::new(name)
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+        ctx
+    }
+
+    async fn add(ctx: &KanbanContext, title: &str, description: &str) -> String {
+        let task = AddTask::new(title)
+            .with_description(description)
+            .execute(ctx)
+            .await
+            .into_result()
+            .unwrap();
+        task["id"].as_str().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_get_workspace_merges_columns_and_qualifies_ids() {
+        let temp = TempDir::new().unwrap();
+        let api = board(temp.path(), "api").await;
+        let web = board(temp.path(), "web").await;
+        let login = add(&api, "Login endpoint", "#auth").await;
+        add(&web, "Login page", "#auth").await;
+        let shipped = add(&web, "Shipped", "").await;
+        MoveTask::to_column(shipped.as_str(), "done")
+            .execute(&web)
+            .await
+            .into_result()
+            .unwrap();
+        AddColumn::new("review", "Review")
+            .with_order(1)
+            .execute(&web)
+            .await
+            .into_result()
+            .unwrap();
+        std::fs::create_dir_all(temp.path().join("broken/.kanban/boards")).unwrap();
+        std::fs::write(temp.path().join("broken/.kanban/boards/board.yaml"), "{").unwrap();
+
+        let root = temp.path().to_string_lossy().to_string();
+        let view = GetWorkspace::new()
+            .with_workspace_root(&root)
+            .execute(&api)
+            .await
+            .into_result()
+            .unwrap();
+
+        let keys: Vec<&str> = view["boards"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|b| b["key"].as_str().unwrap())
+            .collect();
+        assert_eq!(keys, vec!["api", "web"]);
+        assert_eq!(view["errors"][0]["board"], "broken");
+
+        let columns: Vec<&str> = view["columns"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(columns, vec!["todo", "review", "doing", "done"]);
+        let todo = &view["columns"][0]["tasks"];
+        assert_eq!(todo[0]["id"], format!("api/{login}"));
+        assert_eq!(todo[0]["board"], "api");
+        assert_eq!(todo[1]["title"], "Login page");
+        assert_eq!(view["summary"]["total_tasks"], 3);
+        assert_eq!(view["summary"]["done_tasks"], 1);
+
+        let filtered = GetWorkspace::new()
+            .with_workspace_root(root)
+            .with_filter("#auth")
+            .execute(&api)
+            .await
+            .into_result()
+            .unwrap();
+        assert_eq!(filtered["summary"]["total_tasks"], 2);
+    }
+}
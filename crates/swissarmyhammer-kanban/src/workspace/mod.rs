@@ -0,0 +1,21 @@
+//! Workspaces: several boards read as one
+//!
+//! One repo is one board, but work often spans repos. A workspace is the set
+//! of boards listed in `~/.sah/workspace.yaml` (directories to search and
+//! boards named one by one) or, without that file, the boards in the
+//! repositories directly next to the current one. The current board always
+//! belongs.
+//!
+//! Workspaces are read-only. `search tasks` and `next task` with `workspace`
+//! set look across every board, and `get workspace` merges the boards'
+//! columns into one view. Task ids from a workspace are qualified with the
+//! board key (`api/01J...`) so they stay unique; the board's own operations
+//! take the unqualified id.
+
+mod get;
+mod registry;
+
+pub use get::GetWorkspace;
+pub use registry::{
+    split_qualified_id, Workspace, WorkspaceBoard, WorkspaceConfig, WORKSPACE_FILE,
+};
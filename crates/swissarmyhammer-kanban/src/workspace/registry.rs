@@ -0,0 +1,347 @@
+//! Which boards make up a workspace
+
+use crate::context::KanbanContext;
+use crate::error::{KanbanError, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use swissarmyhammer_directory::{DirectoryConfig, SwissarmyhammerConfig};
+
+/// File in `~/.sah` that lists the workspace's boards.
+pub const WORKSPACE_FILE: &str = "workspace.yaml";
+
+/// How many directory levels below a configured root are searched for boards.
+const MAX_DISCOVERY_DEPTH: usize = 3;
+
+/// Directories never searched for boards: build output and vendored code.
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target", "vendor"];
+
+/// Separates the board key from the task id in a workspace-wide task id.
+const QUALIFIED_ID_SEPARATOR: char = '/';
+
+/// Task fields that hold a task id, or a list of them, and are qualified with
+/// the board key.
+const TASK_REFERENCE_FIELDS: &[&str] = &[
+    "id",
+    "parent",
+    "recurred_from",
+    "recurred_as",
+    "depends_on",
+    "blocked_by",
+    "blocks",
+    "children",
+];
+
+/// `~/.sah/workspace.yaml`.
+///
+/// ```yaml
+/// roots:
+///   - ~/src/platform      # every board up to three levels below
+/// boards:
+///   - ~/src/tools         # a repo, or its .kanban directory
+/// ```
+///
+/// Relative paths are resolved against the home directory.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkspaceConfig {
+    /// Directories searched for boards
+    #[serde(default)]
+    pub roots: Vec<PathBuf>,
+    /// Boards listed one by one
+    #[serde(default)]
+    pub boards: Vec<PathBuf>,
+}
+
+impl WorkspaceConfig {
+    /// Path of the user's workspace file, when there is a home directory.
+    pub fn user_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| {
+            home.join(SwissarmyhammerConfig::DIR_NAME)
+                .join(WORKSPACE_FILE)
+        })
+    }
+
+    /// Parse a workspace file.
+    pub fn parse(content: &str) -> Result<Self> {
+        serde_yaml_ng::from_str::<Option<WorkspaceConfig>>(content)
+            .map(Option::unwrap_or_default)
+            .map_err(|e| KanbanError::parse(format!("{WORKSPACE_FILE}: {e}")))
+    }
+
+    /// Load the workspace file at `path`; `None` when there is none.
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Self::parse(&content).map(Some),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// One board in a workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceBoard {
+    /// Short name unique within the workspace, used to prefix task ids.
+    /// The name of the directory holding `.kanban`, with a numeric suffix
+    /// when two boards share it.
+    pub key: String,
+    /// The board's `.kanban` directory
+    pub root: PathBuf,
+}
+
+impl WorkspaceBoard {
+    /// Open a context for the board.
+    pub fn context(&self) -> KanbanContext {
+        KanbanContext::new(self.root.clone())
+    }
+
+    /// The workspace-wide form of one of this board's task ids.
+    pub fn qualify(&self, id: &str) -> String {
+        format!("{}{QUALIFIED_ID_SEPARATOR}{id}", self.key)
+    }
+
+    /// Qualify the ids in a task's JSON and record which board it is on.
+    ///
+    /// The task's own id and the task ids it refers to (dependencies,
+    /// blockers, parent and children, and the instances a recurring task
+    /// came from or recurred as) all live on this board, so all of them gain
+    /// the prefix.
+    pub fn qualify_task_json(&self, task: &mut Value) {
+        let Some(obj) = task.as_object_mut() else {
+            return;
+        };
+        for field in TASK_REFERENCE_FIELDS {
+            match obj.get_mut(*field) {
+                Some(Value::String(id)) => *id = self.qualify(id),
+                Some(Value::Array(ids)) => {
+                    for id in ids.iter_mut() {
+                        if let Value::String(s) = id {
+                            *s = self.qualify(s);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        obj.insert("board".to_string(), Value::String(self.key.clone()));
+    }
+}
+
+/// Split a workspace-wide task id into its board key and task id.
+pub fn split_qualified_id(id: &str) -> Option<(&str, &str)> {
+    id.split_once(QUALIFIED_ID_SEPARATOR)
+}
+
+/// A set of boards read together: federated search, a workspace-wide
+/// `next task`, and the merged board view.
+#[derive(Debug, Clone, Default)]
+pub struct Workspace {
+    boards: Vec<WorkspaceBoard>,
+}
+
+impl Workspace {
+    /// An empty workspace.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The workspace seen from the board at `current`, which is always part
+    /// of it.
+    ///
+    /// With `root` the workspace is the boards found under that directory.
+    /// Otherwise it is the one `~/.sah/workspace.yaml` describes or, without
+    /// that file, the boards in the repositories next to the current one —
+    /// only those, not the repositories nested further down, since nothing
+    /// named that directory as a workspace.
+    pub fn resolve(current: &Path, root: Option<&Path>) -> Result<Self> {
+        let mut workspace = Self::new();
+        workspace.add_board(current);
+        if let Some(root) = root {
+            workspace.discover(root);
+            return Ok(workspace);
+        }
+        let config = match WorkspaceConfig::user_path() {
+            Some(path) => WorkspaceConfig::load(&path)?,
+            None => None,
+        };
+        match config {
+            Some(config) => workspace.add_config(&config),
+            None => {
+                // `<parent>/<repo>/.kanban`: look through `<parent>`.
+                if let Some(parent) = current.parent().and_then(Path::parent) {
+                    workspace.discover_siblings(parent);
+                }
+            }
+        }
+        Ok(workspace)
+    }
+
+    /// Add the roots and boards of a workspace file.
+    pub fn add_config(&mut self, config: &WorkspaceConfig) {
+        for root in &config.roots {
+            self.discover(&expand_home(root));
+        }
+        for board in &config.boards {
+            let path = expand_home(board);
+            if path.file_name().is_some_and(|name| name == ".kanban") {
+                self.add_board(&path);
+            } else {
+                self.add_board(&path.join(".kanban"));
+            }
+        }
+    }
+
+    /// Add every initialized board up to [`MAX_DISCOVERY_DEPTH`] levels
+    /// below `dir`, in path order. Hidden directories and build output are
+    /// not searched; unreadable directories are skipped.
+    pub fn discover(&mut self, dir: &Path) {
+        self.discover_at(dir, 0, MAX_DISCOVERY_DEPTH);
+    }
+
+    /// Add the initialized boards of the directories directly inside `dir`,
+    /// in path order, as [`discover`](Self::discover) does one level deep.
+    pub fn discover_siblings(&mut self, dir: &Path) {
+        self.discover_at(dir, 0, 1);
+    }
+
+    fn discover_at(&mut self, dir: &Path, depth: usize, max_depth: usize) {
+        let kanban = dir.join(".kanban");
+        if kanban.is_dir() && KanbanContext::new(kanban.clone()).is_initialized() {
+            self.add_board(&kanban);
+        }
+        if depth == max_depth {
+            return;
+        }
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        let mut children: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+            .filter(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_str())
+            })
+            .map(|entry| entry.path())
+            .collect();
+        children.sort();
+        for child in children {
+            self.discover_at(&child, depth + 1, max_depth);
+        }
+    }
+
+    /// Add the board whose `.kanban` directory is `root`, unless it is
+    /// already part of the workspace.
+    pub fn add_board(&mut self, root: &Path) {
+        let root = std::fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+        if self.boards.iter().any(|board| board.root == root) {
+            return;
+        }
+        let base = root
+            .parent()
+            .and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| "board".to_string());
+        let mut key = base.clone();
+        let mut n = 1;
+        while self.board(&key).is_some() {
+            n += 1;
+            key = format!("{base}-{n}");
+        }
+        self.boards.push(WorkspaceBoard { key, root });
+    }
+
+    /// The boards, the current one first.
+    pub fn boards(&self) -> &[WorkspaceBoard] {
+        &self.boards
+    }
+
+    /// The board with this key.
+    pub fn board(&self, key: &str) -> Option<&WorkspaceBoard> {
+        self.boards.iter().find(|board| board.key == key)
+    }
+}
+
+/// Resolve `~/...` and relative paths against the home directory.
+fn expand_home(path: &Path) -> PathBuf {
+    let Some(home) = dirs::home_dir() else {
+        return path.to_path_buf();
+    };
+    match path.strip_prefix("~") {
+        Ok(rest) => home.join(rest),
+        Err(_) if path.is_relative() => home.join(path),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::InitBoard;
+    use crate::Execute;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    async fn init(dir: &Path) {
+        InitBoard::new("Test")
+            .execute(&KanbanContext::new(dir.join(".kanban")))
+            .await
+            .into_result()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_discover_finds_nested_boards_with_unique_keys() {
+        let temp = TempDir::new().unwrap();
+        for repo in ["api", "web", "libs/api", "web/node_modules/pkg", ".cache/x"] {
+            init(&temp.path().join(repo)).await;
+        }
+
+        let mut workspace = Workspace::new();
+        workspace.add_board(&temp.path().join("web").join(".kanban"));
+        workspace.discover(temp.path());
+
+        let keys: Vec<&str> = workspace.boards().iter().map(|b| b.key.as_str()).collect();
+        assert_eq!(keys, vec!["web", "api", "api-2"]);
+        assert!(workspace
+            .board("api-2")
+            .unwrap()
+            .root
+            .ends_with("libs/api/.kanban"));
+    }
+
+    #[tokio::test]
+    async fn test_discover_siblings_stays_one_level_deep() {
+        let temp = TempDir::new().unwrap();
+        for repo in ["api", "web", "libs/core"] {
+            init(&temp.path().join(repo)).await;
+        }
+
+        let mut workspace = Workspace::new();
+        workspace.add_board(&temp.path().join("web").join(".kanban"));
+        workspace.discover_siblings(temp.path());
+
+        let keys: Vec<&str> = workspace.boards().iter().map(|b| b.key.as_str()).collect();
+        assert_eq!(keys, vec!["web", "api"]);
+    }
+
+    #[test]
+    fn test_qualify_task_json_prefixes_ids() {
+        let board = WorkspaceBoard {
+            key: "api".into(),
+            root: PathBuf::from("/src/api/.kanban"),
+        };
+        let mut task = json!({
+            "id": "01A", "parent": "01P", "depends_on": ["01B"], "children": [], "title": "T",
+            "recurred_from": "01R"
+        });
+        board.qualify_task_json(&mut task);
+        assert_eq!(task["id"], "api/01A");
+        assert_eq!(task["parent"], "api/01P");
+        assert_eq!(task["depends_on"], json!(["api/01B"]));
+        assert_eq!(task["recurred_from"], "api/01R");
+        assert_eq!(task["board"], "api");
+        assert_eq!(split_qualified_id("api/01A"), Some(("api", "01A")));
+    }
+}
@@ -17,7 +17,7 @@
 
 use swissarmyhammer_commands::CommandsRegistry;
 
-/// The 30 kanban-specific command IDs shipped under
+/// The 31 kanban-specific command IDs shipped under
 /// `swissarmyhammer-kanban/builtin/commands/`.
 ///
 /// Grouped by source file for quick auditing against the YAMLs on disk.
@@ -25,10 +25,11 @@ const KANBAN_COMMAND_IDS: &[&str] = &[
     // board.yaml (1) — `update.board` is the dispatch-layer wrapper around
     // `crate::board::UpdateBoard`.
     "update.board",
-    // task.yaml (3)
+    // task.yaml (4)
     "task.move",
     "task.untag",
     "task.doThisNext",
+    "task.promoteChecklistItem",
     // column.yaml (1)
     "column.reorder",
     // tag.yaml (1)
@@ -174,7 +175,7 @@ fn kanban_yaml_preserves_command_metadata() {
 /// Proves that the file moves and the focus-crate addition lost no
 /// commands.
 ///
-/// Count: 33 (commands-crate) + 9 (focus-crate nav.*) + 30 (kanban-crate) = 72.
+/// Count: 33 (commands-crate) + 9 (focus-crate nav.*) + 31 (kanban-crate) = 73.
 ///
 /// The 34/26 → 32/28 shift came from relocating `ui.view.set` and
 /// `ui.perspective.set` into the kanban domain (new ids `view.set` and
@@ -192,9 +193,11 @@ fn kanban_yaml_preserves_command_metadata() {
 /// layer wrapper around `crate::board::UpdateBoard` that lets a board-
 /// metadata editor persist board metadata through the unified dispatcher.
 /// The -5 to 72 came from removing the `ai.*` AI-panel command scope
-/// (01KZ23M5980FBS5HHGVTPVW1Q6).
+/// (01KZ23M5980FBS5HHGVTPVW1Q6). The +1 to 73 came from adding the hidden
+/// `task.promoteChecklistItem` that turns a description checkbox into a
+/// subtask.
 #[test]
-fn composed_builtins_register_all_seventy_two_commands() {
+fn composed_builtins_register_all_seventy_three_commands() {
     let commands_sources = swissarmyhammer_commands::builtin_yaml_sources();
     let focus_sources = swissarmyhammer_focus::builtin_yaml_sources();
     let kanban_sources = swissarmyhammer_kanban::builtin_yaml_sources();
@@ -209,7 +212,7 @@ fn composed_builtins_register_all_seventy_two_commands() {
 
     assert_eq!(
         registry.all_commands().len(),
-        72,
+        73,
         "composed registry must match the post-focus command count",
     );
 
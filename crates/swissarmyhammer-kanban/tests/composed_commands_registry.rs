@@ -19,7 +19,8 @@ use swissarmyhammer_commands::{compose_registry, CommandsRegistry};
 /// AI-panel commands (task 01KRRN69YDB2B03RB1N9G6RR3J) lifts it to 76;
 /// adding `update.board` (task 01KSNJ6AE18EQYDC2WSYFSSAY1) lifts it to 77;
 /// removing the AI panel and its five `ai.*` commands (task
-/// 01KZ23M5980FBS5HHGVTPVW1Q6) drops it to 72.
+/// 01KZ23M5980FBS5HHGVTPVW1Q6) drops it to 72; adding the hidden
+/// `task.promoteChecklistItem` lifts it to 73.
 #[test]
 fn composed_registry_matches_manual_composition() {
     let registry: CommandsRegistry = compose_registry![
@@ -30,7 +31,7 @@ fn composed_registry_matches_manual_composition() {
 
     assert_eq!(
         registry.all_commands().len(),
-        72,
+        73,
         "composed registry must contain the full generic + focus + kanban command set",
     );
 
@@ -135,7 +136,9 @@ fn composed_registry_command_id_set_snapshot() {
     // `01KSNJ6AE18EQYDC2WSYFSSAY1`) so a board-metadata editor can
     // persist its selection through the unified dispatcher. Dropped to
     // 72 when the AI panel and its five `ai.*` commands were removed
-    // (task `01KZ23M5980FBS5HHGVTPVW1Q6`). If you intentionally add or
+    // (task `01KZ23M5980FBS5HHGVTPVW1Q6`). Bumped to 73 when the hidden
+    // `task.promoteChecklistItem` joined so the inspector can turn a
+    // description checkbox into a subtask. If you intentionally add or
     // remove a command, update this list and explain why in the commit
     // message.
     let expected: Vec<&str> = vec![
@@ -198,6 +201,7 @@ fn composed_registry_command_id_set_snapshot() {
         "tag.update",
         "task.doThisNext",
         "task.move",
+        "task.promoteChecklistItem",
         "task.untag",
         "ui.entity.startRename",
         "ui.inspect",
@@ -214,5 +218,5 @@ fn composed_registry_command_id_set_snapshot() {
     ];
 
     assert_eq!(ids, expected, "command id set drifted; ids = {ids:?}",);
-    assert_eq!(ids.len(), 72);
+    assert_eq!(ids.len(), 73);
 }
@@ -8,7 +8,7 @@
 
 use serde_json::json;
 use swissarmyhammer_kanban::{
-    board::InitBoard, entity::UpdateEntityField, task::AddTask, task::MoveTask,
+    board::InitBoard, entity::UpdateEntityField, task::AddTask, task::ArchiveTask, task::MoveTask,
     task_helpers::enrich_task_entity, virtual_tags::default_virtual_tag_registry, KanbanContext,
     KanbanOperationProcessor, OperationProcessor,
 };
@@ -144,6 +144,64 @@ async fn board_percent_complete_updates_after_move_to_done() {
     assert_eq!(pc["percent"], 50);
 }
 
+#[tokio::test]
+async fn board_flow_fields_are_opt_in() {
+    let (_temp, ctx, processor) = setup().await;
+    processor
+        .process(&AddTask::new("Task A"), &ctx)
+        .await
+        .unwrap();
+
+    // Reading the board does not read task history unless the board asks.
+    let ectx = ctx.entity_context().await.unwrap();
+    let bag = ectx.read("board", "board").await.unwrap().to_json();
+    assert!(bag.get("cycle_time").is_none(), "{bag}");
+    assert!(bag.get("throughput").is_none(), "{bag}");
+}
+
+#[tokio::test]
+async fn board_flow_fields_count_recently_completed_tasks() {
+    let (temp, _ctx, _processor) = setup().await;
+    let kanban_dir = temp.path().join(".kanban");
+    std::fs::create_dir_all(kanban_dir.join("entities")).unwrap();
+    std::fs::write(
+        kanban_dir.join("entities").join("board.yaml"),
+        "name: board\nicon: kanban\nsearch_display_field: name\nfields:\n  - name\n  - description\n  - percent_complete\n  - cycle_time\n  - throughput\n",
+    )
+    .unwrap();
+    let ctx = KanbanContext::new(&kanban_dir);
+    let processor = KanbanOperationProcessor::new();
+
+    let r1 = processor
+        .process(&AddTask::new("Task A"), &ctx)
+        .await
+        .unwrap();
+    let task_id = r1["id"].as_str().unwrap().to_string();
+    processor
+        .process(&AddTask::new("Task B"), &ctx)
+        .await
+        .unwrap();
+    let mv = MoveTask::to_column(task_id.as_str(), "done");
+    processor.process(&mv, &ctx).await.unwrap();
+
+    // Both fields are aggregates over task changelogs, read through the board
+    let ectx = ctx.entity_context().await.unwrap();
+    let bag = ectx.read("board", "board").await.unwrap().to_json();
+    assert_eq!(bag["throughput"]["completed"], 1, "{}", bag["throughput"]);
+    assert_eq!(bag["throughput"]["days"], 28);
+    assert_eq!(bag["cycle_time"]["count"], 1, "{}", bag["cycle_time"]);
+    assert!(bag["cycle_time"]["p50"].is_number());
+
+    // Archiving the finished task keeps it in the board's flow
+    processor
+        .process(&ArchiveTask::new(task_id.as_str()), &ctx)
+        .await
+        .unwrap();
+    let bag = ectx.read("board", "board").await.unwrap().to_json();
+    assert_eq!(bag["throughput"]["completed"], 1, "{}", bag["throughput"]);
+    assert_eq!(bag["cycle_time"]["count"], 1, "{}", bag["cycle_time"]);
+}
+
 #[tokio::test]
 async fn depends_on_triggers_cascade_for_board_when_task_changes() {
     let (_temp, ctx, _processor) = setup().await;
@@ -290,3 +348,78 @@ async fn depends_on_field_persists_through_save_and_reload() {
         "depends_on must survive save/reload"
     );
 }
+
+#[tokio::test]
+async fn board_local_js_computed_field_is_derived() {
+    let (temp, ctx, processor) = setup().await;
+    let kanban_dir = temp.path().join(".kanban");
+    std::fs::write(
+        kanban_dir.join("definitions/risk_score.yaml"),
+        "id: 01JR5K5C0RE000000000000000\nname: risk_score\ntype:\n  kind: computed\n  derive: js\n  script: |\n    const words = fields.title.split(' ').length;\n    return fields.body.includes('#urgent') ? words * 10 : words;\n",
+    )
+    .unwrap();
+    let (_, task_yaml) = swissarmyhammer_kanban::defaults::builtin_entity_definitions()
+        .into_iter()
+        .find(|(name, _)| *name == "task")
+        .unwrap();
+    std::fs::write(
+        kanban_dir.join("entities/task.yaml"),
+        format!("{task_yaml}\n  - risk_score\n"),
+    )
+    .unwrap();
+
+    let result = processor
+        .process(
+            &AddTask::new("Fix the login").with_description("#urgent"),
+            &ctx,
+        )
+        .await
+        .unwrap();
+    let task_id = result["id"].as_str().unwrap().to_string();
+
+    // A fresh context picks up the board's local definitions.
+    let ctx = KanbanContext::new(&kanban_dir);
+    let ectx = ctx.entity_context().await.unwrap();
+    let entity = ectx.read("task", &task_id).await.unwrap();
+    assert_eq!(entity.get("risk_score"), Some(&json!(30)));
+}
+
+#[tokio::test]
+async fn board_local_entity_rules_reject_ops_naming_the_field() {
+    let (temp, _ctx, processor) = setup().await;
+    let kanban_dir = temp.path().join(".kanban");
+    let (_, task_yaml) = swissarmyhammer_kanban::defaults::builtin_entity_definitions()
+        .into_iter()
+        .find(|(name, _)| *name == "task")
+        .unwrap();
+    std::fs::write(
+        kanban_dir.join("entities/task.yaml"),
+        format!("{task_yaml}\nrules:\n  - field: due\n    not_before: scheduled\n"),
+    )
+    .unwrap();
+    let ctx = KanbanContext::new(&kanban_dir);
+
+    let err = processor
+        .process(
+            &AddTask::new("Launch")
+                .with_scheduled("2026-04-15")
+                .with_due("2026-04-01"),
+            &ctx,
+        )
+        .await
+        .unwrap_err();
+    let violations = err.rule_violations().expect("a rule violation");
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].field, "due");
+    assert_eq!(violations[0].rule, "not_before");
+
+    processor
+        .process(
+            &AddTask::new("Launch")
+                .with_scheduled("2026-04-15")
+                .with_due("2026-04-30"),
+            &ctx,
+        )
+        .await
+        .unwrap();
+}
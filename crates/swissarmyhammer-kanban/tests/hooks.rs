@@ -0,0 +1,215 @@
+//! Board event hooks end to end: `.kanban/hooks.yaml` drives a command hook
+//! and a webhook posted to a receiver on localhost.
+//!
+//! Each test starts the hooks the way the one-shot CLI does: `start_hooks`
+//! before the operations, `drain_hooks` after, so every quick delivery has
+//! finished (or been dead-lettered) by the time the assertions run. Command
+//! hooks are started with the test board trusted explicitly, in place of the
+//! user's `~/.sah/trusted_hooks.yaml`.
+
+use serde_json::Value;
+use std::time::{Duration, Instant};
+use swissarmyhammer_kanban::{
+    board::InitBoard,
+    hooks::{dead_letter_path, HookTrust, HOOKS_FILE},
+    task::{AddTask, MoveTask, TagTask},
+    Execute, KanbanContext,
+};
+use tempfile::TempDir;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+
+async fn setup(hooks_yaml: &str) -> (TempDir, KanbanContext) {
+    let temp = TempDir::new().unwrap();
+    let kanban_dir = temp.path().join(".kanban");
+    let ctx = KanbanContext::new(kanban_dir.clone());
+    InitBoard::new("Test")
+        .execute(&ctx)
+        .await
+        .into_result()
+        .unwrap();
+    std::fs::write(kanban_dir.join(HOOKS_FILE), hooks_yaml).unwrap();
+    (temp, ctx)
+}
+
+/// Trust the board in `temp` to run command hooks.
+fn trusted(temp: &TempDir) -> HookTrust {
+    HookTrust::new([temp.path()])
+}
+
+async fn add_task(ctx: &KanbanContext, task: AddTask) -> String {
+    let result = task.execute(ctx).await.into_result().unwrap();
+    result["id"].as_str().unwrap().to_string()
+}
+
+/// Accept HTTP requests on localhost, answer `200 OK` and forward each
+/// request's headers and body. Returns the base URL.
+async fn receiver() -> (String, mpsc::UnboundedReceiver<(String, String)>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut buf = Vec::new();
+            let mut chunk = [0u8; 4096];
+            let (head, body_start) = loop {
+                let n = stream.read(&mut chunk).await.unwrap();
+                if n == 0 {
+                    return;
+                }
+                buf.extend_from_slice(&chunk[..n]);
+                if let Some(end) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                    break (String::from_utf8_lossy(&buf[..end]).to_string(), end + 4);
+                }
+            };
+            let length = head
+                .lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("content-length")
+                        .then(|| value.trim().parse::<usize>().ok())?
+                })
+                .unwrap_or(0);
+            while buf.len() < body_start + length {
+                let n = stream.read(&mut chunk).await.unwrap();
+                if n == 0 {
+                    break;
+                }
+                buf.extend_from_slice(&chunk[..n]);
+            }
+            let body = String::from_utf8_lossy(&buf[body_start..]).to_string();
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+                .await
+                .unwrap();
+            let _ = tx.send((head.to_lowercase(), body));
+        }
+    });
+    (url, rx)
+}
+
+#[tokio::test]
+async fn test_webhook_posts_when_task_enters_column() {
+    let (url, mut requests) = receiver().await;
+    let (_temp, ctx) = setup(&format!(
+        "hooks:\n  - name: shipped\n    on: task.entered_column\n    column: done\n    url: {url}\n    headers:\n      x-token: secret\n"
+    ))
+    .await;
+    let id = add_task(&ctx, AddTask::new("Ship it")).await;
+
+    assert!(ctx.start_hooks().await.unwrap());
+    MoveTask::to_column(id.as_str(), "doing")
+        .execute(&ctx)
+        .await
+        .into_result()
+        .unwrap();
+    MoveTask::to_column(id.as_str(), "done")
+        .execute(&ctx)
+        .await
+        .into_result()
+        .unwrap();
+    ctx.drain_hooks().await;
+
+    let (head, body) = requests.recv().await.unwrap();
+    assert!(head.starts_with("post /hook"));
+    assert!(head.contains("x-kanban-event: task.entered_column"));
+    assert!(head.contains("x-token: secret"));
+    let event: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(event["hook"], "shipped");
+    assert_eq!(event["column"], "done");
+    assert_eq!(event["previous_column"], "doing");
+    assert_eq!(event["task"]["id"], id.as_str());
+    assert_eq!(event["task"]["title"], "Ship it");
+    assert!(
+        requests.try_recv().is_err(),
+        "the move to doing does not match"
+    );
+}
+
+#[tokio::test]
+async fn test_command_hook_receives_templated_payload_on_tag() {
+    let (temp, ctx) = setup(
+        "hooks:\n  - name: triage\n    on: task.tagged\n    tag: bug\n    command: 'cat >> tagged.txt'\n    payload: '{{ task.title }} tagged {{ tag }}'\n",
+    )
+    .await;
+    let id = add_task(&ctx, AddTask::new("Crash on start")).await;
+
+    ctx.start_hooks_with_trust(&trusted(&temp)).await.unwrap();
+    for tag in ["feature", "bug"] {
+        TagTask::new(id.as_str(), tag)
+            .execute(&ctx)
+            .await
+            .into_result()
+            .unwrap();
+    }
+    ctx.drain_hooks().await;
+
+    let written = std::fs::read_to_string(temp.path().join("tagged.txt")).unwrap();
+    assert_eq!(written, "Crash on start tagged bug");
+}
+
+#[tokio::test]
+async fn test_overdue_fires_once_and_failures_are_dead_lettered() {
+    let (temp, ctx) = setup(
+        "hooks:\n  - name: late\n    on: task.overdue\n    command: 'cat >> overdue.txt'\n  - name: unreachable\n    on: task.overdue\n    url: http://127.0.0.1:9/\n    retries: 1\n    backoff_ms: 1\n    timeout_secs: 2\n",
+    )
+    .await;
+    let yesterday = (chrono::Local::now() - chrono::Duration::days(1))
+        .format("%Y-%m-%d")
+        .to_string();
+    let id = add_task(&ctx, AddTask::new("Late").with_due(yesterday)).await;
+    add_task(&ctx, AddTask::new("Not due")).await;
+
+    ctx.start_hooks_with_trust(&trusted(&temp)).await.unwrap();
+    ctx.drain_hooks().await;
+
+    let written = std::fs::read_to_string(temp.path().join("overdue.txt")).unwrap();
+    let event: Value = serde_json::from_str(&written).unwrap();
+    assert_eq!(event["event"], "task.overdue");
+    assert_eq!(event["task"]["id"], id.as_str());
+
+    let dead = std::fs::read_to_string(dead_letter_path(&temp.path().join(".kanban"))).unwrap();
+    let record: Value = serde_json::from_str(dead.lines().next().unwrap()).unwrap();
+    assert_eq!(record["hook"], "unreachable");
+    assert_eq!(record["attempts"], 2);
+
+    // A later run remembers the notice was sent.
+    let again = KanbanContext::new(temp.path().join(".kanban"));
+    again.start_hooks_with_trust(&trusted(&temp)).await.unwrap();
+    again.drain_hooks().await;
+    let written_again = std::fs::read_to_string(temp.path().join("overdue.txt")).unwrap();
+    assert_eq!(written_again, written);
+}
+
+#[tokio::test]
+async fn test_command_hooks_need_the_board_to_be_trusted() {
+    let (temp, ctx) = setup(
+        "hooks:\n  - name: created\n    on: task.created\n    command: 'cat >> created.txt'\n",
+    )
+    .await;
+
+    assert!(!ctx
+        .start_hooks_with_trust(&HookTrust::default())
+        .await
+        .unwrap());
+    add_task(&ctx, AddTask::new("Untrusted")).await;
+    ctx.drain_hooks().await;
+
+    assert!(!temp.path().join("created.txt").exists());
+}
+
+#[tokio::test]
+async fn test_drain_does_not_wait_for_slow_deliveries() {
+    let (temp, ctx) = setup(
+        "hooks:\n  - name: slow\n    on: task.created\n    command: 'sleep 30'\n    timeout_secs: 60\n",
+    )
+    .await;
+
+    ctx.start_hooks_with_trust(&trusted(&temp)).await.unwrap();
+    add_task(&ctx, AddTask::new("Slow")).await;
+    let started = Instant::now();
+    ctx.drain_hooks().await;
+
+    assert!(started.elapsed() < Duration::from_secs(20));
+}
@@ -30,7 +30,11 @@ use swissarmyhammer_kanban::clipboard::{
 use swissarmyhammer_kanban::commands::register_commands;
 use swissarmyhammer_kanban::test_support::composed_builtin_yaml_sources;
 use swissarmyhammer_kanban::{
-    board::InitBoard, KanbanContext, KanbanOperationProcessor, OperationProcessor,
+    board::InitBoard,
+    bulk::MoveTasks,
+    column::UpdateColumn,
+    task::{AddTask, ArchiveTask, CompleteTask, MoveTask},
+    KanbanContext, KanbanOperationProcessor, OperationProcessor,
 };
 use swissarmyhammer_perspectives::{PerspectiveEvent, PerspectiveStore};
 use swissarmyhammer_store::{StoreContext, StoreHandle};
@@ -1538,3 +1542,232 @@ async fn undo_column_reorder_restores_original_order() {
         "one app.redo must reapply the entire column reorder"
     );
 }
+
+// ===========================================================================
+// Templated add — the task and its subtasks undo as one step.
+// ===========================================================================
+
+/// `add task` with a template writes the task and then its subtasks. They
+/// share one undo group, so a single undo removes all of them and a single
+/// redo brings all of them back.
+#[tokio::test]
+async fn undo_templated_add_removes_task_and_subtasks() {
+    let engine = UndoEngine::new().await;
+    let ectx = engine.kanban.entity_context().await.unwrap();
+    let before = ectx.list("task").await.unwrap().len();
+
+    let cmd = AddTask::new("Crash on save")
+        .with_template("bugfix")
+        .with_params(HashMap::from([(
+            "component".to_string(),
+            "editor".to_string(),
+        )]));
+    let added = KanbanOperationProcessor::new()
+        .process(&cmd, &engine.kanban)
+        .await
+        .expect("templated add task");
+    assert_eq!(added["subtasks"].as_array().unwrap().len(), 2);
+    assert_eq!(ectx.list("task").await.unwrap().len(), before + 3);
+
+    engine.undo().await;
+    assert_eq!(
+        ectx.list("task").await.unwrap().len(),
+        before,
+        "one app.undo must remove the task and every subtask it created"
+    );
+
+    engine.redo().await;
+    assert_eq!(
+        ectx.list("task").await.unwrap().len(),
+        before + 3,
+        "one app.redo must bring the whole set back"
+    );
+}
+
+/// A templated add that fails part-way, here on a subtask over the column's
+/// WIP limit, rolls back what it already wrote instead of leaving the task or
+/// its subtasks on the board.
+#[tokio::test]
+async fn failed_templated_add_leaves_nothing_behind() {
+    let engine = UndoEngine::new().await;
+    let ectx = engine.kanban.entity_context().await.unwrap();
+    let before = ectx.list("task").await.unwrap().len();
+    let room = tasks_in_column(&engine, "todo").await + 1;
+    KanbanOperationProcessor::new()
+        .process(
+            &UpdateColumn::new("todo").with_wip_limit(room),
+            &engine.kanban,
+        )
+        .await
+        .expect("cap todo");
+
+    let cmd = AddTask::new("Crash on save")
+        .with_template("bugfix")
+        .with_params(HashMap::from([(
+            "component".to_string(),
+            "editor".to_string(),
+        )]));
+    KanbanOperationProcessor::new()
+        .process(&cmd, &engine.kanban)
+        .await
+        .expect_err("the first subtask exceeds the WIP limit");
+    assert_eq!(
+        ectx.list("task").await.unwrap().len(),
+        before,
+        "the task written before the failure must be rolled back"
+    );
+}
+
+// ===========================================================================
+// Parent cascades — the parent and the subtasks it carries undo as one step.
+// ===========================================================================
+
+/// Add a parent with two subtasks. Returns `(parent, [child, grandchild])`;
+/// the grandchild hangs off the child so cascades have to recurse.
+async fn add_family(engine: &UndoEngine) -> (String, Vec<String>) {
+    let processor = KanbanOperationProcessor::new();
+    let mut ids = Vec::new();
+    for (title, parent) in [
+        ("Parent", None),
+        ("Child", Some(0)),
+        ("Grandchild", Some(1)),
+    ] {
+        let mut cmd = AddTask::new(title);
+        if let Some(index) = parent {
+            cmd = cmd.with_parent(ids[index].as_str());
+        }
+        let added = processor.process(&cmd, &engine.kanban).await.unwrap();
+        ids.push(added["id"].as_str().unwrap().to_string());
+    }
+    let parent = ids.remove(0);
+    (parent, ids)
+}
+
+#[tokio::test]
+async fn undo_move_returns_parent_and_subtasks() {
+    let engine = UndoEngine::new().await;
+    let (parent, _) = add_family(&engine).await;
+    KanbanOperationProcessor::new()
+        .process(
+            &MoveTask::to_column(parent.as_str(), "doing"),
+            &engine.kanban,
+        )
+        .await
+        .expect("move parent");
+    assert_eq!(tasks_in_column(&engine, "doing").await, 3);
+
+    engine.undo().await;
+    assert_eq!(
+        tasks_in_column(&engine, "todo").await,
+        3,
+        "one app.undo must return the parent and every carried subtask"
+    );
+}
+
+#[tokio::test]
+async fn undo_complete_reopens_parent_and_subtasks() {
+    let engine = UndoEngine::new().await;
+    let (parent, _) = add_family(&engine).await;
+    KanbanOperationProcessor::new()
+        .process(&CompleteTask::new(parent.as_str()), &engine.kanban)
+        .await
+        .expect("complete parent");
+    assert_eq!(tasks_in_column(&engine, "done").await, 3);
+
+    engine.undo().await;
+    assert_eq!(
+        tasks_in_column(&engine, "done").await,
+        0,
+        "one app.undo must reopen the parent and every completed subtask"
+    );
+}
+
+/// Completing a recurring task writes the next instance and the completed
+/// task; one undo removes the new instance and reopens the original.
+#[tokio::test]
+async fn undo_complete_recurring_removes_next_instance() {
+    let engine = UndoEngine::new().await;
+    let processor = KanbanOperationProcessor::new();
+    let added = processor
+        .process(
+            &AddTask::new("Dependency audit").with_recurrence("freq=weekly"),
+            &engine.kanban,
+        )
+        .await
+        .unwrap();
+    let id = added["id"].as_str().unwrap().to_string();
+    let completed = processor
+        .process(&CompleteTask::new(id.as_str()), &engine.kanban)
+        .await
+        .expect("complete recurring task");
+    let next_id = completed["recurred_as"].as_str().unwrap().to_string();
+
+    engine.undo().await;
+    let ectx = engine.kanban.entity_context().await.unwrap();
+    assert!(
+        ectx.read("task", &next_id).await.is_err(),
+        "one app.undo must remove the spawned instance"
+    );
+    let original = ectx.read("task", &id).await.unwrap();
+    assert_eq!(original.get_str("position_column"), Some("todo"));
+    assert_eq!(original.get_str("recurred_as"), None);
+}
+
+#[tokio::test]
+async fn undo_archive_restores_parent_and_subtasks() {
+    let engine = UndoEngine::new().await;
+    let (parent, children) = add_family(&engine).await;
+    KanbanOperationProcessor::new()
+        .process(&ArchiveTask::new(parent.as_str()), &engine.kanban)
+        .await
+        .expect("archive parent");
+    let ectx = engine.kanban.entity_context().await.unwrap();
+    assert!(ectx.list("task").await.unwrap().is_empty());
+
+    engine.undo().await;
+    for id in std::iter::once(&parent).chain(&children) {
+        assert!(
+            ectx.read("task", id).await.is_ok(),
+            "one app.undo must restore {id} along with the rest of the subtree"
+        );
+    }
+}
+
+// =========================================================================
+// Bulk operations
+// =========================================================================
+
+async fn tasks_in_column(engine: &UndoEngine, column: &str) -> usize {
+    let ectx = engine.kanban.entity_context().await.unwrap();
+    ectx.list("task")
+        .await
+        .unwrap()
+        .iter()
+        .filter(|t| t.get_str("position_column") == Some(column))
+        .count()
+}
+
+#[tokio::test]
+async fn undo_bulk_move_returns_every_task() {
+    let engine = UndoEngine::new().await;
+    let processor = KanbanOperationProcessor::new();
+    for title in ["Bug one", "Bug two", "Bug three"] {
+        let mut cmd = AddTask::new(title);
+        cmd.tags = vec!["bug".to_string()];
+        processor.process(&cmd, &engine.kanban).await.unwrap();
+    }
+    let moved = processor
+        .process(&MoveTasks::new("#bug", "done"), &engine.kanban)
+        .await
+        .expect("bulk move");
+    assert_eq!(moved["changed"], 3);
+    assert_eq!(tasks_in_column(&engine, "done").await, 3);
+
+    engine.undo().await;
+    assert_eq!(
+        tasks_in_column(&engine, "done").await,
+        0,
+        "one app.undo must return the whole batch"
+    );
+    assert_eq!(tasks_in_column(&engine, "todo").await, 3);
+}
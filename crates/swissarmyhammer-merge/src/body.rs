@@ -0,0 +1,457 @@
+//! Structure-aware three-way merge of markdown bodies.
+//!
+//! A line-level text merge sees two branches that tick different boxes of the
+//! same checklist, or append different items to it, as overlapping edits. This
+//! module merges the body as a sequence of blocks instead, each keyed by its
+//! content identity:
+//!
+//! - a GFM checklist item (`- [ ] text`) by its indent and text — not by its
+//!   checked state, so ticking a box changes the block without replacing it;
+//! - a heading by its level and text;
+//! - any other line by its exact content.
+//!
+//! The three block sequences are aligned on those keys with a diff3. Blocks
+//! present on every side are merged one by one (a box ticked on one side is
+//! taken from that side); the regions between them follow the usual three-way
+//! rules, except that two branches adding only checklist items at the same
+//! place are both kept, ours first. Only a region both branches changed
+//! differently is a conflict, and each one is reported on its own as a
+//! [`BlockConflict`].
+
+use std::fmt;
+
+/// A region of the body that both branches changed differently.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockConflict {
+    /// 1-based line in the merged body where the region's conflict markers start.
+    pub line: usize,
+    /// The nearest heading above the region in the merged body, if any.
+    pub heading: Option<String>,
+    /// The region's text in the common ancestor.
+    pub base: String,
+    /// The region's text on our branch.
+    pub ours: String,
+    /// The region's text on their branch.
+    pub theirs: String,
+}
+
+impl BlockConflict {
+    /// The git-style conflict markers written into the merged body for this region.
+    pub fn markers(&self) -> String {
+        format!(
+            "<<<<<<< ours\n{}=======\n{}>>>>>>> theirs\n",
+            with_newline(&self.ours),
+            with_newline(&self.theirs)
+        )
+    }
+}
+
+impl fmt::Display for BlockConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.heading {
+            Some(heading) => write!(
+                f,
+                "line {} (under `{}`):\n{}",
+                self.line,
+                heading,
+                self.markers()
+            ),
+            None => write!(f, "line {}:\n{}", self.line, self.markers()),
+        }
+    }
+}
+
+/// The outcome of [`merge_body`]: the merged text and the regions that conflicted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BodyMerge {
+    /// The merged body. Each conflicting region appears as conflict markers.
+    pub merged: String,
+    /// One entry per conflicting region, in body order.
+    pub conflicts: Vec<BlockConflict>,
+}
+
+impl BodyMerge {
+    /// Whether the merge needed no conflict markers.
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+/// The identity a block is aligned on across the three versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Key<'a> {
+    /// A checklist item: its indent and text, without the checked state.
+    Task { indent: &'a str, text: &'a str },
+    /// A heading: its `#` markers and text.
+    Heading(&'a str),
+    /// Any other line, by its exact content.
+    Line(&'a str),
+}
+
+/// One line of a body with its identity. `text` keeps the line terminator.
+#[derive(Debug, Clone, Copy)]
+struct Block<'a> {
+    key: Key<'a>,
+    text: &'a str,
+}
+
+impl Block<'_> {
+    /// Whether this block is a checklist item.
+    fn is_task(&self) -> bool {
+        matches!(self.key, Key::Task { .. })
+    }
+}
+
+/// Split a body into blocks, one per line.
+fn blocks(body: &str) -> Vec<Block<'_>> {
+    body.split_inclusive('\n')
+        .map(|text| {
+            let line = text.trim_end_matches(['\n', '\r']);
+            Block {
+                key: block_key(line),
+                text,
+            }
+        })
+        .collect()
+}
+
+/// Classify one line (without its terminator).
+fn block_key(line: &str) -> Key<'_> {
+    let trimmed = line.trim_start();
+    let indent = &line[..line.len() - trimmed.len()];
+
+    let item = trimmed
+        .strip_prefix("- [")
+        .or_else(|| trimmed.strip_prefix("* ["))
+        .or_else(|| trimmed.strip_prefix("+ ["));
+    if let Some(rest) = item {
+        let text = rest
+            .strip_prefix(" ]")
+            .or_else(|| rest.strip_prefix("x]"))
+            .or_else(|| rest.strip_prefix("X]"));
+        if let Some(text) = text.filter(|t| t.is_empty() || t.starts_with(' ')) {
+            return Key::Task {
+                indent,
+                text: text.trim(),
+            };
+        }
+    }
+
+    let hashes = trimmed.len() - trimmed.trim_start_matches('#').len();
+    if indent.len() <= 3 && (1..=6).contains(&hashes) {
+        let rest = &trimmed[hashes..];
+        if rest.is_empty() || rest.starts_with(' ') {
+            return Key::Heading(trimmed.trim_end());
+        }
+    }
+
+    Key::Line(line)
+}
+
+/// The largest table [`lcs`] fills in, in cells (16 MiB of `u32`s). Past it, the
+/// blocks between the common prefix and suffix are left unaligned.
+const MAX_LCS_CELLS: usize = 4 << 20;
+
+/// Pairs `(a_index, b_index)` of a common subsequence of block keys.
+///
+/// The common prefix and suffix are matched directly and a longest common
+/// subsequence is computed for what lies between them. When that middle is too
+/// large for the quadratic table it is left unaligned instead, so the merge
+/// treats it as one region: still clean if only one branch changed it.
+fn lcs(a: &[Block<'_>], b: &[Block<'_>]) -> Vec<(usize, usize)> {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x.key == y.key).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x.key == y.key)
+        .count();
+    let (n, m) = (a.len() - prefix - suffix, b.len() - prefix - suffix);
+
+    let mut pairs: Vec<(usize, usize)> = (0..prefix).map(|i| (i, i)).collect();
+    if n.saturating_mul(m) <= MAX_LCS_CELLS {
+        let (a_mid, b_mid) = (&a[prefix..prefix + n], &b[prefix..prefix + m]);
+        pairs.extend(
+            lcs_table(a_mid, b_mid)
+                .into_iter()
+                .map(|(i, j)| (prefix + i, prefix + j)),
+        );
+    }
+    pairs.extend((0..suffix).map(|s| (prefix + n + s, prefix + m + s)));
+    pairs
+}
+
+/// A longest common subsequence of block keys by the quadratic table.
+fn lcs_table(a: &[Block<'_>], b: &[Block<'_>]) -> Vec<(usize, usize)> {
+    let (n, m) = (a.len(), b.len());
+    let mut table = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            table[i][j] = if a[i].key == b[j].key {
+                table[i + 1][j + 1] + 1
+            } else {
+                table[i + 1][j].max(table[i][j + 1])
+            };
+        }
+    }
+
+    let mut pairs = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if a[i].key == b[j].key {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if table[i + 1][j] >= table[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
+
+/// For each base block, the index of the block it aligns with on the other side.
+fn alignment(base: &[Block<'_>], other: &[Block<'_>]) -> Vec<Option<usize>> {
+    let mut matched = vec![None; base.len()];
+    for (b, o) in lcs(base, other) {
+        matched[b] = Some(o);
+    }
+    matched
+}
+
+/// Concatenate the text of a run of blocks.
+fn text_of(blocks: &[Block<'_>]) -> String {
+    blocks.iter().map(|b| b.text).collect()
+}
+
+/// `text` with a trailing newline, so conflict markers start on their own line.
+fn with_newline(text: &str) -> String {
+    if text.is_empty() || text.ends_with('\n') {
+        text.to_owned()
+    } else {
+        format!("{text}\n")
+    }
+}
+
+/// Accumulates the merged body and the conflicts found along the way.
+#[derive(Default)]
+struct Output {
+    text: String,
+    heading: Option<String>,
+    conflicts: Vec<BlockConflict>,
+}
+
+impl Output {
+    fn push(&mut self, blocks: &[Block<'_>]) {
+        for block in blocks {
+            self.push_block(block);
+        }
+    }
+
+    fn push_block(&mut self, block: &Block<'_>) {
+        if let Key::Heading(heading) = block.key {
+            self.heading = Some(heading.to_owned());
+        }
+        self.text.push_str(block.text);
+    }
+
+    fn conflict(&mut self, base: String, ours: String, theirs: String) {
+        if !self.text.is_empty() && !self.text.ends_with('\n') {
+            self.text.push('\n');
+        }
+        let conflict = BlockConflict {
+            line: self.text.matches('\n').count() + 1,
+            heading: self.heading.clone(),
+            base,
+            ours,
+            theirs,
+        };
+        self.text.push_str(&conflict.markers());
+        self.conflicts.push(conflict);
+    }
+
+    /// Merge a region between two aligned blocks.
+    fn region(&mut self, base: &[Block<'_>], ours: &[Block<'_>], theirs: &[Block<'_>]) {
+        let (b, o, t) = (text_of(base), text_of(ours), text_of(theirs));
+        if o == t || t == b {
+            self.push(ours);
+        } else if o == b {
+            self.push(theirs);
+        } else if base.is_empty() && ours.iter().chain(theirs).all(Block::is_task) {
+            // Both branches added checklist items here: keep both, without
+            // repeating an item that both added.
+            self.push(ours);
+            for block in theirs {
+                if !ours.iter().any(|o| o.key == block.key) {
+                    self.push_block(block);
+                }
+            }
+        } else {
+            self.conflict(b, o, t);
+        }
+    }
+
+    /// Merge one block that all three versions share.
+    fn block(&mut self, base: &Block<'_>, ours: &Block<'_>, theirs: &Block<'_>) {
+        if ours.text == theirs.text || theirs.text == base.text {
+            self.push_block(ours);
+        } else if ours.text == base.text {
+            self.push_block(theirs);
+        } else {
+            self.conflict(
+                base.text.to_owned(),
+                ours.text.to_owned(),
+                theirs.text.to_owned(),
+            );
+        }
+    }
+}
+
+/// Three-way merge of markdown bodies by block; see the [module docs](self).
+///
+/// Never fails: regions both branches changed differently are written into
+/// [`BodyMerge::merged`] as conflict markers and listed in
+/// [`BodyMerge::conflicts`].
+pub fn merge_body(base: &str, ours: &str, theirs: &str) -> BodyMerge {
+    let base = blocks(base);
+    let ours = blocks(ours);
+    let theirs = blocks(theirs);
+    let in_ours = alignment(&base, &ours);
+    let in_theirs = alignment(&base, &theirs);
+
+    let mut out = Output::default();
+    let (mut i, mut j, mut k) = (0, 0, 0);
+    loop {
+        // The next base block kept on both sides anchors the region before it.
+        let anchor = (i..base.len()).find_map(|b| Some((b, in_ours[b]?, in_theirs[b]?)));
+        let (b_end, o_end, t_end) = anchor.unwrap_or((base.len(), ours.len(), theirs.len()));
+        out.region(&base[i..b_end], &ours[j..o_end], &theirs[k..t_end]);
+
+        let Some((b, o, t)) = anchor else { break };
+        out.block(&base[b], &ours[o], &theirs[t]);
+        (i, j, k) = (b + 1, o + 1, t + 1);
+    }
+
+    BodyMerge {
+        merged: out.text,
+        conflicts: out.conflicts,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Each branch ticks a different box of the same checklist → clean merge.
+    #[test]
+    fn ticking_different_boxes_merges_cleanly() {
+        let base = "## Steps\n- [ ] one\n- [ ] two\n- [ ] three\n";
+        let ours = "## Steps\n- [x] one\n- [ ] two\n- [ ] three\n";
+        let theirs = "## Steps\n- [ ] one\n- [ ] two\n- [x] three\n";
+
+        let result = merge_body(base, ours, theirs);
+        assert!(result.is_clean(), "{:?}", result.conflicts);
+        assert_eq!(
+            result.merged,
+            "## Steps\n- [x] one\n- [ ] two\n- [x] three\n"
+        );
+    }
+
+    /// Both branches append different items at the end of a list → both kept.
+    #[test]
+    fn appending_different_items_keeps_both() {
+        let base = "- [ ] one\n";
+        let ours = "- [ ] one\n- [ ] ours\n";
+        let theirs = "- [ ] one\n- [ ] theirs\n- [ ] ours\n";
+
+        let result = merge_body(base, ours, theirs);
+        assert!(result.is_clean());
+        assert_eq!(result.merged, "- [ ] one\n- [ ] ours\n- [ ] theirs\n");
+    }
+
+    /// A box ticked on one side while the other edits a nearby paragraph.
+    #[test]
+    fn tick_and_text_edit_merge_cleanly() {
+        let base = "Intro.\n\n- [ ] one\n- [ ] two\n";
+        let ours = "Intro, reworded.\n\n- [ ] one\n- [ ] two\n";
+        let theirs = "Intro.\n\n- [ ] one\n- [x] two\n";
+
+        let result = merge_body(base, ours, theirs);
+        assert!(result.is_clean());
+        assert_eq!(result.merged, "Intro, reworded.\n\n- [ ] one\n- [x] two\n");
+    }
+
+    /// Two differing rewrites of the same line are one conflict, reported with
+    /// its line and heading, while a clean change elsewhere still lands.
+    #[test]
+    fn conflicts_are_reported_per_block() {
+        let base = "# Plan\nShared line.\n\n## Notes\nNote.\n";
+        let ours = "# Plan\nOurs line.\n\n## Notes\nNote.\n";
+        let theirs = "# Plan\nTheirs line.\n\n## Notes\nNote, expanded.\n";
+
+        let result = merge_body(base, ours, theirs);
+        assert_eq!(result.conflicts.len(), 1);
+        let conflict = &result.conflicts[0];
+        assert_eq!(conflict.line, 2);
+        assert_eq!(conflict.heading.as_deref(), Some("# Plan"));
+        assert_eq!(conflict.base, "Shared line.\n");
+        assert_eq!(
+            result.merged,
+            "# Plan\n<<<<<<< ours\nOurs line.\n=======\nTheirs line.\n>>>>>>> theirs\n\n## Notes\nNote, expanded.\n"
+        );
+    }
+
+    /// Checklist items, headings and plain lines are keyed as described.
+    #[test]
+    fn block_keys() {
+        assert_eq!(
+            block_key("  - [x] Ship it "),
+            Key::Task {
+                indent: "  ",
+                text: "Ship it"
+            }
+        );
+        assert_eq!(
+            block_key("* [ ] Star"),
+            Key::Task {
+                indent: "",
+                text: "Star"
+            }
+        );
+        assert_eq!(block_key("## Notes  "), Key::Heading("## Notes"));
+        assert_eq!(block_key("#hashtag"), Key::Line("#hashtag"));
+        assert_eq!(block_key("- [y] nope"), Key::Line("- [y] nope"));
+    }
+
+    /// Two branches inserting different prose at the same place conflict.
+    #[test]
+    fn inserting_different_lines_conflicts() {
+        let base = "[config]\n";
+        let ours = "[config]\nfoo = 1\n";
+        let theirs = "[config]\nfoo = 2\n";
+
+        let result = merge_body(base, ours, theirs);
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(result.conflicts[0].ours, "foo = 1\n");
+        assert_eq!(result.conflicts[0].theirs, "foo = 2\n");
+    }
+
+    /// Bodies too large for the table still merge a one-sided change cleanly,
+    /// and edits on both sides within the common prefix and suffix still align.
+    #[test]
+    fn large_bodies_fall_back_without_the_table() {
+        let lines =
+            |tag: &str| -> String { (0..3000).map(|i| format!("{tag} line {i}\n")).collect() };
+        let base = format!("- [ ] one\n{}- [ ] two\n", lines("base"));
+        let ours = format!("- [x] one\n{}- [ ] two\n", lines("ours"));
+        let theirs = format!("- [ ] one\n{}- [x] two\n", lines("base"));
+
+        let result = merge_body(&base, &ours, &theirs);
+        assert!(result.is_clean(), "{:?}", result.conflicts);
+        assert_eq!(
+            result.merged,
+            format!("- [x] one\n{}- [x] two\n", lines("ours"))
+        );
+    }
+}
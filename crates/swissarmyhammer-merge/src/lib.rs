@@ -1,3 +1,4 @@
+pub mod body;
 pub mod frontmatter;
 pub mod jsonl;
 pub mod md;
@@ -1,24 +1,41 @@
-//! Markdown merge strategy: YAML frontmatter + line-level body merge.
+//! Markdown merge strategy: YAML frontmatter + structure-aware body merge.
 //!
 //! This module merges markdown files that may optionally contain a YAML frontmatter
 //! block (delimited by `---` fences).  The merge operates in two phases:
 //!
 //! 1. **Frontmatter phase** — the frontmatter YAML (if present) is merged using the
-//!    same three-way field merge from [`crate::yaml`].
-//! 2. **Body phase** — the markdown body is merged line-by-line using `diffy`'s
-//!    three-way text merge.  Non-overlapping changes from both sides are auto-merged;
-//!    overlapping changes produce git-style conflict markers and cause the function to
-//!    return `Err(MergeConflict)`.
+//!    same three-way field merge from [`crate::yaml`].  The `comments` field, a
+//!    task's comment log, is first merged member by member (keyed by each
+//!    comment's `id`), so comments appended on both branches are all kept.
+//! 2. **Body phase** — the markdown body is merged block by block with
+//!    [`crate::body::merge_body`]: checklist items, headings and lines are aligned
+//!    by content identity, so ticking different boxes or appending different items
+//!    merges cleanly.  Regions both sides changed differently get git-style
+//!    conflict markers, one [`BlockConflict`] each.
 //!
 //! If a file has no frontmatter the entire content is treated as body and only the
-//! line-level merge is performed.
+//! body merge is performed.
 
+use crate::body::{merge_body, BlockConflict};
 use crate::frontmatter::{join_frontmatter, split_frontmatter};
-use crate::yaml::{merge_yaml, MergeOpts};
+use crate::yaml::{merge_yaml, MergeOpts, Precedence};
 use crate::{MergeConflict, MergeError};
+use serde_yaml_ng::Value;
+
+/// The frontmatter field holding a task's comment log.
+const COMMENT_LOG_FIELD: &str = "comments";
+
+/// The outcome of [`merge_md_report`]: the merged document and any body conflicts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MdMerge {
+    /// The merged document. Conflicting body regions appear as conflict markers.
+    pub merged: String,
+    /// One entry per conflicting body region, in document order.
+    pub conflicts: Vec<BlockConflict>,
+}
 
-/// Merge three markdown documents using YAML frontmatter field merge + line-level body
-/// merge.
+/// Merge three markdown documents using YAML frontmatter field merge + block-level
+/// body merge.
 ///
 /// # Arguments
 /// - `base` — common ancestor markdown content
@@ -32,20 +49,39 @@ use crate::{MergeConflict, MergeError};
 /// - `Err(MergeError::ParseFailure)` when any input cannot be parsed (propagated from
 ///   frontmatter YAML merge).
 /// - `Err(MergeError::Conflict)` when the body has overlapping edits that cannot be
-///   auto-merged. The inner `MergeConflict.conflicting_ids` contains a human-readable
-///   description (the text of the conflict markers).
+///   auto-merged. The inner `MergeConflict.conflicting_ids` holds one entry per
+///   conflicting block: its location and its conflict markers.
 ///
-/// # Body merge behaviour
-/// The body is passed directly to `diffy::merge` which performs a line-level three-way
-/// merge.  `Ok` from `diffy` means the merge was clean; `Err` from `diffy` means
-/// conflict markers were inserted into the output text.  We propagate that as a
-/// `MergeError::Conflict`.
+/// Use [`merge_md_report`] to also get the merged document when there are conflicts.
 pub fn merge_md(
     base: &str,
     ours: &str,
     theirs: &str,
     opts: &MergeOpts,
 ) -> Result<String, MergeError> {
+    let report = merge_md_report(base, ours, theirs, opts)?;
+    if report.conflicts.is_empty() {
+        Ok(report.merged)
+    } else {
+        Err(MergeError::Conflict(MergeConflict {
+            conflicting_ids: report.conflicts.iter().map(|c| c.to_string()).collect(),
+        }))
+    }
+}
+
+/// Merge three markdown documents like [`merge_md`], returning the merged document
+/// even when body blocks conflict.
+///
+/// # Returns
+/// - `Ok(MdMerge)` — `merged` is the whole document, with conflict markers around each
+///   entry of `conflicts`; an empty `conflicts` means a clean merge.
+/// - `Err(MergeError::ParseFailure)` when the frontmatter of any input cannot be parsed.
+pub fn merge_md_report(
+    base: &str,
+    ours: &str,
+    theirs: &str,
+    opts: &MergeOpts,
+) -> Result<MdMerge, MergeError> {
     let base_parts = split_frontmatter(base);
     let ours_parts = split_frontmatter(ours);
     let theirs_parts = split_frontmatter(theirs);
@@ -65,7 +101,9 @@ pub fn merge_md(
             let ours_fm = ours_parts.frontmatter.as_deref().unwrap_or("");
             let theirs_fm = theirs_parts.frontmatter.as_deref().unwrap_or("");
 
-            let merged = merge_yaml(base_fm, ours_fm, theirs_fm, opts)?;
+            let (base_fm, ours_fm, theirs_fm) =
+                with_merged_comment_log(base_fm, ours_fm, theirs_fm, opts.fallback_precedence);
+            let merged = merge_yaml(&base_fm, &ours_fm, &theirs_fm, opts)?;
             // An empty merged frontmatter (all fields removed) yields None, collapsing the
             // fences.
             if merged.trim().is_empty() {
@@ -77,21 +115,130 @@ pub fn merge_md(
     };
 
     // --- Merge the body ---
-    let base_body = &base_parts.body;
-    let ours_body = &ours_parts.body;
-    let theirs_body = &theirs_parts.body;
-
-    let merged_body = diffy::merge(base_body, ours_body, theirs_body).map_err(|conflict_text| {
-        MergeError::Conflict(MergeConflict {
-            conflicting_ids: vec![conflict_text],
-        })
-    })?;
+    let body = merge_body(&base_parts.body, &ours_parts.body, &theirs_parts.body);
 
     // --- Reassemble ---
-    Ok(join_frontmatter(
-        merged_frontmatter.as_deref(),
-        &merged_body,
-    ))
+    Ok(MdMerge {
+        merged: join_frontmatter(merged_frontmatter.as_deref(), &body.merged),
+        conflicts: body.conflicts,
+    })
+}
+
+/// Merge the comment log of three frontmatter blocks member by member and write the
+/// result into all three, so the field merge that follows sees no change to it.
+///
+/// Members are matched by `id`: a comment added on either side is kept, one deleted
+/// on a side (and not edited on the other) is dropped, and one edited differently on
+/// both sides is settled by `precedence`.  The log is kept in ascending `id` order,
+/// which is creation order for the ULID ids comments use.  Entries without an
+/// `id` are matched by content and follow the others.
+///
+/// Frontmatter that does not parse, or has no comment log on any side, is returned
+/// unchanged; the field merge reports parse failures.
+fn with_merged_comment_log(
+    base: &str,
+    ours: &str,
+    theirs: &str,
+    precedence: Precedence,
+) -> (String, String, String) {
+    let unchanged = || (base.to_owned(), ours.to_owned(), theirs.to_owned());
+    let parse = |yaml: &str| -> Option<Value> {
+        if yaml.trim().is_empty() {
+            return Some(Value::Mapping(Default::default()));
+        }
+        serde_yaml_ng::from_str::<Value>(yaml)
+            .ok()
+            .filter(|v| v.is_mapping())
+    };
+    let (Some(mut base_v), Some(mut ours_v), Some(mut theirs_v)) =
+        (parse(base), parse(ours), parse(theirs))
+    else {
+        return unchanged();
+    };
+    let log =
+        |v: &Value| -> Option<Vec<Value>> { v.get(COMMENT_LOG_FIELD)?.as_sequence().cloned() };
+    let (base_log, ours_log, theirs_log) = (log(&base_v), log(&ours_v), log(&theirs_v));
+    if ours_log.is_none() && theirs_log.is_none() {
+        return unchanged();
+    }
+
+    let base_log = base_log.unwrap_or_default();
+    let ours_log = ours_log.unwrap_or_default();
+    let theirs_log = theirs_log.unwrap_or_default();
+    let member_id = |m: &Value| m.get("id").and_then(Value::as_str).map(str::to_owned);
+    let find = |log: &[Value], id: &str| {
+        log.iter()
+            .find(|m| member_id(m).as_deref() == Some(id))
+            .cloned()
+    };
+
+    let mut ids: Vec<String> = base_log
+        .iter()
+        .chain(&ours_log)
+        .chain(&theirs_log)
+        .filter_map(member_id)
+        .collect();
+    ids.sort();
+    ids.dedup();
+
+    let mut merged = Vec::with_capacity(ids.len());
+    for id in &ids {
+        let b = find(&base_log, id);
+        let o = find(&ours_log, id);
+        let t = find(&theirs_log, id);
+        let chosen = match (b, o, t) {
+            (_, None, None) => None,
+            (None, Some(o), None) => Some(o),
+            (None, None, Some(t)) => Some(t),
+            // Deleted on one side: the deletion wins unless the other side edited it.
+            (Some(b), None, Some(t)) => (t != b).then_some(t),
+            (Some(b), Some(o), None) => (o != b).then_some(o),
+            (Some(b), Some(o), Some(t)) if o == b => Some(t),
+            (_, Some(o), Some(t)) if o == t => Some(o),
+            (Some(b), Some(o), Some(t)) if t == b => Some(o),
+            (_, Some(o), Some(t)) => Some(match precedence {
+                Precedence::Ours => o,
+                Precedence::Theirs => t,
+            }),
+        };
+        merged.extend(chosen);
+    }
+
+    // Entries without an id are matched by content: one added on either side is
+    // kept, one from the base is kept unless a side deleted it.
+    let without_id = |log: &[Value]| -> Vec<Value> {
+        log.iter()
+            .filter(|m| member_id(m).is_none())
+            .cloned()
+            .collect()
+    };
+    let (base_rest, ours_rest, theirs_rest) = (
+        without_id(&base_log),
+        without_id(&ours_log),
+        without_id(&theirs_log),
+    );
+    for entry in ours_rest.iter().chain(&theirs_rest) {
+        let kept = if base_rest.contains(entry) {
+            ours_rest.contains(entry) && theirs_rest.contains(entry)
+        } else {
+            true
+        };
+        if kept && !merged.contains(entry) {
+            merged.push(entry.clone());
+        }
+    }
+
+    let merged = Value::Sequence(merged);
+    for side in [&mut base_v, &mut ours_v, &mut theirs_v] {
+        if let Value::Mapping(map) = side {
+            map.insert(Value::String(COMMENT_LOG_FIELD.to_owned()), merged.clone());
+        }
+    }
+    let to_yaml = |v: &Value| serde_yaml_ng::to_string(v).ok();
+    match (to_yaml(&base_v), to_yaml(&ours_v), to_yaml(&theirs_v)) {
+        (Some(b), Some(o), Some(t)) => (b, o, t),
+        _ => unchanged(),
+    }
 }
 
 #[cfg(test)]
@@ -345,4 +492,73 @@ mod tests {
         );
         assert!(merged.contains("Body."), "body should be preserved");
     }
+
+    /// Comments appended on each branch are both kept, in id order, and a box
+    /// ticked on each side lands from both.
+    #[test]
+    fn comment_log_and_checklist_merge_cleanly() {
+        let base =
+            "---\ntitle: Task\ncomments:\n- id: 01a\n  text: first\n---\n- [ ] one\n- [ ] two\n";
+        let ours = "---\ntitle: Task\ncomments:\n- id: 01a\n  text: first\n- id: 01c\n  text: ours\n---\n- [x] one\n- [ ] two\n";
+        let theirs = "---\ntitle: Task\ncomments:\n- id: 01a\n  text: first\n- id: 01b\n  text: theirs\n---\n- [ ] one\n- [x] two\n";
+
+        let report = merge_md_report(base, ours, theirs, &default_opts()).expect("should parse");
+        assert!(report.conflicts.is_empty(), "{:?}", report.conflicts);
+
+        let parts = split_frontmatter(&report.merged);
+        assert_eq!(parts.body, "- [x] one\n- [x] two\n");
+        let fm: serde_yaml_ng::Value =
+            serde_yaml_ng::from_str(parts.frontmatter.as_deref().unwrap()).unwrap();
+        let ids: Vec<&str> = fm["comments"]
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .map(|c| c["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(ids, ["01a", "01b", "01c"]);
+    }
+
+    /// Comment entries without an id survive the merge; one deleted on a side
+    /// stays deleted.
+    #[test]
+    fn comment_log_keeps_entries_without_id() {
+        let base = "---\ncomments:\n- text: legacy\n- text: stale\n---\nBody.\n";
+        let ours =
+            "---\ncomments:\n- text: legacy\n- text: stale\n- id: 01a\n  text: ours\n---\nBody.\n";
+        let theirs = "---\ncomments:\n- text: legacy\n- text: added\n---\nBody.\n";
+
+        let report = merge_md_report(base, ours, theirs, &default_opts()).expect("should parse");
+        assert!(report.conflicts.is_empty(), "{:?}", report.conflicts);
+
+        let parts = split_frontmatter(&report.merged);
+        let fm: serde_yaml_ng::Value =
+            serde_yaml_ng::from_str(parts.frontmatter.as_deref().unwrap()).unwrap();
+        let texts: Vec<&str> = fm["comments"]
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .map(|c| c["text"].as_str().unwrap())
+            .collect();
+        assert_eq!(texts, ["ours", "legacy", "added"]);
+    }
+
+    /// Each conflicting body block is its own entry, with the merged document
+    /// still available from the report.
+    #[test]
+    fn conflicts_are_reported_per_block() {
+        let base = "# A\nalpha\n# B\nbeta\n";
+        let ours = "# A\nalpha ours\n# B\nbeta ours\n";
+        let theirs = "# A\nalpha theirs\n# B\nbeta theirs\n";
+
+        let report = merge_md_report(base, ours, theirs, &default_opts()).expect("should parse");
+        assert_eq!(report.conflicts.len(), 2);
+        assert_eq!(report.conflicts[1].heading.as_deref(), Some("# B"));
+        assert!(report.merged.contains("# B\n<<<<<<< ours\nbeta ours\n"));
+
+        let err = merge_md(base, ours, theirs, &default_opts()).expect_err("conflict expected");
+        match err {
+            crate::MergeError::Conflict(c) => assert_eq!(c.conflicting_ids.len(), 2),
+            other => panic!("expected MergeError::Conflict, got: {other:?}"),
+        }
+    }
 }
@@ -35,14 +35,22 @@ pub mod config;
 /// Shell command security validation and control system
 pub mod security;
 
+/// Parsing of shell command lines into the simple commands they run
+pub mod syntax;
+
+/// Linux sandbox for shell commands
+pub mod sandbox;
+
 /// Performance monitoring and profiling for shell command execution
 pub mod performance;
 
 // Re-export config types
 pub use config::{
-    evaluate_command, load_shell_config, load_shell_config_from_paths, parse_shell_config,
-    CompiledRule, CompiledShellConfig, PatternCompileError, PatternRule, ShellSecurityConfig,
-    ShellSettings, BUILTIN_CONFIG_YAML, DEFAULT_MAX_COMMAND_LENGTH, DEFAULT_MAX_ENV_VALUE_LENGTH,
+    evaluate_command, evaluate_command_at, evaluate_command_in, load_shell_config,
+    load_shell_config_from_paths, parse_shell_config, CommandRule, CompiledCommandRule,
+    CompiledRule, CompiledShellConfig, PathScope, PatternCompileError, PatternRule, SandboxConfig,
+    ShellSecurityConfig, ShellSettings, BUILTIN_CONFIG_YAML, DEFAULT_MAX_COMMAND_LENGTH,
+    DEFAULT_MAX_ENV_VALUE_LENGTH,
 };
 
 // Re-export core types for convenience
@@ -51,6 +59,10 @@ pub use security::{
     ShellSecurityPolicy, ShellSecurityValidator,
 };
 
+pub use syntax::{parse_command_line, SimpleCommand, Word};
+
+pub use sandbox::{load_sandbox, Sandbox, SandboxAccess};
+
 pub use performance::{
     PerformanceConfig, PerformanceStatistics, ShellPerformanceMetrics, ShellPerformanceProfiler,
 };
@@ -58,7 +70,8 @@ pub use performance::{
 // Re-export workflow validation functions that are heavily used by swissarmyhammer-tools
 // These were previously in swissarmyhammer::workflow but are shell-specific
 pub use security::{
-    validate_command, validate_environment_variables_security, validate_working_directory_security,
+    validate_command, validate_command_at, validate_command_in,
+    validate_environment_variables_security, validate_working_directory_security,
 };
 
 /// Result type for shell operations
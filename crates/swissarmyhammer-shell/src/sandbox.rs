@@ -0,0 +1,590 @@
+//! Linux sandbox for shell commands.
+//!
+//! Validation decides whether a command may run; the sandbox limits what it
+//! can do once it does. A sandboxed command runs under a Landlock ruleset that
+//! lets it read and execute anything but write only beneath the workspace, the
+//! configured writable directories, and a few device files shells write to.
+//! When the network is blocked, a seccomp filter makes every IPv4 and IPv6
+//! `socket` call fail with `ENONET`, an error `socket` never returns on its
+//! own.
+//!
+//! The rules are built in this process by [`Sandbox::confine`] and entered by
+//! the child between fork and exec, so they cover the shell and everything it
+//! starts, and nothing else. The kernel refuses a forbidden access with an
+//! error the command sees, not with a signal; [`Sandbox::violation`] reads the
+//! command's output for those errors to report them. A refused socket is told
+//! apart by its errno. A refused write gets the same `EACCES` as any other
+//! permission error, so it only counts when the path it names lies outside
+//! the sandbox's writable directories and this unconfined process could have
+//! written it.
+//!
+//! The sandbox is opt-in, configured in the `sandbox` section of the stacked
+//! shell config (see [`crate::config::SandboxConfig`]). Where it is enabled but
+//! cannot be set up — another OS, or a kernel without Landlock — commands are
+//! refused with [`ShellSecurityError::SandboxUnavailable`] rather than run
+//! unconfined.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use tracing::warn;
+
+use crate::config::SandboxConfig;
+use crate::security::ShellSecurityError;
+
+/// How a command reports the error the kernel gives a write the Landlock
+/// ruleset does not allow (`EACCES`), after the path it names.
+const WRITE_DENIED_SUFFIX: &str = ": Permission denied";
+
+/// How the C library describes the error the seccomp filter gives a blocked
+/// socket (`ENONET`).
+const NETWORK_DENIED_MESSAGE: &str = "Machine is not on the network";
+
+/// Quotes commands put around the path in an error message.
+const PATH_QUOTES: &[char] = &['\'', '"', '`', '\u{2018}', '\u{2019}'];
+
+/// An access the sandbox refuses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SandboxAccess {
+    /// Writing outside the workspace and the writable directories.
+    Write,
+    /// Opening an IPv4 or IPv6 socket.
+    Network,
+}
+
+impl fmt::Display for SandboxAccess {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SandboxAccess::Write => {
+                write!(
+                    f,
+                    "write access outside the workspace and writable directories"
+                )
+            }
+            SandboxAccess::Network => write!(f, "network access"),
+        }
+    }
+}
+
+/// The sandbox commands run from one workspace are confined to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sandbox {
+    writable: Vec<PathBuf>,
+    block_network: bool,
+}
+
+impl Sandbox {
+    /// The sandbox `config` describes for commands run from `workspace`, or
+    /// `None` when the sandbox is off.
+    ///
+    /// Writable directories that do not exist are left out with a warning.
+    ///
+    /// # Errors
+    ///
+    /// Reports [`ShellSecurityError::SandboxUnavailable`] when the sandbox is
+    /// on but this system cannot enforce it.
+    pub fn from_config(
+        config: &SandboxConfig,
+        workspace: &Path,
+    ) -> Result<Option<Self>, ShellSecurityError> {
+        if !config.is_enabled() {
+            return Ok(None);
+        }
+        platform::check_available()?;
+
+        let mut writable = vec![workspace.to_path_buf()];
+        for dir in &config.writable {
+            let Some(path) = expand_home(dir) else {
+                warn!("Sandbox writable directory '{}' has no home to expand", dir);
+                continue;
+            };
+            if path.exists() {
+                writable.push(path);
+            } else {
+                warn!(
+                    "Sandbox writable directory {} does not exist; leaving it out",
+                    path.display()
+                );
+            }
+        }
+
+        Ok(Some(Self {
+            writable,
+            block_network: config.blocks_network(),
+        }))
+    }
+
+    /// The directories sandboxed commands may write beneath, the workspace
+    /// first.
+    pub fn writable(&self) -> &[PathBuf] {
+        &self.writable
+    }
+
+    /// Whether sandboxed commands are denied the network.
+    pub fn blocks_network(&self) -> bool {
+        self.block_network
+    }
+
+    /// Make `cmd` enter this sandbox when it is spawned.
+    ///
+    /// The rules are built here; the child only hands them to the kernel
+    /// between fork and exec. A child whose kernel does not enforce them fails
+    /// to spawn.
+    ///
+    /// # Errors
+    ///
+    /// Reports [`ShellSecurityError::SandboxUnavailable`] when the rules
+    /// cannot be built.
+    pub fn confine(&self, cmd: &mut std::process::Command) -> Result<(), ShellSecurityError> {
+        platform::confine(self, cmd)
+    }
+
+    /// The violation the output of a sandboxed command that failed in `cwd`
+    /// points to, if any.
+    ///
+    /// The kernel's refusal reaches the command only as an error, so its
+    /// output is the only trace. A blocked socket reads "Machine is not on
+    /// the network", which nothing but the seccomp filter produces. A
+    /// "Permission denied" counts as a refused write only for a path outside
+    /// the writable directories that the unconfined process may write, so
+    /// ordinary permission errors are not blamed on the sandbox.
+    pub fn violation(&self, command: &str, cwd: &Path, output: &str) -> Option<ShellSecurityError> {
+        let access = if self.block_network && output.contains(NETWORK_DENIED_MESSAGE) {
+            SandboxAccess::Network
+        } else if denied_paths(output, cwd).any(|path| self.refuses_write(&path)) {
+            SandboxAccess::Write
+        } else {
+            return None;
+        };
+        Some(ShellSecurityError::SandboxViolation {
+            access,
+            command: command.to_string(),
+        })
+    }
+}
+
+impl Sandbox {
+    /// Whether a write to `path` fails only because of this sandbox: the
+    /// path is outside every writable directory and the process would be
+    /// allowed to write it without the sandbox.
+    fn refuses_write(&self, path: &Path) -> bool {
+        !self.writable.iter().any(|dir| path.starts_with(dir)) && platform::could_write(path)
+    }
+}
+
+/// The paths named by the "Permission denied" errors in `output`, with
+/// relative paths taken from `cwd`.
+///
+/// Commands put the path last before the error, as in `touch: cannot touch
+/// '/etc/x': Permission denied` or `sh: 1: cannot create /etc/x: Permission
+/// denied`.
+fn denied_paths<'a>(output: &'a str, cwd: &'a Path) -> impl Iterator<Item = PathBuf> + 'a {
+    output.lines().filter_map(move |line| {
+        let (before, _) = line.split_once(WRITE_DENIED_SUFFIX)?;
+        let named = before
+            .rsplit(char::is_whitespace)
+            .next()?
+            .trim_matches(PATH_QUOTES);
+        (!named.is_empty()).then(|| cwd.join(named))
+    })
+}
+
+/// Load the sandbox the stacked shell config puts commands run from
+/// `workspace` in, or `None` when it is off.
+///
+/// Reads `builtin/shell/config.yaml` → `~/.shell/config.yaml` →
+/// `./.shell/config.yaml` fresh on each call, like [`crate::load_validator`].
+pub fn load_sandbox(workspace: &Path) -> Result<Option<Sandbox>, ShellSecurityError> {
+    Sandbox::from_config(&crate::config::load_shell_config().sandbox, workspace)
+}
+
+/// `dir` with a leading `~` replaced by the home directory, or `None` when
+/// there is no home to replace it with.
+fn expand_home(dir: &str) -> Option<PathBuf> {
+    match dir.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => {
+            let home = std::env::var_os("HOME")?;
+            Some(PathBuf::from(home).join(rest.trim_start_matches('/')))
+        }
+        _ => Some(PathBuf::from(dir)),
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::collections::BTreeMap;
+    use std::ffi::CString;
+    use std::io;
+    use std::os::unix::ffi::OsStrExt;
+    use std::os::unix::process::CommandExt;
+    use std::path::Path;
+    use std::sync::Mutex;
+
+    use landlock::{
+        path_beneath_rules, Access, AccessFs, Ruleset, RulesetAttr, RulesetCreated,
+        RulesetCreatedAttr, RulesetStatus, ABI,
+    };
+    use seccompiler::{
+        BpfProgram, SeccompAction, SeccompCmpArgLen, SeccompCmpOp, SeccompCondition, SeccompFilter,
+        SeccompRule,
+    };
+
+    use super::Sandbox;
+    use crate::security::ShellSecurityError;
+
+    /// Device files every sandboxed command may write, since shells redirect
+    /// to them all the time.
+    const DEVICE_FILES: &[&str] = &["/dev/null", "/dev/zero", "/dev/full", "/dev/tty"];
+
+    /// The newest Landlock ABI whose rights the ruleset asks for. Older
+    /// kernels enforce the subset they know.
+    const LANDLOCK_ABI: ABI = ABI::V3;
+
+    /// `LANDLOCK_CREATE_RULESET_VERSION`: asks `landlock_create_ruleset` for
+    /// the ABI version instead of a ruleset.
+    const LANDLOCK_CREATE_RULESET_VERSION: libc::c_uint = 1;
+
+    fn unavailable(reason: impl std::fmt::Display) -> ShellSecurityError {
+        ShellSecurityError::SandboxUnavailable {
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Whether this process may write `path`, or create it when it does not
+    /// exist yet.
+    pub(super) fn could_write(path: &Path) -> bool {
+        let writable = |path: &Path| {
+            let Ok(path) = CString::new(path.as_os_str().as_bytes()) else {
+                return false;
+            };
+            // SAFETY: `path` is a valid NUL-terminated string for the call.
+            unsafe { libc::access(path.as_ptr(), libc::W_OK) == 0 }
+        };
+        if path.symlink_metadata().is_ok() {
+            return writable(path);
+        }
+        path.parent().is_some_and(writable)
+    }
+
+    pub(super) fn check_available() -> Result<(), ShellSecurityError> {
+        // SAFETY: with a null attribute and the version flag the call only
+        // reports the ABI version.
+        let abi = unsafe {
+            libc::syscall(
+                libc::SYS_landlock_create_ruleset,
+                std::ptr::null::<libc::c_void>(),
+                0usize,
+                LANDLOCK_CREATE_RULESET_VERSION,
+            )
+        };
+        if abi < 1 {
+            return Err(unavailable(
+                "this kernel does not support Landlock (Linux 5.13 or later, with \
+                 Landlock enabled, is required)",
+            ));
+        }
+        Ok(())
+    }
+
+    pub(super) fn confine(
+        sandbox: &Sandbox,
+        cmd: &mut std::process::Command,
+    ) -> Result<(), ShellSecurityError> {
+        let ruleset = Mutex::new(Some(ruleset(sandbox).map_err(unavailable)?));
+        let network_filter = if sandbox.block_network {
+            Some(network_filter()?)
+        } else {
+            None
+        };
+
+        // SAFETY: the closure runs in the child between fork and exec, where
+        // only async-signal-safe work is allowed. The ruleset and the filter
+        // were built above; what is left are the prctl, landlock and seccomp
+        // syscalls that hand them to the kernel. The mutex is reached by no
+        // one but this closure, so it is never held across the fork. Errors
+        // are built from errno values with `from_raw_os_error` and
+        // `last_os_error`, which do not allocate, and a missing ruleset fails
+        // the spawn rather than run the command unconfined.
+        unsafe {
+            cmd.pre_exec(move || {
+                let ruleset = ruleset.lock().ok().and_then(|mut ruleset| ruleset.take());
+                let Some(ruleset) = ruleset else {
+                    return Err(io::Error::from_raw_os_error(libc::EINVAL));
+                };
+                let status = ruleset
+                    .restrict_self()
+                    .map_err(|_| io::Error::last_os_error())?;
+                if status.ruleset == RulesetStatus::NotEnforced {
+                    return Err(io::Error::from_raw_os_error(libc::EOPNOTSUPP));
+                }
+                if let Some(filter) = &network_filter {
+                    seccompiler::apply_filter(filter).map_err(|_| io::Error::last_os_error())?;
+                }
+                Ok(())
+            });
+        }
+        Ok(())
+    }
+
+    /// Read and execute anywhere; write beneath the writable directories and
+    /// to the device files.
+    fn ruleset(sandbox: &Sandbox) -> Result<RulesetCreated, landlock::RulesetError> {
+        Ruleset::default()
+            .handle_access(AccessFs::from_all(LANDLOCK_ABI))?
+            .create()?
+            .add_rules(path_beneath_rules(["/"], AccessFs::from_read(LANDLOCK_ABI)))?
+            .add_rules(path_beneath_rules(
+                &sandbox.writable,
+                AccessFs::from_all(LANDLOCK_ABI),
+            ))?
+            .add_rules(path_beneath_rules(
+                DEVICE_FILES
+                    .iter()
+                    .filter(|file| std::path::Path::new(file).exists()),
+                AccessFs::from_all(LANDLOCK_ABI),
+            ))
+    }
+
+    /// A filter that fails `socket(AF_INET, ...)` and `socket(AF_INET6, ...)`
+    /// with `ENONET` and allows everything else.
+    fn network_filter() -> Result<BpfProgram, ShellSecurityError> {
+        let domain = |family: libc::c_int| {
+            SeccompCondition::new(0, SeccompCmpArgLen::Dword, SeccompCmpOp::Eq, family as u64)
+                .and_then(|condition| SeccompRule::new(vec![condition]))
+        };
+        let rules = vec![domain(libc::AF_INET), domain(libc::AF_INET6)]
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(unavailable)?;
+
+        let arch = std::env::consts::ARCH.try_into().map_err(|_| {
+            unavailable(format!(
+                "seccomp filters are not supported on {}",
+                std::env::consts::ARCH
+            ))
+        })?;
+        let filter = SeccompFilter::new(
+            BTreeMap::from([(libc::SYS_socket, rules)]),
+            SeccompAction::Allow,
+            SeccompAction::Errno(libc::ENONET as u32),
+            arch,
+        )
+        .map_err(unavailable)?;
+        filter.try_into().map_err(unavailable)
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod platform {
+    use super::Sandbox;
+    use crate::security::ShellSecurityError;
+
+    fn unavailable() -> ShellSecurityError {
+        ShellSecurityError::SandboxUnavailable {
+            reason: format!(
+                "the sandbox needs Linux Landlock, and this is {}",
+                std::env::consts::OS
+            ),
+        }
+    }
+
+    pub(super) fn check_available() -> Result<(), ShellSecurityError> {
+        Err(unavailable())
+    }
+
+    pub(super) fn could_write(_path: &std::path::Path) -> bool {
+        false
+    }
+
+    pub(super) fn confine(
+        _sandbox: &Sandbox,
+        _cmd: &mut std::process::Command,
+    ) -> Result<(), ShellSecurityError> {
+        Err(unavailable())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sandbox(block_network: bool) -> Sandbox {
+        Sandbox {
+            writable: vec![PathBuf::from("/work/project")],
+            block_network,
+        }
+    }
+
+    #[test]
+    fn test_disabled_sandbox_is_none() {
+        let sandbox = Sandbox::from_config(&SandboxConfig::default(), Path::new("/work"));
+        assert!(matches!(sandbox, Ok(None)));
+    }
+
+    #[test]
+    fn test_expand_home() {
+        let home = PathBuf::from(std::env::var_os("HOME").unwrap());
+        assert_eq!(expand_home("~"), Some(home.clone()));
+        assert_eq!(expand_home("~/.cache"), Some(home.join(".cache")));
+        assert_eq!(expand_home("/tmp"), Some(PathBuf::from("/tmp")));
+        assert_eq!(expand_home("~other"), Some(PathBuf::from("~other")));
+    }
+
+    #[test]
+    fn test_denied_paths() {
+        let cwd = Path::new("/work/project");
+        let output = "touch: cannot touch '/etc/x': Permission denied\n\
+                      sh: 1: cannot create build/out: Permission denied\n\
+                      mkdir: cannot create directory \u{2018}/opt/y\u{2019}: Permission denied\n\
+                      ok\n";
+        let paths: Vec<PathBuf> = denied_paths(output, cwd).collect();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("/etc/x"),
+                PathBuf::from("/work/project/build/out"),
+                PathBuf::from("/opt/y"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_violation_reads_the_kernel_errors() {
+        let cwd = Path::new("/work/project");
+        let violation = sandbox(true).violation(
+            "curl https://example.com",
+            cwd,
+            "curl: (7) Couldn't connect: Machine is not on the network",
+        );
+        assert!(matches!(
+            violation,
+            Some(ShellSecurityError::SandboxViolation {
+                access: SandboxAccess::Network,
+                ..
+            })
+        ));
+
+        assert!(sandbox(true).violation("false", cwd, "").is_none());
+        assert!(sandbox(false)
+            .violation("curl x", cwd, NETWORK_DENIED_MESSAGE)
+            .is_none());
+        // Another address family error is the host's, not the filter's.
+        assert!(sandbox(true)
+            .violation("curl x", cwd, "Address family not supported by protocol")
+            .is_none());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_violation_blames_only_writes_the_sandbox_refuses() {
+        let cwd = Path::new("/work/project");
+        let outside = tempfile::TempDir::new().unwrap();
+        let target = outside.path().join("x");
+        let output = format!(
+            "touch: cannot touch '{}': Permission denied",
+            target.display()
+        );
+        let violation = sandbox(false).violation("touch x", cwd, &output);
+        assert!(matches!(
+            violation,
+            Some(ShellSecurityError::SandboxViolation {
+                access: SandboxAccess::Write,
+                ..
+            })
+        ));
+        assert!(violation
+            .unwrap()
+            .to_string()
+            .contains("write access outside the workspace"));
+
+        // Inside the writable directories the refusal is the filesystem's.
+        let inside = "touch: cannot touch 'notes.txt': Permission denied";
+        assert!(sandbox(false).violation("touch x", cwd, inside).is_none());
+        // So is a path nobody may create, the sandbox or not.
+        let missing = format!(
+            "touch: cannot touch '{}': Permission denied",
+            outside.path().join("no/such/dir/x").display()
+        );
+        assert!(sandbox(false).violation("touch x", cwd, &missing).is_none());
+    }
+
+    #[cfg(target_os = "linux")]
+    mod linux {
+        use super::super::*;
+        use std::process::Command;
+
+        /// The sandbox for `workspace`, or `None` on a kernel without Landlock,
+        /// where these tests have nothing to check.
+        fn enabled(workspace: &Path, block_network: bool) -> Option<Sandbox> {
+            let config = SandboxConfig {
+                enabled: Some(true),
+                writable: vec![],
+                block_network: Some(block_network),
+            };
+            Sandbox::from_config(&config, workspace).ok().flatten()
+        }
+
+        fn run(sandbox: &Sandbox, script: &str, dir: &Path) -> std::process::Output {
+            let mut cmd = Command::new("sh");
+            cmd.args(["-c", script]).current_dir(dir);
+            sandbox.confine(&mut cmd).expect("confine");
+            cmd.output().expect("run sandboxed command")
+        }
+
+        #[test]
+        fn test_sandbox_writes_only_inside_the_workspace() {
+            let workspace = tempfile::TempDir::new().unwrap();
+            let outside = tempfile::TempDir::new().unwrap();
+            let Some(sandbox) = enabled(workspace.path(), false) else {
+                return;
+            };
+
+            let inside = run(
+                &sandbox,
+                "echo ok > inside.txt && cat inside.txt",
+                workspace.path(),
+            );
+            assert!(inside.status.success(), "{inside:?}");
+
+            let target = outside.path().join("outside.txt");
+            let script = format!("echo no > '{}'", target.display());
+            let denied = run(&sandbox, &script, workspace.path());
+            assert!(!denied.status.success());
+            assert!(!target.exists());
+            let stderr = String::from_utf8_lossy(&denied.stderr);
+            assert!(
+                sandbox
+                    .violation(&script, workspace.path(), &stderr)
+                    .is_some(),
+                "stderr: {stderr}"
+            );
+
+            let devnull = run(&sandbox, "echo quiet > /dev/null", workspace.path());
+            assert!(devnull.status.success(), "{devnull:?}");
+        }
+
+        #[test]
+        fn test_sandbox_blocks_inet_sockets() {
+            let workspace = tempfile::TempDir::new().unwrap();
+            let Some(sandbox) = enabled(workspace.path(), true) else {
+                return;
+            };
+            let mut cmd = Command::new("sh");
+            cmd.arg("-c").arg("exit 0");
+            sandbox.confine(&mut cmd).expect("confine");
+            // SAFETY: runs after the sandbox's own hook; only calls socket(2).
+            unsafe {
+                std::os::unix::process::CommandExt::pre_exec(&mut cmd, || {
+                    let fd = libc::socket(libc::AF_INET, libc::SOCK_STREAM, 0);
+                    if fd >= 0 {
+                        libc::close(fd);
+                        return Err(std::io::Error::other("socket was allowed"));
+                    }
+                    Ok(())
+                });
+            }
+            let status = cmd.status().expect("run sandboxed command");
+            assert!(status.success());
+        }
+    }
+}
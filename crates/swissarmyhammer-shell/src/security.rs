@@ -80,6 +80,22 @@ pub enum ShellSecurityError {
         /// Reason for the validation failure
         reason: String,
     },
+
+    /// Sandboxed command was refused an access the sandbox does not grant
+    #[error("Sandbox denied {access} in command: {command}")]
+    SandboxViolation {
+        /// The access that was refused
+        access: crate::sandbox::SandboxAccess,
+        /// The command that attempted it
+        command: String,
+    },
+
+    /// Sandbox is enabled but cannot be set up for the command
+    #[error("Sandbox unavailable: {reason}")]
+    SandboxUnavailable {
+        /// Why the sandbox cannot be set up
+        reason: String,
+    },
 }
 
 impl Severity for ShellSecurityError {
@@ -88,6 +104,7 @@ impl Severity for ShellSecurityError {
             // Critical: Security violations and unauthorized access attempts
             ShellSecurityError::BlockedCommandPattern { .. } => ErrorSeverity::Critical,
             ShellSecurityError::DirectoryAccessDenied { .. } => ErrorSeverity::Critical,
+            ShellSecurityError::SandboxViolation { .. } => ErrorSeverity::Critical,
 
             // Error: Validation failures that prevent operation but aren't security violations
             ShellSecurityError::CommandTooLong { .. } => ErrorSeverity::Error,
@@ -95,6 +112,7 @@ impl Severity for ShellSecurityError {
             ShellSecurityError::InvalidEnvironmentVariable { .. } => ErrorSeverity::Error,
             ShellSecurityError::InvalidEnvironmentVariableValue { .. } => ErrorSeverity::Error,
             ShellSecurityError::ValidationFailed { .. } => ErrorSeverity::Error,
+            ShellSecurityError::SandboxUnavailable { .. } => ErrorSeverity::Error,
         }
     }
 }
@@ -108,6 +126,10 @@ pub struct ShellSecurityPolicy {
     /// List of blocked command patterns (regex patterns)
     pub blocked_commands: Vec<String>,
 
+    /// Rules that block commands by the programs, flags and paths they run
+    #[serde(default)]
+    pub command_rules: Vec<crate::config::CommandRule>,
+
     /// List of allowed directories for command execution (optional)
     pub allowed_directories: Option<Vec<PathBuf>>,
 
@@ -132,6 +154,7 @@ impl Default for ShellSecurityPolicy {
         Self {
             enable_validation: true,
             blocked_commands: config.deny.into_iter().map(|rule| rule.pattern).collect(),
+            command_rules: config.deny_commands,
             allowed_directories: None, // No directory restrictions by default
             max_command_length: MAX_COMMAND_LENGTH,
             enable_audit_logging: true,
@@ -162,11 +185,13 @@ impl ShellSecurityValidator {
                     reason: format!("Blocked by policy: {}", p),
                 })
                 .collect(),
+            deny_commands: policy.command_rules.clone(),
             settings: crate::config::ShellSettings {
                 max_command_length: policy.max_command_length,
                 max_env_value_length: policy.max_env_value_length,
                 enable_audit_logging: policy.enable_audit_logging,
             },
+            sandbox: crate::config::SandboxConfig::default(),
         };
 
         let compiled_config =
@@ -194,6 +219,7 @@ impl ShellSecurityValidator {
         let policy = ShellSecurityPolicy {
             enable_validation: true,
             blocked_commands: config.deny.iter().map(|r| r.pattern.clone()).collect(),
+            command_rules: config.deny_commands.clone(),
             allowed_directories: None,
             max_command_length: config.settings.max_command_length,
             enable_audit_logging: config.settings.enable_audit_logging,
@@ -211,8 +237,37 @@ impl ShellSecurityValidator {
         Self::new(ShellSecurityPolicy::default())
     }
 
-    /// Validate a command against the security policy
+    /// Validate a command against the security policy, taking the current
+    /// directory as the workspace
     pub fn validate_command(&self, command: &str) -> std::result::Result<(), ShellSecurityError> {
+        let workspace = std::env::current_dir().unwrap_or_default();
+        self.validate_command_in(command, &workspace)
+    }
+
+    /// Validate a command run from `workspace` against the security policy
+    ///
+    /// Command rules that limit paths to the workspace resolve relative
+    /// paths against `workspace`.
+    pub fn validate_command_in(
+        &self,
+        command: &str,
+        workspace: &Path,
+    ) -> std::result::Result<(), ShellSecurityError> {
+        self.validate_command_at(command, workspace, workspace)
+    }
+
+    /// Validate a command started in `cwd` under `workspace` against the
+    /// security policy
+    ///
+    /// Command rules resolve relative paths against `cwd` and the directory
+    /// changes that precede them in the command, and limit them to
+    /// `workspace`.
+    pub fn validate_command_at(
+        &self,
+        command: &str,
+        workspace: &Path,
+        cwd: &Path,
+    ) -> std::result::Result<(), ShellSecurityError> {
         if !self.policy.enable_validation {
             return Ok(());
         }
@@ -221,7 +276,7 @@ impl ShellSecurityValidator {
         self.check_command_length(command)?;
 
         // Evaluate permit/deny rules (permit-first)
-        crate::config::evaluate_command(command, &self.compiled_config)?;
+        crate::config::evaluate_command_at(command, workspace, cwd, &self.compiled_config)?;
 
         Ok(())
     }
@@ -559,6 +614,31 @@ pub fn validate_command(command: &str) -> std::result::Result<(), ShellSecurityE
     load_validator().validate_command(command)
 }
 
+/// Validate a command run from `workspace` for security issues.
+///
+/// Like [`validate_command`], with relative paths resolved against
+/// `workspace` rather than the current directory.
+pub fn validate_command_in(
+    command: &str,
+    workspace: &Path,
+) -> std::result::Result<(), ShellSecurityError> {
+    load_validator().validate_command_in(command, workspace)
+}
+
+/// Validate a command started in `cwd` under `workspace` for security
+/// issues.
+///
+/// Like [`validate_command_in`], for a command that does not start in the
+/// workspace itself: another `working_directory`, or the directory a
+/// persistent shell was left in.
+pub fn validate_command_at(
+    command: &str,
+    workspace: &Path,
+    cwd: &Path,
+) -> std::result::Result<(), ShellSecurityError> {
+    load_validator().validate_command_at(command, workspace, cwd)
+}
+
 /// Validate working directory access security.
 ///
 /// Checks for path traversal attempts. Does not depend on YAML config.
@@ -879,6 +959,15 @@ mod tests {
             ErrorSeverity::Error,
             "Validation failed should be Error"
         );
+
+        let sandbox_unavailable = ShellSecurityError::SandboxUnavailable {
+            reason: "no Landlock".to_string(),
+        };
+        assert_eq!(
+            sandbox_unavailable.severity(),
+            ErrorSeverity::Error,
+            "Sandbox unavailable should be Error"
+        );
     }
 
     #[test]
@@ -894,6 +983,10 @@ mod tests {
             ShellSecurityError::DirectoryAccessDenied {
                 directory: PathBuf::from("../etc"),
             },
+            ShellSecurityError::SandboxViolation {
+                access: crate::sandbox::SandboxAccess::Write,
+                command: "touch /etc/x".to_string(),
+            },
         ];
 
         for error in security_errors {
@@ -1051,7 +1144,9 @@ mod tests {
                 pattern: r"test_pattern".to_string(),
                 reason: "test reason".to_string(),
             }],
+            deny_commands: vec![],
             settings: crate::config::ShellSettings::default(),
+            sandbox: crate::config::SandboxConfig::default(),
         };
         let validator = ShellSecurityValidator::from_config(&config);
         assert!(validator.is_ok());
@@ -1084,12 +1179,28 @@ mod tests {
                 pattern: r"[invalid(regex".to_string(),
                 reason: "bad pattern".to_string(),
             }],
+            deny_commands: vec![],
             settings: crate::config::ShellSettings::default(),
+            sandbox: crate::config::SandboxConfig::default(),
         };
         let result = ShellSecurityValidator::from_config(&config);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_validate_command_in_measures_paths_from_the_workspace() {
+        let validator = ShellSecurityValidator::with_default_policy().unwrap();
+        let workspace = Path::new("/work/project");
+
+        assert!(validator
+            .validate_command_in("rm -rf target", workspace)
+            .is_ok());
+        assert!(matches!(
+            validator.validate_command_in("rm -rf ../sibling", workspace),
+            Err(ShellSecurityError::BlockedCommandPattern { .. })
+        ));
+    }
+
     #[test]
     fn test_directory_access_backslash_traversal() {
         // Exercises the ..\ path traversal check (line 255-258)
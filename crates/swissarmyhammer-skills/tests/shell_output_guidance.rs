@@ -4,7 +4,7 @@
 //! 1. The op blocks until the command exits (or the timeout kills it).
 //! 2. Do not pipe to `tail`, `head`, or `grep` — read the stored output later
 //!    with `get lines` or `grep history`.
-//! 3. A command the timeout kills stores no output at all.
+//! 3. A command the timeout kills keeps the output it printed until then.
 //! 4. This shell does not search files. The file search tools do, and `rg` is
 //!    the fallback.
 //! 5. This shell does not edit files. The file editing tools do.
@@ -14,10 +14,10 @@
 //! skill text carried only a weak bullet ("skip `| tail` / `| grep`
 //! pipelines"), which did not stop that habit.
 //!
-//! Fact 3 is the limit of fact 2. `finalize_timed_out` only marks the command;
-//! `store_command_output` runs solely in `finalize_completed`. Guidance that
-//! promises stored output without naming that limit sends the agent to
-//! `get lines` for output that was never written.
+//! Fact 3 is the reach of fact 2. `execute command` streams output into the
+//! history as it arrives, so a timed-out command's output is there too.
+//! Guidance that leaves this out sends the agent to rerun a slow command just
+//! to see what it printed.
 //!
 //! Fact 4 is the gap facts 2 and 3 left open. The no-pipe rule is about
 //! discarding captured output, so it says nothing about which tool searches
@@ -43,11 +43,9 @@ const BLOCKING_MARKER: &str = "blocks until the command exits";
 /// States the no-pipe rule.
 const NO_PIPE_MARKER: &str = "Do not pipe to `tail`";
 
-/// States that a timed-out command keeps nothing. `finalize_timed_out` marks
-/// the command and returns; only `finalize_completed` calls
-/// `store_command_output`. Text that promises stored output without this
-/// qualifier sends the agent to `get lines` for output that does not exist.
-const TIMEOUT_MARKER: &str = "no output is stored";
+/// States that a timed-out command keeps what it printed. Output streams into
+/// the history as it arrives, so `get lines` reads it after the timeout.
+const TIMEOUT_MARKER: &str = "output printed before the timeout is kept";
 
 /// The weak bullet this guidance replaced.
 const OLD_WEAK_BULLET: &str = "skip `| tail`";
@@ -90,8 +88,8 @@ fn shell_output_guidance_states_blocking_and_no_tail() {
     assert!(
         body.contains(TIMEOUT_MARKER),
         "builtin skill 'shell' must state that a timed-out command keeps \
-         nothing ('{TIMEOUT_MARKER}'), or the agent reads `get lines` for \
-         output that does not exist"
+         its output ('{TIMEOUT_MARKER}'), or the agent reruns the command to \
+         see it"
     );
     assert!(
         !body.contains(OLD_WEAK_BULLET),
@@ -3,8 +3,12 @@
 //! Each line in the file is a JSON-serialized `ChangelogEntry`. This format
 //! is append-friendly, human-readable with `jq`, and resilient to partial
 //! writes (only the last line can be corrupt).
+//!
+//! Entries written inside [`with_changelog_actor`] record that actor, so a
+//! change can be attributed to whoever made it.
 
 use std::collections::HashSet;
+use std::future::Future;
 use std::io;
 use std::path::PathBuf;
 
@@ -28,6 +32,41 @@ pub enum ChangeOp {
     Archive,
     /// An item was unarchived (restored from `.archive/`).
     Unarchive,
+    /// A run of older entries folded together by [`Changelog::compact_to_snapshot`].
+    ///
+    /// The forward patch carries the item from its state before the folded
+    /// entries to its state after them. A snapshot is never on the undo
+    /// stack, so it cannot be undone or redone.
+    Snapshot,
+}
+
+/// Which entries [`Changelog::compact_to_snapshot`] folds into a snapshot.
+///
+/// An entry is folded when either bound selects it: it is older than the
+/// newest `keep_latest` entries, or it was recorded before `before`. With
+/// neither bound set nothing is folded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompactionPolicy {
+    /// Keep this many of the newest entries as they are.
+    pub keep_latest: Option<usize>,
+    /// Fold entries recorded before this time.
+    pub before: Option<DateTime<Utc>>,
+}
+
+impl CompactionPolicy {
+    /// Number of leading entries of `entries` the policy selects.
+    fn fold_count(&self, entries: &[ChangelogEntry]) -> usize {
+        let by_count = self
+            .keep_latest
+            .map_or(0, |keep| entries.len().saturating_sub(keep));
+        let by_age = self.before.map_or(0, |before| {
+            entries
+                .iter()
+                .position(|e| e.timestamp >= before)
+                .unwrap_or(entries.len())
+        });
+        by_count.max(by_age)
+    }
 }
 
 /// A single entry in the changelog, recording one mutation to the store.
@@ -51,6 +90,9 @@ pub struct ChangelogEntry {
     pub reverse_patch: String,
     /// Optional transaction ID for grouping related changes.
     pub transaction_id: Option<String>,
+    /// Who made the change, when it was made inside [`with_changelog_actor`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
 }
 
 impl ChangelogEntry {
@@ -58,7 +100,8 @@ impl ChangelogEntry {
     ///
     /// `transaction_id` defaults to `None`; callers that need transactional
     /// grouping can set it on the returned value before passing it to
-    /// [`Changelog::append`].
+    /// [`Changelog::append`]. `actor` is taken from an enclosing
+    /// [`with_changelog_actor`].
     ///
     /// Provided primarily for external crates (the type is `#[non_exhaustive]`,
     /// so the struct literal form is only available within this crate).
@@ -78,10 +121,27 @@ impl ChangelogEntry {
             forward_patch,
             reverse_patch,
             transaction_id: None,
+            actor: changelog_actor(),
         }
     }
 }
 
+tokio::task_local! {
+    static CHANGELOG_ACTOR: String;
+}
+
+/// Run `fut` with every changelog entry it writes attributed to `actor`.
+///
+/// The actor is task-local: work spawned onto other tasks is not attributed.
+pub async fn with_changelog_actor<F: Future>(actor: impl Into<String>, fut: F) -> F::Output {
+    CHANGELOG_ACTOR.scope(actor.into(), fut).await
+}
+
+/// The actor set by an enclosing [`with_changelog_actor`], if any.
+pub fn changelog_actor() -> Option<String> {
+    CHANGELOG_ACTOR.try_with(|actor| actor.clone()).ok()
+}
+
 /// Handle for an append-only JSONL changelog file.
 ///
 /// The changelog file is created on first append. Reading a nonexistent
@@ -199,6 +259,86 @@ impl Changelog {
         tokio::fs::write(&self.path, buf.as_bytes()).await?;
         Ok(())
     }
+
+    /// Fold the older entries selected by `policy` into one
+    /// [`ChangeOp::Snapshot`] entry, returning how many entries were folded.
+    ///
+    /// The item's history stays replayable from the empty string: a leading
+    /// `Create` entry is kept (it dates the item), and the snapshot's patch
+    /// goes from the text after it to the text after the last folded entry.
+    /// The snapshot takes that entry's timestamp, so history is exact from
+    /// the snapshot onwards and only the steps inside the folded run are lost.
+    ///
+    /// Entries whose ids are in `pinned` (referenced by the undo stack) are
+    /// never folded, and neither is anything after them, so undo and redo
+    /// keep working. Runs of fewer than two entries are left alone, as is a
+    /// file with lines that are not store entries. The file is rewritten via
+    /// a temporary file and a rename.
+    pub async fn compact_to_snapshot(
+        &self,
+        policy: &CompactionPolicy,
+        pinned: &HashSet<UndoEntryId>,
+    ) -> io::Result<usize> {
+        let contents = match tokio::fs::read_to_string(&self.path).await {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e),
+            Ok(c) => c,
+        };
+        let mut entries = Vec::new();
+        for line in contents.lines().filter(|l| !l.trim().is_empty()) {
+            match serde_json::from_str::<ChangelogEntry>(line) {
+                Ok(entry) => entries.push(entry),
+                Err(_) => return Ok(0),
+            }
+        }
+
+        let start = usize::from(entries.first().is_some_and(|e| e.op == ChangeOp::Create));
+        let pinned_at = entries
+            .iter()
+            .position(|e| pinned.contains(&e.id))
+            .unwrap_or(entries.len());
+        let end = policy.fold_count(&entries).min(pinned_at);
+        if end < start + 2 {
+            return Ok(0);
+        }
+
+        let replay = |text: String, run: &[ChangelogEntry]| -> io::Result<String> {
+            run.iter().try_fold(text, |text, entry| {
+                crate::diff::apply_patch(&text, &entry.forward_patch)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+            })
+        };
+        let before = replay(String::new(), &entries[..start])?;
+        let after = replay(before.clone(), &entries[start..end])?;
+        let (forward_patch, reverse_patch) = crate::diff::create_patches(&before, &after);
+        let last = &entries[end - 1];
+        let snapshot = ChangelogEntry {
+            id: UndoEntryId::new(),
+            timestamp: last.timestamp,
+            op: ChangeOp::Snapshot,
+            item_id: last.item_id.clone(),
+            forward_patch,
+            reverse_patch,
+            transaction_id: None,
+            actor: None,
+        };
+
+        let mut buf = String::new();
+        let kept = entries[..start]
+            .iter()
+            .chain(std::iter::once(&snapshot))
+            .chain(&entries[end..]);
+        for entry in kept {
+            let line = serde_json::to_string(entry)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            buf.push_str(&line);
+            buf.push('\n');
+        }
+        let tmp_path = self.path.with_extension("jsonl.tmp");
+        tokio::fs::write(&tmp_path, buf.as_bytes()).await?;
+        tokio::fs::rename(&tmp_path, &self.path).await?;
+        Ok(end - start)
+    }
 }
 
 #[cfg(test)]
@@ -217,6 +357,7 @@ mod tests {
             forward_patch,
             reverse_patch,
             transaction_id: None,
+            actor: None,
         }
     }
 
@@ -341,6 +482,118 @@ mod tests {
         assert_eq!(entries[0].item_id, StoredItemId::from("task-2"));
     }
 
+    /// Append a chain of entries taking one item through `texts`, one hour
+    /// apart, and return them.
+    async fn append_history(changelog: &Changelog, texts: &[&str]) -> Vec<ChangelogEntry> {
+        let start = Utc::now() - chrono::Duration::hours(texts.len() as i64);
+        let mut previous = "";
+        let mut entries = Vec::new();
+        for (i, &text) in texts.iter().enumerate() {
+            let (forward_patch, reverse_patch) = crate::diff::create_patches(previous, text);
+            let op = if i == 0 {
+                ChangeOp::Create
+            } else {
+                ChangeOp::Update
+            };
+            let entry = ChangelogEntry {
+                timestamp: start + chrono::Duration::hours(i as i64),
+                forward_patch,
+                reverse_patch,
+                ..make_entry("task-1", op)
+            };
+            changelog.append(&entry).await.unwrap();
+            entries.push(entry);
+            previous = text;
+        }
+        entries
+    }
+
+    /// Replay every forward patch from the empty string.
+    fn replay(entries: &[ChangelogEntry]) -> String {
+        entries.iter().fold(String::new(), |text, e| {
+            crate::diff::apply_patch(&text, &e.forward_patch).unwrap()
+        })
+    }
+
+    #[tokio::test]
+    async fn compact_to_snapshot_folds_old_entries_and_keeps_create() {
+        let dir = TempDir::new().unwrap();
+        let changelog = Changelog::new(dir.path().join("changelog.jsonl"));
+        let texts = ["a\n", "b\n", "c\n", "d\n", "e\n", "f\n"];
+        let original = append_history(&changelog, &texts).await;
+
+        let policy = CompactionPolicy {
+            keep_latest: Some(2),
+            before: None,
+        };
+        let folded = changelog
+            .compact_to_snapshot(&policy, &HashSet::new())
+            .await
+            .unwrap();
+        assert_eq!(folded, 3);
+
+        let entries = changelog.read_all().await.unwrap();
+        let ops: Vec<&ChangeOp> = entries.iter().map(|e| &e.op).collect();
+        assert_eq!(
+            ops,
+            [
+                &ChangeOp::Create,
+                &ChangeOp::Snapshot,
+                &ChangeOp::Update,
+                &ChangeOp::Update
+            ]
+        );
+        assert_eq!(entries[0], original[0]);
+        assert_eq!(entries[1].timestamp, original[3].timestamp);
+        assert_eq!(replay(&entries[..2]), "d\n");
+        assert_eq!(replay(&entries), "f\n");
+
+        // Folding again with the same policy finds nothing left to fold.
+        let folded = changelog
+            .compact_to_snapshot(&policy, &HashSet::new())
+            .await
+            .unwrap();
+        assert_eq!(folded, 0);
+    }
+
+    #[tokio::test]
+    async fn compact_to_snapshot_by_age_stops_at_pinned_entries() {
+        let dir = TempDir::new().unwrap();
+        let changelog = Changelog::new(dir.path().join("changelog.jsonl"));
+        let original = append_history(&changelog, &["a\n", "b\n", "c\n", "d\n", "e\n"]).await;
+
+        let policy = CompactionPolicy {
+            keep_latest: None,
+            before: Some(Utc::now()),
+        };
+        let pinned: HashSet<UndoEntryId> = [original[3].id].into_iter().collect();
+        let folded = changelog
+            .compact_to_snapshot(&policy, &pinned)
+            .await
+            .unwrap();
+        assert_eq!(
+            folded, 2,
+            "entries 1 and 2; the pinned entry and later stay"
+        );
+
+        let entries = changelog.read_all().await.unwrap();
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries[1].op, ChangeOp::Snapshot);
+        assert_eq!(&entries[2..], &original[3..]);
+        assert_eq!(replay(&entries), "e\n");
+
+        // A pin right after the create leaves nothing to fold.
+        let pinned: HashSet<UndoEntryId> = [entries[1].id].into_iter().collect();
+        assert_eq!(
+            changelog
+                .compact_to_snapshot(&policy, &pinned)
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(changelog.read_all().await.unwrap(), entries);
+    }
+
     #[tokio::test]
     async fn read_all_skips_corrupt_json_lines() {
         let dir = TempDir::new().unwrap();
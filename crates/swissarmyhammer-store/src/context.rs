@@ -4,6 +4,7 @@
 //! [`ErasedStore`] instances. It dispatches undo/redo to the correct store
 //! and aggregates change events from all stores.
 
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
@@ -69,15 +70,20 @@ pub struct StoreContext {
 ///
 /// Returned by [`StoreContext::begin_undo_group`]. Dropping the guard
 /// clears the context's active group state so subsequent writes are
-/// pushed as independent undo entries again.
+/// pushed as independent undo entries again. A guard returned while a
+/// group was already open does not own it and leaves it open on drop, so
+/// nested commands fold into the outermost group.
 pub struct UndoGroupGuard<'a> {
     ctx: &'a StoreContext,
+    owns: bool,
 }
 
 impl<'a> UndoGroupGuard<'a> {
     /// Explicitly end the group. Equivalent to dropping the guard.
     pub async fn end(self) {
-        self.ctx.end_undo_group().await;
+        if self.owns {
+            self.ctx.end_undo_group().await;
+        }
         std::mem::forget(self);
     }
 }
@@ -88,6 +94,9 @@ impl<'a> Drop for UndoGroupGuard<'a> {
         // explicit `.end().await`. The mutex contention here is bounded
         // — only the command that opened the group holds it — so a
         // blocking lock attempt is safe.
+        if !self.owns {
+            return;
+        }
         if let Ok(mut g) = self.ctx.current_group.try_lock() {
             *g = None;
         }
@@ -124,13 +133,15 @@ impl StoreContext {
     ///
     /// Calling this while a group is already open returns a guard that
     /// reuses the current group id — nested calls do not create
-    /// sub-groups.
+    /// sub-groups, and dropping the nested guard leaves the outer group
+    /// open.
     pub async fn begin_undo_group(&self) -> UndoGroupGuard<'_> {
         let mut g = self.current_group.lock().await;
-        if g.is_none() {
+        let owns = g.is_none();
+        if owns {
             *g = Some(UndoEntryId::new());
         }
-        UndoGroupGuard { ctx: self }
+        UndoGroupGuard { ctx: self, owns }
     }
 
     /// Clear the active undo group. Called by [`UndoGroupGuard::end`]
@@ -308,6 +319,20 @@ impl StoreContext {
         self.stack.read().await.pointer()
     }
 
+    /// Ids of every changelog entry on the undo stack, undo and redo side.
+    ///
+    /// Changelog compaction must leave these entries (and everything after
+    /// them) in place so undo and redo can still find them.
+    pub async fn pinned_entries(&self) -> HashSet<UndoEntryId> {
+        self.stack
+            .read()
+            .await
+            .entries()
+            .iter()
+            .map(|e| e.id)
+            .collect()
+    }
+
     /// Flush changes from all registered stores and aggregate events.
     pub async fn flush_all(&self) -> Vec<ChangeEvent> {
         let store_clones: Vec<Arc<dyn ErasedStore>> = {
@@ -485,6 +510,35 @@ mod tests {
         assert_eq!(events.len(), 2);
     }
 
+    #[tokio::test]
+    async fn nested_group_guard_leaves_outer_group_open() {
+        let dir = TempDir::new().unwrap();
+        let store_dir = dir.path().join("store1");
+        std::fs::create_dir_all(&store_dir).unwrap();
+
+        let handle = make_handle(&store_dir);
+        let ctx = StoreContext::new(dir.path().to_path_buf());
+        ctx.register(handle.clone()).await;
+
+        let outer = ctx.begin_undo_group().await;
+        for name in ["a", "b", "c"] {
+            let inner = ctx.begin_undo_group().await;
+            let item = format!("{name}\ndata");
+            let entry_id = handle.write(&item).await.unwrap().unwrap();
+            ctx.push(entry_id, format!("create {name}"), StoredItemId::from(name))
+                .await;
+            drop(inner);
+        }
+        outer.end().await;
+
+        // One undo reverses all three writes made under the nested guards.
+        ctx.undo().await.unwrap();
+        for name in ["a", "b", "c"] {
+            assert!(!store_dir.join(format!("{name}.txt")).exists());
+        }
+        assert!(!ctx.can_undo().await);
+    }
+
     #[tokio::test]
     async fn store_for_path_finds_matching_store() {
         let dir = TempDir::new().unwrap();
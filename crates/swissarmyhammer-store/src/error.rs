@@ -37,6 +37,10 @@ pub enum StoreError {
     #[error("deserialization error: {0}")]
     Deserialize(String),
 
+    /// The entry records compacted history and cannot be undone or redone.
+    #[error("changelog entry cannot be undone or redone: {0}")]
+    NotUndoable(String),
+
     /// No registered store could handle the given undo entry.
     #[error("no provider found for undo entry: {0}")]
     NoProvider(String),
@@ -11,7 +11,7 @@ use std::sync::Arc;
 use chrono::Utc;
 use tokio::sync::RwLock;
 
-use crate::changelog::{ChangeOp, Changelog, ChangelogEntry};
+use crate::changelog::{changelog_actor, ChangeOp, Changelog, ChangelogEntry};
 use crate::diff;
 use crate::error::{Result, StoreError};
 use crate::event::ChangeEvent;
@@ -107,6 +107,7 @@ impl<S: TrackedStore> StoreHandle<S> {
             forward_patch,
             reverse_patch,
             transaction_id: None,
+            actor: changelog_actor(),
         };
 
         self.changelog_for(&stored_id)
@@ -155,6 +156,7 @@ impl<S: TrackedStore> StoreHandle<S> {
             forward_patch,
             reverse_patch,
             transaction_id: None,
+            actor: changelog_actor(),
         };
 
         self.changelog_for(&stored_id)
@@ -208,6 +210,7 @@ impl<S: TrackedStore> StoreHandle<S> {
             forward_patch,
             reverse_patch,
             transaction_id: None,
+            actor: changelog_actor(),
         };
 
         self.changelog_for(&stored_id)
@@ -308,6 +311,7 @@ impl<S: TrackedStore> StoreHandle<S> {
             forward_patch,
             reverse_patch,
             transaction_id: None,
+            actor: changelog_actor(),
         };
 
         self.changelog_for(&stored_id)
@@ -336,6 +340,7 @@ impl<S: TrackedStore> StoreHandle<S> {
     /// - Delete: restores the file and its changelog from trash
     /// - Archive: restores the file and its changelog from `.archive/`
     /// - Unarchive: moves the file back to `.archive/`
+    /// - Snapshot: refused; compacted history is not on the undo stack
     pub async fn undo(&self, entry_id: &UndoEntryId, item_id: &StoredItemId) -> Result<S::Item> {
         // If the changelog is in trash (from a prior delete) or archive
         // (from a prior archive), restore it first so we can read the entry.
@@ -493,6 +498,7 @@ impl<S: TrackedStore> StoreHandle<S> {
                     .map_err(|_| StoreError::Deserialize(entry.item_id.to_string()))?;
                 self.store.deserialize(&id, &content_text)
             }
+            ChangeOp::Snapshot => Err(StoreError::NotUndoable(entry.id.to_string())),
         }
     }
 
@@ -505,6 +511,7 @@ impl<S: TrackedStore> StoreHandle<S> {
     /// - Delete: trashes the file and its changelog again
     /// - Archive: moves the file back to `.archive/`
     /// - Unarchive: restores the file from `.archive/`
+    /// - Snapshot: refused; compacted history is not on the undo stack
     pub async fn redo(&self, entry_id: &UndoEntryId, item_id: &StoredItemId) -> Result<S::Item> {
         // If the changelog is in trash (from a prior undo of a create) or archive
         // (from a prior undo of an unarchive), restore it first so we can read
@@ -663,6 +670,7 @@ impl<S: TrackedStore> StoreHandle<S> {
                     .map_err(|_| StoreError::Deserialize(entry.item_id.to_string()))?;
                 self.store.deserialize(&id, &restored_text)
             }
+            ChangeOp::Snapshot => Err(StoreError::NotUndoable(entry.id.to_string())),
         }
     }
 
@@ -800,6 +808,24 @@ mod tests {
         assert_eq!(entries[0].item_id, item1_id);
     }
 
+    #[tokio::test]
+    async fn write_records_scoped_actor() {
+        let (_dir, handle) = setup();
+        handle.write(&"item1\nv1".to_string()).await.unwrap();
+        crate::changelog::with_changelog_actor("agent", async {
+            handle.write(&"item1\nv2".to_string()).await.unwrap();
+        })
+        .await;
+
+        let entries = handle
+            .changelog_for(&StoredItemId::from("item1"))
+            .read_all()
+            .await
+            .unwrap();
+        assert_eq!(entries[0].actor, None);
+        assert_eq!(entries[1].actor.as_deref(), Some("agent"));
+    }
+
     #[tokio::test]
     async fn write_same_content_is_idempotent() {
         let (_dir, handle) = setup();
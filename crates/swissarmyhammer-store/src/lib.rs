@@ -35,7 +35,9 @@ pub mod stack;
 pub mod store;
 pub mod trash;
 
-pub use changelog::{ChangeOp, ChangelogEntry};
+pub use changelog::{
+    changelog_actor, with_changelog_actor, ChangeOp, ChangelogEntry, CompactionPolicy,
+};
 pub use context::{StoreContext, UndoOutcome};
 pub use error::StoreError;
 pub use event::ChangeEvent;
@@ -539,14 +539,25 @@ impl McpTool for KanbanTool {
             McpError::invalid_params(format!("failed to parse kanban operation: {}", e), None)
         })?;
 
-        // Execute each operation and collect results
+        // Execute each operation and collect results. Hooks fire for the
+        // writes; the response waits a few seconds at most for their
+        // deliveries, and slower ones finish in the background.
+        if let Err(e) = ctx.start_hooks().await {
+            tracing::warn!(error = %e, "kanban hooks not started");
+        }
         let mut results = Vec::new();
         let mut should_include_plan = false;
         let mut last_affected_task_id: Option<String> = None;
         let mut last_trigger = String::new();
 
         for op in &operations {
-            let result = execute_operation(&ctx, op).await?;
+            let result = match execute_operation(&ctx, op).await {
+                Ok(result) => result,
+                Err(e) => {
+                    ctx.drain_hooks().await;
+                    return Err(e);
+                }
+            };
 
             // Track if we need to include plan in response
             if is_task_modifying_operation(op.verb, op.noun) {
@@ -561,6 +572,7 @@ impl McpTool for KanbanTool {
 
             results.push(result);
         }
+        ctx.drain_hooks().await;
 
         // Build response with plan data if any task-modifying operations were executed
         let mut response = if results.len() == 1 {
@@ -604,10 +616,22 @@ fn build_schema(
 ///
 /// Delegates to [`swissarmyhammer_kanban::dispatch::execute_operation`] — the single
 /// source of truth for operation dispatch — and maps errors to MCP format.
+///
+/// A write that breaks declarative field rules is reported as invalid params
+/// with the broken rules as `{"violations": [{field, rule, message}]}` data.
 async fn execute_operation(ctx: &KanbanContext, op: &KanbanOperation) -> Result<Value, McpError> {
     swissarmyhammer_kanban::dispatch::execute_operation(ctx, op)
         .await
-        .map_err(|e| McpError::internal_error(format!("{}: {}", op.op_string(), e), None))
+        .map_err(|e| {
+            let message = format!("{}: {}", op.op_string(), e);
+            match e.rule_violations() {
+                Some(violations) => McpError::invalid_params(
+                    message,
+                    Some(serde_json::json!({ "violations": violations })),
+                ),
+                None => McpError::internal_error(message, None),
+            }
+        })
 }
 
 /// Register all kanban tools with the tool registry
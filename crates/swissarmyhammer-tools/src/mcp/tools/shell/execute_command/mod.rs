@@ -2,20 +2,24 @@
 //!
 //! This module implements the "execute command" operation which runs shell commands
 //! with timeout management, output capture, environment control, and security validation.
+//! A command that names a `session` runs in that session's persistent shell; see
+//! [`super::session`].
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use rmcp::model::CallToolResult;
 use rmcp::ErrorData as McpError;
 use swissarmyhammer_common::Pretty;
 use swissarmyhammer_operations::{Operation, ParamMeta, ParamType};
+use swissarmyhammer_shell::Sandbox;
 use tokio::sync::Mutex;
 
-use super::infrastructure::{ShellError, ShellExecuteRequest};
-use super::process::{execute_with_guard, spawn_shell_command};
+use super::infrastructure::{OutputLimits, ShellError, ShellExecuteRequest, ShellExecutionResult};
+use super::process::{prepare_working_directory, spawn_shell_command, stream_output_to_state};
+use super::session::PersistentShell;
 use super::state::{CommandStatus, ShellState};
 use crate::mcp::shared_utils::{McpErrorHandler, McpValidation};
 use crate::mcp::tool_registry::{BaseToolImpl, ToolContext};
@@ -27,13 +31,13 @@ use crate::mcp::tool_registry::{BaseToolImpl, ToolContext};
 const DEFAULT_TAIL_LINES: usize = 32;
 
 /// Response key that carries the id the caller passes back to `get lines`,
-/// `grep history`, and `kill process`.
-const COMMAND_ID_KEY: &str = "command_id";
+/// `grep history`, `wait command`, and `kill process`.
+pub(super) const COMMAND_ID_KEY: &str = "command_id";
 
-/// Response key that carries the command's terminal state. The value is always
+/// Response key that carries the command's state. The value is always
 /// a [`CommandStatus`] rendered through its `Display` impl, so the response and
 /// `list processes` never disagree on a status name.
-const STATUS_KEY: &str = "status";
+pub(super) const STATUS_KEY: &str = "status";
 
 /// Operation metadata for executing shell commands
 #[derive(Debug, Default)]
@@ -55,6 +59,9 @@ static EXECUTE_COMMAND_PARAMS: &[ParamMeta] = &[
     ParamMeta::new("environment")
         .description("Additional environment variables as JSON string (optional, e.g., '{\"KEY1\":\"value1\",\"KEY2\":\"value2\"}')")
         .param_type(ParamType::String),
+    ParamMeta::new("session")
+        .description("Run in this named persistent shell, which keeps cwd, environment and functions between commands (optional; working_directory and environment apply when the session starts; without a timeout a session command times out after 600 seconds)")
+        .param_type(ParamType::String),
 ];
 
 impl Operation for ExecuteCommand {
@@ -72,7 +79,7 @@ impl Operation for ExecuteCommand {
         "Execute a shell command with timeout and environment control; blocks until the command exits or the timeout kills it"
     }
     /// Returns the parameter metadata the `execute command` operation accepts:
-    /// `command`, `timeout`, `working_directory`, and `environment`.
+    /// `command`, `timeout`, `working_directory`, `environment`, and `session`.
     fn parameters(&self) -> &'static [ParamMeta] {
         EXECUTE_COMMAND_PARAMS
     }
@@ -100,41 +107,97 @@ pub async fn run(
 ) -> Result<CallToolResult, McpError> {
     let request: ShellExecuteRequest = BaseToolImpl::parse_arguments(args)?;
     tracing::info!("Executing shell command: {}", Pretty(&request.command));
-    validate_shell_request(&request)?;
-
+    if request.pty {
+        return Err(McpError::invalid_params(
+            "pty applies to start command only; start the command with pty: true",
+            None,
+        ));
+    }
     // Commands without an explicit working_directory run in the session working
     // directory (the board dir), never the process CWD.
     let default_dir = context.session_root();
+    validate_shell_request(&request, &default_dir)?;
+    let sandbox = load_command_sandbox(&default_dir)?;
+    if let Some(name) = request.session.as_deref() {
+        return run_in_session(&request, name, &state, default_dir, sandbox.as_ref()).await;
+    }
+    let output_limits = OutputLimits::with_defaults().map_err(|e| {
+        McpError::internal_error(format!("invalid output configuration: {e}"), None)
+    })?;
     let (cmd_id, mut process_guard, work_dir) =
-        prepare_command(&request, &state, default_dir).await?;
+        prepare_command(&request, &state, default_dir, sandbox.as_ref()).await?;
+    let Some(child) = process_guard.child_mut() else {
+        return Err(McpError::internal_error(
+            "process guard has no child process",
+            None,
+        ));
+    };
 
-    let result = match run_with_optional_timeout(
-        request.timeout,
-        &mut process_guard,
-        cmd_id,
-        request.command.clone(),
-        work_dir,
-    )
-    .await
-    {
-        RunOutcome::Completed(r) => r,
-        RunOutcome::TimedOut { timeout_secs } => {
-            return finalize_timed_out(&state, cmd_id, timeout_secs).await;
+    // Output streams into shell state as it arrives, so a command the
+    // timeout kills still answers with what it printed.
+    let started = Instant::now();
+    let timeout = request.timeout.map(Duration::from_secs);
+    let exit_code =
+        match stream_output_to_state(child, &state, cmd_id, &output_limits, timeout).await {
+            Ok(Some(exit_code)) => exit_code,
+            Ok(None) => {
+                drop(process_guard);
+                let timeout_secs = request.timeout.unwrap_or_default();
+                return finalize_timed_out(&state, cmd_id, timeout_secs, None).await;
+            }
+            Err(e) => return finalize_completed(&state, cmd_id, Err(e), sandbox.as_ref()).await,
+        };
+    // The child has been reaped; nothing is left for the guard to kill.
+    process_guard.take_child();
+
+    let output = {
+        let mut guard = state.lock().await;
+        guard.complete_command(cmd_id, Some(exit_code)).await;
+        let lines = guard.get_lines(cmd_id, None, None).unwrap_or_default();
+        let stdout = lines
+            .into_iter()
+            .map(|(_, text)| text)
+            .collect::<Vec<_>>()
+            .join("\n");
+        ShellExecutionResult {
+            command_id: cmd_id,
+            command: request.command.clone(),
+            exit_code,
+            total_output_size: stdout.len(),
+            output_truncated: stdout.len() >= output_limits.max_output_size,
+            stdout,
+            stderr: String::new(),
+            execution_time_ms: started.elapsed().as_millis() as u64,
+            working_directory: work_dir,
+            binary_output_detected: false,
         }
     };
+    respond_completed(&state, cmd_id, &output, sandbox.as_ref()).await
+}
 
-    finalize_completed(&state, cmd_id, result).await
+/// Load the sandbox the shell config puts commands run from `workspace` in,
+/// or `None` when it is off.
+///
+/// A sandbox that is on but cannot be enforced here refuses the command
+/// rather than letting it run unconfined.
+pub(super) fn load_command_sandbox(workspace: &Path) -> Result<Option<Sandbox>, McpError> {
+    swissarmyhammer_shell::load_sandbox(workspace).map_err(|e| {
+        tracing::warn!("Command sandbox is unavailable: {}", e);
+        McpError::invalid_params(format!("command sandbox check failed: {e}"), None)
+    })
 }
 
 /// Parse the request's environment and working directory, register the command
-/// in shell state, spawn the child process, and track its PID.
+/// in shell state, spawn the child process inside `sandbox` when there is one,
+/// and track its PID.
 ///
 /// Returns the newly assigned command id, the live process guard, and the
 /// resolved working directory that downstream code should attribute output to.
-async fn prepare_command(
+pub(super) async fn prepare_command(
     request: &ShellExecuteRequest,
     state: &Arc<Mutex<ShellState>>,
     default_dir: PathBuf,
+    sandbox: Option<&Sandbox>,
 ) -> Result<(usize, super::process::AsyncProcessGuard, PathBuf), McpError> {
     let parsed_environment = parse_environment_variables(request.environment.as_deref())?;
     let working_directory = request.working_directory.clone().map(PathBuf::from);
@@ -149,6 +212,7 @@ async fn prepare_command(
         working_directory,
         default_dir,
         parsed_environment.as_ref(),
+        sandbox,
     )
     .map_err(|e| McpError::internal_error(format!("failed to spawn command: {}", e), None))?;
 
@@ -160,30 +224,150 @@ async fn prepare_command(
     Ok((cmd_id, process_guard, work_dir))
 }
 
+/// Seconds a session command without a `timeout` may run before the session
+/// is reset.
+const DEFAULT_SESSION_TIMEOUT_SECS: u64 = 600;
+
+/// Run the command in the persistent shell of session `name`, starting that
+/// shell on the session's first command.
+///
+/// The response, the history, and the statuses are those of any other
+/// `execute command`. A shell that exits or times out takes the session with
+/// it: the shell is ended, the session closed, and the next command that names
+/// it starts a fresh shell in the default directory.
+///
+/// A session command reads output up to a sentinel line the shell prints
+/// after it. A command that moves the shell's own stdout (`exec >file`) hides
+/// that line, so a command without a `timeout` gets
+/// [`DEFAULT_SESSION_TIMEOUT_SECS`] rather than waiting forever.
+///
+/// A new shell starts inside `sandbox`. A running shell cannot leave the
+/// sandbox it started in, so when the shell config has changed the sandbox
+/// since, the session is closed and the command refused; the next command
+/// that names it starts a fresh shell in the current sandbox.
+async fn run_in_session(
+    request: &ShellExecuteRequest,
+    name: &str,
+    state: &Arc<Mutex<ShellState>>,
+    default_dir: PathBuf,
+    sandbox: Option<&Sandbox>,
+) -> Result<CallToolResult, McpError> {
+    McpValidation::validate_not_empty(name, "session name")
+        .map_err(|e| McpErrorHandler::handle_error(e, "validate session name"))?;
+    let output_limits = OutputLimits::with_defaults().map_err(|e| {
+        McpError::internal_error(format!("invalid output configuration: {e}"), None)
+    })?;
+
+    let workspace = default_dir.clone();
+    let session = {
+        let mut guard = state.lock().await;
+        match guard.session(name) {
+            Some(session) => {
+                if request.working_directory.is_some() || request.environment.is_some() {
+                    return Err(McpError::invalid_params(
+                        format!(
+                            "session '{name}' is already running; change its directory or \
+                             environment with `cd` and `export` in the command"
+                        ),
+                        None,
+                    ));
+                }
+                session
+            }
+            None => {
+                let environment = parse_environment_variables(request.environment.as_deref())?;
+                let shell = prepare_working_directory(
+                    request.working_directory.clone().map(PathBuf::from),
+                    default_dir,
+                )
+                .and_then(|dir| PersistentShell::spawn(name, &dir, environment.as_ref(), sandbox))
+                .map_err(|e| {
+                    McpError::internal_error(format!("failed to start session: {}", e), None)
+                })?;
+                guard.open_session(name, shell)
+            }
+        }
+    };
+
+    // Waits for a command already running in this session, whose `cd`s decide
+    // where this one starts.
+    let mut shell = session.lock().await;
+    if shell.sandbox() != sandbox {
+        shell.close().await;
+        state.lock().await.close_session(name);
+        return Err(McpError::invalid_params(
+            format!(
+                "the shell config changed the sandbox since session '{name}' started; the \
+                 session is closed and its next command starts a fresh shell"
+            ),
+            None,
+        ));
+    }
+    validate_command_from(&request.command, &workspace, shell.working_directory())?;
+    let cmd_id = {
+        let mut guard = state.lock().await;
+        let cmd_id = guard.start_command(request.command.as_str());
+        if let Some(pid) = shell.pid() {
+            guard.register_process(cmd_id, pid);
+        }
+        cmd_id
+    };
+    let started = Instant::now();
+    let run = shell.run(&request.command, output_limits.max_output_size);
+    let secs = request.timeout.unwrap_or(DEFAULT_SESSION_TIMEOUT_SECS);
+    let outcome = match tokio::time::timeout(Duration::from_secs(secs), run).await {
+        Ok(outcome) => outcome,
+        Err(_) => {
+            shell.close().await;
+            state.lock().await.close_session(name);
+            return finalize_timed_out(state, cmd_id, secs, Some(name)).await;
+        }
+    };
+
+    let result = match outcome {
+        Ok(output) => Ok(ShellExecutionResult {
+            command_id: cmd_id,
+            command: request.command.clone(),
+            exit_code: output.exit_code,
+            stdout: output.lines.join("\n"),
+            stderr: String::new(),
+            execution_time_ms: started.elapsed().as_millis() as u64,
+            working_directory: output.working_directory,
+            output_truncated: output.truncated,
+            total_output_size: output.total_bytes,
+            binary_output_detected: false,
+        }),
+        Err(ShellError::ExecutionError { command, message }) => {
+            shell.close().await;
+            state.lock().await.close_session(name);
+            Err(ShellError::ExecutionError {
+                command,
+                message: format!(
+                    "{message}; session '{name}' is closed and its next command starts a fresh shell"
+                ),
+            })
+        }
+        Err(e) => Err(e),
+    };
+    finalize_completed(state, cmd_id, result, shell.sandbox()).await
+}
+
 /// Produce the MCP response for a completed command, recording its output in
 /// shell state and translating any inner shell error into a tool-level error.
+///
+/// A command that ran in `sandbox` and failed because the sandbox refused it
+/// a write or the network is a [`ShellError::SandboxError`]; its output is
+/// still stored under its command id.
 async fn finalize_completed(
     state: &Arc<Mutex<ShellState>>,
     cmd_id: usize,
-    result: Result<super::infrastructure::ShellExecutionResult, ShellError>,
+    result: Result<ShellExecutionResult, ShellError>,
+    sandbox: Option<&Sandbox>,
 ) -> Result<CallToolResult, McpError> {
     match result {
         Ok(output) => {
             store_command_output(state, cmd_id, &output).await;
-            let total_lines = output.stdout.lines().count() + output.stderr.lines().count();
-            let mut response = format!(
-                "{COMMAND_ID_KEY}: {}\n{STATUS_KEY}: {}\nexit_code: {}\nlines: {}\nduration: {}ms",
-                cmd_id,
-                CommandStatus::Completed,
-                output.exit_code,
-                total_lines,
-                output.execution_time_ms,
-            );
-            if let Some(tail) = format_output_tail(state, cmd_id, total_lines).await {
-                response.push_str("\n\n");
-                response.push_str(&tail);
-            }
-            Ok(BaseToolImpl::create_success_response(response))
+            respond_completed(state, cmd_id, &output, sandbox).await
         }
         Err(shell_error) => {
             mark_command_errored(state, cmd_id).await;
@@ -193,6 +377,51 @@ async fn finalize_completed(
     }
 }
 
+/// Produce the MCP response for a completed command whose output is already
+/// stored in shell state.
+async fn respond_completed(
+    state: &Arc<Mutex<ShellState>>,
+    cmd_id: usize,
+    output: &ShellExecutionResult,
+    sandbox: Option<&Sandbox>,
+) -> Result<CallToolResult, McpError> {
+    if let Some(source) = sandbox_violation(sandbox, output) {
+        let error = ShellError::SandboxError { source };
+        tracing::warn!("Shell: {}", error);
+        return Ok(CallToolResult::error(vec![rmcp::model::Content::text(
+            format!("Shell execution failed: {error}\n{COMMAND_ID_KEY}: {cmd_id}"),
+        )]));
+    }
+    let total_lines = stored_line_count(state, cmd_id).await;
+    let mut response = format!(
+        "{COMMAND_ID_KEY}: {}\n{STATUS_KEY}: {}\nexit_code: {}\nlines: {}\nduration: {}ms",
+        cmd_id,
+        CommandStatus::Completed,
+        output.exit_code,
+        total_lines,
+        output.execution_time_ms,
+    );
+    if let Some(tail) = format_output_tail(state, cmd_id, total_lines).await {
+        response.push_str("\n\n");
+        response.push_str(&tail);
+    }
+    Ok(BaseToolImpl::create_success_response(response))
+}
+
+/// The sandbox violation the output of a failed command points to, if it ran
+/// in `sandbox` at all.
+fn sandbox_violation(
+    sandbox: Option<&Sandbox>,
+    output: &ShellExecutionResult,
+) -> Option<swissarmyhammer_shell::ShellSecurityError> {
+    let sandbox = sandbox?;
+    if output.exit_code == 0 {
+        return None;
+    }
+    let text = format!("{}\n{}", output.stdout, output.stderr);
+    sandbox.violation(&output.command, &output.working_directory, &text)
+}
+
 /// Build the output-tail block appended to a completed command's response.
 ///
 /// Reads the last [`DEFAULT_TAIL_LINES`] stored lines back from shell state via
@@ -207,7 +436,7 @@ async fn finalize_completed(
 ///
 /// Returns `None` when there is no output (`total_lines == 0`) so the caller
 /// omits the block entirely.
-async fn format_output_tail(
+pub(super) async fn format_output_tail(
     state: &Arc<Mutex<ShellState>>,
     cmd_id: usize,
     total_lines: usize,
@@ -244,58 +473,50 @@ async fn format_output_tail(
 }
 
 /// Produce the MCP response for a command that exceeded its timeout, updating
-/// shell state so `list processes` reflects the `timed_out` status.
+/// shell state so `list processes` reflects the `timed_out` status. The tail
+/// of whatever the command printed before it was killed is included, and
+/// `session` names the session the timeout closed, if any.
 async fn finalize_timed_out(
     state: &Arc<Mutex<ShellState>>,
     cmd_id: usize,
     timeout_secs: u64,
+    session: Option<&str>,
 ) -> Result<CallToolResult, McpError> {
     mark_timed_out(state, cmd_id).await;
-    Ok(BaseToolImpl::create_success_response(format!(
-        "{COMMAND_ID_KEY}: {}\n{STATUS_KEY}: {}\ntimeout: {}s\nCommand timed out after {} seconds.",
+    let total_lines = stored_line_count(state, cmd_id).await;
+    let mut response = format!(
+        "{COMMAND_ID_KEY}: {}\n{STATUS_KEY}: {}\ntimeout: {}s\nlines: {}\nCommand timed out after {} seconds.",
         cmd_id,
         CommandStatus::TimedOut,
         timeout_secs,
+        total_lines,
         timeout_secs,
-    )))
-}
-
-/// Result of driving the command to completion, distinguishing a real outcome
-/// from a timeout the caller must translate into a user-facing status response.
-enum RunOutcome {
-    /// The command finished (successfully or with an inner shell error).
-    Completed(Result<super::infrastructure::ShellExecutionResult, ShellError>),
-    /// The command exceeded `timeout_secs` and the process guard has been dropped.
-    TimedOut { timeout_secs: u64 },
+    );
+    if let Some(name) = session {
+        response.push_str(&format!(
+            " Session '{name}' is closed and its next command starts a fresh shell."
+        ));
+    }
+    if let Some(tail) = format_output_tail(state, cmd_id, total_lines).await {
+        response.push_str("\n\n");
+        response.push_str(&tail);
+    }
+    Ok(BaseToolImpl::create_success_response(response))
 }
 
-/// Execute the command with an optional timeout.
-///
-/// When `timeout` is `None`, the command runs to completion unbounded. When
-/// `Some`, a breach of the deadline drops the process guard (killing the
-/// child) and returns [`RunOutcome::TimedOut`].
-async fn run_with_optional_timeout(
-    timeout: Option<u64>,
-    process_guard: &mut super::process::AsyncProcessGuard,
-    cmd_id: usize,
-    command: String,
-    work_dir: PathBuf,
-) -> RunOutcome {
-    let fut = execute_with_guard(process_guard, cmd_id, command, work_dir);
-    let Some(timeout_secs) = timeout else {
-        return RunOutcome::Completed(fut.await);
-    };
-    match tokio::time::timeout(Duration::from_secs(timeout_secs), fut).await {
-        Ok(r) => RunOutcome::Completed(r),
-        Err(_) => RunOutcome::TimedOut { timeout_secs },
-    }
+/// How many output lines shell state holds for `cmd_id`.
+async fn stored_line_count(state: &Arc<Mutex<ShellState>>, cmd_id: usize) -> usize {
+    let guard = state.lock().await;
+    guard
+        .get_command(cmd_id)
+        .map_or(0, |record| record.line_count)
 }
 
 /// Persist stdout/stderr into shell history and mark the command complete.
 async fn store_command_output(
     state: &Arc<Mutex<ShellState>>,
     cmd_id: usize,
-    output: &super::infrastructure::ShellExecutionResult,
+    output: &ShellExecutionResult,
 ) {
     let mut guard = state.lock().await;
     append_stream(&mut guard, cmd_id, &output.stdout, "stdout").await;
@@ -335,22 +556,30 @@ async fn mark_command_errored(state: &Arc<Mutex<ShellState>>, cmd_id: usize) {
 ///
 /// Checks that the command is non-empty, passes security policy validation,
 /// and (if provided) the working directory is non-empty and passes security checks.
+/// Command rules resolve relative paths against the directory the command
+/// runs in: the `working_directory`, or else the workspace.
 ///
 /// # Parameters
 ///
 /// - `request`: the parsed shell execution request
+/// - `workspace`: the session root, which command rules limited to the
+///   workspace measure paths against
 ///
 /// # Returns
 ///
 /// `Ok(())` if valid, or an `McpError` describing the validation failure.
-fn validate_shell_request(request: &ShellExecuteRequest) -> Result<(), McpError> {
+pub(super) fn validate_shell_request(
+    request: &ShellExecuteRequest,
+    workspace: &Path,
+) -> Result<(), McpError> {
     McpValidation::validate_not_empty(&request.command, "shell command")
         .map_err(|e| McpErrorHandler::handle_error(e, "validate shell command"))?;
 
-    swissarmyhammer_shell::validate_command(&request.command).map_err(|e| {
-        tracing::warn!("Command security validation failed: {}", e);
-        McpError::invalid_params(format!("command security check failed: {e}"), None)
-    })?;
+    let cwd = match request.working_directory.as_deref() {
+        Some(dir) => std::path::absolute(dir).unwrap_or_else(|_| PathBuf::from(dir)),
+        None => workspace.to_path_buf(),
+    };
+    validate_command_from(&request.command, workspace, &cwd)?;
 
     if let Some(ref working_dir) = request.working_directory {
         McpValidation::validate_not_empty(working_dir, "working directory")
@@ -371,6 +600,16 @@ fn validate_shell_request(request: &ShellExecuteRequest) -> Result<(), McpError>
     Ok(())
 }
 
+/// Validate `command`, started in `cwd`, against the shell security policy.
+///
+/// Command rules limited to the workspace measure paths against `workspace`.
+fn validate_command_from(command: &str, workspace: &Path, cwd: &Path) -> Result<(), McpError> {
+    swissarmyhammer_shell::validate_command_at(command, workspace, cwd).map_err(|e| {
+        tracing::warn!("Command security validation failed: {}", e);
+        McpError::invalid_params(format!("command security check failed: {e}"), None)
+    })
+}
+
 /// Parse and validate environment variables from JSON string.
 ///
 /// Deserializes a JSON string into a `HashMap<String, String>` and validates
@@ -384,7 +623,7 @@ fn validate_shell_request(request: &ShellExecuteRequest) -> Result<(), McpError>
 ///
 /// `Ok(Some(map))` if a string was provided and parsed successfully,
 /// `Ok(None)` if no string was provided, or an `McpError` on parse/validation failure.
-fn parse_environment_variables(
+pub(super) fn parse_environment_variables(
     env_str: Option<&str>,
 ) -> Result<Option<HashMap<String, String>>, McpError> {
     if let Some(env_str) = env_str {
@@ -438,8 +677,8 @@ mod tests {
     use std::time::Duration;
 
     use super::super::test_helpers::{
-        assert_paths_blocked, extract_text, test_blocked_commands_with_policy, ResultValidator,
-        TestCommandBuilder,
+        assert_paths_blocked, execute_op, execute_op_with, extract_text, shared_tool,
+        test_blocked_commands_with_policy, ResultValidator, TestCommandBuilder,
     };
     use super::super::ShellExecuteTool;
     use super::DEFAULT_TAIL_LINES;
@@ -906,6 +1145,35 @@ mod tests {
         );
     }
 
+    /// A command the timeout kills still answers with what it printed.
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_execute_timed_out_response_includes_output_so_far() {
+        let mut args = serde_json::Map::new();
+        args.insert(
+            "command".to_string(),
+            serde_json::json!("echo before; sleep 30"),
+        );
+        args.insert("timeout".to_string(), serde_json::json!(1));
+        args.insert("working_directory".to_string(), serde_json::json!("/tmp"));
+
+        let call_result = TestCommandBuilder::new("unused")
+            .with_custom_args(args)
+            .execute()
+            .await
+            .expect("a timed-out command still returns a success response");
+
+        let text = extract_text(&call_result);
+        assert!(
+            text.contains("status: timed_out"),
+            "Expected timed_out status. Got:\n{text}"
+        );
+        assert!(
+            text.contains("1: before"),
+            "Expected the output printed before the timeout. Got:\n{text}"
+        );
+    }
+
     #[tokio::test]
     async fn test_execute_response_no_output_section_when_empty() {
         let result = TestCommandBuilder::new("true").execute().await;
@@ -1488,4 +1756,227 @@ mod tests {
             );
         }
     }
+
+    // Persistent session tests
+
+    /// Run `command` in session `name` through `tool` and return the response
+    /// text.
+    async fn run_in_session_text(tool: &ShellExecuteTool, name: &str, command: &str) -> String {
+        let result = execute_op_with(
+            tool,
+            "execute command",
+            vec![("command", json!(command)), ("session", json!(name))],
+        )
+        .await
+        .expect("execute command in a session");
+        extract_text(&result)
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_session_keeps_directory_and_environment_between_commands() {
+        let tool = shared_tool();
+        run_in_session_text(&tool, "dev", "cd /tmp && export SESSION_FLAVOR=kept").await;
+
+        let text = run_in_session_text(&tool, "dev", "pwd; echo $SESSION_FLAVOR").await;
+        assert!(text.contains("1: /tmp"), "cwd must carry over: {text}");
+        assert!(text.contains("2: kept"), "exports must carry over: {text}");
+
+        let other = run_in_session_text(&tool, "other", "echo ${SESSION_FLAVOR:-unset}").await;
+        assert!(
+            other.contains("1: unset"),
+            "sessions must not share state: {other}"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_session_that_exits_restarts_fresh() {
+        let tool = shared_tool();
+        run_in_session_text(&tool, "dies", "export LOST=1").await;
+
+        let result = execute_op_with(
+            &tool,
+            "execute command",
+            vec![("command", json!("exit 4")), ("session", json!("dies"))],
+        )
+        .await
+        .expect("a dead session still answers");
+        assert_eq!(result.is_error, Some(true));
+        assert!(extract_text(&result).contains("fresh shell"));
+
+        let text = run_in_session_text(&tool, "dies", "echo ${LOST:-unset}").await;
+        assert!(
+            text.contains("1: unset"),
+            "a restarted session starts clean: {text}"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_session_rejects_working_directory_once_running() {
+        let tool = shared_tool();
+        run_in_session_text(&tool, "pinned", "true").await;
+
+        let err = execute_op_with(
+            &tool,
+            "execute command",
+            vec![
+                ("command", json!("pwd")),
+                ("session", json!("pinned")),
+                ("working_directory", json!("/tmp")),
+            ],
+        )
+        .await
+        .expect_err("an open session keeps its own directory");
+        assert!(err.to_string().contains("cd"), "{err}");
+    }
+
+    /// A command that moves the shell's stdout never prints the sentinel; the
+    /// timeout resets the session instead of waiting forever.
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_session_that_hides_its_sentinel_times_out_and_restarts() {
+        let tool = shared_tool();
+        run_in_session_text(&tool, "redirected", "export LOST=1").await;
+
+        let result = execute_op_with(
+            &tool,
+            "execute command",
+            vec![
+                ("command", json!("exec >/dev/null")),
+                ("session", json!("redirected")),
+                ("timeout", json!(1)),
+            ],
+        )
+        .await
+        .expect("a timed-out session command still answers");
+        let text = extract_text(&result);
+        assert!(text.contains("status: timed_out"), "{text}");
+        assert!(text.contains("fresh shell"), "{text}");
+
+        let text = run_in_session_text(&tool, "redirected", "echo ${LOST:-unset}").await;
+        assert!(text.contains("1: unset"), "the session restarts: {text}");
+    }
+
+    #[tokio::test]
+    async fn test_execute_rejects_pty() {
+        let err = execute_op(
+            "execute command",
+            vec![("command", json!("echo hi")), ("pty", json!(true))],
+        )
+        .await
+        .expect_err("pty is for start command");
+        assert!(err.to_string().contains("start command"), "{err}");
+    }
+
+    /// A session started outside the sandbox is closed, not reused, once the
+    /// sandbox is on. Skipped on kernels without Landlock.
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_session_closes_when_the_sandbox_changes() {
+        use std::sync::Arc;
+        use swissarmyhammer_shell::{Sandbox, SandboxConfig};
+        use tokio::sync::Mutex;
+
+        let workspace = tempfile::TempDir::new().unwrap();
+        let config = SandboxConfig {
+            enabled: Some(true),
+            writable: vec![],
+            block_network: Some(false),
+        };
+        let Ok(Some(sandbox)) = Sandbox::from_config(&config, workspace.path()) else {
+            return;
+        };
+        let shell_dir = tempfile::TempDir::new().unwrap();
+        let state = Arc::new(Mutex::new(
+            super::ShellState::with_dir(shell_dir.path()).unwrap(),
+        ));
+        let request: super::ShellExecuteRequest =
+            serde_json::from_value(json!({"command": "true", "session": "boxed"})).unwrap();
+        let dir = workspace.path().to_path_buf();
+
+        super::run_in_session(&request, "boxed", &state, dir.clone(), None)
+            .await
+            .expect("unsandboxed session runs");
+        let err = super::run_in_session(&request, "boxed", &state, dir.clone(), Some(&sandbox))
+            .await
+            .expect_err("a session outside the new sandbox must not run it");
+        assert!(err.to_string().contains("sandbox"), "{err}");
+        assert!(state.lock().await.session("boxed").is_none());
+
+        super::run_in_session(&request, "boxed", &state, dir, Some(&sandbox))
+            .await
+            .expect("the next command starts a sandboxed shell");
+    }
+
+    /// Recursive delete of a path that does not exist, so a rule that fails
+    /// to block it harms nothing.
+    const STRAY_DELETE: &str = "rm -rf sah-no-such-directory";
+
+    #[tokio::test]
+    async fn test_deny_rules_resolve_paths_from_the_working_directory() {
+        let err = execute_op(
+            "execute command",
+            vec![
+                ("command", json!(STRAY_DELETE)),
+                ("working_directory", json!("/")),
+            ],
+        )
+        .await
+        .expect_err("a relative path under / is outside the workspace");
+        assert!(err.to_string().contains("security check failed"), "{err}");
+    }
+
+    /// The workspace the test tool measures paths against: the process
+    /// directory, since the test context names no session root.
+    fn test_workspace() -> String {
+        std::env::current_dir()
+            .expect("current directory")
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[tokio::test]
+    async fn test_deny_rules_follow_cd_in_the_command() {
+        let err = execute_op(
+            "execute command",
+            vec![
+                ("command", json!(format!("cd / && {STRAY_DELETE}"))),
+                ("working_directory", json!(test_workspace())),
+            ],
+        )
+        .await
+        .expect_err("a path after `cd /` is outside the workspace");
+        assert!(err.to_string().contains("security check failed"), "{err}");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_deny_rules_use_the_session_directory() {
+        let tool = shared_tool();
+        execute_op_with(
+            &tool,
+            "execute command",
+            vec![
+                ("command", json!("cd /")),
+                ("session", json!("moved")),
+                ("working_directory", json!(test_workspace())),
+            ],
+        )
+        .await
+        .expect("cd in a session");
+
+        let err = execute_op_with(
+            &tool,
+            "execute command",
+            vec![
+                ("command", json!(STRAY_DELETE)),
+                ("session", json!("moved")),
+            ],
+        )
+        .await
+        .expect_err("a path in a session left in / is outside the workspace");
+        assert!(err.to_string().contains("security check failed"), "{err}");
+    }
 }
@@ -159,6 +159,14 @@ pub(crate) struct ShellExecuteRequest {
 
     /// Optional environment variables as JSON string
     pub(crate) environment: Option<String>,
+
+    /// Run the command on a pseudo-terminal. Only `start command` reads it.
+    #[serde(default)]
+    pub(crate) pty: bool,
+
+    /// Name of the persistent shell session to run the command in. Only
+    /// `execute command` reads it.
+    pub(crate) session: Option<String>,
 }
 
 /// Result structure for shell command execution
@@ -604,6 +612,12 @@ pub enum ShellError {
         /// Error message describing the working directory issue
         message: String,
     },
+
+    /// The command sandbox could not be set up, or refused the command an access
+    SandboxError {
+        /// The sandbox's security error
+        source: swissarmyhammer_shell::ShellSecurityError,
+    },
 }
 
 impl fmt::Display for ShellError {
@@ -625,6 +639,7 @@ impl fmt::Display for ShellError {
             ShellError::WorkingDirectoryError { message } => {
                 write!(f, "working directory error: {message}")
             }
+            ShellError::SandboxError { source } => write!(f, "sandbox error: {source}"),
         }
     }
 }
@@ -633,6 +648,7 @@ impl std::error::Error for ShellError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             ShellError::CommandSpawnError { source, .. } => Some(source),
+            ShellError::SandboxError { source } => Some(source),
             _ => None,
         }
     }
@@ -644,6 +660,7 @@ impl Severity for ShellError {
             // Critical: System-level failures that prevent shell from functioning
             ShellError::CommandSpawnError { .. } => ErrorSeverity::Critical,
             ShellError::SystemError { .. } => ErrorSeverity::Critical,
+            ShellError::SandboxError { .. } => ErrorSeverity::Critical,
 
             // Error: Command execution failures but system remains functional
             ShellError::ExecutionError { .. } => ErrorSeverity::Error,
@@ -1038,6 +1055,16 @@ mod tests {
             ErrorSeverity::Critical,
             "SystemError should be Critical",
         );
+
+        assert_error_severity(
+            ShellError::SandboxError {
+                source: swissarmyhammer_shell::ShellSecurityError::SandboxUnavailable {
+                    reason: "landlock is not supported".to_string(),
+                },
+            },
+            ErrorSeverity::Critical,
+            "SandboxError should be Critical",
+        );
     }
 
     #[test]
@@ -1090,6 +1117,11 @@ mod tests {
             ShellError::WorkingDirectoryError {
                 message: "test".to_string(),
             },
+            ShellError::SandboxError {
+                source: swissarmyhammer_shell::ShellSecurityError::SandboxUnavailable {
+                    reason: "test".to_string(),
+                },
+            },
         ];
 
         for error in errors {
@@ -2,10 +2,16 @@
 //!
 //! ## Operations
 //!
-//! Dispatches between five operations:
+//! Dispatches between ten operations:
 //! - `execute command`: Run a shell command with timeout and output capture.
 //!   The response includes the last 32 output lines (or the full output when
 //!   it is 32 lines or fewer); use `get lines` to retrieve the rest.
+//! - `start command`: Run a shell command in the background and return its id
+//!   at once; its output streams into the history while it runs
+//! - `wait command`: Wait for a command to exit, up to a deadline
+//! - `send input`: Type text into a command started with `pty: true`
+//! - `send keys`: Press named keys (Ctrl-C, arrows, Enter) in such a command
+//! - `read screen`: Render such a command's terminal as text
 //! - `list processes`: Show all commands with status, timing, exit codes
 //! - `kill process`: Stop a running command by ID
 //! - `grep history`: Regex pattern match across command output
@@ -18,12 +24,26 @@
 //! that kills and reaps the process on drop, preventing orphans and zombies even
 //! when a timeout or cancellation occurs.
 //!
-//! Output is streamed through an [`OutputBuffer`](infrastructure::OutputBuffer) that
-//! enforces size limits (10 MB default), detects binary content, and truncates at
-//! line boundaries. The output of a command that exits is stored in
-//! [`ShellState`](state::ShellState) for later retrieval via `get lines` or
-//! `grep history`. A command that the timeout kills stores no output, because
-//! only the completion path writes to the log.
+//! Output streams into [`ShellState`](state::ShellState) line by line as it
+//! arrives, up to a size limit (10 MB default), for later retrieval via
+//! `get lines` or `grep history`. A command that the timeout kills keeps what
+//! it printed until then, and its response shows the last lines of it.
+//!
+//! A command `start command` launches streams the same way from a background
+//! task that owns its process guard, so the output of a running, killed, or
+//! timed-out command is all readable.
+//!
+//! With `pty: true`, `start command` runs the program on a pseudo-terminal
+//! ([`pty::PtySession`]) instead, so prompts, REPLs, and full-screen programs
+//! behave as they do for a person. A VT parser keeps the screen; the terminal
+//! ops type into it and read it back, and the last screen is stored in the
+//! history when the program exits.
+//!
+//! An `execute command` that names a `session` runs in that session's
+//! [`PersistentShell`](session::PersistentShell) instead of a fresh process, so
+//! `cd`, `export`, and `source` carry over to the session's next command.
+//! Sentinel lines delimit each command's output and exit code; a shell that
+//! exits or times out closes its session, and the next command starts anew.
 //!
 //! ## Security
 //!
@@ -36,9 +56,12 @@
 //!
 //! - [`infrastructure`]: Types, output buffer, error types
 //! - [`process`]: Process spawning, streaming, guard
+//! - [`pty`]: Pseudo-terminal sessions, screen rendering, key names
+//! - [`session`]: Persistent shell sessions for `execute command`
 //! - [`state`]: Command history, output log
-//! - [`execute_command`], [`list_processes`], [`kill_process`],
-//!   [`grep_history`], [`get_lines`]: Per-operation modules
+//! - [`execute_command`], [`start_command`], [`wait_command`],
+//!   [`send_input`], [`send_keys`], [`read_screen`], [`list_processes`],
+//!   [`kill_process`], [`grep_history`], [`get_lines`]: Per-operation modules
 
 pub mod execute_command;
 pub mod get_lines;
@@ -47,7 +70,14 @@ pub mod infrastructure;
 pub mod kill_process;
 pub mod list_processes;
 pub mod process;
+pub mod pty;
+pub mod read_screen;
+pub mod send_input;
+pub mod send_keys;
+pub mod session;
+pub mod start_command;
 pub mod state;
+pub mod wait_command;
 
 #[cfg(test)]
 pub(crate) mod test_helpers;
@@ -111,6 +141,21 @@ const SHELL_TOOL_CATEGORY: &str = "tools";
 /// caller sends no `op`.
 const EXECUTE_COMMAND_OP: &str = "execute command";
 
+/// Operation string that starts a shell command in the background.
+const START_COMMAND_OP: &str = "start command";
+
+/// Operation string that waits, up to a deadline, for a command to exit.
+const WAIT_COMMAND_OP: &str = "wait command";
+
+/// Operation string that types text into a terminal command.
+const SEND_INPUT_OP: &str = "send input";
+
+/// Operation string that presses named keys in a terminal command.
+const SEND_KEYS_OP: &str = "send keys";
+
+/// Operation string that renders a terminal command's screen as text.
+const READ_SCREEN_OP: &str = "read screen";
+
 /// Operation string that lists every command this session has run.
 const LIST_PROCESSES_OP: &str = "list processes";
 
@@ -126,6 +171,12 @@ const GET_LINES_OP: &str = "get lines";
 // Static operation instances for schema generation
 static EXECUTE_CMD: Lazy<execute_command::ExecuteCommand> =
     Lazy::new(execute_command::ExecuteCommand::default);
+static START_CMD: Lazy<start_command::StartCommand> =
+    Lazy::new(start_command::StartCommand::default);
+static WAIT_CMD: Lazy<wait_command::WaitCommand> = Lazy::new(wait_command::WaitCommand::default);
+static SEND_INPUT: Lazy<send_input::SendInput> = Lazy::new(send_input::SendInput::default);
+static SEND_KEYS: Lazy<send_keys::SendKeys> = Lazy::new(send_keys::SendKeys::default);
+static READ_SCREEN: Lazy<read_screen::ReadScreen> = Lazy::new(read_screen::ReadScreen::default);
 static LIST_PROCS: Lazy<list_processes::ListProcesses> =
     Lazy::new(list_processes::ListProcesses::default);
 static KILL_PROC: Lazy<kill_process::KillProcess> = Lazy::new(kill_process::KillProcess::default);
@@ -133,7 +184,8 @@ static GREP_HIST: Lazy<grep_history::GrepHistory> = Lazy::new(grep_history::Grep
 static GET_LNS: Lazy<get_lines::GetLines> = Lazy::new(get_lines::GetLines::default);
 
 /// Static registry of every operation the `shell` tool supports — `execute
-/// command`, `list processes`, `kill process`, `grep history`, and `get
+/// command`, `start command`, `wait command`, `send input`, `send keys`,
+/// `read screen`, `list processes`, `kill process`, `grep history`, and `get
 /// lines`.
 ///
 /// It is the single source of truth for the tool's operation set: schema
@@ -142,6 +194,11 @@ static GET_LNS: Lazy<get_lines::GetLines> = Lazy::new(get_lines::GetLines::defau
 pub static SHELL_OPERATIONS: Lazy<Vec<&'static dyn Operation>> = Lazy::new(|| {
     vec![
         &*EXECUTE_CMD as &dyn Operation,
+        &*START_CMD as &dyn Operation,
+        &*WAIT_CMD as &dyn Operation,
+        &*SEND_INPUT as &dyn Operation,
+        &*SEND_KEYS as &dyn Operation,
+        &*READ_SCREEN as &dyn Operation,
         &*LIST_PROCS as &dyn Operation,
         &*KILL_PROC as &dyn Operation,
         &*GREP_HIST as &dyn Operation,
@@ -245,12 +302,13 @@ fn check_builtin_config(cat: &str) -> Vec<HealthCheck> {
         }
     };
     let deny_count = config.deny.len();
+    let deny_command_count = config.deny_commands.len();
     let permit_count = config.permit.len();
     let mut checks = vec![HealthCheck::ok(
         BUILTIN_CONFIG_CHECK,
         format!(
-            "Builtin shell config parsed successfully ({} deny patterns, {} permit patterns)",
-            deny_count, permit_count
+            "Builtin shell config parsed successfully ({} deny patterns, {} deny command rules, {} permit patterns)",
+            deny_count, deny_command_count, permit_count
         ),
         cat,
     )];
@@ -328,11 +386,12 @@ fn check_config_file(check_name: &str, path: &std::path::Path, cat: &str) -> Hea
         Ok(config) => HealthCheck::ok(
             check_name,
             format!(
-                "{} loaded from {} ({} deny, {} permit patterns)",
+                "{} loaded from {} ({} deny, {} permit patterns, {} deny command rules)",
                 check_name,
                 path.display(),
                 config.deny.len(),
-                config.permit.len()
+                config.permit.len(),
+                config.deny_commands.len()
             ),
             cat,
         ),
@@ -720,6 +779,13 @@ impl McpTool for ShellExecuteTool {
             EXECUTE_COMMAND_OP | "" => {
                 execute_command::run(args, self.state.clone(), _context).await
             }
+            START_COMMAND_OP => {
+                start_command::execute_start_command(args, self.state.clone(), _context).await
+            }
+            WAIT_COMMAND_OP => wait_command::execute_wait_command(&args, self.state.clone()).await,
+            SEND_INPUT_OP => send_input::execute_send_input(&args, self.state.clone()).await,
+            SEND_KEYS_OP => send_keys::execute_send_keys(&args, self.state.clone()).await,
+            READ_SCREEN_OP => read_screen::execute_read_screen(&args, self.state.clone()).await,
             LIST_PROCESSES_OP => list_processes::execute_list_processes(self.state.clone()).await,
             KILL_PROCESS_OP => kill_process::execute_kill_process(&args, self.state.clone()).await,
             GREP_HISTORY_OP => grep_history::execute_grep_history(&args, self.state.clone()).await,
@@ -888,15 +954,20 @@ mod tests {
     async fn test_shell_tool_has_operations() {
         let tool = ShellExecuteTool::new_isolated();
         let ops = tool.operations();
-        assert_eq!(ops.len(), 5);
+        assert_eq!(ops.len(), 10);
         assert!(ops.iter().any(|o| o.op_string() == "execute command"));
+        assert!(ops.iter().any(|o| o.op_string() == "start command"));
+        assert!(ops.iter().any(|o| o.op_string() == "wait command"));
+        assert!(ops.iter().any(|o| o.op_string() == "send input"));
+        assert!(ops.iter().any(|o| o.op_string() == "send keys"));
+        assert!(ops.iter().any(|o| o.op_string() == "read screen"));
         assert!(ops.iter().any(|o| o.op_string() == "list processes"));
         assert!(ops.iter().any(|o| o.op_string() == "kill process"));
         assert!(ops.iter().any(|o| o.op_string() == "grep history"));
         assert!(ops.iter().any(|o| o.op_string() == "get lines"));
     }
 
-    /// The dispatch constants and [`SHELL_OPERATIONS`] must name the same five
+    /// The dispatch constants and [`SHELL_OPERATIONS`] must name the same ten
     /// operations. A constant that drifts from the registry would route an
     /// operation the schema advertises into the unknown-operation arm.
     #[test]
@@ -904,6 +975,11 @@ mod tests {
         let registry: Vec<String> = SHELL_OPERATIONS.iter().map(|o| o.op_string()).collect();
         let constants = [
             EXECUTE_COMMAND_OP,
+            START_COMMAND_OP,
+            WAIT_COMMAND_OP,
+            SEND_INPUT_OP,
+            SEND_KEYS_OP,
+            READ_SCREEN_OP,
             LIST_PROCESSES_OP,
             KILL_PROCESS_OP,
             GREP_HISTORY_OP,
@@ -949,11 +1025,9 @@ mod tests {
     /// pipeline throws it away. Both the tool description and the operation
     /// description carry the blocking fact.
     ///
-    /// The text must also name the limit of that promise: a command the
-    /// timeout kills stores nothing. `finalize_timed_out` only marks the
-    /// command, and `store_command_output` runs solely in
-    /// `finalize_completed`, so `get lines` on a timed-out command returns
-    /// nothing.
+    /// The text must also name the reach of that promise: a command the
+    /// timeout kills keeps the output it printed until then, since output
+    /// streams into the history as it arrives.
     ///
     /// The text must also send file search and file edits off the shell:
     /// this tool is not the file search tool (`rg` is the shell fallback),
@@ -972,7 +1046,7 @@ mod tests {
             "Do not pipe to `tail`",
             "get lines",
             "grep history",
-            "no output is stored",
+            "output printed before the timeout is kept",
             "Do not use grep to search files",
             "use `rg`",
             "Do not use shell to edit files",
@@ -1131,6 +1205,11 @@ mod tests {
         // Should list all valid operations
         for expected_op in &[
             "execute command",
+            "start command",
+            "wait command",
+            "send input",
+            "send keys",
+            "read screen",
             "list processes",
             "kill process",
             "grep history",
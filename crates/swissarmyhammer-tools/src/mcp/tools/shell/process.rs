@@ -4,13 +4,16 @@
 //! output streaming functions, and command spawning utilities.
 
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use swissarmyhammer_common::command::{shell_command, Shell};
-use swissarmyhammer_common::Pretty;
-use tokio::io::{AsyncBufReadExt, BufReader};
+use swissarmyhammer_shell::Sandbox;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, BufReader};
 use tokio::process::{Child, Command};
+use tokio::sync::Mutex;
 
-use super::infrastructure::{OutputBuffer, OutputLimits, ShellError, ShellExecutionResult};
+use super::infrastructure::{OutputLimits, ShellError};
+use super::state::ShellState;
 
 /// How long `Drop` polls a killed child before it gives up on reaping it.
 /// `Drop` cannot await, so this bounds the blocking wait.
@@ -23,6 +26,19 @@ const PROCESS_REAP_POLL_MILLIS: u64 = 10;
 /// when a signal terminates the process.
 const SIGNAL_TERMINATED_EXIT_CODE: i32 = -1;
 
+/// How long each stream is still read after the child exits, for output the
+/// pipes buffered before the exit.
+pub(super) const REMAINING_OUTPUT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// How long a streamed line may wait in memory before it is written to the
+/// shell history, so a chatty command opens the log once per batch rather
+/// than once per line.
+const STREAM_FLUSH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How many streamed lines are written to the shell history at most in one
+/// batch.
+const STREAM_BATCH_LINES: usize = 256;
+
 /// How a process group is asked to end.
 #[derive(Debug, Clone, Copy)]
 enum GroupSignal {
@@ -251,284 +267,180 @@ impl Drop for AsyncProcessGuard {
     }
 }
 
-/// Context for processing output lines
-struct OutputLineContext<'a> {
-    line_count: &'a mut u32,
-    output_buffer: &'a mut OutputBuffer,
-    binary_notified: &'a mut bool,
-}
-
-/// Helper function to process a single output line with common logic
-///
-/// This eliminates code duplication between stdout and stderr processing,
-/// as well as between pre-exit and post-exit output processing.
-///
-/// # Arguments
-///
-/// * `line` - The output line to process
-/// * `ctx` - Context containing line counter, buffer, and progress tracking
-/// * `append_fn` - Function to append data to the appropriate buffer (stdout or stderr)
+/// Where [`stream_output_to_state`] stores what it reads: one command's
+/// output in shell state, capped at a byte budget.
 ///
-/// # Returns
-///
-/// Returns the number of bytes written, or 0 if the buffer limit was reached
-#[inline]
-fn process_output_line(
-    line: String,
-    ctx: &mut OutputLineContext<'_>,
-    append_fn: impl FnOnce(&mut OutputBuffer, &[u8]) -> usize,
-) -> usize {
-    *ctx.line_count += 1;
-
-    // Convert line to bytes with newline
-    let line_bytes = line.as_bytes();
-    let mut line_with_newline = Vec::with_capacity(line_bytes.len() + 1);
-    line_with_newline.extend_from_slice(line_bytes);
-    line_with_newline.push(b'\n');
-
-    // Append to the appropriate buffer
-    let bytes_written = append_fn(ctx.output_buffer, &line_with_newline);
-
-    // Check for binary detection and log once
-    if ctx.output_buffer.has_binary_content() && !*ctx.binary_notified {
-        *ctx.binary_notified = true;
-        tracing::info!("Shell output: Binary content detected");
-    }
-
-    bytes_written
+/// Lines are held in arrival order and written in batches by
+/// [`flush`](Self::flush), one `append_lines` call — and so one open of the
+/// log — per batch.
+struct StateLineSink<'a> {
+    state: &'a Arc<Mutex<ShellState>>,
+    cmd_id: usize,
+    stored_bytes: usize,
+    max_bytes: usize,
+    pending: Vec<String>,
 }
 
-/// Helper function to process a single stream line with error handling
-///
-/// This eliminates duplication in the tokio::select! branches by extracting the common
-/// pattern of processing a line result, handling EOF, and checking buffer limits.
-///
-/// # Returns
-///
-/// Returns true if processing should continue, false if it should stop (due to error or buffer limit)
-#[inline]
-fn process_stream_line_result(
-    line_result: Result<Option<String>, std::io::Error>,
-    ctx: &mut OutputLineContext<'_>,
-    append_fn: impl FnOnce(&mut OutputBuffer, &[u8]) -> usize,
-    stream_name: &str,
-) -> bool {
-    match line_result {
-        Ok(Some(line)) => {
-            let bytes_written = process_output_line(line, ctx, append_fn);
-
-            // If we couldn't write anything, we've hit the limit
-            if bytes_written == 0 && ctx.output_buffer.is_at_limit() {
-                tracing::debug!("Output buffer limit reached, stopping {stream_name} processing");
-                false
-            } else {
-                true
+impl StateLineSink<'_> {
+    /// Hold one line read from `stream_name` for the next batch and report
+    /// whether the stream is still open. A full batch is written at once.
+    ///
+    /// Lines past the byte budget are read and dropped, so the child never
+    /// blocks on a full pipe; the line that crosses the budget is followed by
+    /// a marker recording the cut.
+    async fn store(&mut self, line: std::io::Result<Option<String>>, stream_name: &str) -> bool {
+        let line = match line {
+            Ok(Some(line)) => line,
+            Ok(None) => return false,
+            Err(e) => {
+                tracing::warn!("Error reading {stream_name}: {e}");
+                return false;
             }
+        };
+        if self.stored_bytes >= self.max_bytes {
+            return true;
         }
-        Ok(None) => {
-            // EOF on stream
-            tracing::debug!("{stream_name} EOF reached");
-            true
+        self.stored_bytes += line.len() + 1;
+        self.pending.push(line);
+        if self.stored_bytes >= self.max_bytes {
+            self.pending
+                .push(format!("[output truncated at {} bytes]", self.max_bytes));
         }
-        Err(e) => {
-            tracing::warn!("Error reading {stream_name}: {e}");
-            false
+        if self.pending.len() >= STREAM_BATCH_LINES {
+            self.flush().await;
         }
+        true
     }
-}
 
-/// Helper function to read remaining output from any stream after process exit
-///
-/// This generic function works with both stdout and stderr readers by accepting
-/// any type that implements AsyncRead + Unpin, eliminating code duplication.
-async fn read_remaining_stream_output<R>(
-    reader: &mut tokio::io::Lines<BufReader<R>>,
-    ctx: &mut OutputLineContext<'_>,
-    append_fn: impl Fn(&mut OutputBuffer, &[u8]) -> usize,
-) where
-    R: tokio::io::AsyncRead + Unpin,
-{
-    while let Ok(Some(line)) = reader.next_line().await {
-        if ctx.output_buffer.is_at_limit() {
-            break;
+    /// Write the held lines to shell state in one batch.
+    async fn flush(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+        let lines = std::mem::take(&mut self.pending);
+        if let Err(e) = self
+            .state
+            .lock()
+            .await
+            .append_lines(self.cmd_id, &lines)
+            .await
+        {
+            tracing::warn!("Failed to store output for command {}: {}", self.cmd_id, e);
         }
-        process_output_line(line, ctx, &append_fn);
     }
 }
 
-/// Helper function to read remaining output from a stream with context creation
+/// Read one line, replacing bytes that are not UTF-8 instead of failing on
+/// them. The line ending is dropped; `Ok(None)` means end of stream.
 ///
-/// This wrapper eliminates duplication when creating OutputLineContext for reading
-/// remaining stream output after process exit.
-async fn read_remaining_with_context<R>(
-    reader: &mut tokio::io::Lines<BufReader<R>>,
-    line_count: &mut u32,
-    output_buffer: &mut OutputBuffer,
-    binary_notified: &mut bool,
-    append_fn: impl Fn(&mut OutputBuffer, &[u8]) -> usize,
-) where
-    R: tokio::io::AsyncRead + Unpin,
-{
-    let mut ctx = OutputLineContext {
-        line_count,
-        output_buffer,
-        binary_notified,
-    };
-    read_remaining_stream_output(reader, &mut ctx, append_fn).await;
-}
-
-/// Setup for output capture from child process
-struct OutputCaptureSetup {
-    stdout_reader: tokio::io::Lines<BufReader<tokio::process::ChildStdout>>,
-    stderr_reader: tokio::io::Lines<BufReader<tokio::process::ChildStderr>>,
-    output_buffer: OutputBuffer,
+/// Bytes a cancelled call had already read stay in `buf`, so this can run as a
+/// `select!` branch as long as every call for a stream passes the same `buf`.
+async fn read_line_lossy<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    buf: &mut Vec<u8>,
+) -> std::io::Result<Option<String>> {
+    if reader.read_until(b'\n', buf).await? == 0 && buf.is_empty() {
+        return Ok(None);
+    }
+    let mut line = std::mem::take(buf);
+    if line.last() == Some(&b'\n') {
+        line.pop();
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+    }
+    Ok(Some(String::from_utf8_lossy(&line).into_owned()))
 }
 
-/// Initialize output capture for a child process
-fn setup_output_capture(
+/// Stream a running command's output into shell state as it arrives, so
+/// `get lines` and `grep history` read it while the command still runs.
+///
+/// Lines land in the log in arrival order, stdout and stderr interleaved, up
+/// to `output_limits.max_output_size` bytes. Returns the exit code, -1 when a
+/// signal ended the command, or `None` when `timeout` passed first. A timed
+/// out command is left running for the caller to kill, and everything it
+/// printed until then — a last line without its newline included — is stored.
+pub(super) async fn stream_output_to_state(
     child: &mut Child,
+    state: &Arc<Mutex<ShellState>>,
+    cmd_id: usize,
     output_limits: &OutputLimits,
-) -> Result<OutputCaptureSetup, ShellError> {
+    timeout: Option<Duration>,
+) -> Result<Option<i32>, ShellError> {
     let stdout = child.stdout.take().ok_or_else(|| ShellError::SystemError {
         message: "Failed to capture stdout from child process".to_string(),
     })?;
-
     let stderr = child.stderr.take().ok_or_else(|| ShellError::SystemError {
         message: "Failed to capture stderr from child process".to_string(),
     })?;
+    let mut stdout = BufReader::new(stdout);
+    let mut stderr = BufReader::new(stderr);
+    let mut stdout_buf = Vec::new();
+    let mut stderr_buf = Vec::new();
+    let mut sink = StateLineSink {
+        state,
+        cmd_id,
+        stored_bytes: 0,
+        max_bytes: output_limits.max_output_size,
+        pending: Vec::new(),
+    };
+    let mut stdout_open = true;
+    let mut stderr_open = true;
+    let mut flush_timer = tokio::time::interval(STREAM_FLUSH_INTERVAL);
+    let deadline = async {
+        match timeout {
+            Some(timeout) => tokio::time::sleep(timeout).await,
+            None => std::future::pending().await,
+        }
+    };
+    tokio::pin!(deadline);
 
-    Ok(OutputCaptureSetup {
-        stdout_reader: BufReader::new(stdout).lines(),
-        stderr_reader: BufReader::new(stderr).lines(),
-        output_buffer: OutputBuffer::new(output_limits.max_output_size),
-    })
-}
-
-/// Collect any remaining output after process exits
-async fn collect_remaining_output(
-    stdout_reader: &mut tokio::io::Lines<BufReader<tokio::process::ChildStdout>>,
-    stderr_reader: &mut tokio::io::Lines<BufReader<tokio::process::ChildStderr>>,
-    line_count: &mut u32,
-    output_buffer: &mut OutputBuffer,
-    binary_notified: &mut bool,
-) {
-    const REMAINING_OUTPUT_TIMEOUT: Duration = Duration::from_millis(500);
-
-    let stdout_future = read_remaining_with_context(
-        stdout_reader,
-        line_count,
-        output_buffer,
-        binary_notified,
-        |buf, data| buf.append_stdout(data),
-    );
-    let _ = tokio::time::timeout(REMAINING_OUTPUT_TIMEOUT, stdout_future).await;
-
-    let stderr_future = read_remaining_with_context(
-        stderr_reader,
-        line_count,
-        output_buffer,
-        binary_notified,
-        |buf, data| buf.append_stderr(data),
-    );
-    let _ = tokio::time::timeout(REMAINING_OUTPUT_TIMEOUT, stderr_future).await;
-}
-
-/// Stream output until process completes or buffer limit reached
-async fn stream_output_until_complete(
-    stdout_reader: &mut tokio::io::Lines<BufReader<tokio::process::ChildStdout>>,
-    stderr_reader: &mut tokio::io::Lines<BufReader<tokio::process::ChildStderr>>,
-    child: &mut Child,
-    line_count: &mut u32,
-    output_buffer: &mut OutputBuffer,
-    binary_notified: &mut bool,
-) -> Result<std::process::ExitStatus, ShellError> {
-    loop {
+    let exit_status = loop {
         tokio::select! {
-            stdout_line = stdout_reader.next_line() => {
-                let mut ctx = OutputLineContext {
-                    line_count, output_buffer, binary_notified,
-                };
-                if !process_stream_line_result(
-                    stdout_line, &mut ctx,
-                    |buf, data| buf.append_stdout(data), "stdout",
-                ) { break; }
+            line = read_line_lossy(&mut stdout, &mut stdout_buf), if stdout_open => {
+                stdout_open = sink.store(line, "stdout").await;
             }
-
-            stderr_line = stderr_reader.next_line() => {
-                let mut ctx = OutputLineContext {
-                    line_count, output_buffer, binary_notified,
-                };
-                if !process_stream_line_result(
-                    stderr_line, &mut ctx,
-                    |buf, data| buf.append_stderr(data), "stderr",
-                ) { break; }
+            line = read_line_lossy(&mut stderr, &mut stderr_buf), if stderr_open => {
+                stderr_open = sink.store(line, "stderr").await;
             }
-
-            exit_status = child.wait() => {
-                return exit_status.map_err(|e| ShellError::ExecutionError {
-                    command: "child process".to_string(),
-                    message: format!("Failed to wait for process: {e}"),
-                });
+            _ = flush_timer.tick() => sink.flush().await,
+            _ = &mut deadline => {
+                for partial in [stdout_buf, stderr_buf] {
+                    if !partial.is_empty() {
+                        let line = String::from_utf8_lossy(&partial).into_owned();
+                        sink.store(Ok(Some(line)), "output").await;
+                    }
+                }
+                sink.flush().await;
+                return Ok(None);
             }
+            status = child.wait() => break status,
         }
-
-        if output_buffer.is_at_limit() {
-            tracing::debug!("Output buffer at limit, stopping all processing");
-            break;
-        }
-    }
-
-    child.wait().await.map_err(|e| ShellError::ExecutionError {
+    };
+    let exit_status = exit_status.map_err(|e| ShellError::ExecutionError {
         command: "child process".to_string(),
         message: format!("Failed to wait for process: {e}"),
-    })
-}
+    })?;
 
-/// Process child output streams with limits using async streaming
-///
-/// Handles the streaming capture of stdout and stderr from a child process
-/// with configurable size limits, binary detection, and intelligent truncation.
-pub(super) async fn process_child_output_with_limits(
-    child: &mut Child,
-    output_limits: &OutputLimits,
-) -> Result<(std::process::ExitStatus, OutputBuffer, u32), ShellError> {
-    let mut setup = setup_output_capture(child, output_limits)?;
-    let mut line_count: u32 = 0;
-    let mut binary_notified = false;
-
-    let exit_status = stream_output_until_complete(
-        &mut setup.stdout_reader,
-        &mut setup.stderr_reader,
-        child,
-        &mut line_count,
-        &mut setup.output_buffer,
-        &mut binary_notified,
-    )
-    .await?;
-
-    #[derive(serde::Serialize, Debug)]
-    struct ProcessExitInfo {
-        exit_code: Option<i32>,
-        success: bool,
-    }
-    let exit_info = ProcessExitInfo {
-        exit_code: exit_status.code(),
-        success: exit_status.success(),
+    let stdout_rest = async {
+        while stdout_open {
+            let line = read_line_lossy(&mut stdout, &mut stdout_buf).await;
+            stdout_open = sink.store(line, "stdout").await;
+        }
     };
-    tracing::debug!("Process exited with status: {}", Pretty(&exit_info));
-
-    collect_remaining_output(
-        &mut setup.stdout_reader,
-        &mut setup.stderr_reader,
-        &mut line_count,
-        &mut setup.output_buffer,
-        &mut binary_notified,
-    )
-    .await;
-
-    setup.output_buffer.add_truncation_marker();
+    let _ = tokio::time::timeout(REMAINING_OUTPUT_TIMEOUT, stdout_rest).await;
+    let stderr_rest = async {
+        while stderr_open {
+            let line = read_line_lossy(&mut stderr, &mut stderr_buf).await;
+            stderr_open = sink.store(line, "stderr").await;
+        }
+    };
+    let _ = tokio::time::timeout(REMAINING_OUTPUT_TIMEOUT, stderr_rest).await;
+    sink.flush().await;
 
-    Ok((exit_status, setup.output_buffer, line_count))
+    Ok(Some(
+        exit_status.code().unwrap_or(SIGNAL_TERMINATED_EXIT_CODE),
+    ))
 }
 
 /// Validate and prepare working directory.
@@ -555,21 +467,38 @@ pub(super) fn prepare_working_directory(
 ///
 /// The interpreter and the stream wiring come from
 /// [`shell_command`](swissarmyhammer_common::command::shell_command); this
-/// adds the working directory and the caller's environment, and hands the
-/// result to tokio so the child can be awaited.
+/// adds the working directory and the caller's environment, confines the
+/// child to `sandbox` when one is given, and hands the result to tokio so the
+/// child can be awaited.
+///
+/// On Unix the child leads a process group of its own, so `kill process` and
+/// the guard's cleanup reach every process the command started.
+///
+/// # Errors
+///
+/// Reports [`ShellError::SandboxError`] when the sandbox cannot be prepared.
 pub(super) fn prepare_shell_command(
     command: &str,
     work_dir: &Path,
     environment: Option<&std::collections::HashMap<String, String>>,
-) -> Command {
-    let mut cmd = Command::from(shell_command(Shell::Platform, command));
+    sandbox: Option<&Sandbox>,
+) -> Result<Command, ShellError> {
+    let mut std_cmd = shell_command(Shell::Platform, command);
+    if let Some(sandbox) = sandbox {
+        sandbox
+            .confine(&mut std_cmd)
+            .map_err(|source| ShellError::SandboxError { source })?;
+    }
+    let mut cmd = Command::from(std_cmd);
     cmd.current_dir(work_dir);
+    #[cfg(unix)]
+    cmd.process_group(0);
 
     if let Some(env_vars) = environment {
         cmd.envs(env_vars);
     }
 
-    cmd
+    Ok(cmd)
 }
 
 /// Spawn command process with error handling
@@ -593,54 +522,6 @@ pub(super) fn spawn_command_process(
     })
 }
 
-/// Format execution result from output buffer
-pub(super) fn format_execution_result(
-    command_id: usize,
-    command: String,
-    work_dir: PathBuf,
-    exit_status: std::process::ExitStatus,
-    output_buffer: OutputBuffer,
-    execution_time_ms: u64,
-    output_limits: &OutputLimits,
-) -> ShellExecutionResult {
-    let exit_code = exit_status.code().unwrap_or(SIGNAL_TERMINATED_EXIT_CODE);
-    let truncation_info = if output_buffer.is_truncated() {
-        format!(
-            " (output truncated at {} bytes)",
-            output_limits.max_output_size
-        )
-    } else {
-        String::new()
-    };
-    let binary_info = if output_buffer.has_binary_content() {
-        " (binary content detected)"
-    } else {
-        ""
-    };
-
-    tracing::info!(
-        "Command '{}' completed with exit code {} in {}ms{}{}",
-        command,
-        exit_code,
-        execution_time_ms,
-        truncation_info,
-        binary_info
-    );
-
-    ShellExecutionResult {
-        command_id,
-        command,
-        exit_code,
-        stdout: output_buffer.get_stdout(),
-        stderr: output_buffer.get_stderr(),
-        execution_time_ms,
-        working_directory: work_dir,
-        output_truncated: output_buffer.is_truncated(),
-        total_output_size: output_buffer.total_bytes_processed(),
-        binary_output_detected: output_buffer.has_binary_content(),
-    }
-}
-
 /// Spawn a shell command and return the guard (with PID available) and working dir.
 /// The guard owns the child process — if dropped, it kills the process.
 pub(super) fn spawn_shell_command(
@@ -648,49 +529,15 @@ pub(super) fn spawn_shell_command(
     working_directory: Option<PathBuf>,
     default_dir: PathBuf,
     environment: Option<&std::collections::HashMap<String, String>>,
+    sandbox: Option<&Sandbox>,
 ) -> Result<(AsyncProcessGuard, PathBuf), ShellError> {
     let work_dir = prepare_working_directory(working_directory, default_dir)?;
-    let cmd = prepare_shell_command(command, &work_dir, environment);
+    let cmd = prepare_shell_command(command, &work_dir, environment, sandbox)?;
     let child = spawn_command_process(cmd, command, &work_dir)?;
     let process_guard = AsyncProcessGuard::new(child, command.to_string());
     Ok((process_guard, work_dir))
 }
 
-/// Execute using an already-spawned process guard. The guard retains child ownership,
-/// so if this future is cancelled (e.g., by timeout), the guard's Drop kills the process.
-pub(super) async fn execute_with_guard(
-    process_guard: &mut AsyncProcessGuard,
-    command_id: usize,
-    command: String,
-    work_dir: PathBuf,
-) -> Result<ShellExecutionResult, ShellError> {
-    let start_time = Instant::now();
-    let output_limits = OutputLimits::with_defaults().map_err(|e| ShellError::SystemError {
-        message: format!("Invalid output configuration: {e}"),
-    })?;
-
-    let child = process_guard
-        .child_mut()
-        .ok_or_else(|| ShellError::SystemError {
-            message: "Process guard has no child process".to_string(),
-        })?;
-
-    let (exit_status, output_buffer, _) =
-        process_child_output_with_limits(child, &output_limits).await?;
-
-    let execution_time_ms = start_time.elapsed().as_millis() as u64;
-
-    Ok(format_execution_result(
-        command_id,
-        command,
-        work_dir,
-        exit_status,
-        output_buffer,
-        execution_time_ms,
-        &output_limits,
-    ))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1026,7 +873,7 @@ mod tests {
     #[test]
     fn test_prepare_shell_command_sets_working_dir() {
         let tmp = tempfile::tempdir().unwrap();
-        let cmd = prepare_shell_command("echo hello", tmp.path(), None);
+        let cmd = prepare_shell_command("echo hello", tmp.path(), None, None).unwrap();
         // We can't inspect Command internals easily, but the command should not panic
         // and should be constructable. The test validates the code path runs.
         let _ = cmd;
@@ -1038,7 +885,7 @@ mod tests {
         let tmp = tempfile::tempdir().unwrap();
         let mut env = std::collections::HashMap::new();
         env.insert("MY_VAR".to_string(), "my_value".to_string());
-        let cmd = prepare_shell_command("echo $MY_VAR", tmp.path(), Some(&env));
+        let cmd = prepare_shell_command("echo $MY_VAR", tmp.path(), Some(&env), None).unwrap();
         let _ = cmd;
     }
 
@@ -1050,7 +897,7 @@ mod tests {
     #[tokio::test]
     async fn test_spawn_command_process_success() {
         let tmp = tempfile::tempdir().unwrap();
-        let cmd = prepare_shell_command("echo spawn_test", tmp.path(), None);
+        let cmd = prepare_shell_command("echo spawn_test", tmp.path(), None, None).unwrap();
         let result = spawn_command_process(cmd, "echo spawn_test", tmp.path());
         assert!(result.is_ok());
         let mut child = result.unwrap();
@@ -1085,6 +932,7 @@ mod tests {
             Some(tmp.path().to_path_buf()),
             PathBuf::from("/unused"),
             None,
+            None,
         );
         assert!(result.is_ok());
         let (mut guard, work_dir) = result.unwrap();
@@ -1104,6 +952,7 @@ mod tests {
             Some(tmp.path().to_path_buf()),
             PathBuf::from("/unused"),
             Some(&env),
+            None,
         );
         assert!(result.is_ok());
         let (mut guard, _) = result.unwrap();
@@ -1118,402 +967,68 @@ mod tests {
             Some(PathBuf::from("/nonexistent/dir")),
             PathBuf::from("/unused"),
             None,
+            None,
         );
         assert!(result.is_err());
     }
 
     // -----------------------------------------------------------------------
-    // format_execution_result tests
+    // stream_output_to_state tests
     // -----------------------------------------------------------------------
 
-    /// Test that `format_execution_result` produces correct fields for a successful command.
-    #[test]
-    fn test_format_execution_result_success() {
-        let limits = OutputLimits::default();
-        let buffer = OutputBuffer::new(limits.max_output_size);
-        let status = std::process::Command::new("true")
-            .status()
-            .expect("failed to run `true`");
-
-        let result = format_execution_result(
-            42,
-            "echo test".to_string(),
-            PathBuf::from("/tmp"),
-            status,
-            buffer,
-            150,
-            &limits,
-        );
-
-        assert_eq!(result.command_id, 42);
-        assert_eq!(result.command, "echo test");
-        assert_eq!(result.exit_code, 0);
-        assert_eq!(result.execution_time_ms, 150);
-        assert_eq!(result.working_directory, PathBuf::from("/tmp"));
-        assert!(!result.output_truncated);
-        assert!(!result.binary_output_detected);
-    }
-
-    /// Test that `format_execution_result` reports truncation correctly.
-    #[test]
-    fn test_format_execution_result_with_truncation() {
-        let limits = OutputLimits {
-            max_output_size: 10,
-            max_line_length: 100,
-            enable_streaming: false,
-        };
-        let mut buffer = OutputBuffer::new(10);
-        // Fill buffer beyond capacity to trigger truncation
-        buffer.append_stdout(b"12345678901234567890");
-        buffer.add_truncation_marker();
-
-        let status = std::process::Command::new("true")
-            .status()
-            .expect("failed to run `true`");
-
-        let result = format_execution_result(
-            1,
-            "big_output".to_string(),
-            PathBuf::from("/tmp"),
-            status,
-            buffer,
-            50,
-            &limits,
-        );
-
-        assert!(result.output_truncated);
-    }
-
-    /// Test that `format_execution_result` reports binary content detection.
-    #[test]
-    fn test_format_execution_result_with_binary() {
-        let limits = OutputLimits::default();
-        let mut buffer = OutputBuffer::new(limits.max_output_size);
-        // Write binary data (null bytes trigger binary detection)
-        buffer.append_stdout(&[0x00, 0x01, 0x02, 0xFF]);
-
-        let status = std::process::Command::new("true")
-            .status()
-            .expect("failed to run `true`");
-
-        let result = format_execution_result(
-            2,
-            "binary_cmd".to_string(),
-            PathBuf::from("/tmp"),
-            status,
-            buffer,
-            10,
-            &limits,
-        );
-
-        assert!(result.binary_output_detected);
-    }
-
-    // -----------------------------------------------------------------------
-    // execute_with_guard tests
-    // -----------------------------------------------------------------------
-
-    /// Test that `execute_with_guard` captures output from a simple echo command.
-    #[tokio::test]
-    async fn test_execute_with_guard_captures_output() {
-        let tmp = tempfile::tempdir().unwrap();
-        let (mut guard, work_dir) = spawn_shell_command(
-            "echo guard_test",
-            Some(tmp.path().to_path_buf()),
-            PathBuf::from("/unused"),
-            None,
-        )
-        .expect("spawn failed");
-
-        let result =
-            execute_with_guard(&mut guard, 99, "echo guard_test".to_string(), work_dir).await;
-
-        assert!(result.is_ok());
-        let exec_result = result.unwrap();
-        assert_eq!(exec_result.command_id, 99);
-        assert_eq!(exec_result.exit_code, 0);
-        assert!(exec_result.stdout.contains("guard_test"));
-    }
-
-    /// Test that `execute_with_guard` captures stderr.
-    #[tokio::test]
-    async fn test_execute_with_guard_captures_stderr() {
-        let tmp = tempfile::tempdir().unwrap();
-        let (mut guard, work_dir) = spawn_shell_command(
-            "echo error_output >&2",
-            Some(tmp.path().to_path_buf()),
-            PathBuf::from("/unused"),
-            None,
-        )
-        .expect("spawn failed");
-
-        let result = execute_with_guard(
-            &mut guard,
-            100,
-            "echo error_output >&2".to_string(),
-            work_dir,
-        )
-        .await;
-
-        assert!(result.is_ok());
-        let exec_result = result.unwrap();
-        assert!(exec_result.stderr.contains("error_output"));
+    /// Spawn `command` with piped output and a fresh shell state to stream it
+    /// into.
+    fn spawn_streamed(
+        command: &str,
+        dir: &tempfile::TempDir,
+    ) -> (AsyncProcessGuard, Arc<Mutex<ShellState>>, usize) {
+        let (guard, _) = spawn_shell_command(command, None, dir.path().to_path_buf(), None, None)
+            .expect("spawn should succeed");
+        let mut state = ShellState::new_in_dir(dir.path().join(".shell")).unwrap();
+        let cmd_id = state.start_command(command);
+        (guard, Arc::new(Mutex::new(state)), cmd_id)
     }
 
-    /// Test that `execute_with_guard` reports non-zero exit codes.
+    /// Invalid UTF-8 is replaced rather than ending the stream early.
     #[tokio::test]
-    async fn test_execute_with_guard_nonzero_exit() {
-        let tmp = tempfile::tempdir().unwrap();
-        let (mut guard, work_dir) = spawn_shell_command(
-            "exit 42",
-            Some(tmp.path().to_path_buf()),
-            PathBuf::from("/unused"),
-            None,
-        )
-        .expect("spawn failed");
-
-        let result = execute_with_guard(&mut guard, 101, "exit 42".to_string(), work_dir).await;
-
-        assert!(result.is_ok());
-        let exec_result = result.unwrap();
-        assert_eq!(exec_result.exit_code, 42);
-    }
-
-    /// Test that `execute_with_guard` errors when guard has no child.
-    #[tokio::test]
-    async fn test_execute_with_guard_no_child() {
-        let tmp = tempfile::tempdir().unwrap();
-        let (mut guard, work_dir) = spawn_shell_command(
-            "echo hello",
-            Some(tmp.path().to_path_buf()),
-            PathBuf::from("/unused"),
-            None,
-        )
-        .expect("spawn failed");
-
-        // Take the child away so the guard is empty
-        let _ = guard.take_child();
-
-        let result = execute_with_guard(&mut guard, 102, "echo hello".to_string(), work_dir).await;
-
-        assert!(result.is_err());
-        assert!(matches!(
-            result.unwrap_err(),
-            ShellError::SystemError { .. }
-        ));
-    }
-
-    // -----------------------------------------------------------------------
-    // process_child_output_with_limits tests
-    // -----------------------------------------------------------------------
-
-    /// Test that `process_child_output_with_limits` captures both stdout and stderr.
-    #[tokio::test]
-    async fn test_process_child_output_with_limits_captures_both_streams() {
-        let tmp = tempfile::tempdir().unwrap();
-        let cmd =
-            prepare_shell_command("echo stdout_data && echo stderr_data >&2", tmp.path(), None);
-        let mut child =
-            spawn_command_process(cmd, "echo stdout_data && echo stderr_data >&2", tmp.path())
-                .unwrap();
-
+    async fn test_stream_output_to_state_keeps_reading_past_invalid_utf8() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let (mut guard, state, cmd_id) = spawn_streamed("printf 'a\\377b\\nafter\\n'", &dir);
         let limits = OutputLimits::with_defaults().unwrap();
-        let (status, buffer, line_count) = process_child_output_with_limits(&mut child, &limits)
-            .await
-            .unwrap();
-
-        assert!(status.success());
-        assert!(line_count >= 2);
-        let stdout = buffer.get_stdout();
-        let stderr = buffer.get_stderr();
-        assert!(stdout.contains("stdout_data"));
-        assert!(stderr.contains("stderr_data"));
-    }
-
-    // -----------------------------------------------------------------------
-    // setup_output_capture tests
-    // -----------------------------------------------------------------------
 
-    /// Test that `setup_output_capture` fails when stdout is already taken.
-    #[tokio::test]
-    async fn test_setup_output_capture_no_stdout() {
-        let mut cmd = Command::new("echo");
-        cmd.arg("test");
-        // Don't pipe stdout — leave it as null
-        cmd.stdout(std::process::Stdio::null());
-        cmd.stderr(std::process::Stdio::piped());
+        let exit =
+            stream_output_to_state(guard.child_mut().unwrap(), &state, cmd_id, &limits, None)
+                .await
+                .unwrap();
 
-        let mut child = cmd.spawn().expect("Failed to spawn");
-        let limits = OutputLimits::default();
-        let result = setup_output_capture(&mut child, &limits);
-        assert!(result.is_err());
+        assert_eq!(exit, Some(0));
+        let lines = state.lock().await.get_lines(cmd_id, None, None).unwrap();
+        let texts: Vec<&str> = lines.iter().map(|(_, text)| text.as_str()).collect();
+        assert_eq!(texts, vec!["a\u{FFFD}b", "after"]);
     }
 
-    /// Test that `setup_output_capture` fails when stderr is already taken.
+    /// A timeout stores what the command printed before it, partial last
+    /// line included.
     #[tokio::test]
-    async fn test_setup_output_capture_no_stderr() {
-        let mut cmd = Command::new("echo");
-        cmd.arg("test");
-        cmd.stdout(std::process::Stdio::piped());
-        // Don't pipe stderr — leave it as null
-        cmd.stderr(std::process::Stdio::null());
-
-        let mut child = cmd.spawn().expect("Failed to spawn");
-        let limits = OutputLimits::default();
-        let result = setup_output_capture(&mut child, &limits);
-        assert!(result.is_err());
-    }
-
-    // -----------------------------------------------------------------------
-    // process_output_line / process_stream_line_result tests
-    // -----------------------------------------------------------------------
-
-    /// Test that `process_output_line` increments line count and writes to buffer.
-    #[test]
-    fn test_process_output_line_basic() {
-        let mut line_count: u32 = 0;
-        let mut buffer = OutputBuffer::new(1024);
-        let mut binary_notified = false;
-
-        let mut ctx = OutputLineContext {
-            line_count: &mut line_count,
-            output_buffer: &mut buffer,
-            binary_notified: &mut binary_notified,
-        };
-
-        let bytes = process_output_line("hello world".to_string(), &mut ctx, |buf, data| {
-            buf.append_stdout(data)
-        });
-
-        assert_eq!(line_count, 1);
-        assert!(bytes > 0);
-        assert!(buffer.get_stdout().contains("hello world"));
-    }
-
-    /// Test that `process_output_line` detects binary content.
-    #[test]
-    fn test_process_output_line_binary_detection() {
-        let mut line_count: u32 = 0;
-        let mut buffer = OutputBuffer::new(1024);
-        let mut binary_notified = false;
-
-        // First, write some binary content to trigger detection
-        buffer.append_stdout(&[0x00, 0x01, 0x02]);
-
-        let mut ctx = OutputLineContext {
-            line_count: &mut line_count,
-            output_buffer: &mut buffer,
-            binary_notified: &mut binary_notified,
-        };
-
-        let _ = process_output_line("after binary".to_string(), &mut ctx, |buf, data| {
-            buf.append_stdout(data)
-        });
-
-        assert!(binary_notified);
-    }
-
-    /// Test that `process_stream_line_result` handles Ok(Some(line)) correctly.
-    #[test]
-    fn test_process_stream_line_result_ok_some() {
-        let mut line_count: u32 = 0;
-        let mut buffer = OutputBuffer::new(1024);
-        let mut binary_notified = false;
-
-        let mut ctx = OutputLineContext {
-            line_count: &mut line_count,
-            output_buffer: &mut buffer,
-            binary_notified: &mut binary_notified,
-        };
-
-        let should_continue = process_stream_line_result(
-            Ok(Some("test line".to_string())),
-            &mut ctx,
-            |buf, data| buf.append_stdout(data),
-            "stdout",
-        );
-
-        assert!(should_continue);
-        assert_eq!(line_count, 1);
-    }
-
-    /// Test that `process_stream_line_result` handles Ok(None) (EOF) correctly.
-    #[test]
-    fn test_process_stream_line_result_eof() {
-        let mut line_count: u32 = 0;
-        let mut buffer = OutputBuffer::new(1024);
-        let mut binary_notified = false;
-
-        let mut ctx = OutputLineContext {
-            line_count: &mut line_count,
-            output_buffer: &mut buffer,
-            binary_notified: &mut binary_notified,
-        };
-
-        let should_continue = process_stream_line_result(
-            Ok(None),
-            &mut ctx,
-            |buf, data| buf.append_stdout(data),
-            "stdout",
-        );
-
-        // EOF returns true (continue processing other streams)
-        assert!(should_continue);
-        assert_eq!(line_count, 0);
-    }
-
-    /// Test that `process_stream_line_result` handles Err correctly.
-    #[test]
-    fn test_process_stream_line_result_error() {
-        let mut line_count: u32 = 0;
-        let mut buffer = OutputBuffer::new(1024);
-        let mut binary_notified = false;
-
-        let mut ctx = OutputLineContext {
-            line_count: &mut line_count,
-            output_buffer: &mut buffer,
-            binary_notified: &mut binary_notified,
-        };
-
-        let should_continue = process_stream_line_result(
-            Err(std::io::Error::new(
-                std::io::ErrorKind::BrokenPipe,
-                "pipe broken",
-            )),
-            &mut ctx,
-            |buf, data| buf.append_stdout(data),
-            "stdout",
-        );
-
-        // Error returns false (stop processing)
-        assert!(!should_continue);
-    }
-
-    /// Test that `process_stream_line_result` stops when buffer limit reached.
-    #[test]
-    fn test_process_stream_line_result_buffer_limit() {
-        let mut line_count: u32 = 0;
-        // Tiny buffer that will fill quickly
-        let mut buffer = OutputBuffer::new(5);
-        let mut binary_notified = false;
-
-        // Fill the buffer first
-        buffer.append_stdout(b"12345");
-
-        let mut ctx = OutputLineContext {
-            line_count: &mut line_count,
-            output_buffer: &mut buffer,
-            binary_notified: &mut binary_notified,
-        };
+    async fn test_stream_output_to_state_stores_output_before_timeout() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let (mut guard, state, cmd_id) =
+            spawn_streamed("echo started; printf partial; sleep 30", &dir);
+        let limits = OutputLimits::with_defaults().unwrap();
 
-        let should_continue = process_stream_line_result(
-            Ok(Some("overflow data".to_string())),
-            &mut ctx,
-            |buf, data| buf.append_stdout(data),
-            "stdout",
-        );
+        let exit = stream_output_to_state(
+            guard.child_mut().unwrap(),
+            &state,
+            cmd_id,
+            &limits,
+            Some(Duration::from_secs(1)),
+        )
+        .await
+        .unwrap();
 
-        // Should stop because buffer is at limit
-        assert!(!should_continue);
+        assert_eq!(exit, None);
+        let lines = state.lock().await.get_lines(cmd_id, None, None).unwrap();
+        let texts: Vec<&str> = lines.iter().map(|(_, text)| text.as_str()).collect();
+        assert_eq!(texts, vec!["started", "partial"]);
     }
 }
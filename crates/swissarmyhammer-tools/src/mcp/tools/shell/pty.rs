@@ -0,0 +1,284 @@
+//! Pseudo-terminal sessions for interactive commands
+//!
+//! A command started with `pty: true` runs on a pseudo-terminal instead of
+//! pipes, so programs that insist on a terminal — REPLs, `git rebase` prompts,
+//! debuggers, TUI installers — behave as they would for a person at a
+//! keyboard. A reader thread feeds everything the program draws through a VT
+//! parser, `read screen` renders the parser's grid as text, and `send input`
+//! and `send keys` write to the terminal's input side.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use portable_pty::{native_pty_system, Child, CommandBuilder, MasterPty, PtySize};
+use swissarmyhammer_common::command::Shell;
+
+use super::infrastructure::ShellError;
+
+/// Rows of the terminal every session gets.
+pub const PTY_ROWS: u16 = 24;
+
+/// Columns of the terminal every session gets.
+pub const PTY_COLS: u16 = 80;
+
+/// `TERM` the program sees, naming the sequences the VT parser understands.
+const PTY_TERM: &str = "xterm-256color";
+
+/// Bytes the reader thread takes from the terminal per read.
+const PTY_READ_BUFFER_BYTES: usize = 4096;
+
+/// How long `send input` and `send keys` let the program react before they
+/// answer with the screen.
+pub(super) const INPUT_SETTLE: Duration = Duration::from_millis(200);
+
+/// How long `send input` and `send keys` wait for the terminal to take their
+/// bytes before they give up on a program that does not read its input.
+pub(super) const INPUT_WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Names `send keys` accepts besides `ctrl-<letter>`, with the bytes a
+/// terminal sends for each.
+const NAMED_KEYS: &[(&str, &[u8])] = &[
+    ("enter", b"\r"),
+    ("tab", b"\t"),
+    ("space", b" "),
+    ("escape", b"\x1b"),
+    ("backspace", b"\x7f"),
+    ("delete", b"\x1b[3~"),
+    ("up", b"\x1b[A"),
+    ("down", b"\x1b[B"),
+    ("right", b"\x1b[C"),
+    ("left", b"\x1b[D"),
+    ("home", b"\x1b[H"),
+    ("end", b"\x1b[F"),
+    ("pageup", b"\x1b[5~"),
+    ("pagedown", b"\x1b[6~"),
+];
+
+/// The input side of a terminal, shared so a write can run without holding
+/// the session.
+///
+/// A write blocks until the terminal takes the bytes, which it stops doing
+/// once its buffer is full of input the program does not read.
+#[derive(Clone)]
+pub struct TerminalInput(Arc<Mutex<Box<dyn Write + Send>>>);
+
+impl TerminalInput {
+    /// Write `bytes` to the program's input, blocking until the terminal
+    /// takes them all.
+    ///
+    /// # Errors
+    ///
+    /// Reports any error the terminal returns for the write.
+    pub fn write(&self, bytes: &[u8]) -> std::io::Result<()> {
+        let mut writer = self
+            .0
+            .lock()
+            .map_err(|_| std::io::Error::other("a previous write to the terminal panicked"))?;
+        writer.write_all(bytes)?;
+        writer.flush()
+    }
+}
+
+/// One command running on a pseudo-terminal.
+///
+/// The session outlives its program, so the last screen stays readable after
+/// the program exits; only the input side closes.
+pub struct PtySession {
+    /// The terminal's controlling side. Dropping it hangs the program up, so
+    /// the session holds it for as long as it lives.
+    _master: Box<dyn MasterPty + Send>,
+    /// The terminal's input side, or `None` once the program has exited.
+    writer: Option<TerminalInput>,
+    /// Everything the program drew, parsed into a grid.
+    parser: Arc<Mutex<vt100::Parser>>,
+    /// The thread feeding the parser, until someone waits for it.
+    reader: Option<JoinHandle<()>>,
+}
+
+impl fmt::Debug for PtySession {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PtySession")
+            .field("accepts_input", &self.writer.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl PtySession {
+    /// Start `command` on a new pseudo-terminal through the platform shell.
+    ///
+    /// Returns the session and the child, which the caller waits on.
+    pub(super) fn spawn(
+        command: &str,
+        work_dir: &Path,
+        environment: Option<&HashMap<String, String>>,
+    ) -> Result<(Self, Box<dyn Child + Send + Sync>), ShellError> {
+        let failed = |e: anyhow::Error| ShellError::ExecutionError {
+            command: command.to_string(),
+            message: format!("failed to start on a pseudo-terminal: {e}"),
+        };
+        let pair = native_pty_system()
+            .openpty(PtySize {
+                rows: PTY_ROWS,
+                cols: PTY_COLS,
+                pixel_width: 0,
+                pixel_height: 0,
+            })
+            .map_err(failed)?;
+
+        let (program, flag) = Shell::Platform.program_and_flag();
+        let mut cmd = CommandBuilder::new(program);
+        cmd.arg(flag);
+        cmd.arg(command);
+        cmd.cwd(work_dir);
+        cmd.env("TERM", PTY_TERM);
+        if let Some(env_vars) = environment {
+            for (key, value) in env_vars {
+                cmd.env(key, value);
+            }
+        }
+
+        let child = pair.slave.spawn_command(cmd).map_err(failed)?;
+        // The child holds its own handle on the terminal; ours would keep the
+        // reader from seeing the end of the output.
+        drop(pair.slave);
+        let mut output = pair.master.try_clone_reader().map_err(failed)?;
+        let writer = pair.master.take_writer().map_err(failed)?;
+
+        let parser = Arc::new(Mutex::new(vt100::Parser::new(PTY_ROWS, PTY_COLS, 0)));
+        let screen = Arc::clone(&parser);
+        let reader = std::thread::spawn(move || {
+            let mut buf = [0u8; PTY_READ_BUFFER_BYTES];
+            loop {
+                match output.read(&mut buf) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => screen
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .process(&buf[..n]),
+                }
+            }
+        });
+
+        Ok((
+            Self {
+                _master: pair.master,
+                writer: Some(TerminalInput(Arc::new(Mutex::new(writer)))),
+                parser,
+                reader: Some(reader),
+            },
+            child,
+        ))
+    }
+
+    /// A handle on the program's input, to write to once the session is out
+    /// of reach.
+    ///
+    /// # Errors
+    ///
+    /// Reports [`std::io::ErrorKind::BrokenPipe`] once the program has exited.
+    pub fn input(&self) -> std::io::Result<TerminalInput> {
+        self.writer.clone().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::BrokenPipe, "the program has exited")
+        })
+    }
+
+    /// Close the input side, once the program has exited.
+    pub(super) fn close_input(&mut self) {
+        self.writer = None;
+    }
+
+    /// Hand over the reader thread, so the caller can wait for the last of
+    /// the output to reach the screen.
+    pub(super) fn take_reader(&mut self) -> Option<JoinHandle<()>> {
+        self.reader.take()
+    }
+
+    /// The screen's rows as text, with trailing blanks and blank rows at the
+    /// bottom removed.
+    pub fn screen_lines(&self) -> Vec<String> {
+        let parser = self.parser.lock().unwrap_or_else(|e| e.into_inner());
+        let mut lines: Vec<String> = parser
+            .screen()
+            .rows(0, PTY_COLS)
+            .map(|row| row.trim_end().to_string())
+            .collect();
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+        lines
+    }
+
+    /// The cursor's `(row, column)`, counting from zero.
+    pub fn cursor(&self) -> (u16, u16) {
+        let parser = self.parser.lock().unwrap_or_else(|e| e.into_inner());
+        parser.screen().cursor_position()
+    }
+}
+
+/// The bytes a terminal sends for the key `name`: one of [`key_names`], or
+/// `ctrl-<letter>`. Names ignore case; `None` for a name no key carries.
+pub fn key_sequence(name: &str) -> Option<Vec<u8>> {
+    let key = name.trim().to_ascii_lowercase();
+    if let Some((_, bytes)) = NAMED_KEYS.iter().find(|(named, _)| *named == key) {
+        return Some(bytes.to_vec());
+    }
+    let letter = key
+        .strip_prefix("ctrl-")
+        .or_else(|| key.strip_prefix("ctrl+"))?;
+    match letter.as_bytes() {
+        [c] if c.is_ascii_lowercase() => Some(vec![c & 0x1f]),
+        _ => None,
+    }
+}
+
+/// Every key name [`key_sequence`] accepts, `ctrl-<letter>` aside.
+pub fn key_names() -> impl Iterator<Item = &'static str> {
+    NAMED_KEYS.iter().map(|(name, _)| *name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_sequence_maps_named_and_control_keys() {
+        assert_eq!(key_sequence("ctrl-c"), Some(vec![0x03]));
+        assert_eq!(key_sequence("Ctrl+D"), Some(vec![0x04]));
+        assert_eq!(key_sequence("up"), Some(b"\x1b[A".to_vec()));
+        assert_eq!(key_sequence("ENTER"), Some(b"\r".to_vec()));
+        assert_eq!(key_sequence("ctrl-1"), None);
+        assert_eq!(key_sequence("hyper"), None);
+        assert!(key_names().any(|name| name == "pagedown"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_session_renders_what_the_program_draws() {
+        let (mut session, mut child) = PtySession::spawn(
+            "printf 'one\\r\\ntwo'; read line; printf \"\\r\\ngot %s\" \"$line\"",
+            Path::new("/tmp"),
+            None,
+        )
+        .expect("spawn on a pty");
+
+        session
+            .input()
+            .expect("input")
+            .write(b"three\r")
+            .expect("write");
+        child.wait().expect("wait");
+        session.take_reader().expect("reader").join().expect("join");
+
+        let lines = session.screen_lines();
+        assert_eq!(lines.first().map(String::as_str), Some("one"));
+        assert!(
+            lines.iter().any(|line| line == "got three"),
+            "screen: {lines:?}"
+        );
+    }
+}
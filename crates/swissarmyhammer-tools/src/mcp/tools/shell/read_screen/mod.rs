@@ -0,0 +1,129 @@
+//! Read screen operation for the shell tool.
+//!
+//! This module implements the "read screen" operation which renders the
+//! terminal of a command started with `pty: true` as text: every row the
+//! program drew, and where the cursor stands.
+
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use swissarmyhammer_operations::{Operation, ParamMeta, ParamType};
+
+use super::execute_command::{COMMAND_ID_KEY, STATUS_KEY};
+use super::infrastructure::value_as_u64_tolerant;
+use super::state::ShellState;
+use crate::mcp::tool_registry::BaseToolImpl;
+
+/// Operation metadata for reading a terminal command's screen
+#[derive(Debug, Default)]
+pub struct ReadScreen;
+
+static READ_SCREEN_PARAMS: &[ParamMeta] = &[ParamMeta::new("command_id")
+    .description("Which terminal command's screen to read")
+    .param_type(ParamType::Integer)
+    .required()];
+
+impl Operation for ReadScreen {
+    fn verb(&self) -> &'static str {
+        "read"
+    }
+    fn noun(&self) -> &'static str {
+        "screen"
+    }
+    fn description(&self) -> &'static str {
+        "Render the terminal of a command started with pty: true as text, with the cursor position"
+    }
+    fn parameters(&self) -> &'static [ParamMeta] {
+        READ_SCREEN_PARAMS
+    }
+}
+
+/// Read the required `command_id` argument of a terminal operation.
+pub(super) fn terminal_command_id(
+    args: &serde_json::Map<String, serde_json::Value>,
+    op: &str,
+) -> Result<usize, McpError> {
+    args.get("command_id")
+        .and_then(value_as_u64_tolerant)
+        .map(|id| id as usize)
+        .ok_or_else(|| {
+            McpError::invalid_params(format!("'command_id' parameter is required for {op}"), None)
+        })
+}
+
+/// Execute the "read screen" operation.
+///
+/// # Parameters
+///
+/// - `args`: the MCP argument map (without the "op" key)
+/// - `state`: shared shell state holding the command's terminal
+///
+/// # Returns
+///
+/// A `CallToolResult` with the screen, or an `McpError` when the command has
+/// no terminal.
+pub async fn execute_read_screen(
+    args: &serde_json::Map<String, serde_json::Value>,
+    state: Arc<Mutex<ShellState>>,
+) -> Result<CallToolResult, McpError> {
+    let command_id = terminal_command_id(args, "read screen")?;
+    screen_response(&state, command_id).await
+}
+
+/// Answer with the command's status, the cursor position, and the screen's
+/// rows under a `screen:` header. `send input` and `send keys` answer the
+/// same way, so one round trip types and shows the result.
+pub(super) async fn screen_response(
+    state: &Arc<Mutex<ShellState>>,
+    command_id: usize,
+) -> Result<CallToolResult, McpError> {
+    let mut guard = state.lock().await;
+    let status = guard
+        .get_command(command_id)
+        .map(|record| record.status.to_string())
+        .ok_or_else(|| {
+            McpError::invalid_params(format!("unknown command ID {command_id}"), None)
+        })?;
+    let session = guard
+        .terminal_mut(command_id)
+        .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+    let (row, col) = session.cursor();
+
+    let mut response = format!(
+        "{COMMAND_ID_KEY}: {}\n{STATUS_KEY}: {}\ncursor: {},{}\nscreen:",
+        command_id, status, row, col
+    );
+    for line in session.screen_lines() {
+        response.push('\n');
+        response.push_str(&line);
+    }
+    Ok(BaseToolImpl::create_success_response(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::super::test_helpers::{execute_op, execute_op_with, run_command_with, shared_tool};
+
+    #[tokio::test]
+    async fn test_read_screen_missing_command_id_returns_error() {
+        let err = execute_op("read screen", vec![])
+            .await
+            .expect_err("read screen without command_id should fail");
+        assert!(err.to_string().contains("command_id"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_read_screen_rejects_a_command_without_a_terminal() {
+        let tool = shared_tool();
+        let cmd_id = run_command_with(&tool, "echo piped").await;
+
+        let err = execute_op_with(&tool, "read screen", vec![("command_id", json!(cmd_id))])
+            .await
+            .expect_err("a piped command has no screen");
+        assert!(err.to_string().contains("pty: true"), "{err}");
+    }
+}
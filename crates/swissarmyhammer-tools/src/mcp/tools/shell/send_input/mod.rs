@@ -0,0 +1,240 @@
+//! Send input operation for the shell tool.
+//!
+//! This module implements the "send input" operation which types text into
+//! the terminal of a command started with `pty: true` — an answer to a
+//! prompt, a line for a REPL — and answers with the screen that follows.
+
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use swissarmyhammer_operations::{Operation, ParamMeta, ParamType};
+
+use super::pty::{INPUT_SETTLE, INPUT_WRITE_TIMEOUT};
+use super::read_screen::{screen_response, terminal_command_id};
+use super::state::ShellState;
+
+/// Operation metadata for typing text into a terminal command
+#[derive(Debug, Default)]
+pub struct SendInput;
+
+static SEND_INPUT_PARAMS: &[ParamMeta] = &[
+    ParamMeta::new("command_id")
+        .description("Which terminal command to type into")
+        .param_type(ParamType::Integer)
+        .required(),
+    ParamMeta::new("text")
+        .description("The text to type")
+        .param_type(ParamType::String)
+        .required(),
+    ParamMeta::new("enter")
+        .description("Press Enter after the text (default: true)")
+        .param_type(ParamType::Boolean),
+];
+
+impl Operation for SendInput {
+    fn verb(&self) -> &'static str {
+        "send"
+    }
+    fn noun(&self) -> &'static str {
+        "input"
+    }
+    fn description(&self) -> &'static str {
+        "Type text into the terminal of a command started with pty: true, then show the screen"
+    }
+    fn parameters(&self) -> &'static [ParamMeta] {
+        SEND_INPUT_PARAMS
+    }
+}
+
+/// Execute the "send input" operation.
+///
+/// Extracts `command_id` and `text` (both required) and an optional `enter`,
+/// types the text, and answers with the screen once the program has had a
+/// moment to react.
+///
+/// # Parameters
+///
+/// - `args`: the MCP argument map (without the "op" key)
+/// - `state`: shared shell state holding the command's terminal
+///
+/// # Returns
+///
+/// A `CallToolResult` with the screen, or an `McpError` when the command has
+/// no terminal or has exited.
+pub async fn execute_send_input(
+    args: &serde_json::Map<String, serde_json::Value>,
+    state: Arc<Mutex<ShellState>>,
+) -> Result<CallToolResult, McpError> {
+    let command_id = terminal_command_id(args, "send input")?;
+    let text = args.get("text").and_then(|v| v.as_str()).ok_or_else(|| {
+        McpError::invalid_params("'text' parameter is required for send input", None)
+    })?;
+    let enter = args.get("enter").and_then(|v| v.as_bool()).unwrap_or(true);
+
+    let mut bytes = text.as_bytes().to_vec();
+    if enter {
+        bytes.push(b'\r');
+    }
+    send_to_terminal(&state, command_id, &bytes).await
+}
+
+/// Write `bytes` to the command's terminal, wait [`INPUT_SETTLE`] for the
+/// program to react, and answer with the screen.
+///
+/// The write blocks while the terminal's buffer is full, so it runs on a
+/// blocking thread with the shell state unlocked, and gives up after
+/// [`INPUT_WRITE_TIMEOUT`] on a program that does not read its input.
+pub(super) async fn send_to_terminal(
+    state: &Arc<Mutex<ShellState>>,
+    command_id: usize,
+    bytes: &[u8],
+) -> Result<CallToolResult, McpError> {
+    let no_input = |e: std::io::Error| {
+        McpError::invalid_params(
+            format!("command ID {command_id} takes no more input: {e}"),
+            None,
+        )
+    };
+    let input = {
+        let mut guard = state.lock().await;
+        guard
+            .terminal_mut(command_id)
+            .map_err(|e| McpError::invalid_params(e.to_string(), None))?
+            .input()
+            .map_err(no_input)?
+    };
+
+    let bytes = bytes.to_vec();
+    let write = tokio::task::spawn_blocking(move || input.write(&bytes));
+    match tokio::time::timeout(INPUT_WRITE_TIMEOUT, write).await {
+        Ok(Ok(written)) => written.map_err(no_input)?,
+        Ok(Err(e)) => {
+            return Err(McpError::internal_error(
+                format!("writing to command ID {command_id} failed: {e}"),
+                None,
+            ))
+        }
+        Err(_) => {
+            return Err(McpError::invalid_params(
+                format!(
+                    "command ID {command_id} is not reading its input; \
+                     its terminal took nothing for {}s",
+                    INPUT_WRITE_TIMEOUT.as_secs()
+                ),
+                None,
+            ))
+        }
+    }
+    tokio::time::sleep(INPUT_SETTLE).await;
+    screen_response(state, command_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::super::test_helpers::{
+        execute_op, execute_op_with, extract_text, parse_status_response, shared_tool,
+    };
+
+    #[tokio::test]
+    async fn test_send_input_missing_text_returns_error() {
+        let err = execute_op("send input", vec![("command_id", json!(1))])
+            .await
+            .expect_err("send input without text should fail");
+        assert!(err.to_string().contains("text"), "{err}");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_send_input_answers_a_prompt() {
+        let tool = shared_tool();
+        let started = execute_op_with(
+            &tool,
+            "start command",
+            vec![
+                (
+                    "command",
+                    json!("printf 'name? '; read name; echo \"hello $name\"; sleep 5"),
+                ),
+                ("working_directory", json!("/tmp")),
+                ("pty", json!(true)),
+            ],
+        )
+        .await
+        .expect("start command with pty");
+        let cmd_id = parse_status_response(&started)["command_id"].clone();
+
+        let result = execute_op_with(
+            &tool,
+            "send input",
+            vec![("command_id", json!(cmd_id)), ("text", json!("world"))],
+        )
+        .await
+        .expect("send input");
+        let mut screen = extract_text(&result);
+        for _ in 0..20 {
+            if screen.contains("hello world") {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            let result = execute_op_with(&tool, "read screen", vec![("command_id", json!(cmd_id))])
+                .await
+                .expect("read screen");
+            screen = extract_text(&result);
+        }
+        assert!(screen.contains("hello world"), "screen: {screen}");
+
+        let _ = execute_op_with(&tool, "kill process", vec![("id", json!(cmd_id))]).await;
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_send_input_to_a_program_not_reading_leaves_other_ops_free() {
+        let tool = shared_tool();
+        let started = execute_op_with(
+            &tool,
+            "start command",
+            vec![
+                ("command", json!("sleep 30")),
+                ("working_directory", json!("/tmp")),
+                ("pty", json!(true)),
+            ],
+        )
+        .await
+        .expect("start command with pty");
+        let cmd_id = parse_status_response(&started)["command_id"].clone();
+
+        let flood = {
+            let tool = tool.clone();
+            let cmd_id = cmd_id.clone();
+            tokio::spawn(async move {
+                execute_op_with(
+                    &tool,
+                    "send input",
+                    vec![
+                        ("command_id", json!(cmd_id)),
+                        ("text", json!("x".repeat(1024 * 1024))),
+                    ],
+                )
+                .await
+            })
+        };
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+
+        let listed = tokio::time::timeout(
+            std::time::Duration::from_secs(2),
+            execute_op_with(&tool, "list processes", vec![]),
+        )
+        .await;
+        assert!(
+            listed.is_ok(),
+            "a blocked terminal write must not hold the shell state"
+        );
+
+        let _ = execute_op_with(&tool, "kill process", vec![("id", json!(cmd_id))]).await;
+        let _ = flood.await;
+    }
+}
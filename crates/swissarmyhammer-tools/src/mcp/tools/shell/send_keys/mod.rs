@@ -0,0 +1,165 @@
+//! Send keys operation for the shell tool.
+//!
+//! This module implements the "send keys" operation which presses named keys
+//! — Ctrl-C, arrows, Enter, Escape — in the terminal of a command started
+//! with `pty: true`, and answers with the screen that follows.
+
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use swissarmyhammer_operations::{Operation, ParamMeta, ParamType};
+
+use super::pty::{key_names, key_sequence};
+use super::read_screen::terminal_command_id;
+use super::send_input::send_to_terminal;
+use super::state::ShellState;
+
+/// Operation metadata for pressing keys in a terminal command
+#[derive(Debug, Default)]
+pub struct SendKeys;
+
+static SEND_KEYS_PARAMS: &[ParamMeta] = &[
+    ParamMeta::new("command_id")
+        .description("Which terminal command to press keys in")
+        .param_type(ParamType::Integer)
+        .required(),
+    ParamMeta::new("keys")
+        .description("Keys to press in order, e.g. [\"ctrl-c\"] or [\"down\", \"down\", \"enter\"]; also accepted as a comma-separated string")
+        .param_type(ParamType::Array)
+        .required(),
+];
+
+impl Operation for SendKeys {
+    fn verb(&self) -> &'static str {
+        "send"
+    }
+    fn noun(&self) -> &'static str {
+        "keys"
+    }
+    fn description(&self) -> &'static str {
+        "Press keys (ctrl-<letter>, enter, tab, escape, arrows, ...) in the terminal of a command started with pty: true, then show the screen"
+    }
+    fn parameters(&self) -> &'static [ParamMeta] {
+        SEND_KEYS_PARAMS
+    }
+}
+
+/// Execute the "send keys" operation.
+///
+/// Extracts `command_id` and `keys` (both required), translates every key
+/// name into the bytes a terminal sends for it, and presses them in order. A
+/// name no key carries rejects the whole call before anything is sent.
+///
+/// # Parameters
+///
+/// - `args`: the MCP argument map (without the "op" key)
+/// - `state`: shared shell state holding the command's terminal
+///
+/// # Returns
+///
+/// A `CallToolResult` with the screen, or an `McpError` for an unknown key,
+/// a command without a terminal, or one that has exited.
+pub async fn execute_send_keys(
+    args: &serde_json::Map<String, serde_json::Value>,
+    state: Arc<Mutex<ShellState>>,
+) -> Result<CallToolResult, McpError> {
+    let command_id = terminal_command_id(args, "send keys")?;
+    let names: Vec<String> = match args.get("keys") {
+        Some(serde_json::Value::Array(keys)) => keys
+            .iter()
+            .filter_map(|key| key.as_str().map(str::to_string))
+            .collect(),
+        Some(serde_json::Value::String(keys)) => keys
+            .split(',')
+            .map(|key| key.trim().to_string())
+            .filter(|key| !key.is_empty())
+            .collect(),
+        _ => Vec::new(),
+    };
+    if names.is_empty() {
+        return Err(McpError::invalid_params(
+            "'keys' parameter is required for send keys",
+            None,
+        ));
+    }
+
+    let mut bytes = Vec::new();
+    for name in &names {
+        let sequence = key_sequence(name).ok_or_else(|| {
+            McpError::invalid_params(
+                format!(
+                    "unknown key '{}'. Valid keys: ctrl-<letter>, {}",
+                    name,
+                    key_names().collect::<Vec<_>>().join(", ")
+                ),
+                None,
+            )
+        })?;
+        bytes.extend(sequence);
+    }
+    send_to_terminal(&state, command_id, &bytes).await
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::super::test_helpers::{
+        execute_op, execute_op_with, parse_status_response, shared_tool,
+    };
+
+    #[tokio::test]
+    async fn test_send_keys_rejects_unknown_keys() {
+        let err = execute_op(
+            "send keys",
+            vec![
+                ("command_id", json!(1)),
+                ("keys", json!(["ctrl-c", "hyper"])),
+            ],
+        )
+        .await
+        .expect_err("an unknown key should fail");
+        let message = err.to_string();
+        assert!(message.contains("hyper"), "{message}");
+        assert!(message.contains("ctrl-<letter>"), "{message}");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_send_keys_ctrl_c_interrupts_the_program() {
+        let tool = shared_tool();
+        let started = execute_op_with(
+            &tool,
+            "start command",
+            vec![
+                ("command", json!("sleep 30")),
+                ("working_directory", json!("/tmp")),
+                ("pty", json!(true)),
+            ],
+        )
+        .await
+        .expect("start command with pty");
+        let cmd_id = parse_status_response(&started)["command_id"].clone();
+
+        execute_op_with(
+            &tool,
+            "send keys",
+            vec![("command_id", json!(cmd_id)), ("keys", json!("ctrl-c"))],
+        )
+        .await
+        .expect("send keys");
+
+        let waited = execute_op_with(
+            &tool,
+            "wait command",
+            vec![("command_id", json!(cmd_id)), ("timeout", json!(10))],
+        )
+        .await
+        .expect("wait command");
+        let fields = parse_status_response(&waited);
+        assert_eq!(fields.get("status").map(String::as_str), Some("completed"));
+        assert_ne!(fields.get("exit_code").map(String::as_str), Some("0"));
+    }
+}
@@ -0,0 +1,332 @@
+//! Persistent shell sessions for the virtual shell
+//!
+//! Every `execute command` normally starts a fresh shell, so `cd`, `export`
+//! and `source venv/bin/activate` are gone by the next call. A command that
+//! names a `session` runs instead in one long-lived bash process kept per
+//! session name, so the working directory, the environment, and any functions
+//! the shell defined carry over to the next command in that session.
+//!
+//! Each command is written to the shell's stdin through `eval`, followed by a
+//! sentinel line carrying a marker unique to the command, the exit status, and
+//! the working directory. Output is read up to that line. A shell that exits —
+//! `exit`, a crash, `kill process` — ends its output before any sentinel; the
+//! caller then closes the session, and the next command starts a fresh one.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use swissarmyhammer_common::command::Shell;
+use swissarmyhammer_shell::Sandbox;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{ChildStdin, ChildStdout, Command};
+
+use super::infrastructure::ShellError;
+use super::process::AsyncProcessGuard;
+
+/// Start of the line that ends each command's output. A fresh ULID follows it
+/// for every command, so no output can end a command early by accident.
+const SENTINEL_PREFIX: &str = "__SAH_SESSION_END_";
+
+/// The exit code reported when the sentinel carries none the session can
+/// parse.
+const UNKNOWN_EXIT_CODE: i32 = -1;
+
+/// One long-lived shell that runs a session's commands one after another.
+///
+/// The shell leads its own process group, so killing the session also ends
+/// whatever command it is running. The guard kills the group when the session
+/// is dropped.
+#[derive(Debug)]
+pub struct PersistentShell {
+    guard: AsyncProcessGuard,
+    stdin: ChildStdin,
+    output: BufReader<ChildStdout>,
+    sandbox: Option<Sandbox>,
+    working_directory: PathBuf,
+}
+
+/// What one command run in a session produced.
+#[derive(Debug)]
+pub struct SessionOutput {
+    /// Output lines, stdout and stderr interleaved in the order they arrived.
+    pub lines: Vec<String>,
+    /// Whether lines past the byte budget were dropped.
+    pub truncated: bool,
+    /// Bytes of output the command wrote, including dropped lines.
+    pub total_bytes: usize,
+    /// The command's exit status.
+    pub exit_code: i32,
+    /// The shell's working directory once the command finished.
+    pub working_directory: PathBuf,
+}
+
+impl PersistentShell {
+    /// Start the shell for session `name` in `work_dir`, with `environment`
+    /// added to the inherited one.
+    ///
+    /// The shell reads no startup files, so a session behaves the same on
+    /// every machine until its own commands change it. With a `sandbox` the
+    /// shell — and so every command the session runs — stays confined to it.
+    pub(super) fn spawn(
+        name: &str,
+        work_dir: &Path,
+        environment: Option<&HashMap<String, String>>,
+        sandbox: Option<&Sandbox>,
+    ) -> Result<Self, ShellError> {
+        let label = format!("shell session '{name}'");
+        let (program, _) = Shell::Bash.program_and_flag();
+        let mut std_cmd = std::process::Command::new(program);
+        if let Some(sandbox) = sandbox {
+            sandbox
+                .confine(&mut std_cmd)
+                .map_err(|source| ShellError::SandboxError { source })?;
+        }
+        let mut cmd = Command::from(std_cmd);
+        cmd.args(["--noprofile", "--norc"])
+            .current_dir(work_dir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null());
+        #[cfg(unix)]
+        cmd.process_group(0);
+        if let Some(env_vars) = environment {
+            cmd.envs(env_vars);
+        }
+
+        let mut child = cmd
+            .spawn()
+            .map_err(|source| ShellError::CommandSpawnError {
+                command: label.clone(),
+                source,
+            })?;
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            return Err(ShellError::SystemError {
+                message: format!("{label} has no stdin or stdout pipe"),
+            });
+        };
+        Ok(Self {
+            guard: AsyncProcessGuard::new(child, label),
+            stdin,
+            output: BufReader::new(stdout),
+            sandbox: sandbox.cloned(),
+            working_directory: work_dir.to_path_buf(),
+        })
+    }
+
+    /// The shell's working directory once its last command finished, which
+    /// the next command starts in.
+    pub fn working_directory(&self) -> &Path {
+        &self.working_directory
+    }
+
+    /// The sandbox the shell runs in, if any.
+    pub fn sandbox(&self) -> Option<&Sandbox> {
+        self.sandbox.as_ref()
+    }
+
+    /// The PID of the shell, which also names its process group.
+    pub fn pid(&mut self) -> Option<u32> {
+        self.guard.child_mut().and_then(|child| child.id())
+    }
+
+    /// Run `command` in the shell and collect its output.
+    ///
+    /// Output past `max_bytes` is read and dropped, so the command never
+    /// blocks on a full pipe. The command reads no stdin; the shell's stdin
+    /// carries the session's commands.
+    ///
+    /// # Errors
+    ///
+    /// Reports [`ShellError::ExecutionError`] when the shell has exited or
+    /// exits before the command finishes. The session is unusable afterwards.
+    pub(super) async fn run(
+        &mut self,
+        command: &str,
+        max_bytes: usize,
+    ) -> Result<SessionOutput, ShellError> {
+        let ended = |message: String| ShellError::ExecutionError {
+            command: command.to_string(),
+            message,
+        };
+        let marker = format!("{SENTINEL_PREFIX}{}__", ulid::Ulid::new());
+        let script = format!(
+            "eval {} </dev/null 2>&1\nprintf '%s %d %s\\n' '{}' \"$?\" \"$PWD\"\n",
+            single_quoted(command),
+            marker
+        );
+        self.stdin
+            .write_all(script.as_bytes())
+            .await
+            .map_err(|e| ended(format!("the session shell has exited: {e}")))?;
+        self.stdin
+            .flush()
+            .await
+            .map_err(|e| ended(format!("the session shell has exited: {e}")))?;
+
+        let mut lines = Vec::new();
+        let mut total_bytes = 0;
+        let mut truncated = false;
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            let read = self
+                .output
+                .read_until(b'\n', &mut buf)
+                .await
+                .map_err(|e| ended(format!("reading the session shell failed: {e}")))?;
+            if read == 0 {
+                return Err(ended(
+                    "the session shell exited before the command finished".to_string(),
+                ));
+            }
+            let text = String::from_utf8_lossy(&buf);
+            let line = text.strip_suffix('\n').unwrap_or(&text);
+
+            // Output that ends without a newline shares its last line with
+            // the sentinel.
+            let (line, sentinel) = match line.find(&marker) {
+                Some(at) => (&line[..at], Some(&line[at + marker.len()..])),
+                None => (line, None),
+            };
+            if sentinel.is_none() || !line.is_empty() {
+                total_bytes += line.len() + 1;
+                if total_bytes <= max_bytes {
+                    lines.push(line.to_string());
+                } else if !truncated {
+                    truncated = true;
+                    lines.push(format!("[output truncated at {max_bytes} bytes]"));
+                }
+            }
+            if let Some(sentinel) = sentinel {
+                let (exit_code, working_directory) = parse_sentinel(sentinel);
+                self.working_directory = working_directory.clone();
+                return Ok(SessionOutput {
+                    lines,
+                    truncated,
+                    total_bytes,
+                    exit_code,
+                    working_directory,
+                });
+            }
+        }
+    }
+
+    /// End the shell and everything it started.
+    pub(super) async fn close(&mut self) {
+        if let Err(e) = self.guard.force_kill().await {
+            tracing::warn!("Failed to end a shell session: {}", e);
+        }
+    }
+}
+
+/// Split what follows the marker on a sentinel line — ` <status> <pwd>` —
+/// into the exit code and the working directory.
+fn parse_sentinel(rest: &str) -> (i32, PathBuf) {
+    let (code, dir) = rest
+        .trim_start()
+        .split_once(' ')
+        .unwrap_or((rest.trim(), ""));
+    (
+        code.parse().unwrap_or(UNKNOWN_EXIT_CODE),
+        PathBuf::from(dir),
+    )
+}
+
+/// Quote `text` as one shell word that the shell takes literally.
+fn single_quoted(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Byte budget large enough that no test output is dropped.
+    const TEST_MAX_BYTES: usize = 1024 * 1024;
+
+    #[test]
+    fn test_single_quoted_escapes_quotes() {
+        assert_eq!(single_quoted("echo 'hi'"), r"'echo '\''hi'\'''");
+    }
+
+    #[test]
+    fn test_parse_sentinel_reads_code_and_directory() {
+        assert_eq!(
+            parse_sentinel(" 3 /tmp/with space"),
+            (3, PathBuf::from("/tmp/with space"))
+        );
+        assert_eq!(parse_sentinel(" garbled").0, UNKNOWN_EXIT_CODE);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_session_keeps_directory_environment_and_functions() {
+        let mut shell = PersistentShell::spawn("test", Path::new("/tmp"), None, None).unwrap();
+
+        let setup = shell
+            .run(
+                "cd / && export SESSION_VAR=kept && greet() { echo \"hi $1\"; }",
+                TEST_MAX_BYTES,
+            )
+            .await
+            .unwrap();
+        assert_eq!(setup.exit_code, 0);
+        assert_eq!(setup.working_directory, PathBuf::from("/"));
+        assert_eq!(shell.working_directory(), Path::new("/"));
+
+        let output = shell
+            .run("pwd; echo $SESSION_VAR; greet there", TEST_MAX_BYTES)
+            .await
+            .unwrap();
+        assert_eq!(output.lines, vec!["/", "kept", "hi there"]);
+        shell.close().await;
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_session_reports_exit_code_stderr_and_unterminated_output() {
+        let mut shell = PersistentShell::spawn("test", Path::new("/tmp"), None, None).unwrap();
+
+        let output = shell
+            .run("echo oops >&2; printf partial; false", TEST_MAX_BYTES)
+            .await
+            .unwrap();
+        assert_eq!(output.lines, vec!["oops", "partial"]);
+        assert_eq!(output.exit_code, 1);
+
+        let syntax = shell.run("if then", TEST_MAX_BYTES).await.unwrap();
+        assert_ne!(
+            syntax.exit_code, 0,
+            "a syntax error must not end the session"
+        );
+        let after = shell.run("echo still here", TEST_MAX_BYTES).await.unwrap();
+        assert_eq!(after.lines, vec!["still here"]);
+        shell.close().await;
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_session_exit_ends_the_shell() {
+        let mut shell = PersistentShell::spawn("test", Path::new("/tmp"), None, None).unwrap();
+
+        let err = shell.run("exit 3", TEST_MAX_BYTES).await.unwrap_err();
+        assert!(err.to_string().contains("session shell"), "{err}");
+        assert!(shell.run("echo gone", TEST_MAX_BYTES).await.is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_session_truncates_output_past_the_budget() {
+        let mut shell = PersistentShell::spawn("test", Path::new("/tmp"), None, None).unwrap();
+
+        let output = shell.run("seq 1 100", 20).await.unwrap();
+        assert!(output.truncated);
+        assert_eq!(
+            output.lines.last().map(String::as_str),
+            Some("[output truncated at 20 bytes]")
+        );
+        assert_eq!(output.exit_code, 0);
+        shell.close().await;
+    }
+}
@@ -0,0 +1,411 @@
+//! Start command operation for the shell tool.
+//!
+//! This module implements the "start command" operation which spawns a shell
+//! command in the background and answers with its id at once. The command's
+//! output streams into the shell history while it runs, so `get lines` and
+//! `grep history` read it before it exits, and `wait command` waits for it.
+//! With `pty: true` the command runs on a pseudo-terminal instead, for
+//! programs that need one; see [`super::pty`].
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use portable_pty::{Child as PtyChild, ChildKiller};
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use swissarmyhammer_common::Pretty;
+use swissarmyhammer_operations::{Operation, ParamMeta, ParamType};
+use tokio::sync::Mutex;
+
+use super::execute_command::{
+    load_command_sandbox, parse_environment_variables, prepare_command, validate_shell_request,
+    COMMAND_ID_KEY, STATUS_KEY,
+};
+use super::infrastructure::{OutputLimits, ShellExecuteRequest};
+use super::process::{
+    prepare_working_directory, stream_output_to_state, AsyncProcessGuard, REMAINING_OUTPUT_TIMEOUT,
+};
+use super::pty::PtySession;
+use super::state::{CommandStatus, ShellState};
+use crate::mcp::tool_registry::{BaseToolImpl, ToolContext};
+
+/// Operation metadata for starting a shell command in the background
+#[derive(Debug, Default)]
+pub struct StartCommand;
+
+/// Parameter metadata the `start command` operation accepts, in the order
+/// the generated schema and the CLI help list them.
+static START_COMMAND_PARAMS: &[ParamMeta] = &[
+    ParamMeta::new("command")
+        .description("The shell command to start")
+        .param_type(ParamType::String)
+        .required(),
+    ParamMeta::new("timeout")
+        .description("Seconds before killing the command (optional, default: none)")
+        .param_type(ParamType::Integer),
+    ParamMeta::new("working_directory")
+        .description("Working directory for command execution (optional, defaults to current directory)")
+        .param_type(ParamType::String),
+    ParamMeta::new("environment")
+        .description("Additional environment variables as JSON string (optional, e.g., '{\"KEY1\":\"value1\",\"KEY2\":\"value2\"}')")
+        .param_type(ParamType::String),
+    ParamMeta::new("pty")
+        .description("Run the command on a pseudo-terminal, for programs that need one; drive it with send input, send keys and read screen (optional, default: false)")
+        .param_type(ParamType::Boolean),
+];
+
+impl Operation for StartCommand {
+    /// Returns the verb part of the operation: `"start"`.
+    fn verb(&self) -> &'static str {
+        "start"
+    }
+    /// Returns the noun part of the operation: `"command"`.
+    fn noun(&self) -> &'static str {
+        "command"
+    }
+    /// Returns a one-line description of the start command operation.
+    fn description(&self) -> &'static str {
+        "Start a shell command in the background and return its command id at once; output streams into the history while it runs"
+    }
+    /// Returns the parameter metadata the `start command` operation accepts:
+    /// the same `command`, `timeout`, `working_directory`, and `environment`
+    /// as `execute command`, plus `pty`.
+    fn parameters(&self) -> &'static [ParamMeta] {
+        START_COMMAND_PARAMS
+    }
+}
+
+/// Execute the "start command" operation.
+///
+/// Validates and spawns the command exactly as `execute command` does, then
+/// hands the process to a background task and answers with the command id
+/// and the `running` status. The task streams output into shell state line by
+/// line and records the exit code, the timeout, or a failure when the command
+/// ends.
+///
+/// With `pty: true` the command runs on a pseudo-terminal instead; see
+/// [`start_on_terminal`].
+///
+/// # Parameters
+///
+/// - `args`: the MCP argument map (without the "op" key)
+/// - `state`: shared shell state for command history and process tracking
+/// - `context`: tool context that names the session working directory
+///
+/// # Returns
+///
+/// A `CallToolResult` with the command id, or an `McpError` when the request
+/// is invalid or the command cannot be spawned.
+pub async fn execute_start_command(
+    args: serde_json::Map<String, serde_json::Value>,
+    state: Arc<Mutex<ShellState>>,
+    context: &ToolContext,
+) -> Result<CallToolResult, McpError> {
+    let request: ShellExecuteRequest = BaseToolImpl::parse_arguments(args)?;
+    tracing::info!("Starting shell command: {}", Pretty(&request.command));
+    if request.session.is_some() {
+        return Err(McpError::invalid_params(
+            "session applies to execute command only; start command always runs a fresh process",
+            None,
+        ));
+    }
+    validate_shell_request(&request, &context.session_root())?;
+    let sandbox = load_command_sandbox(&context.session_root())?;
+
+    let cmd_id = if request.pty {
+        if sandbox.is_some() {
+            return Err(McpError::invalid_params(
+                "pty commands cannot run in the command sandbox; start the command without pty",
+                None,
+            ));
+        }
+        start_on_terminal(&request, &state, context.session_root()).await?
+    } else {
+        let output_limits = OutputLimits::with_defaults().map_err(|e| {
+            McpError::internal_error(format!("invalid output configuration: {e}"), None)
+        })?;
+        let (cmd_id, process_guard, _work_dir) =
+            prepare_command(&request, &state, context.session_root(), sandbox.as_ref()).await?;
+        tokio::spawn(run_in_background(
+            process_guard,
+            state,
+            cmd_id,
+            request.timeout,
+            output_limits,
+        ));
+        cmd_id
+    };
+
+    Ok(BaseToolImpl::create_success_response(format!(
+        "{COMMAND_ID_KEY}: {}\n{STATUS_KEY}: {}",
+        cmd_id,
+        CommandStatus::Running,
+    )))
+}
+
+/// Drive a started command to its end and record how it ended.
+///
+/// The task owns the process guard, so a timeout — or a runtime shutting down
+/// under the task — kills the process group.
+async fn run_in_background(
+    mut process_guard: AsyncProcessGuard,
+    state: Arc<Mutex<ShellState>>,
+    cmd_id: usize,
+    timeout: Option<u64>,
+    output_limits: OutputLimits,
+) {
+    let Some(child) = process_guard.child_mut() else {
+        return;
+    };
+    let timeout = timeout.map(Duration::from_secs);
+    let exit_code =
+        match stream_output_to_state(child, &state, cmd_id, &output_limits, timeout).await {
+            Ok(None) => {
+                drop(process_guard);
+                None
+            }
+            result => {
+                // The child has been reaped; nothing is left for the guard to kill.
+                process_guard.take_child();
+                Some(result.map_or_else(
+                    |e| {
+                        tracing::error!("Shell: background command {} failed - {}", cmd_id, e);
+                        -1
+                    },
+                    |code| code.unwrap_or(-1),
+                ))
+            }
+        };
+    record_end(&state, cmd_id, exit_code).await;
+}
+
+/// Start the command on a pseudo-terminal and return its command id.
+///
+/// The terminal is registered in shell state, where `send input`, `send keys`
+/// and `read screen` find it, and a background task waits for the program.
+/// Nothing streams into the history while it runs, since a terminal program
+/// redraws rather than appends; when it ends, its last screen is stored there.
+async fn start_on_terminal(
+    request: &ShellExecuteRequest,
+    state: &Arc<Mutex<ShellState>>,
+    default_dir: PathBuf,
+) -> Result<usize, McpError> {
+    let environment = parse_environment_variables(request.environment.as_deref())?;
+    let spawned = prepare_working_directory(
+        request.working_directory.clone().map(PathBuf::from),
+        default_dir,
+    )
+    .and_then(|dir| PtySession::spawn(&request.command, &dir, environment.as_ref()));
+    let (session, child) = spawned
+        .map_err(|e| McpError::internal_error(format!("failed to spawn command: {}", e), None))?;
+
+    let cmd_id = {
+        let mut guard = state.lock().await;
+        let cmd_id = guard.start_command(request.command.as_str());
+        if let Some(pid) = child.process_id() {
+            guard.register_process(cmd_id, pid);
+        }
+        guard.register_terminal(cmd_id, session);
+        cmd_id
+    };
+    tokio::spawn(run_on_terminal(
+        child,
+        state.clone(),
+        cmd_id,
+        request.timeout,
+    ));
+    Ok(cmd_id)
+}
+
+/// Wait for a program on a pseudo-terminal to end, store its last screen in
+/// the history, and record how it ended.
+async fn run_on_terminal(
+    mut child: Box<dyn PtyChild + Send + Sync>,
+    state: Arc<Mutex<ShellState>>,
+    cmd_id: usize,
+    timeout: Option<u64>,
+) {
+    let mut killer = child.clone_killer();
+    let waited = tokio::task::spawn_blocking(move || child.wait());
+    let outcome = match timeout {
+        Some(secs) => tokio::time::timeout(Duration::from_secs(secs), waited)
+            .await
+            .ok(),
+        None => Some(waited.await),
+    };
+    let exit_code = match outcome {
+        Some(Ok(Ok(status))) => Some(status.exit_code() as i32),
+        Some(Ok(Err(e))) => {
+            tracing::error!(
+                "Shell: waiting on terminal command {} failed - {}",
+                cmd_id,
+                e
+            );
+            Some(-1)
+        }
+        Some(Err(e)) => {
+            tracing::error!(
+                "Shell: waiting on terminal command {} failed - {}",
+                cmd_id,
+                e
+            );
+            Some(-1)
+        }
+        None => {
+            if let Err(e) = killer.kill() {
+                tracing::warn!("Failed to kill timed-out command {}: {}", cmd_id, e);
+            }
+            None
+        }
+    };
+
+    // Let the last of the output reach the screen before it is stored.
+    let reader = state
+        .lock()
+        .await
+        .terminal_mut(cmd_id)
+        .ok()
+        .and_then(PtySession::take_reader);
+    if let Some(reader) = reader {
+        let joined = tokio::task::spawn_blocking(move || reader.join());
+        let _ = tokio::time::timeout(REMAINING_OUTPUT_TIMEOUT, joined).await;
+    }
+
+    {
+        let mut guard = state.lock().await;
+        let screen = guard.terminal_mut(cmd_id).ok().map(|session| {
+            session.close_input();
+            session.screen_lines()
+        });
+        if let Some(lines) = screen {
+            if let Err(e) = guard.append_lines(cmd_id, &lines).await {
+                tracing::warn!("Failed to store the screen of command {}: {}", cmd_id, e);
+            }
+        }
+    }
+    record_end(&state, cmd_id, exit_code).await;
+}
+
+/// Record how a background command ended: its exit code, or a timeout when
+/// `exit_code` is `None`. A command `kill process` already ended keeps its
+/// `killed` status.
+async fn record_end(state: &Arc<Mutex<ShellState>>, cmd_id: usize, exit_code: Option<i32>) {
+    let mut guard = state.lock().await;
+    let still_running = guard
+        .get_command(cmd_id)
+        .is_some_and(|r| r.status == CommandStatus::Running);
+    if !still_running {
+        return;
+    }
+    match exit_code {
+        Some(code) => guard.complete_command(cmd_id, Some(code)).await,
+        None => guard.timeout_command(cmd_id).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::super::test_helpers::{
+        execute_op, execute_op_with, extract_text, parse_status_response, shared_tool,
+    };
+
+    #[tokio::test]
+    async fn test_start_command_answers_before_the_command_exits() {
+        let tool = shared_tool();
+        let started = std::time::Instant::now();
+        let result = execute_op_with(
+            &tool,
+            "start command",
+            vec![
+                ("command", json!("sleep 5")),
+                ("working_directory", json!("/tmp")),
+            ],
+        )
+        .await
+        .expect("start command should succeed");
+
+        assert!(started.elapsed() < std::time::Duration::from_secs(2));
+        let fields = parse_status_response(&result);
+        assert_eq!(fields.get("status").map(String::as_str), Some("running"));
+        let cmd_id = fields["command_id"].clone();
+
+        let killed = execute_op_with(&tool, "kill process", vec![("id", json!(cmd_id))]).await;
+        assert!(killed.is_ok(), "kill process: {:?}", killed.err());
+    }
+
+    #[tokio::test]
+    async fn test_start_command_streams_output_before_exit() {
+        let tool = shared_tool();
+        let result = execute_op_with(
+            &tool,
+            "start command",
+            vec![
+                ("command", json!("echo STREAMED_EARLY; sleep 5")),
+                ("working_directory", json!("/tmp")),
+            ],
+        )
+        .await
+        .unwrap();
+        let cmd_id = parse_status_response(&result)["command_id"].clone();
+
+        let mut text = String::new();
+        for _ in 0..50 {
+            let lines = execute_op_with(&tool, "get lines", vec![("command_id", json!(cmd_id))])
+                .await
+                .unwrap();
+            text = extract_text(&lines);
+            if text.contains("STREAMED_EARLY") {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+        assert!(
+            text.contains("STREAMED_EARLY"),
+            "output must be readable while the command runs: {text}"
+        );
+
+        let _ = execute_op_with(&tool, "kill process", vec![("id", json!(cmd_id))]).await;
+    }
+
+    #[tokio::test]
+    async fn test_start_command_rejects_session() {
+        let err = execute_op(
+            "start command",
+            vec![("command", json!("echo hi")), ("session", json!("dev"))],
+        )
+        .await
+        .expect_err("sessions are for execute command");
+        assert!(err.to_string().contains("execute command"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_start_command_rejects_blocked_commands() {
+        let tool = shared_tool();
+        let result = execute_op_with(
+            &tool,
+            "start command",
+            vec![("command", json!("sudo echo hello"))],
+        )
+        .await;
+        assert!(result.is_err(), "blocked commands must not start");
+    }
+
+    #[tokio::test]
+    async fn test_start_command_checks_paths_from_the_working_directory() {
+        let result = execute_op(
+            "start command",
+            vec![
+                ("command", json!("rm -rf sah-no-such-directory")),
+                ("working_directory", json!("/")),
+            ],
+        )
+        .await;
+        assert!(
+            result.is_err(),
+            "a delete outside the workspace must not start"
+        );
+    }
+}
@@ -7,6 +7,7 @@ use std::fmt;
 use std::fs::{self, OpenOptions};
 use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::Instant;
 
 use chrono::{DateTime, Local};
@@ -15,6 +16,10 @@ use grep::searcher::sinks::UTF8;
 use grep::searcher::{BinaryDetection, SearcherBuilder};
 
 use swissarmyhammer_directory::{DirectoryConfig, ShellConfig};
+use tokio::sync::Mutex;
+
+use super::pty::PtySession;
+use super::session::PersistentShell;
 
 /// Number of matches [`ShellState::grep`] returns when the caller names no
 /// limit. The reported total match count is never capped.
@@ -90,6 +95,13 @@ pub enum ShellStateError {
         /// The command id the caller named.
         cmd_id: usize,
     },
+    /// The command the caller named was not started on a pseudo-terminal, so
+    /// it has no screen and takes no input.
+    #[error("command ID {cmd_id} has no terminal; start it with `pty: true`")]
+    NoTerminal {
+        /// The command id the caller named.
+        cmd_id: usize,
+    },
     /// The caller's search pattern is not a valid regular expression.
     #[error("invalid regex pattern: {source}")]
     InvalidPattern {
@@ -111,8 +123,9 @@ pub enum CommandStatus {
     /// `kill process` sent SIGKILL to the command's process group. This state
     /// is transient while `execute command` still owns the child: that task
     /// reaps the signalled child and then writes [`CommandStatus::Completed`]
-    /// with exit code -1 over it. The status stays `Killed` only when nothing
-    /// is left to reap the child, as after the request is cancelled.
+    /// with exit code -1 over it. The status stays `Killed` when nothing is
+    /// left to reap the child, as after the request is cancelled, and for a
+    /// command `start command` launched, whose task leaves a kill in place.
     Killed,
     /// The command's timeout elapsed, so the process guard killed it.
     TimedOut,
@@ -183,6 +196,8 @@ pub struct ShellState {
     pub session_id: String,
     commands: Vec<CommandRecord>,
     processes: HashMap<usize, u32>, // cmd_id -> PID
+    terminals: HashMap<usize, PtySession>,
+    sessions: HashMap<String, Arc<Mutex<PersistentShell>>>,
     log_path: PathBuf,
 }
 
@@ -288,6 +303,8 @@ impl ShellState {
             session_id,
             commands: Vec::new(),
             processes: HashMap::new(),
+            terminals: HashMap::new(),
+            sessions: HashMap::new(),
             log_path,
         })
     }
@@ -315,6 +332,49 @@ impl ShellState {
         self.processes.insert(cmd_id, pid);
     }
 
+    /// Attach the pseudo-terminal a command runs on.
+    pub fn register_terminal(&mut self, cmd_id: usize, session: PtySession) {
+        self.terminals.insert(cmd_id, session);
+    }
+
+    /// The pseudo-terminal a command runs on.
+    ///
+    /// # Errors
+    ///
+    /// Reports [`ShellStateError::NoTerminal`] when the command was not
+    /// started on one.
+    pub fn terminal_mut(&mut self, cmd_id: usize) -> Result<&mut PtySession, ShellStateError> {
+        self.terminals
+            .get_mut(&cmd_id)
+            .ok_or(ShellStateError::NoTerminal { cmd_id })
+    }
+
+    /// The persistent shell of session `name`, or `None` when no command has
+    /// opened it yet or its shell has been closed.
+    ///
+    /// The shell sits behind its own lock, so a command waits for the one
+    /// already running in the same session without holding this state.
+    pub fn session(&self, name: &str) -> Option<Arc<Mutex<PersistentShell>>> {
+        self.sessions.get(name).cloned()
+    }
+
+    /// Keep `shell` as session `name` and return the handle commands run it
+    /// through.
+    pub fn open_session(
+        &mut self,
+        name: impl Into<String>,
+        shell: PersistentShell,
+    ) -> Arc<Mutex<PersistentShell>> {
+        let shell = Arc::new(Mutex::new(shell));
+        self.sessions.insert(name.into(), Arc::clone(&shell));
+        shell
+    }
+
+    /// Forget session `name`, so its next command starts a fresh shell.
+    pub fn close_session(&mut self, name: &str) {
+        self.sessions.remove(name);
+    }
+
     /// Append output lines from a command to the log.
     ///
     /// Note: This performs blocking file I/O (log file append). This is acceptable because
@@ -438,6 +498,11 @@ impl ShellState {
         &self.commands
     }
 
+    /// The record of one command, or `None` when no record carries `cmd_id`.
+    pub fn get_command(&self, cmd_id: usize) -> Option<&CommandRecord> {
+        self.commands.iter().find(|r| r.id == cmd_id)
+    }
+
     /// Get lines from a specific command's output by reading the log file.
     ///
     /// An absent `start` reads from [`DEFAULT_START_LINE`], and an absent `end`
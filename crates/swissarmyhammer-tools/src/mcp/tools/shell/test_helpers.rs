@@ -250,7 +250,13 @@ pub(crate) async fn execute_op_with(
     }
     // Default working_directory to /tmp for execute command ops to avoid
     // racing against parallel CWD mutators (see TestCommandBuilder::new).
-    if is_execute_command && !has_explicit_working_dir && args.contains_key("command") {
+    // Session commands keep the session's own directory, which a running
+    // session refuses to have changed.
+    if is_execute_command
+        && !has_explicit_working_dir
+        && args.contains_key("command")
+        && !args.contains_key("session")
+    {
         args.insert("working_directory".to_string(), json!("/tmp"));
     }
     tool.execute(args, &context).await
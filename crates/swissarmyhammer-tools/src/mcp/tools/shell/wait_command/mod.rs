@@ -0,0 +1,264 @@
+//! Wait command operation for the shell tool.
+//!
+//! This module implements the "wait command" operation which waits for a
+//! command `start command` launched, up to a deadline, and answers with its
+//! status and the tail of the output stored so far.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use swissarmyhammer_operations::{Operation, ParamMeta, ParamType};
+
+use super::execute_command::{format_output_tail, COMMAND_ID_KEY, STATUS_KEY};
+use super::infrastructure::value_as_u64_tolerant;
+use super::state::{CommandStatus, ShellState};
+use crate::mcp::tool_registry::BaseToolImpl;
+
+/// Seconds `wait command` waits when the caller names no timeout.
+const DEFAULT_WAIT_SECS: u64 = 30;
+
+/// Longest wait one `wait command` call accepts; a longer `timeout` is cut
+/// to this, and the caller waits again if the command is still running.
+const MAX_WAIT_SECS: u64 = 3600;
+
+/// How often `wait command` looks at the command's record while it waits.
+const WAIT_POLL_MILLIS: u64 = 50;
+
+/// Operation metadata for waiting on a background command
+#[derive(Debug, Default)]
+pub struct WaitCommand;
+
+static WAIT_COMMAND_PARAMS: &[ParamMeta] = &[
+    ParamMeta::new("command_id")
+        .description("Which command to wait for")
+        .param_type(ParamType::Integer)
+        .required(),
+    ParamMeta::new("timeout")
+        .description(
+            "Seconds to wait before answering with the command still running (default: 30, at most 3600)",
+        )
+        .param_type(ParamType::Integer),
+];
+
+impl Operation for WaitCommand {
+    fn verb(&self) -> &'static str {
+        "wait"
+    }
+    fn noun(&self) -> &'static str {
+        "command"
+    }
+    fn description(&self) -> &'static str {
+        "Wait for a command to exit, up to a deadline; the command keeps running when the deadline passes"
+    }
+    fn parameters(&self) -> &'static [ParamMeta] {
+        WAIT_COMMAND_PARAMS
+    }
+}
+
+/// Execute the "wait command" operation.
+///
+/// Extracts `command_id` (required) and an optional `timeout` in seconds, then
+/// waits until the command leaves the `running` state or the deadline passes.
+/// Reaching the deadline kills nothing: the response reports `running` and the
+/// caller waits again, reads the output, or kills the command.
+///
+/// # Parameters
+///
+/// - `args`: the MCP argument map (without the "op" key)
+/// - `state`: shared shell state containing the command records
+///
+/// # Returns
+///
+/// A `CallToolResult` with the command's status, exit code, line count,
+/// duration, and output tail, or an `McpError` for an unknown command id.
+pub async fn execute_wait_command(
+    args: &serde_json::Map<String, serde_json::Value>,
+    state: Arc<Mutex<ShellState>>,
+) -> Result<CallToolResult, McpError> {
+    let command_id = args
+        .get("command_id")
+        .and_then(value_as_u64_tolerant)
+        .ok_or_else(|| {
+            McpError::invalid_params("'command_id' parameter is required for wait command", None)
+        })? as usize;
+    let timeout_secs = args
+        .get("timeout")
+        .and_then(value_as_u64_tolerant)
+        .unwrap_or(DEFAULT_WAIT_SECS)
+        .min(MAX_WAIT_SECS);
+    let deadline = Instant::now() + Duration::from_secs(timeout_secs);
+
+    let record = loop {
+        let record = {
+            let guard = state.lock().await;
+            guard.get_command(command_id).cloned().ok_or_else(|| {
+                McpError::invalid_params(format!("unknown command ID {command_id}"), None)
+            })?
+        };
+        let now = Instant::now();
+        if record.status != CommandStatus::Running || now >= deadline {
+            break record;
+        }
+        let pause = Duration::from_millis(WAIT_POLL_MILLIS).min(deadline - now);
+        tokio::time::sleep(pause).await;
+    };
+
+    let exit_code = record
+        .exit_code
+        .map(|code| code.to_string())
+        .unwrap_or_else(|| "none".to_string());
+    let mut response = format!(
+        "{COMMAND_ID_KEY}: {}\n{STATUS_KEY}: {}\nexit_code: {}\nlines: {}\nduration: {}ms",
+        command_id,
+        record.status,
+        exit_code,
+        record.line_count,
+        record.duration().as_millis(),
+    );
+    if let Some(tail) = format_output_tail(&state, command_id, record.line_count).await {
+        response.push_str("\n\n");
+        response.push_str(&tail);
+    }
+    Ok(BaseToolImpl::create_success_response(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::super::test_helpers::{
+        execute_op, execute_op_with, extract_text, parse_status_response, shared_tool,
+    };
+    use crate::mcp::tools::shell::ShellExecuteTool;
+
+    /// Start `command` in `/tmp` on `tool` and return its command id.
+    async fn start(tool: &ShellExecuteTool, command: &str) -> String {
+        let result = execute_op_with(
+            tool,
+            "start command",
+            vec![
+                ("command", json!(command)),
+                ("working_directory", json!("/tmp")),
+            ],
+        )
+        .await
+        .expect("start command should succeed");
+        parse_status_response(&result)["command_id"].clone()
+    }
+
+    #[tokio::test]
+    async fn test_wait_command_missing_command_id_returns_error() {
+        let result = execute_op("wait command", vec![]).await;
+        let err = result.expect_err("wait command without command_id should fail");
+        assert!(err.to_string().contains("command_id"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_wait_command_unknown_id_returns_error() {
+        let result = execute_op("wait command", vec![("command_id", json!(999))]).await;
+        let err = result.expect_err("an unknown command id should fail");
+        assert!(err.to_string().contains("999"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_wait_command_reports_exit_code_and_output() {
+        let tool = shared_tool();
+        let cmd_id = start(&tool, "echo WAITED_FOR; exit 4").await;
+
+        let result = execute_op_with(
+            &tool,
+            "wait command",
+            vec![("command_id", json!(cmd_id)), ("timeout", json!(10))],
+        )
+        .await
+        .unwrap();
+
+        let fields = parse_status_response(&result);
+        assert_eq!(fields.get("status").map(String::as_str), Some("completed"));
+        assert_eq!(fields.get("exit_code").map(String::as_str), Some("4"));
+        assert!(extract_text(&result).contains("WAITED_FOR"));
+    }
+
+    #[tokio::test]
+    async fn test_wait_command_accepts_an_enormous_timeout() {
+        let tool = shared_tool();
+        let cmd_id = start(&tool, "echo DONE").await;
+
+        let result = execute_op_with(
+            &tool,
+            "wait command",
+            vec![("command_id", json!(cmd_id)), ("timeout", json!(u64::MAX))],
+        )
+        .await
+        .unwrap();
+
+        let fields = parse_status_response(&result);
+        assert_eq!(fields.get("status").map(String::as_str), Some("completed"));
+    }
+
+    #[tokio::test]
+    async fn test_wait_command_deadline_leaves_the_command_running() {
+        let tool = shared_tool();
+        let cmd_id = start(&tool, "echo PARTIAL; sleep 10").await;
+
+        let result = execute_op_with(
+            &tool,
+            "wait command",
+            vec![("command_id", json!(cmd_id)), ("timeout", json!(1))],
+        )
+        .await
+        .unwrap();
+
+        let fields = parse_status_response(&result);
+        assert_eq!(fields.get("status").map(String::as_str), Some("running"));
+        assert!(
+            extract_text(&result).contains("PARTIAL"),
+            "the output so far must come back with a running command"
+        );
+
+        execute_op_with(&tool, "kill process", vec![("id", json!(cmd_id))])
+            .await
+            .expect("kill process");
+        let result = execute_op_with(
+            &tool,
+            "wait command",
+            vec![("command_id", json!(cmd_id)), ("timeout", json!(5))],
+        )
+        .await
+        .unwrap();
+        let fields = parse_status_response(&result);
+        assert_eq!(fields.get("status").map(String::as_str), Some("killed"));
+    }
+
+    #[tokio::test]
+    async fn test_wait_command_timeout_kills_and_keeps_partial_output() {
+        let tool = shared_tool();
+        let result = execute_op_with(
+            &tool,
+            "start command",
+            vec![
+                ("command", json!("echo BEFORE_TIMEOUT; sleep 30")),
+                ("working_directory", json!("/tmp")),
+                ("timeout", json!(1)),
+            ],
+        )
+        .await
+        .unwrap();
+        let cmd_id = parse_status_response(&result)["command_id"].clone();
+
+        let result = execute_op_with(
+            &tool,
+            "wait command",
+            vec![("command_id", json!(cmd_id)), ("timeout", json!(10))],
+        )
+        .await
+        .unwrap();
+
+        let fields = parse_status_response(&result);
+        assert_eq!(fields.get("status").map(String::as_str), Some("timed_out"));
+        assert!(extract_text(&result).contains("BEFORE_TIMEOUT"));
+    }
+}